@@ -0,0 +1,96 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use tauri_plugin_debug_tools::{ConsoleLogEntry, DebugToolsConfig};
+
+const BATCH: usize = 100;
+
+// A field value of roughly `bytes` when serialized: an array of small
+// objects, like the state dumps apps attach to console entries.
+fn field_value(bytes: usize) -> serde_json::Value {
+    let item = serde_json::json!({ "id": 12345, "name": "item name", "ok": true });
+    let item_bytes = item.to_string().len() + 1;
+    serde_json::Value::Array(vec![item; (bytes / item_bytes).max(1)])
+}
+
+fn batch(value_bytes: usize) -> Vec<ConsoleLogEntry> {
+    let value = field_value(value_bytes);
+    (0..BATCH)
+        .map(|i| {
+            let mut fields = serde_json::Map::new();
+            fields.insert("request_id".to_string(), format!("req-{}", i).into());
+            fields.insert("state".to_string(), value.clone());
+            ConsoleLogEntry {
+                timestamp: 1_700_000_000_000 + i as i64,
+                level: "info".to_string(),
+                message: format!("render {}", i),
+                args: serde_json::Value::Array(Vec::new()),
+                stack_trace: None,
+                fields: Some(fields),
+            }
+        })
+        .collect()
+}
+
+// What `cap_log_fields` did before the budget: serialize each value in
+// full to learn its size.
+fn cap_by_full_serialization(config: &DebugToolsConfig, logs: &mut [ConsoleLogEntry]) -> usize {
+    let mut substituted = 0;
+    for fields in logs.iter_mut().filter_map(|entry| entry.fields.as_mut()) {
+        for value in fields.values_mut() {
+            let size = serde_json::to_string(value).map_or(0, |json| json.len());
+            if size > config.max_log_field_value_bytes {
+                *value = serde_json::json!({ "truncated": true, "size_bytes": size });
+                substituted += 1;
+            }
+        }
+    }
+    substituted
+}
+
+fn cap_log_fields(c: &mut Criterion) {
+    let config = DebugToolsConfig::default();
+    // Under the default 1 KiB budget, and well over it.
+    for (name, value_bytes) in [("small", 512), ("large", 256 * 1024)] {
+        let logs = batch(value_bytes);
+        let mut group = c.benchmark_group(format!("cap_log_fields/{}", name));
+        group.throughput(Throughput::Elements(BATCH as u64));
+        group.bench_function("full_serialization", |b| {
+            b.iter_batched_ref(
+                || logs.clone(),
+                |logs| black_box(cap_by_full_serialization(&config, logs)),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function("budgeted", |b| {
+            b.iter_batched_ref(
+                || logs.clone(),
+                |logs| black_box(config.cap_log_fields(logs)),
+                BatchSize::LargeInput,
+            )
+        });
+        group.finish();
+    }
+}
+
+// The JSONL lines a batch becomes once its fields are capped.
+fn serialize_capped_batch(c: &mut Criterion) {
+    let config = DebugToolsConfig::default();
+    let mut logs = batch(256 * 1024);
+    config.cap_log_fields(&mut logs);
+    let mut group = c.benchmark_group("serialize_capped_batch");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("jsonl", |b| {
+        let mut line = Vec::new();
+        b.iter(|| {
+            line.clear();
+            for entry in &logs {
+                serde_json::to_writer(&mut line, entry).expect("serialize");
+                line.push(b'\n');
+            }
+            black_box(line.len())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cap_log_fields, serialize_capped_batch);
+criterion_main!(benches);
@@ -10,6 +10,84 @@ const COMMANDS: &[&str] = &[
     "capture_dom_snapshot",
     "capture_full_debug_state",
     "get_log_directory",
+    "list_debug_screenshots",
+    "delete_debug_screenshot",
+    "set_quiet_mode",
+    "get_debug_stats",
+    "list_profiles",
+    "set_active_profile",
+    "get_repository_metrics",
+    "export_flamegraph",
+    "send_debug_command_with_reply",
+    "submit_debug_command_reply",
+    "register_debug_command_handlers",
+    "get_debug_command_history",
+    "capture_security_context",
+    "write_typed_debug_snapshot",
+    "get_error_rate",
+    "set_body_capture",
+    "get_body_capture",
+    "append_network_logs",
+    "report_renderer_info",
+    "get_debug_environment",
+    "benchmark_capture",
+    "query_console_logs",
+    "get_log_histogram",
+    "get_log_field_counts",
+    "capture_resources",
+    "search_dom_snapshots",
+    "export_filtered_logs",
+    "record_visibility_change",
+    "run_self_test",
+    "capture_dom_delta",
+    "reconstruct_from_baseline",
+    "list_snapshots_paged",
+    "import_console_logs",
+    "capture_route_state",
+    "get_capture_size_report",
+    "check_clock_skew",
+    "get_storage_status",
+    "capture_text_summary",
+    "merge_debug_bundles",
+    "load_debug_snapshot",
+    "capture_form_state",
+    "annotate_snapshot",
+    "start_window_recording",
+    "stop_window_recording",
+    "capture_storage_quota",
+    "get_log_stats",
+    "get_deprecation_report",
+    "diff_snapshot_screenshots",
+    "set_legal_hold",
+    "shutdown_debug_tools",
+    "compare_debug_sessions",
+    "begin_dom_capture",
+    "append_dom_chunk",
+    "finish_dom_capture",
+    "get_artifact_schema_manifest",
+    "flush_debug_logs",
+    "claim_existing_files",
+    "submit_final_beacon",
+    "get_debug_config",
+    "set_privacy_preset",
+    "repair_console_log",
+    "annotate_log_entry",
+    "list_log_annotations",
+    "get_plugin_init_report",
+    "capture_debug_screenshot_region",
+    "capture_element_screenshot",
+    "tail_plugin_log",
+    "mark_debug_moment",
+    "report_user_agent",
+    "save_dom_snapshot",
+    "list_debug_snapshots",
+    "capture_screenshot",
+    "clear_strict_violations",
+    "read_debug_snapshot",
+    "set_debug_config",
+    "list_debug_sessions",
+    "export_encrypted_bundle",
+    "import_encrypted_bundle",
 ];
 
 fn main() {
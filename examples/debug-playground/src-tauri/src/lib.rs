@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_debug_tools::DebugToolsState;
+
+const EXERCISE_FLAG: &str = "--exercise-all";
+// Upper bound for the whole `--exercise-all` run, so a hung command fails CI
+// instead of blocking it.
+const EXERCISE_TIMEOUT: Duration = Duration::from_secs(180);
+
+const FIXTURE_SMALL_PNG: &[u8] = include_bytes!("../../../src-tauri/icons/32x32.png");
+const FIXTURE_LARGE_PNG: &[u8] = include_bytes!("../../../src-tauri/icons/128x128.png");
+
+struct ExerciseMode(bool);
+
+#[derive(Debug, Serialize)]
+struct Fixtures {
+    small_png: PathBuf,
+    large_png: PathBuf,
+    import_log: PathBuf,
+}
+
+// One entry per step of the frontend's command table.
+#[derive(Debug, Deserialize)]
+struct StepOutcome {
+    name: String,
+    command: String,
+    ok: bool,
+    #[serde(default)]
+    optional: bool,
+    #[serde(default)]
+    error: Option<String>,
+    // Paths the step's result claims to have written.
+    #[serde(default)]
+    files: Vec<PathBuf>,
+}
+
+#[tauri::command]
+fn exercise_mode(mode: State<'_, ExerciseMode>) -> bool {
+    mode.0
+}
+
+// Files the steps need as input: two PNGs of different sizes (screenshot
+// copy and diff) and a JSONL export to import. The import has to sit inside
+// the plugin's log directory, so it goes in its `imports/` folder.
+#[tauri::command]
+fn playground_fixtures(app: AppHandle) -> Result<Fixtures, String> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join("playground-fixtures");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let import_dir = app.state::<DebugToolsState>().config.bundle_import_dir();
+    std::fs::create_dir_all(&import_dir).map_err(|e| e.to_string())?;
+
+    let fixtures = Fixtures {
+        small_png: dir.join("small.png"),
+        large_png: dir.join("large.png"),
+        import_log: import_dir.join("playground_import.jsonl"),
+    };
+    std::fs::write(&fixtures.small_png, FIXTURE_SMALL_PNG).map_err(|e| e.to_string())?;
+    std::fs::write(&fixtures.large_png, FIXTURE_LARGE_PNG).map_err(|e| e.to_string())?;
+    std::fs::write(
+        &fixtures.import_log,
+        concat!(
+            r#"{"timestamp":1700000000000,"level":"info","message":"imported-1","args":[],"stack_trace":null}"#,
+            "\n",
+            r#"{"timestamp":1700000000500,"level":"error","message":"imported-2","args":[],"stack_trace":null}"#,
+            "\n",
+        ),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(fixtures)
+}
+
+// Prints the run and exits: 0 when every required step passed and every
+// file it reported exists, 1 otherwise.
+#[tauri::command]
+fn finish_exercise(app: AppHandle, outcomes: Vec<StepOutcome>) {
+    let mut failed = 0;
+    for outcome in &outcomes {
+        let missing: Vec<&Path> = outcome
+            .files
+            .iter()
+            .map(PathBuf::as_path)
+            .filter(|path| !path.exists())
+            .collect();
+        let passed = outcome.ok && missing.is_empty();
+
+        let status = match (passed, outcome.optional) {
+            (true, _) => "ok  ",
+            (false, true) => "skip",
+            (false, false) => "FAIL",
+        };
+        println!("{} {} ({})", status, outcome.name, outcome.command);
+        if let Some(error) = &outcome.error {
+            println!("       {}", error);
+        }
+        for path in &missing {
+            println!("       missing file: {}", path.display());
+        }
+
+        if !passed && !outcome.optional {
+            failed += 1;
+        }
+    }
+
+    println!("{} steps, {} failed", outcomes.len(), failed);
+    app.exit(if failed == 0 { 0 } else { 1 });
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let exercise = std::env::args().any(|arg| arg == EXERCISE_FLAG);
+    if exercise {
+        std::thread::spawn(|| {
+            std::thread::sleep(EXERCISE_TIMEOUT);
+            eprintln!(
+                "Exercise run did not finish within {}s",
+                EXERCISE_TIMEOUT.as_secs()
+            );
+            std::process::exit(2);
+        });
+    }
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_debug_tools::init())
+        .manage(ExerciseMode(exercise))
+        .invoke_handler(tauri::generate_handler![
+            exercise_mode,
+            playground_fixtures,
+            finish_exercise
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
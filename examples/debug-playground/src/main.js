@@ -0,0 +1,116 @@
+import { steps } from "./steps.js";
+
+const { invoke } = window.__TAURI__.core;
+const { listen } = window.__TAURI__.event;
+
+const ctx = {};
+let outputEl;
+
+const label = (step) => step.name ?? step.command;
+
+function show(step, value) {
+  outputEl.textContent = `${label(step)}\n${JSON.stringify(value, null, 2)}`;
+}
+
+// Runs one step; never throws, so a run always reaches its summary.
+async function runStep(step) {
+  const outcome = {
+    name: label(step),
+    command: step.command,
+    ok: false,
+    optional: Boolean(step.optional),
+    error: null,
+    files: [],
+  };
+  try {
+    const result = await invoke(
+      `plugin:debug-tools|${step.command}`,
+      step.args ? step.args(ctx) : {},
+    );
+    show(step, result);
+    const problem = step.check?.(result, ctx);
+    outcome.ok = !problem;
+    outcome.error = problem ?? null;
+    outcome.files = (step.files?.(result) ?? []).filter(Boolean);
+  } catch (error) {
+    show(step, { error: String(error) });
+    outcome.error = String(error);
+  }
+  return outcome;
+}
+
+async function runAll(buttons) {
+  const outcomes = [];
+  for (const [index, step] of steps.entries()) {
+    const outcome = await runStep(step);
+    outcomes.push(outcome);
+    markButton(buttons[index], outcome);
+  }
+  const failed = outcomes.filter((o) => !o.ok && !o.optional).length;
+  document.querySelector("#summary").textContent =
+    `${outcomes.length} steps, ${failed} failed`;
+  return outcomes;
+}
+
+function markButton(button, outcome) {
+  const item = button.parentElement;
+  item.classList.remove("passed", "failed", "skipped");
+  if (outcome.ok) item.classList.add("passed");
+  else item.classList.add(outcome.optional ? "skipped" : "failed");
+  button.title = outcome.error ?? "";
+}
+
+// The bounding rect of `selector`, as `capture_element_screenshot` asks for.
+function elementRect({ selector }) {
+  const rect = document.querySelector(selector)?.getBoundingClientRect();
+  return rect
+    ? { x: rect.left, y: rect.top, width: rect.width, height: rect.height }
+    : null;
+}
+
+// Answers `echo` for the `send_debug_command_with_reply` step and element
+// rect requests for the `capture_element_screenshot` step.
+async function listenForDebugCommands() {
+  await listen("debug-command-request", async (event) => {
+    const { request_id: requestId, command, payload } = event.payload;
+    const reply =
+      command === "echo"
+        ? { ok: true, value: payload }
+        : command === "debug-tools:element-rect"
+          ? { ok: true, value: elementRect(payload) }
+          : { ok: false, error: { message: `Unknown command ${command}` } };
+    await invoke("plugin:debug-tools|submit_debug_command_reply", {
+      requestId,
+      reply,
+    });
+  });
+}
+
+window.addEventListener("DOMContentLoaded", async () => {
+  outputEl = document.querySelector("#output");
+  const list = document.querySelector("#steps");
+  const buttons = steps.map((step) => {
+    const item = document.createElement("li");
+    const button = document.createElement("button");
+    button.type = "button";
+    button.textContent = label(step);
+    button.addEventListener("click", async () => {
+      markButton(button, await runStep(step));
+    });
+    item.append(button);
+    list.append(item);
+    return button;
+  });
+
+  await listenForDebugCommands();
+  ctx.fixtures = await invoke("playground_fixtures");
+
+  document
+    .querySelector("#run-all")
+    .addEventListener("click", () => runAll(buttons));
+
+  if (await invoke("exercise_mode")) {
+    const outcomes = await runAll(buttons);
+    await invoke("finish_exercise", { outcomes });
+  }
+});
@@ -0,0 +1,745 @@
+// Every plugin command, in an order where each step can use what earlier
+// steps produced. This table is the frontend bridge contract: argument
+// names are the camelCase keys `invoke` expects, and `check` states what a
+// caller may rely on in the result.
+//
+// Step fields:
+// - `command`: plugin command name, invoked as `plugin:debug-tools|<command>`
+// - `args(ctx)`: arguments, built from the shared context
+// - `check(result, ctx)`: returns an error message when the result is wrong,
+//   and may store values in `ctx` for later steps
+// - `files(result)`: paths the result claims were written; the backend
+//   checks they exist
+// - `optional`: platform-dependent; failures are reported but do not fail
+//   the run
+
+const now = () => Date.now();
+
+const logEntry = (message, level = "info") => ({
+  timestamp: now(),
+  level,
+  message,
+  args: [message],
+  stack_trace: null,
+  fields: { step: "playground" },
+});
+
+const domPayload = (marker) => ({
+  html: `<html><body><main id="playground">${marker}</main></body></html>`,
+  url: window.location.href,
+  title: document.title,
+  viewport_width: window.innerWidth,
+  viewport_height: window.innerHeight,
+});
+
+// One source per page load, so a second run is not taken for redelivery.
+const beaconSource = `playground-${now()}`;
+
+const playgroundBeacon = () => ({
+  source: beaconSource,
+  reason: "playground",
+  entries: [
+    { ...logEntry("final beacon entry"), seq: 0 },
+    { ...logEntry("final beacon error", "error"), seq: 1 },
+  ],
+  state: {
+    url: window.location.href,
+    title: document.title,
+    visibility_state: document.visibilityState,
+    pending_entries: 2,
+    in_flight_entries: 0,
+    dropped_entries: 0,
+    truncated_entries: 0,
+  },
+});
+
+const captureId = (path) =>
+  path
+    .split(/[\\/]/)
+    .pop()
+    .replace(/\.json$/, "");
+
+const expect = (condition, message) => (condition ? undefined : message);
+
+export const steps = [
+  // Environment
+  {
+    command: "get_log_directory",
+    check: (dirs, ctx) => {
+      ctx.dirs = dirs;
+      return expect(dirs.base_dir, "base_dir is empty");
+    },
+    files: (dirs) => [dirs.base_dir],
+  },
+  {
+    command: "tail_plugin_log",
+    args: () => ({ lines: 20 }),
+    check: (lines) => expect(lines.length <= 20, "more lines than asked for"),
+  },
+  { command: "get_debug_environment" },
+  { command: "get_storage_status" },
+  {
+    command: "run_self_test",
+    args: () => ({ frontendNowMs: now() }),
+    check: (checks) => expect(Array.isArray(checks), "expected a check list"),
+  },
+  {
+    command: "check_clock_skew",
+    args: () => ({ frontendTimestamp: now() }),
+  },
+  {
+    command: "capture_webview_state",
+    check: (state) =>
+      expect(state.title === "Debug Playground", `title was ${state.title}`),
+  },
+  {
+    command: "report_user_agent",
+    args: () => ({ userAgent: navigator.userAgent }),
+  },
+
+  // Console logs
+  {
+    command: "reset_debug_logs",
+    files: (path) => [path],
+  },
+  {
+    command: "append_debug_logs",
+    args: () => ({
+      logs: [logEntry("playground-info"), logEntry("playground-error", "error")],
+      dropped: 0,
+    }),
+    // A bare path string when the backend runs with `compat_mode`.
+    check: (result) =>
+      expect(typeof result === "object", "expected an AppendLogsResult"),
+    files: (result) => [result.path],
+  },
+  { command: "get_log_stats" },
+  { command: "get_console_logs" },
+  {
+    command: "query_console_logs",
+    args: () => ({ query: { text: "playground" } }),
+    check: (entries) =>
+      expect(entries.length >= 2, `expected 2 entries, got ${entries.length}`),
+  },
+  {
+    command: "query_console_logs",
+    name: "find an entry to annotate",
+    args: () => ({ query: { text: "playground", limit: 1 } }),
+    check: (entries, ctx) => {
+      ctx.annotatedSeq = entries[0]?.fields?.log_seq;
+      return expect(ctx.annotatedSeq > 0, "entry has no log_seq");
+    },
+  },
+  {
+    command: "annotate_log_entry",
+    args: (ctx) => ({
+      seq: ctx.annotatedSeq,
+      note: "Annotated by the playground",
+      tag: "relevant",
+    }),
+    check: (annotation, ctx) =>
+      expect(annotation.seq === ctx.annotatedSeq, "wrong entry annotated"),
+  },
+  {
+    command: "list_log_annotations",
+    check: (annotations, ctx) =>
+      expect(
+        annotations.some((annotation) => annotation.seq === ctx.annotatedSeq),
+        "annotation not listed",
+      ),
+  },
+  {
+    command: "mark_debug_moment",
+    args: () => ({ label: "playground start" }),
+    check: (moment, ctx) => {
+      ctx.startMoment = moment.id;
+      return expect(moment.id, "moment has no id");
+    },
+  },
+  {
+    command: "mark_debug_moment",
+    args: () => ({ label: "playground end" }),
+    check: (moment, ctx) => {
+      ctx.endMoment = moment.id;
+      return expect(moment.id !== ctx.startMoment, "moment id reused");
+    },
+  },
+  {
+    command: "query_console_logs",
+    name: "slice between two moments",
+    args: (ctx) => ({
+      query: { from_moment: ctx.startMoment, to_moment: ctx.endMoment },
+    }),
+    check: (entries) =>
+      expect(
+        entries.filter((entry) => entry.fields?.debug_moment).length === 2,
+        "expected both moment markers",
+      ),
+  },
+  {
+    command: "get_log_histogram",
+    args: () => ({ bucketMs: 1000 }),
+  },
+  {
+    command: "get_log_field_counts",
+    args: () => ({ field: "step" }),
+  },
+  {
+    command: "export_filtered_logs",
+    args: () => ({
+      request: {
+        output_name: "playground",
+        format: "jsonl",
+        resolve_stack_traces: true,
+      },
+    }),
+    files: (result) => [result.path],
+  },
+  {
+    command: "import_console_logs",
+    args: (ctx) => ({ path: ctx.fixtures.import_log, sourceLabel: "fixture" }),
+    check: (result) =>
+      expect(result.imported === 2, `imported ${result.imported} entries`),
+    files: (result) => [result.path],
+  },
+  { command: "get_error_rate" },
+  {
+    command: "set_quiet_mode",
+    args: () => ({ level: "warn", durationSecs: 1 }),
+  },
+
+  // Network
+  {
+    command: "set_body_capture",
+    args: () => ({ rule: { url_pattern: "example.com" } }),
+  },
+  {
+    command: "get_body_capture",
+    check: (rule) => expect(rule?.url_pattern === "example.com", "rule not set"),
+  },
+  {
+    command: "append_network_logs",
+    args: () => ({
+      entries: [
+        {
+          timestamp: now(),
+          method: "GET",
+          url: "https://example.com/api",
+          status: 200,
+          duration_ms: 12.5,
+          error: null,
+        },
+      ],
+    }),
+    files: (path) => [path],
+  },
+
+  // Debug commands; `echo` and the element rect request are answered by
+  // the handler in main.js, which goes through `submit_debug_command_reply`.
+  {
+    command: "send_debug_command",
+    args: () => ({ command: "ping", payload: { ok: true } }),
+  },
+  {
+    command: "register_debug_command_handlers",
+    args: () => ({ commands: ["echo", "debug-tools:element-rect"] }),
+  },
+  {
+    command: "send_debug_command_with_reply",
+    args: () => ({ command: "echo", payload: { value: 42 }, timeoutMs: 5000 }),
+    check: (outcome) =>
+      expect(
+        outcome.status === "ok" && outcome.value?.value === 42,
+        `outcome was ${JSON.stringify(outcome)}`,
+      ),
+  },
+  {
+    command: "get_debug_command_history",
+    args: () => ({ limit: 10 }),
+    check: (history) =>
+      expect(history.recent.length > 0, "history is empty"),
+  },
+
+  // Frontend context
+  {
+    command: "capture_security_context",
+    args: () => ({
+      context: {
+        captured_at: now(),
+        url: window.location.href,
+        content_security_policy: [],
+        referrer_policy: null,
+        is_secure_context: window.isSecureContext,
+        violations: [],
+      },
+    }),
+  },
+  {
+    command: "capture_route_state",
+    args: () => ({ routeState: { path: "/playground" } }),
+  },
+  {
+    command: "capture_form_state",
+    args: () => ({
+      formState: {
+        captured_at: now(),
+        url: window.location.href,
+        fields: [{ name: "password", field_type: "password", value: "hunter2" }],
+      },
+    }),
+    check: (form) =>
+      expect(form.fields[0]?.value !== "hunter2", "password was not masked"),
+  },
+  {
+    command: "capture_storage_quota",
+    args: () => ({ quota: { captured_at: now(), supported: false } }),
+  },
+  {
+    command: "capture_resources",
+    args: () => ({
+      resources: [
+        {
+          url: window.location.href,
+          initiator_type: "navigation",
+          start_time_ms: 0,
+          duration_ms: 1,
+        },
+      ],
+    }),
+  },
+  {
+    command: "report_renderer_info",
+    args: () => ({
+      info: {
+        reported_at: now(),
+        webgl_version: null,
+        vendor: null,
+        renderer: null,
+        unmasked_vendor: null,
+        unmasked_renderer: null,
+      },
+    }),
+  },
+  {
+    command: "record_visibility_change",
+    args: () => ({ state: "visible" }),
+  },
+
+  // DOM
+  {
+    command: "capture_dom_snapshot",
+    args: () => ({ payload: domPayload("snapshot") }),
+    check: (result, ctx) => {
+      ctx.domPath = result.path;
+    },
+    files: (result) => [result.path],
+  },
+  {
+    command: "save_dom_snapshot",
+    args: () => {
+      const { html, url, title, viewport_width, viewport_height } =
+        domPayload("saved");
+      return {
+        html,
+        url,
+        title,
+        viewportWidth: viewport_width,
+        viewportHeight: viewport_height,
+      };
+    },
+    files: (result) => [result.path],
+  },
+  {
+    command: "capture_dom_delta",
+    args: () => ({ payload: domPayload("delta") }),
+    check: (result, ctx) => {
+      ctx.deltaPath = result.path;
+    },
+    files: (result) => [result.path, result.baseline_path],
+  },
+  {
+    command: "reconstruct_from_baseline",
+    args: (ctx) => ({ deltaPath: ctx.deltaPath }),
+    check: (dom) => expect(dom.html.includes("delta"), "delta not applied"),
+  },
+  {
+    command: "begin_dom_capture",
+    check: (uploadId, ctx) => {
+      ctx.uploadId = uploadId;
+    },
+  },
+  {
+    name: "append second chunk first",
+    command: "append_dom_chunk",
+    args: (ctx) => ({
+      uploadId: ctx.uploadId,
+      seq: 1,
+      bytes: btoa("chunked</main></body></html>"),
+      encoding: "base64",
+    }),
+  },
+  {
+    command: "append_dom_chunk",
+    args: (ctx) => ({
+      uploadId: ctx.uploadId,
+      seq: 0,
+      bytes: '<html><body><main id="playground">',
+    }),
+  },
+  {
+    name: "append duplicate chunk",
+    command: "append_dom_chunk",
+    args: (ctx) => ({
+      uploadId: ctx.uploadId,
+      seq: 0,
+      bytes: '<html><body><main id="playground">',
+    }),
+    check: (ack) => expect(ack.duplicate, "resent chunk not detected"),
+  },
+  {
+    command: "finish_dom_capture",
+    args: (ctx) => ({
+      uploadId: ctx.uploadId,
+      metadata: {
+        url: window.location.href,
+        title: document.title,
+        viewport_width: window.innerWidth,
+        viewport_height: window.innerHeight,
+        total_chunks: 2,
+      },
+    }),
+    files: (result) => [result.path],
+  },
+  {
+    command: "search_dom_snapshots",
+    args: () => ({ query: { query: "playground" } }),
+  },
+
+  // Screenshots and snapshots
+  {
+    command: "copy_screenshot_to_debug_dir",
+    name: "copy small screenshot",
+    args: (ctx) => ({ sourcePath: ctx.fixtures.small_png }),
+    check: (result, ctx) => {
+      ctx.screenshots = [result.destination_path];
+    },
+    files: (result) => [result.destination_path],
+  },
+  {
+    command: "copy_screenshot_to_debug_dir",
+    name: "copy large screenshot",
+    args: (ctx) => ({ sourcePath: ctx.fixtures.large_png }),
+    check: (result, ctx) => {
+      ctx.screenshots.push(result.destination_path);
+    },
+    files: (result) => [result.destination_path],
+  },
+  {
+    command: "list_debug_screenshots",
+    args: () => ({ limit: 10 }),
+    check: (screenshots) =>
+      expect(screenshots.length >= 2, "copied screenshots are not listed"),
+  },
+  {
+    command: "write_debug_snapshot",
+    args: () => ({ payload: { source: "playground", ok: true } }),
+    files: (path) => [path],
+  },
+  {
+    command: "write_typed_debug_snapshot",
+    args: () => ({ payload: { label: "playground", note: "typed" } }),
+    check: (snapshot) =>
+      expect(snapshot.label === "playground", "label was not kept"),
+  },
+  {
+    command: "capture_full_debug_state",
+    name: "full state with small screenshot",
+    args: (ctx) => ({
+      consoleLogs: [logEntry("playground-full-1")],
+      screenshotPath: ctx.fixtures.small_png,
+      domSnapshotPath: ctx.domPath,
+    }),
+  },
+  {
+    command: "capture_full_debug_state",
+    name: "full state with large screenshot",
+    args: (ctx) => ({
+      consoleLogs: [logEntry("playground-full-2")],
+      screenshotPath: ctx.fixtures.large_png,
+      domSnapshotPath: ctx.domPath,
+    }),
+  },
+  {
+    command: "list_snapshots_paged",
+    args: () => ({ offset: 0, limit: 10, sort: "desc" }),
+    // Newest first: the two full-state snapshots lead.
+    check: (page, ctx) => {
+      ctx.snapshots = page.entries.map((entry) => entry.path);
+      return expect(page.entries.length >= 3, `${page.total} snapshots`);
+    },
+    files: (page) => page.entries.map((entry) => entry.path),
+  },
+  {
+    command: "list_debug_snapshots",
+    args: () => ({ limit: 10 }),
+    check: (summaries, ctx) => {
+      ctx.snapshotTimestamp = summaries.find(
+        (summary) => !summary.legacy,
+      )?.timestamp;
+      return expect(ctx.snapshotTimestamp, "no typed snapshot listed");
+    },
+    files: (summaries) => summaries.map((summary) => summary.path),
+  },
+  {
+    command: "read_debug_snapshot",
+    args: (ctx) => ({ timestamp: ctx.snapshotTimestamp }),
+    check: (snapshot) =>
+      expect(snapshot.webview_state?.url, "snapshot has no webview state"),
+  },
+  {
+    command: "get_capture_size_report",
+    args: (ctx) => ({ captureId: captureId(ctx.snapshots[0]) }),
+  },
+  {
+    command: "load_debug_snapshot",
+    args: (ctx) => ({ captureId: captureId(ctx.snapshots[0]) }),
+    check: (snapshot) =>
+      expect(snapshot.console_logs.length > 0, "snapshot has no logs"),
+  },
+  {
+    command: "annotate_snapshot",
+    args: (ctx) => ({ path: ctx.snapshots[0], key: "playground", value: true }),
+    check: (entry) =>
+      expect(entry.annotations?.playground === true, "annotation missing"),
+  },
+  {
+    command: "diff_snapshot_screenshots",
+    args: (ctx) => ({ pathA: ctx.snapshots[1], pathB: ctx.snapshots[0] }),
+    check: (diff) => expect(diff.size_mismatch, "sizes should differ"),
+    files: (diff) => [diff.diff_path],
+  },
+  {
+    command: "set_legal_hold",
+    name: "hold newest snapshot",
+    args: (ctx) => ({ paths: [ctx.snapshots[0]], held: true }),
+    check: (holds) => expect(holds.paths.length > 0, "hold not recorded"),
+  },
+  {
+    command: "get_debug_environment",
+    name: "read session id",
+    check: (environment, ctx) => {
+      ctx.sessionId = environment.session_id;
+    },
+  },
+  // Comparing the session with itself still yields every section.
+  {
+    command: "compare_debug_sessions",
+    args: (ctx) => ({
+      sessionA: ctx.sessionId,
+      sessionB: ctx.sessionId,
+      writeMarkdown: true,
+    }),
+    check: (report) =>
+      expect(
+        report.error_fingerprints.status === "available",
+        "error fingerprints unavailable",
+      ),
+    files: (report) => [report.markdown_path],
+  },
+  {
+    command: "list_debug_sessions",
+    check: (sessions, ctx) =>
+      expect(
+        sessions.some((session) => session.session_id === ctx.sessionId),
+        "current session not listed",
+      ),
+  },
+  {
+    command: "get_artifact_schema_manifest",
+    check: (manifest) =>
+      expect(
+        manifest.artifacts.some((a) => a.name === "console_log") &&
+          manifest.schemas.ConsoleLogEntry,
+        "console log schema missing",
+      ),
+  },
+  {
+    command: "capture_text_summary",
+    args: () => ({ errorCount: 5 }),
+    check: (summary) => expect(summary.length > 0, "summary is empty"),
+  },
+  {
+    command: "benchmark_capture",
+    args: () => ({ request: { dom_html: "<p>benchmark</p>" } }),
+  },
+
+  // Profiles and diagnostics
+  {
+    command: "list_profiles",
+    check: (profiles, ctx) => {
+      ctx.profile = profiles.find((profile) => profile.active) ?? profiles[0];
+      return expect(ctx.profile, "no profiles");
+    },
+  },
+  {
+    command: "set_active_profile",
+    args: (ctx) => ({ name: ctx.profile.name }),
+  },
+  {
+    command: "set_debug_config",
+    args: (ctx) => ({ update: { active_profile: ctx.profile.name } }),
+    check: (config, ctx) =>
+      expect(
+        config.active_profile.name === ctx.profile.name,
+        "profile not applied",
+      ),
+  },
+  { command: "get_debug_stats" },
+  {
+    command: "clear_strict_violations",
+    check: (cleared) =>
+      expect(Number.isInteger(cleared), "expected a violation count"),
+  },
+  { command: "get_repository_metrics", optional: true },
+  {
+    command: "get_deprecation_report",
+    check: (report) =>
+      expect(
+        report.surfaces.some((use) => use.surface === "write_debug_snapshot"),
+        "write_debug_snapshot was not reported",
+      ),
+  },
+
+  // Platform-dependent captures
+  {
+    command: "start_window_recording",
+    args: () => ({ fps: 2, maxFrames: 4 }),
+    optional: true,
+  },
+  {
+    command: "stop_window_recording",
+    args: () => ({ assemble: true }),
+    optional: true,
+  },
+  {
+    command: "capture_debug_screenshot_region",
+    args: () => ({
+      windowLabel: "main",
+      rect: { x: -20, y: 0, width: 120, height: 80 },
+    }),
+    check: (shot) => expect(shot.region.clamped, "region was not clamped"),
+    files: (shot) => [shot.path],
+    optional: true,
+  },
+  {
+    command: "capture_screenshot",
+    args: () => ({ label: "main" }),
+    files: (path) => [path],
+    optional: true,
+  },
+  {
+    command: "capture_element_screenshot",
+    args: () => ({ windowLabel: "main", selector: "#run-all" }),
+    check: (shot) =>
+      expect(shot.region.selector === "#run-all", "selector not recorded"),
+    files: (shot) => [shot.path],
+    optional: true,
+  },
+  // Needs the `flamegraph` feature.
+  { command: "export_flamegraph", optional: true },
+
+  // Bundles and cleanup
+  {
+    command: "merge_debug_bundles",
+    // Written under the log directory's `merged/`, the only place allowed.
+    args: (ctx) => ({
+      paths: [ctx.dirs.base_dir],
+      resolveStackTraces: true,
+    }),
+    files: (report) => [report.timeline_path, report.fingerprints_path],
+  },
+  {
+    command: "export_encrypted_bundle",
+    args: () => ({ passphrase: "playground" }),
+    check: (bundle, ctx) => {
+      ctx.encryptedBundle = bundle.path;
+      return expect(bundle.files > 0, "bundle is empty");
+    },
+    files: (bundle) => [bundle.path],
+  },
+  {
+    command: "import_encrypted_bundle",
+    args: (ctx) => ({ path: ctx.encryptedBundle, passphrase: "playground" }),
+    check: (bundle) => expect(bundle.entries > 1, "bundle has no artifacts"),
+    files: (bundle) => [bundle.path],
+  },
+  {
+    command: "set_legal_hold",
+    name: "release newest snapshot",
+    args: (ctx) => ({ paths: [ctx.snapshots[0]], held: false }),
+  },
+  {
+    command: "delete_debug_screenshot",
+    args: (ctx) => ({ path: ctx.screenshots[1] }),
+  },
+  {
+    command: "submit_final_beacon",
+    args: () => ({ beacon: playgroundBeacon() }),
+    check: (record) =>
+      expect(record.written_entries === 2, "beacon entries were not written"),
+  },
+  {
+    command: "submit_final_beacon",
+    name: "resubmit the same entries",
+    args: () => ({ beacon: playgroundBeacon() }),
+    check: (record) =>
+      expect(record.duplicate_entries === 2, "redelivery was not dropped"),
+  },
+  {
+    command: "set_privacy_preset",
+    args: () => ({ preset: "strict" }),
+    check: (status) =>
+      expect(!status.effective.capture_dom, "strict preset kept DOM capture"),
+  },
+  {
+    command: "get_debug_config",
+    check: (config) =>
+      expect(config.privacy?.preset === "strict", "preset not reported"),
+  },
+  {
+    command: "set_privacy_preset",
+    name: "return to the individual flags",
+    args: () => ({ preset: null }),
+  },
+  {
+    command: "repair_console_log",
+    args: (ctx) => ({ path: ctx.dirs.frontend_log }),
+    check: (report) =>
+      expect(report.kept_entries > 0, "repair kept no console entries"),
+  },
+  {
+    command: "get_plugin_init_report",
+    check: (report) =>
+      expect(
+        report.phases.some((phase) => phase.name === "tracing"),
+        "init report has no tracing phase",
+      ),
+  },
+  { command: "flush_debug_logs" },
+  {
+    command: "claim_existing_files",
+    name: "reject a path outside the log directory",
+    args: (ctx) => ({ paths: [ctx.fixtures.small_png] }),
+    check: (result) => {
+      expect(result.rejected_paths.length === 1, "fixture was not rejected");
+    },
+  },
+  { command: "claim_existing_files" },
+  { command: "clear_debug_log_files_command" },
+  // Must stay last: backend tracing stops here.
+  {
+    command: "shutdown_debug_tools",
+    args: () => ({ exportLogs: true }),
+    files: (summary) => (summary.export ? [summary.export.path] : []),
+  },
+];
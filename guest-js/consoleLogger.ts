@@ -2,7 +2,15 @@
  * Collect error logs without wrapping console methods.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { AdaptiveFlushPolicy, estimateEntryBytes } from "./flushPolicy";
+
+/** Emitted by `flush_debug_logs` to make every webview flush its queue. */
+export const FLUSH_LOGS_EVENT = "debug-tools://flush-logs";
+
+// Browsers refuse beacons above 64 KiB; this leaves room for the envelope.
+const MAX_FINAL_BEACON_BYTES = 60_000;
 
 export interface ConsoleLogEntry {
   timestamp: number;
@@ -10,6 +18,70 @@ export interface ConsoleLogEntry {
   message: string;
   args: unknown[];
   stack_trace?: string;
+  /** App-defined structured metadata, e.g. `{ userId, requestId }`. */
+  fields?: Record<string, unknown>;
+  /** Position in this collector's stream, for dropping redelivered entries. */
+  seq?: number;
+}
+
+export interface DeduplicatedLog {
+  message: string;
+  /** Occurrences in the batch, including the one that was kept. */
+  count: number;
+}
+
+export interface AppendLogsResult {
+  path: string;
+  deduplicated: DeduplicatedLog[];
+  /** Oldest entries of the batch dropped for exceeding `max_queued_entries`. */
+  dropped: number;
+  /** Entries dropped because this collector had already delivered them. */
+  duplicates?: number;
+}
+
+/** Collector state reported in a final beacon. */
+export interface FinalBeaconState {
+  url: string;
+  title: string;
+  visibility_state?: string;
+  pending_entries: number;
+  in_flight_entries: number;
+  dropped_entries: number;
+  /** Oldest entries left out to keep the beacon under the browser limit. */
+  truncated_entries: number;
+}
+
+/** The pathway an entry reached the backend by. */
+export type ConsoleSource =
+  | "bridge"
+  | "collector"
+  | "host"
+  | "import"
+  | "marker";
+
+export interface FinalBeacon {
+  /** Identifies the sending collector; one per page load. */
+  source: string;
+  ingest_source?: ConsoleSource;
+  reason?: string;
+  entries: ConsoleLogEntry[];
+  state: FinalBeaconState;
+}
+
+export interface LogPipelineStats {
+  flush_interval_ms: number;
+  min_flush_interval_ms: number;
+  max_flush_interval_ms: number;
+  flush_byte_threshold: number;
+  flush_burst_entries_per_sec: number;
+  flush_batch_size: number;
+  max_queued_entries: number;
+  /** Entries lost this session before they reached the log file. */
+  dropped_entries: number;
+  /** Sources whose entries are kept when pathways overlap. */
+  console_sources: ConsoleSource[];
+  /** Entries dropped because another source already delivered them. */
+  cross_source_duplicates: Partial<Record<ConsoleSource, number>>;
 }
 
 class ConsoleLogCollector {
@@ -17,10 +89,20 @@ class ConsoleLogCollector {
   private readonly maxLogs = 1000; // Max size for the ring buffer.
   private pendingLogs: ConsoleLogEntry[] = [];
   private flushTimer: number | null = null;
-  private readonly flushIntervalMs = 1000;
-  private readonly maxPendingLogs = 200;
+  private pendingBytes = 0;
+  // Defaults until the backend's `flush_*` settings arrive.
+  private readonly flushPolicy = new AdaptiveFlushPolicy();
+  private flushBatchSize = 200;
+  private maxPendingLogs = 1000;
+  private droppedSinceFlush = 0;
+  private readonly source = createSourceId();
+  private nextSeq = 0;
+  // Batches handed to `invoke` that have not settled yet, by batch id.
+  private inFlight = new Map<number, ConsoleLogEntry[]>();
+  private nextBatchId = 0;
   private tauriReady = false;
   private logsReset = false;
+  private dedupHandlers = new Set<(collapsed: DeduplicatedLog[]) => void>();
   private readonly originalConsole: {
     log: typeof console.log;
     warn: typeof console.warn;
@@ -41,6 +123,7 @@ class ConsoleLogCollector {
 
     this.setupTauriReadyListener();
     this.setupErrorHandlers();
+    this.setupFinalBeacon();
   }
 
   private isDev(): boolean {
@@ -52,6 +135,13 @@ class ConsoleLogCollector {
 
   private setupErrorHandlers(): void {
     if (typeof window === "undefined") return;
+    // The injected console bridge already records uncaught errors.
+    if (
+      (window as Window & { __DEBUG_TOOLS_CONSOLE_BRIDGE__?: boolean })
+        .__DEBUG_TOOLS_CONSOLE_BRIDGE__
+    ) {
+      return;
+    }
 
     window.addEventListener("error", (event) => {
       const error = event.error as Error | undefined;
@@ -80,10 +170,20 @@ class ConsoleLogCollector {
     });
   }
 
+  private setupFinalBeacon(): void {
+    if (typeof window === "undefined") return;
+    // `pagehide` also fires when the page enters the back/forward cache;
+    // entries flushed again after it comes back are dropped by `seq`.
+    window.addEventListener("pagehide", () => {
+      this.sendFinalBeacon("pagehide");
+    });
+  }
+
   private addLog(
     level: ConsoleLogEntry["level"],
     args: unknown[],
     stack_trace?: string,
+    fields?: Record<string, unknown>,
   ): void {
     const entry: ConsoleLogEntry = {
       timestamp: Date.now(),
@@ -91,6 +191,8 @@ class ConsoleLogCollector {
       message: this.formatArgs(args),
       args,
       stack_trace,
+      ...(fields ? { fields } : {}),
+      seq: this.nextSeq++,
     };
 
     this.logs.push(entry);
@@ -104,19 +206,35 @@ class ConsoleLogCollector {
 
   private enqueuePending(entry: ConsoleLogEntry): void {
     this.pendingLogs.push(entry);
+    this.pendingBytes += estimateEntryBytes(entry);
     if (this.pendingLogs.length > this.maxPendingLogs) {
-      this.pendingLogs.shift();
+      const oldest = this.pendingLogs.shift();
+      if (oldest) this.pendingBytes -= estimateEntryBytes(oldest);
+      this.droppedSinceFlush += 1;
+    }
+    if (!this.tauriReady) {
+      this.scheduleFlush();
+    } else if (this.pendingLogs.length >= this.flushBatchSize) {
+      this.scheduleFlush(0);
+    } else {
+      this.scheduleFlush(
+        this.flushPolicy.delayFor(entry.level, this.pendingBytes),
+      );
     }
-    this.scheduleFlush();
   }
 
-  private scheduleFlush(): void {
+  private scheduleFlush(
+    delayMs = this.flushPolicy.currentIntervalMs,
+  ): void {
     if (typeof window === "undefined") return;
-    if (this.flushTimer !== null) return;
+    if (this.flushTimer !== null) {
+      if (delayMs > 0) return;
+      window.clearTimeout(this.flushTimer);
+    }
     this.flushTimer = window.setTimeout(() => {
       this.flushTimer = null;
       void this.flushPending();
-    }, this.flushIntervalMs);
+    }, delayMs);
   }
 
   private async flushPending(): Promise<void> {
@@ -125,11 +243,53 @@ class ConsoleLogCollector {
       return;
     }
     if (this.pendingLogs.length === 0) return;
-    const batch = this.pendingLogs.splice(0, this.pendingLogs.length);
+    const batch = this.pendingLogs.splice(0, this.flushBatchSize);
+    for (const entry of batch) this.pendingBytes -= estimateEntryBytes(entry);
+    const dropped = this.droppedSinceFlush;
+    this.droppedSinceFlush = 0;
+    this.flushPolicy.recordFlush(batch.length, Date.now());
+    // Whatever did not fit in this batch goes out right after it.
+    if (this.pendingLogs.length > 0) this.scheduleFlush(0);
+    const batchId = this.nextBatchId++;
+    this.inFlight.set(batchId, batch);
     try {
-      await invoke("plugin:debug-tools|append_debug_logs", { logs: batch });
+      // A bare path string when the backend runs with `compat_mode`.
+      const result = await invoke<AppendLogsResult | string>(
+        "plugin:debug-tools|append_debug_logs",
+        {
+          logs: batch,
+          dropped,
+          flushIntervalMs: this.flushPolicy.currentIntervalMs,
+          source: this.source,
+          ingestSource: "collector",
+        },
+      );
+      if (typeof result !== "string" && result.deduplicated.length > 0) {
+        this.dedupHandlers.forEach((handler) => handler(result.deduplicated));
+      }
     } catch (error) {
       this.originalConsole.error("[debug] append logs failed", error);
+    } finally {
+      this.inFlight.delete(batchId);
+    }
+  }
+
+  private async loadPipelineSettings(): Promise<void> {
+    try {
+      const stats = await invoke<LogPipelineStats>(
+        "plugin:debug-tools|get_log_stats",
+      );
+      this.flushPolicy.configure({
+        baseIntervalMs: stats.flush_interval_ms,
+        minIntervalMs: stats.min_flush_interval_ms,
+        maxIntervalMs: stats.max_flush_interval_ms,
+        byteThreshold: stats.flush_byte_threshold,
+        burstEntriesPerSec: stats.flush_burst_entries_per_sec,
+      });
+      this.flushBatchSize = Math.max(1, stats.flush_batch_size);
+      this.maxPendingLogs = Math.max(1, stats.max_queued_entries);
+    } catch (error) {
+      this.originalConsole.error("[debug] loading log settings failed", error);
     }
   }
 
@@ -149,7 +309,13 @@ class ConsoleLogCollector {
 
   private handleTauriReady(): void {
     this.tauriReady = true;
-    void this.resetLogsFile().finally(() => {
+    void listen(FLUSH_LOGS_EVENT, () => this.flush()).catch((error) => {
+      this.originalConsole.error("[debug] flush listener failed", error);
+    });
+    void Promise.all([
+      this.loadPipelineSettings(),
+      this.resetLogsFile(),
+    ]).finally(() => {
       void this.flushPending();
     });
   }
@@ -215,10 +381,14 @@ class ConsoleLogCollector {
     return origin ? [...args, `[origin] ${origin}`] : args;
   }
 
-  public record(level: ConsoleLogEntry["level"], args: unknown[]): void {
+  public record(
+    level: ConsoleLogEntry["level"],
+    args: unknown[],
+    fields?: Record<string, unknown>,
+  ): void {
     const { origin, stack } = this.buildOrigin(args);
     const enrichedArgs = this.withOriginArgs(args, origin);
-    this.addLog(level, enrichedArgs, this.normalizeStack(stack));
+    this.addLog(level, enrichedArgs, this.normalizeStack(stack), fields);
   }
 
   public log(...args: unknown[]): void {
@@ -274,6 +444,17 @@ class ConsoleLogCollector {
     return [header, ...filtered].join("\n");
   }
 
+  /**
+   * Subscribe to entries the backend collapsed when `dedup_console_logs` is
+   * enabled. Returns a function that removes the handler.
+   */
+  public onDeduplicated(
+    handler: (collapsed: DeduplicatedLog[]) => void,
+  ): () => void {
+    this.dedupHandlers.add(handler);
+    return () => this.dedupHandlers.delete(handler);
+  }
+
   /**
    * Get all logs.
    */
@@ -302,6 +483,75 @@ class ConsoleLogCollector {
     return this.logs.slice(-count);
   }
 
+  /**
+   * Current interval of the adaptive flush policy: how long an entry below
+   * `warn` may wait before it is sent.
+   */
+  public getFlushIntervalMs(): number {
+    return this.flushPolicy.currentIntervalMs;
+  }
+
+  /**
+   * Send every pending entry to the backend now instead of waiting for the
+   * flush interval. Resolves once all batches were handed over.
+   */
+  public async flush(): Promise<void> {
+    if (!this.tauriReady) return;
+    while (this.pendingLogs.length > 0) {
+      await this.flushPending();
+    }
+  }
+
+  /**
+   * Hand every undelivered entry, including those of flushes still in
+   * flight, to the backend in one request that survives the page unloading.
+   * Sent on `pagehide`; call it yourself before tearing a webview down.
+   * Returns whether the browser accepted the request.
+   */
+  public sendFinalBeacon(reason = "manual"): boolean {
+    if (typeof window === "undefined") return false;
+    const inFlight = [...this.inFlight.values()].flat();
+    const entries = [...inFlight, ...this.pendingLogs].sort(
+      (a, b) => (a.seq ?? 0) - (b.seq ?? 0),
+    );
+    const beacon: FinalBeacon = {
+      source: this.source,
+      ingest_source: "collector",
+      reason,
+      entries,
+      state: {
+        url: window.location.href,
+        title: typeof document === "undefined" ? "" : document.title,
+        visibility_state:
+          typeof document === "undefined"
+            ? undefined
+            : document.visibilityState,
+        pending_entries: this.pendingLogs.length,
+        in_flight_entries: inFlight.length,
+        dropped_entries: this.droppedSinceFlush,
+        truncated_entries: 0,
+      },
+    };
+    const body = encodeFinalBeacon(beacon);
+
+    try {
+      const url = convertFileSrc("final-beacon", "debug-tools");
+      // Custom schemes may refuse beacons on some platforms; a keepalive
+      // fetch outlives the page there as well.
+      if (navigator.sendBeacon?.(url, body)) return true;
+      void fetch(url, {
+        method: "POST",
+        body,
+        keepalive: true,
+        mode: "no-cors",
+      }).catch(() => undefined);
+      return true;
+    } catch (error) {
+      this.originalConsole.error("[debug] final beacon failed", error);
+      return false;
+    }
+  }
+
   /**
    * Clear logs.
    */
@@ -335,6 +585,34 @@ class ConsoleLogCollector {
   }
 }
 
+function createSourceId(): string {
+  const random = Math.random().toString(36).slice(2, 10);
+  return `${Date.now().toString(36)}-${random}`;
+}
+
+// Serializes `beacon`, leaving out its oldest entries until it fits the
+// beacon size limit.
+function encodeFinalBeacon(beacon: FinalBeacon): string {
+  const encoder = new TextEncoder();
+  const sizes = beacon.entries.map(
+    (entry) => encoder.encode(JSON.stringify(entry)).length + 1,
+  );
+  // Room for the truncation count to grow by a few digits.
+  const envelope =
+    encoder.encode(JSON.stringify({ ...beacon, entries: [] })).length + 8;
+  let total = sizes.reduce((sum, size) => sum + size, envelope);
+  let skip = 0;
+  while (total > MAX_FINAL_BEACON_BYTES && skip < sizes.length) {
+    total -= sizes[skip];
+    skip += 1;
+  }
+  return JSON.stringify({
+    ...beacon,
+    entries: beacon.entries.slice(skip),
+    state: { ...beacon.state, truncated_entries: skip },
+  });
+}
+
 // Singleton instance.
 export const consoleLogger = new ConsoleLogCollector();
 
@@ -344,17 +622,30 @@ export const debugTools = {
   warn: (...args: unknown[]) => consoleLogger.warn(...args),
   error: (...args: unknown[]) => consoleLogger.error(...args),
   debug: (...args: unknown[]) => consoleLogger.debug(...args),
-  record: (level: ConsoleLogEntry["level"], args: unknown[]) =>
-    consoleLogger.record(level, args),
+  record: (
+    level: ConsoleLogEntry["level"],
+    args: unknown[],
+    fields?: Record<string, unknown>,
+  ) => consoleLogger.record(level, args, fields),
 };
 
+export const onDeduplicated = (
+  handler: (collapsed: DeduplicatedLog[]) => void,
+) => consoleLogger.onDeduplicated(handler);
+
+export const sendFinalBeacon = (reason?: string) =>
+  consoleLogger.sendFinalBeacon(reason);
+
 export const log = (...args: unknown[]) => consoleLogger.log(...args);
 export const info = (...args: unknown[]) => consoleLogger.info(...args);
 export const warn = (...args: unknown[]) => consoleLogger.warn(...args);
 export const error = (...args: unknown[]) => consoleLogger.error(...args);
 export const debug = (...args: unknown[]) => consoleLogger.debug(...args);
-export const record = (level: ConsoleLogEntry["level"], args: unknown[]) =>
-  consoleLogger.record(level, args);
+export const record = (
+  level: ConsoleLogEntry["level"],
+  args: unknown[],
+  fields?: Record<string, unknown>,
+) => consoleLogger.record(level, args, fields);
 
 // Expose globally so debugBridge can read logs.
 if (typeof window !== "undefined") {
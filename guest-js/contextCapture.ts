@@ -0,0 +1,423 @@
+/**
+ * Page context capture utilities for tauri-plugin-debug-tools
+ *
+ * Collects state that is only visible from the WebView (security policy,
+ * violations, ...) and reports it to the backend, where it is embedded into
+ * the next full debug snapshot.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface CspViolation {
+  timestamp: number;
+  blocked_uri: string;
+  violated_directive: string;
+  effective_directive: string;
+  original_policy: string;
+  disposition: string;
+  source_file: string | null;
+  line_number: number | null;
+  column_number: number | null;
+}
+
+export interface SecurityContext {
+  captured_at: number;
+  url: string;
+  content_security_policy: string[];
+  referrer_policy: string | null;
+  is_secure_context: boolean;
+  violations: CspViolation[];
+}
+
+const MAX_CSP_VIOLATIONS = 100;
+const cspViolations: CspViolation[] = [];
+
+if (typeof document !== "undefined") {
+  document.addEventListener("securitypolicyviolation", (event) => {
+    cspViolations.push({
+      timestamp: Date.now(),
+      blocked_uri: event.blockedURI,
+      violated_directive: event.violatedDirective,
+      effective_directive: event.effectiveDirective,
+      original_policy: event.originalPolicy,
+      disposition: event.disposition,
+      source_file: event.sourceFile || null,
+      line_number: event.lineNumber || null,
+      column_number: event.columnNumber || null,
+    });
+    if (cspViolations.length > MAX_CSP_VIOLATIONS) {
+      cspViolations.shift();
+    }
+  });
+}
+
+export interface LifecycleEvent {
+  timestamp: number;
+  kind: "focus" | "blur" | "visible" | "hidden" | "suspected_suspend";
+  window_label: string | null;
+  gap_ms: number | null;
+}
+
+/**
+ * Record a page visibility change as a lifecycle marker in the log timeline.
+ * Called automatically on `visibilitychange`.
+ */
+export async function recordVisibilityChange(
+  state: DocumentVisibilityState,
+): Promise<LifecycleEvent> {
+  return await invoke<LifecycleEvent>(
+    "plugin:debug-tools|record_visibility_change",
+    { state },
+  );
+}
+
+if (typeof document !== "undefined") {
+  document.addEventListener("visibilitychange", () => {
+    recordVisibilityChange(document.visibilityState).catch(() => {
+      // The backend may not be ready yet; a missed marker is harmless.
+    });
+  });
+}
+
+function metaContent(httpEquivOrName: string): string[] {
+  if (typeof document === "undefined") return [];
+  return Array.from(
+    document.querySelectorAll<HTMLMetaElement>(
+      `meta[http-equiv="${httpEquivOrName}" i], meta[name="${httpEquivOrName}" i]`,
+    ),
+  )
+    .map((meta) => meta.content)
+    .filter(Boolean);
+}
+
+/**
+ * Collect the effective CSP (from meta tags) and recorded violation events.
+ */
+export function getSecurityContext(): SecurityContext {
+  return {
+    captured_at: Date.now(),
+    url: typeof window !== "undefined" ? window.location.href : "",
+    content_security_policy: metaContent("Content-Security-Policy"),
+    referrer_policy: metaContent("referrer")[0] ?? null,
+    is_secure_context:
+      typeof window !== "undefined" ? window.isSecureContext : false,
+    violations: [...cspViolations],
+  };
+}
+
+/**
+ * Report the security context to the backend so it is stored on the next snapshot.
+ *
+ * @example
+ * ```typescript
+ * const context = await captureSecurityContext();
+ * console.log(`CSP violations: ${context.violations.length}`);
+ * ```
+ */
+export async function captureSecurityContext(): Promise<SecurityContext> {
+  return await invoke<SecurityContext>(
+    "plugin:debug-tools|capture_security_context",
+    { context: getSecurityContext() },
+  );
+}
+
+export interface FrontendRendererInfo {
+  reported_at: number;
+  webgl_version: string | null;
+  vendor: string | null;
+  renderer: string | null;
+  unmasked_vendor: string | null;
+  unmasked_renderer: string | null;
+}
+
+/**
+ * Read WebGL vendor/renderer strings. Fields the WebView withholds are null.
+ */
+export function getRendererInfo(): FrontendRendererInfo {
+  const info: FrontendRendererInfo = {
+    reported_at: Date.now(),
+    webgl_version: null,
+    vendor: null,
+    renderer: null,
+    unmasked_vendor: null,
+    unmasked_renderer: null,
+  };
+  if (typeof document === "undefined") return info;
+
+  const canvas = document.createElement("canvas");
+  const gl =
+    (canvas.getContext("webgl2") as WebGL2RenderingContext | null) ??
+    (canvas.getContext("webgl") as WebGLRenderingContext | null);
+  if (!gl) return info;
+
+  info.webgl_version = String(gl.getParameter(gl.VERSION) ?? "") || null;
+  info.vendor = String(gl.getParameter(gl.VENDOR) ?? "") || null;
+  info.renderer = String(gl.getParameter(gl.RENDERER) ?? "") || null;
+
+  const debugInfo = gl.getExtension("WEBGL_debug_renderer_info");
+  if (debugInfo) {
+    info.unmasked_vendor =
+      String(gl.getParameter(debugInfo.UNMASKED_VENDOR_WEBGL) ?? "") || null;
+    info.unmasked_renderer =
+      String(gl.getParameter(debugInfo.UNMASKED_RENDERER_WEBGL) ?? "") || null;
+  }
+
+  gl.getExtension("WEBGL_lose_context")?.loseContext();
+  return info;
+}
+
+let rendererReport: Promise<FrontendRendererInfo> | null = null;
+
+/**
+ * Report WebGL renderer details to the backend. Collected once per page
+ * session; later calls return the first report.
+ */
+export function reportRendererInfo(): Promise<FrontendRendererInfo> {
+  rendererReport ??= invoke<FrontendRendererInfo>(
+    "plugin:debug-tools|report_renderer_info",
+    { info: getRendererInfo() },
+  ).catch((error) => {
+    rendererReport = null;
+    throw error;
+  });
+  return rendererReport;
+}
+
+export interface ResourceTiming {
+  url: string;
+  initiator_type: string;
+  start_time_ms: number;
+  duration_ms: number;
+  transfer_size: number;
+  encoded_body_size: number;
+  response_status: number | null;
+}
+
+/**
+ * Read the page's loaded resources from the Performance API.
+ */
+export function getResourceTimings(): ResourceTiming[] {
+  if (typeof performance === "undefined") return [];
+  return (
+    performance.getEntriesByType("resource") as PerformanceResourceTiming[]
+  ).map((entry) => {
+    const status = (entry as PerformanceResourceTiming & {
+      responseStatus?: number;
+    }).responseStatus;
+    return {
+      url: entry.name,
+      initiator_type: entry.initiatorType,
+      start_time_ms: entry.startTime,
+      duration_ms: entry.duration,
+      transfer_size: entry.transferSize,
+      encoded_body_size: entry.encodedBodySize,
+      // 0 means the engine did not expose the status.
+      response_status: status ? status : null,
+    };
+  });
+}
+
+/**
+ * Report loaded resources to the backend so they are stored on the next
+ * snapshot. Requires DOM capture to be enabled in the active profile.
+ *
+ * @example
+ * ```typescript
+ * const resources = await captureResources();
+ * const slow = resources.filter((r) => r.duration_ms > 1000);
+ * ```
+ */
+export async function captureResources(): Promise<ResourceTiming[]> {
+  return await invoke<ResourceTiming[]>(
+    "plugin:debug-tools|capture_resources",
+    { resources: getResourceTimings() },
+  );
+}
+
+export interface RouteState {
+  /** Logical route (e.g. `/users/:id`); snapshots are indexed by it. */
+  route: string;
+  params?: Record<string, unknown>;
+  /** Entries of the router's navigation stack, oldest first. */
+  navigation_stack?: unknown[];
+  [key: string]: unknown;
+}
+
+/**
+ * Report the router's current state so it is stored on the next snapshot.
+ * Useful for hash and in-memory routers whose route is not in the URL.
+ *
+ * @example
+ * ```typescript
+ * router.afterEach((to) => {
+ *   void captureRouteState({ route: to.matched.at(-1)?.path ?? to.path, params: to.params });
+ * });
+ * ```
+ */
+export async function captureRouteState(
+  routeState: RouteState,
+): Promise<RouteState> {
+  return await invoke<RouteState>("plugin:debug-tools|capture_route_state", {
+    routeState,
+  });
+}
+
+export interface FormFieldValue {
+  /** `id` or `name` of the enclosing form, if any. */
+  form: string | null;
+  name: string;
+  /** Input `type`, or `select` / `textarea`. */
+  field_type: string;
+  value: string;
+  masked: boolean;
+}
+
+export interface FormState {
+  captured_at: number;
+  url: string;
+  fields: FormFieldValue[];
+}
+
+const MASKED_VALUE = "********";
+
+/**
+ * Collect the current values of named form fields. Password fields are
+ * masked; file inputs report file names only.
+ */
+export function collectFormState(root: ParentNode = document): FormState {
+  const fields: FormFieldValue[] = [];
+  const elements = root.querySelectorAll<
+    HTMLInputElement | HTMLSelectElement | HTMLTextAreaElement
+  >("input, select, textarea");
+
+  for (const element of Array.from(elements)) {
+    const name = element.name || element.id;
+    if (!name) continue;
+
+    const fieldType =
+      element instanceof HTMLInputElement
+        ? element.type
+        : element.tagName.toLowerCase();
+    if (
+      fieldType === "submit" ||
+      fieldType === "button" ||
+      fieldType === "reset"
+    ) {
+      continue;
+    }
+
+    const masked = fieldType === "password";
+    let value: string;
+    if (masked) {
+      value = MASKED_VALUE;
+    } else if (element instanceof HTMLInputElement && fieldType === "file") {
+      value = Array.from(element.files ?? [])
+        .map((file) => file.name)
+        .join(", ");
+    } else if (
+      element instanceof HTMLInputElement &&
+      (fieldType === "checkbox" || fieldType === "radio")
+    ) {
+      value = element.checked ? element.value || "on" : "";
+    } else if (element instanceof HTMLSelectElement && element.multiple) {
+      value = Array.from(element.selectedOptions)
+        .map((option) => option.value)
+        .join(", ");
+    } else {
+      value = element.value;
+    }
+
+    fields.push({
+      form: element.form ? element.form.id || element.form.name || null : null,
+      name,
+      field_type: fieldType,
+      value,
+      masked,
+    });
+  }
+
+  return { captured_at: Date.now(), url: window.location.href, fields };
+}
+
+/**
+ * Report the page's form field values so they are stored on the next
+ * snapshot as `form_state`. Requires `enable_form_capture` in the plugin
+ * config; values are redacted with the active profile's patterns.
+ *
+ * @example
+ * ```typescript
+ * window.addEventListener("error", () => void captureFormState());
+ * ```
+ */
+export async function captureFormState(
+  root: ParentNode = document,
+): Promise<FormState> {
+  return await invoke<FormState>("plugin:debug-tools|capture_form_state", {
+    formState: collectFormState(root),
+  });
+}
+
+export interface StorageQuota {
+  captured_at: number;
+  /** `false` when the WebView has no `navigator.storage.estimate()`. */
+  supported: boolean;
+  usage_bytes: number | null;
+  quota_bytes: number | null;
+  /** Per-store breakdown (`indexedDB`, `caches`, ...) where available. */
+  usage_details: Record<string, unknown> | null;
+  persisted: boolean | null;
+}
+
+/**
+ * Read `navigator.storage.estimate()`. Never throws: WebViews without the
+ * StorageManager API report `supported: false`.
+ */
+export async function collectStorageQuota(): Promise<StorageQuota> {
+  const quota: StorageQuota = {
+    captured_at: Date.now(),
+    supported: false,
+    usage_bytes: null,
+    quota_bytes: null,
+    usage_details: null,
+    persisted: null,
+  };
+  const storage =
+    typeof navigator !== "undefined" ? navigator.storage : undefined;
+  if (!storage || typeof storage.estimate !== "function") return quota;
+
+  try {
+    const estimate = (await storage.estimate()) as StorageEstimate & {
+      usageDetails?: Record<string, unknown>;
+    };
+    quota.supported = true;
+    quota.usage_bytes = estimate.usage ?? null;
+    quota.quota_bytes = estimate.quota ?? null;
+    quota.usage_details = estimate.usageDetails ?? null;
+  } catch {
+    return quota;
+  }
+  if (typeof storage.persisted === "function") {
+    quota.persisted = await storage.persisted().catch(() => null);
+  }
+  return quota;
+}
+
+/**
+ * Report the WebView's storage usage and quota so they are stored on the
+ * next snapshot as `storage_quota`. Explains writes that fail silently
+ * because origin storage was full.
+ *
+ * @example
+ * ```typescript
+ * const quota = await captureStorageQuota();
+ * if (quota.usage_bytes && quota.quota_bytes) {
+ *   console.log(`${Math.round((quota.usage_bytes / quota.quota_bytes) * 100)}% used`);
+ * }
+ * ```
+ */
+export async function captureStorageQuota(): Promise<StorageQuota> {
+  return await invoke<StorageQuota>(
+    "plugin:debug-tools|capture_storage_quota",
+    { quota: await collectStorageQuota() },
+  );
+}
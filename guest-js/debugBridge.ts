@@ -1,5 +1,26 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { ConsoleLogEntry } from "./consoleLogger";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import {
+  consoleLogger,
+  type ConsoleLogEntry,
+  type ConsoleSource,
+  type FinalBeacon,
+  type FinalBeaconState,
+  type LogPipelineStats,
+} from "./consoleLogger";
+import type {
+  FormState,
+  FrontendRendererInfo,
+  LifecycleEvent,
+  ResourceTiming,
+  StorageQuota,
+} from "./contextCapture";
+import {
+  captureDOMHTML,
+  captureDOMSnapshot,
+  type DomCaptureOptions,
+} from "./domCapture";
 
 export interface WebViewState {
   url: string;
@@ -21,6 +42,139 @@ export interface ClearDebugLogsResult {
   deleted_paths: string[];
   truncated_paths: string[];
   failed_paths: string[];
+  /** Left in place because they are under legal hold. */
+  held_paths: string[];
+  /** Named like plugin files but not created by it; left in place. */
+  skipped_foreign: string[];
+  /** Every deleted or truncated path. */
+  deletions: DeletedFile[];
+}
+
+export interface DeletedFile {
+  path: string;
+  /** The contents were overwritten with zeros before deletion. */
+  securely_deleted: boolean;
+  /** Why secure deletion was asked for but not done. */
+  note?: string;
+}
+
+export interface ClaimFilesResult {
+  claimed_paths: string[];
+  already_owned: number;
+  /** Outside the log directory, missing, or not regular files. */
+  rejected_paths: string[];
+}
+
+/** Contents of `final_beacon_<received_at>.json`. */
+export interface FinalBeaconRecord {
+  final_beacon: true;
+  source: string;
+  reason?: string | null;
+  received_at: number;
+  session_id?: string | null;
+  state: FinalBeaconState;
+  first_seq?: number | null;
+  last_seq?: number | null;
+  written_entries: number;
+  /** Entries a regular flush had already written. */
+  duplicate_entries: number;
+}
+
+/**
+ * A note on one console entry, by the entry's `fields.log_seq`. Query
+ * results, exports and merged timelines show it as `fields.annotation`.
+ */
+export interface LogAnnotation {
+  session_id: string;
+  seq: number;
+  /** File name of the console log holding the entry. */
+  log_file: string;
+  note: string;
+  tag: string | null;
+  annotated_at: number;
+}
+
+/** Rejection of `annotateLogEntry`, distinguished by `code`. */
+export type AnnotationError =
+  | { code: "unknown_seq"; seq: number }
+  | { code: "note_too_long"; chars: number; max: number }
+  | { code: "tag_too_long"; chars: number; max: number }
+  | { code: "limit_reached"; max: number }
+  | { code: "storage"; message: string };
+
+/** Rejection of `readDebugSnapshot`, distinguished by `code`. */
+export type SnapshotReadError =
+  | { code: "invalid_timestamp"; timestamp: number }
+  | { code: "not_found"; timestamp: number }
+  | { code: "unreadable"; timestamp: number; message: string };
+
+/** Rejection of `deleteDebugScreenshot`, distinguished by `code`. */
+export type ScreenshotDeleteError =
+  | { code: "invalid_path"; message: string }
+  /** A legal hold covers the screenshot or a snapshot linking it. */
+  | { code: "pinned_artifact"; path: string; pinned_by: string[] }
+  | { code: "still_referenced"; path: string; snapshots: string[] }
+  | { code: "delete"; message: string };
+
+/** A line of tauri-plugin-log's file; `raw` lines only have `message`. */
+export interface PluginLogLine {
+  timestamp: number | null;
+  level: "trace" | "debug" | "info" | "warn" | "error" | null;
+  target: string | null;
+  message: string;
+  raw: boolean;
+}
+
+/** The newest persisted console entries of this session, oldest first. */
+export interface ConsoleLogTail {
+  entries: ConsoleLogEntry[];
+  /** Well-formed entries in the file, before `limit` and `offset`. */
+  total: number;
+  /** Lines that did not parse and were skipped. */
+  malformed_lines: number;
+}
+
+/** One timed step of the plugin's setup. */
+export interface InitPhase {
+  name: string;
+  duration_ms: number;
+  thread: string;
+  /** What the phase did instead of its usual path, if anything. */
+  fallback: string | null;
+  /** Finished after setup returned, on another thread. */
+  background: boolean;
+}
+
+/**
+ * Where the plugin's setup spent its time. `total_ms` covers the setup
+ * closure only; background phases are appended as they finish.
+ */
+export interface PluginInitReport {
+  started_at: number;
+  total_ms: number;
+  thread: string;
+  phases: InitPhase[];
+}
+
+/** Half-open byte range `[start, end)` in the original file. */
+export interface ByteRange {
+  start: number;
+  end: number;
+}
+
+/** One entry of `repaired_logs.json`. */
+export interface LogRepairReport {
+  path: string;
+  /** `null` when every line parsed and no copy was written. */
+  repaired_path: string | null;
+  /** Length of the original when it was scanned. */
+  source_bytes: number;
+  kept_entries: number;
+  discarded_lines: number;
+  discarded_bytes: number;
+  /** The first discarded ranges only, adjacent lines merged. */
+  discarded_ranges: ByteRange[];
+  repaired_at: number;
 }
 
 export interface CopyScreenshotResult {
@@ -28,11 +182,481 @@ export interface CopyScreenshotResult {
   destination_path: string;
 }
 
+export interface ScreenshotInfo {
+  path: string;
+  capture_id: string;
+  timestamp: number;
+  width: number | null;
+  height: number | null;
+  size_bytes: number;
+  format: string;
+  has_thumbnail: boolean;
+  /** Set on crops of a region or element. */
+  region?: ScreenshotRegion;
+}
+
+/** CSS pixels (logical) or physical pixels of a window's content area. */
+export interface ScreenshotRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface ScreenshotRegion {
+  window_label: string;
+  requested: ScreenshotRect;
+  logical: boolean;
+  scale_factor: number;
+  /** Physical pixels of the full capture that were kept. */
+  crop: ScreenshotRect;
+  /** Part of the requested rect fell outside the window. */
+  clamped: boolean;
+  selector?: string;
+}
+
+export interface RegionScreenshot {
+  path: string;
+  region: ScreenshotRegion;
+}
+
+/** Rejection of the region and element captures, distinguished by `code`. */
+export type ScreenshotRegionError =
+  | { code: "disabled" }
+  | { code: "window_not_found"; window_label: string }
+  | {
+      code: "empty_region";
+      rect: ScreenshotRect;
+      image_width: number;
+      image_height: number;
+    }
+  | { code: "element_not_found"; selector: string }
+  | { code: "element_rect_unavailable"; message: string }
+  | { code: "capture"; message: string };
+
+export interface QuietModeStatus {
+  level: "trace" | "debug" | "info" | "warn" | "error";
+  started_at: number;
+  until: number;
+  suppressed_entries: number;
+  /** Navigation and error-alarm snapshots skipped while active. */
+  paused_snapshots: number;
+}
+
+export interface CaptureProfile {
+  enable_dom_capture: boolean;
+  enable_screenshots: boolean;
+  max_console_logs: number | null;
+  redact_patterns: string[];
+  /** Set by the privacy settings: redact secrets found without a pattern. */
+  redact_sensitive_values?: boolean;
+}
+
+export interface CaptureProfileInfo {
+  name: string;
+  active: boolean;
+  profile: CaptureProfile;
+}
+
+export type PrivacyPreset = "open" | "balanced" | "strict";
+
+/** Capture ceilings after the preset and overrides resolved. */
+export interface PrivacySettings {
+  redact_sensitive_values: boolean;
+  strip_url_queries: boolean;
+  capture_form_state: boolean;
+  capture_bodies: boolean;
+  capture_screenshots: boolean;
+  capture_dom: boolean;
+  /** Share of console entries below `warn` that are written. */
+  log_sample_rate: number;
+}
+
+export interface PrivacyStatus {
+  /** `null` when the individual config flags apply. */
+  preset: PrivacyPreset | null;
+  overrides: Partial<Record<keyof PrivacySettings, boolean | number | null>>;
+  effective: PrivacySettings;
+  sampled_out_entries: number;
+}
+
+/** Settings in force, as also written to `config_history/`. */
+export interface EffectiveConfig {
+  written_at: number;
+  reason: string;
+  log_dir: string;
+  max_log_size_bytes: number;
+  log_format: "json" | "text";
+  enable_dom_capture: boolean;
+  enable_rust_logging: boolean;
+  active_profile: { name: string; profile: CaptureProfile };
+  quiet_mode: QuietModeStatus | null;
+  privacy: PrivacyStatus | null;
+  /** Absent from files written before session ids were recorded. */
+  session_id?: string | null;
+}
+
+export interface OperationMetrics {
+  calls: number;
+  errors: number;
+  bytes_written: number;
+  avg_latency_us: number;
+  max_latency_us: number;
+  latency_histogram: { le_ms: number | null; count: number }[];
+}
+
+export interface RepositoryMetricsReport {
+  save_snapshot: OperationMetrics;
+  save_dom: OperationMetrics;
+  save_console_logs: OperationMetrics;
+  /** Time spent flushing/syncing writes, per configured durability level. */
+  durability: DurabilityLatency[];
+  /** Reuse of snapshot and DOM serialization buffers. */
+  buffer_pool?: BufferPoolStats;
+}
+
+export interface BufferPoolStats {
+  /** Captures that reused a pooled buffer. */
+  hits: number;
+  /** Captures that allocated a new buffer. */
+  misses: number;
+  pooled_buffers: number;
+  pooled_bytes: number;
+  max_pooled_bytes: number;
+}
+
+export interface DurabilityLatency {
+  durability: "none" | "flush" | "fsync";
+  writes: number;
+  avg_sync_us: number;
+  max_sync_us: number;
+}
+
+export interface DebugCommandReplyError {
+  message: string;
+  stack?: string | null;
+}
+
+export interface DebugCommandReply {
+  ok: boolean;
+  value?: unknown;
+  error?: DebugCommandReplyError;
+}
+
+export type DebugCommandOutcome =
+  | { status: "sent" }
+  | { status: "ok"; value: unknown }
+  | { status: "handler_error"; error: DebugCommandReplyError }
+  | { status: "missing_listener" }
+  | { status: "timeout"; timeout_ms: number };
+
+export interface DebugCommandRecord {
+  request_id: string;
+  command: string;
+  window_label: string;
+  sent_at: number;
+  duration_ms: number;
+  outcome: DebugCommandOutcome;
+  /** W3C trace context the command was sent under. */
+  traceparent?: string;
+}
+
+export interface DebugCommandHistory {
+  recent: DebugCommandRecord[];
+  recent_failures: DebugCommandRecord[];
+}
+
+/** Passed to debug command handlers alongside the payload. */
+export interface DebugCommandContext {
+  /**
+   * W3C trace context of the command. Forward it as the `traceparent`
+   * header on any network call the handler makes, and log it as the
+   * `traceparent` field, so the action can be followed across services.
+   */
+  traceparent: string;
+}
+
+export type DebugCommandHandler = (
+  payload: unknown,
+  context: DebugCommandContext,
+) => unknown;
+
+export interface PartialDebugSnapshot {
+  console_logs?: ConsoleLogEntry[];
+  extensions?: Record<string, unknown>;
+  label?: string;
+  note?: string;
+  webview_state?: WebViewState;
+}
+
+export interface BuildInfo {
+  app_name: string;
+  app_version: string;
+  plugin_version: string;
+  debug_build: boolean;
+}
+
+export interface GpuAdapterInfo {
+  name: string | null;
+  vendor_id: string | null;
+  device_id: string | null;
+  driver: string | null;
+}
+
+export interface PlatformGpuInfo {
+  os: string;
+  arch: string;
+  adapters: GpuAdapterInfo[];
+}
+
+export interface RendererInfo {
+  frontend: FrontendRendererInfo | null;
+  platform: PlatformGpuInfo | null;
+}
+
+export interface StorageStatus {
+  log_dir: string;
+  filesystem: {
+    kind: "local" | "network" | "unknown";
+    fs_type: string | null;
+  };
+  /** Copy+delete instead of rename, and longer retries on transient errors. */
+  conservative_writes: boolean;
+  /** Set when `log_dir` is on a network filesystem. */
+  warning: string | null;
+}
+
+export interface DebugEnvironment {
+  session_id: string;
+  build_info: BuildInfo;
+  renderer: RendererInfo;
+  storage: StorageStatus;
+}
+
+export interface DebugSnapshot {
+  timestamp: number;
+  webview_state: WebViewState;
+  console_logs: ConsoleLogEntry[];
+  screenshot_path: string | null;
+  dom_snapshot_path: string | null;
+  effective_config_path: string | null;
+  session_id: string | null;
+  build_info: BuildInfo | null;
+  label: string | null;
+  note: string | null;
+  extensions: Record<string, unknown>;
+  resources: ResourceTiming[];
+  recent_lifecycle_events: LifecycleEvent[];
+  route_state: Record<string, unknown> | null;
+  size_report: CaptureSizeReport | null;
+  clock_skew: ClockSkewMeasurement | null;
+  /** 0 for legacy payloads wrapped under `extensions.legacy_payload`. */
+  schema_version: number;
+  form_state: FormState | null;
+  storage_quota: StorageQuota | null;
+  /** Added after capture by `annotateSnapshot`. */
+  annotations: Record<string, unknown>;
+  /** `screenshot_path` links an earlier capture other snapshots may share. */
+  screenshot_reused?: boolean;
+  /** Age of the reused screenshot when the snapshot was taken. */
+  screenshot_age_secs?: number;
+}
+
+/** How `capture_full_debug_state` gets a screenshot when given no path. */
+export type ScreenshotPolicy =
+  | { mode: "always_capture" }
+  | { mode: "reuse_within"; secs: number }
+  | { mode: "never" };
+
+export interface CaptureSizeReport {
+  /** Includes the embedded console logs and extensions. */
+  snapshot_json_bytes: number;
+  console_logs_bytes: number;
+  extensions_bytes: number;
+  screenshot_bytes: number | null;
+  dom_bytes: number | null;
+  total_bytes: number;
+}
+
+export interface BackendLogEvent {
+  timestamp: number;
+  level: "trace" | "debug" | "info" | "warn" | "error";
+  target: string;
+  message: string;
+}
+
+export interface BackendLogBatch {
+  events: BackendLogEvent[];
+  dropped_in_window: number;
+}
+
+export interface ErrorRateWindow {
+  start: number;
+  errors: number;
+  errors_per_minute: number;
+}
+
+export interface ErrorRateReport {
+  window_secs: number;
+  current_errors_per_minute: number;
+  windows: ErrorRateWindow[];
+}
+
+export interface CaptureBenchmark {
+  state_ms: number;
+  dom_ms: number | null;
+  screenshot_ms: number | null;
+  save_ms: number;
+  total_ms: number;
+  persisted: boolean;
+  snapshot_path: string | null;
+  /** Time spent serializing the DOM in the WebView (frontend-measured). */
+  dom_serialize_ms?: number;
+}
+
+export interface BenchmarkCaptureOptions {
+  includeDom?: boolean;
+  includeScreenshot?: boolean;
+  /** Keep the captured artifacts instead of discarding them (default false). */
+  persist?: boolean;
+}
+
+export interface ErrorAlarmStatus {
+  threshold_count: number;
+  window_secs: number;
+  cooldown_secs: number;
+  errors_in_window: number;
+  firings: number;
+  last_fired_at: number | null;
+  cooling_down: boolean;
+}
+
+export interface DebugStats {
+  quiet_mode: QuietModeStatus | null;
+  /** Unix seconds quiet mode lasts until; `null` when it is off. */
+  suppress_below_level_until: number | null;
+  total_suppressed_entries: number;
+  /** `null` unless `error_alarm` is configured. */
+  error_alarm: ErrorAlarmStatus | null;
+  /** Last `checkClockSkew` result, if any. */
+  clock_skew: ClockSkewMeasurement | null;
+  screenshot_queue: ScreenshotQueueStats;
+  /**
+   * Backend log events raised while the plugin's own log writers were
+   * running; they went to stdout only, so they cannot trigger more writes.
+   */
+  reentrant_events_suppressed: number;
+  /**
+   * Current adaptive flush interval of the console collector, as sent with
+   * its latest batch; `null` before the first batch.
+   */
+  console_flush_interval_ms: number | null;
+  /** Latest housekeeping measurement; `null` before the first one. */
+  disk_usage: DiskUsage | null;
+  strict_mode: StrictModeStatus;
+  /**
+   * tauri-plugin-screenshots registers on a background thread just after
+   * startup; screenshot commands wait up to 2 seconds for it.
+   */
+  screenshots_plugin: ScreenshotsPluginStatus;
+}
+
+export type ScreenshotsPluginStatus =
+  | { state: "registering" }
+  | { state: "ready" }
+  | { state: "unavailable"; reason: string };
+
+export type StrictViolationKind =
+  | "queue_overflow"
+  | "storage_fallback"
+  | "screenshots_unavailable"
+  | "serialization_substitution"
+  | "clock_skew";
+
+export interface StrictViolation {
+  kind: StrictViolationKind;
+  message: string;
+  /** What the plugin would have done with `strict_mode` off. */
+  non_strict_behavior: string;
+  /** `null` when the condition arose outside a command, e.g. in setup. */
+  command: string | null;
+  timestamp: number;
+}
+
+export interface StrictModeStatus {
+  enabled: boolean;
+  /** Sticky until `clearStrictViolations`. */
+  strict_violation: boolean;
+  total_violations: number;
+  /** The most recent violations, oldest first. */
+  violations: StrictViolation[];
+}
+
+export type DiskCategory =
+  | "console_logs"
+  | "network_logs"
+  | "backend_logs"
+  | "snapshots"
+  | "screenshots"
+  | "dom_snapshots"
+  | "recordings"
+  | "exports"
+  | "other";
+
+export interface CategoryUsage {
+  category: DiskCategory;
+  bytes: number;
+  files: number;
+}
+
+export interface DiskUsage {
+  measured_at: number;
+  total_bytes: number;
+  /**
+   * `max_total_disk_bytes`, or the usage plus the free disk space when no
+   * quota is set; `null` when neither is known.
+   */
+  limit_bytes: number | null;
+  limit_source: "quota" | "free_disk" | null;
+  usage_ratio: number | null;
+  /** Largest first. */
+  categories: CategoryUsage[];
+}
+
+/** A command call expected to free `frees_bytes`. */
+export interface CleanupSuggestion {
+  command: string;
+  args: Record<string, unknown>;
+  frees_bytes: number;
+}
+
+export interface DiskUsageWarning {
+  /** The share of the limit crossed, `0.7` or `0.9`. */
+  threshold: number;
+  usage: DiskUsage;
+  suggested_cleanup: CleanupSuggestion | null;
+}
+
+export interface ScreenshotQueueStats {
+  /** Requests waiting for a slot or running. */
+  depth: number;
+  in_flight: number;
+  concurrency: number;
+  /** Requests served by another request's capture. */
+  coalesced: number;
+}
+
 /**
  * Get WebView state.
+ * @param label Window to query (default: the focused window, else `main`)
  */
-export async function captureWebViewState(): Promise<WebViewState> {
-  return await invoke<WebViewState>("plugin:debug-tools|capture_webview_state");
+export async function captureWebViewState(
+  label?: string,
+): Promise<WebViewState> {
+  return await invoke<WebViewState>(
+    "plugin:debug-tools|capture_webview_state",
+    { label },
+  );
 }
 
 /**
@@ -89,26 +713,36 @@ export function clearConsoleLogs(): void {
 }
 
 /**
- * Send a debug command (event-based).
+ * Send a debug command (event-based). The `debug-command` event carries
+ * `[command, payload, traceparent]`.
  * @param command Command name
  * @param payload Payload data
+ * @param traceparent W3C trace context to continue (default: a new trace)
  */
 export async function sendDebugCommand(
   command: string,
   payload: Record<string, unknown>,
+  traceparent?: string,
 ): Promise<string> {
   return await invoke<string>("plugin:debug-tools|send_debug_command", {
     command,
     payload,
+    traceparent,
   });
 }
 
 /**
- * Clear frontend/backend debug log files for the current host app.
+ * Clear frontend/backend debug log files for the current host app. Only
+ * files the plugin created are removed; see {@link claimExistingFiles}.
+ * @param secureDelete Overwrite files before deleting them; defaults to
+ * the `secure_delete` config
  */
-export async function clearDebugLogFiles(): Promise<ClearDebugLogsResult> {
+export async function clearDebugLogFiles(
+  secureDelete?: boolean,
+): Promise<ClearDebugLogsResult> {
   return await invoke<ClearDebugLogsResult>(
     "plugin:debug-tools|clear_debug_log_files_command",
+    { secureDelete },
   );
 }
 
@@ -125,3 +759,1815 @@ export async function copyScreenshotToDebugDir(
     { sourcePath },
   );
 }
+
+/**
+ * List screenshots in the debug-tools screenshots directory, newest first.
+ * @param limit Maximum number of entries (default 50)
+ * @param beforeTs Only return screenshots captured before this Unix timestamp (seconds)
+ */
+export async function listDebugScreenshots(
+  limit?: number,
+  beforeTs?: number,
+): Promise<ScreenshotInfo[]> {
+  return await invoke<ScreenshotInfo[]>(
+    "plugin:debug-tools|list_debug_screenshots",
+    { limit, beforeTs },
+  );
+}
+
+/**
+ * Delete a screenshot from the debug-tools screenshots directory. Rejects
+ * with a {@link ScreenshotDeleteError}, `pinned_artifact` while a legal
+ * hold keeps it.
+ * @param path Absolute path returned by `listDebugScreenshots`
+ * @returns The deletion, overwritten first when `secure_delete` is set
+ */
+export async function deleteDebugScreenshot(
+  path: string,
+): Promise<DeletedFile> {
+  return await invoke<DeletedFile>(
+    "plugin:debug-tools|delete_debug_screenshot",
+    { path },
+  );
+}
+
+/**
+ * Suppress persistence of console entries below `level` for a while.
+ * Suppressed entries are counted and summarized in a marker entry on expiry,
+ * written by the next housekeeping tick or log write after the deadline.
+ * @param level Minimum level still persisted (e.g. "warn")
+ * @param durationSecs How long quiet mode stays active
+ */
+export async function setQuietMode(
+  level: string,
+  durationSecs: number,
+): Promise<QuietModeStatus> {
+  return await invoke<QuietModeStatus>("plugin:debug-tools|set_quiet_mode", {
+    level,
+    durationSecs,
+  });
+}
+
+/**
+ * Get runtime statistics of the debug-tools backend.
+ */
+export async function getDebugStats(): Promise<DebugStats> {
+  return await invoke<DebugStats>("plugin:debug-tools|get_debug_stats");
+}
+
+/**
+ * List the configured capture profiles and which one is active.
+ */
+export async function listCaptureProfiles(): Promise<CaptureProfileInfo[]> {
+  return await invoke<CaptureProfileInfo[]>("plugin:debug-tools|list_profiles");
+}
+
+/**
+ * Switch the runtime-effective capture profile (e.g. "perf", "crash").
+ * @param name Profile name from `listCaptureProfiles`
+ */
+export async function setActiveCaptureProfile(
+  name: string,
+): Promise<{ name: string; profile: CaptureProfile }> {
+  return await invoke<{ name: string; profile: CaptureProfile }>(
+    "plugin:debug-tools|set_active_profile",
+    { name },
+  );
+}
+
+/**
+ * Get per-operation write metrics of the snapshot repository
+ * (call counts, bytes written, latency histograms).
+ */
+export async function getRepositoryMetrics(): Promise<RepositoryMetricsReport> {
+  return await invoke<RepositoryMetricsReport>(
+    "plugin:debug-tools|get_repository_metrics",
+  );
+}
+
+/**
+ * Render the folded tracing spans into an SVG flamegraph under the log directory.
+ * Requires the plugin to be built with the `flamegraph` Cargo feature.
+ * @returns Path to the generated SVG
+ */
+export async function exportFlamegraph(): Promise<string> {
+  return await invoke<string>("plugin:debug-tools|export_flamegraph");
+}
+
+const debugCommandHandlers = new Map<string, DebugCommandHandler>();
+let debugCommandListener: Promise<unknown> | null = null;
+
+function toReplyError(error: unknown): DebugCommandReplyError {
+  if (error instanceof Error) {
+    return { message: error.message, stack: error.stack };
+  }
+  return { message: String(error) };
+}
+
+async function handleDebugCommandRequest(request: {
+  request_id: string;
+  command: string;
+  payload: unknown;
+  traceparent: string;
+}): Promise<void> {
+  const handler = debugCommandHandlers.get(request.command);
+  let reply: DebugCommandReply;
+  if (!handler) {
+    reply = {
+      ok: false,
+      error: { message: `No handler registered for ${request.command}` },
+    };
+  } else {
+    try {
+      reply = {
+        ok: true,
+        value: await handler(request.payload, {
+          traceparent: request.traceparent,
+        }),
+      };
+    } catch (error) {
+      reply = { ok: false, error: toReplyError(error) };
+    }
+  }
+
+  await invoke("plugin:debug-tools|submit_debug_command_reply", {
+    requestId: request.request_id,
+    reply,
+  });
+}
+
+/**
+ * Register a frontend handler for `sendDebugCommandWithReply`.
+ * The handler's return value (or thrown error) is sent back to the backend.
+ * @param command Command name
+ * @param handler Handler invoked with the command payload
+ */
+export async function registerDebugCommandHandler(
+  command: string,
+  handler: DebugCommandHandler,
+): Promise<void> {
+  debugCommandHandlers.set(command, handler);
+
+  if (!debugCommandListener) {
+    debugCommandListener = getCurrentWebviewWindow().listen<{
+      request_id: string;
+      command: string;
+      payload: unknown;
+      traceparent: string;
+    }>("debug-command-request", (event) => {
+      void handleDebugCommandRequest(event.payload);
+    });
+  }
+  await debugCommandListener;
+
+  await invoke("plugin:debug-tools|register_debug_command_handlers", {
+    commands: [...debugCommandHandlers.keys()],
+  });
+}
+
+/**
+ * Send a debug command and wait for the frontend handler's reply.
+ * Timeouts, missing listeners and handler errors are distinguished by `status`.
+ * @param command Command name
+ * @param payload Payload data
+ * @param timeoutMs Reply timeout (default 5000ms)
+ * @param traceparent W3C trace context to continue (default: a new trace)
+ */
+export async function sendDebugCommandWithReply(
+  command: string,
+  payload: Record<string, unknown>,
+  timeoutMs?: number,
+  traceparent?: string,
+): Promise<DebugCommandOutcome> {
+  return await invoke<DebugCommandOutcome>(
+    "plugin:debug-tools|send_debug_command_with_reply",
+    { command, payload, timeoutMs, traceparent },
+  );
+}
+
+/**
+ * Get recently sent debug commands, including the last few failures.
+ * @param limit Maximum number of recent records (default 50)
+ */
+export async function getDebugCommandHistory(
+  limit?: number,
+): Promise<DebugCommandHistory> {
+  return await invoke<DebugCommandHistory>(
+    "plugin:debug-tools|get_debug_command_history",
+    { limit },
+  );
+}
+
+/**
+ * Persist a validated debug snapshot. The backend fills in the timestamp,
+ * session id, build info and (unless overridden) the cached WebView state.
+ * Invalid payloads are rejected with field-level messages.
+ */
+export async function writeTypedDebugSnapshot(
+  payload: PartialDebugSnapshot,
+): Promise<DebugSnapshot> {
+  return await invoke<DebugSnapshot>(
+    "plugin:debug-tools|write_typed_debug_snapshot",
+    { payload },
+  );
+}
+
+/**
+ * Get error-level console entries per minute over the most recent windows
+ * (newest first). Backed by an in-memory counter; no log files are scanned.
+ * @param windows Number of windows to return (default: all retained)
+ */
+export async function getErrorRate(windows?: number): Promise<ErrorRateReport> {
+  return await invoke<ErrorRateReport>("plugin:debug-tools|get_error_rate", {
+    windows,
+  });
+}
+
+/**
+ * Subscribe to batched backend tracing events. Requires
+ * `forward_backend_logs` to be enabled in the plugin config.
+ * @param handler Called with each batch
+ */
+export async function onBackendLogBatch(
+  handler: (batch: BackendLogBatch) => void,
+): Promise<UnlistenFn> {
+  return await listen<BackendLogBatch>(
+    "debug-tools://backend-log-batch",
+    (event) => handler(event.payload),
+  );
+}
+
+/**
+ * Get session, build and renderer details (platform GPU info is cached per session).
+ */
+export async function getDebugEnvironment(): Promise<DebugEnvironment> {
+  return await invoke<DebugEnvironment>(
+    "plugin:debug-tools|get_debug_environment",
+  );
+}
+
+/**
+ * Run one capture and report how long each phase took, to tune which
+ * artifacts fit the latency budget on this device.
+ *
+ * @example
+ * ```typescript
+ * const timing = await benchmarkCapture({ includeDom: true });
+ * console.log(`total ${timing.total_ms.toFixed(1)}ms`);
+ * ```
+ */
+export async function benchmarkCapture(
+  options: BenchmarkCaptureOptions = {},
+): Promise<CaptureBenchmark> {
+  let domHtml: string | undefined;
+  let domSerializeMs: number | undefined;
+  if (options.includeDom) {
+    const started = performance.now();
+    domHtml = captureDOMHTML();
+    domSerializeMs = performance.now() - started;
+  }
+
+  const result = await invoke<CaptureBenchmark>(
+    "plugin:debug-tools|benchmark_capture",
+    {
+      request: {
+        dom_html: domHtml,
+        include_screenshot: options.includeScreenshot ?? false,
+        persist: options.persist ?? false,
+      },
+    },
+  );
+
+  return domSerializeMs === undefined
+    ? result
+    : { ...result, dom_serialize_ms: domSerializeMs };
+}
+
+export interface LogQuery {
+  /** Minimum level: "trace" | "debug" | "info" | "warn" | "error". */
+  min_level?: string;
+  /** Inclusive lower bound, milliseconds since epoch. */
+  since?: number;
+  /** Exclusive upper bound, milliseconds since epoch. */
+  until?: number;
+  /** Case-insensitive substring of the message. */
+  text?: string;
+  /** Keep the newest N matches (default 500). */
+  limit?: number;  /** Structured fields that must all match exactly. */
+  field_equals?: Record<string, unknown>;
+  /** Only entries that arrived by this pathway. */
+  ingest_source?: ConsoleSource;
+  /** Start at this moment's marker (an id from `markDebugMoment`). */
+  from_moment?: string;
+  /** End at this moment's marker, which is included. */
+  to_moment?: string;
+  /** W3C trace id in the `trace_id` field or a `traceparent` field. */
+  trace_id?: string;
+}
+
+export interface DebugMoment {
+  id: string;
+  label: string;
+  /** Milliseconds since epoch. */
+  timestamp: number;
+}
+
+export interface LogHistogramBucket {
+  start: number;
+  total: number;
+  trace: number;
+  debug: number;
+  info: number;
+  warn: number;
+  error: number;
+  unknown: number;
+  /** A negative timestamp was clamped or an entry went backwards in time. */
+  clock_skew_detected: boolean;
+}
+
+/**
+ * Query this session's persisted console logs, oldest first.
+ *
+ * @example
+ * ```typescript
+ * const errors = await queryConsoleLogs({ min_level: "error", limit: 50 });
+ * ```
+ */
+export async function queryConsoleLogs(
+  query: LogQuery = {},
+): Promise<ConsoleLogEntry[]> {
+  return await invoke<ConsoleLogEntry[]>(
+    "plugin:debug-tools|query_console_logs",
+    { query },
+  );
+}
+
+/**
+ * Count persisted console logs per level in fixed time buckets.
+ *
+ * @param bucketMs - Bucket width in milliseconds (default 60000)
+ */
+export async function getLogHistogram(
+  bucketMs?: number,
+  query?: LogQuery,
+): Promise<LogHistogramBucket[]> {
+  return await invoke<LogHistogramBucket[]>(
+    "plugin:debug-tools|get_log_histogram",
+    { bucketMs, query },
+  );
+}
+
+export interface LogFieldCount {
+  value: unknown;
+  count: number;
+}
+
+/**
+ * Count persisted console logs by the value of a structured field,
+ * most frequent first.
+ *
+ * @example
+ * ```typescript
+ * const errorsPerUser = await getLogFieldCounts("userId", { min_level: "error" });
+ * ```
+ */
+export async function getLogFieldCounts(
+  field: string,
+  query?: LogQuery,
+): Promise<LogFieldCount[]> {
+  return await invoke<LogFieldCount[]>(
+    "plugin:debug-tools|get_log_field_counts",
+    { field, query },
+  );
+}
+
+export interface NavigationSnapshotEvent {
+  webview_label: string;
+  url: string;
+  snapshot_timestamp: number;
+}
+
+/**
+ * Subscribe to automatic navigation snapshots. Requires
+ * `auto_snapshot_on_navigation` to be enabled in the plugin config.
+ * @param handler Called after each navigation snapshot is saved
+ */
+export async function onNavigationSnapshot(
+  handler: (event: NavigationSnapshotEvent) => void,
+): Promise<UnlistenFn> {
+  return await getCurrentWebviewWindow().listen<NavigationSnapshotEvent>(
+    "debug-tools://navigation-snapshot",
+    (event) => handler(event.payload),
+  );
+}
+
+/**
+ * Add a DOM snapshot to every automatic navigation snapshot of this webview.
+ *
+ * @example
+ * ```typescript
+ * const stop = await enableNavigationDomCapture();
+ * // later
+ * stop();
+ * ```
+ */
+export async function enableNavigationDomCapture(
+  options: DomCaptureOptions = {},
+): Promise<UnlistenFn> {
+  return await onNavigationSnapshot(() => {
+    captureDOMSnapshot(options).catch((error) => {
+      console.warn("[debug] navigation DOM capture failed", error);
+    });
+  });
+}
+
+export interface SnapshotCapturedEvent {
+  path: string;
+  timestamp: number;
+  label: string | null;
+  session_id: string | null;
+}
+
+/**
+ * Subscribe to completed full debug snapshots, from any window.
+ * @param handler Called after each snapshot is persisted
+ */
+export async function onSnapshotCaptured(
+  handler: (event: SnapshotCapturedEvent) => void,
+): Promise<UnlistenFn> {
+  return await getCurrentWebviewWindow().listen<SnapshotCapturedEvent>(
+    "debug-tools://snapshot-captured",
+    (event) => handler(event.payload),
+  );
+}
+
+export interface DomSearchQuery {
+  query: string;
+  /** Treat `query` as a regular expression (literal queries ignore case). */
+  regex?: boolean;
+  /** Snapshot timestamps in seconds; `until` is exclusive. */
+  since?: number;
+  until?: number;
+  /** Maximum number of matching files, newest first (default 20). */
+  limit?: number;
+}
+
+export interface DomSearchMatch {
+  offset: number;
+  context: string;
+}
+
+export interface DomSearchFileResult {
+  path: string;
+  timestamp: number;
+  url: string | null;
+  match_count: number;
+  matches: DomSearchMatch[];
+}
+
+export interface DomSearchReport {
+  results: DomSearchFileResult[];
+  files_scanned: number;
+  skipped: { path: string; reason: string }[];
+}
+
+/**
+ * Search saved DOM snapshots for text or a regular expression.
+ *
+ * @example
+ * ```typescript
+ * const { results } = await searchDomSnapshots({ query: "error-banner" });
+ * results.forEach((r) => console.log(r.path, r.match_count));
+ * ```
+ */
+export async function searchDomSnapshots(
+  query: DomSearchQuery,
+): Promise<DomSearchReport> {
+  return await invoke<DomSearchReport>(
+    "plugin:debug-tools|search_dom_snapshots",
+    { query },
+  );
+}
+
+export interface ExportFilteredLogsOptions {
+  /** Minimum level: "trace" | "debug" | "info" | "warn" | "error". */
+  level?: string;
+  /** Inclusive lower bound, milliseconds since epoch. */
+  from_ts?: number;
+  /** Exclusive upper bound, milliseconds since epoch. */
+  to_ts?: number;
+  /** Case-insensitive substring of the message. */
+  pattern?: string;
+  /** Start at this moment's marker (an id from `markDebugMoment`). */
+  from_moment?: string;
+  /** End at this moment's marker, which is included. */
+  to_moment?: string;
+  /**
+   * File name under `<log_dir>/exports/`; the format's extension (`.jsonl`,
+   * `.html`, `.csv` or `.json`) is added when missing.
+   */
+  output_name: string;
+  /**
+   * `html` writes a self-contained report colored by level, with a level
+   * filter. `csv` and `timeline` (Chrome trace event JSON) add a column per
+   * field named in the backend's `export_field_columns`.
+   */
+  format?: "jsonl" | "html" | "csv" | "timeline";
+  /**
+   * Rewrite stack frames to original file/line/name using the `.map` files
+   * in the backend's `sourcemap_dir`. Frames without a matching map are
+   * kept; resolved entries list the maps used in `fields.sourcemaps`.
+   */
+  resolve_stack_traces?: boolean;
+}
+
+export interface LogExportResult {
+  path: string;
+  entries: number;
+  /** Entries whose stack trace was rewritten through a source map. */
+  resolved_stack_traces: number;
+}
+
+/**
+ * Write the matching console logs to a new shareable `.jsonl` file, or a
+ * standalone `.html` report with `format: "html"`.
+ *
+ * @example
+ * ```typescript
+ * const { path } = await exportFilteredLogs({
+ *   level: "warn",
+ *   pattern: "checkout",
+ *   output_name: "checkout-bug",
+ * });
+ * ```
+ */
+export async function exportFilteredLogs(
+  options: ExportFilteredLogsOptions,
+): Promise<LogExportResult> {
+  return await invoke<LogExportResult>(
+    "plugin:debug-tools|export_filtered_logs",
+    { request: options },
+  );
+}
+
+export interface SelfTestCheck {
+  check: string;
+  passed: boolean;
+  detail: string;
+}
+
+/**
+ * Verify that logging, snapshot storage and every debug directory work on
+ * this machine, and that the frontend and backend clocks agree.
+ *
+ * @example
+ * ```typescript
+ * const failed = (await runSelfTest()).filter((c) => !c.passed);
+ * ```
+ */
+export async function runSelfTest(): Promise<SelfTestCheck[]> {
+  return await invoke<SelfTestCheck[]>("plugin:debug-tools|run_self_test", {
+    frontendNowMs: Date.now(),
+  });
+}
+
+export interface SnapshotListEntry {
+  path: string;
+  /** Unix timestamp (seconds) from the snapshot file name. */
+  timestamp: number;
+  size_bytes: number;
+  route?: string;
+  size_report?: CaptureSizeReport;
+  /** Written by the untyped `write_debug_snapshot`. */
+  legacy: boolean;
+  annotations?: Record<string, unknown>;
+}
+
+export interface SnapshotPage {
+  entries: SnapshotListEntry[];
+  total: number;
+}
+
+/** A saved snapshot as a panel lists it; see `listDebugSnapshots`. */
+export interface SnapshotSummary {
+  path: string;
+  /** Unix timestamp (seconds) from the snapshot file name. */
+  timestamp: number;
+  size_bytes: number;
+  legacy: boolean;
+  url: string | null;
+  title: string | null;
+  label: string | null;
+  console_log_count: number;
+  /** Whether the referenced screenshot file still exists. */
+  has_screenshot: boolean;
+  /** Whether the referenced DOM snapshot file still exists. */
+  has_dom_snapshot: boolean;
+  /** Set when the file could not be parsed; the other fields are empty. */
+  error?: string;
+}
+
+export interface ListSnapshotsOptions {
+  offset?: number;
+  /** Page size (default 50, max 500). */
+  limit?: number;
+  /** Timestamp order (default "desc"). */
+  sort?: "asc" | "desc";
+  /** Only snapshots captured on this route (see `captureRouteState`). */
+  route?: string;
+}
+
+/**
+ * List saved full debug snapshots one page at a time.
+ *
+ * @example
+ * ```typescript
+ * const first = await listSnapshotsPaged({ limit: 20 });
+ * const next = await listSnapshotsPaged({ offset: 20, limit: 20 });
+ * console.log(`${first.total} snapshots in total`);
+ * ```
+ */
+export async function listSnapshotsPaged(
+  options: ListSnapshotsOptions = {},
+): Promise<SnapshotPage> {
+  return await invoke<SnapshotPage>(
+    "plugin:debug-tools|list_snapshots_paged",
+    {
+      offset: options.offset,
+      limit: options.limit,
+      sort: options.sort,
+      route: options.route,
+    },
+  );
+}
+
+export interface ErrorFingerprintCount {
+  /** First line of the message with digit runs collapsed to `#`. */
+  fingerprint: string;
+  count: number;
+}
+
+export interface ErrorAlarmEvent {
+  /** Unix timestamp (milliseconds). */
+  fired_at: number;
+  window_secs: number;
+  threshold_count: number;
+  errors_in_window: number;
+  top_fingerprints: ErrorFingerprintCount[];
+}
+
+/**
+ * Subscribe to error alarms, fired when the configured number of errors
+ * lands inside the sliding window.
+ *
+ * @example
+ * ```typescript
+ * await onErrorAlarm((alarm) => {
+ *   console.warn(`${alarm.errors_in_window} errors`, alarm.top_fingerprints);
+ * });
+ * ```
+ */
+export async function onErrorAlarm(
+  handler: (event: ErrorAlarmEvent) => void,
+): Promise<UnlistenFn> {
+  return await listen<ErrorAlarmEvent>("debug-tools://error-alarm", (event) =>
+    handler(event.payload),
+  );
+}
+
+export interface ImportSkippedLine {
+  /** 1-based line number in the imported file. */
+  line: number;
+  reason: string;
+}
+
+export interface LogImportResult {
+  path: string;
+  source_label: string;
+  imported: number;
+  skipped: number;
+  /** The first skipped lines only; `skipped` has the full count. */
+  skipped_lines: ImportSkippedLine[];
+}
+
+export interface LogImportProgress {
+  path: string;
+  bytes_read: number;
+  total_bytes: number;
+  imported: number;
+  skipped: number;
+}
+
+/**
+ * Import an external JSONL log into the current session's console log.
+ * Each line needs a millisecond `timestamp`; entries are tagged with
+ * `fields.source` and the current session id. The file must be inside the
+ * plugin's log directory (for example its `imports/` folder).
+ *
+ * @example
+ * ```typescript
+ * const { base_dir } = await getLogDirectory();
+ * const result = await importConsoleLogs(`${base_dir}/imports/cli.jsonl`, "cli");
+ * console.log(`${result.imported} imported, ${result.skipped} skipped`);
+ * ```
+ */
+export async function importConsoleLogs(
+  path: string,
+  sourceLabel: string,
+): Promise<LogImportResult> {
+  return await invoke<LogImportResult>(
+    "plugin:debug-tools|import_console_logs",
+    { path, sourceLabel },
+  );
+}
+
+/**
+ * Subscribe to progress of large log imports.
+ * @param handler Called after each imported batch
+ */
+export async function onLogImportProgress(
+  handler: (progress: LogImportProgress) => void,
+): Promise<UnlistenFn> {
+  return await listen<LogImportProgress>(
+    "debug-tools://log-import-progress",
+    (event) => handler(event.payload),
+  );
+}
+
+/**
+ * Get the size breakdown of a full capture.
+ * @param captureId Snapshot file stem, e.g. `snapshot_1700000000`
+ */
+export async function getCaptureSizeReport(
+  captureId: string,
+): Promise<CaptureSizeReport> {
+  return await invoke<CaptureSizeReport>(
+    "plugin:debug-tools|get_capture_size_report",
+    { captureId },
+  );
+}
+
+export interface ClockSkewMeasurement {
+  frontend_timestamp: number;
+  backend_timestamp: number;
+  /** Backend minus frontend; positive means the frontend clock is behind. */
+  skew_ms: number;
+  within_tolerance: boolean;
+}
+
+/**
+ * Compare the frontend clock with the backend's. The result is kept for the
+ * session and embedded in later snapshots.
+ *
+ * @example
+ * ```typescript
+ * const { skew_ms, within_tolerance } = await checkClockSkew();
+ * if (!within_tolerance) console.warn(`clock skew: ${skew_ms}ms`);
+ * ```
+ */
+export async function checkClockSkew(): Promise<ClockSkewMeasurement> {
+  return await invoke<ClockSkewMeasurement>(
+    "plugin:debug-tools|check_clock_skew",
+    { frontendTimestamp: Date.now() },
+  );
+}
+
+/**
+ * Get the filesystem the log directory lives on and the write mode in use.
+ */
+export async function getStorageStatus(): Promise<StorageStatus> {
+  return await invoke<StorageStatus>("plugin:debug-tools|get_storage_status");
+}
+
+/**
+ * Get a plain-text summary of the current state (URL, title, viewport,
+ * platform, recent errors) for pasting into an issue or chat.
+ * @param errorCount Number of recent errors to include (default 5, max 50)
+ *
+ * @example
+ * ```typescript
+ * await navigator.clipboard.writeText(await captureTextSummary());
+ * ```
+ */
+export async function captureTextSummary(errorCount?: number): Promise<string> {
+  return await invoke<string>("plugin:debug-tools|capture_text_summary", {
+    errorCount,
+  });
+}
+
+export interface MergedBundle {
+  source: string;
+  /** Directory under `bundles/` holding this bundle's artifacts. */
+  namespace: string;
+  device_id: string;
+  session_id: string | null;
+  files: number;
+  console_entries: number;
+  corrupt_lines: number;
+  /** Timeline entries that carry `fields.annotation`. */
+  annotated_entries: number;
+  /** Lines of the bundle's `plugin_log/*.log` files. */
+  plugin_log_entries: number;
+}
+
+export interface SkippedBundle {
+  source: string;
+  reason: string;
+}
+
+export interface MergeReport {
+  out_dir: string;
+  created_at: number;
+  bundles: MergedBundle[];
+  skipped: SkippedBundle[];
+  timeline_path: string;
+  timeline_entries: number;
+  fingerprints_path: string;
+  fingerprints: number;
+  /** Timeline entries whose stack trace was rewritten through a source map. */
+  resolved_stack_traces: number;
+}
+
+export interface MergeProgress {
+  bundle: number;
+  total: number;
+  source: string;
+}
+
+/**
+ * Merge debug bundles from several devices into one directory with a unified
+ * timeline and a cross-device error summary. Unreadable bundles are skipped
+ * and listed in the report.
+ * @param paths Bundle directories or `.zip` archives of them
+ * @param outDir Output directory under `<log_dir>/merged` (defaults to `<log_dir>/merged/merged_<ts>`)
+ * @param resolveStackTraces Rewrite timeline stack traces through the
+ *   backend's `sourcemap_dir`
+ *
+ * @example
+ * ```typescript
+ * const report = await mergeDebugBundles(["/logs/alice", "/logs/bob"]);
+ * console.log(report.timeline_path, report.skipped);
+ * ```
+ */
+export async function mergeDebugBundles(
+  paths: string[],
+  outDir?: string,
+  resolveStackTraces?: boolean,
+): Promise<MergeReport> {
+  return await invoke<MergeReport>("plugin:debug-tools|merge_debug_bundles", {
+    paths,
+    outDir,
+    resolveStackTraces,
+  });
+}
+
+export type EncryptedBundleError =
+  | { code: "empty_passphrase" }
+  | { code: "not_encrypted" }
+  | { code: "unsupported_version"; version: number }
+  | { code: "unsupported_parameters" }
+  /** Wrong passphrase, or the file was corrupted or edited. */
+  | { code: "decrypt" }
+  | { code: "kdf"; message: string }
+  | { code: "io"; message: string };
+
+export interface EncryptedBundleExport {
+  path: string;
+  /** Artifacts in the bundle, not counting its manifest. */
+  files: number;
+  zip_bytes: number;
+  encrypted_bytes: number;
+}
+
+export interface ImportedBundle {
+  path: string;
+  /** Archive entries, the manifest included. */
+  entries: number;
+  zip_bytes: number;
+}
+
+/**
+ * Zip the plugin's artifacts into a bundle and encrypt it with a key
+ * derived from `passphrase` (Argon2id, AES-256-GCM), written as
+ * `exports/debug_bundle_<ts>.zip.enc`. Rejects with an
+ * {@link EncryptedBundleError}.
+ *
+ * @example
+ * ```typescript
+ * const { path } = await exportEncryptedBundle(passphrase);
+ * ```
+ */
+export async function exportEncryptedBundle(
+  passphrase: string,
+): Promise<EncryptedBundleExport> {
+  return await invoke<EncryptedBundleExport>(
+    "plugin:debug-tools|export_encrypted_bundle",
+    { passphrase },
+  );
+}
+
+/**
+ * Decrypt a bundle from `exportEncryptedBundle` into a `.zip` that
+ * `mergeDebugBundles` accepts. A wrong passphrase rejects with
+ * `code: "decrypt"` and writes nothing.
+ * @param path The `.zip.enc` file
+ * @param outDir Output directory under `<log_dir>/imports` (the default)
+ */
+export async function importEncryptedBundle(
+  path: string,
+  passphrase: string,
+  outDir?: string,
+): Promise<ImportedBundle> {
+  return await invoke<ImportedBundle>(
+    "plugin:debug-tools|import_encrypted_bundle",
+    { path, passphrase, outDir },
+  );
+}
+
+/**
+ * Listen for progress while `mergeDebugBundles` runs, one event per bundle.
+ */
+export async function onMergeProgress(
+  handler: (progress: MergeProgress) => void,
+): Promise<UnlistenFn> {
+  return await listen<MergeProgress>("debug-tools://merge-progress", (event) =>
+    handler(event.payload),
+  );
+}
+
+/**
+ * Load a saved snapshot. Untyped legacy snapshots are returned wrapped, with
+ * the raw payload under `extensions.legacy_payload` and `schema_version: 0`.
+ * @param captureId Snapshot file stem, e.g. `snapshot_1700000000000` or
+ * `tauri_debug_snapshot_1700000000`
+ */
+export async function loadDebugSnapshot(
+  captureId: string,
+): Promise<DebugSnapshot> {
+  return await invoke<DebugSnapshot>("plugin:debug-tools|load_debug_snapshot", {
+    captureId,
+  });
+}
+
+/**
+ * Attach a structured annotation to a saved snapshot, replacing any value
+ * already stored under `key`.
+ * @param path Snapshot file path inside the log directory
+ *
+ * @example
+ * ```typescript
+ * await annotateSnapshot(entry.path, "severity", "high");
+ * await annotateSnapshot(entry.path, "assignee", { name: "sam" });
+ * ```
+ */
+export async function annotateSnapshot(
+  path: string,
+  key: string,
+  value: unknown,
+): Promise<SnapshotListEntry> {
+  return await invoke<SnapshotListEntry>(
+    "plugin:debug-tools|annotate_snapshot",
+    { path, key, value },
+  );
+}
+
+export interface RecordingStatus {
+  dir: string;
+  fps: number;
+  max_frames: number;
+  started_at: number;
+}
+
+export interface RecordingResult {
+  dir: string;
+  frames: number;
+  /** Ticks whose capture failed or timed out. */
+  dropped_frames: number;
+  duration_ms: number;
+  /** Animated PNG of the frames, when `assemble` was set. */
+  animation_path: string | null;
+}
+
+/**
+ * Start capturing the window into numbered PNG frames under
+ * `recordings/<ts>/`. Stops by itself after `maxFrames`; frame rate and count
+ * are clamped to `max_recording_fps` / `max_recording_frames`.
+ * @param fps Frames per second (default 5)
+ * @param maxFrames Frame limit (default 100)
+ *
+ * @example
+ * ```typescript
+ * await startWindowRecording(10, 50);
+ * triggerTransition();
+ * const { animation_path } = await stopWindowRecording(true);
+ * ```
+ */
+export async function startWindowRecording(
+  fps?: number,
+  maxFrames?: number,
+): Promise<RecordingStatus> {
+  return await invoke<RecordingStatus>(
+    "plugin:debug-tools|start_window_recording",
+    { fps, maxFrames },
+  );
+}
+
+/**
+ * Stop the running window recording.
+ * @param assemble Also write the frames as an animated PNG (`recording.png`)
+ */
+export async function stopWindowRecording(
+  assemble?: boolean,
+): Promise<RecordingResult> {
+  return await invoke<RecordingResult>(
+    "plugin:debug-tools|stop_window_recording",
+    { assemble },
+  );
+}
+
+/**
+ * Console log batching settings (the adaptive flush policy's intervals and
+ * thresholds, `flush_batch_size`, `max_queued_entries`) and how many
+ * entries were dropped this session because the queue or a batch
+ * overflowed.
+ *
+ * @example
+ * ```typescript
+ * const { dropped_entries } = await getLogStats();
+ * if (dropped_entries > 0) console.warn(`${dropped_entries} logs were lost`);
+ * ```
+ */
+export async function getLogStats(): Promise<LogPipelineStats> {
+  return await invoke<LogPipelineStats>("plugin:debug-tools|get_log_stats");
+}
+
+export interface DeprecatedSurfaceUse {
+  /** Command name, with the legacy behaviour in parentheses if needed. */
+  surface: string;
+  replacement: string;
+  first_used_at: number;
+  last_used_at: number;
+  calls: number;
+}
+
+export interface DeprecationReport {
+  compat_mode: boolean;
+  surfaces: DeprecatedSurfaceUse[];
+}
+
+/**
+ * List the legacy command surfaces used this session and what replaces
+ * each, to plan a migration off `compat_mode`.
+ *
+ * @example
+ * ```typescript
+ * const { surfaces } = await getDeprecationReport();
+ * for (const s of surfaces) console.warn(`${s.surface} -> ${s.replacement}`);
+ * ```
+ */
+export async function getDeprecationReport(): Promise<DeprecationReport> {
+  return await invoke<DeprecationReport>(
+    "plugin:debug-tools|get_deprecation_report",
+  );
+}
+
+export interface ScreenshotDiff {
+  /** Changed pixels highlighted over a faded copy of the first screenshot. */
+  diff_path: string;
+  width: number;
+  height: number;
+  changed_pixels: number;
+  total_pixels: number;
+  changed_percent: number;
+  /** The screenshots differ in size; non-overlapping pixels count as changed. */
+  size_mismatch: boolean;
+}
+
+/**
+ * Diff the screenshots stored on two saved snapshots.
+ * @param pathA Snapshot file under the log directory
+ * @param pathB Snapshot file under the log directory
+ *
+ * @example
+ * ```typescript
+ * const { entries } = await listSnapshotsPaged({ limit: 2 });
+ * const diff = await diffSnapshotScreenshots(entries[1].path, entries[0].path);
+ * console.log(`${diff.changed_percent.toFixed(1)}% changed`, diff.diff_path);
+ * ```
+ */
+export async function diffSnapshotScreenshots(
+  pathA: string,
+  pathB: string,
+): Promise<ScreenshotDiff> {
+  return await invoke<ScreenshotDiff>(
+    "plugin:debug-tools|diff_snapshot_screenshots",
+    { pathA, pathB },
+  );
+}
+
+export interface LegalHolds {
+  paths: string[];
+  sessions: string[];
+}
+
+/**
+ * Place artifacts under legal hold, or release them. Held files are skipped
+ * by retention and `clear_debug_log_files_command`, and deleting one
+ * explicitly fails. Holding a session keeps its snapshots and what they
+ * reference. Holds are stored in `holds.json` in the log directory.
+ *
+ * @example
+ * ```typescript
+ * await setLegalHold({ sessionId: "a1b2c3" }, true);
+ * await setLegalHold({ paths: [snapshotPath] }, false);
+ * ```
+ */
+export async function setLegalHold(
+  target: { paths?: string[]; sessionId?: string },
+  held: boolean,
+): Promise<LegalHolds> {
+  return await invoke<LegalHolds>("plugin:debug-tools|set_legal_hold", {
+    paths: target.paths,
+    sessionId: target.sessionId,
+    held,
+  });
+}
+
+export interface ShutdownSummary {
+  reason: "command" | "exit";
+  started_at: number;
+  duration_ms: number;
+  forwarded_events: number;
+  retention_removed: number | null;
+  export: LogExportResult | null;
+  abandoned_dom_uploads: number;
+  tracing_flushed: boolean;
+  failures: string[];
+}
+
+/**
+ * Run the shutdown sequence: pending console logs are sent first, then the
+ * backend flushes forwarded log events, runs a final retention pass,
+ * optionally exports all logs to `exports/`, and flushes tracing. The
+ * sequence runs once; later calls (and the exit hook) return the first
+ * summary.
+ *
+ * @example
+ * ```typescript
+ * const summary = await shutdownDebugTools({ exportLogs: true });
+ * console.log(summary.export?.path, summary.failures);
+ * ```
+ */
+export async function shutdownDebugTools(
+  options: { exportLogs?: boolean } = {},
+): Promise<ShutdownSummary> {
+  await consoleLogger.flush();
+  return await invoke<ShutdownSummary>(
+    "plugin:debug-tools|shutdown_debug_tools",
+    { exportLogs: options.exportLogs },
+  );
+}
+
+/** A compared section, or why it could not be compared. */
+export type SectionComparison<T> =
+  | ({ status: "available" } & T)
+  | { status: "unavailable"; reason: string };
+
+export interface FieldChange {
+  /** Dotted path into the compared value, e.g. `active_profile.name`. */
+  field: string;
+  a: unknown;
+  b: unknown;
+}
+
+export interface FingerprintCount {
+  fingerprint: string;
+  count_a: number;
+  count_b: number;
+  /** First message seen with this fingerprint. */
+  sample: string;
+}
+
+export interface SessionSide {
+  session_id: string;
+  snapshots: number;
+  first_snapshot_at: number | null;
+  last_snapshot_at: number | null;
+}
+
+export interface SessionSummary {
+  session_id: string;
+  snapshots: number;
+  first_snapshot_at: number | null;
+  last_snapshot_at: number | null;
+  start_config_path: string | null;
+  /** Settings in force when the session started. */
+  start_config: EffectiveConfig | null;
+}
+
+export interface SessionComparison {
+  session_a: SessionSide;
+  session_b: SessionSide;
+  build_info: SectionComparison<{ changed: FieldChange[] }>;
+  effective_config: SectionComparison<{ changed: FieldChange[] }>;
+  error_fingerprints: SectionComparison<{
+    /** Only in session B. */
+    new: FingerprintCount[];
+    /** Only in session A. */
+    resolved: FingerprintCount[];
+    persisting: FingerprintCount[];
+  }>;
+  log_volume: SectionComparison<{
+    levels: { level: string; a: number; b: number }[];
+  }>;
+  latency: SectionComparison<{
+    operations: {
+      operation: string;
+      avg_latency_us_a: number;
+      avg_latency_us_b: number;
+      /** Relative to A; `null` when A averaged zero. */
+      change_percent: number | null;
+    }[];
+  }>;
+  /** Set when `writeMarkdown` was requested. */
+  markdown_path: string | null;
+}
+
+/**
+ * Compare two sessions by the snapshots they left in the log directory:
+ * build info and effective config changes, new/resolved/persisting error
+ * fingerprints, log volume per level, and write latency. Sections one
+ * session has no data for come back as `unavailable`.
+ *
+ * @example
+ * ```typescript
+ * const report = await compareDebugSessions(lastGood, current, {
+ *   writeMarkdown: true,
+ * });
+ * if (report.error_fingerprints.status === "available") {
+ *   console.log(report.error_fingerprints.new);
+ * }
+ * ```
+ */
+export async function compareDebugSessions(
+  sessionA: string,
+  sessionB: string,
+  options: { writeMarkdown?: boolean } = {},
+): Promise<SessionComparison> {
+  return await invoke<SessionComparison>(
+    "plugin:debug-tools|compare_debug_sessions",
+    { sessionA, sessionB, writeMarkdown: options.writeMarkdown },
+  );
+}
+
+/**
+ * Sessions that left snapshots or config history in the log directory,
+ * newest first, each with the config in force when it started.
+ *
+ * @example
+ * ```typescript
+ * for (const session of await listDebugSessions()) {
+ *   console.log(session.session_id, session.start_config?.active_profile.name);
+ * }
+ * ```
+ */
+export async function listDebugSessions(): Promise<SessionSummary[]> {
+  return await invoke<SessionSummary[]>(
+    "plugin:debug-tools|list_debug_sessions",
+  );
+}
+
+export type ArtifactFormat =
+  | "jsonl"
+  | "json"
+  | "html"
+  | "png"
+  | "svg"
+  | "markdown"
+  | "text";
+
+export interface ArtifactDescription {
+  name: string;
+  /** Relative to the log directory; `{placeholders}` vary per file. */
+  directory: string;
+  filename_pattern: string;
+  format: ArtifactFormat;
+  /** JSON Schema of the file, or of one line for `jsonl`. */
+  schema: Record<string, unknown> | null;
+  embedded_metadata: {
+    prefix: string;
+    suffix: string;
+    schema: Record<string, unknown>;
+  } | null;
+  description: string;
+}
+
+export interface ArtifactSchemaManifest {
+  /** Bumped whenever any artifact or schema changes. */
+  manifest_version: number;
+  json_schema: string;
+  artifacts: ArtifactDescription[];
+  /** Command results and events that are not files of their own. */
+  records: {
+    name: string;
+    source: string;
+    schema: Record<string, unknown>;
+    description: string;
+  }[];
+  /** Shared definitions; `$ref`s point to `#/schemas/<name>`. */
+  schemas: Record<string, Record<string, unknown>>;
+}
+
+/**
+ * Get the versioned description of every artifact the plugin writes:
+ * directory, filename pattern, format and JSON Schema.
+ *
+ * @example
+ * ```typescript
+ * const manifest = await getArtifactSchemaManifest();
+ * const logs = manifest.artifacts.find((a) => a.name === "console_log");
+ * console.log(manifest.manifest_version, logs?.filename_pattern);
+ * ```
+ */
+export async function getArtifactSchemaManifest(): Promise<ArtifactSchemaManifest> {
+  return await invoke<ArtifactSchemaManifest>(
+    "plugin:debug-tools|get_artifact_schema_manifest",
+  );
+}
+
+/**
+ * Flush console logs now instead of waiting for the adaptive interval:
+ * this webview's queue first, then forwarded backend events and the queues
+ * of every other webview.
+ *
+ * @example
+ * ```typescript
+ * await flushDebugLogs();
+ * const logs = await queryConsoleLogs({ limit: 10 });
+ * ```
+ */
+export async function flushDebugLogs(): Promise<void> {
+  await consoleLogger.flush();
+  await invoke("plugin:debug-tools|flush_debug_logs");
+}
+
+/**
+ * Let clear and retention manage files the plugin did not record creating,
+ * such as logs written by versions before files were tracked. Without
+ * `paths`, every file in the managed directories named like a plugin
+ * artifact is claimed.
+ *
+ * @example
+ * ```typescript
+ * const { claimed_paths } = await claimExistingFiles();
+ * console.log(`${claimed_paths.length} legacy files are now managed`);
+ * ```
+ */
+export async function claimExistingFiles(
+  paths?: string[],
+): Promise<ClaimFilesResult> {
+  return await invoke<ClaimFilesResult>(
+    "plugin:debug-tools|claim_existing_files",
+    { paths },
+  );
+}
+
+/**
+ * Submit a final beacon through IPC and wait for it to be written, for
+ * teardown code that can still await. Entries whose `seq` the same `source`
+ * already delivered are skipped. The collector's own beacon on `pagehide`
+ * goes through the `debug-tools` protocol instead.
+ *
+ * @example
+ * ```typescript
+ * const record = await submitFinalBeacon({
+ *   source: "settings-window",
+ *   reason: "close",
+ *   entries: [],
+ *   state: {
+ *     url: location.href,
+ *     title: document.title,
+ *     pending_entries: 0,
+ *     in_flight_entries: 0,
+ *     dropped_entries: 0,
+ *     truncated_entries: 0,
+ *   },
+ * });
+ * console.log(`${record.written_entries} entries written`);
+ * ```
+ */
+export async function submitFinalBeacon(
+  beacon: FinalBeacon,
+): Promise<FinalBeaconRecord> {
+  return await invoke<FinalBeaconRecord>(
+    "plugin:debug-tools|submit_final_beacon",
+    { beacon },
+  );
+}
+
+/**
+ * The settings in force, including the privacy preset and the flags it
+ * resolved to.
+ *
+ * @example
+ * ```typescript
+ * const { privacy } = await getDebugConfig();
+ * console.log(privacy?.preset, privacy?.effective.capture_dom);
+ * ```
+ */
+export async function getDebugConfig(): Promise<EffectiveConfig> {
+  return await invoke<EffectiveConfig>("plugin:debug-tools|get_debug_config");
+}
+
+/**
+ * Switch the privacy preset at runtime. The effective flags are resolved
+ * again; `privacy_overrides` from the config keep winning. `null` returns to
+ * the individual config flags.
+ *
+ * @example
+ * ```typescript
+ * const status = await setPrivacyPreset("strict");
+ * console.log(status.effective.log_sample_rate); // 0.1
+ * ```
+ */
+export async function setPrivacyPreset(
+  preset: PrivacyPreset | null,
+): Promise<PrivacyStatus> {
+  return await invoke<PrivacyStatus>("plugin:debug-tools|set_privacy_preset", {
+    preset,
+  });
+}
+
+/** Omitted fields keep their value; `privacy_preset: null` clears the preset. */
+export interface DebugConfigUpdate {
+  active_profile?: string;
+  privacy_preset?: PrivacyPreset | null;
+}
+
+/**
+ * Change several runtime settings at once. One `config_history/` entry is
+ * written for the whole update, and nothing is applied when the profile is
+ * unknown.
+ *
+ * @example
+ * ```typescript
+ * const config = await setDebugConfig({
+ *   active_profile: "visual",
+ *   privacy_preset: "strict",
+ * });
+ * ```
+ */
+export async function setDebugConfig(
+  update: DebugConfigUpdate,
+): Promise<EffectiveConfig> {
+  return await invoke<EffectiveConfig>("plugin:debug-tools|set_debug_config", {
+    update,
+  });
+}
+
+/**
+ * Salvage a torn console log. Entries that parse are copied into a
+ * `.repaired.jsonl` next to it, which queries read from then on; the
+ * original is left untouched. Logs of earlier sessions are checked on
+ * startup without calling this.
+ *
+ * @example
+ * ```typescript
+ * const { frontend_log } = await getLogDirectory();
+ * const report = await repairConsoleLog(frontend_log);
+ * console.log(`${report.discarded_lines} lines discarded`);
+ * ```
+ */
+export async function repairConsoleLog(
+  path: string,
+): Promise<LogRepairReport> {
+  return await invoke<LogRepairReport>(
+    "plugin:debug-tools|repair_console_log",
+    { path },
+  );
+}
+
+/**
+ * Annotate an entry of this session's console log. The log file is not
+ * changed; annotating the same `seq` again replaces the annotation. Notes
+ * are capped at 2000 characters, tags at 64, and a session at 500
+ * annotated entries. Rejects with an {@link AnnotationError}.
+ *
+ * @example
+ * ```typescript
+ * const [entry] = await queryConsoleLogs({ text: "checkout", limit: 1 });
+ * const seq = entry.fields?.log_seq as number;
+ * await annotateLogEntry(seq, "Starts the failing flow", "relevant");
+ * ```
+ */
+export async function annotateLogEntry(
+  seq: number,
+  note: string,
+  tag?: string,
+): Promise<LogAnnotation> {
+  return await invoke<LogAnnotation>("plugin:debug-tools|annotate_log_entry", {
+    seq,
+    note,
+    tag,
+  });
+}
+
+/**
+ * This session's annotations in `seq` order, optionally only those made at
+ * or after `since` (milliseconds since epoch).
+ *
+ * @example
+ * ```typescript
+ * const annotations = await listLogAnnotations();
+ * console.log(annotations.map((a) => `${a.seq}: ${a.note}`));
+ * ```
+ */
+export async function listLogAnnotations(
+  since?: number,
+): Promise<LogAnnotation[]> {
+  return await invoke<LogAnnotation[]>(
+    "plugin:debug-tools|list_log_annotations",
+    { since },
+  );
+}
+
+/**
+ * How long each phase of the plugin's setup took, which fallbacks it took
+ * and the thread it ran on. The session's first snapshot embeds the same
+ * report as `system_info.plugin_init`.
+ *
+ * @example
+ * ```typescript
+ * const report = await getPluginInitReport();
+ * const slowest = [...report.phases].sort(
+ *   (a, b) => b.duration_ms - a.duration_ms,
+ * )[0];
+ * console.log(`${slowest.name}: ${slowest.duration_ms.toFixed(1)}ms`);
+ * ```
+ */
+export async function getPluginInitReport(): Promise<PluginInitReport> {
+  return await invoke<PluginInitReport>(
+    "plugin:debug-tools|get_plugin_init_report",
+  );
+}
+
+/**
+ * Subscribe to disk usage warnings, sent once each time the debug directory
+ * crosses 70% or 90% of its limit.
+ *
+ * @example
+ * ```typescript
+ * await onDiskUsageWarning(async ({ suggested_cleanup }) => {
+ *   if (suggested_cleanup) {
+ *     await invoke(
+ *       `plugin:debug-tools|${suggested_cleanup.command}`,
+ *       suggested_cleanup.args,
+ *     );
+ *   }
+ * });
+ * ```
+ */
+export async function onDiskUsageWarning(
+  handler: (event: DiskUsageWarning) => void,
+): Promise<UnlistenFn> {
+  return await listen<DiskUsageWarning>(
+    "debug-tools://disk-usage-warning",
+    (event) => handler(event.payload),
+  );
+}
+
+/**
+ * Capture part of a window, cropped in the backend before it is saved to
+ * the screenshots directory. Rects partly outside the window are clamped.
+ * @param rect Region of the window's content area
+ * @param options `logical: false` takes `rect` in physical pixels
+ *
+ * @example
+ * ```typescript
+ * const { path, region } = await captureDebugScreenshotRegion({
+ *   x: 0,
+ *   y: 0,
+ *   width: 320,
+ *   height: 200,
+ * });
+ * ```
+ */
+export async function captureDebugScreenshotRegion(
+  rect: ScreenshotRect,
+  options: { windowLabel?: string; logical?: boolean } = {},
+): Promise<RegionScreenshot> {
+  return await invoke<RegionScreenshot>(
+    "plugin:debug-tools|capture_debug_screenshot_region",
+    {
+      windowLabel: options.windowLabel ?? getCurrentWebviewWindow().label,
+      rect,
+      logical: options.logical,
+    },
+  );
+}
+
+/**
+ * Answer the backend's element rect requests from this window, so
+ * `capture_element_screenshot` works when called from Rust or an agent.
+ */
+export async function registerElementRectHandler(): Promise<void> {
+  await registerDebugCommandHandler("debug-tools:element-rect", (payload) => {
+    const { selector } = payload as { selector: string };
+    const element = document.querySelector(selector);
+    if (!element) {
+      return null;
+    }
+    const rect = element.getBoundingClientRect();
+    return {
+      x: rect.left,
+      y: rect.top,
+      width: rect.width,
+      height: rect.height,
+    };
+  });
+}
+
+/**
+ * Capture the element matching `selector`, cropped to its bounding rect.
+ * The selector and rect are recorded in the screenshot's metadata.
+ *
+ * @example
+ * ```typescript
+ * const shot = await captureElementScreenshot("#checkout-form");
+ * console.log(shot.region.crop);
+ * ```
+ */
+export async function captureElementScreenshot(
+  selector: string,
+  timeoutMs?: number,
+): Promise<RegionScreenshot> {
+  await registerElementRectHandler();
+  return await invoke<RegionScreenshot>(
+    "plugin:debug-tools|capture_element_screenshot",
+    {
+      windowLabel: getCurrentWebviewWindow().label,
+      selector,
+      timeoutMs,
+    },
+  );
+}
+
+/**
+ * Read the newest lines of tauri-plugin-log's file (`debug.log` by default)
+ * next to the debug-tools directory, oldest first.
+ * @param lines Number of lines (default 200, at most 10000)
+ *
+ * @example
+ * ```typescript
+ * const errors = (await tailPluginLog(500)).filter(
+ *   (line) => line.level === "error",
+ * );
+ * ```
+ */
+export async function tailPluginLog(lines?: number): Promise<PluginLogLine[]> {
+  return await invoke<PluginLogLine[]>("plugin:debug-tools|tail_plugin_log", {
+    lines,
+  });
+}
+
+/**
+ * Stamp a named moment into the console log, the backend log and the next
+ * full snapshot, so artifacts can be aligned on it. Pass the returned id as
+ * `from_moment` / `to_moment` to slice queries and exports.
+ * @param label Short description, such as "REPRO START"
+ *
+ * @example
+ * ```typescript
+ * const start = await markDebugMoment("REPRO START");
+ * // ...reproduce the bug...
+ * const end = await markDebugMoment("REPRO END");
+ * const logs = await queryConsoleLogs({
+ *   from_moment: start.id,
+ *   to_moment: end.id,
+ * });
+ * ```
+ */
+export async function markDebugMoment(label: string): Promise<DebugMoment> {
+  return await invoke<DebugMoment>("plugin:debug-tools|mark_debug_moment", {
+    label,
+  });
+}
+
+/**
+ * Read back the console entries persisted this session, for an in-app
+ * panel. Unlike `getConsoleLogs`, this includes entries from before a reload.
+ * @param limit Number of entries (default 200, at most 10000)
+ * @param offset Newest entries to skip first, for paging back
+ *
+ * @example
+ * ```typescript
+ * const latest = await readConsoleLogs(50);
+ * const previous = await readConsoleLogs(50, 50);
+ * ```
+ */
+export async function readConsoleLogs(
+  limit?: number,
+  offset?: number,
+): Promise<ConsoleLogTail> {
+  return await invoke<ConsoleLogTail>("plugin:debug-tools|get_console_logs", {
+    limit,
+    offset,
+  });
+}
+
+/**
+ * List saved snapshots newest first, with the URL, title and console log
+ * count read from each file. Unreadable files are listed with `error` set.
+ * @param limit Page size (default 50, at most 500)
+ * @param beforeTimestamp Only snapshots older than this (seconds)
+ *
+ * @example
+ * ```typescript
+ * const page = await listDebugSnapshots(20);
+ * const older = await listDebugSnapshots(20, page.at(-1)?.timestamp);
+ * ```
+ */
+export async function listDebugSnapshots(
+  limit?: number,
+  beforeTimestamp?: number,
+): Promise<SnapshotSummary[]> {
+  return await invoke<SnapshotSummary[]>(
+    "plugin:debug-tools|list_debug_snapshots",
+    { limit, beforeTimestamp },
+  );
+}
+
+/**
+ * Capture a window into the debug-tools screenshots directory. The returned
+ * path can be passed as `screenshot_path` to `capture_full_debug_state`.
+ * Rejects while the screenshots plugin is still registering after startup.
+ * @param label Window label (default "main")
+ *
+ * @example
+ * ```typescript
+ * const path = await captureScreenshot("settings");
+ * ```
+ */
+export async function captureScreenshot(label?: string): Promise<string> {
+  return await invoke<string>("plugin:debug-tools|capture_screenshot", {
+    label,
+  });
+}
+
+/**
+ * Clear the recorded strict mode violations, e.g. between CI tests.
+ * @returns How many violations were cleared
+ *
+ * @example
+ * ```typescript
+ * afterEach(async () => {
+ *   expect(await clearStrictViolations()).toBe(0);
+ * });
+ * ```
+ */
+export async function clearStrictViolations(): Promise<number> {
+  return await invoke<number>("plugin:debug-tools|clear_strict_violations");
+}
+
+/**
+ * The violation a command rejected with under strict mode, or `null` for
+ * any other error.
+ *
+ * @example
+ * ```typescript
+ * try {
+ *   await checkClockSkew();
+ * } catch (error) {
+ *   const violation = parseStrictViolation(error);
+ *   if (violation) console.error(violation.non_strict_behavior);
+ * }
+ * ```
+ */
+export function parseStrictViolation(error: unknown): StrictViolation | null {
+  if (typeof error !== "string") return null;
+  try {
+    const parsed = JSON.parse(error);
+    return parsed?.code === "strict_violation" ? parsed : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Load the saved `snapshot_{timestamp}.json`, e.g. to display a snapshot
+ * whose timestamp the webview kept. Rejects with a
+ * {@link SnapshotReadError}.
+ * @param timestamp The snapshot's `timestamp`
+ *
+ * @example
+ * ```typescript
+ * const snapshot = await readDebugSnapshot(saved.timestamp);
+ * ```
+ */
+export async function readDebugSnapshot(
+  timestamp: number,
+): Promise<DebugSnapshot> {
+  return await invoke<DebugSnapshot>("plugin:debug-tools|read_debug_snapshot", {
+    timestamp,
+  });
+}
@@ -16,8 +16,51 @@ export interface DomSnapshotMetadata {
   title: string;
   timestamp: number;
   viewport: ViewportInfo;
+  shadow_dom_included?: boolean;
+  replacement_chars?: number;
 }
 
+export interface DomCaptureOptions {
+  /**
+   * Serialize open shadow roots (recursively) as declarative
+   * `<template shadowrootmode>` elements. Heavier, but needed for
+   * web-component-based apps whose content lives in shadow trees.
+   */
+  includeShadowDom?: boolean;
+}
+
+export interface DomSnapshotOptions extends DomCaptureOptions {
+  /**
+   * Documents longer than this (in UTF-16 code units) are sent in chunks
+   * instead of one IPC message. Defaults to 4 MiB; `Infinity` always sends
+   * them in one message.
+   */
+  chunkThreshold?: number;
+  /** Chunk length in UTF-16 code units. Defaults to 1 MiB. */
+  chunkSize?: number;
+}
+
+export interface DomChunkAck {
+  seq: number;
+  /** The chunk had already been received; nothing was written. */
+  duplicate: boolean;
+  received_chunks: number;
+  received_bytes: number;
+}
+
+export interface DomUploadMetadata {
+  url: string;
+  title: string;
+  viewport_width: number;
+  viewport_height: number;
+  shadow_dom_included?: boolean;
+  /** Chunks sent; without it every chunk up to the highest `seq` counts. */
+  total_chunks?: number;
+}
+
+const DEFAULT_CHUNK_THRESHOLD = 4 * 1024 * 1024;
+const DEFAULT_CHUNK_SIZE = 1024 * 1024;
+
 export interface DomSnapshotResult {
   path: string;
   metadata: DomSnapshotMetadata;
@@ -29,15 +72,78 @@ export interface LogDirectoryInfo {
   backend_log: string;
   screenshot_dir: string;
   dom_snapshot_dir: string;
+  /** tauri-plugin-log's file, when `plugin_log_file_name` is set. */
+  plugin_log: string | null;
+  /** Where console logs go; `base_dir` unless `console_log_root` is set. */
+  console_log_dir: string;
+  /** Where snapshots go; `base_dir` unless `snapshot_root` is set. */
+  snapshot_dir: string;
+  /** Where network logs go; `base_dir` unless `network_log_root` is set. */
+  network_log_dir: string;
+  config_history_dir: string;
+  recording_dir: string;
+  export_dir: string;
+  /** Where annotations go; `base_dir` unless `annotation_root` is set. */
+  annotation_dir: string;
+  /** Path of `holds.json`. */
+  legal_holds: string;
+  /** Config names of the artifact root overrides in effect. */
+  overridden_roots: string[];
+}
+
+function cloneWithShadowRoots(node: Node): Node {
+  if (!(node instanceof Element) || node instanceof HTMLTemplateElement) {
+    return node.cloneNode(true);
+  }
+
+  const clone = node.cloneNode(false) as Element;
+
+  if (node.shadowRoot) {
+    const template = document.createElement("template");
+    template.setAttribute("shadowrootmode", node.shadowRoot.mode);
+    for (const child of Array.from(node.shadowRoot.childNodes)) {
+      template.content.appendChild(cloneWithShadowRoots(child));
+    }
+    clone.appendChild(template);
+  }
+
+  for (const child of Array.from(node.childNodes)) {
+    clone.appendChild(cloneWithShadowRoots(child));
+  }
+
+  return clone;
+}
+
+// Lone UTF-16 surrogates cannot be encoded as JSON for the IPC call, so they
+// are replaced with U+FFFD (counted by the backend as `replacement_chars`).
+function toWellFormed(text: string): string {
+  let result = "";
+  let start = 0;
+  for (let i = 0; i < text.length; i++) {
+    const code = text.charCodeAt(i);
+    if (code < 0xd800 || code > 0xdfff) continue;
+    const next = text.charCodeAt(i + 1);
+    if (code <= 0xdbff && next >= 0xdc00 && next <= 0xdfff) {
+      i++;
+      continue;
+    }
+    result += `${text.slice(start, i)}\ufffd`;
+    start = i + 1;
+  }
+  return start === 0 ? text : result + text.slice(start);
 }
 
 /**
  * Capture full DOM as HTML string
  */
-export function captureDOMHTML(): string {
+export function captureDOMHTML(options: DomCaptureOptions = {}): string {
   if (typeof document === "undefined") {
     return "";
   }
+  if (options.includeShadowDom) {
+    return (cloneWithShadowRoots(document.documentElement) as Element)
+      .outerHTML;
+  }
   return document.documentElement.outerHTML;
 }
 
@@ -56,8 +162,24 @@ export function getDOMMetadata(): DomSnapshotMetadata {
   };
 }
 
+// Splits `text` into pieces of about `size` code units without separating a
+// surrogate pair.
+function splitChunks(text: string, size: number): string[] {
+  const chunks: string[] = [];
+  const step = Math.max(2, Math.floor(size));
+  for (let start = 0; start < text.length; ) {
+    let end = Math.min(start + step, text.length);
+    const last = text.charCodeAt(end - 1);
+    if (end < text.length && last >= 0xd800 && last <= 0xdbff) end--;
+    chunks.push(text.slice(start, end));
+    start = end;
+  }
+  return chunks;
+}
+
 /**
- * Capture and save DOM snapshot to backend
+ * Capture and save DOM snapshot to backend. Large documents are sent in
+ * chunks (see {@link DomSnapshotOptions.chunkThreshold}).
  *
  * @returns Path to saved DOM snapshot file with metadata
  *
@@ -66,12 +188,32 @@ export function getDOMMetadata(): DomSnapshotMetadata {
  * const result = await captureDOMSnapshot();
  * console.log(`DOM saved: ${result.path}`);
  * console.log(`URL: ${result.metadata.url}`);
+ *
+ * // Include open shadow roots of web components
+ * await captureDOMSnapshot({ includeShadowDom: true });
  * ```
  */
-export async function captureDOMSnapshot(): Promise<DomSnapshotResult> {
-  const html = captureDOMHTML();
+export async function captureDOMSnapshot(
+  options: DomSnapshotOptions = {},
+): Promise<DomSnapshotResult> {
+  const includeShadowDom = options.includeShadowDom ?? false;
+  const html = toWellFormed(captureDOMHTML({ includeShadowDom }));
   const metadata = getDOMMetadata();
 
+  if (html.length > (options.chunkThreshold ?? DEFAULT_CHUNK_THRESHOLD)) {
+    return await uploadDOMSnapshot(
+      html,
+      {
+        url: metadata.url,
+        title: metadata.title,
+        viewport_width: metadata.viewport.width,
+        viewport_height: metadata.viewport.height,
+        shadow_dom_included: includeShadowDom,
+      },
+      options.chunkSize,
+    );
+  }
+
   const result = await invoke<DomSnapshotResult>(
     "plugin:debug-tools|capture_dom_snapshot",
     {
@@ -81,6 +223,7 @@ export async function captureDOMSnapshot(): Promise<DomSnapshotResult> {
         title: metadata.title,
         viewport_width: metadata.viewport.width,
         viewport_height: metadata.viewport.height,
+        shadow_dom_included: includeShadowDom,
       },
     },
   );
@@ -88,6 +231,106 @@ export async function captureDOMSnapshot(): Promise<DomSnapshotResult> {
   return result;
 }
 
+export interface DomDeltaResult {
+  path: string;
+  baseline_path: string;
+  /** True when no baseline existed yet and this capture was saved in full. */
+  baseline_created: boolean;
+  inserted_bytes: number;
+  deleted_bytes: number;
+}
+
+export interface ReconstructedDom {
+  html: string;
+  url: string;
+  title: string;
+  timestamp: number;
+  baseline_path: string;
+}
+
+/**
+ * Save `html` through the chunked upload commands: `begin_dom_capture`, one
+ * `append_dom_chunk` per chunk, then `finish_dom_capture`. Chunks are sent
+ * one at a time, so serialization never blocks the UI for long. An upload
+ * abandoned midway is discarded by the backend after
+ * `dom_upload_timeout_secs`.
+ *
+ * @example
+ * ```typescript
+ * const html = document.documentElement.outerHTML;
+ * const result = await uploadDOMSnapshot(html, {
+ *   url: location.href,
+ *   title: document.title,
+ *   viewport_width: innerWidth,
+ *   viewport_height: innerHeight,
+ * });
+ * console.log(`DOM saved: ${result.path}`);
+ * ```
+ */
+export async function uploadDOMSnapshot(
+  html: string,
+  metadata: Omit<DomUploadMetadata, "total_chunks">,
+  chunkSize = DEFAULT_CHUNK_SIZE,
+): Promise<DomSnapshotResult> {
+  const uploadId = await invoke<string>(
+    "plugin:debug-tools|begin_dom_capture",
+  );
+  const chunks = splitChunks(toWellFormed(html), chunkSize);
+  for (const [seq, bytes] of chunks.entries()) {
+    await invoke<DomChunkAck>("plugin:debug-tools|append_dom_chunk", {
+      uploadId,
+      seq,
+      bytes,
+      encoding: "utf8",
+    });
+  }
+  return await invoke<DomSnapshotResult>(
+    "plugin:debug-tools|finish_dom_capture",
+    { uploadId, metadata: { ...metadata, total_chunks: chunks.length } },
+  );
+}
+
+/**
+ * Save only the difference between the current DOM and the session's first
+ * DOM capture (the baseline). The first call in a session saves the full DOM.
+ *
+ * @example
+ * ```typescript
+ * const delta = await captureDOMDelta();
+ * console.log(`+${delta.inserted_bytes} / -${delta.deleted_bytes} bytes since load`);
+ * ```
+ */
+export async function captureDOMDelta(
+  options: DomCaptureOptions = {},
+): Promise<DomDeltaResult> {
+  const includeShadowDom = options.includeShadowDom ?? false;
+  const html = toWellFormed(captureDOMHTML({ includeShadowDom }));
+  const metadata = getDOMMetadata();
+
+  return await invoke<DomDeltaResult>("plugin:debug-tools|capture_dom_delta", {
+    payload: {
+      html,
+      url: metadata.url,
+      title: metadata.title,
+      viewport_width: metadata.viewport.width,
+      viewport_height: metadata.viewport.height,
+      shadow_dom_included: includeShadowDom,
+    },
+  });
+}
+
+/**
+ * Rebuild the full HTML of a saved DOM delta from its baseline snapshot.
+ */
+export async function reconstructFromBaseline(
+  deltaPath: string,
+): Promise<ReconstructedDom> {
+  return await invoke<ReconstructedDom>(
+    "plugin:debug-tools|reconstruct_from_baseline",
+    { deltaPath },
+  );
+}
+
 /**
  * Get unified log directory information
  *
@@ -0,0 +1,101 @@
+/**
+ * Adaptive flush cadence for the console log collector.
+ */
+
+import type { ConsoleLogEntry } from "./consoleLogger";
+
+export interface FlushPolicySettings {
+  /** Starting interval, and the one steady traffic settles back to. */
+  baseIntervalMs: number;
+  /** Tightest interval during bursts. */
+  minIntervalMs: number;
+  /** Longest interval while traffic is low. */
+  maxIntervalMs: number;
+  /** Pending bytes that trigger an immediate flush. */
+  byteThreshold: number;
+  /** Entries per second above which a flush counts as a burst. */
+  burstEntriesPerSec: number;
+}
+
+export const DEFAULT_FLUSH_POLICY: FlushPolicySettings = {
+  baseIntervalMs: 1000,
+  minIntervalMs: 100,
+  maxIntervalMs: 10_000,
+  byteThreshold: 256 * 1024,
+  burstEntriesPerSec: 50,
+};
+
+const URGENT_LEVELS: ReadonlySet<ConsoleLogEntry["level"]> = new Set([
+  "warn",
+  "error",
+]);
+
+/**
+ * Decides how long queued entries may wait. Warnings, errors and a full
+ * byte budget flush right away; otherwise the interval doubles after a
+ * flush that carried a single entry or less than one per second, halves
+ * after one that carried a burst, and moves halfway back to the base
+ * interval in between.
+ */
+export class AdaptiveFlushPolicy {
+  private settings: FlushPolicySettings;
+  private intervalMs: number;
+  private lastFlushAt: number | null = null;
+
+  constructor(settings: FlushPolicySettings = DEFAULT_FLUSH_POLICY) {
+    this.settings = settings;
+    this.intervalMs = this.clamp(settings.baseIntervalMs);
+  }
+
+  get currentIntervalMs(): number {
+    return this.intervalMs;
+  }
+
+  configure(settings: FlushPolicySettings): void {
+    this.settings = settings;
+    this.intervalMs = this.clamp(settings.baseIntervalMs);
+  }
+
+  /** Delay before the queue holding this entry is flushed; 0 is now. */
+  delayFor(level: ConsoleLogEntry["level"], pendingBytes: number): number {
+    if (URGENT_LEVELS.has(level)) return 0;
+    if (pendingBytes >= this.settings.byteThreshold) return 0;
+    return this.intervalMs;
+  }
+
+  /** Adapts the interval to a flush of `entries` entries at `now`. */
+  recordFlush(entries: number, now: number): void {
+    const elapsedMs =
+      this.lastFlushAt === null
+        ? this.intervalMs
+        : Math.max(1, now - this.lastFlushAt);
+    this.lastFlushAt = now;
+
+    const perSecond = (entries * 1000) / elapsedMs;
+    if (entries <= 1 || perSecond < 1) {
+      this.intervalMs = this.clamp(this.intervalMs * 2);
+    } else if (perSecond >= this.settings.burstEntriesPerSec) {
+      this.intervalMs = this.clamp(this.intervalMs / 2);
+    } else {
+      this.intervalMs = this.clamp(
+        (this.intervalMs + this.settings.baseIntervalMs) / 2,
+      );
+    }
+  }
+
+  private clamp(intervalMs: number): number {
+    const { minIntervalMs, maxIntervalMs } = this.settings;
+    const min = Math.max(0, minIntervalMs);
+    return Math.round(Math.min(Math.max(intervalMs, min), maxIntervalMs));
+  }
+}
+
+/** Rough serialized size of an entry, for the byte threshold. */
+export function estimateEntryBytes(entry: ConsoleLogEntry): number {
+  return (
+    entry.message.length +
+    (entry.stack_trace?.length ?? 0) +
+    // Timestamp, level and JSON punctuation.
+    64
+  );
+}
@@ -1,3 +1,6 @@
 export * from "./consoleLogger";
+export * from "./contextCapture";
 export * from "./debugBridge";
 export * from "./domCapture";
+export * from "./flushPolicy";
+export * from "./networkCapture";
@@ -0,0 +1,226 @@
+/**
+ * Network capture utilities for tauri-plugin-debug-tools
+ *
+ * Wraps `fetch` to record method, URL, status and timing of every request.
+ * Full request/response bodies are only captured for URLs matching the
+ * backend's body capture rule, so a single API interaction can be inspected
+ * without paying the cost for all traffic.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface BodyCaptureRule {
+  /** URL pattern; `*` matches any sequence of characters. */
+  url_pattern: string;
+  /** Per-body size cap in bytes (default 64 KiB, max 1 MiB). */
+  max_body_bytes?: number;
+}
+
+export interface CapturedBody {
+  content_type: string | null;
+  size_bytes: number;
+  truncated: boolean;
+  body: string;
+}
+
+export interface NetworkLogEntry {
+  timestamp: number;
+  method: string;
+  url: string;
+  status: number | null;
+  duration_ms: number | null;
+  error: string | null;
+  request_body?: CapturedBody | null;
+  response_body?: CapturedBody | null;
+}
+
+const DEFAULT_MAX_BODY_BYTES = 64 * 1024;
+const FLUSH_INTERVAL_MS = 1000;
+const MAX_PENDING_ENTRIES = 200;
+
+let bodyCaptureRule: BodyCaptureRule | null = null;
+let bodyCaptureMatcher: RegExp | null = null;
+let pendingEntries: NetworkLogEntry[] = [];
+let flushTimer: number | null = null;
+let installed = false;
+
+function compilePattern(pattern: string): RegExp {
+  const escaped = pattern
+    .split("*")
+    .map((part) => part.replace(/[.+?^${}()|[\]\\]/g, "\\$&"))
+    .join(".*");
+  return new RegExp(`^${escaped}$`);
+}
+
+function applyRule(rule: BodyCaptureRule | null): void {
+  bodyCaptureRule = rule;
+  bodyCaptureMatcher = rule ? compilePattern(rule.url_pattern) : null;
+}
+
+function shouldCaptureBody(url: string): boolean {
+  return bodyCaptureMatcher?.test(url) ?? false;
+}
+
+function capBody(body: string, contentType: string | null): CapturedBody {
+  const maxBytes = bodyCaptureRule?.max_body_bytes ?? DEFAULT_MAX_BODY_BYTES;
+  const encoded = new TextEncoder().encode(body);
+  if (encoded.length <= maxBytes) {
+    return {
+      content_type: contentType,
+      size_bytes: encoded.length,
+      truncated: false,
+      body,
+    };
+  }
+  return {
+    content_type: contentType,
+    size_bytes: encoded.length,
+    truncated: true,
+    // Decoding drops a trailing partial code point instead of throwing.
+    body: new TextDecoder().decode(encoded.slice(0, maxBytes)),
+  };
+}
+
+async function readRequestBody(
+  input: RequestInfo | URL,
+  init?: RequestInit,
+): Promise<CapturedBody | null> {
+  const contentType =
+    new Headers(
+      init?.headers ?? (input instanceof Request ? input.headers : undefined),
+    ).get("content-type") ?? null;
+
+  const body = init?.body;
+  if (typeof body === "string") return capBody(body, contentType);
+  if (body instanceof URLSearchParams) {
+    return capBody(body.toString(), contentType);
+  }
+  if (body === undefined && input instanceof Request) {
+    try {
+      return capBody(await input.clone().text(), contentType);
+    } catch {
+      return null;
+    }
+  }
+  return null;
+}
+
+async function readResponseBody(
+  response: Response,
+): Promise<CapturedBody | null> {
+  try {
+    return capBody(
+      await response.clone().text(),
+      response.headers.get("content-type"),
+    );
+  } catch {
+    return null;
+  }
+}
+
+function enqueue(entry: NetworkLogEntry): void {
+  pendingEntries.push(entry);
+  if (pendingEntries.length > MAX_PENDING_ENTRIES) {
+    pendingEntries.shift();
+  }
+  if (flushTimer !== null || typeof window === "undefined") return;
+  flushTimer = window.setTimeout(() => {
+    flushTimer = null;
+    void flushNetworkLogs();
+  }, FLUSH_INTERVAL_MS);
+}
+
+/**
+ * Send buffered network log entries to the backend.
+ */
+export async function flushNetworkLogs(): Promise<void> {
+  if (pendingEntries.length === 0) return;
+  const entries = pendingEntries;
+  pendingEntries = [];
+  try {
+    await invoke("plugin:debug-tools|append_network_logs", { entries });
+  } catch {
+    // Keep the newest entries for the next attempt.
+    pendingEntries = [...entries, ...pendingEntries].slice(
+      -MAX_PENDING_ENTRIES,
+    );
+  }
+}
+
+/**
+ * Install the fetch interceptor. Safe to call more than once.
+ */
+export async function installNetworkCapture(): Promise<void> {
+  if (installed || typeof window === "undefined") return;
+  installed = true;
+
+  applyRule(
+    await invoke<BodyCaptureRule | null>("plugin:debug-tools|get_body_capture"),
+  );
+
+  const originalFetch = window.fetch.bind(window);
+
+  window.fetch = async (input: RequestInfo | URL, init?: RequestInit) => {
+    const url =
+      input instanceof Request
+        ? input.url
+        : input instanceof URL
+          ? input.href
+          : String(input);
+    const method = (
+      init?.method ?? (input instanceof Request ? input.method : "GET")
+    ).toUpperCase();
+    const captureBody = shouldCaptureBody(url);
+    const requestBody = captureBody ? await readRequestBody(input, init) : null;
+    const started = performance.now();
+    const timestamp = Date.now();
+
+    try {
+      const response = await originalFetch(input, init);
+      enqueue({
+        timestamp,
+        method,
+        url,
+        status: response.status,
+        duration_ms: performance.now() - started,
+        error: null,
+        request_body: requestBody,
+        response_body: captureBody ? await readResponseBody(response) : null,
+      });
+      return response;
+    } catch (error) {
+      enqueue({
+        timestamp,
+        method,
+        url,
+        status: null,
+        duration_ms: performance.now() - started,
+        error: error instanceof Error ? error.message : String(error),
+        request_body: requestBody,
+        response_body: null,
+      });
+      throw error;
+    }
+  };
+}
+
+/**
+ * Capture full request/response bodies for URLs matching a pattern.
+ * Pass `null` to turn body capture off again.
+ *
+ * @example
+ * ```typescript
+ * await installNetworkCapture();
+ * await setBodyCapture({ url_pattern: "https://api.example.com/orders/*" });
+ * ```
+ */
+export async function setBodyCapture(
+  rule: BodyCaptureRule | null,
+): Promise<BodyCaptureRule | null> {
+  const applied = await invoke<BodyCaptureRule | null>(
+    "plugin:debug-tools|set_body_capture",
+    { rule },
+  );
+  applyRule(applied);
+  return applied;
+}
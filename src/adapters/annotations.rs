@@ -0,0 +1,106 @@
+use super::ownership::OwnedFiles;
+use crate::config::DebugToolsConfig;
+use crate::domain::annotations::{attach_annotation, log_seq, MAX_ANNOTATIONS_PER_SESSION};
+use crate::domain::{AnnotationError, ConsoleLogEntry, LogAnnotation};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// `annotations_<session>.jsonl`, appended to and never rewritten, with the
+// latest annotation per seq kept in memory.
+#[derive(Debug)]
+pub struct AnnotationStore {
+    path: PathBuf,
+    annotations: Mutex<BTreeMap<u64, LogAnnotation>>,
+}
+
+impl AnnotationStore {
+    pub fn load(config: &DebugToolsConfig, session_id: &str) -> Self {
+        let path = config.annotations_path(session_id);
+        let annotations = match read_annotations(&path) {
+            Ok(annotations) => annotations
+                .into_iter()
+                .map(|annotation| (annotation.seq, annotation))
+                .collect(),
+            Err(e) => {
+                if e.kind() != ErrorKind::NotFound {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to read log annotations");
+                }
+                BTreeMap::new()
+            }
+        };
+
+        Self {
+            path,
+            annotations: Mutex::new(annotations),
+        }
+    }
+
+    // Replaces any earlier annotation of the same seq, which does not count
+    // towards the limit again.
+    pub fn annotate(
+        &self,
+        annotation: LogAnnotation,
+        owned: &OwnedFiles,
+    ) -> Result<LogAnnotation, AnnotationError> {
+        let mut annotations = self.annotations.lock().unwrap_or_else(|e| e.into_inner());
+        if !annotations.contains_key(&annotation.seq)
+            && annotations.len() >= MAX_ANNOTATIONS_PER_SESSION
+        {
+            return Err(AnnotationError::LimitReached {
+                max: MAX_ANNOTATIONS_PER_SESSION,
+            });
+        }
+
+        let mut line = serde_json::to_vec(&annotation).map_err(AnnotationError::storage)?;
+        line.push(b'\n');
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&line))
+            .map_err(AnnotationError::storage)?;
+        owned.register(&self.path);
+
+        annotations.insert(annotation.seq, annotation.clone());
+        Ok(annotation)
+    }
+
+    // For entries read back from this session's console log.
+    pub fn attach(&self, entries: &mut [ConsoleLogEntry]) {
+        let annotations = self.annotations.lock().unwrap_or_else(|e| e.into_inner());
+        if annotations.is_empty() {
+            return;
+        }
+        for entry in entries {
+            if let Some(annotation) = log_seq(entry).and_then(|seq| annotations.get(&seq)) {
+                attach_annotation(entry, annotation);
+            }
+        }
+    }
+
+    // In seq order; `since` is compared with `annotated_at`.
+    pub fn list(&self, since: Option<i64>) -> Vec<LogAnnotation> {
+        self.annotations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|annotation| since.map_or(true, |since| annotation.annotated_at >= since))
+            .cloned()
+            .collect()
+    }
+}
+
+// Every parseable line, in file order; torn lines are skipped.
+pub fn read_annotations(path: &Path) -> std::io::Result<Vec<LogAnnotation>> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut annotations = Vec::new();
+    for line in reader.split(b'\n') {
+        if let Ok(annotation) = serde_json::from_slice::<LogAnnotation>(&line?) {
+            annotations.push(annotation);
+        }
+    }
+    Ok(annotations)
+}
@@ -0,0 +1,95 @@
+use crate::domain::BufferPoolStats;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Serialization buffers kept between captures, so a burst of snapshots
+// reuses a few large allocations instead of growing fresh ones. Buffers
+// are cleared before reuse; only their capacity carries over.
+#[derive(Debug)]
+pub struct BufferPool {
+    max_pooled_bytes: usize,
+    idle: Duration,
+    inner: Mutex<PoolInner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    buffers: Vec<Vec<u8>>,
+    pooled_bytes: usize,
+    last_used: Instant,
+}
+
+impl BufferPool {
+    // A `max_pooled_bytes` of 0 turns pooling off.
+    pub fn new(max_pooled_bytes: usize, idle: Duration) -> Self {
+        Self {
+            max_pooled_bytes,
+            idle,
+            inner: Mutex::new(PoolInner {
+                buffers: Vec::new(),
+                pooled_bytes: 0,
+                last_used: Instant::now(),
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    // The largest pooled buffer, empty.
+    pub fn take(&self) -> Vec<u8> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.last_used = Instant::now();
+        let largest = (0..inner.buffers.len()).max_by_key(|&i| inner.buffers[i].capacity());
+        match largest {
+            Some(index) => {
+                let buffer = inner.buffers.swap_remove(index);
+                inner.pooled_bytes -= buffer.capacity();
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                buffer
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                Vec::new()
+            }
+        }
+    }
+
+    // Kept only while the pool stays under `max_pooled_bytes`.
+    pub fn give(&self, mut buffer: Vec<u8>) {
+        buffer.clear();
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.last_used = Instant::now();
+        if inner.pooled_bytes + buffer.capacity() <= self.max_pooled_bytes {
+            inner.pooled_bytes += buffer.capacity();
+            inner.buffers.push(buffer);
+        }
+    }
+
+    // Frees every pooled buffer once nothing has used the pool for `idle`.
+    pub fn release_if_idle(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if !inner.buffers.is_empty() && inner.last_used.elapsed() >= self.idle {
+            tracing::debug!(
+                buffers = inner.buffers.len(),
+                bytes = inner.pooled_bytes,
+                "Releasing idle serialization buffers"
+            );
+            inner.buffers = Vec::new();
+            inner.pooled_bytes = 0;
+        }
+    }
+
+    pub fn stats(&self) -> BufferPoolStats {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        BufferPoolStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            pooled_buffers: inner.buffers.len(),
+            pooled_bytes: inner.pooled_bytes as u64,
+            max_pooled_bytes: self.max_pooled_bytes as u64,
+        }
+    }
+}
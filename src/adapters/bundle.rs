@@ -0,0 +1,133 @@
+use super::filesystem::{artifact_root, ensure_within_root};
+use super::ownership::OwnedFiles;
+use super::plugin_log::plugin_log_files;
+use super::snapshot_index::snapshot_file_timestamp;
+use crate::analysis::merge::{BundleManifest, BUNDLE_MANIFEST_FILE, BUNDLE_PLUGIN_LOG_DIR};
+use crate::config::DebugToolsConfig;
+use crate::domain::RepositoryError;
+use std::fs;
+use std::io::{self, Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+pub struct BundleArchive {
+    pub zip: Vec<u8>,
+    // Artifacts in the archive, not counting its manifest.
+    pub files: usize,
+}
+
+fn name_of(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|name| name.to_str())
+}
+
+fn is_root_artifact(name: &str) -> bool {
+    let jsonl = |prefix: &str| name.starts_with(prefix) && name.ends_with(".jsonl");
+    jsonl("frontend_console_")
+        || jsonl("network_")
+        || jsonl("annotations_")
+        || snapshot_file_timestamp(name).is_some()
+}
+
+// Regular files directly in `dir` that the plugin wrote, in name order.
+// A directory that resolves outside its root is left out with a warning,
+// as cleanup does.
+fn owned_files_in(
+    config: &DebugToolsConfig,
+    owned: &OwnedFiles,
+    dir: &Path,
+    keep: impl Fn(&str) -> bool,
+) -> Vec<PathBuf> {
+    if !dir.is_dir() {
+        return Vec::new();
+    }
+    if let Err(e) = artifact_root(config, dir).and_then(|root| ensure_within_root(&root, dir)) {
+        tracing::warn!(directory = %dir.display(), error = %e, "Leaving directory out of the bundle");
+        return Vec::new();
+    }
+    let Ok(listing) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = listing
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| name_of(path).is_some_and(|name| keep(name) && !name.contains(".tmp.")))
+        .filter(|path| owned.is_owned(path))
+        .collect();
+    files.sort();
+    files
+}
+
+// Zips the plugin's own artifacts in the layout `merge_bundles` reads:
+// console, network and annotation logs and snapshots at the root, with
+// `screenshots/`, `dom_snapshots/` and tauri-plugin-log's files in
+// `plugin_log/` beside them, plus a manifest.
+pub fn build_bundle_zip(
+    config: &DebugToolsConfig,
+    owned: &OwnedFiles,
+    manifest: &BundleManifest,
+) -> Result<BundleArchive, RepositoryError> {
+    let paths = config.artifact_paths();
+    let mut entries: Vec<(String, PathBuf)> = Vec::new();
+    let mut add = |prefix: &str, files: Vec<PathBuf>| {
+        for path in files {
+            if let Some(name) = name_of(&path).map(|name| format!("{}{}", prefix, name)) {
+                if !entries.iter().any(|(existing, _)| *existing == name) {
+                    entries.push((name, path));
+                }
+            }
+        }
+    };
+    for dir in [
+        &paths.console_logs,
+        &paths.snapshots,
+        &paths.network_logs,
+        &paths.annotations,
+        &paths.log_dir,
+    ] {
+        add("", owned_files_in(config, owned, dir, is_root_artifact));
+    }
+    add(
+        "screenshots/",
+        owned_files_in(config, owned, &paths.screenshots, |_| true),
+    );
+    add(
+        "dom_snapshots/",
+        owned_files_in(config, owned, &paths.dom_snapshots, |_| true),
+    );
+    // The host's files, so never recorded as owned; reading them is all the
+    // bundle does.
+    add(
+        &format!("{}/", BUNDLE_PLUGIN_LOG_DIR),
+        plugin_log_files(config)
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect(),
+    );
+
+    let options = SimpleFileOptions::default();
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(BUNDLE_MANIFEST_FILE, options)
+        .map_err(io::Error::from)?;
+    zip.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+
+    let mut files = 0;
+    for (name, path) in entries {
+        // Files can go between listing and reading; the rest still ship.
+        let mut source = match fs::File::open(&path) {
+            Ok(source) => source,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Leaving file out of the bundle");
+                continue;
+            }
+        };
+        zip.start_file(name, options).map_err(io::Error::from)?;
+        io::copy(&mut source, &mut zip)?;
+        files += 1;
+    }
+
+    let zip = zip.finish().map_err(io::Error::from)?.into_inner();
+    Ok(BundleArchive { zip, files })
+}
@@ -0,0 +1,143 @@
+use super::platform::available_disk_bytes;
+use crate::config::DebugToolsConfig;
+use crate::domain::{CategoryUsage, DiskCategory, DiskLimitSource, DiskUsage};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// Walks `log_dir` and the artifact root overrides without following
+// symlinks. Files that vanish during the walk are skipped; only an
+// unreadable `log_dir` is an error.
+pub fn measure_disk_usage(
+    config: &DebugToolsConfig,
+    now_millis: i64,
+) -> std::io::Result<DiskUsage> {
+    let mut usage: BTreeMap<DiskCategory, CategoryUsage> = BTreeMap::new();
+    add_top_level(&config.log_dir, &mut usage)?;
+
+    let paths = config.artifact_paths();
+    for root in paths.override_roots() {
+        let category = [
+            (&paths.screenshots, DiskCategory::Screenshots),
+            (&paths.dom_snapshots, DiskCategory::DomSnapshots),
+            (&paths.recordings, DiskCategory::Recordings),
+            (&paths.exports, DiskCategory::Exports),
+            (&paths.config_history, DiskCategory::Other),
+        ]
+        .into_iter()
+        .find_map(|(dir, category)| (dir.as_path() == root).then_some(category));
+        if let Some(category) = category {
+            add_directory(root, category, &mut usage);
+        } else if let Err(e) = add_top_level(root, &mut usage) {
+            tracing::debug!(root = %root.display(), error = %e, "Skipping unreadable artifact root");
+        }
+    }
+
+    let total_bytes = usage.values().map(|category| category.bytes).sum();
+    let (limit_bytes, limit_source) = match config.max_total_disk_bytes {
+        Some(quota) => (Some(quota), Some(DiskLimitSource::Quota)),
+        None => match available_disk_bytes(&config.log_dir) {
+            Some(free) => (Some(total_bytes + free), Some(DiskLimitSource::FreeDisk)),
+            None => (None, None),
+        },
+    };
+    let mut categories: Vec<CategoryUsage> = usage.into_values().collect();
+    categories.sort_by_key(|category| std::cmp::Reverse(category.bytes));
+
+    Ok(DiskUsage {
+        measured_at: now_millis,
+        total_bytes,
+        limit_bytes,
+        limit_source,
+        usage_ratio: limit_bytes
+            .filter(|limit| *limit > 0)
+            .map(|limit| total_bytes as f64 / limit as f64),
+        categories,
+    })
+}
+
+// Files in `dir` by name, subdirectories by theirs.
+fn add_top_level(
+    dir: &Path,
+    usage: &mut BTreeMap<DiskCategory, CategoryUsage>,
+) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if file_type.is_dir() {
+            add_directory(&entry.path(), directory_category(&name), usage);
+        } else if file_type.is_file() {
+            if let Ok(metadata) = entry.metadata() {
+                add(usage, file_category(&name), metadata.len());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn file_category(name: &str) -> DiskCategory {
+    if name.starts_with("frontend_console_") {
+        DiskCategory::ConsoleLogs
+    } else if name.starts_with("network_") {
+        DiskCategory::NetworkLogs
+    } else if name == "rust_debug.log" || name.starts_with("rust_debug.log.") {
+        DiskCategory::BackendLogs
+    } else if name.starts_with("snapshot_")
+        || name.starts_with("tauri_debug_snapshot_")
+        || name.starts_with("final_beacon_")
+    {
+        DiskCategory::Snapshots
+    } else {
+        DiskCategory::Other
+    }
+}
+
+fn directory_category(name: &str) -> DiskCategory {
+    match name {
+        "screenshots" => DiskCategory::Screenshots,
+        "dom_snapshots" | ".dom_uploads" => DiskCategory::DomSnapshots,
+        "recordings" => DiskCategory::Recordings,
+        "exports" => DiskCategory::Exports,
+        _ => DiskCategory::Other,
+    }
+}
+
+fn add_directory(
+    dir: &Path,
+    category: DiskCategory,
+    usage: &mut BTreeMap<DiskCategory, CategoryUsage>,
+) {
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                if let Ok(metadata) = entry.metadata() {
+                    add(usage, category, metadata.len());
+                }
+            }
+        }
+    }
+}
+
+fn add(usage: &mut BTreeMap<DiskCategory, CategoryUsage>, category: DiskCategory, bytes: u64) {
+    let entry = usage.entry(category).or_insert(CategoryUsage {
+        category,
+        bytes: 0,
+        files: 0,
+    });
+    entry.bytes += bytes;
+    entry.files += 1;
+}
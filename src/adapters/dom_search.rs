@@ -0,0 +1,256 @@
+use crate::config::DebugToolsConfig;
+use crate::domain::{
+    DomSearchFileResult, DomSearchMatch, DomSearchQuery, DomSearchReport, DomSearchSkippedFile,
+    DomSnapshotMetadata, RepositoryError,
+};
+use flate2::read::GzDecoder;
+use regex::bytes::{Regex, RegexBuilder};
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+const CHUNK_BYTES: usize = 64 * 1024;
+// Carried over between chunks so matches straddling a boundary are still
+// found; longer matches across a boundary may be missed.
+const OVERLAP_BYTES: usize = 4 * 1024;
+const CONTEXT_BYTES: usize = 40;
+const MAX_CONTEXT_MATCH_BYTES: usize = 200;
+const MAX_MATCHES_PER_FILE: usize = 5;
+const DEFAULT_LIMIT: usize = 20;
+const METADATA_PREFIX: &[u8] = b"<!--\nDOM Snapshot Metadata:\n";
+const METADATA_SUFFIX: &[u8] = b"\n-->\n";
+
+pub fn build_matcher(query: &DomSearchQuery) -> Result<Regex, String> {
+    if query.query.is_empty() {
+        return Err("Search query is empty".into());
+    }
+
+    let pattern = if query.regex {
+        query.query.clone()
+    } else {
+        format!("(?i){}", regex::escape(&query.query))
+    };
+
+    let matcher = RegexBuilder::new(&pattern)
+        .size_limit(1 << 20)
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {}", e))?;
+
+    if matcher.is_match(b"") {
+        return Err("Search pattern must not match empty text".into());
+    }
+
+    Ok(matcher)
+}
+
+pub fn search_dom_snapshots(
+    config: &DebugToolsConfig,
+    query: &DomSearchQuery,
+    matcher: &Regex,
+) -> Result<DomSearchReport, RepositoryError> {
+    let mut snapshots = list_dom_snapshot_files(&config.dom_snapshot_dir())?;
+    snapshots.retain(|(timestamp, _)| {
+        query.since.map_or(true, |since| *timestamp >= since)
+            && query.until.map_or(true, |until| *timestamp < until)
+    });
+    snapshots.sort_by_key(|(timestamp, _)| std::cmp::Reverse(*timestamp));
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let mut report = DomSearchReport {
+        results: Vec::new(),
+        files_scanned: 0,
+        skipped: Vec::new(),
+    };
+
+    for (timestamp, path) in snapshots {
+        if report.results.len() >= limit {
+            break;
+        }
+        report.files_scanned += 1;
+
+        match search_file(&path, matcher) {
+            Ok(Some(hits)) => report.results.push(DomSearchFileResult {
+                path,
+                timestamp,
+                url: hits.url,
+                match_count: hits.match_count,
+                matches: hits.matches,
+            }),
+            Ok(None) => {}
+            Err(reason) => report.skipped.push(DomSearchSkippedFile { path, reason }),
+        }
+    }
+
+    tracing::debug!(
+        files_scanned = report.files_scanned,
+        files_matched = report.results.len(),
+        skipped = report.skipped.len(),
+        "DOM snapshot search finished"
+    );
+
+    Ok(report)
+}
+
+fn list_dom_snapshot_files(dir: &Path) -> Result<Vec<(i64, PathBuf)>, RepositoryError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?;
+            let stem = name
+                .strip_suffix(".html.gz")
+                .or_else(|| name.strip_suffix(".html"))?;
+            let timestamp = stem.strip_prefix("dom_")?.parse::<i64>().ok()?;
+            Some((timestamp, path))
+        })
+        .collect())
+}
+
+struct FileHits {
+    url: Option<String>,
+    match_count: u64,
+    matches: Vec<DomSearchMatch>,
+}
+
+// Reads the file in fixed-size chunks (decompressing `.gz` on the fly) so
+// memory stays bounded regardless of snapshot size.
+fn search_file(path: &Path, matcher: &Regex) -> Result<Option<FileHits>, String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let gzipped = path.extension().is_some_and(|ext| ext == "gz");
+    let mut reader: Box<dyn Read> = if gzipped {
+        Box::new(GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    let mut chunk = vec![0u8; CHUNK_BYTES];
+    let mut buf: Vec<u8> = Vec::with_capacity(CHUNK_BYTES + OVERLAP_BYTES);
+    // Offsets are positions in the decoded stream.
+    let mut buf_start = 0u64;
+    let mut body_start = 0u64;
+    let mut reported_until = 0u64;
+    let mut first_chunk = true;
+
+    let mut hits = FileHits {
+        url: None,
+        match_count: 0,
+        matches: Vec::new(),
+    };
+
+    loop {
+        let read = read_chunk(reader.as_mut(), &mut chunk).map_err(|e| e.to_string())?;
+        let eof = read < chunk.len();
+
+        if chunk[..read].contains(&0) {
+            return Err("binary content".into());
+        }
+        buf.extend_from_slice(&chunk[..read]);
+
+        if first_chunk {
+            first_chunk = false;
+            if let Some(skip) = strip_metadata(&buf, &mut hits.url) {
+                buf.drain(..skip);
+                buf_start = skip as u64;
+                body_start = buf_start;
+                reported_until = buf_start;
+            }
+        }
+
+        match std::str::from_utf8(&buf) {
+            Ok(_) => {}
+            // A code point split by the chunk boundary completes in the next read.
+            Err(e) if e.error_len().is_none() && !eof => {}
+            Err(_) => return Err("not valid UTF-8".into()),
+        }
+
+        let search_end = if eof {
+            buf.len()
+        } else {
+            buf.len().saturating_sub(OVERLAP_BYTES)
+        };
+
+        for found in matcher.find_iter(&buf) {
+            if found.start() >= search_end {
+                break;
+            }
+            let absolute = buf_start + found.start() as u64;
+            if absolute < reported_until {
+                continue;
+            }
+
+            hits.match_count += 1;
+            reported_until = buf_start + found.end() as u64;
+            if hits.matches.len() < MAX_MATCHES_PER_FILE {
+                hits.matches.push(DomSearchMatch {
+                    offset: absolute - body_start,
+                    context: context_around(&buf, found.start(), found.end()),
+                });
+            }
+        }
+
+        if eof {
+            break;
+        }
+
+        let mut cut = buf.len().saturating_sub(OVERLAP_BYTES);
+        while cut > 0 && is_continuation_byte(buf[cut]) {
+            cut -= 1;
+        }
+        buf.drain(..cut);
+        buf_start += cut as u64;
+    }
+
+    Ok((hits.match_count > 0).then_some(hits))
+}
+
+// Returns the length of the leading metadata comment written by `save_dom`.
+fn strip_metadata(buf: &[u8], url: &mut Option<String>) -> Option<usize> {
+    let rest = buf.strip_prefix(METADATA_PREFIX)?;
+    let end = rest
+        .windows(METADATA_SUFFIX.len())
+        .position(|window| window == METADATA_SUFFIX)?;
+
+    *url = serde_json::from_slice::<DomSnapshotMetadata>(&rest[..end])
+        .ok()
+        .map(|metadata| metadata.url);
+
+    Some(METADATA_PREFIX.len() + end + METADATA_SUFFIX.len())
+}
+
+fn read_chunk(reader: &mut dyn Read, chunk: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < chunk.len() {
+        match reader.read(&mut chunk[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn context_around(buf: &[u8], start: usize, end: usize) -> String {
+    let end = end.min(start + MAX_CONTEXT_MATCH_BYTES);
+
+    let mut from = start.saturating_sub(CONTEXT_BYTES);
+    while from < start && is_continuation_byte(buf[from]) {
+        from += 1;
+    }
+    let mut to = (end + CONTEXT_BYTES).min(buf.len());
+    while to > end && to < buf.len() && is_continuation_byte(buf[to]) {
+        to -= 1;
+    }
+
+    String::from_utf8_lossy(&buf[from..to]).into_owned()
+}
+
+fn is_continuation_byte(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
@@ -0,0 +1,310 @@
+use crate::config::DebugToolsConfig;
+use crate::domain::Clock;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+// Uploads open at once, so abandoned ones cannot fill the disk before they
+// expire.
+const MAX_OPEN_UPLOADS: usize = 8;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DomUploadError {
+    #[error("Unknown or expired DOM upload {0}")]
+    UnknownUpload(String),
+    #[error("Too many DOM uploads in progress (at most {0})")]
+    TooManyUploads(usize),
+    #[error("DOM upload exceeds {0} bytes")]
+    TooLarge(u64),
+    #[error("Chunk {0} was already received with different content")]
+    ConflictingChunk(u32),
+    #[error("DOM upload is missing chunks {0:?}")]
+    MissingChunks(Vec<u32>),
+    #[error("Invalid base64 chunk: {0}")]
+    InvalidBase64(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkEncoding {
+    #[default]
+    Utf8,
+    Base64,
+}
+
+impl ChunkEncoding {
+    pub fn decode(self, data: String) -> Result<Vec<u8>, DomUploadError> {
+        match self {
+            Self::Utf8 => Ok(data.into_bytes()),
+            Self::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| DomUploadError::InvalidBase64(e.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomChunkAck {
+    pub seq: u32,
+    // A resend of a chunk already stored; nothing was written.
+    pub duplicate: bool,
+    pub received_chunks: usize,
+    pub received_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChunkInfo {
+    len: u64,
+    digest: u64,
+}
+
+#[derive(Debug)]
+struct Upload {
+    dir: PathBuf,
+    chunks: BTreeMap<u32, ChunkInfo>,
+    bytes: u64,
+    last_activity: i64,
+}
+
+// Chunked DOM uploads staged under `.dom_uploads/`, one directory per upload
+// and one file per chunk, so chunks may arrive in any order. Finishing
+// concatenates them by sequence number.
+pub struct DomUploadStore {
+    dir: PathBuf,
+    max_bytes: u64,
+    timeout_ms: i64,
+    clock: Arc<dyn Clock>,
+    uploads: Mutex<HashMap<String, Upload>>,
+}
+
+impl DomUploadStore {
+    // Removes what a previous run left behind when it exited mid-upload.
+    pub fn new(config: &DebugToolsConfig, clock: Arc<dyn Clock>) -> Self {
+        let dir = config.dom_upload_dir();
+        if let Err(e) = remove_uploads(&dir) {
+            tracing::warn!(path = %dir.display(), error = %e, "Failed to remove stale DOM uploads");
+        }
+
+        Self {
+            dir,
+            max_bytes: config.max_dom_upload_bytes,
+            timeout_ms: config.dom_upload_timeout_secs.saturating_mul(1000) as i64,
+            clock,
+            uploads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    // `upload_id` must be safe to use as a directory name.
+    pub fn begin(&self, upload_id: String) -> Result<(), DomUploadError> {
+        self.expire();
+
+        let mut uploads = self.uploads.lock().unwrap_or_else(|e| e.into_inner());
+        if uploads.len() >= MAX_OPEN_UPLOADS {
+            return Err(DomUploadError::TooManyUploads(MAX_OPEN_UPLOADS));
+        }
+
+        let dir = self.dir.join(&upload_id);
+        fs::create_dir_all(&dir)?;
+        uploads.insert(
+            upload_id,
+            Upload {
+                dir,
+                chunks: BTreeMap::new(),
+                bytes: 0,
+                last_activity: self.clock.now_millis(),
+            },
+        );
+        Ok(())
+    }
+
+    // A resent chunk with the same content is acknowledged as a duplicate;
+    // different content under a stored `seq` is rejected. Going over the size
+    // cap aborts the whole upload.
+    pub fn append(
+        &self,
+        upload_id: &str,
+        seq: u32,
+        bytes: &[u8],
+    ) -> Result<DomChunkAck, DomUploadError> {
+        let mut uploads = self.uploads.lock().unwrap_or_else(|e| e.into_inner());
+        let upload = uploads
+            .get_mut(upload_id)
+            .ok_or_else(|| DomUploadError::UnknownUpload(upload_id.to_string()))?;
+        upload.last_activity = self.clock.now_millis();
+
+        let info = ChunkInfo {
+            len: bytes.len() as u64,
+            digest: digest(bytes),
+        };
+        let duplicate = match upload.chunks.get(&seq) {
+            Some(stored) if stored.len == info.len && stored.digest == info.digest => true,
+            Some(_) => return Err(DomUploadError::ConflictingChunk(seq)),
+            None => false,
+        };
+
+        if !duplicate {
+            if upload.bytes + info.len > self.max_bytes {
+                if let Some(upload) = uploads.remove(upload_id) {
+                    discard(&upload);
+                }
+                return Err(DomUploadError::TooLarge(self.max_bytes));
+            }
+            fs::write(chunk_path(&upload.dir, seq), bytes)?;
+            upload.chunks.insert(seq, info);
+            upload.bytes += info.len;
+        }
+
+        Ok(DomChunkAck {
+            seq,
+            duplicate,
+            received_chunks: upload.chunks.len(),
+            received_bytes: upload.bytes,
+        })
+    }
+
+    // Chunks must be numbered from 0 without gaps, up to `total_chunks` when
+    // given. A gap leaves the upload open so the missing chunks can be resent.
+    pub fn finish(
+        &self,
+        upload_id: &str,
+        total_chunks: Option<u32>,
+    ) -> Result<Vec<u8>, DomUploadError> {
+        let mut uploads = self.uploads.lock().unwrap_or_else(|e| e.into_inner());
+        let upload = uploads
+            .get_mut(upload_id)
+            .ok_or_else(|| DomUploadError::UnknownUpload(upload_id.to_string()))?;
+        upload.last_activity = self.clock.now_millis();
+
+        let expected = total_chunks.unwrap_or_else(|| {
+            upload
+                .chunks
+                .last_key_value()
+                .map_or(0, |(seq, _)| seq.saturating_add(1))
+        });
+        let missing: Vec<u32> = (0..expected)
+            .filter(|seq| !upload.chunks.contains_key(seq))
+            .collect();
+        if !missing.is_empty() {
+            return Err(DomUploadError::MissingChunks(missing));
+        }
+
+        let Some(upload) = uploads.remove(upload_id) else {
+            return Err(DomUploadError::UnknownUpload(upload_id.to_string()));
+        };
+        drop(uploads);
+
+        let mut html = Vec::with_capacity(upload.bytes as usize);
+        let read = upload
+            .chunks
+            .keys()
+            .filter(|seq| **seq < expected)
+            .try_for_each(|seq| {
+                html.extend_from_slice(&fs::read(chunk_path(&upload.dir, *seq))?);
+                Ok::<_, std::io::Error>(())
+            });
+        discard(&upload);
+        read?;
+
+        Ok(html)
+    }
+
+    // Drops uploads with no chunk for longer than `dom_upload_timeout_secs`.
+    pub fn expire(&self) -> usize {
+        let now = self.clock.now_millis();
+        let mut uploads = self.uploads.lock().unwrap_or_else(|e| e.into_inner());
+        let expired: Vec<String> = uploads
+            .iter()
+            .filter(|(_, upload)| now - upload.last_activity > self.timeout_ms)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in &expired {
+            if let Some(upload) = uploads.remove(id) {
+                tracing::info!(upload_id = %id, chunks = upload.chunks.len(), "Abandoned DOM upload expired");
+                discard(&upload);
+            }
+        }
+        expired.len()
+    }
+
+    // Drops every upload in progress; used on shutdown.
+    pub fn clear(&self) -> usize {
+        let mut uploads = self.uploads.lock().unwrap_or_else(|e| e.into_inner());
+        let open = uploads.len();
+        uploads.clear();
+        if let Err(e) = remove_uploads(&self.dir) {
+            tracing::warn!(path = %self.dir.display(), error = %e, "Failed to remove DOM uploads");
+        }
+        open
+    }
+}
+
+fn chunk_path(dir: &Path, seq: u32) -> PathBuf {
+    dir.join(format!("{:010}.part", seq))
+}
+
+fn digest(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn discard(upload: &Upload) {
+    if let Err(e) = remove_dir(&upload.dir) {
+        tracing::warn!(path = %upload.dir.display(), error = %e, "Failed to remove DOM upload");
+    }
+}
+
+// Removes the upload directories under `dir`, recognized by holding nothing
+// but chunk files; anything else found there is left alone.
+fn remove_uploads(dir: &Path) -> std::io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() && holds_only_chunks(&entry.path())? {
+            remove_dir(&entry.path())?;
+        }
+    }
+    // Fails while foreign entries remain, which is fine.
+    let _ = fs::remove_dir(dir);
+    Ok(())
+}
+
+fn holds_only_chunks(dir: &Path) -> std::io::Result<bool> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let is_chunk = entry.file_type()?.is_file()
+            && entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".part"))
+                .is_some_and(|seq| seq.len() == 10 && seq.bytes().all(|b| b.is_ascii_digit()));
+        if !is_chunk {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn remove_dir(dir: &Path) -> std::io::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -1,39 +1,138 @@
-use crate::config::DebugToolsConfig;
+use super::annotations::AnnotationStore;
+use super::buffer_pool::BufferPool;
+use super::log_repair::forget_repair;
+use super::log_rotation::{rotate_if_full, RotatedNames};
+use super::ownership::OwnedFiles;
+use super::plugin_log::{is_live_plugin_log, plugin_log_files};
+use super::secure_delete::{overwrite_with_zeros, SecureDelete};
+use super::snapshot_index::{scan_snapshots, snapshot_file_timestamp, SnapshotIndex};
+use super::storage::{DurabilityStats, WritePolicy};
+use crate::analysis::sourcemap::StackResolver;
+use crate::config::{
+    normalize_file_name, sanitize_filename, ConfigError, DebugToolsConfig, EffectiveConfig,
+};
 use crate::domain::{
-    ConsoleLogEntry, DebugSnapshot, DomSnapshotMetadata, DomSnapshotResult, DomState,
-    RepositoryError, SnapshotRepository,
+    csv_header, html_report_head, html_report_tail, render_csv_row, render_html_row,
+    render_timeline_event, timeline_head, timeline_tail, ArtifactKind, ConsoleLogEntry,
+    DebugSnapshot, DeletedFile, DomDelta, DomSnapshotMetadata, DomSnapshotResult, DomState,
+    DurabilityLatency, FinalBeaconRecord, InternalActivity, LegalHolds, LogExportFormat,
+    LogExportResult, LogQuery, LogQueryService, NetworkLogEntry, RepositoryError, RetentionPolicy,
+    SnapshotListEntry, SnapshotRepository,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::io::ErrorKind;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const INSTANCE_LOCK_FILE: &str = ".debug-tools.lock";
+pub(crate) const DOM_METADATA_PREFIX: &str = "<!--\nDOM Snapshot Metadata:\n";
+pub(crate) const DOM_METADATA_SUFFIX: &str = "\n-->\n";
 
 #[derive(Debug, Default)]
 pub struct ClearLogFilesReport {
     pub deleted_paths: Vec<PathBuf>,
     pub truncated_paths: Vec<PathBuf>,
     pub failed_paths: Vec<PathBuf>,
+    pub held_paths: Vec<PathBuf>,
+    // Matched by name but not created by the plugin; left in place.
+    pub skipped_foreign: Vec<PathBuf>,
+    // Every deleted or truncated path, with whether it was overwritten.
+    pub deletions: Vec<DeletedFile>,
+}
+
+#[derive(Debug, Default)]
+pub struct RetentionReport {
+    pub pruned_snapshots: Vec<PathBuf>,
+    pub orphaned_artifacts: Vec<PathBuf>,
+    pub expired_logs: Vec<PathBuf>,
+    pub failed_paths: Vec<PathBuf>,
+    // Would have been removed but are under legal hold.
+    pub held_paths: Vec<PathBuf>,
+    // Not created by the plugin, so never removed.
+    pub skipped_foreign: Vec<PathBuf>,
+    pub retained_snapshots: usize,
+    // Every removed path, with whether it was overwritten.
+    pub deletions: Vec<DeletedFile>,
 }
 
 pub struct FileSystemRepository {
     config: Arc<DebugToolsConfig>,
     app_name: String,
     pid: u32,
+    activity: Arc<InternalActivity>,
+    snapshot_index: Option<Arc<SnapshotIndex>>,
+    owned_files: Option<Arc<OwnedFiles>>,
+    buffer_pool: Option<Arc<BufferPool>>,
+    durability_stats: DurabilityStats,
 }
 
 impl FileSystemRepository {
-    pub fn new(config: Arc<DebugToolsConfig>, app_name: String) -> Self {
+    pub fn new(
+        config: Arc<DebugToolsConfig>,
+        app_name: String,
+        pid: u32,
+        activity: Arc<InternalActivity>,
+    ) -> Self {
         Self {
             config,
             app_name,
-            pid: std::process::id(),
+            pid,
+            activity,
+            snapshot_index: None,
+            owned_files: None,
+            buffer_pool: None,
+            durability_stats: DurabilityStats::default(),
+        }
+    }
+
+    pub fn with_snapshot_index(mut self, index: Arc<SnapshotIndex>) -> Self {
+        self.snapshot_index = Some(index);
+        self
+    }
+
+    pub fn with_owned_files(mut self, owned: Arc<OwnedFiles>) -> Self {
+        self.owned_files = Some(owned);
+        self
+    }
+
+    pub fn with_buffer_pool(mut self, pool: Arc<BufferPool>) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
+    fn take_buffer(&self) -> Vec<u8> {
+        self.buffer_pool
+            .as_ref()
+            .map_or_else(Vec::new, |pool| pool.take())
+    }
+
+    fn give_buffer(&self, buffer: Vec<u8>) {
+        if let Some(pool) = &self.buffer_pool {
+            pool.give(buffer);
+        }
+    }
+
+    fn register(&self, path: &Path) {
+        if let Some(owned) = &self.owned_files {
+            owned.register(path);
         }
     }
 
     fn ensure_directories(&self) -> Result<(), RepositoryError> {
-        fs::create_dir_all(self.config.screenshot_dir())?;
-        fs::create_dir_all(self.config.dom_snapshot_dir())?;
+        let paths = self.config.artifact_paths();
+        for dir in [&paths.snapshots, &paths.screenshots, &paths.dom_snapshots] {
+            fs::create_dir_all(dir)?;
+        }
+
+        let root = log_root(&self.config)?;
+        ensure_within_root(&root, &self.config.log_dir)?;
+        for dir in [&paths.snapshots, &paths.dom_snapshots] {
+            ensure_within_root(&artifact_root(&self.config, dir)?, dir)?;
+        }
         Ok(())
     }
 
@@ -46,13 +145,37 @@ impl SnapshotRepository for FileSystemRepository {
     fn save_snapshot(&self, snapshot: &DebugSnapshot) -> Result<PathBuf, RepositoryError> {
         self.ensure_directories()?;
 
-        let filename = format!("snapshot_{}.json", snapshot.timestamp);
-        let path = self.config.log_dir.join(filename);
+        let path = self.config.snapshot_path(snapshot.timestamp);
+
+        let mut json = self.take_buffer();
+        serde_json::to_writer_pretty(&mut json, snapshot)?;
+        let kind = if snapshot.is_crash() {
+            ArtifactKind::CrashSnapshot
+        } else {
+            ArtifactKind::Snapshot
+        };
+        let policy = WritePolicy::for_artifact(&self.config, kind);
+        let synced = policy.retry(|| policy.replace_file(&path, &json))?;
+        self.durability_stats.record(policy.durability, synced);
+        self.register(&path);
 
-        let json = serde_json::to_string_pretty(snapshot)?;
-        fs::write(&path, json)?;
+        if let Some(index) = &self.snapshot_index {
+            index.record(SnapshotListEntry {
+                path: path.clone(),
+                timestamp: snapshot.timestamp,
+                size_bytes: json.len() as u64,
+                route: snapshot
+                    .route_state
+                    .as_ref()
+                    .and_then(DebugSnapshot::route_key),
+                size_report: snapshot.size_report.clone(),
+                legacy: false,
+                annotations: snapshot.annotations.clone(),
+            });
+        }
 
-        tracing::info!(path = %path.display(), "Debug snapshot saved");
+        self.give_buffer(json);
+        self.activity.record_snapshot();
 
         Ok(path)
     }
@@ -72,17 +195,28 @@ impl SnapshotRepository for FileSystemRepository {
             title: dom.title.clone(),
             timestamp: dom.captured_at,
             viewport: dom.viewport.clone(),
+            shadow_dom_included: dom.shadow_dom_included,
+            replacement_chars: dom.replacement_chars,
         };
 
-        let metadata_json = serde_json::to_string_pretty(&metadata)?;
-        let full_html = format!(
-            "<!--\nDOM Snapshot Metadata:\n{}\n-->\n{}",
-            metadata_json, dom.html
-        );
+        let metadata_json = comment_safe_json(&serde_json::to_string_pretty(&metadata)?);
+        let mut full_html = self.take_buffer();
+        for part in [
+            DOM_METADATA_PREFIX,
+            &metadata_json,
+            DOM_METADATA_SUFFIX,
+            &dom.html,
+        ] {
+            full_html.extend_from_slice(part.as_bytes());
+        }
 
-        fs::write(&path, full_html)?;
+        let policy = WritePolicy::for_artifact(&self.config, ArtifactKind::DomSnapshot);
+        let synced = policy.retry(|| policy.replace_file(&path, &full_html))?;
+        self.durability_stats.record(policy.durability, synced);
+        self.register(&path);
+        self.give_buffer(full_html);
 
-        tracing::info!(path = %path.display(), "DOM snapshot saved");
+        self.activity.record_dom_snapshot();
 
         Ok(DomSnapshotResult { path, metadata })
     }
@@ -93,41 +227,219 @@ impl SnapshotRepository for FileSystemRepository {
         }
 
         let path = self.console_log_path();
-
-        let mut file = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&path)?;
-
+        let mut lines = Vec::new();
         for entry in logs {
-            let line = serde_json::to_string(entry)?;
-            writeln!(file, "{}", line)?;
+            serde_json::to_writer(&mut lines, entry)?;
+            lines.push(b'\n');
         }
 
-        tracing::debug!(
-            path = %path.display(),
-            count = logs.len(),
-            "Console logs appended"
-        );
+        // A batch is never split across files: the live log is rotated before
+        // the batch that would take it past `max_log_size_bytes`.
+        let rotated = rotate_if_full(
+            &path,
+            lines.len() as u64,
+            self.config.max_log_size_bytes,
+            &RotatedNames::console_log(&path),
+        )?;
+        if let Some(rotated) = rotated {
+            self.register(&rotated);
+        }
+
+        // Only the open is retried: a retried append could duplicate lines.
+        let policy = WritePolicy::for_artifact(&self.config, ArtifactKind::ConsoleLog);
+        let mut file =
+            policy.retry(|| fs::OpenOptions::new().create(true).append(true).open(&path))?;
+        self.register(&path);
+
+        file.write_all(&lines)?;
+        let synced = policy.sync(&mut file)?;
+        self.durability_stats.record(policy.durability, synced);
+
+        self.activity.record_console_batch(logs.len());
 
         Ok(path)
     }
+
+    fn durability_latency(&self) -> Vec<DurabilityLatency> {
+        self.durability_stats.report()
+    }
+}
+
+// Metadata is embedded in an HTML comment, so a URL or title containing `-->`
+// would end it early. Angle brackets only occur inside JSON strings here, where
+// the `\u003c`/`\u003e` escapes keep the JSON equivalent. The html itself
+// follows the comment and is written unchanged.
+fn comment_safe_json(json: &str) -> String {
+    json.replace('<', "\\u003c").replace('>', "\\u003e")
+}
+
+pub fn save_dom_delta(
+    config: &DebugToolsConfig,
+    delta: &DomDelta,
+    owned: &OwnedFiles,
+) -> Result<PathBuf, RepositoryError> {
+    let dir = config.dom_snapshot_dir();
+    fs::create_dir_all(&dir)?;
+    ensure_within_root(&artifact_root(config, &dir)?, &dir)?;
+
+    let path = dir.join(format!("dom_delta_{}.json", delta.timestamp));
+    let json = serde_json::to_vec(delta)?;
+    let policy = WritePolicy::for_artifact(config, ArtifactKind::DomSnapshot);
+    policy.retry(|| policy.replace_file(&path, &json))?;
+    owned.register(&path);
+
+    Ok(path)
+}
+
+// Reads a saved DOM snapshot back without the metadata comment `save_dom`
+// puts in front of it.
+pub fn read_dom_snapshot_html(path: &Path) -> Result<String, RepositoryError> {
+    let content = fs::read_to_string(path)?;
+    let html = content
+        .strip_prefix(DOM_METADATA_PREFIX)
+        .and_then(|rest| rest.split_once(DOM_METADATA_SUFFIX))
+        .map(|(_, html)| html.to_string())
+        .unwrap_or(content);
+    Ok(html)
+}
+
+// Loads a saved snapshot. Files that are valid JSON but match no known
+// schema are wrapped by `DebugSnapshot::from_legacy` with the timestamp
+// taken from the file name.
+pub fn load_snapshot(path: &Path) -> Result<DebugSnapshot, RepositoryError> {
+    let bytes = fs::read(path)?;
+    let typed_err = match serde_json::from_slice::<DebugSnapshot>(&bytes) {
+        Ok(snapshot) => return Ok(snapshot),
+        Err(e) => e,
+    };
+
+    let payload: serde_json::Value = serde_json::from_slice(&bytes)?;
+    let timestamp = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(snapshot_file_timestamp)
+        .map(|(timestamp, _)| timestamp)
+        .ok_or(RepositoryError::Serialization(typed_err))?;
+
+    Ok(DebugSnapshot::from_legacy(payload, timestamp))
+}
+
+// Sets `annotations[key]` in a saved snapshot and rewrites the file in
+// place. The JSON is edited as a value so fields this version does not know
+// about survive; a legacy payload must be an object to be annotated.
+pub fn annotate_snapshot(
+    config: &DebugToolsConfig,
+    path: &Path,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<SnapshotListEntry, RepositoryError> {
+    let (timestamp, legacy) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(snapshot_file_timestamp)
+        .ok_or_else(|| RepositoryError::Load(format!("Not a snapshot: {}", path.display())))?;
+
+    let mut snapshot: serde_json::Value = serde_json::from_slice(&fs::read(path)?)?;
+    let object = snapshot
+        .as_object_mut()
+        .ok_or_else(|| RepositoryError::Load("Snapshot is not a JSON object".to_string()))?;
+    let annotations = object
+        .entry("annotations")
+        .or_insert_with(|| serde_json::Value::Object(Default::default()));
+    let Some(annotations) = annotations.as_object_mut() else {
+        return Err(RepositoryError::Load(
+            "Snapshot `annotations` is not an object".to_string(),
+        ));
+    };
+    annotations.insert(key.to_string(), value);
+    let annotations = annotations.clone();
+
+    let json = serde_json::to_string_pretty(&snapshot)?;
+    let policy = WritePolicy::for_config(config);
+    policy.retry(|| policy.replace_file(path, json.as_bytes()))?;
+
+    Ok(SnapshotListEntry {
+        path: path.to_path_buf(),
+        timestamp,
+        size_bytes: json.len() as u64,
+        route: snapshot
+            .get("route_state")
+            .and_then(DebugSnapshot::route_key),
+        size_report: snapshot
+            .get("size_report")
+            .and_then(|report| serde_json::from_value(report.clone()).ok()),
+        legacy,
+        annotations,
+    })
+}
+
+pub fn save_final_beacon(
+    config: &DebugToolsConfig,
+    record: &FinalBeaconRecord,
+    owned: &OwnedFiles,
+) -> Result<PathBuf, RepositoryError> {
+    fs::create_dir_all(&config.log_dir)?;
+    let path = config.final_beacon_path(record.received_at);
+    let json = serde_json::to_vec_pretty(record)?;
+    let policy = WritePolicy::for_artifact(config, ArtifactKind::ConsoleLog);
+    policy.retry(|| policy.replace_file(&path, &json))?;
+    owned.register(&path);
+
+    Ok(path)
+}
+
+pub fn append_network_logs(
+    config: &DebugToolsConfig,
+    app_name: &str,
+    pid: u32,
+    entries: &[NetworkLogEntry],
+    activity: &InternalActivity,
+    owned: &OwnedFiles,
+) -> Result<PathBuf, RepositoryError> {
+    let path = config.network_log_path(app_name, pid);
+    if entries.is_empty() {
+        return Ok(path);
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    owned.register(&path);
+
+    for entry in entries {
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    }
+    WritePolicy::for_artifact(config, ArtifactKind::NetworkLog).sync(&mut file)?;
+
+    activity.record_network_batch(entries.len());
+
+    Ok(path)
 }
 
 pub fn reset_console_logs(
     config: &DebugToolsConfig,
     app_name: &str,
     pid: u32,
+    owned: &OwnedFiles,
 ) -> Result<PathBuf, RepositoryError> {
     let path = config.frontend_log_path(app_name, pid);
 
+    let secure = SecureDelete::configured(config, &config.console_log_dir());
+    let (securely_deleted, note) = if path.is_file() {
+        overwrite_first(&path, secure.as_ref())
+    } else {
+        (false, None)
+    };
     fs::OpenOptions::new()
         .create(true)
         .write(true)
         .truncate(true)
         .open(&path)?;
+    owned.register(&path);
+    forget_repair(config, &path, owned)?;
 
-    tracing::info!(path = %path.display(), "Console logs reset");
+    tracing::info!(path = %path.display(), securely_deleted, note, "Console logs reset");
 
     Ok(path)
 }
@@ -148,49 +460,243 @@ fn remove_or_truncate(path: &PathBuf) -> Result<bool, RepositoryError> {
     }
 }
 
+fn log_root(config: &DebugToolsConfig) -> Result<PathBuf, RepositoryError> {
+    config.resolve_log_root().map_err(|e| match e {
+        ConfigError::CreateDirectory(io) => RepositoryError::Io(io),
+        other => {
+            tracing::warn!(error = %other, "Refusing to use log directory");
+            RepositoryError::OutsideLogRoot(config.log_dir.clone())
+        }
+    })
+}
+
+// The resolved root of `directory`'s artifact kind, `log_dir`'s unless the
+// kind has a root override.
+pub(crate) fn artifact_root(
+    config: &DebugToolsConfig,
+    directory: &Path,
+) -> Result<PathBuf, RepositoryError> {
+    config
+        .resolve_artifact_root(directory)
+        .map_err(|e| match e {
+            ConfigError::CreateDirectory(io) => RepositoryError::Io(io),
+            other => {
+                tracing::warn!(error = %other, "Refusing to use artifact root");
+                RepositoryError::OutsideLogRoot(directory.to_path_buf())
+            }
+        })
+}
+
+// A symlinked subdirectory could point anywhere; resolve it before writing
+// into or deleting from it.
+pub(crate) fn ensure_within_root(root: &Path, directory: &Path) -> Result<(), RepositoryError> {
+    let resolved = directory.canonicalize()?;
+    if !resolved.starts_with(root) {
+        return Err(RepositoryError::OutsideLogRoot(resolved));
+    }
+    Ok(())
+}
+
+// With `secure`, the contents are overwritten before the file goes. A file
+// that cannot be overwritten is still deleted, with a note saying so.
+fn clear_file(path: PathBuf, secure: Option<&SecureDelete>, report: &mut ClearLogFilesReport) {
+    let (securely_deleted, note) = overwrite_first(&path, secure);
+    match remove_or_truncate(&path) {
+        Ok(truncated) => {
+            report.deletions.push(DeletedFile {
+                path: path.clone(),
+                securely_deleted,
+                note,
+            });
+            if truncated {
+                report.truncated_paths.push(path);
+            } else {
+                report.deleted_paths.push(path);
+            }
+        }
+        Err(_) => report.failed_paths.push(path),
+    }
+}
+
+fn overwrite_first(path: &Path, secure: Option<&SecureDelete>) -> (bool, Option<String>) {
+    match secure {
+        None => (false, None),
+        Some(secure) if !secure.overwrites() => (false, secure.note.clone()),
+        Some(_) => match overwrite_with_zeros(path) {
+            Ok(()) => (true, None),
+            Err(e) => (false, Some(format!("not overwritten: {}", e))),
+        },
+    }
+}
+
+// Removes a file the plugin owns, overwriting it first with `secure`, as
+// `clear_file` does.
+pub(crate) fn delete_owned_file(
+    path: &Path,
+    secure: Option<&SecureDelete>,
+) -> std::io::Result<DeletedFile> {
+    let (securely_deleted, note) = overwrite_first(path, secure);
+    fs::remove_file(path)?;
+    Ok(DeletedFile {
+        path: path.to_path_buf(),
+        securely_deleted,
+        note,
+    })
+}
+
+// tauri-plugin-log's files sit outside the log root and are never recorded
+// as owned; `manage_plugin_log` is the host's consent instead. The live
+// file is truncated rather than deleted, as the log plugin keeps it open.
+fn clear_plugin_log_files(
+    config: &DebugToolsConfig,
+    holds: &LegalHolds,
+    secure: Option<&SecureDelete>,
+    report: &mut ClearLogFilesReport,
+) {
+    for path in plugin_log_files(config) {
+        let is_file = fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_file());
+        if !is_file {
+            continue;
+        }
+        if holds.holds_path(&path) {
+            report.held_paths.push(path);
+            continue;
+        }
+        if !is_live_plugin_log(config, &path) {
+            clear_file(path, secure, report);
+            continue;
+        }
+
+        let (securely_deleted, note) = overwrite_first(&path, secure);
+        let truncated = fs::OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&path);
+        match truncated {
+            Ok(_) => {
+                report.deletions.push(DeletedFile {
+                    path: path.clone(),
+                    securely_deleted,
+                    note,
+                });
+                report.truncated_paths.push(path);
+            }
+            Err(_) => report.failed_paths.push(path),
+        }
+    }
+}
+
 fn clear_directory_files(
+    config: &DebugToolsConfig,
     directory: &PathBuf,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+    secure: Option<&SecureDelete>,
     report: &mut ClearLogFilesReport,
 ) -> Result<(), RepositoryError> {
     if !directory.exists() {
         return Ok(());
     }
 
+    let within_root =
+        artifact_root(config, directory).and_then(|root| ensure_within_root(&root, directory));
+    if let Err(e) = within_root {
+        tracing::warn!(directory = %directory.display(), error = %e, "Skipping cleanup outside the log directory");
+        report.failed_paths.push(directory.clone());
+        return Ok(());
+    }
+
     for entry in fs::read_dir(directory)? {
         let entry = entry?;
         let path = entry.path();
 
-        if !path.is_file() {
+        // `file_type` does not follow symlinks, so linked files are never
+        // truncated through.
+        if !entry.file_type()?.is_file() {
             continue;
         }
-
-        match remove_or_truncate(&path) {
-            Ok(true) => report.truncated_paths.push(path),
-            Ok(false) => report.deleted_paths.push(path),
-            Err(_) => report.failed_paths.push(path),
+        if !owned.is_owned(&path) {
+            report.skipped_foreign.push(path);
+            continue;
+        }
+        if holds.holds_path(&path) {
+            report.held_paths.push(path);
+            continue;
         }
+
+        clear_file(path, secure, report);
     }
 
     Ok(())
 }
 
+// The log root's entries, followed by those of the console, network and
+// backend log roots that are overridden.
+fn read_log_dirs(
+    config: &DebugToolsConfig,
+    root: &Path,
+) -> Result<Vec<std::io::Result<fs::DirEntry>>, RepositoryError> {
+    let mut entries: Vec<_> = fs::read_dir(root)?.collect();
+    let paths = config.artifact_paths();
+    for dir in [
+        &paths.console_logs,
+        &paths.network_logs,
+        &paths.backend_logs,
+    ] {
+        if *dir != config.log_dir && dir.exists() {
+            ensure_within_root(&artifact_root(config, dir)?, dir)?;
+            entries.extend(fs::read_dir(dir)?);
+        }
+    }
+    Ok(entries)
+}
+
+// Only files the plugin created are removed; others that match the names
+// below are reported in `skipped_foreign`.
 pub fn clear_debug_log_files(
     config: &DebugToolsConfig,
     app_name: &str,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+) -> Result<ClearLogFilesReport, RepositoryError> {
+    clear_debug_log_files_with_secure_delete(config, app_name, holds, owned, config.secure_delete)
+}
+
+// `secure_delete` in place of the configured one, for a per-call override.
+pub fn clear_debug_log_files_with_secure_delete(
+    config: &DebugToolsConfig,
+    app_name: &str,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+    secure_delete: bool,
 ) -> Result<ClearLogFilesReport, RepositoryError> {
     let mut report = ClearLogFilesReport::default();
-    let sanitized_name = app_name.replace(' ', "_");
-    let frontend_prefix = format!("frontend_console_{}", sanitized_name);
+    // Older versions only replaced spaces. Both sides are compared in NFC,
+    // since a file named in NFD on disk is still this app's log.
+    let legacy = normalize_file_name(&app_name.replace(' ', "_"));
+    let prefixes: Vec<String> = [sanitize_filename(app_name), legacy]
+        .iter()
+        .flat_map(|name| {
+            [
+                format!("frontend_console_{}", name),
+                format!("network_{}", name),
+            ]
+        })
+        .collect();
 
     if !config.log_dir.exists() {
         return Ok(report);
     }
 
-    for entry in fs::read_dir(&config.log_dir)? {
+    let root = log_root(config)?;
+    let secure = secure_delete.then(|| SecureDelete::for_directory(&root));
+    let secure = secure.as_ref();
+
+    for entry in read_log_dirs(config, &root)? {
         let entry = entry?;
         let path = entry.path();
 
-        if !path.is_file() {
+        if !entry.file_type()?.is_file() {
             continue;
         }
 
@@ -198,31 +704,722 @@ pub fn clear_debug_log_files(
             continue;
         };
 
-        let should_clear_frontend =
-            filename.starts_with(&frontend_prefix) && filename.ends_with(".jsonl");
+        let normalized = normalize_file_name(filename);
+        let should_clear_frontend = prefixes
+            .iter()
+            .any(|prefix| normalized.starts_with(prefix.as_str()))
+            && filename.ends_with(".jsonl");
         let should_clear_backend =
             filename == "rust_debug.log" || filename.starts_with("rust_debug.log.");
 
         if !(should_clear_frontend || should_clear_backend) {
             continue;
         }
-
-        match remove_or_truncate(&path) {
-            Ok(true) => report.truncated_paths.push(path),
-            Ok(false) => report.deleted_paths.push(path),
-            Err(_) => report.failed_paths.push(path),
+        if !owned.is_owned(&path) {
+            report.skipped_foreign.push(path);
+            continue;
         }
+        if holds.holds_path(&path) {
+            report.held_paths.push(path);
+            continue;
+        }
+
+        clear_file(path, secure, &mut report);
     }
 
-    clear_directory_files(&config.dom_snapshot_dir(), &mut report)?;
-    clear_directory_files(&config.screenshot_dir(), &mut report)?;
+    for directory in [config.dom_snapshot_dir(), config.screenshot_dir()] {
+        clear_directory_files(config, &directory, holds, owned, secure, &mut report)?;
+    }
+    if config.manage_plugin_log {
+        clear_plugin_log_files(config, holds, secure, &mut report);
+    }
+    owned.forget(&report.deleted_paths);
 
     tracing::info!(
         deleted = report.deleted_paths.len(),
+        securely_deleted = report
+            .deletions
+            .iter()
+            .filter(|deletion| deletion.securely_deleted)
+            .count(),
         truncated = report.truncated_paths.len(),
         failed = report.failed_paths.len(),
+        held = report.held_paths.len(),
+        skipped_foreign = report.skipped_foreign.len(),
         "Debug log files cleanup finished"
     );
 
     Ok(report)
 }
+
+// Only the artifact references, plus what decides the snapshot's kind and
+// holds, are needed to decide what a snapshot keeps alive.
+#[derive(Default, Deserialize)]
+struct SnapshotArtifacts {
+    #[serde(default)]
+    screenshot_path: Option<PathBuf>,
+    #[serde(default)]
+    dom_snapshot_path: Option<PathBuf>,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    session_id: Option<String>,
+}
+
+impl SnapshotArtifacts {
+    fn read(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn kind(&self) -> ArtifactKind {
+        if self.label.as_deref() == Some("crash") {
+            ArtifactKind::CrashSnapshot
+        } else {
+            ArtifactKind::Snapshot
+        }
+    }
+
+    fn paths(self) -> Vec<PathBuf> {
+        self.screenshot_path
+            .into_iter()
+            .chain(self.dom_snapshot_path)
+            .collect()
+    }
+}
+
+// Snapshots whose screenshot or DOM snapshot is `artifact`. Reused
+// screenshots can have several.
+pub fn snapshots_referencing(
+    config: &DebugToolsConfig,
+    artifact: &Path,
+) -> Result<Vec<PathBuf>, RepositoryError> {
+    Ok(scan_snapshots(&config.snapshot_dir())?
+        .into_iter()
+        .filter(|snapshot| {
+            SnapshotArtifacts::read(&snapshot.path)
+                .paths()
+                .iter()
+                .any(|path| path == artifact)
+        })
+        .map(|snapshot| snapshot.path)
+        .collect())
+}
+
+// The snapshots referencing `artifact` that a legal hold keeps, by path or
+// by session.
+pub fn held_snapshots_referencing(
+    config: &DebugToolsConfig,
+    artifact: &Path,
+    holds: &LegalHolds,
+) -> Result<Vec<PathBuf>, RepositoryError> {
+    Ok(snapshots_referencing(config, artifact)?
+        .into_iter()
+        .filter(|snapshot| {
+            holds.holds_path(snapshot)
+                || holds.holds_session(SnapshotArtifacts::read(snapshot).session_id.as_deref())
+        })
+        .collect())
+}
+
+fn age_cutoff_secs(now_secs: i64, ttl_secs: Option<u64>) -> Option<i64> {
+    ttl_secs.map(|age| now_secs.saturating_sub(age as i64))
+}
+
+// Removes full snapshots beyond the policy's count and per-kind age limits,
+// oldest first. Held snapshots stay, along with what they reference; foreign
+// ones neither count nor go. Returns the artifacts still referenced by the
+// snapshots that remain and those referenced only by pruned ones.
+pub fn prune_snapshots(
+    config: &DebugToolsConfig,
+    policy: &RetentionPolicy,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+    now_secs: i64,
+    report: &mut RetentionReport,
+) -> Result<(HashSet<PathBuf>, HashSet<PathBuf>), RepositoryError> {
+    let mut retained = HashSet::new();
+    let mut released = HashSet::new();
+    if !config.log_dir.exists() {
+        return Ok((retained, released));
+    }
+
+    let snapshot_dir = config.snapshot_dir();
+    if !snapshot_dir.exists() {
+        return Ok((retained, released));
+    }
+    ensure_within_root(&artifact_root(config, &snapshot_dir)?, &snapshot_dir)?;
+    let (snapshots, foreign): (Vec<_>, Vec<_>) = scan_snapshots(&snapshot_dir)?
+        .into_iter()
+        .partition(|snapshot| owned.is_owned(&snapshot.path));
+    for snapshot in foreign {
+        retained.extend(SnapshotArtifacts::read(&snapshot.path).paths());
+        report.skipped_foreign.push(snapshot.path);
+    }
+
+    let over_count = policy
+        .max_snapshots
+        .map_or(0, |max| snapshots.len().saturating_sub(max));
+
+    let secure = SecureDelete::configured(config, &config.log_dir);
+    for (position, snapshot) in snapshots.into_iter().enumerate() {
+        let fields = SnapshotArtifacts::read(&snapshot.path);
+        // Snapshots are stamped in seconds, like `now_secs`.
+        let expired = age_cutoff_secs(now_secs, policy.ttl_for(fields.kind()))
+            .is_some_and(|cutoff| snapshot.timestamp < cutoff);
+        if position >= over_count && !expired {
+            retained.extend(fields.paths());
+            report.retained_snapshots += 1;
+            continue;
+        }
+        if holds.holds_path(&snapshot.path) || holds.holds_session(fields.session_id.as_deref()) {
+            retained.extend(fields.paths());
+            report.held_paths.push(snapshot.path);
+            report.retained_snapshots += 1;
+            continue;
+        }
+
+        let artifacts = fields.paths();
+        match delete_owned_file(&snapshot.path, secure.as_ref()) {
+            Ok(deletion) => {
+                report.deletions.push(deletion);
+                released.extend(artifacts);
+                report.pruned_snapshots.push(snapshot.path);
+            }
+            Err(e) => {
+                tracing::warn!(path = %snapshot.path.display(), error = %e, "Failed to prune snapshot");
+                retained.extend(artifacts);
+                report.failed_paths.push(snapshot.path);
+                report.retained_snapshots += 1;
+            }
+        }
+    }
+
+    Ok((retained, released))
+}
+
+fn modified_before(entry: &fs::DirEntry, ttl_secs: Option<u64>) -> bool {
+    let Some(cutoff) =
+        ttl_secs.and_then(|age| SystemTime::now().checked_sub(Duration::from_secs(age)))
+    else {
+        return false;
+    };
+    entry
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified < cutoff)
+}
+
+// Deletes DOM snapshots and screenshots that no remaining snapshot refers
+// to, when they were released by `prune_snapshots` or are older than their
+// age limit. Held and foreign paths are never removed.
+pub fn orphan_cleanup(
+    config: &DebugToolsConfig,
+    policy: &RetentionPolicy,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+    referenced: &HashSet<PathBuf>,
+    released: &HashSet<PathBuf>,
+    report: &mut RetentionReport,
+) -> Result<(), RepositoryError> {
+    if !config.log_dir.exists() {
+        return Ok(());
+    }
+
+    log_root(config)?;
+
+    let secure = SecureDelete::configured(config, &config.log_dir);
+    for (directory, ttl) in [
+        (
+            config.dom_snapshot_dir(),
+            policy.ttl_for(ArtifactKind::DomSnapshot),
+        ),
+        (config.screenshot_dir(), policy.max_snapshot_age_secs),
+    ] {
+        if !directory.exists() {
+            continue;
+        }
+        let within_root = artifact_root(config, &directory)
+            .and_then(|root| ensure_within_root(&root, &directory));
+        if let Err(e) = within_root {
+            tracing::warn!(directory = %directory.display(), error = %e, "Skipping cleanup outside the log directory");
+            report.failed_paths.push(directory);
+            continue;
+        }
+
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            if !entry.file_type()?.is_file() || referenced.contains(&path) {
+                continue;
+            }
+
+            if !(modified_before(&entry, ttl) || released.contains(&path)) {
+                continue;
+            }
+            if !owned.is_owned(&path) {
+                report.skipped_foreign.push(path);
+                continue;
+            }
+            if holds.holds_path(&path) {
+                report.held_paths.push(path);
+                continue;
+            }
+
+            match delete_owned_file(&path, secure.as_ref()) {
+                Ok(deletion) => {
+                    report.deletions.push(deletion);
+                    report.orphaned_artifacts.push(path);
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to remove orphaned artifact");
+                    report.failed_paths.push(path);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Console and network log files older than their kind's TTL. Files are
+// matched by prefix, so logs of earlier runs and other app names go too, as
+// long as the plugin wrote them.
+pub fn expire_log_files(
+    config: &DebugToolsConfig,
+    policy: &RetentionPolicy,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+    report: &mut RetentionReport,
+) -> Result<(), RepositoryError> {
+    let console_ttl = policy.ttl_for(ArtifactKind::ConsoleLog);
+    let network_ttl = policy.ttl_for(ArtifactKind::NetworkLog);
+    if (console_ttl.is_none() && network_ttl.is_none()) || !config.log_dir.exists() {
+        return Ok(());
+    }
+
+    let root = log_root(config)?;
+    let secure = SecureDelete::configured(config, &root);
+    for entry in read_log_dirs(config, &root)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let ttl = if !name.ends_with(".jsonl") {
+            None
+        } else if name.starts_with("frontend_console_") {
+            console_ttl
+        } else if name.starts_with("network_") {
+            network_ttl
+        } else {
+            None
+        };
+        if !modified_before(&entry, ttl) {
+            continue;
+        }
+        if !owned.is_owned(&path) {
+            report.skipped_foreign.push(path);
+            continue;
+        }
+        if holds.holds_path(&path) {
+            report.held_paths.push(path);
+            continue;
+        }
+
+        match delete_owned_file(&path, secure.as_ref()) {
+            Ok(deletion) => {
+                report.deletions.push(deletion);
+                report.expired_logs.push(path);
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to remove expired log");
+                report.failed_paths.push(path);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Rotated tauri-plugin-log copies older than the console log TTL. The live
+// file is left to the log plugin's own rotation.
+fn expire_plugin_logs(
+    config: &DebugToolsConfig,
+    policy: &RetentionPolicy,
+    holds: &LegalHolds,
+    report: &mut RetentionReport,
+) {
+    let Some(cutoff) = policy
+        .ttl_for(ArtifactKind::ConsoleLog)
+        .and_then(|age| SystemTime::now().checked_sub(Duration::from_secs(age)))
+    else {
+        return;
+    };
+
+    let secure = SecureDelete::configured(config, &config.log_dir);
+    for path in plugin_log_files(config).into_iter().skip(1) {
+        let expired = fs::symlink_metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified < cutoff);
+        if !expired {
+            continue;
+        }
+        if holds.holds_path(&path) {
+            report.held_paths.push(path);
+            continue;
+        }
+        match delete_owned_file(&path, secure.as_ref()) {
+            Ok(deletion) => {
+                report.deletions.push(deletion);
+                report.expired_logs.push(path);
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to remove expired plugin log");
+                report.failed_paths.push(path);
+            }
+        }
+    }
+}
+
+// Paths in `keep` are never removed, like artifacts a snapshot refers to.
+pub fn apply_retention(
+    config: &DebugToolsConfig,
+    policy: &RetentionPolicy,
+    holds: &LegalHolds,
+    owned: &OwnedFiles,
+    now_secs: i64,
+    keep: &HashSet<PathBuf>,
+) -> Result<RetentionReport, RepositoryError> {
+    let mut report = RetentionReport::default();
+    let (mut referenced, released) =
+        prune_snapshots(config, policy, holds, owned, now_secs, &mut report)?;
+    referenced.extend(keep.iter().cloned());
+    orphan_cleanup(
+        config,
+        policy,
+        holds,
+        owned,
+        &referenced,
+        &released,
+        &mut report,
+    )?;
+    expire_log_files(config, policy, holds, owned, &mut report)?;
+    if config.manage_plugin_log {
+        expire_plugin_logs(config, policy, holds, &mut report);
+    }
+    owned.forget(
+        report
+            .pruned_snapshots
+            .iter()
+            .chain(&report.orphaned_artifacts)
+            .chain(&report.expired_logs),
+    );
+    Ok(report)
+}
+
+pub fn write_effective_config(
+    config: &DebugToolsConfig,
+    effective: &EffectiveConfig,
+    owned: &OwnedFiles,
+) -> Result<PathBuf, RepositoryError> {
+    let history_dir = config.config_history_dir();
+    fs::create_dir_all(&history_dir)?;
+
+    let path = history_dir.join(format!("effective_config_{}.json", effective.written_at));
+    let json = serde_json::to_string(effective)?;
+    let policy = WritePolicy::for_config(config);
+    policy.retry(|| policy.replace_file(&path, json.as_bytes()))?;
+    owned.register(&path);
+
+    let mut previous: Vec<PathBuf> = fs::read_dir(&history_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with("effective_config_") && name.ends_with(".json")
+                })
+                && owned.is_owned(path)
+        })
+        .collect();
+
+    if previous.len() > config.max_config_history_files {
+        // Filenames embed millisecond timestamps of equal width, so lexical order is chronological.
+        previous.sort();
+        let excess = previous.len() - config.max_config_history_files;
+        let mut pruned = Vec::new();
+        let secure = SecureDelete::configured(config, &history_dir);
+        for stale in previous.into_iter().take(excess) {
+            match delete_owned_file(&stale, secure.as_ref()) {
+                Ok(_) => pruned.push(stale),
+                Err(e) => {
+                    tracing::warn!(path = %stale.display(), error = %e, "Failed to prune config history")
+                }
+            }
+        }
+        owned.forget(&pruned);
+    }
+
+    tracing::debug!(path = %path.display(), reason = %effective.reason, "Effective config written");
+
+    Ok(path)
+}
+
+// Every parseable file in `config_history/`, across sessions; unreadable
+// ones are skipped.
+pub fn read_config_history(
+    config: &DebugToolsConfig,
+) -> Result<Vec<(PathBuf, EffectiveConfig)>, RepositoryError> {
+    let history_dir = config.config_history_dir();
+    let entries = match fs::read_dir(&history_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut history = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        let is_history = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("effective_config_") && name.ends_with(".json"));
+        if !is_history {
+            continue;
+        }
+        if let Some(effective) = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<EffectiveConfig>(&bytes).ok())
+        {
+            history.push((path, effective));
+        }
+    }
+    history.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(history)
+}
+
+// Streams every entry matching `query` into a new JSONL file; an existing
+// export is never overwritten. With a resolver, stack traces are rewritten
+// through source maps on the way out, and annotated entries carry their
+// annotation; stored logs are left as they are.
+pub fn export_console_logs(
+    log_query: &dyn LogQueryService,
+    query: &LogQuery,
+    path: &Path,
+    format: LogExportFormat,
+    field_columns: &[String],
+    resolver: Option<&StackResolver>,
+    annotations: &AnnotationStore,
+) -> Result<LogExportResult, RepositoryError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    let mut writer = std::io::BufWriter::new(file);
+
+    let mut entries = 0usize;
+    let mut resolved_stack_traces = 0usize;
+    let head = match format {
+        LogExportFormat::Jsonl => String::new(),
+        LogExportFormat::Html => html_report_head().to_string(),
+        LogExportFormat::Csv => csv_header(field_columns),
+        LogExportFormat::Timeline => timeline_head().to_string(),
+    };
+    let mut write_error: Option<RepositoryError> =
+        writer.write_all(head.as_bytes()).err().map(Into::into);
+    let scanned = log_query.scan(query, &mut |mut entry| {
+        if write_error.is_some() {
+            return;
+        }
+        if resolver.is_some_and(|resolver| resolver.resolve_entry(&mut entry)) {
+            resolved_stack_traces += 1;
+        }
+        annotations.attach(std::slice::from_mut(&mut entry));
+        let line = match format {
+            LogExportFormat::Jsonl => serde_json::to_writer(&mut writer, &entry)
+                .map_err(RepositoryError::from)
+                .and_then(|()| writer.write_all(b"\n").map_err(RepositoryError::from)),
+            LogExportFormat::Html => writer
+                .write_all(render_html_row(&entry).as_bytes())
+                .map_err(RepositoryError::from),
+            LogExportFormat::Csv => writer
+                .write_all(render_csv_row(&entry, field_columns).as_bytes())
+                .map_err(RepositoryError::from),
+            LogExportFormat::Timeline => render_timeline_event(&entry, field_columns)
+                .map_err(RepositoryError::from)
+                .and_then(|event| {
+                    let separator = if entries == 0 { "" } else { ",\n" };
+                    writer
+                        .write_all(format!("{}{}", separator, event).as_bytes())
+                        .map_err(RepositoryError::from)
+                }),
+        };
+        match line {
+            Ok(()) => entries += 1,
+            Err(e) => write_error = Some(e),
+        }
+    });
+
+    let result = scanned
+        .and_then(|()| write_error.map_or(Ok(()), Err))
+        .and_then(|()| match format {
+            LogExportFormat::Jsonl | LogExportFormat::Csv => Ok(()),
+            LogExportFormat::Html => writer
+                .write_all(html_report_tail().as_bytes())
+                .map_err(RepositoryError::from),
+            LogExportFormat::Timeline => writer
+                .write_all(timeline_tail().as_bytes())
+                .map_err(RepositoryError::from),
+        })
+        .and_then(|()| writer.flush().map_err(RepositoryError::from));
+    if let Err(e) = result {
+        drop(writer);
+        let _ = fs::remove_file(path);
+        return Err(e);
+    }
+
+    tracing::info!(
+        path = %path.display(),
+        entries,
+        resolved_stack_traces,
+        "Console logs exported"
+    );
+
+    Ok(LogExportResult {
+        path: path.to_path_buf(),
+        entries,
+        resolved_stack_traces,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct InstanceLockInfo {
+    pid: u32,
+    session_id: String,
+    acquired_at: i64,
+}
+
+#[derive(Debug)]
+pub struct InstanceLock {
+    pub path: PathBuf,
+    // Live process that already owned the configured log directory; this
+    // instance moved to a suffixed subdirectory instead.
+    pub shared_with: Option<u32>,
+    // Dead process whose stale lock was taken over.
+    pub reclaimed_from: Option<u32>,
+}
+
+// Claims `config.log_dir` for this process. When another live process holds
+// it, `config.log_dir` is redirected to `instance_<pid>/` so the two never
+// interleave writes.
+pub fn acquire_instance_lock(
+    config: &mut DebugToolsConfig,
+    pid: u32,
+    session_id: &str,
+    now_millis: i64,
+) -> Result<InstanceLock, RepositoryError> {
+    let info = InstanceLockInfo {
+        pid,
+        session_id: session_id.to_string(),
+        acquired_at: now_millis,
+    };
+
+    let mut shared_with = None;
+    let mut reclaimed_from = None;
+
+    loop {
+        fs::create_dir_all(&config.log_dir)?;
+        let path = config.log_dir.join(INSTANCE_LOCK_FILE);
+
+        if create_lock_file(&path, &info)? {
+            return Ok(InstanceLock {
+                path,
+                shared_with,
+                reclaimed_from,
+            });
+        }
+
+        match read_instance_lock(&path) {
+            Some(holder) if holder.pid == pid => {
+                fs::write(&path, serde_json::to_string(&info)?)?;
+                return Ok(InstanceLock {
+                    path,
+                    shared_with,
+                    reclaimed_from,
+                });
+            }
+            Some(holder) if crate::adapters::platform::process_alive(holder.pid) => {
+                if shared_with.is_some() {
+                    return Err(RepositoryError::Io(std::io::Error::new(
+                        ErrorKind::AddrInUse,
+                        format!("{} is held by pid {}", path.display(), holder.pid),
+                    )));
+                }
+                shared_with = Some(holder.pid);
+                config.log_dir = config.log_dir.join(format!("instance_{}", pid));
+            }
+            // Locks are published fully written, so an unreadable one is corrupt
+            // and treated like a stale one.
+            holder => {
+                reclaimed_from = holder.map(|holder| holder.pid);
+                match fs::remove_file(&path) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+    }
+}
+
+// Writes the lock next to its final path and hard-links it into place, so
+// other instances never observe a half-written lock. Returns false when the
+// lock is already taken.
+fn create_lock_file(path: &Path, info: &InstanceLockInfo) -> Result<bool, RepositoryError> {
+    let staging = path.with_extension(format!("lock.{}", info.pid));
+    fs::write(&staging, serde_json::to_string(info)?)?;
+
+    let linked = fs::hard_link(&staging, path);
+    let _ = fs::remove_file(&staging);
+
+    match linked {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        // Filesystems without hard links fall back to an exclusive create.
+        Err(_) => match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+        {
+            Ok(mut file) => {
+                file.write_all(serde_json::to_string(info)?.as_bytes())?;
+                Ok(true)
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e.into()),
+        },
+    }
+}
+
+pub fn release_instance_lock(lock: &InstanceLock, pid: u32) {
+    // Another instance may have reclaimed the file in the meantime.
+    if read_instance_lock(&lock.path).is_some_and(|holder| holder.pid == pid) {
+        if let Err(e) = fs::remove_file(&lock.path) {
+            tracing::warn!(path = %lock.path.display(), error = %e, "Failed to release instance lock");
+        }
+    }
+}
+
+fn read_instance_lock(path: &Path) -> Option<InstanceLockInfo> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+// Connections served at once; further ones are closed unanswered.
+const MAX_CONNECTIONS: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub path: String,
+    pub query: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn json(status: u16, body: &impl serde::Serialize) -> Self {
+        Self {
+            status,
+            body: serde_json::to_string(body).unwrap_or_else(|_| "null".to_string()),
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+}
+
+pub type HttpHandler = Box<dyn Fn(&HttpRequest) -> HttpResponse + Send + Sync>;
+
+// Read-only GET responder on 127.0.0.1 for monitoring that cannot use IPC.
+// Each connection is served on its own thread, so a client that stalls
+// cannot hold up the others; requests must carry
+// `Authorization: Bearer <token>`.
+pub struct HealthHttpServer {
+    addr: SocketAddr,
+    stopping: Arc<AtomicBool>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl HealthHttpServer {
+    pub fn start(port: u16, token: String, handler: HttpHandler) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let addr = listener.local_addr()?;
+        let stopping = Arc::new(AtomicBool::new(false));
+        let signal = stopping.clone();
+        let token: Arc<str> = token.into();
+        let handler: Arc<HttpHandler> = Arc::new(handler);
+        let open = Arc::new(AtomicUsize::new(0));
+
+        let thread = thread::Builder::new()
+            .name("debug-tools-health-http".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if signal.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                        open.fetch_sub(1, Ordering::SeqCst);
+                        continue;
+                    }
+                    let (token, handler, done) = (token.clone(), handler.clone(), open.clone());
+                    let spawned = thread::Builder::new()
+                        .name("debug-tools-health-http-conn".to_string())
+                        .spawn(move || {
+                            if let Err(e) = serve(stream, &token, &handler) {
+                                tracing::debug!(error = %e, "Health HTTP request failed");
+                            }
+                            done.fetch_sub(1, Ordering::SeqCst);
+                        });
+                    if let Err(e) = spawned {
+                        open.fetch_sub(1, Ordering::SeqCst);
+                        tracing::debug!(error = %e, "Failed to spawn health HTTP connection thread");
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            addr,
+            stopping,
+            thread: Mutex::new(Some(thread)),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    // Unblocks the accept loop with a throwaway connection and waits for the
+    // thread, so the port is released before the app exits.
+    pub fn stop(&self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect_timeout(&self.addr, IO_TIMEOUT);
+        let thread = self.thread.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn serve(mut stream: TcpStream, token: &str, handler: &HttpHandler) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    let response = match read_head(&mut stream)? {
+        Some(head) => respond(&head, token, handler),
+        None => HttpResponse::error(400, "malformed request"),
+    };
+    write_response(&mut stream, &response)
+}
+
+// Everything up to the blank line; bodies are never read.
+fn read_head(stream: &mut TcpStream) -> io::Result<Option<String>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+        let read = stream.read(&mut buf)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    Ok(String::from_utf8(head).ok())
+}
+
+fn respond(head: &str, token: &str, handler: &HttpHandler) -> HttpResponse {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return HttpResponse::error(400, "malformed request line");
+    };
+    if method != "GET" {
+        return HttpResponse::error(405, "only GET is supported");
+    }
+
+    let authorized = lines
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .filter_map(|(_, value)| value.trim().strip_prefix("Bearer "))
+        .any(|presented| constant_time_eq(presented.trim().as_bytes(), token.as_bytes()));
+    if !authorized {
+        return HttpResponse::error(401, "missing or invalid bearer token");
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+
+    handler(&HttpRequest {
+        path: path.to_string(),
+        query,
+    })
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn write_response(stream: &mut TcpStream, response: &HttpResponse) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
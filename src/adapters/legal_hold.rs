@@ -0,0 +1,77 @@
+use super::storage::WritePolicy;
+use crate::config::DebugToolsConfig;
+use crate::domain::{Durability, LegalHolds, RepositoryError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// `holds.json` with an in-memory copy; cleanup paths read the copy, and
+// every change is written through before it takes effect for callers.
+#[derive(Debug)]
+pub struct LegalHoldStore {
+    path: PathBuf,
+    holds: Mutex<LegalHolds>,
+}
+
+impl LegalHoldStore {
+    // An unreadable file is logged and treated as holding nothing rather
+    // than failing setup; it is not overwritten until holds change.
+    pub fn load(config: &DebugToolsConfig) -> Self {
+        let path = config.legal_holds_path();
+        let holds = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring unreadable legal holds");
+                LegalHolds::default()
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => LegalHolds::default(),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to read legal holds");
+                LegalHolds::default()
+            }
+        };
+
+        Self {
+            path,
+            holds: Mutex::new(holds),
+        }
+    }
+
+    pub fn current(&self) -> LegalHolds {
+        self.holds.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(
+        &self,
+        config: &DebugToolsConfig,
+        paths: Vec<PathBuf>,
+        session_id: Option<String>,
+        held: bool,
+    ) -> Result<LegalHolds, RepositoryError> {
+        let mut holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+        let mut updated = holds.clone();
+        if held {
+            updated.paths.extend(paths);
+            updated.sessions.extend(session_id);
+        } else {
+            for path in &paths {
+                updated.paths.remove(path);
+            }
+            if let Some(session_id) = &session_id {
+                updated.sessions.remove(session_id);
+            }
+        }
+
+        if updated != *holds {
+            let json = serde_json::to_vec_pretty(&updated)?;
+            let policy = WritePolicy {
+                durability: Durability::Fsync,
+                ..WritePolicy::for_config(config)
+            };
+            policy.retry(|| policy.replace_file(&self.path, &json))?;
+            *holds = updated;
+        }
+
+        Ok(holds.clone())
+    }
+}
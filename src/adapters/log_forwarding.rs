@@ -0,0 +1,241 @@
+use super::logging::layer_write_scope;
+use crate::config::DebugToolsConfig;
+use crate::domain::{BackendLogBatch, BackendLogEvent, Clock, LogLevel};
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+pub const BACKEND_LOG_BATCH_EVENT: &str = "debug-tools://backend-log-batch";
+
+pub type BackendLogEmitter = Arc<dyn Fn(&BackendLogBatch) + Send + Sync>;
+
+// Buffers forwarded events and decides when a batch goes out: on every
+// interval tick, immediately for warn/error, and at most once per interval
+// when the buffer fills up. Events arriving while the buffer is full after
+// that are dropped and counted. Time is passed in explicitly so the policy
+// does not depend on the wall clock.
+#[derive(Debug)]
+pub struct BatchAggregator {
+    interval_ms: i64,
+    batch_max: usize,
+    buffer: Vec<BackendLogEvent>,
+    dropped_in_window: u64,
+    last_flush_ms: i64,
+    size_flushed_in_window: bool,
+}
+
+impl BatchAggregator {
+    pub fn new(interval_ms: u64, batch_max: usize, now_ms: i64) -> Self {
+        Self {
+            interval_ms: interval_ms as i64,
+            batch_max: batch_max.max(1),
+            buffer: Vec::new(),
+            dropped_in_window: 0,
+            last_flush_ms: now_ms,
+            size_flushed_in_window: false,
+        }
+    }
+
+    pub fn push(&mut self, event: BackendLogEvent, now_ms: i64) -> Option<BackendLogBatch> {
+        if event.level >= LogLevel::Warn {
+            self.buffer.push(event);
+            return self.flush(now_ms);
+        }
+
+        if self.buffer.len() >= self.batch_max {
+            self.dropped_in_window += 1;
+            return self.poll(now_ms);
+        }
+
+        self.buffer.push(event);
+
+        if self.interval_elapsed(now_ms) {
+            return self.flush(now_ms);
+        }
+
+        if self.buffer.len() >= self.batch_max && !self.size_flushed_in_window {
+            self.size_flushed_in_window = true;
+            return Some(self.take_batch());
+        }
+
+        None
+    }
+
+    pub fn poll(&mut self, now_ms: i64) -> Option<BackendLogBatch> {
+        if self.interval_elapsed(now_ms) {
+            return self.flush(now_ms);
+        }
+        None
+    }
+
+    fn interval_elapsed(&self, now_ms: i64) -> bool {
+        now_ms - self.last_flush_ms >= self.interval_ms
+    }
+
+    pub fn flush(&mut self, now_ms: i64) -> Option<BackendLogBatch> {
+        self.last_flush_ms = now_ms;
+        self.size_flushed_in_window = false;
+
+        if self.buffer.is_empty() && self.dropped_in_window == 0 {
+            return None;
+        }
+        Some(self.take_batch())
+    }
+
+    fn take_batch(&mut self) -> BackendLogBatch {
+        let batch = BackendLogBatch {
+            events: std::mem::take(&mut self.buffer),
+            dropped_in_window: self.dropped_in_window,
+        };
+        self.dropped_in_window = 0;
+        batch
+    }
+}
+
+pub struct BackendLogForwarder {
+    clock: Arc<dyn Clock>,
+    aggregator: Mutex<BatchAggregator>,
+    emitter: Mutex<Option<BackendLogEmitter>>,
+}
+
+impl BackendLogForwarder {
+    pub fn new(config: &DebugToolsConfig, clock: Arc<dyn Clock>) -> Arc<Self> {
+        let forwarder = Arc::new(Self {
+            aggregator: Mutex::new(BatchAggregator::new(
+                config.emit_interval_ms,
+                config.emit_batch_max,
+                clock.now_millis(),
+            )),
+            clock,
+            emitter: Mutex::new(None),
+        });
+
+        let interval = Duration::from_millis(config.emit_interval_ms.max(1));
+        let weak = Arc::downgrade(&forwarder);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            let Some(forwarder) = weak.upgrade() else {
+                break;
+            };
+            let batch = forwarder
+                .aggregator
+                .lock()
+                .ok()
+                .and_then(|mut aggregator| aggregator.poll(forwarder.clock.now_millis()));
+            if let Some(batch) = batch {
+                forwarder.emit(&batch);
+            }
+        });
+
+        forwarder
+    }
+
+    // The app handle only exists once the plugin is set up; events before
+    // that are buffered and go out with the first batch.
+    pub fn set_emitter(&self, emitter: BackendLogEmitter) {
+        if let Ok(mut slot) = self.emitter.lock() {
+            *slot = Some(emitter);
+        }
+    }
+
+    // Emits whatever is buffered now, regardless of the interval. Returns how
+    // many events went out.
+    pub fn flush(&self) -> usize {
+        let batch = self
+            .aggregator
+            .lock()
+            .ok()
+            .and_then(|mut aggregator| aggregator.flush(self.clock.now_millis()));
+        let Some(batch) = batch else {
+            return 0;
+        };
+        self.emit(&batch);
+        batch.events.len()
+    }
+
+    pub fn layer(self: &Arc<Self>) -> BackendLogForwardingLayer {
+        BackendLogForwardingLayer {
+            forwarder: self.clone(),
+        }
+    }
+
+    fn push(&self, event: BackendLogEvent) {
+        let batch = self
+            .aggregator
+            .lock()
+            .ok()
+            .and_then(|mut aggregator| aggregator.push(event, self.clock.now_millis()));
+        if let Some(batch) = batch {
+            self.emit(&batch);
+        }
+    }
+
+    fn emit(&self, batch: &BackendLogBatch) {
+        let Some(emitter) = self.emitter.lock().ok().and_then(|slot| slot.clone()) else {
+            return;
+        };
+
+        // Events raised by the emit path itself are not forwarded again.
+        let _scope = layer_write_scope();
+        emitter(batch);
+    }
+}
+
+pub struct BackendLogForwardingLayer {
+    forwarder: Arc<BackendLogForwarder>,
+}
+
+impl<S: Subscriber> Layer<S> for BackendLogForwardingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        self.forwarder.push(BackendLogEvent {
+            timestamp: self.forwarder.clock.now_millis(),
+            level: LogLevel::from(*metadata.level()),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+#[derive(Default)]
+pub(super) struct MessageVisitor {
+    pub(super) message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields.trim_start().to_string()
+        } else {
+            format!("{}{}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
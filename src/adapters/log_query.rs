@@ -0,0 +1,153 @@
+use super::log_repair::open_console_log;
+use crate::config::DebugToolsConfig;
+use crate::domain::{ConsoleLogEntry, ConsoleLogTail, LogQuery, LogQueryService, RepositoryError};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Streams the current session's frontend JSONL line by line, so memory stays
+// bounded by the query result rather than the file size. Malformed lines
+// (e.g. a torn final write) are skipped.
+pub struct FileSystemLogQueryService {
+    config: Arc<DebugToolsConfig>,
+    app_name: String,
+    pid: u32,
+}
+
+impl FileSystemLogQueryService {
+    pub fn new(config: Arc<DebugToolsConfig>, app_name: String, pid: u32) -> Self {
+        Self {
+            config,
+            app_name,
+            pid,
+        }
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.config.frontend_log_path(&self.app_name, self.pid)
+    }
+}
+
+impl LogQueryService for FileSystemLogQueryService {
+    fn scan(
+        &self,
+        query: &LogQuery,
+        visit: &mut dyn FnMut(ConsoleLogEntry),
+    ) -> Result<(), RepositoryError> {
+        let reader = match open_console_log(&self.log_path()) {
+            Ok(reader) => reader,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut skipped = 0usize;
+        for line in reader.split(b'\n') {
+            let line = line?;
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            match serde_json::from_slice::<ConsoleLogEntry>(&line) {
+                Ok(entry) if query.matches(&entry) => visit(entry),
+                Ok(_) => {}
+                Err(_) => skipped += 1,
+            }
+        }
+
+        if skipped > 0 {
+            tracing::debug!(skipped, "Skipped malformed console log lines");
+        }
+
+        Ok(())
+    }
+}
+
+// The `limit` entries before the newest `offset`, oldest first, in one
+// streaming pass that holds at most `limit + offset` of them. A missing file
+// reads as empty.
+pub fn tail_console_log(path: &Path, limit: usize, offset: usize) -> io::Result<ConsoleLogTail> {
+    let reader = match open_console_log(path) {
+        Ok(reader) => reader,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Ok(ConsoleLogTail {
+                entries: Vec::new(),
+                total: 0,
+                malformed_lines: 0,
+            })
+        }
+        Err(e) => return Err(e),
+    };
+
+    let window = limit.saturating_add(offset);
+    let mut tail = VecDeque::with_capacity(window.min(4096));
+    let (mut total, mut malformed_lines) = (0, 0);
+    for line in reader.split(b'\n') {
+        let line = line?;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_slice::<ConsoleLogEntry>(&line) else {
+            malformed_lines += 1;
+            continue;
+        };
+        total += 1;
+        if tail.len() == window {
+            tail.pop_front();
+        }
+        if window > 0 {
+            tail.push_back(entry);
+        }
+    }
+
+    let keep = tail.len().saturating_sub(offset);
+    Ok(ConsoleLogTail {
+        entries: tail.into_iter().take(keep).collect(),
+        total,
+        malformed_lines,
+    })
+}
+
+#[cfg(feature = "test-utils")]
+pub use in_memory::InMemoryLogQueryService;
+
+#[cfg(feature = "test-utils")]
+mod in_memory {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    pub struct InMemoryLogQueryService {
+        entries: Mutex<Vec<ConsoleLogEntry>>,
+    }
+
+    impl InMemoryLogQueryService {
+        pub fn new(entries: Vec<ConsoleLogEntry>) -> Self {
+            Self {
+                entries: Mutex::new(entries),
+            }
+        }
+
+        pub fn push(&self, entries: impl IntoIterator<Item = ConsoleLogEntry>) {
+            self.entries
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extend(entries);
+        }
+    }
+
+    impl LogQueryService for InMemoryLogQueryService {
+        fn scan(
+            &self,
+            query: &LogQuery,
+            visit: &mut dyn FnMut(ConsoleLogEntry),
+        ) -> Result<(), RepositoryError> {
+            let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            entries
+                .iter()
+                .filter(|entry| query.matches(entry))
+                .cloned()
+                .for_each(visit);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use super::ownership::OwnedFiles;
+use super::storage::WritePolicy;
+use crate::config::DebugToolsConfig;
+use crate::domain::{ArtifactKind, ByteRange, ConsoleLogEntry, LogRepairReport, RepositoryError};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// Next to the logs it describes, so copied bundles keep their repairs.
+pub const REPAIR_INDEX_FILE: &str = "repaired_logs.json";
+const REPAIRED_SUFFIX: &str = ".repaired.jsonl";
+const MAX_REPORTED_RANGES: usize = 100;
+
+// The startup check and `repair_console_log` may update the index at once.
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+type RepairIndex = BTreeMap<String, LogRepairReport>;
+
+pub fn is_repaired_copy(name: &str) -> bool {
+    name.ends_with(REPAIRED_SUFFIX)
+}
+
+// `frontend_console_app_1.jsonl` -> `frontend_console_app_1.repaired.jsonl`.
+pub fn repaired_copy_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = name.strip_suffix(".jsonl").unwrap_or(&name);
+    path.with_file_name(format!("{}{}", stem, REPAIRED_SUFFIX))
+}
+
+// Copies every line of `path` that parses as a console entry into its
+// repaired copy and records the rest in the index. `path` is only read.
+// An intact file is recorded without a copy, and a stale copy is removed.
+pub fn repair_console_log(
+    config: &DebugToolsConfig,
+    path: &Path,
+    owned: &OwnedFiles,
+    now_millis: i64,
+) -> Result<LogRepairReport, RepositoryError> {
+    let repaired = repaired_copy_path(path);
+    let staging = repaired.with_extension(format!("tmp.{}", std::process::id()));
+    let policy = WritePolicy::for_artifact(config, ArtifactKind::ConsoleLog);
+
+    let scanned = scan_into(path, &staging, policy);
+    let (source_bytes, scan) = match scanned {
+        Ok(scanned) => scanned,
+        Err(e) => {
+            let _ = fs::remove_file(&staging);
+            return Err(e);
+        }
+    };
+
+    let repaired_path = if scan.discarded_lines == 0 {
+        let _ = fs::remove_file(&staging);
+        match fs::remove_file(&repaired) {
+            Ok(()) => owned.forget([&repaired]),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        None
+    } else {
+        policy.move_into(&staging, &repaired)?;
+        owned.register(&repaired);
+        Some(repaired)
+    };
+
+    let report = LogRepairReport {
+        path: path.to_path_buf(),
+        repaired_path,
+        source_bytes,
+        kept_entries: scan.kept_entries,
+        discarded_lines: scan.discarded_lines,
+        discarded_bytes: scan.discarded_bytes,
+        discarded_ranges: scan.discarded_ranges,
+        repaired_at: now_millis,
+    };
+    record(config, path, &report, owned)?;
+
+    if report.discarded_lines > 0 {
+        tracing::warn!(
+            path = %path.display(),
+            kept = report.kept_entries,
+            discarded_lines = report.discarded_lines,
+            discarded_bytes = report.discarded_bytes,
+            "Console log repaired"
+        );
+    }
+
+    Ok(report)
+}
+
+// Drops the repair of a log that is being emptied, so the copy is not read
+// once the restarted file grows past its old length.
+pub fn forget_repair(
+    config: &DebugToolsConfig,
+    path: &Path,
+    owned: &OwnedFiles,
+) -> Result<(), RepositoryError> {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(());
+    };
+    let dir = path.parent().unwrap_or(&config.log_dir);
+
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut index = load_index(dir);
+    if index.remove(name).is_none() {
+        return Ok(());
+    }
+    save_index(config, dir, &index, owned)?;
+
+    let repaired = repaired_copy_path(path);
+    match fs::remove_file(&repaired) {
+        Ok(()) => owned.forget([&repaired]),
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+// Session-start check of the console logs earlier sessions left behind.
+// Files the index already covers at their current length are skipped, so
+// each is read once.
+pub fn check_previous_logs(
+    config: &DebugToolsConfig,
+    current: &Path,
+    owned: &OwnedFiles,
+    now_millis: i64,
+) -> Result<Vec<LogRepairReport>, RepositoryError> {
+    let dir = config.console_log_dir();
+    let index = load_index(&dir);
+    let mut repaired = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let is_console_log = name.starts_with("frontend_console_")
+            && name.ends_with(".jsonl")
+            && !is_repaired_copy(name);
+        if !is_console_log || path == current || !entry.file_type()?.is_file() {
+            continue;
+        }
+        let length = entry.metadata()?.len();
+        if index
+            .get(name)
+            .is_some_and(|report| report.source_bytes == length)
+        {
+            continue;
+        }
+
+        match repair_console_log(config, &path, owned, now_millis) {
+            Ok(report) if report.repaired_path.is_some() => repaired.push(report),
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to check console log")
+            }
+        }
+    }
+    Ok(repaired)
+}
+
+// Reads a console log through its repaired copy when the index has one:
+// the copy, then whatever was appended to the original after the repair.
+// A copy older than a reset or truncation of the original is ignored.
+pub fn open_console_log(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let mut original = fs::File::open(path)?;
+    let report = path
+        .parent()
+        .zip(path.file_name().and_then(|name| name.to_str()))
+        .and_then(|(dir, name)| load_index(dir).remove(name))
+        .filter(|report| report.repaired_path.is_some());
+    let Some(report) = report else {
+        return Ok(Box::new(BufReader::new(original)));
+    };
+
+    let current_bytes = original.metadata()?.len();
+    let copy = match fs::File::open(repaired_copy_path(path)) {
+        Ok(copy) if current_bytes >= report.source_bytes => copy,
+        _ => return Ok(Box::new(BufReader::new(original))),
+    };
+    original.seek(SeekFrom::Start(report.source_bytes))?;
+    // The copy ends with a newline, so the appended tail starts a new line.
+    Ok(Box::new(BufReader::new(copy.chain(original))))
+}
+
+#[derive(Default)]
+struct Scan {
+    kept_entries: usize,
+    discarded_lines: usize,
+    discarded_bytes: u64,
+    discarded_ranges: Vec<ByteRange>,
+}
+
+// Lines are split on raw bytes, so invalid UTF-8 only costs its own line.
+fn scan_into(
+    path: &Path,
+    staging: &Path,
+    policy: WritePolicy,
+) -> Result<(u64, Scan), RepositoryError> {
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    let mut file = fs::File::create(staging)?;
+    let mut writer = BufWriter::new(&mut file);
+    let mut scan = Scan::default();
+    let mut offset = 0u64;
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 {
+            break;
+        }
+        let start = offset;
+        offset += read as u64;
+
+        let content = line.strip_suffix(b"\n").unwrap_or(&line);
+        let content = content.strip_suffix(b"\r").unwrap_or(content);
+        if content.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        if serde_json::from_slice::<ConsoleLogEntry>(content).is_ok() {
+            writer.write_all(content)?;
+            writer.write_all(b"\n")?;
+            scan.kept_entries += 1;
+            continue;
+        }
+
+        scan.discarded_lines += 1;
+        scan.discarded_bytes += read as u64;
+        let ranges = &mut scan.discarded_ranges;
+        if let Some(range) = ranges.last_mut().filter(|range| range.end == start) {
+            range.end = offset;
+        } else if ranges.len() < MAX_REPORTED_RANGES {
+            ranges.push(ByteRange { start, end: offset });
+        }
+    }
+
+    writer.flush()?;
+    drop(writer);
+    policy.sync(&mut file)?;
+    Ok((offset, scan))
+}
+
+fn load_index(dir: &Path) -> RepairIndex {
+    match fs::read(dir.join(REPAIR_INDEX_FILE)) {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring unreadable console log repair index");
+            RepairIndex::new()
+        }),
+        Err(_) => RepairIndex::new(),
+    }
+}
+
+// Entries whose log no longer exists are dropped on the way.
+fn record(
+    config: &DebugToolsConfig,
+    path: &Path,
+    report: &LogRepairReport,
+    owned: &OwnedFiles,
+) -> Result<(), RepositoryError> {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(());
+    };
+    let dir = path.parent().unwrap_or(&config.log_dir);
+
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut index = load_index(dir);
+    index.retain(|name, _| dir.join(name).exists());
+    index.insert(name.to_string(), report.clone());
+    save_index(config, dir, &index, owned)
+}
+
+fn save_index(
+    config: &DebugToolsConfig,
+    dir: &Path,
+    index: &RepairIndex,
+    owned: &OwnedFiles,
+) -> Result<(), RepositoryError> {
+    let index_path = dir.join(REPAIR_INDEX_FILE);
+    let json = serde_json::to_vec_pretty(index)?;
+    let policy = WritePolicy::for_config(config);
+    policy.retry(|| policy.replace_file(&index_path, &json))?;
+    owned.register(&index_path);
+    Ok(())
+}
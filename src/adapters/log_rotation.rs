@@ -0,0 +1,127 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+// Rotated copies sit next to the live file as `<prefix><n><suffix>`, keeping
+// the prefix and extension that cleanup, retention and bundles match on.
+pub struct RotatedNames {
+    dir: PathBuf,
+    prefix: String,
+    suffix: &'static str,
+}
+
+impl RotatedNames {
+    // `frontend_console_app_1.jsonl` -> `frontend_console_app_1.<n>.jsonl`.
+    pub fn console_log(live: &Path) -> Self {
+        Self::new(live, live.file_stem(), ".jsonl")
+    }
+
+    // `rust_debug.log` -> `rust_debug.log.<n>`.
+    pub fn backend_log(live: &Path) -> Self {
+        Self::new(live, live.file_name(), "")
+    }
+
+    fn new(live: &Path, base: Option<&std::ffi::OsStr>, suffix: &'static str) -> Self {
+        Self {
+            dir: live.parent().map(Path::to_path_buf).unwrap_or_default(),
+            prefix: format!("{}.", base.unwrap_or_default().to_string_lossy()),
+            suffix,
+        }
+    }
+
+    pub fn path(&self, n: u64) -> PathBuf {
+        self.dir
+            .join(format!("{}{}{}", self.prefix, n, self.suffix))
+    }
+
+    // One past the highest number in use, so the newest copy always has the
+    // highest number even after older ones expire.
+    fn next(&self) -> u64 {
+        fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix(self.prefix.as_str())?
+                    .strip_suffix(self.suffix)?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .max()
+            .map_or(1, |n| n + 1)
+    }
+}
+
+// Renames `live` to its next rotated name when writing `incoming` more bytes
+// would take it past `max_bytes`. A file that is still empty is never
+// rotated, so an entry larger than the cap gets a file of its own. A cap of
+// 0 turns rotation off.
+pub fn rotate_if_full(
+    live: &Path,
+    incoming: u64,
+    max_bytes: u64,
+    names: &RotatedNames,
+) -> io::Result<Option<PathBuf>> {
+    if max_bytes == 0 {
+        return Ok(None);
+    }
+    let size = match fs::metadata(live) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if size == 0 || size.saturating_add(incoming) <= max_bytes {
+        return Ok(None);
+    }
+
+    let target = names.path(names.next());
+    fs::rename(live, &target)?;
+    Ok(Some(target))
+}
+
+// The `rust_debug.log` writer: appends to the live file and rotates it by
+// size, like the console log, at event boundaries.
+pub struct SizeCappedFile {
+    path: PathBuf,
+    names: RotatedNames,
+    max_bytes: u64,
+    file: fs::File,
+}
+
+impl SizeCappedFile {
+    pub fn open(path: PathBuf, max_bytes: u64) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = open_append(&path)?;
+        Ok(Self {
+            names: RotatedNames::backend_log(&path),
+            path,
+            max_bytes,
+            file,
+        })
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new().create(true).append(true).open(path)
+}
+
+impl Write for SizeCappedFile {
+    // tracing-appender's worker hands over one formatted event per call.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let rotated = rotate_if_full(&self.path, buf.len() as u64, self.max_bytes, &self.names)?;
+        if rotated.is_some() {
+            self.file = open_append(&self.path)?;
+        }
+        self.file.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
@@ -1,45 +1,261 @@
-use crate::config::DebugToolsConfig;
-use crate::domain::LogError;
+use super::log_forwarding::{BackendLogForwarder, MessageVisitor};
+use super::log_rotation::SizeCappedFile;
+use crate::config::{DebugToolsConfig, LogFormat};
+use crate::domain::{Clock, ErrorAlarm, LogError, LogLevel, QuietMode};
+use std::cell::Cell;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tracing::subscriber::Interest;
+use tracing::{Level, Metadata};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 
 pub struct TracingGuard {
-    _guard: WorkerGuard,
+    // `None` when `enable_rust_logging` is off and no file is written.
+    _guard: Option<WorkerGuard>,
+    #[cfg(feature = "flamegraph")]
+    flame_guard: tracing_flame::FlushGuard<std::io::BufWriter<std::fs::File>>,
 }
 
-pub fn init_tracing(config: Arc<DebugToolsConfig>) -> Result<TracingGuard, LogError> {
-    let log_path = config.backend_log_path();
-    let log_dir = log_path
-        .parent()
-        .ok_or_else(|| LogError::Initialization("Invalid log path".into()))?;
+#[cfg(feature = "flamegraph")]
+impl TracingGuard {
+    pub fn flush_flamegraph(&self) -> Result<(), LogError> {
+        self.flame_guard
+            .flush()
+            .map_err(|e| LogError::Flamegraph(e.to_string()))
+    }
+}
+
+#[cfg(feature = "flamegraph")]
+pub fn render_flamegraph(
+    config: &DebugToolsConfig,
+    timestamp: i64,
+) -> Result<std::path::PathBuf, LogError> {
+    let folded = std::fs::File::open(config.flamegraph_folded_path())
+        .map_err(|e| LogError::Flamegraph(e.to_string()))?;
+
+    let svg_path = config
+        .log_dir
+        .join(format!("tracing_flamegraph_{}.svg", timestamp));
+    let svg = std::fs::File::create(&svg_path).map_err(|e| LogError::Flamegraph(e.to_string()))?;
+
+    let mut options = inferno::flamegraph::Options::default();
+    options.title = "tauri-plugin-debug-tools spans".to_string();
+
+    inferno::flamegraph::from_reader(
+        &mut options,
+        std::io::BufReader::new(folded),
+        std::io::BufWriter::new(svg),
+    )
+    .map_err(|e| LogError::Flamegraph(e.to_string()))?;
+
+    Ok(svg_path)
+}
+
+struct QuietModeFilter {
+    quiet_mode: Arc<QuietMode>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<S> Filter<S> for QuietModeFilter {
+    fn enabled(&self, metadata: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        !self
+            .quiet_mode
+            .suppresses(LogLevel::from(*metadata.level()), self.clock.now_secs())
+    }
+
+    // Quiet mode toggles at runtime, so callsite interest must never be cached.
+    fn callsite_enabled(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+}
+
+thread_local! {
+    // Set while a tracing layer (or work it started) writes to the
+    // repository or emits to the app. Events raised meanwhile only reach
+    // stdout, so a failing write cannot log its way back into another write.
+    static IN_LAYER_WRITE: Cell<bool> = const { Cell::new(false) };
+}
+
+static SUPPRESSED_REENTRANT_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+pub struct LayerWriteScope {
+    previous: bool,
+}
 
-    let file_appender = tracing_appender::rolling::daily(log_dir, "rust_debug.log");
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+impl Drop for LayerWriteScope {
+    fn drop(&mut self) {
+        IN_LAYER_WRITE.with(|flag| flag.set(self.previous));
+    }
+}
+
+pub fn layer_write_scope() -> LayerWriteScope {
+    LayerWriteScope {
+        previous: IN_LAYER_WRITE.with(|flag| flag.replace(true)),
+    }
+}
+
+// Runs every poll of `future` inside a layer write scope, for tasks a layer
+// spawns: they may resume on any worker thread.
+pub fn in_layer_write_scope<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let mut future = Box::pin(future);
+    std::future::poll_fn(move |cx| {
+        let _scope = layer_write_scope();
+        future.as_mut().poll(cx)
+    })
+}
+
+pub fn suppressed_reentrant_events() -> u64 {
+    SUPPRESSED_REENTRANT_EVENTS.load(Ordering::Relaxed)
+}
+
+// Keeps events raised inside a layer write scope away from the layers that
+// write or emit. Only one instance counts, so each event is counted once.
+struct OutsideLayerWrite {
+    count: bool,
+}
+
+impl<S> Filter<S> for OutsideLayerWrite {
+    fn enabled(&self, metadata: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        if !metadata.is_event() || !IN_LAYER_WRITE.with(Cell::get) {
+            return true;
+        }
+        if self.count {
+            SUPPRESSED_REENTRANT_EVENTS.fetch_add(1, Ordering::Relaxed);
+        }
+        false
+    }
+
+    fn callsite_enabled(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+}
+
+const SELF_TARGET_PREFIX: &str = "tauri_plugin_debug_tools";
+
+// Applies `self_log_level` to the plugin's own events on top of whatever the
+// host's `RUST_LOG` allows. Other targets are left to the env filter.
+struct SelfLogFilter {
+    min_level: LogLevel,
+}
+
+impl<S: tracing::Subscriber> Layer<S> for SelfLogFilter {
+    fn enabled(&self, metadata: &Metadata<'_>, _cx: Context<'_, S>) -> bool {
+        if !metadata.is_event() || !metadata.target().starts_with(SELF_TARGET_PREFIX) {
+            return true;
+        }
+        let level = LogLevel::from(*metadata.level());
+        level == LogLevel::Error || level >= self.min_level
+    }
+}
+
+// Feeds backend error events into the error alarm alongside frontend entries.
+struct ErrorAlarmLayer {
+    alarm: Arc<ErrorAlarm>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<S: tracing::Subscriber> Layer<S> for ErrorAlarmLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::ERROR {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let _scope = layer_write_scope();
+        self.alarm
+            .observe_backend_error(&visitor.message, self.clock.now_millis());
+    }
+}
+
+pub fn init_tracing(
+    config: Arc<DebugToolsConfig>,
+    quiet_mode: Arc<QuietMode>,
+    clock: Arc<dyn Clock>,
+    forwarder: Option<Arc<BackendLogForwarder>>,
+    error_alarm: Option<Arc<ErrorAlarm>>,
+) -> Result<TracingGuard, LogError> {
+    let (non_blocking, guard) = if config.enable_rust_logging {
+        let file = SizeCappedFile::open(config.backend_log_path(), config.max_log_size_bytes)
+            .map_err(|e| LogError::Initialization(e.to_string()))?;
+        let (non_blocking, guard) = tracing_appender::non_blocking(file);
+        (Some(non_blocking), Some(guard))
+    } else {
+        (None, None)
+    };
 
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("tauri_plugin_debug_tools=debug,info"));
 
-    let file_layer = fmt::layer()
-        .json()
-        .with_writer(non_blocking)
-        .with_target(true)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true);
-
-    let stdout_layer = fmt::layer()
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_file(false)
-        .with_line_number(false);
-
-    tracing_subscriber::registry()
+    let file_layer = non_blocking.map(|non_blocking| {
+        let layer = fmt::layer()
+            .with_writer(non_blocking)
+            .with_ansi(false)
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_file(true)
+            .with_line_number(true);
+        match config.log_format {
+            LogFormat::Json => layer
+                .json()
+                .with_filter(OutsideLayerWrite { count: true })
+                .boxed(),
+            LogFormat::Text => layer.with_filter(OutsideLayerWrite { count: true }).boxed(),
+        }
+    });
+
+    let stdout_layer = config.log_to_stdout.then(|| {
+        fmt::layer()
+            .with_target(true)
+            .with_thread_ids(false)
+            .with_file(false)
+            .with_line_number(false)
+            .with_filter(QuietModeFilter {
+                quiet_mode: quiet_mode.clone(),
+                clock: clock.clone(),
+            })
+    });
+
+    let registry = tracing_subscriber::registry()
         .with(env_filter)
+        .with(SelfLogFilter {
+            min_level: config.self_log_level,
+        })
         .with(file_layer)
         .with(stdout_layer)
+        .with(forwarder.map(|forwarder| {
+            forwarder
+                .layer()
+                .with_filter(QuietModeFilter {
+                    quiet_mode,
+                    clock: clock.clone(),
+                })
+                .with_filter(OutsideLayerWrite { count: false })
+        }))
+        .with(error_alarm.map(|alarm| {
+            ErrorAlarmLayer { alarm, clock }.with_filter(OutsideLayerWrite { count: false })
+        }));
+
+    #[cfg(feature = "flamegraph")]
+    let (registry, flame_guard) = {
+        let (flame_layer, flame_guard) =
+            tracing_flame::FlameLayer::with_file(config.flamegraph_folded_path())
+                .map_err(|e| LogError::Initialization(e.to_string()))?;
+        (
+            registry.with(flame_layer.with_threads_collapsed(true)),
+            flame_guard,
+        )
+    };
+
+    registry
         .try_init()
         .map_err(|e| LogError::Initialization(e.to_string()))?;
 
-    Ok(TracingGuard { _guard: guard })
+    Ok(TracingGuard {
+        _guard: guard,
+        #[cfg(feature = "flamegraph")]
+        flame_guard,
+    })
 }
@@ -0,0 +1,182 @@
+use crate::domain::{
+    ConsoleLogEntry, DebugSnapshot, DomSnapshotResult, DomState, DurabilityLatency, LatencyBucket,
+    OperationMetrics, RepositoryError, RepositoryMetricsReport, SnapshotRepository,
+};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+const LATENCY_BUCKETS_MS: &[u64] = &[1, 5, 10, 25, 50, 100, 250, 500, 1000];
+
+#[derive(Debug, Default, Clone)]
+struct OperationCounters {
+    calls: u64,
+    errors: u64,
+    bytes_written: u64,
+    total_latency_us: u64,
+    max_latency_us: u64,
+    // One slot per entry in LATENCY_BUCKETS_MS plus a final overflow slot.
+    bucket_counts: Vec<u64>,
+}
+
+impl OperationCounters {
+    fn record(&mut self, elapsed_us: u64, bytes: u64, failed: bool) {
+        if self.bucket_counts.is_empty() {
+            self.bucket_counts = vec![0; LATENCY_BUCKETS_MS.len() + 1];
+        }
+
+        self.calls += 1;
+        self.bytes_written += bytes;
+        self.total_latency_us += elapsed_us;
+        self.max_latency_us = self.max_latency_us.max(elapsed_us);
+        if failed {
+            self.errors += 1;
+        }
+
+        let slot = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|le_ms| elapsed_us <= le_ms * 1000)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.bucket_counts[slot] += 1;
+    }
+
+    fn report(&self) -> OperationMetrics {
+        let latency_histogram = (0..=LATENCY_BUCKETS_MS.len())
+            .map(|slot| LatencyBucket {
+                le_ms: LATENCY_BUCKETS_MS.get(slot).copied(),
+                count: self.bucket_counts.get(slot).copied().unwrap_or_default(),
+            })
+            .collect();
+
+        OperationMetrics {
+            calls: self.calls,
+            errors: self.errors,
+            bytes_written: self.bytes_written,
+            avg_latency_us: self.total_latency_us.checked_div(self.calls).unwrap_or(0),
+            max_latency_us: self.max_latency_us,
+            latency_histogram,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MetricsState {
+    save_snapshot: OperationCounters,
+    save_dom: OperationCounters,
+    save_console_logs: OperationCounters,
+}
+
+// Decorates any repository backend so host-provided implementations are
+// measured the same way as the filesystem one.
+pub struct MeteredRepository<R: SnapshotRepository> {
+    inner: R,
+    state: Mutex<MetricsState>,
+}
+
+impl<R: SnapshotRepository> MeteredRepository<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: Mutex::new(MetricsState::default()),
+        }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    fn record<T>(
+        &self,
+        select: fn(&mut MetricsState) -> &mut OperationCounters,
+        started: Instant,
+        result: &Result<T, RepositoryError>,
+        bytes: u64,
+    ) {
+        let elapsed_us = started.elapsed().as_micros() as u64;
+        if let Ok(mut state) = self.state.lock() {
+            select(&mut state).record(elapsed_us, bytes, result.is_err());
+        }
+    }
+}
+
+impl<R: SnapshotRepository> SnapshotRepository for MeteredRepository<R> {
+    fn save_snapshot(&self, snapshot: &DebugSnapshot) -> Result<PathBuf, RepositoryError> {
+        let started = Instant::now();
+        let result = self.inner.save_snapshot(snapshot);
+        let bytes = result.as_ref().map(|path| file_size(path)).unwrap_or(0);
+        self.record(|s| &mut s.save_snapshot, started, &result, bytes);
+        result
+    }
+
+    fn save_dom(
+        &self,
+        dom: &DomState,
+        timestamp: i64,
+    ) -> Result<DomSnapshotResult, RepositoryError> {
+        let started = Instant::now();
+        let result = self.inner.save_dom(dom, timestamp);
+        let bytes = result
+            .as_ref()
+            .map(|saved| file_size(&saved.path))
+            .unwrap_or(0);
+        self.record(|s| &mut s.save_dom, started, &result, bytes);
+        result
+    }
+
+    fn save_console_logs(&self, logs: &[ConsoleLogEntry]) -> Result<PathBuf, RepositoryError> {
+        let started = Instant::now();
+        let result = self.inner.save_console_logs(logs);
+        let bytes = if result.is_ok() {
+            serialized_len(logs)
+        } else {
+            0
+        };
+        self.record(|s| &mut s.save_console_logs, started, &result, bytes);
+        result
+    }
+
+    fn metrics(&self) -> Option<RepositoryMetricsReport> {
+        let state = self.state.lock().ok()?;
+        Some(RepositoryMetricsReport {
+            save_snapshot: state.save_snapshot.report(),
+            save_dom: state.save_dom.report(),
+            save_console_logs: state.save_console_logs.report(),
+            durability: self.inner.durability_latency(),
+            buffer_pool: None,
+        })
+    }
+
+    fn durability_latency(&self) -> Vec<DurabilityLatency> {
+        self.inner.durability_latency()
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+struct CountingWriter(u64);
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Appends land in a shared JSONL file, so the byte count is derived from the
+// serialized lines rather than the resulting file size.
+fn serialized_len(logs: &[ConsoleLogEntry]) -> u64 {
+    let mut counter = CountingWriter(0);
+    for entry in logs {
+        if serde_json::to_writer(&mut counter, entry).is_ok() {
+            counter.0 += 1;
+        }
+    }
+    counter.0
+}
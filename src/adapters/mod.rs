@@ -1,5 +1,33 @@
+pub mod annotations;
+pub mod buffer_pool;
+pub mod bundle;
+pub mod disk_usage;
+pub mod dom_search;
+pub mod dom_upload;
 pub mod filesystem;
+pub mod health_http;
+pub mod legal_hold;
+pub mod log_forwarding;
+pub mod log_query;
+pub mod log_repair;
+pub mod log_rotation;
 pub mod logging;
+pub mod metrics;
+pub mod ownership;
+pub mod platform;
+pub mod plugin_log;
+pub mod recording;
+pub mod screenshot_diff;
+pub mod screenshots;
+pub mod secure_delete;
+pub mod snapshot_index;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod storage;
 
 pub use filesystem::FileSystemRepository;
+pub use log_query::FileSystemLogQueryService;
 pub use logging::init_tracing;
+pub use metrics::MeteredRepository;
+pub use screenshots::ScreenshotIndex;
+pub use snapshot_index::SnapshotIndex;
@@ -0,0 +1,267 @@
+use super::screenshots::image_format;
+use super::snapshot_index::snapshot_file_timestamp;
+use super::storage::WritePolicy;
+use crate::config::{normalize_file_name, DebugToolsConfig};
+use crate::domain::RepositoryError;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const BACKEND_LOG_FILE: &str = "rust_debug.log";
+
+type NameMatcher = fn(&Path, &str) -> bool;
+// A backend log line longer than this is not recognized; the file is then
+// treated as foreign, which errs on the side of keeping it.
+const MAX_FIRST_LINE_BYTES: u64 = 64 * 1024;
+
+// Files the plugin created under `log_dir`, listed in `.owned_files` one
+// path per line, relative to the log directory. Files under an artifact
+// root override are listed by absolute path instead. Clear and retention only
+// remove files found here, so a `log_dir` shared with other tools never
+// loses their files. The backend log is written by tracing-appender rather
+// than this crate and is recognized by its first line instead.
+#[derive(Debug)]
+pub struct OwnedFiles {
+    root: PathBuf,
+    // Cleanup lists the canonical log root, while writes use `log_dir` as
+    // configured; paths under either are accepted.
+    canonical_root: Option<PathBuf>,
+    // Artifact root overrides, with their canonical forms.
+    override_roots: Vec<(PathBuf, Option<PathBuf>)>,
+    path: PathBuf,
+    policy: WritePolicy,
+    files: Mutex<HashSet<PathBuf>>,
+}
+
+impl OwnedFiles {
+    // An unreadable list is logged and treated as empty: cleanup then skips
+    // everything until files are written or claimed again.
+    pub fn load(config: &DebugToolsConfig) -> Self {
+        let path = config.owned_files_path();
+        let files = match fs::read_to_string(&path) {
+            Ok(list) => list
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| PathBuf::from(normalize_file_name(line)))
+                .collect(),
+            Err(e) if e.kind() == ErrorKind::NotFound => HashSet::new(),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to read owned file list");
+                HashSet::new()
+            }
+        };
+
+        let paths = config.artifact_paths();
+        let override_roots = paths
+            .override_roots()
+            .into_iter()
+            .map(|root| (root.to_path_buf(), root.canonicalize().ok()))
+            .collect();
+
+        Self {
+            root: config.log_dir.clone(),
+            canonical_root: config.log_dir.canonicalize().ok(),
+            override_roots,
+            path,
+            policy: WritePolicy::for_config(config),
+            files: Mutex::new(files),
+        }
+    }
+
+    // Called after each write; only a path not seen before touches the list.
+    // A failed update is logged and never fails the write itself.
+    pub fn register(&self, path: &Path) {
+        if let Err(e) = self.claim([path.to_path_buf()]) {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to record owned file");
+        }
+    }
+
+    // Adds `paths` to the list and returns the ones that were not on it.
+    // Paths outside the log directory and the artifact roots are ignored.
+    pub fn claim(
+        &self,
+        paths: impl IntoIterator<Item = PathBuf>,
+    ) -> Result<Vec<PathBuf>, RepositoryError> {
+        let mut files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        let mut added = Vec::new();
+        let mut lines = String::new();
+        for path in paths {
+            let Some(listed) = self.listed_path(&path) else {
+                continue;
+            };
+            let Some(line) = listed.to_str() else {
+                continue;
+            };
+            if files.insert(listed.clone()) {
+                lines.push_str(line);
+                lines.push('\n');
+                added.push(path);
+            }
+        }
+
+        // On failure the paths still count as owned for this session.
+        if !lines.is_empty() {
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?
+                .write_all(lines.as_bytes())?;
+        }
+
+        Ok(added)
+    }
+
+    pub fn is_owned(&self, path: &Path) -> bool {
+        let listed = self.listed_path(path).is_some_and(|listed| {
+            self.files
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .contains(&listed)
+        });
+        listed || is_backend_log(path)
+    }
+
+    // Drops removed files from the list and rewrites it whole.
+    pub fn forget<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        let mut files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        let mut changed = false;
+        for path in paths {
+            if let Some(listed) = self.listed_path(path) {
+                changed |= files.remove(&listed);
+            }
+        }
+        if !changed {
+            return;
+        }
+
+        let mut sorted: Vec<&str> = files.iter().filter_map(|path| path.to_str()).collect();
+        sorted.sort_unstable();
+        let list: String = sorted.iter().map(|line| format!("{}\n", line)).collect();
+        let policy = self.policy;
+        if let Err(e) = policy.retry(|| policy.replace_file(&self.path, list.as_bytes())) {
+            tracing::warn!(path = %self.path.display(), error = %e, "Failed to rewrite owned file list");
+        }
+    }
+
+    // How `path` appears in the list: relative to the log directory, or
+    // under its artifact root as configured.
+    // In NFC, so a name the filesystem hands back decomposed still matches.
+    fn listed_path(&self, path: &Path) -> Option<PathBuf> {
+        let listed =
+            strip_root(path, &self.root, self.canonical_root.as_deref()).or_else(|| {
+                self.override_roots.iter().find_map(|(root, canonical)| {
+                    strip_root(path, root, canonical.as_deref()).map(|relative| root.join(relative))
+                })
+            })?;
+        Some(match listed.to_str() {
+            Some(name) => PathBuf::from(normalize_file_name(name)),
+            None => listed,
+        })
+    }
+}
+
+fn strip_root(path: &Path, root: &Path, canonical_root: Option<&Path>) -> Option<PathBuf> {
+    path.strip_prefix(root)
+        .ok()
+        .or_else(|| canonical_root.and_then(|root| path.strip_prefix(root).ok()))
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+}
+
+// `rust_debug.log` and its rotated `rust_debug.log.<n>` copies are not on
+// the list; they are recognized by the event the file starts with, in either
+// `log_format`.
+fn is_backend_log(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let prefixed = name
+        .strip_prefix(BACKEND_LOG_FILE)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'));
+    if !prefixed {
+        return false;
+    }
+
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    let mut line = String::new();
+    if BufReader::new(file.take(MAX_FIRST_LINE_BYTES))
+        .read_line(&mut line)
+        .is_err()
+    {
+        return false;
+    }
+    let json_event = serde_json::from_str::<serde_json::Value>(&line).is_ok_and(|event| {
+        ["timestamp", "level", "target"]
+            .iter()
+            .all(|key| event.get(key).is_some())
+    });
+    json_event || is_text_event(&line)
+}
+
+// tracing-subscriber's text format: `<RFC 3339 timestamp> <LEVEL> ...`.
+fn is_text_event(line: &str) -> bool {
+    let mut words = line.split_whitespace();
+    let timestamp = words.next().unwrap_or_default();
+    let level = words.next().unwrap_or_default();
+    timestamp.len() >= 20
+        && timestamp.as_bytes()[4] == b'-'
+        && timestamp.as_bytes()[10] == b'T'
+        && ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"].contains(&level)
+}
+
+// Files named like the plugin's artifacts in the directories cleanup
+// manages, for claiming what earlier versions wrote before the list existed.
+pub fn plugin_named_files(config: &DebugToolsConfig) -> Result<Vec<PathBuf>, RepositoryError> {
+    let matchers: [(PathBuf, NameMatcher); 6] = [
+        (config.console_log_dir(), |_, name| {
+            name.ends_with(".jsonl") && name.starts_with("frontend_console_")
+        }),
+        (config.artifact_paths().network_logs, |_, name| {
+            name.ends_with(".jsonl") && name.starts_with("network_")
+        }),
+        (config.snapshot_dir(), |_, name| {
+            snapshot_file_timestamp(name).is_some()
+        }),
+        (config.dom_snapshot_dir(), |_, name| {
+            name.starts_with("dom_")
+                && [".html", ".html.gz", ".json"]
+                    .iter()
+                    .any(|extension| name.ends_with(extension))
+        }),
+        (config.screenshot_dir(), |path, _| {
+            image_format(path).is_some()
+        }),
+        (config.config_history_dir(), |_, name| {
+            name.starts_with("effective_config_") && name.ends_with(".json")
+        }),
+    ];
+
+    let mut paths = Vec::new();
+    for (directory, matches) in matchers {
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| matches(&path, name))
+            {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
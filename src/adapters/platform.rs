@@ -0,0 +1,261 @@
+use crate::domain::{FilesystemInfo, FilesystemKind, GpuAdapterInfo, PlatformGpuInfo};
+
+// Best-effort OS-level GPU enumeration. Only Linux exposes adapters without
+// extra dependencies (via DRM sysfs); elsewhere the adapter list stays empty
+// and the WebGL report from the frontend is the only source.
+pub fn query_gpu_info() -> PlatformGpuInfo {
+    PlatformGpuInfo {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        adapters: query_adapters(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn query_adapters() -> Vec<GpuAdapterInfo> {
+    use std::fs;
+    use std::path::Path;
+
+    let read_trimmed = |path: &Path| {
+        fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    let mut adapters: Vec<(String, GpuAdapterInfo)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            // `card0` is an adapter; `card0-HDMI-A-1` is one of its connectors.
+            let is_card = name.strip_prefix("card").is_some_and(|index| {
+                !index.is_empty() && index.chars().all(|c| c.is_ascii_digit())
+            });
+            if !is_card {
+                return None;
+            }
+
+            let device = entry.path().join("device");
+            let driver = fs::read_link(device.join("driver")).ok().and_then(|link| {
+                link.file_name()
+                    .and_then(|name| name.to_str())
+                    .map(str::to_string)
+            });
+
+            Some((
+                name.clone(),
+                GpuAdapterInfo {
+                    name: Some(name),
+                    vendor_id: read_trimmed(&device.join("vendor")),
+                    device_id: read_trimmed(&device.join("device")),
+                    driver,
+                },
+            ))
+        })
+        .collect();
+
+    adapters.sort_by(|a, b| a.0.cmp(&b.0));
+    adapters.into_iter().map(|(_, adapter)| adapter).collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn query_adapters() -> Vec<GpuAdapterInfo> {
+    Vec::new()
+}
+
+// Used to tell a live instance lock from one left behind by a crashed
+// process. Unknown platforms report every pid as alive so a lock is never
+// reclaimed by mistake.
+#[cfg(target_os = "linux")]
+pub fn process_alive(pid: u32) -> bool {
+    std::path::Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("ps")
+        .args(["-p", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_or(true, |status| status.success())
+}
+
+#[cfg(windows)]
+pub fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map_or(true, |output| {
+            String::from_utf8_lossy(&output.stdout).contains(&pid.to_string())
+        })
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb",
+    "smb2",
+    "smb3",
+    "smbfs",
+    "afpfs",
+    "webdav",
+    "fuse.sshfs",
+    "sshfs",
+    "9p",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "lustre",
+];
+
+fn classify(fs_type: Option<String>) -> FilesystemInfo {
+    let kind = match fs_type.as_deref() {
+        Some(fs_type) if NETWORK_FILESYSTEMS.contains(&fs_type) => FilesystemKind::Network,
+        Some(_) => FilesystemKind::Local,
+        None => FilesystemKind::Unknown,
+    };
+    FilesystemInfo { kind, fs_type }
+}
+
+// Filesystem type of the mount containing `path`: the longest mount point
+// that prefixes it.
+#[cfg(unix)]
+fn longest_mount<'a>(
+    path: &std::path::Path,
+    mounts: impl Iterator<Item = (&'a str, &'a str)>,
+) -> Option<String> {
+    mounts
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .map(|(_, fs_type)| fs_type.to_string())
+}
+
+#[cfg(target_os = "linux")]
+pub fn detect_filesystem(path: &std::path::Path) -> FilesystemInfo {
+    let Ok(mounts) = std::fs::read_to_string("/proc/self/mounts") else {
+        return classify(None);
+    };
+    // Mount points escape spaces as `\040`.
+    let parsed: Vec<(String, String)> = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let fs_type = fields.next()?.to_string();
+            Some((mount_point, fs_type))
+        })
+        .collect();
+
+    classify(longest_mount(
+        path,
+        parsed
+            .iter()
+            .map(|(mount_point, fs_type)| (mount_point.as_str(), fs_type.as_str())),
+    ))
+}
+
+// `mount` prints `<device> on <mount point> (<type>, <options>...)`.
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn detect_filesystem(path: &std::path::Path) -> FilesystemInfo {
+    let Ok(output) = std::process::Command::new("mount").output() else {
+        return classify(None);
+    };
+    let output = String::from_utf8_lossy(&output.stdout);
+    let parsed: Vec<(&str, &str)> = output
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let fs_type = options.split([',', ')']).next()?.trim();
+            Some((mount_point, fs_type))
+        })
+        .collect();
+
+    classify(longest_mount(path, parsed.into_iter()))
+}
+
+// UNC paths are the only case recognisable without Win32 calls; mapped
+// drive letters stay unknown.
+#[cfg(windows)]
+pub fn detect_filesystem(path: &std::path::Path) -> FilesystemInfo {
+    let display = path.to_string_lossy();
+    let unc = display.starts_with(r"\\?\UNC\")
+        || (display.starts_with(r"\\") && !display.starts_with(r"\\?\"));
+    if unc {
+        return FilesystemInfo {
+            kind: FilesystemKind::Network,
+            fs_type: Some("unc".to_string()),
+        };
+    }
+    classify(None)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn detect_filesystem(_path: &std::path::Path) -> FilesystemInfo {
+    classify(None)
+}
+
+// `detect_filesystem` on a helper thread, so reading the mount table does not
+// hold up the caller.
+pub struct FilesystemProbe {
+    path: std::path::PathBuf,
+    handle: Option<std::thread::JoinHandle<FilesystemInfo>>,
+}
+
+impl FilesystemProbe {
+    pub fn spawn(path: std::path::PathBuf) -> Self {
+        let probed = path.clone();
+        let handle = std::thread::Builder::new()
+            .name("debug-tools-fs-probe".to_string())
+            .spawn(move || probe_filesystem(&probed))
+            .map_err(|e| tracing::warn!(error = %e, "Failed to start filesystem probe thread"))
+            .ok();
+        Self { path, handle }
+    }
+
+    // Probes on the calling thread when the helper could not be started or
+    // panicked; the flag says so.
+    pub fn wait(self) -> (FilesystemInfo, bool) {
+        match self.handle.map(|handle| handle.join()) {
+            Some(Ok(filesystem)) => (filesystem, false),
+            _ => (probe_filesystem(&self.path), true),
+        }
+    }
+}
+
+pub fn probe_filesystem(path: &std::path::Path) -> FilesystemInfo {
+    detect_filesystem(&path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+}
+
+// Space left for unprivileged writes on the filesystem holding `path`.
+// `df -P` prints one header line, then `<fs> <blocks> <used> <available> ...`
+// in 1024-byte blocks with `-k`.
+#[cfg(unix)]
+pub fn available_disk_bytes(path: &std::path::Path) -> Option<u64> {
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    let available = output.lines().nth(1)?.split_whitespace().nth(3)?;
+    available.parse::<u64>().ok().map(|blocks| blocks * 1024)
+}
+
+#[cfg(not(unix))]
+pub fn available_disk_bytes(_path: &std::path::Path) -> Option<u64> {
+    None
+}
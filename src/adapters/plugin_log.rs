@@ -0,0 +1,69 @@
+use crate::config::DebugToolsConfig;
+use crate::domain::plugin_log::parse_plugin_log_line;
+use crate::domain::PluginLogLine;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+// The last `lines` lines of `path`, read in one streaming pass so a large
+// file is never held in memory. Invalid UTF-8 is replaced, not skipped.
+pub fn tail_plugin_log(path: &Path, lines: usize) -> io::Result<Vec<PluginLogLine>> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut tail = VecDeque::with_capacity(lines.min(4096));
+    for line in reader.split(b'\n') {
+        let line = line?;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        if tail.len() == lines {
+            tail.pop_front();
+        }
+        if lines > 0 {
+            tail.push_back(line);
+        }
+    }
+    Ok(tail
+        .iter()
+        .map(|line| parse_plugin_log_line(&String::from_utf8_lossy(line)))
+        .collect())
+}
+
+// The live file first, then its rotated copies, which tauri-plugin-log
+// names `<stem>_<timestamp>.<extension>`.
+pub fn plugin_log_files(config: &DebugToolsConfig) -> Vec<PathBuf> {
+    let Some(live) = config.plugin_log_path() else {
+        return Vec::new();
+    };
+    let (Some(dir), Some(stem)) = (
+        live.parent(),
+        live.file_stem().and_then(|stem| stem.to_str()),
+    ) else {
+        return Vec::new();
+    };
+    let extension = live.extension().and_then(|extension| extension.to_str());
+    let rotated_prefix = format!("{}_", stem);
+
+    let mut files = vec![live.clone()];
+    let Ok(listing) = fs::read_dir(dir) else {
+        return files;
+    };
+    let mut rotated: Vec<PathBuf> = listing
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&rotated_prefix))
+                && path.extension().and_then(|extension| extension.to_str()) == extension
+        })
+        .collect();
+    rotated.sort();
+    files.extend(rotated);
+    files
+}
+
+pub fn is_live_plugin_log(config: &DebugToolsConfig, path: &Path) -> bool {
+    config.plugin_log_path().as_deref() == Some(path)
+}
@@ -0,0 +1,81 @@
+use super::screenshot_diff::decode;
+use crate::domain::RepositoryError;
+use std::fs;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+pub fn frame_path(dir: &Path, index: usize) -> PathBuf {
+    dir.join(format!("frame_{:05}.png", index))
+}
+
+// Numbered frames in `dir`, in capture order.
+pub fn list_frames(dir: &Path) -> Result<Vec<PathBuf>, RepositoryError> {
+    let mut frames: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("frame_") && name.ends_with(".png"))
+        })
+        .collect();
+    frames.sort();
+    Ok(frames)
+}
+
+// Encodes the frames as an APNG with png's encoder, every frame decoded to
+// RGBA so palette or bit depth differences between captures do not matter.
+// Frames that do not decode or whose size differs from the first are left
+// out. Each frame is decoded once to pick the usable ones and again while
+// writing, so only one is held in memory at a time. Returns how many frames
+// made it in.
+pub fn assemble_apng(frames: &[PathBuf], fps: u32, out: &Path) -> Result<usize, RepositoryError> {
+    let mut size: Option<(u32, u32)> = None;
+    let mut usable = Vec::new();
+    for path in frames {
+        let frame = match decode(path) {
+            Ok(frame) => frame,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable recording frame");
+                continue;
+            }
+        };
+        match size {
+            Some(size) if size != (frame.width, frame.height) => {
+                tracing::warn!(path = %path.display(), "Skipping recording frame with a different size");
+                continue;
+            }
+            Some(_) => {}
+            None => size = Some((frame.width, frame.height)),
+        }
+        usable.push(path);
+    }
+
+    let Some((width, height)) = size else {
+        return Err(RepositoryError::Save(
+            "No usable frames to assemble".to_string(),
+        ));
+    };
+    let frame_count = u32::try_from(usable.len())
+        .map_err(|_| RepositoryError::Save("Too many frames to assemble".to_string()))?;
+
+    let save = |e: png::EncodingError| RepositoryError::Save(e.to_string());
+    let mut encoder = png::Encoder::new(BufWriter::new(fs::File::create(out)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // Loops forever; the first frame is also the still image viewers
+    // without APNG support show.
+    encoder.set_animated(frame_count, 0).map_err(save)?;
+    encoder
+        .set_frame_delay(1, fps.clamp(1, u16::MAX as u32) as u16)
+        .map_err(save)?;
+    let mut writer = encoder.write_header().map_err(save)?;
+    for path in usable {
+        writer
+            .write_image_data(&decode(path)?.pixels)
+            .map_err(save)?;
+    }
+    writer.finish().map_err(save)?;
+
+    Ok(frame_count as usize)
+}
@@ -0,0 +1,123 @@
+use crate::domain::{RepositoryError, ScreenshotDiff};
+use std::fs;
+use std::io::BufWriter;
+use std::path::Path;
+
+// Channel differences up to this are treated as rendering noise
+// (antialiasing, subpixel text) rather than a change.
+const CHANNEL_TOLERANCE: u8 = 16;
+
+const CHANGED: [u8; 4] = [255, 0, 64, 255];
+
+pub(crate) struct Rgba {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Rgba {
+    fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let at = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels.get(at..at + 4)
+    }
+}
+
+pub(crate) fn decode(path: &Path) -> Result<Rgba, RepositoryError> {
+    let load = |e: png::DecodingError| RepositoryError::Load(format!("{}: {}", path.display(), e));
+
+    let mut decoder = png::Decoder::new(fs::File::open(path)?);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(load)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(load)?;
+    buf.truncate(info.buffer_size());
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buf
+            .chunks_exact(2)
+            .flat_map(|px| [px[0], px[0], px[0], px[1]])
+            .collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => {
+            return Err(RepositoryError::Load(format!(
+                "{}: palette was not expanded",
+                path.display()
+            )))
+        }
+    };
+
+    Ok(Rgba {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+// Writes a diff image sized to cover both screenshots: changed pixels are
+// highlighted, unchanged ones are a faded copy of `a`. Pixels present in
+// only one image count as changed.
+pub fn diff_screenshots(a: &Path, b: &Path, out: &Path) -> Result<ScreenshotDiff, RepositoryError> {
+    let first = decode(a)?;
+    let second = decode(b)?;
+    let width = first.width.max(second.width);
+    let height = first.height.max(second.height);
+
+    let mut image = Vec::with_capacity(width as usize * height as usize * 4);
+    let mut changed_pixels = 0u64;
+    for y in 0..height {
+        for x in 0..width {
+            match (first.pixel(x, y), second.pixel(x, y)) {
+                (Some(pa), Some(pb))
+                    if pa
+                        .iter()
+                        .zip(pb)
+                        .all(|(ca, cb)| ca.abs_diff(*cb) <= CHANNEL_TOLERANCE) =>
+                {
+                    let luma =
+                        (pa[0] as u32 * 299 + pa[1] as u32 * 587 + pa[2] as u32 * 114) / 1000;
+                    let faded = (255 - (255 - luma) / 4) as u8;
+                    image.extend_from_slice(&[faded, faded, faded, 255]);
+                }
+                _ => {
+                    changed_pixels += 1;
+                    image.extend_from_slice(&CHANGED);
+                }
+            }
+        }
+    }
+
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let save = |e: png::EncodingError| RepositoryError::Save(e.to_string());
+    let mut encoder = png::Encoder::new(BufWriter::new(fs::File::create(out)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&image))
+        .map_err(save)?;
+
+    let total_pixels = width as u64 * height as u64;
+    Ok(ScreenshotDiff {
+        diff_path: out.to_path_buf(),
+        width,
+        height,
+        changed_pixels,
+        total_pixels,
+        changed_percent: if total_pixels == 0 {
+            0.0
+        } else {
+            changed_pixels as f64 * 100.0 / total_pixels as f64
+        },
+        size_mismatch: (first.width, first.height) != (second.width, second.height),
+    })
+}
@@ -0,0 +1,371 @@
+use super::filesystem::delete_owned_file;
+use super::screenshot_diff::decode;
+use super::secure_delete::SecureDelete;
+use crate::config::DebugToolsConfig;
+use crate::domain::{DeletedFile, RepositoryError, ScreenshotInfo, ScreenshotRegion};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Runtime};
+
+const HEADER_READ_BYTES: u64 = 64 * 1024;
+// iTXt keyword of the region a cropped screenshot shows.
+const REGION_KEYWORD: &str = "debug-tools:region";
+
+#[derive(Debug, Clone)]
+struct CachedHeader {
+    modified: Option<SystemTime>,
+    size_bytes: u64,
+    dimensions: Option<(u32, u32)>,
+    region: Option<ScreenshotRegion>,
+}
+
+// Dimensions and regions are read from image headers on first listing and
+// reused until the file changes on disk.
+#[derive(Debug, Default)]
+pub struct ScreenshotIndex {
+    entries: Mutex<HashMap<PathBuf, CachedHeader>>,
+}
+
+impl ScreenshotIndex {
+    fn describe(
+        &self,
+        path: &Path,
+        metadata: &fs::Metadata,
+    ) -> (Option<(u32, u32)>, Option<ScreenshotRegion>) {
+        let modified = metadata.modified().ok();
+        let size_bytes = metadata.len();
+
+        if let Ok(entries) = self.entries.lock() {
+            if let Some(cached) = entries.get(path) {
+                if cached.modified == modified && cached.size_bytes == size_bytes {
+                    return (cached.dimensions, cached.region.clone());
+                }
+            }
+        }
+
+        let dimensions = read_image_dimensions(path);
+        let region = read_region(path);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                path.to_path_buf(),
+                CachedHeader {
+                    modified,
+                    size_bytes,
+                    dimensions,
+                    region: region.clone(),
+                },
+            );
+        }
+
+        (dimensions, region)
+    }
+
+    fn forget(&self, path: &Path) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(path);
+        }
+    }
+}
+
+pub fn list_screenshots(
+    config: &DebugToolsConfig,
+    index: &ScreenshotIndex,
+    limit: usize,
+    before_ts: Option<i64>,
+) -> Result<Vec<ScreenshotInfo>, RepositoryError> {
+    let screenshot_dir = config.screenshot_dir();
+    if !screenshot_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let thumbnail_dir = screenshot_dir.join("thumbnails");
+    let mut screenshots = Vec::new();
+
+    for entry in fs::read_dir(&screenshot_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let Some(format) = image_format(&path) else {
+            continue;
+        };
+
+        let Some(capture_id) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+
+        let timestamp = capture_timestamp(&capture_id, &metadata);
+        if before_ts.is_some_and(|before| timestamp >= before) {
+            continue;
+        }
+
+        let (dimensions, region) = index.describe(&path, &metadata);
+
+        screenshots.push(ScreenshotInfo {
+            has_thumbnail: has_thumbnail(&thumbnail_dir, &capture_id),
+            path,
+            capture_id,
+            timestamp,
+            width: dimensions.map(|(width, _)| width),
+            height: dimensions.map(|(_, height)| height),
+            size_bytes: metadata.len(),
+            format: format.to_string(),
+            region,
+        });
+    }
+
+    screenshots.sort_by_key(|screenshot| std::cmp::Reverse(screenshot.timestamp));
+    screenshots.truncate(limit);
+
+    Ok(screenshots)
+}
+
+// The newest full capture (not a region crop) at most `within_secs` old.
+// Capture ids carry whole seconds, so ages are too.
+pub fn newest_screenshot_within(
+    config: &DebugToolsConfig,
+    index: &ScreenshotIndex,
+    now_secs: i64,
+    within_secs: u64,
+) -> Result<Option<ScreenshotInfo>, RepositoryError> {
+    let oldest = now_secs.saturating_sub(within_secs as i64);
+    Ok(list_screenshots(config, index, usize::MAX, None)?
+        .into_iter()
+        .take_while(|screenshot| screenshot.timestamp >= oldest)
+        .find(|screenshot| screenshot.region.is_none()))
+}
+
+pub fn delete_screenshot(
+    config: &DebugToolsConfig,
+    index: &ScreenshotIndex,
+    path: &Path,
+) -> Result<DeletedFile, RepositoryError> {
+    let secure = SecureDelete::configured(config, &config.screenshot_dir());
+    let deletion = delete_owned_file(path, secure.as_ref())?;
+    index.forget(path);
+
+    tracing::info!(
+        path = %path.display(),
+        securely_deleted = deletion.securely_deleted,
+        "Screenshot deleted"
+    );
+
+    Ok(deletion)
+}
+
+// Captures the OS window showing `window_title` through tauri-plugin-screenshots,
+// falling back to any window owned by this app.
+pub async fn capture_window_screenshot<R: Runtime>(
+    app: &AppHandle<R>,
+    window_title: &str,
+) -> Result<PathBuf, String> {
+    let windows = tauri_plugin_screenshots::get_screenshotable_windows().await?;
+    let app_name = &app.package_info().name;
+
+    let target = windows
+        .iter()
+        .find(|window| window.title == window_title)
+        .or_else(|| windows.iter().find(|window| &window.app_name == app_name))
+        .ok_or("No screenshotable window found")?;
+
+    tauri_plugin_screenshots::get_window_screenshot(app.clone(), target.id).await
+}
+
+// Writes the `region.crop` pixels of the PNG at `source` to `out`, with the
+// region recorded in an iTXt chunk ahead of the image data.
+pub fn crop_screenshot(
+    source: &Path,
+    region: &ScreenshotRegion,
+    out: &Path,
+) -> Result<(), RepositoryError> {
+    let image = decode(source)?;
+    let crop = region.crop;
+    if crop.x + crop.width > image.width || crop.y + crop.height > image.height {
+        return Err(RepositoryError::Load(format!(
+            "{}: crop exceeds the {}x{} capture",
+            source.display(),
+            image.width,
+            image.height
+        )));
+    }
+
+    let row_bytes = image.width as usize * 4;
+    let mut pixels = Vec::with_capacity(crop.width as usize * crop.height as usize * 4);
+    for y in crop.y..crop.y + crop.height {
+        let start = y as usize * row_bytes + crop.x as usize * 4;
+        pixels.extend_from_slice(&image.pixels[start..start + crop.width as usize * 4]);
+    }
+
+    let save = |e: png::EncodingError| RepositoryError::Save(e.to_string());
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(fs::File::create(out)?),
+        crop.width,
+        crop.height,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .add_itxt_chunk(REGION_KEYWORD.to_string(), serde_json::to_string(region)?)
+        .map_err(save)?;
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(save)?;
+    Ok(())
+}
+
+pub(crate) fn image_format(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("png"),
+        "jpg" | "jpeg" => Some("jpeg"),
+        "webp" => Some("webp"),
+        _ => None,
+    }
+}
+
+// `copy_screenshot_to_debug_dir` prefixes filenames with the copy time in
+// seconds; anything else falls back to the file's modification time.
+fn capture_timestamp(capture_id: &str, metadata: &fs::Metadata) -> i64 {
+    if let Some(ts) = capture_id
+        .split_once('_')
+        .and_then(|(prefix, _)| prefix.parse::<i64>().ok())
+    {
+        return ts;
+    }
+
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+fn has_thumbnail(thumbnail_dir: &Path, capture_id: &str) -> bool {
+    let Ok(entries) = fs::read_dir(thumbnail_dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        entry
+            .path()
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem == capture_id)
+    })
+}
+
+pub fn read_image_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let mut header = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(HEADER_READ_BYTES)
+        .read_to_end(&mut header)
+        .ok()?;
+
+    png_dimensions(&header)
+        .or_else(|| jpeg_dimensions(&header))
+        .or_else(|| webp_dimensions(&header))
+}
+
+fn read_region(path: &Path) -> Option<ScreenshotRegion> {
+    if image_format(path) != Some("png") {
+        return None;
+    }
+    let reader = png::Decoder::new(fs::File::open(path).ok()?)
+        .read_info()
+        .ok()?;
+    let chunk = reader
+        .info()
+        .utf8_text
+        .iter()
+        .find(|chunk| chunk.keyword == REGION_KEYWORD)?;
+    serde_json::from_str(&chunk.get_text().ok()?).ok()
+}
+
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if bytes.len() < 24 || !bytes.starts_with(SIGNATURE) || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes(bytes[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(bytes[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut offset = 2;
+    while offset + 4 <= bytes.len() {
+        if bytes[offset] != 0xFF {
+            return None;
+        }
+
+        let marker = bytes[offset + 1];
+        if marker == 0xFF {
+            offset += 1;
+            continue;
+        }
+
+        let segment_len = u16::from_be_bytes([bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let is_start_of_frame =
+            matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+
+        if is_start_of_frame {
+            if offset + 9 > bytes.len() {
+                return None;
+            }
+            let height = u16::from_be_bytes([bytes[offset + 5], bytes[offset + 6]]) as u32;
+            let width = u16::from_be_bytes([bytes[offset + 7], bytes[offset + 8]]) as u32;
+            return Some((width, height));
+        }
+
+        offset += 2 + segment_len;
+    }
+
+    None
+}
+
+fn webp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.len() < 30 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return None;
+    }
+
+    match &bytes[12..16] {
+        b"VP8X" => {
+            let width = u32::from_le_bytes([bytes[24], bytes[25], bytes[26], 0]) + 1;
+            let height = u32::from_le_bytes([bytes[27], bytes[28], bytes[29], 0]) + 1;
+            Some((width, height))
+        }
+        b"VP8 " => {
+            let width = u16::from_le_bytes([bytes[26], bytes[27]]) & 0x3FFF;
+            let height = u16::from_le_bytes([bytes[28], bytes[29]]) & 0x3FFF;
+            Some((width as u32, height as u32))
+        }
+        b"VP8L" => {
+            let bits = u32::from_le_bytes([bytes[21], bytes[22], bytes[23], bytes[24]]);
+            let width = (bits & 0x3FFF) + 1;
+            let height = ((bits >> 14) & 0x3FFF) + 1;
+            Some((width, height))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,57 @@
+use super::platform::probe_filesystem;
+use crate::config::DebugToolsConfig;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+static ZEROS: [u8; 64 * 1024] = [0; 64 * 1024];
+
+// Copy-on-write filesystems write the zeros to new blocks and keep the old
+// ones (and any snapshot of them) intact.
+const COPY_ON_WRITE_FILESYSTEMS: &[&str] = &["apfs", "btrfs", "zfs", "bcachefs"];
+
+// Whether overwriting before unlinking is worth doing under a directory,
+// decided once per cleanup. `note` says why it is not.
+#[derive(Debug, Clone)]
+pub struct SecureDelete {
+    pub note: Option<String>,
+}
+
+impl SecureDelete {
+    pub fn for_directory(dir: &Path) -> Self {
+        let filesystem = probe_filesystem(dir);
+        let note = filesystem
+            .fs_type
+            .filter(|fs_type| COPY_ON_WRITE_FILESYSTEMS.contains(&fs_type.as_str()))
+            .map(|fs_type| {
+                format!(
+                    "not overwritten: {} is copy-on-write, so the old blocks would survive",
+                    fs_type
+                )
+            });
+        Self { note }
+    }
+
+    // `None` unless the config asks for secure deletion.
+    pub fn configured(config: &DebugToolsConfig, dir: &Path) -> Option<Self> {
+        config.secure_delete.then(|| Self::for_directory(dir))
+    }
+
+    pub fn overwrites(&self) -> bool {
+        self.note.is_none()
+    }
+}
+
+// One pass of zeros over the file's current length, in fixed-size chunks so
+// large logs are never read or buffered, synced before returning.
+// Best-effort: SSD wear-levelling may still keep old copies of the blocks.
+pub fn overwrite_with_zeros(path: &Path) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new().write(true).open(path)?;
+    let mut remaining = file.metadata()?.len();
+    while remaining > 0 {
+        let chunk = remaining.min(ZEROS.len() as u64) as usize;
+        file.write_all(&ZEROS[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()
+}
@@ -0,0 +1,254 @@
+use crate::config::DebugToolsConfig;
+use crate::domain::{
+    CaptureSizeReport, DebugSnapshot, RepositoryError, SnapshotListEntry, SnapshotPage,
+    SnapshotSort, SnapshotSummary,
+};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// The only fields needed to index a snapshot found on disk.
+#[derive(Default, Deserialize)]
+struct IndexedFields {
+    #[serde(default)]
+    route_state: Option<serde_json::Value>,
+    #[serde(default)]
+    size_report: Option<CaptureSizeReport>,
+    #[serde(default)]
+    annotations: serde_json::Map<String, serde_json::Value>,
+}
+
+// The only fields a summary needs; everything else is skipped unparsed.
+#[derive(Deserialize)]
+struct SummaryFields {
+    #[serde(default)]
+    webview_state: Option<SummaryWebView>,
+    #[serde(default)]
+    console_logs: Vec<IgnoredAny>,
+    #[serde(default)]
+    screenshot_path: Option<PathBuf>,
+    #[serde(default)]
+    dom_snapshot_path: Option<PathBuf>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SummaryWebView {
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+fn read_indexed_fields(path: &Path) -> IndexedFields {
+    fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+// Snapshot files in timestamp order. The log directory is scanned once, on
+// the first listing; saves made through the repository are recorded as they
+// happen, so later pages never touch the disk.
+#[derive(Debug, Default)]
+pub struct SnapshotIndex {
+    entries: Mutex<Option<Vec<SnapshotListEntry>>>,
+}
+
+impl SnapshotIndex {
+    pub fn record(&self, entry: SnapshotListEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        // Until the first listing the scan will pick the file up anyway.
+        let Some(entries) = entries.as_mut() else {
+            return;
+        };
+
+        entries.retain(|existing| existing.path != entry.path);
+        let at = entries.partition_point(|existing| existing.timestamp <= entry.timestamp);
+        entries.insert(at, entry);
+    }
+
+    // Looks a snapshot up by its capture id, the file stem (`snapshot_<ts>`).
+    pub fn find(
+        &self,
+        config: &DebugToolsConfig,
+        capture_id: &str,
+    ) -> Result<Option<SnapshotListEntry>, RepositoryError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.is_none() {
+            *entries = Some(scan_snapshots(&config.snapshot_dir())?);
+        }
+
+        Ok(entries
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|entry| entry.path.file_stem().and_then(|stem| stem.to_str()) == Some(capture_id))
+            .cloned())
+    }
+
+    pub fn forget(&self, path: &Path) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entries) = entries.as_mut() {
+            entries.retain(|entry| entry.path != path);
+        }
+    }
+
+    pub fn page(
+        &self,
+        config: &DebugToolsConfig,
+        offset: usize,
+        limit: usize,
+        sort: SnapshotSort,
+        route: Option<&str>,
+    ) -> Result<SnapshotPage, RepositoryError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.is_none() {
+            *entries = Some(scan_snapshots(&config.snapshot_dir())?);
+        }
+        let entries = entries.as_deref().unwrap_or_default();
+        let matches = |entry: &&SnapshotListEntry| {
+            route.map_or(true, |route| entry.route.as_deref() == Some(route))
+        };
+
+        let total = entries.iter().filter(matches).count();
+        let page = match sort {
+            SnapshotSort::Asc => entries
+                .iter()
+                .filter(matches)
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+            SnapshotSort::Desc => entries
+                .iter()
+                .rev()
+                .filter(matches)
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+        };
+
+        Ok(SnapshotPage {
+            entries: page,
+            total,
+        })
+    }
+
+    // Newest first, only snapshots taken before `before_timestamp`; only the
+    // returned files are read.
+    pub fn summaries(
+        &self,
+        config: &DebugToolsConfig,
+        limit: usize,
+        before_timestamp: Option<i64>,
+    ) -> Result<Vec<SnapshotSummary>, RepositoryError> {
+        let page: Vec<SnapshotListEntry> = {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            if entries.is_none() {
+                *entries = Some(scan_snapshots(&config.snapshot_dir())?);
+            }
+            entries
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .rev()
+                .filter(|entry| before_timestamp.map_or(true, |before| entry.timestamp < before))
+                .take(limit)
+                .cloned()
+                .collect()
+        };
+        Ok(page.iter().map(summarize_snapshot).collect())
+    }
+}
+
+pub fn summarize_snapshot(entry: &SnapshotListEntry) -> SnapshotSummary {
+    let mut summary = SnapshotSummary {
+        path: entry.path.clone(),
+        timestamp: entry.timestamp,
+        size_bytes: entry.size_bytes,
+        legacy: entry.legacy,
+        url: None,
+        title: None,
+        label: None,
+        console_log_count: 0,
+        has_screenshot: false,
+        has_dom_snapshot: false,
+        error: None,
+    };
+    let fields = fs::read(&entry.path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            serde_json::from_slice::<SummaryFields>(&bytes).map_err(|e| e.to_string())
+        });
+    match fields {
+        Ok(fields) => {
+            let webview = fields.webview_state;
+            summary.url = webview.as_ref().and_then(|webview| webview.url.clone());
+            summary.title = webview.and_then(|webview| webview.title);
+            summary.label = fields.label;
+            summary.console_log_count = fields.console_logs.len();
+            summary.has_screenshot = fields.screenshot_path.is_some_and(|path| path.is_file());
+            summary.has_dom_snapshot = fields.dom_snapshot_path.is_some_and(|path| path.is_file());
+        }
+        Err(error) => summary.error = Some(error),
+    }
+    summary
+}
+
+// Timestamp encoded in a snapshot file name, and whether it is a legacy
+// `tauri_debug_snapshot_<ts>.json` file. Both spellings are in seconds, like
+// `DebugSnapshot::timestamp`.
+pub fn snapshot_file_timestamp(name: &str) -> Option<(i64, bool)> {
+    let stem = name.strip_suffix(".json")?;
+    if let Some(secs) = stem.strip_prefix("tauri_debug_snapshot_") {
+        return Some((secs.parse().ok()?, true));
+    }
+    Some((stem.strip_prefix("snapshot_")?.parse().ok()?, false))
+}
+
+pub fn scan_snapshots(dir: &Path) -> Result<Vec<SnapshotListEntry>, RepositoryError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        let Some((timestamp, legacy)) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(snapshot_file_timestamp)
+        else {
+            continue;
+        };
+
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let fields = read_indexed_fields(&path);
+        entries.push(SnapshotListEntry {
+            path,
+            timestamp,
+            size_bytes: metadata.len(),
+            route: fields
+                .route_state
+                .as_ref()
+                .and_then(DebugSnapshot::route_key),
+            size_report: fields.size_report,
+            legacy,
+            annotations: fields.annotations,
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.timestamp);
+    Ok(entries)
+}
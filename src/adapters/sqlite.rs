@@ -0,0 +1,228 @@
+use crate::domain::{
+    ConsoleLogEntry, LogLevel, LogQuery, LogQueryService, LogStore, RepositoryError,
+};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection};
+use std::path::Path;
+use std::sync::Mutex;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS console_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp INTEGER NOT NULL,
+    level TEXT NOT NULL,
+    level_rank INTEGER,
+    message_lower TEXT NOT NULL,
+    entry TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS console_logs_timestamp ON console_logs (timestamp);
+CREATE INDEX IF NOT EXISTS console_logs_level_rank ON console_logs (level_rank);
+";
+
+// Console log store backed by SQLite, for sessions too long to scan as JSONL.
+// Level, time range and message text are filtered in SQL against indexed
+// columns; the remaining `LogQuery` filters (fields, trace id, source) run on
+// the rows SQL returns. With `max_rows` the oldest rows are deleted once the
+// table grows past it.
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+    max_rows: Option<u64>,
+}
+
+impl SqliteRepository {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        Self::with_connection(Connection::open(path).map_err(load_error)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, RepositoryError> {
+        Self::with_connection(Connection::open_in_memory().map_err(load_error)?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, RepositoryError> {
+        connection.execute_batch(SCHEMA).map_err(load_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+            max_rows: None,
+        })
+    }
+
+    pub fn with_max_rows(mut self, max_rows: u64) -> Self {
+        self.max_rows = Some(max_rows.max(1));
+        self
+    }
+
+    pub fn row_count(&self) -> Result<u64, RepositoryError> {
+        let connection = self.connection()?;
+        connection
+            .query_row("SELECT COUNT(*) FROM console_logs", [], |row| row.get(0))
+            .map_err(load_error)
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, RepositoryError> {
+        self.connection
+            .lock()
+            .map_err(|_| RepositoryError::Load("SQLite connection poisoned".to_string()))
+    }
+
+    // Rows matching the SQL-expressible part of `query`, newest first when
+    // `limit` is set (so the limit keeps the most recent ones), then handed to
+    // `visit` in storage order.
+    fn select(
+        &self,
+        query: &LogQuery,
+        limit: Option<usize>,
+        visit: &mut dyn FnMut(ConsoleLogEntry),
+    ) -> Result<(), RepositoryError> {
+        let mut sql = String::from("SELECT entry FROM console_logs WHERE 1 = 1");
+        let mut values: Vec<Value> = Vec::new();
+        if let Some(since) = query.since {
+            sql.push_str(" AND timestamp >= ?");
+            values.push(Value::Integer(since));
+        }
+        if let Some(until) = query.until {
+            sql.push_str(" AND timestamp < ?");
+            values.push(Value::Integer(until));
+        }
+        if let Some(min_level) = query.min_level {
+            sql.push_str(" AND level_rank >= ?");
+            values.push(Value::Integer(level_rank(min_level)));
+        }
+        if let Some(text) = query.text.as_deref().filter(|text| !text.is_empty()) {
+            sql.push_str(" AND message_lower LIKE ? ESCAPE '\\'");
+            values.push(Value::Text(format!(
+                "%{}%",
+                escape_like(&text.to_lowercase())
+            )));
+        }
+        match limit {
+            Some(limit) => {
+                sql.push_str(" ORDER BY id DESC LIMIT ?");
+                values.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+            }
+            None => sql.push_str(" ORDER BY id"),
+        }
+
+        let connection = self.connection()?;
+        let mut statement = connection.prepare(&sql).map_err(load_error)?;
+        let rows = statement
+            .query_map(params_from_iter(values), |row| row.get::<_, String>(0))
+            .map_err(load_error)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let entry: ConsoleLogEntry = serde_json::from_str(&row.map_err(load_error)?)?;
+            if query.matches(&entry) {
+                entries.push(entry);
+            }
+        }
+        if limit.is_some() {
+            entries.reverse();
+        }
+        entries.into_iter().for_each(visit);
+        Ok(())
+    }
+}
+
+// Filters `select` cannot push into SQL; with any of them the limit has to be
+// applied after filtering.
+fn filters_in_rust(query: &LogQuery) -> bool {
+    !query.field_equals.is_empty()
+        || query.ingest_source.is_some()
+        || query.trace_id.as_deref().is_some_and(|id| !id.is_empty())
+}
+
+impl LogQueryService for SqliteRepository {
+    fn scan(
+        &self,
+        query: &LogQuery,
+        visit: &mut dyn FnMut(ConsoleLogEntry),
+    ) -> Result<(), RepositoryError> {
+        self.select(query, None, visit)
+    }
+
+    fn query_console_logs(
+        &self,
+        query: &LogQuery,
+    ) -> Result<Vec<ConsoleLogEntry>, RepositoryError> {
+        let mut entries = Vec::new();
+        if filters_in_rust(query) {
+            self.scan(query, &mut |entry| entries.push(entry))?;
+            let excess = entries
+                .len()
+                .saturating_sub(query.limit.unwrap_or(usize::MAX));
+            entries.drain(..excess);
+        } else {
+            self.select(query, query.limit, &mut |entry| entries.push(entry))?;
+        }
+        Ok(oldest_first(entries))
+    }
+}
+
+impl LogStore for SqliteRepository {
+    fn append_console_logs(&self, logs: &[ConsoleLogEntry]) -> Result<(), RepositoryError> {
+        if logs.is_empty() {
+            return Ok(());
+        }
+
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(save_error)?;
+        {
+            let mut insert = transaction
+                .prepare_cached(
+                    "INSERT INTO console_logs (timestamp, level, level_rank, message_lower, entry)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(save_error)?;
+            for entry in logs {
+                insert
+                    .execute(params![
+                        entry.timestamp,
+                        entry.level,
+                        LogLevel::from_str_lenient(&entry.level).map(level_rank),
+                        entry.message.to_lowercase(),
+                        serde_json::to_string(entry)?,
+                    ])
+                    .map_err(save_error)?;
+            }
+        }
+        if let Some(max_rows) = self.max_rows {
+            transaction
+                .execute(
+                    "DELETE FROM console_logs WHERE id <= (SELECT MAX(id) FROM console_logs) - ?1",
+                    params![i64::try_from(max_rows).unwrap_or(i64::MAX)],
+                )
+                .map_err(save_error)?;
+        }
+        transaction.commit().map_err(save_error)
+    }
+}
+
+// Same tie-break as the JSONL scan: timestamp, then insertion order.
+fn oldest_first(entries: Vec<ConsoleLogEntry>) -> Vec<ConsoleLogEntry> {
+    let mut entries: Vec<(usize, ConsoleLogEntry)> = entries.into_iter().enumerate().collect();
+    entries.sort_by_key(|(seq, entry)| (entry.timestamp, *seq));
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+fn level_rank(level: LogLevel) -> i64 {
+    level as i64
+}
+
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn load_error(e: rusqlite::Error) -> RepositoryError {
+    RepositoryError::Load(e.to_string())
+}
+
+fn save_error(e: rusqlite::Error) -> RepositoryError {
+    RepositoryError::Save(e.to_string())
+}
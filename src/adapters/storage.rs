@@ -0,0 +1,213 @@
+use crate::config::{DebugToolsConfig, StorageMode};
+use crate::domain::{ArtifactKind, Durability, DurabilityLatency};
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The filesystem calls that move a staged file into place, so tests can
+// stand in for a share whose renames fail.
+pub trait FileOps {
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64>;
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdFileOps;
+
+impl FileOps for StdFileOps {
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        fs::copy(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+}
+
+// Write policy for the log directory. Network shares fail renames and
+// report transient errors far more often than local disks, so conservative
+// mode replaces files by copy+delete and retries longer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WritePolicy {
+    pub conservative: bool,
+    pub durability: Durability,
+}
+
+impl WritePolicy {
+    pub fn for_config(config: &DebugToolsConfig) -> Self {
+        Self {
+            conservative: config.storage_mode == StorageMode::Conservative,
+            durability: Durability::None,
+        }
+    }
+
+    pub fn for_artifact(config: &DebugToolsConfig, kind: ArtifactKind) -> Self {
+        Self {
+            durability: config.durability_for(kind),
+            ..Self::for_config(config)
+        }
+    }
+
+    fn attempts(self) -> u32 {
+        if self.conservative {
+            6
+        } else {
+            2
+        }
+    }
+
+    fn initial_backoff(self) -> Duration {
+        Duration::from_millis(if self.conservative { 100 } else { 20 })
+    }
+
+    pub fn retry<T>(self, mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut backoff = self.initial_backoff();
+        let mut attempt = 1;
+        loop {
+            match op() {
+                Err(e) if attempt < self.attempts() && is_transient(&e) => {
+                    tracing::debug!(attempt, error = %e, "Retrying transient write error");
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // Writes `contents` next to `path` and moves it into place, so readers
+    // never see a partial file. Returns the time spent on durability.
+    pub fn replace_file(self, path: &Path, contents: &[u8]) -> io::Result<Duration> {
+        self.replace_file_with(&StdFileOps, path, contents)
+    }
+
+    // On failure `path` keeps its previous contents and the staging file is
+    // removed.
+    pub fn replace_file_with(
+        self,
+        ops: &dyn FileOps,
+        path: &Path,
+        contents: &[u8],
+    ) -> io::Result<Duration> {
+        let staging = path.with_extension(format!("tmp.{}", std::process::id()));
+        let mut synced = {
+            let mut file = fs::File::create(&staging)?;
+            file.write_all(contents)?;
+            self.sync(&mut file)?
+        };
+
+        let moved = move_file(ops, &staging, path, self.conservative);
+        if moved.is_err() {
+            let _ = ops.remove_file(&staging);
+        }
+        moved?;
+
+        // The rename itself is only durable once the directory entry is.
+        if self.durability == Durability::Fsync {
+            let started = Instant::now();
+            if let Some(parent) = path.parent() {
+                sync_dir(parent)?;
+            }
+            synced += started.elapsed();
+        }
+        Ok(synced)
+    }
+
+    pub fn sync(self, file: &mut fs::File) -> io::Result<Duration> {
+        let started = Instant::now();
+        match self.durability {
+            Durability::None => return Ok(Duration::ZERO),
+            Durability::Flush => file.flush()?,
+            Durability::Fsync => file.sync_all()?,
+        }
+        Ok(started.elapsed())
+    }
+
+    pub fn move_into(self, from: &Path, to: &Path) -> io::Result<()> {
+        self.retry(|| move_file(&StdFileOps, from, to, self.conservative))
+    }
+}
+
+// Windows cannot open directories as files; NTFS journals renames anyway.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    fs::File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SyncCounters {
+    writes: u64,
+    total_us: u64,
+    max_us: u64,
+}
+
+// Time spent flushing and syncing, per durability level, for the
+// repository metrics.
+#[derive(Debug, Default)]
+pub struct DurabilityStats {
+    counters: Mutex<[SyncCounters; 3]>,
+}
+
+impl DurabilityStats {
+    pub fn record(&self, durability: Durability, elapsed: Duration) {
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = &mut counters[durability as usize];
+        let elapsed_us = elapsed.as_micros() as u64;
+        slot.writes += 1;
+        slot.total_us += elapsed_us;
+        slot.max_us = slot.max_us.max(elapsed_us);
+    }
+
+    pub fn report(&self) -> Vec<DurabilityLatency> {
+        let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        [Durability::None, Durability::Flush, Durability::Fsync]
+            .into_iter()
+            .zip(counters.iter())
+            .filter(|(_, slot)| slot.writes > 0)
+            .map(|(durability, slot)| DurabilityLatency {
+                durability,
+                writes: slot.writes,
+                avg_sync_us: slot.total_us / slot.writes,
+                max_sync_us: slot.max_us,
+            })
+            .collect()
+    }
+}
+
+fn move_file(ops: &dyn FileOps, from: &Path, to: &Path, conservative: bool) -> io::Result<()> {
+    if !conservative {
+        match ops.rename(from, to) {
+            Ok(()) => return Ok(()),
+            Err(e) => tracing::debug!(error = %e, "Rename failed; copying instead"),
+        }
+    }
+    ops.copy(from, to)?;
+    ops.remove_file(from)
+}
+
+fn is_transient(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::TimedOut
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+    )
+}
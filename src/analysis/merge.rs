@@ -0,0 +1,539 @@
+use crate::adapters::annotations::read_annotations;
+use crate::adapters::log_repair::{is_repaired_copy, open_console_log};
+use crate::analysis::sourcemap::StackResolver;
+use crate::domain::annotations::{attach_annotation, log_seq};
+use crate::domain::error_alarm::fingerprint;
+use crate::domain::plugin_log::{parse_plugin_log_line, to_console_entry};
+use crate::domain::{ConsoleLogEntry, LogAnnotation, LogLevel, RepositoryError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+pub const MERGED_MANIFEST_FILE: &str = "merged_manifest.json";
+pub const MERGED_TIMELINE_FILE: &str = "timeline.jsonl";
+pub const MERGED_FINGERPRINTS_FILE: &str = "error_fingerprints.json";
+// Bundle subdirectory holding copies of tauri-plugin-log's files.
+pub const BUNDLE_PLUGIN_LOG_DIR: &str = "plugin_log";
+// Zip bundles are unpacked here, under `out_dir`, and removed after merging.
+const UNPACK_DIR: &str = ".unpacked";
+// Cap on what one zip bundle may expand to, against zip bombs.
+const MAX_UNPACKED_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+// Optional description a bundle carries about where it came from. Without
+// one the directory name, or the archive's file stem, stands in for the
+// device.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BundleManifest {
+    #[serde(default)]
+    pub device_id: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub app_name: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergedBundle {
+    pub source: PathBuf,
+    // Directory under `bundles/` holding this bundle's artifacts.
+    pub namespace: String,
+    pub device_id: String,
+    pub session_id: Option<String>,
+    pub files: usize,
+    pub console_entries: usize,
+    pub corrupt_lines: usize,
+    // Entries that carried an annotation from the bundle's annotation files.
+    #[serde(default)]
+    pub annotated_entries: usize,
+    // Lines of the bundle's tauri-plugin-log files, in the timeline with
+    // `fields.source == "plugin_log"`.
+    #[serde(default)]
+    pub plugin_log_entries: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SkippedBundle {
+    pub source: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergedFingerprint {
+    pub fingerprint: String,
+    pub count: u64,
+    pub devices: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergeReport {
+    pub out_dir: PathBuf,
+    pub created_at: i64,
+    pub bundles: Vec<MergedBundle>,
+    pub skipped: Vec<SkippedBundle>,
+    pub timeline_path: PathBuf,
+    pub timeline_entries: usize,
+    pub fingerprints_path: PathBuf,
+    pub fingerprints: usize,
+    // Timeline entries whose stack trace was rewritten through a source map.
+    #[serde(default)]
+    pub resolved_stack_traces: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeProgress {
+    // 1-based index of the bundle just processed.
+    pub bundle: usize,
+    pub total: usize,
+    pub source: PathBuf,
+}
+
+pub fn merge_bundles(paths: &[PathBuf], out_dir: &Path) -> Result<MergeReport, RepositoryError> {
+    merge_bundles_with_progress(paths, out_dir, None, &mut |_| {})
+}
+
+// Bundles that cannot be read are reported in `skipped`; only failures to
+// write into `out_dir` abort the merge. With a resolver, timeline stack
+// traces are rewritten through source maps; bundle copies stay as they are.
+pub fn merge_bundles_with_progress(
+    paths: &[PathBuf],
+    out_dir: &Path,
+    resolver: Option<&StackResolver>,
+    on_progress: &mut dyn FnMut(&MergeProgress),
+) -> Result<MergeReport, RepositoryError> {
+    fs::create_dir_all(out_dir.join("bundles"))?;
+
+    let mut bundles = Vec::new();
+    let mut skipped = Vec::new();
+    let mut namespaces = HashSet::new();
+    // (timestamp, bundle, line) orders the timeline deterministically.
+    let mut timeline: Vec<(i64, usize, usize, ConsoleLogEntry)> = Vec::new();
+    let mut fingerprints: HashMap<String, (u64, BTreeSet<String>)> = HashMap::new();
+
+    for (index, source) in paths.iter().enumerate() {
+        let staging = out_dir.join(UNPACK_DIR).join(index.to_string());
+        let loaded = open_bundle(source, &staging)
+            .and_then(|dir| Ok((read_bundle(&dir, &device_name(source))?, dir)));
+        match loaded {
+            Ok((bundle, dir)) => {
+                let namespace = unique_namespace(&bundle, &mut namespaces);
+                let target = out_dir.join("bundles").join(&namespace);
+                let files = copy_artifacts(&dir, &target)?;
+
+                for (line, mut entry) in bundle.entries.into_iter().enumerate() {
+                    if LogLevel::from_str_lenient(&entry.level) == Some(LogLevel::Error) {
+                        let slot = fingerprints.entry(fingerprint(&entry.message)).or_default();
+                        slot.0 += 1;
+                        slot.1.insert(bundle.device_id.clone());
+                    }
+                    let fields = entry.fields.get_or_insert_with(Default::default);
+                    fields.insert("device_id".to_string(), bundle.device_id.clone().into());
+                    fields.insert("bundle".to_string(), namespace.clone().into());
+                    timeline.push((entry.timestamp, bundles.len(), line, entry));
+                }
+
+                bundles.push(MergedBundle {
+                    source: source.clone(),
+                    namespace,
+                    device_id: bundle.device_id,
+                    session_id: bundle.manifest.session_id,
+                    files,
+                    console_entries: bundle.console_entries,
+                    corrupt_lines: bundle.corrupt_lines,
+                    annotated_entries: bundle.annotated_entries,
+                    plugin_log_entries: bundle.plugin_log_entries,
+                });
+            }
+            Err(reason) => {
+                tracing::warn!(source = %source.display(), reason = %reason, "Skipping debug bundle");
+                skipped.push(SkippedBundle {
+                    source: source.clone(),
+                    reason,
+                });
+            }
+        }
+
+        let _ = fs::remove_dir_all(&staging);
+
+        on_progress(&MergeProgress {
+            bundle: index + 1,
+            total: paths.len(),
+            source: source.clone(),
+        });
+    }
+
+    let _ = fs::remove_dir_all(out_dir.join(UNPACK_DIR));
+
+    timeline.sort_by_key(|(timestamp, bundle, line, _)| (*timestamp, *bundle, *line));
+    let timeline_path = out_dir.join(MERGED_TIMELINE_FILE);
+    let mut writer = BufWriter::new(fs::File::create(&timeline_path)?);
+    let mut resolved_stack_traces = 0;
+    for (_, _, _, entry) in &mut timeline {
+        if resolver.is_some_and(|resolver| resolver.resolve_entry(entry)) {
+            resolved_stack_traces += 1;
+        }
+        serde_json::to_writer(&mut writer, entry)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+
+    let mut fingerprints: Vec<MergedFingerprint> = fingerprints
+        .into_iter()
+        .map(|(fingerprint, (count, devices))| MergedFingerprint {
+            fingerprint,
+            count,
+            devices: devices.into_iter().collect(),
+        })
+        .collect();
+    fingerprints.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.fingerprint.cmp(&b.fingerprint))
+    });
+    let fingerprints_path = out_dir.join(MERGED_FINGERPRINTS_FILE);
+    fs::write(
+        &fingerprints_path,
+        serde_json::to_vec_pretty(&fingerprints)?,
+    )?;
+
+    let report = MergeReport {
+        out_dir: out_dir.to_path_buf(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as i64)
+            .unwrap_or_default(),
+        bundles,
+        skipped,
+        timeline_path,
+        timeline_entries: timeline.len(),
+        fingerprints_path,
+        fingerprints: fingerprints.len(),
+        resolved_stack_traces,
+    };
+    fs::write(
+        out_dir.join(MERGED_MANIFEST_FILE),
+        serde_json::to_vec_pretty(&report)?,
+    )?;
+
+    tracing::info!(
+        out_dir = %out_dir.display(),
+        merged = report.bundles.len(),
+        skipped = report.skipped.len(),
+        timeline_entries = report.timeline_entries,
+        "Debug bundles merged"
+    );
+
+    Ok(report)
+}
+
+struct LoadedBundle {
+    manifest: BundleManifest,
+    device_id: String,
+    entries: Vec<ConsoleLogEntry>,
+    console_entries: usize,
+    corrupt_lines: usize,
+    annotated_entries: usize,
+    plugin_log_entries: usize,
+}
+
+fn is_zip(source: &Path) -> bool {
+    source
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+fn device_name(source: &Path) -> String {
+    let name = if is_zip(source) {
+        source.file_stem()
+    } else {
+        source.file_name()
+    };
+    name.map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "device".to_string())
+}
+
+// The directory to read `source` from: itself, or for a `.zip` the root of
+// its contents unpacked into `staging`.
+fn open_bundle(source: &Path, staging: &Path) -> Result<PathBuf, String> {
+    if source.is_dir() {
+        return Ok(source.to_path_buf());
+    }
+    if !(source.is_file() && is_zip(source)) {
+        return Err("not a bundle directory or .zip archive".to_string());
+    }
+
+    unpack_zip(source, staging)?;
+    Ok(bundle_root(staging))
+}
+
+// Entries whose names would land outside `staging` are left out.
+fn unpack_zip(archive: &Path, staging: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive).map_err(|e| format!("unreadable archive: {}", e))?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("invalid zip archive: {}", e))?;
+    let _ = fs::remove_dir_all(staging);
+    fs::create_dir_all(staging).map_err(|e| format!("cannot unpack archive: {}", e))?;
+
+    let mut budget = MAX_UNPACKED_BYTES;
+    for index in 0..zip.len() {
+        let mut entry = zip
+            .by_index(index)
+            .map_err(|e| format!("invalid zip entry: {}", e))?;
+        let Some(relative) = entry.enclosed_name() else {
+            tracing::warn!(archive = %archive.display(), entry = entry.name(), "Skipping zip entry outside the bundle");
+            continue;
+        };
+        let target = staging.join(relative);
+        let unpacked = if entry.is_dir() {
+            fs::create_dir_all(&target)
+        } else {
+            target
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::File::create(&target))
+                .and_then(|mut out| io::copy(&mut (&mut entry).take(budget + 1), &mut out))
+                .map(|written| budget = budget.saturating_sub(written))
+        };
+        unpacked.map_err(|e| format!("corrupt zip entry {}: {}", entry.name(), e))?;
+        if budget == 0 {
+            return Err(format!(
+                "archive expands beyond {} bytes",
+                MAX_UNPACKED_BYTES
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Zipping a bundle directory usually wraps it in one top-level folder.
+fn bundle_root(staging: &Path) -> PathBuf {
+    let Ok(listing) = fs::read_dir(staging) else {
+        return staging.to_path_buf();
+    };
+    let entries: Vec<fs::DirEntry> = listing.flatten().collect();
+    match entries.as_slice() {
+        [only] if only.file_type().is_ok_and(|kind| kind.is_dir()) => only.path(),
+        _ => staging.to_path_buf(),
+    }
+}
+
+fn read_bundle(source: &Path, device_name: &str) -> Result<LoadedBundle, String> {
+    let manifest = match fs::read(source.join(BUNDLE_MANIFEST_FILE)) {
+        Ok(bytes) => serde_json::from_slice::<BundleManifest>(&bytes)
+            .map_err(|e| format!("invalid {}: {}", BUNDLE_MANIFEST_FILE, e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BundleManifest::default(),
+        Err(e) => return Err(format!("unreadable {}: {}", BUNDLE_MANIFEST_FILE, e)),
+    };
+
+    let device_id = manifest
+        .device_id
+        .clone()
+        .unwrap_or_else(|| device_name.to_string());
+
+    let annotations = read_bundle_annotations(source);
+    let mut entries = Vec::new();
+    let mut corrupt_lines = 0;
+    let mut annotated_entries = 0;
+    let mut log_files = 0;
+    let listing = fs::read_dir(source).map_err(|e| format!("unreadable directory: {}", e))?;
+    for entry in listing.flatten() {
+        let path = entry.path();
+        let is_console_log = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.starts_with("frontend_console_")
+                    && name.ends_with(".jsonl")
+                    && !is_repaired_copy(name)
+            });
+        let file_annotations = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| annotations.get(name));
+        if !is_console_log || !entry.file_type().is_ok_and(|kind| kind.is_file()) {
+            continue;
+        }
+
+        log_files += 1;
+        // A bundle copied with its repair index reads the repaired copies.
+        let Ok(reader) = open_console_log(&path) else {
+            corrupt_lines += 1;
+            continue;
+        };
+        for line in reader.split(b'\n') {
+            let Ok(line) = line else {
+                corrupt_lines += 1;
+                break;
+            };
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            match serde_json::from_slice::<ConsoleLogEntry>(&line) {
+                Ok(mut entry) => {
+                    let annotation = log_seq(&entry)
+                        .and_then(|seq| file_annotations.and_then(|by_seq| by_seq.get(&seq)));
+                    if let Some(annotation) = annotation {
+                        attach_annotation(&mut entry, annotation);
+                        annotated_entries += 1;
+                    }
+                    entries.push(entry)
+                }
+                Err(_) => corrupt_lines += 1,
+            }
+        }
+    }
+
+    let console_entries = entries.len();
+    let plugin_log_entries = read_bundle_plugin_logs(source, &mut entries, &mut corrupt_lines);
+
+    if log_files == 0 && plugin_log_entries == 0 && !source.join(BUNDLE_MANIFEST_FILE).exists() {
+        return Err("no manifest and no console logs".to_string());
+    }
+
+    Ok(LoadedBundle {
+        manifest,
+        device_id,
+        console_entries,
+        entries,
+        corrupt_lines,
+        annotated_entries,
+        plugin_log_entries,
+    })
+}
+
+// Every `*.log` in the bundle's `plugin_log/`, in name order. Lines the
+// parser does not know join the timeline as-is, at the time of the last
+// line before them that had one.
+fn read_bundle_plugin_logs(
+    source: &Path,
+    entries: &mut Vec<ConsoleLogEntry>,
+    corrupt_lines: &mut usize,
+) -> usize {
+    let Ok(listing) = fs::read_dir(source.join(BUNDLE_PLUGIN_LOG_DIR)) else {
+        return 0;
+    };
+    let mut paths: Vec<PathBuf> = listing
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "log"))
+        .collect();
+    paths.sort();
+
+    let mut read = 0;
+    for path in paths {
+        let Ok(file) = fs::File::open(&path) else {
+            *corrupt_lines += 1;
+            continue;
+        };
+        let mut last_timestamp = 0;
+        for line in BufReader::new(file).split(b'\n') {
+            let Ok(line) = line else {
+                *corrupt_lines += 1;
+                break;
+            };
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let parsed = parse_plugin_log_line(&String::from_utf8_lossy(&line));
+            let entry = to_console_entry(parsed, last_timestamp);
+            last_timestamp = entry.timestamp;
+            entries.push(entry);
+            read += 1;
+        }
+    }
+    read
+}
+
+// The latest annotation per console log file and seq, from every
+// `annotations_*.jsonl` in the bundle root.
+fn read_bundle_annotations(source: &Path) -> HashMap<String, HashMap<u64, LogAnnotation>> {
+    let mut annotations: HashMap<String, HashMap<u64, LogAnnotation>> = HashMap::new();
+    let Ok(listing) = fs::read_dir(source) else {
+        return annotations;
+    };
+    for entry in listing.flatten() {
+        let path = entry.path();
+        let is_annotation_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("annotations_") && name.ends_with(".jsonl"));
+        if !is_annotation_file {
+            continue;
+        }
+        for annotation in read_annotations(&path).unwrap_or_default() {
+            let by_seq = annotations.entry(annotation.log_file.clone()).or_default();
+            let newer = by_seq.get(&annotation.seq).map_or(true, |existing| {
+                existing.annotated_at <= annotation.annotated_at
+            });
+            if newer {
+                by_seq.insert(annotation.seq, annotation);
+            }
+        }
+    }
+    annotations
+}
+
+fn sanitize(part: &str) -> String {
+    let cleaned: String = part
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+    cleaned.trim_start_matches('.').to_string()
+}
+
+fn unique_namespace(bundle: &LoadedBundle, taken: &mut HashSet<String>) -> String {
+    let base = format!(
+        "{}__{}",
+        sanitize(&bundle.device_id),
+        sanitize(bundle.manifest.session_id.as_deref().unwrap_or("session"))
+    );
+    let mut namespace = base.clone();
+    let mut suffix = 2;
+    while !taken.insert(namespace.clone()) {
+        namespace = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    namespace
+}
+
+// Copies regular files from the bundle root and its first-level
+// subdirectories (DOM snapshots, screenshots). Symlinks are not followed.
+fn copy_artifacts(source: &Path, target: &Path) -> Result<usize, RepositoryError> {
+    let mut copied = 0;
+    let mut pending = vec![(source.to_path_buf(), target.to_path_buf(), 0)];
+
+    while let Some((from_dir, to_dir, depth)) = pending.pop() {
+        fs::create_dir_all(&to_dir)?;
+        let Ok(listing) = fs::read_dir(&from_dir) else {
+            continue;
+        };
+        for entry in listing.flatten() {
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            let to = to_dir.join(entry.file_name());
+            if kind.is_file() {
+                if fs::copy(entry.path(), &to).is_ok() {
+                    copied += 1;
+                }
+            } else if kind.is_dir() && depth == 0 {
+                pending.push((entry.path(), to, depth + 1));
+            }
+        }
+    }
+
+    Ok(copied)
+}
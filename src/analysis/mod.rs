@@ -0,0 +1,6 @@
+// Offline analysis over captured artifacts. Nothing here needs a running
+// app, so a companion CLI can link the crate and call it directly.
+pub mod merge;
+pub mod schema_manifest;
+pub mod session_compare;
+pub mod sourcemap;
@@ -0,0 +1,393 @@
+use crate::analysis::merge::{
+    BundleManifest, MergeReport, MergedFingerprint, BUNDLE_MANIFEST_FILE, MERGED_FINGERPRINTS_FILE,
+    MERGED_MANIFEST_FILE, MERGED_TIMELINE_FILE,
+};
+use crate::config::EffectiveConfig;
+use crate::domain::{
+    BackendLogEvent, ConsoleLogEntry, DebugSnapshot, DomDelta, DomSnapshotMetadata,
+    FinalBeaconRecord, LogAnnotation, LogRepairReport, NetworkLogEntry, SnapshotListEntry,
+};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Bump whenever an artifact below or any schema it references changes. The
+// snapshot in `schemas/artifact-manifest.json` is checked against it by
+// `tests/schema_manifest.rs`.
+pub const ARTIFACT_MANIFEST_VERSION: u32 = 13;
+
+// Where `$ref`s in the embedded schemas point, relative to the manifest root.
+const SCHEMAS_PATH: &str = "#/schemas/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactFormat {
+    // One JSON value per line, each matching `schema`.
+    Jsonl,
+    Json,
+    Html,
+    Png,
+    Svg,
+    Markdown,
+    Text,
+}
+
+// JSON placed in a file of another format, such as the metadata comment at
+// the top of a DOM snapshot: the text between `prefix` and `suffix`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddedMetadata {
+    pub prefix: String,
+    pub suffix: String,
+    pub schema: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactDescription {
+    pub name: String,
+    // Relative to the log directory; `{placeholders}` stand for per-file
+    // values.
+    pub directory: String,
+    pub filename_pattern: String,
+    pub format: ArtifactFormat,
+    // For `jsonl`, the schema of one line.
+    pub schema: Option<Value>,
+    pub embedded_metadata: Option<EmbeddedMetadata>,
+    pub description: String,
+}
+
+// Payloads that are not files of their own: command results and events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordDescription {
+    pub name: String,
+    pub source: String,
+    pub schema: Value,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactSchemaManifest {
+    pub manifest_version: u32,
+    // Draft of the embedded JSON Schemas.
+    pub json_schema: String,
+    pub artifacts: Vec<ArtifactDescription>,
+    pub records: Vec<RecordDescription>,
+    pub schemas: BTreeMap<String, Value>,
+}
+
+pub fn artifact_schema_manifest() -> ArtifactSchemaManifest {
+    let mut settings = SchemaSettings::draft07();
+    settings.definitions_path = SCHEMAS_PATH.to_string();
+    let mut gen = settings.into_generator();
+
+    let console_log = schema_of::<ConsoleLogEntry>(&mut gen);
+    let artifacts = vec![
+        artifact(
+            "console_log",
+            "",
+            "frontend_console_{app}_{pid}.jsonl",
+            ArtifactFormat::Jsonl,
+            Some(console_log.clone()),
+            "Frontend console entries, appended in batches. Each carries its position in the session as `fields.log_seq`.",
+        ),
+        artifact(
+            "rotated_console_log",
+            "",
+            "frontend_console_{app}_{pid}.{n}.jsonl",
+            ArtifactFormat::Jsonl,
+            Some(console_log.clone()),
+            "Earlier entries of a console log, moved aside before a batch that would take the live file past `max_log_size_bytes`. Higher `n` is newer. Queries read the live file only.",
+        ),
+        artifact(
+            "log_annotations",
+            "",
+            "annotations_{session}.jsonl",
+            ArtifactFormat::Jsonl,
+            Some(schema_of::<LogAnnotation>(&mut gen)),
+            "Notes on console entries by `log_seq`, appended as they are made; the latest line per `log_file` and `seq` wins.",
+        ),
+        artifact(
+            "repaired_console_log",
+            "",
+            "frontend_console_{app}_{pid}.repaired.jsonl",
+            ArtifactFormat::Jsonl,
+            Some(console_log.clone()),
+            "The parseable entries of a torn console log. Read in place of the original up to the length the repair index records.",
+        ),
+        artifact(
+            "log_repair_index",
+            "",
+            "repaired_logs.json",
+            ArtifactFormat::Json,
+            Some(schema_of::<BTreeMap<String, LogRepairReport>>(&mut gen)),
+            "Repair reports keyed by console log file name.",
+        ),
+        artifact(
+            "network_log",
+            "",
+            "network_{app}_{pid}.jsonl",
+            ArtifactFormat::Jsonl,
+            Some(schema_of::<NetworkLogEntry>(&mut gen)),
+            "Network requests reported by the frontend.",
+        ),
+        artifact(
+            "backend_log",
+            "",
+            "rust_debug.log",
+            ArtifactFormat::Jsonl,
+            Some(json!({ "$ref": format!("{}BackendLogEntry", SCHEMAS_PATH) })),
+            "The app's tracing events; plain text lines instead when `log_format` is `text`. Rotated to `rust_debug.log.{n}` past `max_log_size_bytes`, higher `n` newer.",
+        ),
+        artifact(
+            "plugin_log",
+            "..",
+            "debug.log",
+            ArtifactFormat::Text,
+            None,
+            "tauri-plugin-log's file target next to the log directory, named by `plugin_log_file_name`, with rotated copies as `<stem>_<timestamp>.log`. Written by the host; bundles carry copies under `plugin_log/`.",
+        ),
+        artifact(
+            "snapshot",
+            "",
+            "snapshot_{timestamp}.json",
+            ArtifactFormat::Json,
+            Some(schema_of::<DebugSnapshot>(&mut gen)),
+            "Full debug snapshot. Files with `schema_version` 0 are wrapped legacy payloads.",
+        ),
+        artifact(
+            "final_beacon",
+            "",
+            "final_beacon_{received_at}.json",
+            ArtifactFormat::Json,
+            Some(schema_of::<FinalBeaconRecord>(&mut gen)),
+            "State a page reported while unloading. Its entries are in the console log, with `final_beacon` set in `fields`.",
+        ),
+        ArtifactDescription {
+            embedded_metadata: Some(EmbeddedMetadata {
+                prefix: crate::adapters::filesystem::DOM_METADATA_PREFIX.to_string(),
+                suffix: crate::adapters::filesystem::DOM_METADATA_SUFFIX.to_string(),
+                schema: schema_of::<DomSnapshotMetadata>(&mut gen),
+            }),
+            ..artifact(
+                "dom_snapshot",
+                "dom_snapshots",
+                "dom_{timestamp}.html",
+                ArtifactFormat::Html,
+                None,
+                "Captured document, preceded by a metadata comment. `<` and `>` inside the metadata are escaped as `\\u003c` and `\\u003e`. Searches also read `.html.gz` copies.",
+            )
+        },
+        artifact(
+            "dom_delta",
+            "dom_snapshots",
+            "dom_delta_{timestamp}.json",
+            ArtifactFormat::Json,
+            Some(schema_of::<DomDelta>(&mut gen)),
+            "Difference of a DOM capture from the session's baseline snapshot.",
+        ),
+        artifact(
+            "screenshot",
+            "screenshots",
+            "{timestamp}_{source_file_name}",
+            ArtifactFormat::Png,
+            None,
+            "Screenshot copied into the log directory.",
+        ),
+        artifact(
+            "screenshot_diff",
+            "screenshots/diffs",
+            "diff_{stem_a}_{stem_b}.png",
+            ArtifactFormat::Png,
+            None,
+            "Changed pixels of two screenshots highlighted.",
+        ),
+        artifact(
+            "recording_frame",
+            "recordings/{started_at}",
+            "frame_{index:05}.png",
+            ArtifactFormat::Png,
+            None,
+            "One frame of a window recording.",
+        ),
+        artifact(
+            "recording",
+            "recordings/{started_at}",
+            "recording.png",
+            ArtifactFormat::Png,
+            None,
+            "Animated PNG assembled from the frames when the recording stops.",
+        ),
+        artifact(
+            "effective_config",
+            "config_history",
+            "effective_config_{timestamp}.json",
+            ArtifactFormat::Json,
+            Some(schema_of::<EffectiveConfig>(&mut gen)),
+            "Settings in force after each runtime change.",
+        ),
+        artifact(
+            "log_export",
+            "exports",
+            "{output_name}.jsonl",
+            ArtifactFormat::Jsonl,
+            Some(console_log.clone()),
+            "Filtered console log export; `shutdown_{timestamp}.jsonl` when written on exit.",
+        ),
+        artifact(
+            "log_export_html",
+            "exports",
+            "{output_name}.html",
+            ArtifactFormat::Html,
+            None,
+            "Self-contained HTML rendering of a console log export.",
+        ),
+        artifact(
+            "session_comparison",
+            "exports",
+            "sessions_{session_a}_vs_{session_b}.md",
+            ArtifactFormat::Markdown,
+            None,
+            "Report written by `compare_debug_sessions`.",
+        ),
+        artifact(
+            "flamegraph_folded",
+            "",
+            "tracing.folded",
+            ArtifactFormat::Text,
+            None,
+            "Folded span stacks (`flamegraph` feature).",
+        ),
+        artifact(
+            "flamegraph",
+            "",
+            "tracing_flamegraph_{timestamp}.svg",
+            ArtifactFormat::Svg,
+            None,
+            "Flamegraph rendered by `export_flamegraph`.",
+        ),
+        artifact(
+            "bundle_manifest",
+            "{bundle}",
+            BUNDLE_MANIFEST_FILE,
+            ArtifactFormat::Json,
+            Some(schema_of::<BundleManifest>(&mut gen)),
+            "Optional description of a collected bundle, read by `merge_debug_bundles`.",
+        ),
+        artifact(
+            "merged_manifest",
+            "merged/merged_{timestamp}",
+            MERGED_MANIFEST_FILE,
+            ArtifactFormat::Json,
+            Some(schema_of::<MergeReport>(&mut gen)),
+            "Report of a bundle merge. The output directory may be chosen by the caller.",
+        ),
+        artifact(
+            "merged_timeline",
+            "merged/merged_{timestamp}",
+            MERGED_TIMELINE_FILE,
+            ArtifactFormat::Jsonl,
+            Some(console_log),
+            "Console entries of all merged bundles in timestamp order.",
+        ),
+        artifact(
+            "merged_fingerprints",
+            "merged/merged_{timestamp}",
+            MERGED_FINGERPRINTS_FILE,
+            ArtifactFormat::Json,
+            Some(schema_of::<Vec<MergedFingerprint>>(&mut gen)),
+            "Error fingerprints across the merged bundles, most frequent first.",
+        ),
+    ];
+
+    let records = vec![
+        RecordDescription {
+            name: "snapshot_index_entry".to_string(),
+            source: "list_snapshots_paged".to_string(),
+            schema: schema_of::<SnapshotListEntry>(&mut gen),
+            description: "One snapshot in a page of the snapshot index.".to_string(),
+        },
+        RecordDescription {
+            name: "backend_log_event".to_string(),
+            source: crate::adapters::log_forwarding::BACKEND_LOG_BATCH_EVENT.to_string(),
+            schema: schema_of::<BackendLogEvent>(&mut gen),
+            description: "Backend tracing event forwarded to the frontend in batches.".to_string(),
+        },
+    ];
+
+    let mut schemas: BTreeMap<String, Value> = gen
+        .take_definitions()
+        .into_iter()
+        .map(|(name, schema)| (name, to_value(&schema)))
+        .collect();
+    schemas.insert("BackendLogEntry".to_string(), backend_log_entry_schema());
+
+    ArtifactSchemaManifest {
+        manifest_version: ARTIFACT_MANIFEST_VERSION,
+        json_schema: "http://json-schema.org/draft-07/schema#".to_string(),
+        artifacts,
+        records,
+        schemas,
+    }
+}
+
+pub fn write_schema_manifest(path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut json = serde_json::to_vec_pretty(&artifact_schema_manifest())?;
+    json.push(b'\n');
+    std::fs::write(path, json)
+}
+
+fn artifact(
+    name: &str,
+    directory: &str,
+    filename_pattern: &str,
+    format: ArtifactFormat,
+    schema: Option<Value>,
+    description: &str,
+) -> ArtifactDescription {
+    ArtifactDescription {
+        name: name.to_string(),
+        directory: directory.to_string(),
+        filename_pattern: filename_pattern.to_string(),
+        format,
+        schema,
+        embedded_metadata: None,
+        description: description.to_string(),
+    }
+}
+
+fn schema_of<T: JsonSchema>(gen: &mut SchemaGenerator) -> Value {
+    to_value(&gen.subschema_for::<T>())
+}
+
+fn to_value(schema: &schemars::schema::Schema) -> Value {
+    serde_json::to_value(schema).unwrap_or_default()
+}
+
+// Lines of `rust_debug.log` come from tracing-subscriber's JSON formatter
+// rather than a type of this crate.
+fn backend_log_entry_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["timestamp", "level", "fields", "target"],
+        "properties": {
+            "timestamp": { "type": "string", "description": "RFC 3339" },
+            "level": { "enum": ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"] },
+            "fields": {
+                "type": "object",
+                "properties": { "message": { "type": "string" } },
+                "additionalProperties": true
+            },
+            "target": { "type": "string" },
+            "filename": { "type": "string" },
+            "line_number": { "type": "integer" },
+            "threadId": { "type": "string" },
+            "span": { "type": "object" },
+            "spans": { "type": "array", "items": { "type": "object" } }
+        }
+    })
+}
@@ -0,0 +1,632 @@
+use crate::config::EffectiveConfig;
+use crate::domain::error_alarm::fingerprint;
+use crate::domain::text_summary::format_utc;
+use crate::domain::{DebugSnapshot, LogLevel, OperationMetrics, RepositoryMetricsReport};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+
+// A section is either compared or says why it could not be; a missing data
+// source never fails the whole comparison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SectionComparison<T> {
+    Available(T),
+    Unavailable { reason: String },
+}
+
+impl<T> SectionComparison<T> {
+    fn unavailable(reason: impl Into<String>) -> Self {
+        Self::Unavailable {
+            reason: reason.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    // Dotted path into the compared value, e.g. `active_profile.name`.
+    pub field: String,
+    pub a: Option<serde_json::Value>,
+    pub b: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDelta {
+    pub changed: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FingerprintCount {
+    pub fingerprint: String,
+    pub count_a: u64,
+    pub count_b: u64,
+    // First message seen with this fingerprint.
+    pub sample: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorFingerprintDelta {
+    // Only in session B.
+    pub new: Vec<FingerprintCount>,
+    // Only in session A.
+    pub resolved: Vec<FingerprintCount>,
+    pub persisting: Vec<FingerprintCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelVolume {
+    pub level: String,
+    pub a: u64,
+    pub b: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogVolume {
+    pub levels: Vec<LevelVolume>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyChange {
+    pub operation: String,
+    pub avg_latency_us_a: u64,
+    pub avg_latency_us_b: u64,
+    // Relative to A; `None` when A averaged zero.
+    pub change_percent: Option<f64>,
+}
+
+// Average write latencies from the repository metrics in each session's
+// latest snapshot; operations not run in both sessions are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyDelta {
+    pub operations: Vec<LatencyChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSide {
+    pub session_id: String,
+    pub snapshots: usize,
+    pub first_snapshot_at: Option<i64>,
+    pub last_snapshot_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionComparison {
+    pub session_a: SessionSide,
+    pub session_b: SessionSide,
+    pub build_info: SectionComparison<FieldDelta>,
+    pub effective_config: SectionComparison<FieldDelta>,
+    pub error_fingerprints: SectionComparison<ErrorFingerprintDelta>,
+    pub log_volume: SectionComparison<LogVolume>,
+    pub latency: SectionComparison<LatencyDelta>,
+    // Set when the report was also written as Markdown.
+    #[serde(default)]
+    pub markdown_path: Option<std::path::PathBuf>,
+}
+
+// Everything known about one session: its snapshots, oldest first.
+pub struct SessionData {
+    pub session_id: String,
+    pub snapshots: Vec<DebugSnapshot>,
+}
+
+impl SessionData {
+    fn side(&self) -> SessionSide {
+        SessionSide {
+            session_id: self.session_id.clone(),
+            snapshots: self.snapshots.len(),
+            first_snapshot_at: self.snapshots.first().map(|s| s.timestamp),
+            last_snapshot_at: self.snapshots.last().map(|s| s.timestamp),
+        }
+    }
+
+    // Snapshots repeat the logs buffered before them, so entries are
+    // counted once per (timestamp, level, message).
+    fn console_entries(&self) -> Vec<&crate::domain::ConsoleLogEntry> {
+        let mut seen = HashSet::new();
+        self.snapshots
+            .iter()
+            .flat_map(|snapshot| &snapshot.console_logs)
+            .filter(|entry| {
+                seen.insert((
+                    entry.timestamp,
+                    entry.level.as_str(),
+                    entry.message.as_str(),
+                ))
+            })
+            .collect()
+    }
+
+    fn latest<T>(&self, pick: impl Fn(&DebugSnapshot) -> Option<T>) -> Option<T> {
+        self.snapshots.iter().rev().find_map(pick)
+    }
+}
+
+// `load_config` reads an effective config file referenced by a snapshot.
+pub fn compare_sessions(
+    a: &SessionData,
+    b: &SessionData,
+    load_config: &dyn Fn(&std::path::Path) -> Option<EffectiveConfig>,
+) -> SessionComparison {
+    SessionComparison {
+        session_a: a.side(),
+        session_b: b.side(),
+        build_info: compare_build_info(a, b),
+        effective_config: compare_effective_config(a, b, load_config),
+        error_fingerprints: compare_errors(a, b),
+        log_volume: compare_log_volume(a, b),
+        latency: compare_latency(a, b),
+        markdown_path: None,
+    }
+}
+
+fn missing_in(a: bool, b: bool, what: &str) -> Option<String> {
+    match (a, b) {
+        (true, true) => None,
+        (false, true) => Some(format!("session A has no {}", what)),
+        (true, false) => Some(format!("session B has no {}", what)),
+        (false, false) => Some(format!("neither session has {}", what)),
+    }
+}
+
+fn compare_build_info(a: &SessionData, b: &SessionData) -> SectionComparison<FieldDelta> {
+    let build_a = a.latest(|s| s.build_info.clone());
+    let build_b = b.latest(|s| s.build_info.clone());
+    let (Some(build_a), Some(build_b)) = (&build_a, &build_b) else {
+        return SectionComparison::unavailable(
+            missing_in(build_a.is_some(), build_b.is_some(), "build info").unwrap_or_default(),
+        );
+    };
+    SectionComparison::Available(field_delta(
+        &serde_json::to_value(build_a).unwrap_or_default(),
+        &serde_json::to_value(build_b).unwrap_or_default(),
+    ))
+}
+
+fn compare_effective_config(
+    a: &SessionData,
+    b: &SessionData,
+    load_config: &dyn Fn(&std::path::Path) -> Option<EffectiveConfig>,
+) -> SectionComparison<FieldDelta> {
+    let load = |session: &SessionData| {
+        session.latest(|s| s.effective_config_path.as_deref().and_then(load_config))
+    };
+    let (config_a, config_b) = (load(a), load(b));
+    let (Some(config_a), Some(config_b)) = (&config_a, &config_b) else {
+        return SectionComparison::unavailable(
+            missing_in(
+                config_a.is_some(),
+                config_b.is_some(),
+                "readable effective config",
+            )
+            .unwrap_or_default(),
+        );
+    };
+
+    let mut delta = field_delta(
+        &serde_json::to_value(config_a).unwrap_or_default(),
+        &serde_json::to_value(config_b).unwrap_or_default(),
+    );
+    // Always differ between files and say nothing about behaviour.
+    delta
+        .changed
+        .retain(|change| change.field != "written_at" && change.field != "reason");
+    SectionComparison::Available(delta)
+}
+
+fn compare_errors(a: &SessionData, b: &SessionData) -> SectionComparison<ErrorFingerprintDelta> {
+    if let Some(reason) = missing_in(
+        !a.snapshots.is_empty(),
+        !b.snapshots.is_empty(),
+        "snapshots with console logs",
+    ) {
+        return SectionComparison::unavailable(reason);
+    }
+
+    // fingerprint -> (count in A, count in B, sample)
+    let mut counts: BTreeMap<String, (u64, u64, String)> = BTreeMap::new();
+    for (session, is_a) in [(a, true), (b, false)] {
+        for entry in session.console_entries() {
+            if LogLevel::from_str_lenient(&entry.level) != Some(LogLevel::Error) {
+                continue;
+            }
+            let slot = counts
+                .entry(fingerprint(&entry.message))
+                .or_insert_with(|| {
+                    (
+                        0,
+                        0,
+                        entry.message.lines().next().unwrap_or_default().to_string(),
+                    )
+                });
+            if is_a {
+                slot.0 += 1;
+            } else {
+                slot.1 += 1;
+            }
+        }
+    }
+
+    let mut delta = ErrorFingerprintDelta {
+        new: Vec::new(),
+        resolved: Vec::new(),
+        persisting: Vec::new(),
+    };
+    for (fingerprint, (count_a, count_b, sample)) in counts {
+        let count = FingerprintCount {
+            fingerprint,
+            count_a,
+            count_b,
+            sample,
+        };
+        match (count_a, count_b) {
+            (0, _) => delta.new.push(count),
+            (_, 0) => delta.resolved.push(count),
+            _ => delta.persisting.push(count),
+        }
+    }
+    for list in [&mut delta.new, &mut delta.resolved, &mut delta.persisting] {
+        list.sort_by_key(|count| std::cmp::Reverse(count.count_a + count.count_b));
+    }
+    SectionComparison::Available(delta)
+}
+
+fn compare_log_volume(a: &SessionData, b: &SessionData) -> SectionComparison<LogVolume> {
+    if let Some(reason) = missing_in(
+        !a.snapshots.is_empty(),
+        !b.snapshots.is_empty(),
+        "snapshots with console logs",
+    ) {
+        return SectionComparison::unavailable(reason);
+    }
+
+    let mut volume: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+    for (session, is_a) in [(a, true), (b, false)] {
+        for entry in session.console_entries() {
+            // Aliases such as `WARNING` count with their level.
+            let level = LogLevel::from_str_lenient(&entry.level).map_or_else(
+                || entry.level.to_ascii_lowercase(),
+                |level| level.to_string(),
+            );
+            let slot = volume.entry(level).or_default();
+            if is_a {
+                slot.0 += 1;
+            } else {
+                slot.1 += 1;
+            }
+        }
+    }
+    SectionComparison::Available(LogVolume {
+        levels: volume
+            .into_iter()
+            .map(|(level, (a, b))| LevelVolume { level, a, b })
+            .collect(),
+    })
+}
+
+fn compare_latency(a: &SessionData, b: &SessionData) -> SectionComparison<LatencyDelta> {
+    let metrics = |session: &SessionData| {
+        session.latest(|s| {
+            s.system_info
+                .as_ref()
+                .and_then(|info| info.repository_metrics.clone())
+        })
+    };
+    let (metrics_a, metrics_b) = (metrics(a), metrics(b));
+    let (Some(metrics_a), Some(metrics_b)) = (&metrics_a, &metrics_b) else {
+        return SectionComparison::unavailable(
+            missing_in(
+                metrics_a.is_some(),
+                metrics_b.is_some(),
+                "repository metrics",
+            )
+            .unwrap_or_default(),
+        );
+    };
+
+    let operations = |metrics: &RepositoryMetricsReport| -> [(&'static str, OperationMetrics); 3] {
+        [
+            ("save_snapshot", metrics.save_snapshot.clone()),
+            ("save_dom", metrics.save_dom.clone()),
+            ("save_console_logs", metrics.save_console_logs.clone()),
+        ]
+    };
+    SectionComparison::Available(LatencyDelta {
+        operations: operations(metrics_a)
+            .into_iter()
+            .zip(operations(metrics_b))
+            // An operation never called in one session has nothing to compare.
+            .filter(|((_, op_a), (_, op_b))| op_a.calls > 0 && op_b.calls > 0)
+            .map(|((operation, op_a), (_, op_b))| LatencyChange {
+                operation: operation.to_string(),
+                avg_latency_us_a: op_a.avg_latency_us,
+                avg_latency_us_b: op_b.avg_latency_us,
+                change_percent: (op_a.avg_latency_us > 0).then(|| {
+                    (op_b.avg_latency_us as f64 - op_a.avg_latency_us as f64) * 100.0
+                        / op_a.avg_latency_us as f64
+                }),
+            })
+            .collect(),
+    })
+}
+
+// Leaf-by-leaf difference of two JSON values; arrays compare as a whole.
+fn field_delta(a: &serde_json::Value, b: &serde_json::Value) -> FieldDelta {
+    let (mut leaves_a, mut leaves_b) = (BTreeMap::new(), BTreeMap::new());
+    flatten("", a, &mut leaves_a);
+    flatten("", b, &mut leaves_b);
+
+    let fields: std::collections::BTreeSet<&String> =
+        leaves_a.keys().chain(leaves_b.keys()).collect();
+    FieldDelta {
+        changed: fields
+            .into_iter()
+            .filter(|field| leaves_a.get(*field) != leaves_b.get(*field))
+            .map(|field| FieldChange {
+                field: field.clone(),
+                a: leaves_a.get(field).cloned(),
+                b: leaves_b.get(field).cloned(),
+            })
+            .collect(),
+    }
+}
+
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut BTreeMap<String, serde_json::Value>) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+// Markdown for pasting into an issue; sections mirror the JSON report.
+pub fn render_markdown(report: &SessionComparison) -> String {
+    let mut out = String::new();
+    let side = |side: &SessionSide| {
+        format!(
+            "`{}` ({} snapshots{})",
+            side.session_id,
+            side.snapshots,
+            side.last_snapshot_at
+                .map(|at| format!(", last {}", format_utc(at)))
+                .unwrap_or_default()
+        )
+    };
+    let _ = writeln!(out, "# Session comparison\n");
+    let _ = writeln!(out, "- A: {}", side(&report.session_a));
+    let _ = writeln!(out, "- B: {}", side(&report.session_b));
+
+    render_field_delta(&mut out, "Build info", &report.build_info);
+    render_field_delta(&mut out, "Effective config", &report.effective_config);
+
+    let _ = writeln!(out, "\n## Error fingerprints\n");
+    match &report.error_fingerprints {
+        SectionComparison::Available(delta) => {
+            for (title, list) in [
+                ("New", &delta.new),
+                ("Resolved", &delta.resolved),
+                ("Persisting", &delta.persisting),
+            ] {
+                let _ = writeln!(out, "### {} ({})\n", title, list.len());
+                for count in list {
+                    let _ = writeln!(
+                        out,
+                        "- {} (A: {}, B: {})",
+                        escape_markdown(&count.sample),
+                        count.count_a,
+                        count.count_b
+                    );
+                }
+                if !list.is_empty() {
+                    out.push('\n');
+                }
+            }
+        }
+        SectionComparison::Unavailable { reason } => {
+            let _ = writeln!(out, "_Unavailable: {}_", reason);
+        }
+    }
+
+    let _ = writeln!(out, "\n## Log volume\n");
+    match &report.log_volume {
+        SectionComparison::Available(volume) => {
+            let _ = writeln!(out, "| Level | A | B |\n| --- | ---: | ---: |");
+            for level in &volume.levels {
+                let _ = writeln!(out, "| {} | {} | {} |", level.level, level.a, level.b);
+            }
+        }
+        SectionComparison::Unavailable { reason } => {
+            let _ = writeln!(out, "_Unavailable: {}_", reason);
+        }
+    }
+
+    let _ = writeln!(out, "\n## Latency\n");
+    match &report.latency {
+        SectionComparison::Available(latency) if latency.operations.is_empty() => {
+            let _ = writeln!(out, "No operation ran in both sessions.");
+        }
+        SectionComparison::Available(latency) => {
+            let _ = writeln!(
+                out,
+                "| Operation | A avg (µs) | B avg (µs) | Change |\n| --- | ---: | ---: | ---: |"
+            );
+            for change in &latency.operations {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} |",
+                    change.operation,
+                    change.avg_latency_us_a,
+                    change.avg_latency_us_b,
+                    change
+                        .change_percent
+                        .map(|percent| format!("{:+.1}%", percent))
+                        .unwrap_or_else(|| "n/a".to_string())
+                );
+            }
+        }
+        SectionComparison::Unavailable { reason } => {
+            let _ = writeln!(out, "_Unavailable: {}_", reason);
+        }
+    }
+
+    out
+}
+
+fn render_field_delta(out: &mut String, title: &str, section: &SectionComparison<FieldDelta>) {
+    let _ = writeln!(out, "\n## {}\n", title);
+    match section {
+        SectionComparison::Available(delta) if delta.changed.is_empty() => {
+            let _ = writeln!(out, "No changes.");
+        }
+        SectionComparison::Available(delta) => {
+            let _ = writeln!(out, "| Field | A | B |\n| --- | --- | --- |");
+            let show = |value: &Option<serde_json::Value>| {
+                value.as_ref().map_or_else(
+                    || "—".to_string(),
+                    |value| escape_markdown(&value.to_string()),
+                )
+            };
+            for change in &delta.changed {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | {} | {} |",
+                    change.field,
+                    show(&change.a),
+                    show(&change.b)
+                );
+            }
+        }
+        SectionComparison::Unavailable { reason } => {
+            let _ = writeln!(out, "_Unavailable: {}_", reason);
+        }
+    }
+}
+
+// Keeps table cells intact and stops messages from turning into markup.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' | '\r' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// Parses an effective config file, for `compare_sessions`.
+pub fn read_effective_config(path: &std::path::Path) -> Option<EffectiveConfig> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+// One row of `list_debug_sessions`. `start_config` is the earliest config
+// history entry the session wrote, i.e. the capture rules in force when it
+// began; older files without a session id fall back to the config linked
+// from the session's first snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub snapshots: usize,
+    pub first_snapshot_at: Option<i64>,
+    pub last_snapshot_at: Option<i64>,
+    pub start_config_path: Option<std::path::PathBuf>,
+    pub start_config: Option<EffectiveConfig>,
+}
+
+impl SessionSummary {
+    fn started_at(&self) -> Option<i64> {
+        let config_at = self.start_config.as_ref().map(|config| config.written_at);
+        match (config_at, self.first_snapshot_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+// Groups snapshots and config history by session, newest session first.
+pub fn summarize_sessions(
+    snapshots: &[DebugSnapshot],
+    history: &[(std::path::PathBuf, EffectiveConfig)],
+    read_config: &dyn Fn(&std::path::Path) -> Option<EffectiveConfig>,
+) -> Vec<SessionSummary> {
+    let mut sessions: BTreeMap<String, SessionSummary> = BTreeMap::new();
+    let summary = |session_id: &str| SessionSummary {
+        session_id: session_id.to_string(),
+        snapshots: 0,
+        first_snapshot_at: None,
+        last_snapshot_at: None,
+        start_config_path: None,
+        start_config: None,
+    };
+
+    for snapshot in snapshots {
+        let Some(session_id) = snapshot.session_id.as_deref() else {
+            continue;
+        };
+        let entry = sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| summary(session_id));
+        entry.snapshots += 1;
+        entry.first_snapshot_at = Some(
+            entry
+                .first_snapshot_at
+                .map_or(snapshot.timestamp, |first| first.min(snapshot.timestamp)),
+        );
+        entry.last_snapshot_at = Some(
+            entry
+                .last_snapshot_at
+                .map_or(snapshot.timestamp, |last| last.max(snapshot.timestamp)),
+        );
+    }
+
+    for (path, config) in history {
+        let Some(session_id) = config.session_id.as_deref() else {
+            continue;
+        };
+        let entry = sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| summary(session_id));
+        let earlier = !matches!(
+            &entry.start_config,
+            Some(start) if start.written_at <= config.written_at
+        );
+        if earlier {
+            entry.start_config_path = Some(path.clone());
+            entry.start_config = Some(config.clone());
+        }
+    }
+
+    for entry in sessions.values_mut() {
+        if entry.start_config.is_some() {
+            continue;
+        }
+        let linked = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.session_id.as_deref() == Some(entry.session_id.as_str()))
+            .min_by_key(|snapshot| snapshot.timestamp)
+            .and_then(|snapshot| snapshot.effective_config_path.clone());
+        if let Some(path) = linked {
+            entry.start_config = read_config(&path);
+            entry.start_config_path = entry.start_config.as_ref().map(|_| path);
+        }
+    }
+
+    let mut sessions: Vec<SessionSummary> = sessions.into_values().collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.started_at()));
+    sessions
+}
@@ -0,0 +1,210 @@
+use crate::domain::ConsoleLogEntry;
+use regex::Regex;
+use sourcemap::DecodedMap;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Field added to a resolved entry listing the map files that were used,
+// relative to the sourcemap directory.
+pub const SOURCEMAPS_FIELD: &str = "sourcemaps";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginalPosition {
+    pub source: String,
+    // 1-based, like the stack frames they replace.
+    pub line: u32,
+    pub column: u32,
+    pub name: Option<String>,
+}
+
+// A decoded Source Map v3. Index maps (`sections`) are flattened when
+// parsed, and `sourceRoot` is already applied to the sources.
+#[derive(Debug)]
+pub struct SourceMap {
+    map: sourcemap::SourceMap,
+}
+
+impl SourceMap {
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let map = match sourcemap::decode_slice(bytes).map_err(|e| e.to_string())? {
+            DecodedMap::Regular(map) => map,
+            DecodedMap::Index(index) => index.flatten().map_err(|e| e.to_string())?,
+            DecodedMap::Hermes(hermes) => (*hermes).clone(),
+        };
+        Ok(Self { map })
+    }
+
+    // `line` and `column` are 1-based. Uses the closest mapping at or before
+    // the column on that line.
+    pub fn lookup(&self, line: u32, column: u32) -> Option<OriginalPosition> {
+        let line = line.checked_sub(1)?;
+        let token = self.map.lookup_token(line, column.saturating_sub(1))?;
+        // The closest mapping may sit on an earlier line, for other code.
+        if token.get_dst_line() != line {
+            return None;
+        }
+
+        Some(OriginalPosition {
+            source: token.get_source()?.to_string(),
+            line: token.get_src_line() + 1,
+            column: token.get_src_col() + 1,
+            name: token.get_name().map(str::to_string),
+        })
+    }
+}
+
+// `url:line:col` at the end of a frame; greedy so scheme and port colons stay
+// in the URL.
+fn location_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"([^\s()@]+):(\d+):(\d+)").expect("valid pattern"))
+}
+
+// `at name (` (V8) or `name@` (SpiderMonkey, JavaScriptCore).
+fn function_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(\s*at (?:async )?)([^\s(]+)( \()|^(\s*)([^@\s]*)(@)").expect("valid pattern")
+    })
+}
+
+// Rewrites stack frames through the `.map` files in one directory. Maps are
+// read on first use and kept; a missing or unparsable map is remembered as
+// such and its frames are left alone.
+pub struct StackResolver {
+    dir: PathBuf,
+    maps: Mutex<HashMap<PathBuf, Option<Arc<SourceMap>>>>,
+}
+
+impl StackResolver {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            maps: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Rewrites `entry.stack_trace` in place and records the maps used under
+    // `fields.sourcemaps`. Returns whether any frame was resolved.
+    pub fn resolve_entry(&self, entry: &mut ConsoleLogEntry) -> bool {
+        let Some((stack, used)) = entry
+            .stack_trace
+            .as_deref()
+            .and_then(|stack| self.resolve_stack(stack))
+        else {
+            return false;
+        };
+
+        entry.stack_trace = Some(stack);
+        entry
+            .fields
+            .get_or_insert_with(Default::default)
+            .insert(SOURCEMAPS_FIELD.to_string(), used.into());
+        true
+    }
+
+    // `None` when no frame could be resolved.
+    pub fn resolve_stack(&self, stack: &str) -> Option<(String, Vec<String>)> {
+        let mut used: Vec<String> = Vec::new();
+        let lines: Vec<String> = stack
+            .lines()
+            .map(|frame| match self.resolve_frame(frame) {
+                Some((resolved, map)) => {
+                    if !used.contains(&map) {
+                        used.push(map);
+                    }
+                    resolved
+                }
+                None => frame.to_string(),
+            })
+            .collect();
+
+        (!used.is_empty()).then(|| (lines.join("\n"), used))
+    }
+
+    fn resolve_frame(&self, frame: &str) -> Option<(String, String)> {
+        let location = location_pattern().captures_iter(frame).last()?;
+        let whole = location.get(0)?;
+        let line = location[2].parse().ok()?;
+        let column = location[3].parse().ok()?;
+
+        let (map_name, map) = self.map_for(&location[1])?;
+        let original = map.lookup(line, column)?;
+
+        let mut resolved = format!(
+            "{}{}:{}:{}{}",
+            &frame[..whole.start()],
+            original.source,
+            original.line,
+            original.column,
+            &frame[whole.end()..]
+        );
+        if let Some(name) = &original.name {
+            resolved = function_pattern()
+                .replace(&resolved, |caps: &regex::Captures<'_>| match caps.get(1) {
+                    Some(prefix) => format!("{}{}{}", prefix.as_str(), name, &caps[3]),
+                    None => format!("{}{}{}", &caps[4], name, &caps[6]),
+                })
+                .into_owned();
+        }
+
+        Some((resolved, map_name))
+    }
+
+    // Looks for `<dir>/<url path>.map`, then `<dir>/<file name>.map`.
+    fn map_for(&self, url: &str) -> Option<(String, Arc<SourceMap>)> {
+        let path = url.split_once("://").map_or(url, |(_, rest)| {
+            rest.split_once('/').map_or("", |(_, path)| path)
+        });
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        // Only plain components, so a frame cannot point outside the directory.
+        let relative: PathBuf = Path::new(path)
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part),
+                _ => None,
+            })
+            .collect();
+        let file_name = relative.file_name()?.to_owned();
+
+        let mut candidates = vec![with_map_suffix(&relative)];
+        if relative
+            .parent()
+            .is_some_and(|parent| !parent.as_os_str().is_empty())
+        {
+            candidates.push(with_map_suffix(Path::new(&file_name)));
+        }
+
+        let mut maps = self.maps.lock().unwrap_or_else(|e| e.into_inner());
+        for candidate in candidates {
+            let map = maps
+                .entry(candidate.clone())
+                .or_insert_with(|| self.load(&candidate))
+                .clone();
+            if let Some(map) = map {
+                return Some((candidate.to_string_lossy().replace('\\', "/"), map));
+            }
+        }
+        None
+    }
+
+    fn load(&self, relative: &Path) -> Option<Arc<SourceMap>> {
+        let path = self.dir.join(relative);
+        let bytes = fs::read(&path).ok()?;
+        match SourceMap::parse(&bytes) {
+            Ok(map) => Some(Arc::new(map)),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring unreadable source map");
+                None
+            }
+        }
+    }
+}
+
+fn with_map_suffix(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".map");
+    PathBuf::from(name)
+}
@@ -0,0 +1,84 @@
+use crate::domain::{CaptureSizeReport, CaptureSizeThresholds, DebugSnapshot};
+use std::fs;
+use std::path::Path;
+
+fn file_size(path: Option<&Path>) -> Option<u64> {
+    fs::metadata(path?).ok().map(|metadata| metadata.len())
+}
+
+fn json_size<T: serde::Serialize + ?Sized>(value: &T) -> u64 {
+    serde_json::to_vec_pretty(value)
+        .map(|bytes| bytes.len() as u64)
+        .unwrap_or(0)
+}
+
+// Measured before the snapshot is written, so the JSON size leaves out the
+// report itself.
+pub fn measure_capture(snapshot: &DebugSnapshot) -> CaptureSizeReport {
+    let snapshot_json_bytes = json_size(snapshot);
+    let screenshot_bytes = file_size(snapshot.screenshot_path.as_deref());
+    let dom_bytes = file_size(snapshot.dom_snapshot_path.as_deref());
+
+    CaptureSizeReport {
+        snapshot_json_bytes,
+        console_logs_bytes: json_size(&snapshot.console_logs),
+        extensions_bytes: json_size(&snapshot.extensions),
+        screenshot_bytes,
+        dom_bytes,
+        total_bytes: snapshot_json_bytes + screenshot_bytes.unwrap_or(0) + dom_bytes.unwrap_or(0),
+    }
+}
+
+// Names each oversized component together with the setting that shrinks it.
+pub fn warn_oversized(report: &CaptureSizeReport, thresholds: &CaptureSizeThresholds) {
+    let components = [
+        (
+            "console_logs",
+            Some(report.console_logs_bytes),
+            thresholds.console_logs_bytes,
+            "lower `max_console_logs` in the capture profile",
+        ),
+        (
+            "extensions",
+            Some(report.extensions_bytes),
+            thresholds.extensions_bytes,
+            "trim snapshot contributors or frontend `extensions`",
+        ),
+        (
+            "screenshot",
+            report.screenshot_bytes,
+            thresholds.screenshot_bytes,
+            "set `enable_screenshots: false` in the capture profile",
+        ),
+        (
+            "dom",
+            report.dom_bytes,
+            thresholds.dom_bytes,
+            "set `enable_dom_capture: false` in the capture profile",
+        ),
+        (
+            "snapshot_json",
+            Some(report.snapshot_json_bytes),
+            thresholds.snapshot_json_bytes,
+            "lower `max_console_logs` or `max_resource_entries`",
+        ),
+    ];
+
+    for (component, bytes, limit, hint) in components {
+        let Some(bytes) = bytes else {
+            continue;
+        };
+        if bytes > limit {
+            tracing::warn!(
+                component,
+                bytes,
+                limit,
+                "Capture component `{}` is {} bytes (limit {}); {}",
+                component,
+                bytes,
+                limit,
+                hint
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::domain::{
+    Clock, DebugCommandHistory, DebugCommandOutcome, DebugCommandRecord, DebugCommandReply,
+    DebugCommandReplyError, IdGenerator, TraceContext,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+const HISTORY_CAPACITY: usize = 100;
+const FAILURE_HISTORY_CAPACITY: usize = 10;
+
+pub struct DebugCommandBroker {
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+    pending: Mutex<HashMap<String, (oneshot::Sender<DebugCommandReply>, TraceContext)>>,
+    listeners: Mutex<HashMap<String, HashSet<String>>>,
+    history: Mutex<VecDeque<DebugCommandRecord>>,
+    failures: Mutex<VecDeque<DebugCommandRecord>>,
+}
+
+pub struct PendingReply {
+    pub request_id: String,
+    receiver: oneshot::Receiver<DebugCommandReply>,
+}
+
+impl DebugCommandBroker {
+    pub fn new(clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self {
+            clock,
+            ids,
+            pending: Mutex::default(),
+            listeners: Mutex::default(),
+            history: Mutex::default(),
+            failures: Mutex::default(),
+        }
+    }
+
+    pub fn next_request_id(&self) -> String {
+        format!("{}-{}", self.clock.now_millis(), self.ids.next_sequence())
+    }
+
+    pub fn register_listeners(&self, window_label: &str, commands: Vec<String>) {
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.insert(window_label.to_string(), commands.into_iter().collect());
+        }
+    }
+
+    pub fn has_listener(&self, window_label: &str, command: &str) -> bool {
+        self.listeners
+            .lock()
+            .map(|listeners| {
+                listeners
+                    .get(window_label)
+                    .is_some_and(|commands| commands.contains(command))
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn expect_reply(&self, trace: TraceContext) -> PendingReply {
+        let request_id = self.next_request_id();
+        let (sender, receiver) = oneshot::channel();
+
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(request_id.clone(), (sender, trace));
+        }
+
+        PendingReply {
+            request_id,
+            receiver,
+        }
+    }
+
+    // The trace a still-pending request was sent under, for the span its
+    // reply is handled in.
+    pub fn pending_trace(&self, request_id: &str) -> Option<TraceContext> {
+        self.pending
+            .lock()
+            .ok()
+            .and_then(|pending| pending.get(request_id).map(|(_, trace)| trace.clone()))
+    }
+
+    // Returns false when the request already timed out or never existed.
+    pub fn resolve(&self, request_id: &str, reply: DebugCommandReply) -> bool {
+        let sender = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(request_id));
+
+        match sender {
+            Some((sender, _)) => sender.send(reply).is_ok(),
+            None => false,
+        }
+    }
+
+    pub async fn wait(&self, pending: PendingReply, timeout: Duration) -> DebugCommandOutcome {
+        let PendingReply {
+            request_id,
+            receiver,
+        } = pending;
+
+        let outcome = match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(reply)) => outcome_from_reply(reply),
+            Ok(Err(_)) | Err(_) => DebugCommandOutcome::Timeout {
+                timeout_ms: timeout.as_millis() as u64,
+            },
+        };
+
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&request_id);
+        }
+
+        outcome
+    }
+
+    pub fn record(&self, record: DebugCommandRecord) {
+        if record.outcome.is_failure() {
+            if let Ok(mut failures) = self.failures.lock() {
+                push_bounded(&mut failures, record.clone(), FAILURE_HISTORY_CAPACITY);
+            }
+        }
+
+        if let Ok(mut history) = self.history.lock() {
+            push_bounded(&mut history, record, HISTORY_CAPACITY);
+        }
+    }
+
+    pub fn history(&self, limit: usize) -> DebugCommandHistory {
+        let recent = self
+            .history
+            .lock()
+            .map(|history| history.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default();
+        let recent_failures = self
+            .failures
+            .lock()
+            .map(|failures| failures.iter().rev().cloned().collect())
+            .unwrap_or_default();
+
+        DebugCommandHistory {
+            recent,
+            recent_failures,
+        }
+    }
+}
+
+fn outcome_from_reply(reply: DebugCommandReply) -> DebugCommandOutcome {
+    if reply.ok {
+        return DebugCommandOutcome::Ok { value: reply.value };
+    }
+
+    DebugCommandOutcome::HandlerError {
+        error: reply.error.unwrap_or_else(|| DebugCommandReplyError {
+            message: "Handler reported failure without an error".to_string(),
+            stack: None,
+        }),
+    }
+}
+
+fn push_bounded<T>(queue: &mut VecDeque<T>, item: T, capacity: usize) {
+    if queue.len() == capacity {
+        queue.pop_front();
+    }
+    queue.push_back(item);
+}
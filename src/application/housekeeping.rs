@@ -0,0 +1,72 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// Wall clock advancing this many tick intervals between two housekeeping
+// ticks means the process was not scheduled, most likely because the
+// machine slept or the app was backgrounded.
+const SUSPEND_GAP_FACTOR: i64 = 3;
+
+#[derive(Debug)]
+pub struct SuspendDetector {
+    interval_ms: i64,
+    last_tick: Option<i64>,
+}
+
+impl SuspendDetector {
+    pub fn new(interval_ms: i64) -> Self {
+        Self {
+            interval_ms: interval_ms.max(1),
+            last_tick: None,
+        }
+    }
+
+    // Returns the unaccounted wall-clock gap when it looks like a suspend.
+    pub fn tick(&mut self, now_millis: i64) -> Option<i64> {
+        let previous = self.last_tick.replace(now_millis)?;
+        let elapsed = now_millis - previous;
+        (elapsed > self.interval_ms * SUSPEND_GAP_FACTOR).then(|| elapsed - self.interval_ms)
+    }
+}
+
+// Runs `job` immediately and then every `interval` on its own thread until
+// cancelled. Cancelling wakes the thread instead of waiting out the sleep;
+// a run already in progress finishes first.
+#[derive(Debug)]
+pub struct PeriodicTask {
+    cancelled: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl PeriodicTask {
+    pub fn spawn(
+        name: &str,
+        interval: Duration,
+        mut job: impl FnMut() + Send + 'static,
+    ) -> std::io::Result<Self> {
+        let cancelled = Arc::new((Mutex::new(false), Condvar::new()));
+        let signal = cancelled.clone();
+
+        thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || loop {
+                job();
+
+                let (lock, wake) = &*signal;
+                let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+                let (guard, _) = wake
+                    .wait_timeout_while(guard, interval, |cancelled| !*cancelled)
+                    .unwrap_or_else(|e| e.into_inner());
+                if *guard {
+                    return;
+                }
+            })?;
+
+        Ok(Self { cancelled })
+    }
+
+    pub fn cancel(&self) {
+        let (lock, wake) = &*self.cancelled;
+        *lock.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        wake.notify_all();
+    }
+}
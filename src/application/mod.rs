@@ -1,3 +1,16 @@
+pub mod capture_size;
+pub mod debug_commands;
+pub mod housekeeping;
+pub mod navigation;
+pub mod recording;
+pub mod screenshot_queue;
+pub mod snapshot_hooks;
 pub mod use_cases;
 
+pub use debug_commands::DebugCommandBroker;
+pub use housekeeping::{PeriodicTask, SuspendDetector};
+pub use navigation::{NavigationDebouncer, NAVIGATION_SNAPSHOT_EVENT};
+pub use recording::WindowRecorder;
+pub use screenshot_queue::ScreenshotQueue;
+pub use snapshot_hooks::{SnapshotCapturedHook, SnapshotHooks, SNAPSHOT_CAPTURED_EVENT};
 pub use use_cases::*;
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub const NAVIGATION_SNAPSHOT_EVENT: &str = "debug-tools://navigation-snapshot";
+
+// Each navigation bumps a per-webview generation; a delayed capture only runs
+// if no newer navigation happened in the meantime, so redirect chains
+// produce a single snapshot of where they ended up.
+#[derive(Debug, Default)]
+pub struct NavigationDebouncer {
+    generations: Mutex<HashMap<String, u64>>,
+}
+
+impl NavigationDebouncer {
+    pub fn bump(&self, webview_label: &str) -> u64 {
+        let mut generations = self.generations.lock().unwrap_or_else(|e| e.into_inner());
+        let generation = generations.entry(webview_label.to_string()).or_default();
+        *generation += 1;
+        *generation
+    }
+
+    pub fn is_latest(&self, webview_label: &str, generation: u64) -> bool {
+        self.generations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(webview_label)
+            .is_some_and(|latest| *latest == generation)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::domain::RecordingStatus;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::async_runtime::JoinHandle;
+
+// (frames written, frames dropped)
+pub type RecordingTask = JoinHandle<(usize, usize)>;
+
+pub struct ActiveRecording {
+    pub status: RecordingStatus,
+    pub stop: Arc<AtomicBool>,
+    pub task: RecordingTask,
+}
+
+// The window recording in progress, if any. Only one runs at a time; a
+// recording that reached its frame limit stays here until it is stopped.
+#[derive(Default)]
+pub struct WindowRecorder {
+    active: Mutex<Option<ActiveRecording>>,
+}
+
+impl WindowRecorder {
+    // Runs `spawn` under the lock so two starts cannot both begin recording.
+    // Returns `None` when a recording is already active.
+    pub fn start(&self, spawn: impl FnOnce() -> ActiveRecording) -> Option<RecordingStatus> {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        if active.is_some() {
+            return None;
+        }
+        let recording = spawn();
+        let status = recording.status.clone();
+        *active = Some(recording);
+        Some(status)
+    }
+
+    pub fn take(&self) -> Option<ActiveRecording> {
+        let recording = self
+            .active
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()?;
+        recording.stop.store(true, Ordering::SeqCst);
+        Some(recording)
+    }
+}
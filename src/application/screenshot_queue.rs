@@ -0,0 +1,116 @@
+use crate::domain::ScreenshotQueueStats;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OnceCell, Semaphore};
+
+type SharedCapture = Arc<OnceCell<Result<PathBuf, String>>>;
+
+// Remembers each key's latest capture so requests arriving while it runs,
+// or shortly after it finished, get its result instead of a new capture.
+struct Recent {
+    started_at: Instant,
+    capture: SharedCapture,
+}
+
+// Serializes screenshot captures: at most `concurrency` run at once and
+// each is bounded by `timeout`. Two captures overlapping on Windows can
+// fail or come back as a black frame.
+pub struct ScreenshotQueue {
+    permits: Semaphore,
+    concurrency: usize,
+    timeout: Duration,
+    coalesce_window: Duration,
+    recent: Mutex<HashMap<String, Recent>>,
+    depth: AtomicUsize,
+    in_flight: AtomicUsize,
+    coalesced: AtomicU64,
+}
+
+impl ScreenshotQueue {
+    pub fn new(concurrency: usize, timeout: Duration, coalesce_window: Duration) -> Self {
+        let concurrency = concurrency.max(1);
+        Self {
+            permits: Semaphore::new(concurrency),
+            concurrency,
+            timeout,
+            coalesce_window,
+            recent: Mutex::new(HashMap::new()),
+            depth: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            coalesced: AtomicU64::new(0),
+        }
+    }
+
+    // `key` identifies the target window; requests for the same key within
+    // the coalesce window share one capture and its path.
+    pub async fn capture<F, Fut>(&self, key: &str, capture: F) -> Result<PathBuf, String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<PathBuf, String>>,
+    {
+        let shared = self.slot(key);
+        if let Some(result) = shared.get() {
+            return result.clone();
+        }
+
+        self.depth.fetch_add(1, Ordering::SeqCst);
+        let result = shared
+            .get_or_init(|| async {
+                let Ok(_permit) = self.permits.acquire().await else {
+                    return Err("Screenshot queue closed".to_string());
+                };
+                self.in_flight.fetch_add(1, Ordering::SeqCst);
+                let outcome = tokio::time::timeout(self.timeout, capture()).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                outcome.unwrap_or_else(|_| {
+                    Err(format!(
+                        "Screenshot capture timed out after {}ms",
+                        self.timeout.as_millis()
+                    ))
+                })
+            })
+            .await
+            .clone();
+        self.depth.fetch_sub(1, Ordering::SeqCst);
+
+        result
+    }
+
+    pub fn stats(&self) -> ScreenshotQueueStats {
+        ScreenshotQueueStats {
+            depth: self.depth.load(Ordering::SeqCst),
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            concurrency: self.concurrency,
+            coalesced: self.coalesced.load(Ordering::SeqCst),
+        }
+    }
+
+    fn slot(&self, key: &str) -> SharedCapture {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        recent.retain(|_, entry| {
+            !entry.capture.initialized()
+                || now.duration_since(entry.started_at) < self.coalesce_window
+        });
+
+        if let Some(entry) = recent.get(key) {
+            self.coalesced.fetch_add(1, Ordering::SeqCst);
+            return entry.capture.clone();
+        }
+
+        let capture = SharedCapture::default();
+        recent.insert(
+            key.to_string(),
+            Recent {
+                started_at: now,
+                capture: capture.clone(),
+            },
+        );
+        capture
+    }
+}
@@ -0,0 +1,103 @@
+use crate::domain::{DebugSnapshot, SnapshotCapturedEvent};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub const SNAPSHOT_CAPTURED_EVENT: &str = "debug-tools://snapshot-captured";
+
+pub type SnapshotCapturedHook = Arc<dyn Fn(&DebugSnapshot, &Path) + Send + Sync>;
+pub type SnapshotEventEmitter = Box<dyn Fn(&SnapshotCapturedEvent) + Send + Sync>;
+
+// Host callbacks run after a snapshot is persisted, off the capture path and
+// in registration order. Each one is bounded by `timeout` and isolated from
+// panics, so a misbehaving callback only loses its own work.
+pub struct SnapshotHooks {
+    hooks: Vec<SnapshotCapturedHook>,
+    timeout: Duration,
+    emitter: Option<SnapshotEventEmitter>,
+}
+
+impl SnapshotHooks {
+    pub fn new(hooks: Vec<SnapshotCapturedHook>, timeout: Duration) -> Self {
+        Self {
+            hooks,
+            timeout,
+            emitter: None,
+        }
+    }
+
+    pub fn with_emitter(mut self, emitter: SnapshotEventEmitter) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn dispatch(&self, snapshot: &DebugSnapshot, path: &Path) {
+        // The completion event does not depend on the callbacks.
+        if let Some(emitter) = &self.emitter {
+            emitter(&SnapshotCapturedEvent {
+                path: path.to_path_buf(),
+                timestamp: snapshot.timestamp,
+                label: snapshot.label.clone(),
+                session_id: snapshot.session_id.clone(),
+            });
+        }
+
+        if self.hooks.is_empty() {
+            return;
+        }
+
+        let hooks = self.hooks.clone();
+        let snapshot = Arc::new(snapshot.clone());
+        let path = path.to_path_buf();
+        let timeout = self.timeout;
+
+        let spawned = thread::Builder::new()
+            .name("debug-tools-snapshot-hooks".into())
+            .spawn(move || {
+                for (index, hook) in hooks.into_iter().enumerate() {
+                    run_isolated(index, hook, snapshot.clone(), path.clone(), timeout);
+                }
+            });
+
+        if let Err(e) = spawned {
+            tracing::error!(error = %e, "Failed to start snapshot hook thread");
+        }
+    }
+}
+
+fn run_isolated(
+    index: usize,
+    hook: SnapshotCapturedHook,
+    snapshot: Arc<DebugSnapshot>,
+    path: PathBuf,
+    timeout: Duration,
+) {
+    let (done, finished) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(format!("debug-tools-snapshot-hook-{}", index))
+        .spawn(move || {
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| hook(&snapshot, &path)));
+            let _ = done.send(outcome.is_ok());
+        });
+
+    if let Err(e) = spawned {
+        tracing::error!(hook = index, error = %e, "Failed to start snapshot hook");
+        return;
+    }
+
+    // A hook that overruns keeps its thread; later hooks just stop waiting for it.
+    match finished.recv_timeout(timeout) {
+        Ok(true) => {}
+        Ok(false) | Err(RecvTimeoutError::Disconnected) => {
+            tracing::error!(hook = index, "Snapshot hook panicked")
+        }
+        Err(RecvTimeoutError::Timeout) => tracing::warn!(
+            hook = index,
+            timeout_ms = timeout.as_millis() as u64,
+            "Snapshot hook timed out"
+        ),
+    }
+}
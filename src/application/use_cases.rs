@@ -1,9 +1,18 @@
+use crate::application::capture_size::{measure_capture, warn_oversized};
+use crate::application::snapshot_hooks::SnapshotHooks;
+use crate::domain::console_sources::tag_ingest_source;
+use crate::domain::moments::MOMENT_SNAPSHOT_EXTENSION;
+use crate::domain::privacy::strip_url_query;
+use crate::domain::user_agents::PLACEHOLDER_USER_AGENT;
 use crate::domain::{
-    ConsoleLogEntry, DebugSnapshot, DomSnapshotResult, DomState, RepositoryError,
-    SnapshotRepository, ViewportInfo, WebViewState,
+    BuildInfo, CaptureContext, CaptureSizeThresholds, Clock, ClockSkewGuard, ConsoleLogEntry,
+    ConsoleSource, DebugMoments, DebugSnapshot, DomSnapshotResult, DomState, LogLevel, LogSequence,
+    LogStore, PartialDebugSnapshot, QuietMode, QuietModeStatus, RendererInfo, RepositoryError,
+    SnapshotFieldError, SnapshotRepository, SystemInfo, UserAgents, ViewportInfo, WebViewState,
+    SNAPSHOT_SCHEMA_VERSION,
 };
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager, Runtime};
 use thiserror::Error;
 
@@ -15,20 +24,39 @@ pub enum UseCaseError {
     WindowProperty(String),
     #[error("Repository error: {0}")]
     Repository(#[from] RepositoryError),
-    #[error("System time error: {0}")]
-    SystemTime(String),
+    #[error("Capture aborted by before_capture hook")]
+    CaptureAborted,
+    #[error("Invalid snapshot payload: {}", join_field_errors(.0))]
+    InvalidSnapshot(Vec<SnapshotFieldError>),
 }
 
+fn join_field_errors(errors: &[SnapshotFieldError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub type BeforeCaptureHook = Box<dyn Fn(&mut DebugSnapshot) -> bool + Send + Sync>;
+pub type SnapshotContributor = Box<dyn Fn(&DebugSnapshot) -> Option<Value> + Send + Sync>;
+
 pub struct CaptureWebViewStateUseCase;
 
+// The window captures fall back to when no label is given.
+pub const DEFAULT_WINDOW_LABEL: &str = "main";
+
 impl CaptureWebViewStateUseCase {
     #[tracing::instrument(skip(app))]
-    pub fn execute<R: Runtime>(app: &AppHandle<R>) -> Result<WebViewState, UseCaseError> {
+    pub fn execute<R: Runtime>(
+        app: &AppHandle<R>,
+        label: &str,
+    ) -> Result<WebViewState, UseCaseError> {
         tracing::debug!("Capturing webview state");
 
         let window = app
-            .get_webview_window("main")
-            .ok_or_else(|| UseCaseError::WindowNotFound("main".into()))?;
+            .get_webview_window(label)
+            .ok_or_else(|| window_not_found(app, label))?;
 
         let url = window
             .url()
@@ -40,10 +68,21 @@ impl CaptureWebViewStateUseCase {
             .inner_size()
             .map_err(|e| UseCaseError::WindowProperty(format!("Failed to get size: {}", e)))?;
 
+        let user_agent = app
+            .try_state::<UserAgents>()
+            .and_then(|agents| agents.get(label))
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    label,
+                    "WebView has not reported its user agent; using a placeholder"
+                );
+                PLACEHOLDER_USER_AGENT.to_string()
+            });
+
         let state = WebViewState {
             url: url.to_string(),
             title,
-            user_agent: "TauriWebView/2.0".to_string(),
+            user_agent,
             viewport: ViewportInfo {
                 width: size.width,
                 height: size.height,
@@ -56,13 +95,57 @@ impl CaptureWebViewStateUseCase {
     }
 }
 
+// The focused window, else `main`.
+pub fn focused_window_label<R: Runtime>(app: &AppHandle<R>) -> String {
+    app.webview_windows()
+        .into_iter()
+        .find(|(_, window)| window.is_focused().unwrap_or(false))
+        .map(|(label, _)| label)
+        .unwrap_or_else(|| DEFAULT_WINDOW_LABEL.to_string())
+}
+
+// Lists the labels that do exist, which is usually enough to spot a typo
+// or a window that has not been created yet.
+pub fn window_not_found<R: Runtime>(app: &AppHandle<R>, label: &str) -> UseCaseError {
+    let mut labels: Vec<String> = app.webview_windows().into_keys().collect();
+    labels.sort();
+    let available = if labels.is_empty() {
+        "none".to_string()
+    } else {
+        labels.join(", ")
+    };
+    UseCaseError::WindowNotFound(format!("{} (available: {})", label, available))
+}
+
+// The first DOM capture of a session, kept in memory so deltas can be
+// computed without re-reading it.
+#[derive(Debug, Clone)]
+pub struct DomBaseline {
+    pub path: std::path::PathBuf,
+    pub timestamp: i64,
+    pub html: Arc<String>,
+}
+
 pub struct SaveDomSnapshotUseCase<R: SnapshotRepository> {
     repository: Arc<R>,
+    clock: Arc<dyn Clock>,
+    baseline: Mutex<Option<DomBaseline>>,
 }
 
 impl<R: SnapshotRepository> SaveDomSnapshotUseCase<R> {
-    pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<R>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            repository,
+            clock,
+            baseline: Mutex::new(None),
+        }
+    }
+
+    pub fn baseline(&self) -> Option<DomBaseline> {
+        self.baseline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
     }
 
     #[tracing::instrument(skip(self, html))]
@@ -73,11 +156,20 @@ impl<R: SnapshotRepository> SaveDomSnapshotUseCase<R> {
         title: String,
         viewport_width: u32,
         viewport_height: u32,
+        shadow_dom_included: bool,
     ) -> Result<DomSnapshotResult, UseCaseError> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| UseCaseError::SystemTime(e.to_string()))?
-            .as_secs() as i64;
+        let timestamp = self.clock.now_secs();
+
+        // `String` guarantees valid UTF-8, but lone surrogates from the
+        // WebView arrive as U+FFFD; count them so a mangled capture is visible.
+        let replacement_chars = html.matches(char::REPLACEMENT_CHARACTER).count();
+        let baseline_html = self.baseline().is_none().then(|| Arc::new(html.clone()));
+        if replacement_chars > 0 {
+            tracing::warn!(
+                replacement_chars,
+                "DOM snapshot contains replacement characters"
+            );
+        }
 
         let dom = DomState {
             html,
@@ -88,10 +180,24 @@ impl<R: SnapshotRepository> SaveDomSnapshotUseCase<R> {
                 height: viewport_height,
             },
             captured_at: timestamp,
+            shadow_dom_included,
+            replacement_chars,
         };
 
         let result = self.repository.save_dom(&dom, timestamp)?;
 
+        if let Some(html) = baseline_html {
+            let mut baseline = self.baseline.lock().unwrap_or_else(|e| e.into_inner());
+            if baseline.is_none() {
+                tracing::info!(path = %result.path.display(), "DOM baseline recorded");
+                *baseline = Some(DomBaseline {
+                    path: result.path.clone(),
+                    timestamp,
+                    html,
+                });
+            }
+        }
+
         tracing::info!(
             path = %result.path.display(),
             "DOM snapshot saved"
@@ -103,35 +209,183 @@ impl<R: SnapshotRepository> SaveDomSnapshotUseCase<R> {
 
 pub struct AppendConsoleLogsUseCase<R: SnapshotRepository> {
     repository: Arc<R>,
+    quiet_mode: Arc<QuietMode>,
+    clock: Arc<dyn Clock>,
+    log_store: Option<Arc<dyn LogStore>>,
+    clock_skew: Option<ClockSkewGuard>,
+    sequence: LogSequence,
 }
 
 impl<R: SnapshotRepository> AppendConsoleLogsUseCase<R> {
-    pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<R>, quiet_mode: Arc<QuietMode>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            repository,
+            quiet_mode,
+            clock,
+            log_store: None,
+            clock_skew: None,
+            sequence: LogSequence::default(),
+        }
+    }
+
+    pub fn with_log_store(mut self, log_store: Option<Arc<dyn LogStore>>) -> Self {
+        self.log_store = log_store;
+        self
+    }
+
+    pub fn with_clock_skew_tolerance(mut self, tolerance_ms: u64) -> Self {
+        self.clock_skew = Some(ClockSkewGuard::new(tolerance_ms));
+        self
     }
 
     #[tracing::instrument(skip(self, logs))]
-    pub fn execute(&self, logs: Vec<ConsoleLogEntry>) -> Result<String, UseCaseError> {
+    pub fn execute(&self, mut logs: Vec<ConsoleLogEntry>) -> Result<String, UseCaseError> {
+        let now = self.clock.now_secs();
+        self.finish_quiet_mode(now)?;
+        self.quiet_mode.retain_persistable(&mut logs, now);
+
         if logs.is_empty() {
-            tracing::debug!("No logs to append");
             return Ok("no logs".to_string());
         }
 
-        tracing::debug!(count = logs.len(), "Appending console logs");
+        if let Some(clock_skew) = &self.clock_skew {
+            let annotated = clock_skew.annotate(&mut logs);
+            if annotated > 0 {
+                tracing::warn!(annotated, "Console log timestamps went backwards");
+            }
+        }
+        self.sequence.stamp(&mut logs);
 
         let path = self.repository.save_console_logs(&logs)?;
 
+        // The JSONL file stays the source of truth; a failing store only
+        // degrades queries.
+        if let Some(log_store) = &self.log_store {
+            if let Err(e) = log_store.append_console_logs(&logs) {
+                tracing::warn!(error = %e, "Failed to mirror console logs into log store");
+            }
+        }
+
         Ok(path.to_string_lossy().into_owned())
     }
+
+    // Markers bypass quiet mode: they are the anchor points a suppressed
+    // timeline needs most.
+    pub fn append_marker(&self, marker: ConsoleLogEntry) -> Result<(), UseCaseError> {
+        let mut markers = [marker];
+        tag_ingest_source(&mut markers, ConsoleSource::Marker);
+        self.sequence.stamp(&mut markers);
+        self.repository.save_console_logs(&markers)?;
+        if let Some(log_store) = &self.log_store {
+            if let Err(e) = log_store.append_console_logs(&markers) {
+                tracing::warn!(error = %e, "Failed to mirror marker into log store");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_quiet_mode(
+        &self,
+        level: LogLevel,
+        duration_secs: u64,
+    ) -> Result<QuietModeStatus, UseCaseError> {
+        let now = self.clock.now_secs();
+        self.finish_quiet_mode(now)?;
+
+        let status = self.quiet_mode.activate(level, duration_secs, now);
+
+        tracing::info!(
+            level = %status.level,
+            until = status.until,
+            "Quiet mode enabled"
+        );
+
+        Ok(status)
+    }
+
+    pub fn finish_quiet_mode(&self, now: i64) -> Result<Option<QuietModeStatus>, UseCaseError> {
+        let Some(finished) = self.quiet_mode.take_expired(now) else {
+            return Ok(None);
+        };
+
+        let mut marker = ConsoleLogEntry {
+            timestamp: now * 1000,
+            level: LogLevel::Info.to_string(),
+            message: format!(
+                "[debug-tools] quiet mode ended: {} entries below {:?} suppressed, {} automatic snapshots paused",
+                finished.suppressed_entries, finished.level, finished.paused_snapshots
+            ),
+            args: serde_json::to_value(&finished).unwrap_or_default(),
+            stack_trace: None,
+            fields: None,
+        };
+
+        tag_ingest_source(std::slice::from_mut(&mut marker), ConsoleSource::Marker);
+        self.sequence.stamp(std::slice::from_mut(&mut marker));
+        self.repository.save_console_logs(&[marker])?;
+
+        tracing::info!(suppressed = finished.suppressed_entries, "Quiet mode ended");
+
+        Ok(Some(finished))
+    }
+}
+
+pub fn build_info<Rt: Runtime>(app: &AppHandle<Rt>) -> BuildInfo {
+    let package_info = app.package_info();
+    BuildInfo {
+        app_name: package_info.name.clone(),
+        app_version: package_info.version.to_string(),
+        plugin_version: env!("CARGO_PKG_VERSION").to_string(),
+        debug_build: cfg!(debug_assertions),
+    }
 }
 
 pub struct CaptureDebugSnapshotUseCase<R: SnapshotRepository> {
     repository: Arc<R>,
+    clock: Arc<dyn Clock>,
+    before_capture: Option<BeforeCaptureHook>,
+    contributors: Vec<(String, SnapshotContributor)>,
+    hooks: Option<Arc<SnapshotHooks>>,
+    size_thresholds: CaptureSizeThresholds,
+    moments: Option<Arc<DebugMoments>>,
 }
 
 impl<R: SnapshotRepository> CaptureDebugSnapshotUseCase<R> {
-    pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<R>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            repository,
+            clock,
+            before_capture: None,
+            contributors: Vec::new(),
+            hooks: None,
+            size_thresholds: CaptureSizeThresholds::default(),
+            moments: None,
+        }
+    }
+
+    pub fn with_before_capture(mut self, hook: Option<BeforeCaptureHook>) -> Self {
+        self.before_capture = hook;
+        self
+    }
+
+    pub fn with_contributors(mut self, contributors: Vec<(String, SnapshotContributor)>) -> Self {
+        self.contributors = contributors;
+        self
+    }
+
+    pub fn with_snapshot_hooks(mut self, hooks: Arc<SnapshotHooks>) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    pub fn with_size_thresholds(mut self, thresholds: CaptureSizeThresholds) -> Self {
+        self.size_thresholds = thresholds;
+        self
+    }
+
+    pub fn with_moments(mut self, moments: Arc<DebugMoments>) -> Self {
+        self.moments = Some(moments);
+        self
     }
 
     #[tracing::instrument(skip(self, app, console_logs))]
@@ -141,21 +395,140 @@ impl<R: SnapshotRepository> CaptureDebugSnapshotUseCase<R> {
         console_logs: Vec<ConsoleLogEntry>,
         screenshot_path: Option<std::path::PathBuf>,
         dom_snapshot_path: Option<std::path::PathBuf>,
+        context: CaptureContext,
     ) -> Result<DebugSnapshot, UseCaseError> {
-        let webview_state = CaptureWebViewStateUseCase::execute(app)?;
+        let webview_state = CaptureWebViewStateUseCase::execute(app, DEFAULT_WINDOW_LABEL)?;
 
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| UseCaseError::SystemTime(e.to_string()))?
-            .as_secs() as i64;
+        let snapshot = self.assemble(
+            app,
+            webview_state,
+            console_logs,
+            screenshot_path,
+            dom_snapshot_path,
+            context,
+        );
+
+        self.persist(snapshot)
+    }
+
+    // Completes a frontend-authored partial snapshot. `cached_webview` is used
+    // when the payload carries no override, avoiding a live window query.
+    #[tracing::instrument(skip(self, app, partial, cached_webview))]
+    pub fn execute_partial<Rt: Runtime>(
+        &self,
+        app: &AppHandle<Rt>,
+        partial: PartialDebugSnapshot,
+        cached_webview: Option<WebViewState>,
+        context: CaptureContext,
+    ) -> Result<DebugSnapshot, UseCaseError> {
+        let errors = partial.validate();
+        if !errors.is_empty() {
+            return Err(UseCaseError::InvalidSnapshot(errors));
+        }
+
+        let webview_state = match partial.webview_state.or(cached_webview) {
+            Some(webview_state) => webview_state,
+            None => CaptureWebViewStateUseCase::execute(app, DEFAULT_WINDOW_LABEL)?,
+        };
 
-        let snapshot = DebugSnapshot {
+        let mut snapshot = self.assemble(
+            app,
+            webview_state,
+            partial.console_logs,
+            None,
+            None,
+            context,
+        );
+        snapshot.label = partial.label;
+        snapshot.note = partial.note;
+        snapshot.extensions = partial.extensions;
+
+        self.persist(snapshot)
+    }
+
+    pub fn assemble<Rt: Runtime>(
+        &self,
+        app: &AppHandle<Rt>,
+        webview_state: WebViewState,
+        console_logs: Vec<ConsoleLogEntry>,
+        screenshot_path: Option<std::path::PathBuf>,
+        dom_snapshot_path: Option<std::path::PathBuf>,
+        context: CaptureContext,
+    ) -> DebugSnapshot {
+        let timestamp = self.clock.now_secs();
+        let mut webview_state = webview_state;
+        if context.strip_url_queries {
+            strip_url_query(&mut webview_state.url);
+        }
+
+        DebugSnapshot {
             timestamp,
             webview_state,
             console_logs,
             screenshot_path,
             dom_snapshot_path,
-        };
+            system_info: Some(SystemInfo {
+                repository_metrics: self.repository.metrics(),
+                renderer: Some(RendererInfo {
+                    frontend: context.frontend_reports.renderer,
+                    platform: context.platform_gpu,
+                }),
+                plugin_init: context.plugin_init,
+            }),
+            security_context: context.frontend_reports.security_context,
+            effective_config_path: context.effective_config_path,
+            session_id: context.session_id,
+            build_info: Some(build_info(app)),
+            label: None,
+            note: None,
+            extensions: serde_json::Map::new(),
+            resources: context.frontend_reports.resources.unwrap_or_default(),
+            recent_lifecycle_events: context.lifecycle_events,
+            route_state: context.frontend_reports.route_state,
+            size_report: None,
+            clock_skew: context.frontend_reports.clock_skew,
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            form_state: context.frontend_reports.form_state,
+            storage_quota: context.frontend_reports.storage_quota,
+            annotations: serde_json::Map::new(),
+            screenshot_reused: false,
+            screenshot_age_secs: None,
+        }
+    }
+
+    pub fn persist(&self, mut snapshot: DebugSnapshot) -> Result<DebugSnapshot, UseCaseError> {
+        // Extensions supplied by the frontend win over a contributor of the same name.
+        for (name, contributor) in &self.contributors {
+            if snapshot.extensions.contains_key(name) {
+                continue;
+            }
+            if let Some(value) = contributor(&snapshot) {
+                snapshot.extensions.insert(name.clone(), value);
+            }
+        }
+
+        // Taken even when the save fails or is aborted below; the markers in
+        // the log still carry them.
+        let moments = self.moments.as_ref().map(|moments| moments.take_pending());
+        if let Some(moments) = moments.filter(|moments| !moments.is_empty()) {
+            if !snapshot.extensions.contains_key(MOMENT_SNAPSHOT_EXTENSION) {
+                snapshot.extensions.insert(
+                    MOMENT_SNAPSHOT_EXTENSION.to_string(),
+                    serde_json::to_value(moments).unwrap_or_default(),
+                );
+            }
+        }
+
+        if let Some(hook) = &self.before_capture {
+            if !hook(&mut snapshot) {
+                tracing::info!("Debug snapshot aborted by before_capture hook");
+                return Err(UseCaseError::CaptureAborted);
+            }
+        }
+
+        let size_report = measure_capture(&snapshot);
+        warn_oversized(&size_report, &self.size_thresholds);
+        snapshot.size_report = Some(size_report);
 
         let saved_path = self.repository.save_snapshot(&snapshot)?;
 
@@ -164,6 +537,10 @@ impl<R: SnapshotRepository> CaptureDebugSnapshotUseCase<R> {
             "Full debug snapshot captured"
         );
 
+        if let Some(hooks) = &self.hooks {
+            hooks.dispatch(&snapshot, &saved_path);
+        }
+
         Ok(snapshot)
     }
 }
@@ -0,0 +1,157 @@
+use super::{stack_resolver, validate_path_in_directory};
+use crate::adapters::bundle::build_bundle_zip;
+use crate::adapters::storage::WritePolicy;
+use crate::analysis::merge::{merge_bundles_with_progress, BundleManifest, MergeReport};
+use crate::config::sanitize_filename;
+use crate::domain::{
+    decrypt_bundle, encrypt_bundle, EncryptedBundleError, EncryptedBundleExport, ImportedBundle,
+};
+use crate::DebugToolsState;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+pub const MERGE_PROGRESS_EVENT: &str = "debug-tools://merge-progress";
+
+// `out_dir` must lie under `merged/` in the log directory; by default each
+// merge gets its own `merged_<ms>` there.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn merge_debug_bundles<R: Runtime>(
+    app: AppHandle<R>,
+    paths: Vec<String>,
+    out_dir: Option<String>,
+    resolve_stack_traces: Option<bool>,
+) -> Result<MergeReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if paths.is_empty() {
+        return Err("paths must not be empty".to_string());
+    }
+
+    let merged = state.config.merged_bundle_dir();
+    let out_dir = match out_dir {
+        Some(dir) => validate_path_in_directory(&dir, &merged)?,
+        None => merged.join(format!("merged_{}", state.clock.now_millis())),
+    };
+    let paths: Vec<std::path::PathBuf> = paths.into_iter().map(Into::into).collect();
+
+    let resolver = stack_resolver(&state, resolve_stack_traces.unwrap_or(false));
+    merge_bundles_with_progress(&paths, &out_dir, resolver, &mut |progress| {
+        if let Err(e) = app.emit(MERGE_PROGRESS_EVENT, progress) {
+            tracing::warn!(error = %e, "Failed to emit merge progress");
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
+// Zips the plugin's artifacts into a bundle `merge_debug_bundles` can read
+// and seals it with the passphrase, as `exports/debug_bundle_<ms>.zip.enc`.
+// The plaintext zip never touches the disk.
+#[tauri::command]
+#[tracing::instrument(skip(app, passphrase))]
+pub async fn export_encrypted_bundle<R: Runtime>(
+    app: AppHandle<R>,
+    passphrase: String,
+) -> Result<EncryptedBundleExport, EncryptedBundleError> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if passphrase.is_empty() {
+        return Err(EncryptedBundleError::EmptyPassphrase);
+    }
+
+    let created_at = state.clock.now_millis();
+    let manifest = BundleManifest {
+        device_id: None,
+        session_id: Some(state.session_id.clone()),
+        app_name: Some(app.package_info().name.clone()),
+        created_at: Some(created_at),
+    };
+    let archive = build_bundle_zip(&state.config, &state.owned_files, &manifest)
+        .map_err(EncryptedBundleError::io)?;
+    // Argon2 holds 19 MiB for two passes; keep it off the async executor.
+    let (archive, sealed) = tauri::async_runtime::spawn_blocking(move || {
+        let sealed = encrypt_bundle(&passphrase, &archive.zip);
+        (archive, sealed)
+    })
+    .await
+    .map_err(EncryptedBundleError::io)?;
+    let sealed = sealed?;
+
+    let dir = state.config.log_export_dir();
+    std::fs::create_dir_all(&dir).map_err(EncryptedBundleError::io)?;
+    let path = dir.join(format!("debug_bundle_{}.zip.enc", created_at));
+    let policy = WritePolicy::for_config(&state.config);
+    policy
+        .retry(|| policy.replace_file(&path, &sealed))
+        .map_err(EncryptedBundleError::io)?;
+    state.owned_files.register(&path);
+
+    tracing::info!(
+        path = %path.display(),
+        files = archive.files,
+        zip_bytes = archive.zip.len(),
+        "Encrypted debug bundle exported"
+    );
+
+    Ok(EncryptedBundleExport {
+        path,
+        files: archive.files,
+        zip_bytes: archive.zip.len() as u64,
+        encrypted_bytes: sealed.len() as u64,
+    })
+}
+
+// Decrypts a bundle from `export_encrypted_bundle`, from anywhere on disk,
+// into `<out_dir>/<name>.zip`. `out_dir` must lie under `imports/` in the
+// log directory, which is also the default. Nothing is written unless the
+// passphrase checks out.
+#[tauri::command]
+#[tracing::instrument(skip(app, passphrase))]
+pub async fn import_encrypted_bundle<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+    passphrase: String,
+    out_dir: Option<String>,
+) -> Result<ImportedBundle, EncryptedBundleError> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let imports = state.config.bundle_import_dir();
+    let dir = match out_dir {
+        Some(dir) => {
+            validate_path_in_directory(&dir, &imports).map_err(EncryptedBundleError::io)?
+        }
+        None => imports,
+    };
+
+    let source = PathBuf::from(&path);
+    let sealed = std::fs::read(&source).map_err(EncryptedBundleError::io)?;
+    let zip = tauri::async_runtime::spawn_blocking(move || decrypt_bundle(&passphrase, &sealed))
+        .await
+        .map_err(EncryptedBundleError::io)??;
+    let entries = zip::ZipArchive::new(std::io::Cursor::new(zip.as_slice()))
+        .map_err(|e| EncryptedBundleError::io(format!("Decrypted bundle is not a zip: {}", e)))?
+        .len();
+
+    let name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.strip_suffix(".enc").unwrap_or(name))
+        .map(|name| name.strip_suffix(".zip").unwrap_or(name))
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("debug_bundle");
+    std::fs::create_dir_all(&dir).map_err(EncryptedBundleError::io)?;
+    let target = dir.join(format!("{}.zip", sanitize_filename(name)));
+    let policy = WritePolicy::for_config(&state.config);
+    policy
+        .retry(|| policy.replace_file(&target, &zip))
+        .map_err(EncryptedBundleError::io)?;
+    state.owned_files.register(&target);
+
+    tracing::info!(path = %target.display(), entries, "Encrypted debug bundle imported");
+
+    Ok(ImportedBundle {
+        path: target,
+        entries,
+        zip_bytes: zip.len() as u64,
+    })
+}
@@ -0,0 +1,149 @@
+use crate::config::{ActiveCaptureProfile, CaptureProfile, EffectiveConfig};
+use crate::domain::{PrivacyPreset, PrivacyStatus};
+use crate::DebugToolsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, State};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CaptureProfileInfo {
+    pub name: String,
+    pub active: bool,
+    pub profile: CaptureProfile,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_profiles<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<CaptureProfileInfo>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let active_name = state
+        .capture_profile
+        .read()
+        .map(|active| active.name.clone())
+        .map_err(|e| e.to_string())?;
+
+    let mut profiles: Vec<CaptureProfileInfo> = state
+        .config
+        .capture_profiles
+        .iter()
+        .map(|(name, profile)| CaptureProfileInfo {
+            active: *name == active_name,
+            name: name.clone(),
+            profile: profile.clone(),
+        })
+        .collect();
+
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(profiles)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_active_profile<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+) -> Result<ActiveCaptureProfile, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let profile = state
+        .config
+        .capture_profiles
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| format!("Unknown capture profile: {}", name))?;
+
+    let active = ActiveCaptureProfile { name, profile };
+
+    *state.capture_profile.write().map_err(|e| e.to_string())? = active.clone();
+
+    tracing::info!(profile = %active.name, "Capture profile switched");
+
+    state.record_effective_config("set_active_profile");
+
+    Ok(active)
+}
+
+// Settings in force right now, including the privacy preset and the flags
+// it resolved to. Nothing is written to `config_history/`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_debug_config<R: Runtime>(app: AppHandle<R>) -> Result<EffectiveConfig, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    Ok(state.effective_config("get_debug_config"))
+}
+
+// Switches the preset and re-resolves the privacy flags; configured
+// `privacy_overrides` keep winning. `None` returns to the individual flags.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_privacy_preset<R: Runtime>(
+    app: AppHandle<R>,
+    preset: Option<PrivacyPreset>,
+) -> Result<PrivacyStatus, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let status = state.privacy.set_preset(preset);
+    tracing::info!(preset = ?status.preset, "Privacy preset switched");
+    state.record_effective_config("set_privacy_preset");
+
+    Ok(status)
+}
+
+// Fields left out keep their current value; `privacy_preset: null` returns to
+// the individual flags, as with `set_privacy_preset`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DebugConfigUpdate {
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    #[serde(default, deserialize_with = "present_or_null")]
+    pub privacy_preset: Option<Option<PrivacyPreset>>,
+}
+
+// Tells a field set to `null` (`Some(None)`) apart from one left out (`None`).
+fn present_or_null<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+// Applies several runtime settings at once and records a single
+// `config_history/` entry for the change. Nothing is applied when the
+// update names an unknown profile.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_debug_config<R: Runtime>(
+    app: AppHandle<R>,
+    update: DebugConfigUpdate,
+) -> Result<EffectiveConfig, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let profile = match &update.active_profile {
+        Some(name) => Some(ActiveCaptureProfile {
+            name: name.clone(),
+            profile: state
+                .config
+                .capture_profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Unknown capture profile: {}", name))?,
+        }),
+        None => None,
+    };
+
+    if let Some(active) = profile {
+        *state.capture_profile.write().map_err(|e| e.to_string())? = active;
+    }
+    if let Some(preset) = update.privacy_preset {
+        state.privacy.set_preset(preset);
+    }
+
+    let effective = state.record_effective_config("set_debug_config");
+    tracing::info!(profile = %effective.active_profile.name, "Debug config updated");
+
+    Ok(effective)
+}
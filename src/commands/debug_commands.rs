@@ -0,0 +1,243 @@
+use crate::domain::trace_context::{TRACEPARENT_FIELD, TRACE_ID_FIELD};
+use crate::domain::{
+    ConsoleLogEntry, DebugCommandHistory, DebugCommandOutcome, DebugCommandRecord,
+    DebugCommandReply, LogLevel, TraceContext,
+};
+use crate::DebugToolsState;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State, WebviewWindow};
+
+#[tauri::command]
+#[tracing::instrument(skip(app, payload))]
+pub async fn send_debug_command<R: Runtime>(
+    app: AppHandle<R>,
+    command: String,
+    payload: serde_json::Value,
+    traceparent: Option<String>,
+) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+    let request_id = state.debug_commands.next_request_id();
+    let trace = debug_command_trace(traceparent.as_deref(), &request_id)?;
+    let _span = trace.span().entered();
+
+    // The trace rides as a third element so two-element listeners still
+    // destructure the first two.
+    window
+        .emit(
+            "debug-command",
+            (command.clone(), payload, trace.traceparent()),
+        )
+        .map_err(|e| format!("Failed to send debug command: {}", e))?;
+
+    tracing::info!(command = %command, "Debug command sent to frontend");
+
+    state.debug_commands.record(DebugCommandRecord {
+        request_id,
+        command,
+        window_label: window.label().to_string(),
+        sent_at: state.clock.now_millis(),
+        duration_ms: 0,
+        outcome: DebugCommandOutcome::Sent,
+        traceparent: Some(trace.traceparent()),
+    });
+
+    Ok("Command sent to frontend".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugCommandRequest {
+    pub request_id: String,
+    pub command: String,
+    pub payload: serde_json::Value,
+    // For the handler to forward on any request it makes.
+    pub traceparent: String,
+}
+
+// The caller's trace context, or a new trace when there is none.
+fn debug_command_trace(
+    traceparent: Option<&str>,
+    request_id: &str,
+) -> Result<TraceContext, String> {
+    match traceparent.filter(|value| !value.trim().is_empty()) {
+        Some(value) => TraceContext::parse(value),
+        None => Ok(TraceContext::generate(request_id)),
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, payload))]
+pub async fn send_debug_command_with_reply<R: Runtime>(
+    app: AppHandle<R>,
+    command: String,
+    payload: serde_json::Value,
+    timeout_ms: Option<u64>,
+    traceparent: Option<String>,
+) -> Result<DebugCommandOutcome, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+    let timeout = std::time::Duration::from_millis(timeout_ms.unwrap_or(5_000));
+    request_frontend_reply(
+        &state,
+        &window,
+        command,
+        payload,
+        timeout,
+        traceparent.as_deref(),
+    )
+    .await
+}
+
+// Sends `command` to the handler `window` registered for it and waits for
+// the reply; failures are persisted and every request is recorded.
+pub(super) async fn request_frontend_reply<R: Runtime>(
+    state: &DebugToolsState,
+    window: &WebviewWindow<R>,
+    command: String,
+    payload: serde_json::Value,
+    timeout: Duration,
+    traceparent: Option<&str>,
+) -> Result<DebugCommandOutcome, String> {
+    let broker = state.debug_commands.clone();
+    let window_label = window.label().to_string();
+    let sent_at = state.clock.now_millis();
+    let trace = debug_command_trace(traceparent, &format!("{}-{}", command, sent_at))?;
+
+    let (request_id, outcome) = if broker.has_listener(&window_label, &command) {
+        let pending = broker.expect_reply(trace.clone());
+        let request_id = pending.request_id.clone();
+
+        trace.span().in_scope(|| {
+            window
+                .emit(
+                    "debug-command-request",
+                    DebugCommandRequest {
+                        request_id: request_id.clone(),
+                        command: command.clone(),
+                        payload,
+                        traceparent: trace.traceparent(),
+                    },
+                )
+                .map_err(|e| format!("Failed to send debug command: {}", e))
+        })?;
+
+        (request_id.clone(), broker.wait(pending, timeout).await)
+    } else {
+        (
+            broker.next_request_id(),
+            DebugCommandOutcome::MissingListener,
+        )
+    };
+
+    if outcome.is_failure() {
+        trace.span().in_scope(|| {
+            tracing::warn!(command = %command, outcome = ?outcome, "Debug command failed");
+        });
+        persist_debug_command_failure(state, &command, &request_id, &outcome, &trace);
+    }
+
+    broker.record(DebugCommandRecord {
+        request_id,
+        command,
+        window_label,
+        sent_at,
+        duration_ms: (state.clock.now_millis() - sent_at).max(0) as u64,
+        outcome: outcome.clone(),
+        traceparent: Some(trace.traceparent()),
+    });
+
+    Ok(outcome)
+}
+
+fn persist_debug_command_failure(
+    state: &DebugToolsState,
+    command: &str,
+    request_id: &str,
+    outcome: &DebugCommandOutcome,
+    trace: &TraceContext,
+) {
+    let (message, stack_trace) = match outcome {
+        DebugCommandOutcome::HandlerError { error } => (error.message.clone(), error.stack.clone()),
+        DebugCommandOutcome::MissingListener => ("no frontend listener registered".into(), None),
+        DebugCommandOutcome::Timeout { timeout_ms } => {
+            (format!("no reply within {}ms", timeout_ms), None)
+        }
+        DebugCommandOutcome::Sent | DebugCommandOutcome::Ok { .. } => return,
+    };
+
+    let entry = ConsoleLogEntry {
+        timestamp: state.clock.now_millis(),
+        level: LogLevel::Error.to_string(),
+        message: format!("[debug-command] {} failed: {}", command, message),
+        args: serde_json::json!({
+            "source": "debug-command",
+            "command": command,
+            "request_id": request_id,
+            "outcome": outcome,
+        }),
+        stack_trace,
+        fields: Some(serde_json::Map::from_iter([
+            (TRACE_ID_FIELD.to_string(), trace.trace_id.clone().into()),
+            (TRACEPARENT_FIELD.to_string(), trace.traceparent().into()),
+        ])),
+    };
+
+    if let Err(e) = state.append_logs_use_case.execute(vec![entry]) {
+        tracing::error!(error = %e, "Failed to persist debug command failure");
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, reply))]
+pub async fn submit_debug_command_reply<R: Runtime>(
+    app: AppHandle<R>,
+    request_id: String,
+    reply: DebugCommandReply,
+) -> Result<(), String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    // Whatever the plugin logs while resolving carries the command's trace.
+    let _span = state
+        .debug_commands
+        .pending_trace(&request_id)
+        .map(|trace| trace.span().entered());
+    if !state.debug_commands.resolve(&request_id, reply) {
+        return Err(format!("No pending debug command: {}", request_id));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, webview))]
+pub async fn register_debug_command_handlers<R: Runtime>(
+    app: AppHandle<R>,
+    webview: tauri::Webview<R>,
+    commands: Vec<String>,
+) -> Result<(), String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    state
+        .debug_commands
+        .register_listeners(webview.label(), commands);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_debug_command_history<R: Runtime>(
+    app: AppHandle<R>,
+    limit: Option<usize>,
+) -> Result<DebugCommandHistory, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    Ok(state.debug_commands.history(limit.unwrap_or(50)))
+}
@@ -0,0 +1,291 @@
+use super::wait_for_screenshots_plugin;
+use crate::adapters::logging::suppressed_reentrant_events;
+use crate::adapters::screenshots::capture_window_screenshot;
+use crate::analysis::schema_manifest::{artifact_schema_manifest, ArtifactSchemaManifest};
+use crate::application::{build_info, CaptureWebViewStateUseCase, DEFAULT_WINDOW_LABEL};
+use crate::domain::{
+    CaptureBenchmark, DebugEnvironment, DebugStats, DeprecationReport, PluginInitReport,
+    RendererInfo, RepositoryMetricsReport, ShutdownSummary, SnapshotRepository, StorageStatus,
+};
+use crate::DebugToolsState;
+use serde::Deserialize;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_deprecation_report<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<DeprecationReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    Ok(DeprecationReport {
+        compat_mode: state.config.compat_mode,
+        surfaces: state.deprecations.used(),
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_debug_stats<R: Runtime>(app: AppHandle<R>) -> Result<DebugStats, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    state.finish_quiet_mode()?;
+    let now = state.clock.now_secs();
+
+    let (clock_skew, console_flush_interval_ms) = {
+        let reports = state.frontend_reports.lock().map_err(|e| e.to_string())?;
+        (
+            reports.clock_skew.clone(),
+            reports.console_flush_interval_ms,
+        )
+    };
+
+    let quiet_mode = state.quiet_mode.status(now);
+    Ok(DebugStats {
+        suppress_below_level_until: quiet_mode.as_ref().map(|status| status.until),
+        quiet_mode,
+        total_suppressed_entries: state.quiet_mode.total_suppressed(),
+        error_alarm: state
+            .error_alarm
+            .as_ref()
+            .map(|alarm| alarm.status(state.clock.now_millis())),
+        clock_skew,
+        screenshot_queue: state.screenshot_queue.stats(),
+        reentrant_events_suppressed: suppressed_reentrant_events(),
+        console_flush_interval_ms,
+        disk_usage: state.disk_usage.latest(),
+        strict_mode: state.strict_mode.status(),
+        screenshots_plugin: state.screenshots_plugin_status(),
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_repository_metrics<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<RepositoryMetricsReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut metrics = state
+        .repository
+        .metrics()
+        .ok_or_else(|| "Repository metrics are unavailable".to_string())?;
+    metrics.buffer_pool = Some(state.buffer_pool.stats());
+    Ok(metrics)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn export_flamegraph<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    #[cfg(feature = "flamegraph")]
+    {
+        let state: State<'_, DebugToolsState> = app.state();
+
+        state
+            .tracing_guard
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .ok_or_else(|| "Debug tools have been shut down".to_string())?
+            .flush_flamegraph()
+            .map_err(|e| e.to_string())?;
+
+        let timestamp = state.clock.now_secs();
+
+        let path = crate::adapters::logging::render_flamegraph(&state.config, timestamp)
+            .map_err(|e| e.to_string())?;
+
+        tracing::info!(path = %path.display(), "Flamegraph exported");
+
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    #[cfg(not(feature = "flamegraph"))]
+    {
+        let _ = app;
+        Err("Flamegraph export requires the `flamegraph` feature".into())
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_debug_environment<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<DebugEnvironment, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let frontend = state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .renderer
+        .clone();
+
+    Ok(DebugEnvironment {
+        session_id: state.session_id.clone(),
+        build_info: build_info(&app),
+        renderer: RendererInfo {
+            frontend,
+            platform: Some(state.platform_gpu_info()),
+        },
+        storage: state.storage_status(),
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_storage_status<R: Runtime>(app: AppHandle<R>) -> Result<StorageStatus, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    Ok(state.storage_status())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkCaptureRequest {
+    // The DOM lives in the WebView, so the frontend serializes it and the
+    // backend times the save path.
+    #[serde(default)]
+    pub dom_html: Option<String>,
+    #[serde(default)]
+    pub include_screenshot: bool,
+    #[serde(default)]
+    pub persist: bool,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, request))]
+pub async fn benchmark_capture<R: Runtime>(
+    app: AppHandle<R>,
+    request: BenchmarkCaptureRequest,
+) -> Result<CaptureBenchmark, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let elapsed_ms = |started: Instant| started.elapsed().as_secs_f64() * 1000.0;
+    let total = Instant::now();
+
+    let started = Instant::now();
+    let webview_state = CaptureWebViewStateUseCase::execute(&app, DEFAULT_WINDOW_LABEL)
+        .map_err(|e| e.to_string())?;
+    let state_ms = elapsed_ms(started);
+
+    let mut dom_ms = None;
+    let mut dom_snapshot_path = None;
+    if let Some(html) = request.dom_html {
+        let started = Instant::now();
+        if request.persist {
+            let saved = state
+                .save_dom_use_case
+                .execute(
+                    html,
+                    webview_state.url.clone(),
+                    webview_state.title.clone(),
+                    webview_state.viewport.width,
+                    webview_state.viewport.height,
+                    false,
+                )
+                .map_err(|e| e.to_string())?;
+            dom_snapshot_path = Some(saved.path);
+        } else {
+            // A scratch file avoids clobbering a real `dom_<ts>.html` from the same second.
+            let scratch = state.config.benchmark_scratch_path();
+            std::fs::write(&scratch, html).map_err(|e| e.to_string())?;
+            let _ = std::fs::remove_file(&scratch);
+        }
+        dom_ms = Some(elapsed_ms(started));
+    }
+
+    let mut screenshot_ms = None;
+    let mut screenshot_path = None;
+    if request.include_screenshot {
+        let started = Instant::now();
+        wait_for_screenshots_plugin(&state).await?;
+        let path = state
+            .screenshot_queue
+            .capture(&webview_state.title, || {
+                capture_window_screenshot(&app, &webview_state.title)
+            })
+            .await?;
+        screenshot_ms = Some(elapsed_ms(started));
+        screenshot_path = request.persist.then_some(path);
+    }
+
+    let snapshot = state.capture_snapshot_use_case.assemble(
+        &app,
+        webview_state,
+        Vec::new(),
+        screenshot_path,
+        dom_snapshot_path,
+        state.capture_context(),
+    );
+
+    // Without `persist` the snapshot is written to a scratch file and removed,
+    // so save_ms still reflects serialization plus disk I/O.
+    let started = Instant::now();
+    let snapshot_path = if request.persist {
+        let saved = state
+            .repository
+            .save_snapshot(&snapshot)
+            .map_err(|e| e.to_string())?;
+        Some(saved)
+    } else {
+        let scratch = state.config.benchmark_scratch_path();
+        let json = serde_json::to_vec_pretty(&snapshot).map_err(|e| e.to_string())?;
+        std::fs::write(&scratch, json).map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_file(&scratch);
+        None
+    };
+    let save_ms = elapsed_ms(started);
+
+    let benchmark = CaptureBenchmark {
+        state_ms,
+        dom_ms,
+        screenshot_ms,
+        save_ms,
+        total_ms: elapsed_ms(total),
+        persisted: request.persist,
+        snapshot_path,
+    };
+
+    tracing::info!(total_ms = benchmark.total_ms, "Capture benchmark finished");
+
+    Ok(benchmark)
+}
+
+// Also runs on `RunEvent::Exit`. After it, backend tracing no longer reaches
+// the log file.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn shutdown_debug_tools<R: Runtime>(
+    app: AppHandle<R>,
+    export_logs: Option<bool>,
+) -> Result<ShutdownSummary, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let export_logs = export_logs.unwrap_or(state.config.export_logs_on_shutdown);
+    Ok(state.shutdown("command", export_logs))
+}
+
+// Versioned description of every file the plugin writes, with JSON Schemas,
+// for log viewers and other companion tooling.
+#[tauri::command]
+#[tracing::instrument]
+pub async fn get_artifact_schema_manifest() -> Result<ArtifactSchemaManifest, String> {
+    Ok(artifact_schema_manifest())
+}
+
+// Phases that finish in the background are appended as they complete.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_plugin_init_report<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<PluginInitReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    Ok(state.init_report.report())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn clear_strict_violations<R: Runtime>(app: AppHandle<R>) -> Result<u64, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let cleared = state.strict_mode.clear();
+    tracing::info!(cleared, "Strict mode violations cleared");
+    Ok(cleared)
+}
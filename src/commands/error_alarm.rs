@@ -0,0 +1,70 @@
+use crate::application::{CaptureWebViewStateUseCase, DEFAULT_WINDOW_LABEL};
+use crate::domain::ErrorAlarmEvent;
+use crate::DebugToolsState;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+pub const ERROR_ALARM_EVENT: &str = "debug-tools://error-alarm";
+
+pub(crate) async fn handle_error_alarm<R: Runtime>(app: AppHandle<R>, event: ErrorAlarmEvent) {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    tracing::warn!(
+        errors_in_window = event.errors_in_window,
+        window_secs = event.window_secs,
+        "Error alarm fired"
+    );
+
+    if let Err(e) = state
+        .append_logs_use_case
+        .append_marker(event.to_log_entry())
+    {
+        tracing::warn!(error = %e, "Failed to persist error alarm marker");
+    }
+
+    if let Err(e) = app.emit(ERROR_ALARM_EVENT, &event) {
+        tracing::warn!(error = %e, "Failed to emit error alarm");
+    }
+
+    let auto_snapshot = state
+        .config
+        .error_alarm
+        .as_ref()
+        .is_some_and(|alarm| alarm.auto_snapshot);
+    if !auto_snapshot {
+        return;
+    }
+    if !state
+        .quiet_mode
+        .allows_automatic_snapshot(state.clock.now_secs())
+    {
+        tracing::debug!("Error alarm snapshot paused by quiet mode");
+        return;
+    }
+
+    let Some(webview_state) = CaptureWebViewStateUseCase::execute(&app, DEFAULT_WINDOW_LABEL)
+        .ok()
+        .or_else(|| state.cached_webview_state())
+    else {
+        tracing::warn!("Skipping error alarm snapshot: no webview state");
+        return;
+    };
+
+    let use_case = &state.capture_snapshot_use_case;
+    let mut snapshot = use_case.assemble(
+        &app,
+        webview_state,
+        Vec::new(),
+        None,
+        None,
+        state.capture_context(),
+    );
+    snapshot.label = Some("error-alarm".to_string());
+    snapshot.extensions.insert(
+        "error_alarm".to_string(),
+        serde_json::to_value(&event).unwrap_or_default(),
+    );
+
+    if let Err(e) = use_case.persist(snapshot) {
+        tracing::warn!(error = %e, "Failed to save error alarm snapshot");
+    }
+}
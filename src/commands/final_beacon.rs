@@ -0,0 +1,162 @@
+use super::{
+    check_queue_overflow, drop_delivered, privacy_url, write_frontend_entries,
+    ConsoleLogEntryPayload,
+};
+use crate::adapters::filesystem::save_final_beacon;
+use crate::domain::{ConsoleSource, FinalBeaconRecord, FinalBeaconState};
+use crate::DebugToolsState;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+// Scheme of the protocol final beacons are posted to, at `/final-beacon`.
+pub const FINAL_BEACON_SCHEME: &str = "debug-tools";
+const FINAL_BEACON_PATH: &str = "/final-beacon";
+// Browsers cap beacons at 64 KiB; the `fetch` fallback has no such limit.
+const MAX_FINAL_BEACON_BYTES: usize = 8 * 1024 * 1024;
+
+// What a collector sends while its page unloads: the entries it had not
+// delivered, including those of flushes still in flight, and its state.
+#[derive(Debug, Deserialize)]
+pub struct FinalBeacon {
+    pub source: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub entries: Vec<ConsoleLogEntryPayload>,
+    #[serde(default)]
+    pub state: FinalBeaconState,
+    #[serde(default)]
+    pub ingest_source: Option<ConsoleSource>,
+}
+
+// Entries a regular flush already wrote are dropped by `seq`; the rest are
+// marked `final_beacon` and written in order, keeping their timestamps.
+pub(crate) fn accept_final_beacon(
+    state: &DebugToolsState,
+    beacon: FinalBeacon,
+) -> Result<FinalBeaconRecord, String> {
+    let FinalBeacon {
+        source,
+        reason,
+        mut entries,
+        state: mut beacon_state,
+        ingest_source,
+    } = beacon;
+    beacon_state.url = beacon_state.url.map(|url| privacy_url(state, url));
+
+    entries.sort_by_key(|entry| (entry.seq, entry.timestamp));
+    let first_seq = entries.iter().filter_map(|entry| entry.seq).min();
+    let last_seq = entries.iter().filter_map(|entry| entry.seq).max();
+
+    let max_queued = state.config.max_queued_entries.max(1);
+    let overflow = entries.len().saturating_sub(max_queued);
+    if overflow > 0 {
+        check_queue_overflow(state, "final_beacon", overflow as u64, max_queued)?;
+    }
+    entries.drain(..overflow);
+    if overflow > 0 {
+        state
+            .activity
+            .record_dropped_console_entries(overflow as u64);
+    }
+
+    let duplicate_entries = drop_delivered(state, Some(&source), &mut entries);
+    for entry in &mut entries {
+        entry
+            .fields
+            .get_or_insert_with(Default::default)
+            .insert("final_beacon".to_string(), serde_json::Value::Bool(true));
+    }
+    let written_entries = entries.len();
+    if !entries.is_empty() {
+        write_frontend_entries(
+            state,
+            "final_beacon",
+            entries,
+            ingest_source.unwrap_or(ConsoleSource::Host),
+        )?;
+    }
+
+    let record = FinalBeaconRecord {
+        final_beacon: true,
+        source,
+        reason,
+        received_at: state.clock.now_millis(),
+        session_id: Some(state.session_id.clone()),
+        state: beacon_state,
+        first_seq,
+        last_seq,
+        written_entries,
+        duplicate_entries,
+    };
+    let path =
+        save_final_beacon(&state.config, &record, &state.owned_files).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        source = %record.source,
+        written = written_entries,
+        duplicates = duplicate_entries,
+        path = %path.display(),
+        "Final beacon received"
+    );
+
+    Ok(record)
+}
+
+// The same as the bridge's own beacon, for callers that tear down on their
+// own terms and can still await a command.
+#[tauri::command]
+#[tracing::instrument(skip(app, beacon))]
+pub async fn submit_final_beacon<R: Runtime>(
+    app: AppHandle<R>,
+    beacon: FinalBeacon,
+) -> Result<FinalBeaconRecord, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    accept_final_beacon(&state, beacon)
+}
+
+// Handler for `debug-tools://…/final-beacon`, which `sendBeacon` and
+// keepalive `fetch` requests can still reach while the page unloads. The
+// page never reads the response, so failures are only logged.
+pub fn final_beacon_protocol<R: Runtime>(
+    app: &AppHandle<R>,
+    request: tauri::http::Request<Vec<u8>>,
+) -> tauri::http::Response<Vec<u8>> {
+    let respond = |status: u16| {
+        tauri::http::Response::builder()
+            .status(status)
+            .header("Access-Control-Allow-Origin", "*")
+            .header("Access-Control-Allow-Headers", "Content-Type")
+            .body(Vec::new())
+            .unwrap_or_default()
+    };
+
+    if request.method() == tauri::http::Method::OPTIONS {
+        return respond(204);
+    }
+    if request.method() != tauri::http::Method::POST || request.uri().path() != FINAL_BEACON_PATH {
+        return respond(404);
+    }
+    if request.body().len() > MAX_FINAL_BEACON_BYTES {
+        tracing::warn!(bytes = request.body().len(), "Final beacon too large");
+        return respond(413);
+    }
+    let Some(state) = app.try_state::<DebugToolsState>() else {
+        return respond(503);
+    };
+
+    let beacon: FinalBeacon = match serde_json::from_slice(request.body()) {
+        Ok(beacon) => beacon,
+        Err(e) => {
+            tracing::warn!(error = %e, "Malformed final beacon");
+            return respond(400);
+        }
+    };
+    match accept_final_beacon(&state, beacon) {
+        Ok(_) => respond(204),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to write final beacon");
+            respond(500)
+        }
+    }
+}
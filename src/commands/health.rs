@@ -0,0 +1,291 @@
+use super::measure_clock_skew;
+use crate::adapters::health_http::{HttpRequest, HttpResponse};
+use crate::domain::{
+    CaptureContext, ConsoleLogEntry, DebugSnapshot, HealthSummary, LogLevel, LogQuery,
+    SelfTestCheck, SnapshotRepository, ViewportInfo, WebViewState,
+};
+use crate::DebugToolsState;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+const SELF_TEST_PROBE_FILE: &str = ".self_test_probe";
+
+fn self_test_check(check: &str, result: Result<String, String>) -> SelfTestCheck {
+    let passed = result.is_ok();
+    SelfTestCheck {
+        check: check.to_string(),
+        passed,
+        detail: result.unwrap_or_else(|e| e),
+    }
+}
+
+fn self_test_console_log(state: &DebugToolsState, probe_id: &str) -> Result<String, String> {
+    let timestamp = state.clock.now_millis();
+    let message = format!("[debug-tools] self-test probe {}", probe_id);
+
+    state
+        .append_logs_use_case
+        .append_marker(ConsoleLogEntry {
+            timestamp,
+            level: LogLevel::Info.to_string(),
+            message: message.clone(),
+            args: serde_json::Value::Null,
+            stack_trace: None,
+            fields: None,
+        })
+        .map_err(|e| format!("append failed: {}", e))?;
+
+    let found = state
+        .log_query
+        .query_console_logs(&LogQuery {
+            since: Some(timestamp),
+            text: Some(message),
+            limit: Some(1),
+            ..LogQuery::default()
+        })
+        .map_err(|e| format!("read back failed: {}", e))?;
+
+    if found.is_empty() {
+        return Err("entry was written but not found when reading back".into());
+    }
+    Ok("entry written and read back".into())
+}
+
+fn self_test_snapshot<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &DebugToolsState,
+) -> Result<String, String> {
+    let webview_state = WebViewState {
+        url: "about:self-test".to_string(),
+        title: "self-test".to_string(),
+        user_agent: String::new(),
+        viewport: ViewportInfo {
+            width: 1,
+            height: 1,
+        },
+    };
+    let mut snapshot = state.capture_snapshot_use_case.assemble(
+        app,
+        webview_state,
+        Vec::new(),
+        None,
+        None,
+        CaptureContext::default(),
+    );
+    // Snapshot files are named by timestamp in seconds; 0 can never collide
+    // with a real capture.
+    snapshot.timestamp = 0;
+    snapshot.label = Some("self-test".to_string());
+
+    let path = state
+        .repository
+        .save_snapshot(&snapshot)
+        .map_err(|e| format!("save failed: {}", e))?;
+
+    let loaded = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str::<DebugSnapshot>(&json).map_err(|e| e.to_string()));
+    let _ = std::fs::remove_file(&path);
+    state.snapshot_index.forget(&path);
+    state.owned_files.forget([&path]);
+
+    match loaded {
+        Ok(loaded) if loaded.label.as_deref() == Some("self-test") => {
+            Ok(format!("saved and loaded {}", path.display()))
+        }
+        Ok(_) => Err("loaded snapshot does not match what was saved".into()),
+        Err(e) => Err(format!("load failed: {}", e)),
+    }
+}
+
+fn self_test_directory(dir: &std::path::Path) -> Result<String, String> {
+    let probe = dir.join(SELF_TEST_PROBE_FILE);
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    std::fs::write(&probe, b"probe").map_err(|e| format!("create failed: {}", e))?;
+    std::fs::remove_file(&probe).map_err(|e| format!("delete failed: {}", e))?;
+    Ok(format!("{} is writable", dir.display()))
+}
+
+fn self_test_tracing(state: &DebugToolsState) -> Result<String, String> {
+    if !tracing::dispatcher::has_been_set() {
+        return Err("no global tracing subscriber is installed".into());
+    }
+    let log_dir = state
+        .config
+        .backend_log_path()
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_default();
+    if !log_dir.is_dir() {
+        return Err(format!(
+            "backend log directory {} is missing",
+            log_dir.display()
+        ));
+    }
+    Ok("global subscriber installed".into())
+}
+
+// Only meaningful when the frontend reports its own `Date.now()`.
+fn self_test_clock(
+    state: &DebugToolsState,
+    frontend_now_ms: Option<i64>,
+) -> Result<String, String> {
+    let Some(frontend_now_ms) = frontend_now_ms else {
+        return Ok("no frontend time reported; skipped".into());
+    };
+    let measurement = measure_clock_skew(state, frontend_now_ms);
+    if !measurement.within_tolerance {
+        return Err(format!(
+            "backend clock is {}ms ahead of the frontend (tolerance {}ms)",
+            measurement.skew_ms, state.config.clock_skew_tolerance_ms
+        ));
+    }
+    Ok(format!(
+        "clocks agree within {}ms",
+        measurement.skew_ms.unsigned_abs()
+    ))
+}
+
+// Fails while a strict mode violation is uncleared.
+fn self_test_strict_mode(state: &DebugToolsState) -> Result<String, String> {
+    let status = state.strict_mode.status();
+    if !status.enabled {
+        return Ok("strict mode off".into());
+    }
+    match status.violations.last() {
+        Some(latest) => Err(format!(
+            "{} violation(s); latest: {} (non-strict: {})",
+            status.total_violations, latest.message, latest.non_strict_behavior
+        )),
+        None => Ok("no violations".into()),
+    }
+}
+
+fn self_test_checks<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &DebugToolsState,
+    frontend_now_ms: Option<i64>,
+) -> Vec<SelfTestCheck> {
+    let probe_id = format!("{}-{}", state.session_id, state.ids.next_sequence());
+
+    let mut checks = vec![
+        self_test_check(
+            "console_log_roundtrip",
+            self_test_console_log(state, &probe_id),
+        ),
+        self_test_check("snapshot_roundtrip", self_test_snapshot(app, state)),
+    ];
+
+    let config = &state.config;
+    let paths = config.artifact_paths();
+    let mut dirs = vec![
+        ("log_dir", config.log_dir.clone()),
+        ("screenshot_dir", paths.screenshots.clone()),
+        ("dom_snapshot_dir", paths.dom_snapshots.clone()),
+        ("config_history_dir", paths.config_history.clone()),
+        ("log_export_dir", paths.exports.clone()),
+    ];
+    // Only overrides; otherwise these are `log_dir` itself.
+    for (name, dir) in [
+        ("console_log_dir", &paths.console_logs),
+        ("snapshot_dir", &paths.snapshots),
+        ("network_log_dir", &paths.network_logs),
+        ("backend_log_dir", &paths.backend_logs),
+        ("annotation_dir", &paths.annotations),
+        ("legal_hold_dir", &paths.legal_holds),
+    ] {
+        if *dir != config.log_dir {
+            dirs.push((name, dir.clone()));
+        }
+    }
+    for (name, dir) in dirs {
+        checks.push(self_test_check(
+            &format!("probe_file:{}", name),
+            self_test_directory(&dir),
+        ));
+    }
+
+    checks.push(self_test_check("tracing", self_test_tracing(state)));
+    checks.push(self_test_check(
+        "clock_sanity",
+        self_test_clock(state, frontend_now_ms),
+    ));
+    checks.push(self_test_check("strict_mode", self_test_strict_mode(state)));
+    checks
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn run_self_test<R: Runtime>(
+    app: AppHandle<R>,
+    frontend_now_ms: Option<i64>,
+) -> Result<Vec<SelfTestCheck>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let checks = self_test_checks(&app, &state, frontend_now_ms);
+
+    let failed = checks.iter().filter(|check| !check.passed).count();
+    if failed > 0 {
+        tracing::warn!(failed, "Self-test found problems");
+    } else {
+        tracing::info!("Self-test passed");
+    }
+
+    if let Ok(mut last) = state.last_self_test.lock() {
+        *last = Some(HealthSummary {
+            healthy: failed == 0,
+            failed,
+            session_id: state.session_id.clone(),
+            checked_at: Some(state.clock.now_millis()),
+            checks: checks.clone(),
+        });
+    }
+
+    Ok(checks)
+}
+
+const DEFAULT_LAST_ERRORS: usize = 20;
+const MAX_LAST_ERRORS: usize = 500;
+
+// Routes for the health HTTP server. Polling must not write anything, so
+// `/healthz` reports the last `run_self_test` result instead of running the
+// checks, answering 503 when one of them failed so plain HTTP monitors can
+// alert on the status. Before any self-test it only says the plugin is up.
+pub(crate) fn serve_health_request<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &HttpRequest,
+) -> HttpResponse {
+    let Some(state) = app.try_state::<DebugToolsState>() else {
+        return HttpResponse::error(503, "plugin is not initialized");
+    };
+
+    match request.path.as_str() {
+        "/healthz" => {
+            let last = state
+                .last_self_test
+                .lock()
+                .map(|last| last.clone())
+                .unwrap_or_default();
+            let summary = last.unwrap_or_else(|| HealthSummary {
+                healthy: true,
+                failed: 0,
+                session_id: state.session_id.clone(),
+                checked_at: None,
+                checks: Vec::new(),
+            });
+            HttpResponse::json(if summary.healthy { 200 } else { 503 }, &summary)
+        }
+        "/last-errors" => {
+            let limit = match request.query.get("limit") {
+                None => DEFAULT_LAST_ERRORS,
+                Some(limit) => match limit.parse::<usize>() {
+                    Ok(limit) => limit.min(MAX_LAST_ERRORS),
+                    Err(_) => return HttpResponse::error(400, "limit must be a number"),
+                },
+            };
+            match state.log_query.query_errors(limit) {
+                Ok(entries) => HttpResponse::json(200, &entries),
+                Err(e) => HttpResponse::error(500, &e.to_string()),
+            }
+        }
+        _ => HttpResponse::error(404, "unknown endpoint"),
+    }
+}
@@ -0,0 +1,1052 @@
+use super::{
+    active_capture_profile, note_legacy_use, sanitize_output_name, validate_artifact_path,
+};
+use crate::adapters::filesystem::{
+    append_network_logs as append_network_logs_file, export_console_logs,
+};
+use crate::adapters::log_query::tail_console_log;
+use crate::adapters::log_repair::{is_repaired_copy, repair_console_log as repair_log_file};
+use crate::adapters::plugin_log::tail_plugin_log as tail_plugin_log_file;
+use crate::analysis::sourcemap::StackResolver;
+use crate::domain::annotations::{validate_annotation, LOG_SEQ_FIELD};
+use crate::domain::console_sources::tag_ingest_source;
+use crate::domain::moments::{DEBUG_MOMENT_FIELD, MAX_MOMENT_LABEL_CHARS, MOMENT_TRACING_TARGET};
+use crate::domain::privacy::strip_url_query;
+use crate::domain::{
+    dedup_entries, parse_imported_entry, AnnotationError, AppendLogsResponse, AppendLogsResult,
+    BodyCaptureRule, ClockSkewMeasurement, ConsoleLogEntry, ConsoleLogTail, ConsoleSource,
+    DebugMoment, DeduplicatedLog, ErrorRateReport, ImportSkippedLine, LifecycleEvent,
+    LifecycleEventKind, LogAnnotation, LogExportFormat, LogExportResult, LogFieldCount,
+    LogHistogramBucket, LogImportProgress, LogImportResult, LogLevel, LogPipelineStats, LogQuery,
+    LogRepairReport, NetworkLogEntry, PluginLogLine, QuietModeStatus, StrictViolationKind,
+    DEFAULT_MAX_BODY_BYTES, MAX_BODY_BYTES_LIMIT,
+};
+use crate::DebugToolsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsoleLogEntryPayload {
+    pub timestamp: i64,
+    pub level: String,
+    pub message: String,
+    pub args: serde_json::Value,
+    pub stack_trace: Option<String>,
+    #[serde(default)]
+    pub fields: Option<serde_json::Map<String, serde_json::Value>>,
+    // Position in the sending collector's stream, for dropping entries that
+    // arrive twice. Not written to the log.
+    #[serde(default)]
+    pub seq: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogDirectoryInfo {
+    pub base_dir: String,
+    pub frontend_log: String,
+    pub backend_log: String,
+    pub screenshot_dir: String,
+    pub dom_snapshot_dir: String,
+    // tauri-plugin-log's file, when `plugin_log_file_name` is set.
+    #[serde(default)]
+    pub plugin_log: Option<String>,
+    // Where console logs and snapshots go; `base_dir` unless overridden.
+    #[serde(default)]
+    pub console_log_dir: String,
+    #[serde(default)]
+    pub snapshot_dir: String,
+    #[serde(default)]
+    pub network_log_dir: String,
+    #[serde(default)]
+    pub config_history_dir: String,
+    #[serde(default)]
+    pub recording_dir: String,
+    #[serde(default)]
+    pub export_dir: String,
+    #[serde(default)]
+    pub annotation_dir: String,
+    #[serde(default)]
+    pub legal_holds: String,
+    // Config names of the artifact root overrides in effect.
+    #[serde(default)]
+    pub overridden_roots: Vec<String>,
+}
+
+const DEFAULT_CONSOLE_LOG_TAIL_ENTRIES: usize = 200;
+const MAX_CONSOLE_LOG_TAIL_ENTRIES: usize = 10_000;
+
+// Reads back what `append_debug_logs` persisted this session: the newest
+// `limit` entries, skipping the newest `offset` first, oldest first.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_console_logs<R: Runtime>(
+    app: AppHandle<R>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<ConsoleLogTail, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let path = state
+        .config
+        .frontend_log_path(&app.package_info().name, state.ids.process_id());
+    let limit = limit
+        .unwrap_or(DEFAULT_CONSOLE_LOG_TAIL_ENTRIES)
+        .min(MAX_CONSOLE_LOG_TAIL_ENTRIES);
+    let tail = tail_console_log(&path, limit, offset.unwrap_or(0))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    if tail.malformed_lines > 0 {
+        tracing::warn!(
+            malformed_lines = tail.malformed_lines,
+            path = %path.display(),
+            "Skipped malformed console log lines"
+        );
+    }
+    Ok(tail)
+}
+
+// `source` identifies the sending collector; with it, entries whose `seq`
+// was already written are dropped. `ingest_source` names the pathway and
+// defaults to `host`.
+#[tauri::command]
+#[tracing::instrument(skip(app, logs))]
+pub async fn append_debug_logs<R: Runtime>(
+    app: AppHandle<R>,
+    mut logs: Vec<ConsoleLogEntryPayload>,
+    dropped: Option<u64>,
+    flush_interval_ms: Option<u64>,
+    source: Option<String>,
+    ingest_source: Option<ConsoleSource>,
+) -> Result<AppendLogsResponse, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if let Some(interval) = flush_interval_ms {
+        if let Ok(mut reports) = state.frontend_reports.lock() {
+            reports.console_flush_interval_ms = Some(interval);
+        }
+    }
+
+    // `dropped` is what the frontend queue lost since its last flush.
+    let max_queued = state.config.max_queued_entries.max(1);
+    let overflow = logs.len().saturating_sub(max_queued);
+    let total_dropped = dropped.unwrap_or(0) + overflow as u64;
+    if total_dropped > 0 {
+        check_queue_overflow(
+            &state,
+            "append_debug_logs",
+            total_dropped,
+            logs.len() - overflow,
+        )?;
+    }
+    logs.drain(..overflow);
+    if total_dropped > 0 {
+        state.activity.record_dropped_console_entries(total_dropped);
+        tracing::warn!(
+            dropped = total_dropped,
+            "Console log entries dropped before they were written"
+        );
+    }
+    let duplicates = drop_delivered(&state, source.as_deref(), &mut logs);
+
+    let (path, deduplicated) = write_frontend_entries(
+        &state,
+        "append_debug_logs",
+        logs,
+        ingest_source.unwrap_or(ConsoleSource::Host),
+    )?;
+
+    if state.config.compat_mode {
+        note_legacy_use(
+            &state,
+            "append_debug_logs (path string response)",
+            "append_debug_logs (AppendLogsResult response, compat_mode off)",
+        );
+        return Ok(AppendLogsResponse::LegacyPath(path));
+    }
+
+    Ok(AppendLogsResponse::Result(AppendLogsResult {
+        path,
+        deduplicated,
+        dropped: overflow,
+        duplicates,
+    }))
+}
+
+// Drops the entries `source` already delivered, by `seq`. Entries without
+// one always pass.
+pub(super) fn drop_delivered(
+    state: &DebugToolsState,
+    source: Option<&str>,
+    logs: &mut Vec<ConsoleLogEntryPayload>,
+) -> usize {
+    let Some(source) = source else {
+        return 0;
+    };
+    let seqs: Vec<u64> = logs.iter().filter_map(|entry| entry.seq).collect();
+    let mut fresh = state.delivered_seqs.claim(source, &seqs).into_iter();
+
+    let before = logs.len();
+    logs.retain(|entry| entry.seq.is_none() || fresh.next().unwrap_or(true));
+    before - logs.len()
+}
+
+// The write path shared by regular flushes and final beacons.
+// `kept` is how many entries non-strict mode would still have written.
+pub(super) fn check_queue_overflow(
+    state: &DebugToolsState,
+    command: &str,
+    dropped: u64,
+    kept: usize,
+) -> Result<(), String> {
+    state.strict_mode.check(
+        StrictViolationKind::QueueOverflow,
+        command,
+        format!(
+            "{} console entries overflowed the queue (max_queued_entries {})",
+            dropped, state.config.max_queued_entries
+        ),
+        format!("drop them and write the remaining {} entries", kept),
+        state.clock.now_millis(),
+    )
+}
+
+// Caps oversized field values, failing under strict mode when any had to be
+// replaced by a marker.
+pub(super) fn cap_fields_strictly(
+    state: &DebugToolsState,
+    command: &str,
+    entries: &mut [ConsoleLogEntry],
+) -> Result<(), String> {
+    let substituted = state.config.cap_log_fields(entries);
+    if substituted == 0 {
+        return Ok(());
+    }
+    state.strict_mode.check(
+        StrictViolationKind::SerializationSubstitution,
+        command,
+        format!(
+            "{} log field values exceeded max_log_field_value_bytes ({})",
+            substituted, state.config.max_log_field_value_bytes
+        ),
+        "replace each with a {\"truncated\": true, \"size_bytes\": n} marker",
+        state.clock.now_millis(),
+    )
+}
+
+pub(super) fn write_frontend_entries(
+    state: &DebugToolsState,
+    command: &str,
+    logs: Vec<ConsoleLogEntryPayload>,
+    ingest_source: ConsoleSource,
+) -> Result<(String, Vec<DeduplicatedLog>), String> {
+    let mut entries: Vec<ConsoleLogEntry> = logs
+        .into_iter()
+        .map(|p| ConsoleLogEntry {
+            timestamp: p.timestamp,
+            level: p.level,
+            message: p.message,
+            args: p.args,
+            stack_trace: p.stack_trace,
+            fields: p.fields,
+        })
+        .collect();
+
+    // Before the error rate and alarm, which should count a call once however
+    // many pathways captured it.
+    let cross_source = state.console_dedup.filter(ingest_source, &mut entries);
+    if cross_source > 0 {
+        tracing::debug!(
+            dropped = cross_source,
+            source = ingest_source.as_str(),
+            "Dropped console entries another pathway already delivered"
+        );
+    }
+    tag_ingest_source(&mut entries, ingest_source);
+
+    if state.config.adjust_frontend_timestamps {
+        adjust_timestamps(state, &mut entries);
+    }
+    cap_fields_strictly(state, command, &mut entries)?;
+    active_capture_profile(state).redact_logs(&mut entries);
+    state.error_rate.record(&entries, state.clock.now_secs());
+    if let Some(alarm) = &state.error_alarm {
+        alarm.observe_entries(&entries, state.clock.now_millis());
+    }
+
+    // After the error rate and alarm, which count every occurrence.
+    state.privacy.sample_logs(&mut entries);
+    let deduplicated = if state.config.dedup_console_logs {
+        dedup_entries(&mut entries)
+    } else {
+        Vec::new()
+    };
+
+    let path = state
+        .append_logs_use_case
+        .execute(entries)
+        .map_err(|e| e.to_string())?;
+
+    Ok((path, deduplicated))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_log_stats<R: Runtime>(app: AppHandle<R>) -> Result<LogPipelineStats, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let config = &state.config;
+
+    // The frontend relies on min <= base <= max.
+    let max_flush_interval_ms = config.max_flush_interval_ms.max(1);
+    let min_flush_interval_ms = config.min_flush_interval_ms.min(max_flush_interval_ms);
+    Ok(LogPipelineStats {
+        flush_interval_ms: config
+            .flush_interval_ms
+            .clamp(min_flush_interval_ms, max_flush_interval_ms),
+        min_flush_interval_ms,
+        max_flush_interval_ms,
+        flush_byte_threshold: config.flush_byte_threshold.max(1),
+        flush_burst_entries_per_sec: config.flush_burst_entries_per_sec.max(1),
+        flush_batch_size: config.flush_batch_size.max(1),
+        max_queued_entries: config.max_queued_entries.max(1),
+        dropped_entries: state.activity.dropped_console_entries(),
+        console_sources: state.console_dedup.authoritative().to_vec(),
+        cross_source_duplicates: state.console_dedup.dropped(),
+    })
+}
+
+fn adjust_timestamps(state: &DebugToolsState, entries: &mut [ConsoleLogEntry]) {
+    let skew_ms = match state.frontend_reports.lock() {
+        Ok(reports) => reports.clock_skew.as_ref().map(|skew| skew.skew_ms),
+        Err(_) => None,
+    };
+    let Some(skew_ms) = skew_ms.filter(|skew| *skew != 0) else {
+        return;
+    };
+
+    for entry in entries {
+        entry
+            .fields
+            .get_or_insert_with(Default::default)
+            .insert("original_timestamp".to_string(), entry.timestamp.into());
+        entry.timestamp = entry.timestamp.saturating_add(skew_ms);
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn check_clock_skew<R: Runtime>(
+    app: AppHandle<R>,
+    frontend_timestamp: i64,
+) -> Result<ClockSkewMeasurement, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let measurement = measure_clock_skew(&state, frontend_timestamp);
+    if measurement.within_tolerance {
+        tracing::debug!(skew_ms = measurement.skew_ms, "Clock skew measured");
+    } else {
+        tracing::warn!(
+            skew_ms = measurement.skew_ms,
+            tolerance_ms = state.config.clock_skew_tolerance_ms,
+            "Frontend and backend clocks disagree"
+        );
+    }
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clock_skew = Some(measurement.clone());
+    if !measurement.within_tolerance {
+        state.strict_mode.check(
+            StrictViolationKind::ClockSkew,
+            "check_clock_skew",
+            format!(
+                "backend clock is {}ms off the frontend's (tolerance {}ms)",
+                measurement.skew_ms, state.config.clock_skew_tolerance_ms
+            ),
+            if state.config.adjust_frontend_timestamps {
+                "shift later frontend timestamps by the measured skew"
+            } else {
+                "report the measurement and keep frontend timestamps as sent"
+            },
+            state.clock.now_millis(),
+        )?;
+    }
+
+    Ok(measurement)
+}
+
+pub(super) fn measure_clock_skew(
+    state: &DebugToolsState,
+    frontend_timestamp: i64,
+) -> ClockSkewMeasurement {
+    let backend_timestamp = state.clock.now_millis();
+    let skew_ms = backend_timestamp - frontend_timestamp;
+    ClockSkewMeasurement {
+        frontend_timestamp,
+        backend_timestamp,
+        skew_ms,
+        within_tolerance: skew_ms.unsigned_abs() <= state.config.clock_skew_tolerance_ms,
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn query_console_logs<R: Runtime>(
+    app: AppHandle<R>,
+    query: Option<LogQuery>,
+) -> Result<Vec<ConsoleLogEntry>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut query = query.unwrap_or_default();
+    query.limit = Some(query.limit.unwrap_or(500));
+    resolve_moment_range(&state, &mut query)?;
+
+    let mut entries = state
+        .log_query
+        .query_console_logs(&query)
+        .map_err(|e| e.to_string())?;
+    state.annotations.attach(&mut entries);
+    Ok(entries)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_log_histogram<R: Runtime>(
+    app: AppHandle<R>,
+    bucket_ms: Option<i64>,
+    query: Option<LogQuery>,
+) -> Result<Vec<LogHistogramBucket>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut query = query.unwrap_or_default();
+    resolve_moment_range(&state, &mut query)?;
+    state
+        .log_query
+        .histogram(bucket_ms.unwrap_or(60_000), &query)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_log_field_counts<R: Runtime>(
+    app: AppHandle<R>,
+    field: String,
+    query: Option<LogQuery>,
+) -> Result<Vec<LogFieldCount>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut query = query.unwrap_or_default();
+    resolve_moment_range(&state, &mut query)?;
+    state
+        .log_query
+        .count_by_field(&field, &query)
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportFilteredLogsRequest {
+    #[serde(default)]
+    pub level: Option<LogLevel>,
+    // Milliseconds since epoch; `to_ts` is exclusive.
+    #[serde(default)]
+    pub from_ts: Option<i64>,
+    #[serde(default)]
+    pub to_ts: Option<i64>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    // Moment ids from `mark_debug_moment`, narrowing the range further.
+    #[serde(default)]
+    pub from_moment: Option<String>,
+    #[serde(default)]
+    pub to_moment: Option<String>,
+    pub output_name: String,
+    #[serde(default)]
+    pub format: LogExportFormat,
+    // Rewrite stack frames through `sourcemap_dir`; frames without a
+    // matching map are exported unchanged.
+    #[serde(default)]
+    pub resolve_stack_traces: bool,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn export_filtered_logs<R: Runtime>(
+    app: AppHandle<R>,
+    request: ExportFilteredLogsRequest,
+) -> Result<LogExportResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let file_name = sanitize_output_name(&request.output_name, request.format.extension())?;
+    let path = state.config.log_export_dir().join(file_name);
+
+    let mut query = LogQuery {
+        min_level: request.level,
+        since: request.from_ts,
+        until: request.to_ts,
+        text: request.pattern,
+        from_moment: request.from_moment,
+        to_moment: request.to_moment,
+        ..LogQuery::default()
+    };
+    resolve_moment_range(&state, &mut query)?;
+
+    export_console_logs(
+        state.log_query.as_ref(),
+        &query,
+        &path,
+        request.format,
+        &state.config.export_field_columns,
+        stack_resolver(&state, request.resolve_stack_traces),
+        &state.annotations,
+    )
+    .map_err(|e| e.to_string())
+}
+
+// The configured resolver when an export asked for resolution. Without a
+// `sourcemap_dir` the export goes ahead unresolved.
+pub(super) fn stack_resolver(state: &DebugToolsState, requested: bool) -> Option<&StackResolver> {
+    if !requested {
+        return None;
+    }
+    let resolver = state.stack_resolver.as_deref();
+    if resolver.is_none() {
+        tracing::warn!("resolve_stack_traces requested but no sourcemap_dir is configured");
+    }
+    resolver
+}
+
+pub const LOG_IMPORT_PROGRESS_EVENT: &str = "debug-tools://log-import-progress";
+// Files above this size report progress after every batch.
+const IMPORT_PROGRESS_THRESHOLD_BYTES: u64 = 4 * 1024 * 1024;
+const IMPORT_BATCH_ENTRIES: usize = 500;
+const MAX_REPORTED_SKIPS: usize = 100;
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn import_console_logs<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+    source_label: String,
+) -> Result<LogImportResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let source_label = source_label.trim();
+    if source_label.is_empty() {
+        return Err("source_label must not be empty".to_string());
+    }
+
+    let path = validate_artifact_path(&path, &state.config)?;
+    let file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+    let metadata = file.metadata().map_err(|e| e.to_string())?;
+    if !metadata.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+    let total_bytes = metadata.len();
+    let report_progress = total_bytes > IMPORT_PROGRESS_THRESHOLD_BYTES;
+
+    let mut result = LogImportResult {
+        path: path.clone(),
+        source_label: source_label.to_string(),
+        imported: 0,
+        skipped: 0,
+        skipped_lines: Vec::new(),
+    };
+    let mut bytes_read = 0u64;
+    let mut batch = Vec::with_capacity(IMPORT_BATCH_ENTRIES);
+    let mut reader = std::io::BufReader::new(file);
+    let mut line = String::new();
+    let mut number = 0usize;
+
+    loop {
+        line.clear();
+        let read =
+            std::io::BufRead::read_line(&mut reader, &mut line).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        bytes_read += read as u64;
+        number += 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_imported_entry(line.trim_end(), source_label, &state.session_id) {
+            Ok(entry) => batch.push(entry),
+            Err(reason) => {
+                result.skipped += 1;
+                if result.skipped_lines.len() < MAX_REPORTED_SKIPS {
+                    result.skipped_lines.push(ImportSkippedLine {
+                        line: number,
+                        reason,
+                    });
+                }
+            }
+        }
+
+        if batch.len() >= IMPORT_BATCH_ENTRIES {
+            result.imported += append_imported(&state, &mut batch)?;
+            if report_progress {
+                emit_import_progress(&app, &result, bytes_read, total_bytes);
+            }
+        }
+    }
+
+    result.imported += append_imported(&state, &mut batch)?;
+    if report_progress {
+        emit_import_progress(&app, &result, bytes_read, total_bytes);
+    }
+
+    tracing::info!(
+        path = %path.display(),
+        source = %result.source_label,
+        imported = result.imported,
+        skipped = result.skipped,
+        "Console logs imported"
+    );
+
+    Ok(result)
+}
+
+// Imported entries are historical, so they bypass the error rate and alarm
+// but get the same field caps and redaction as live ones.
+fn append_imported(
+    state: &DebugToolsState,
+    batch: &mut Vec<ConsoleLogEntry>,
+) -> Result<usize, String> {
+    if batch.is_empty() {
+        return Ok(0);
+    }
+
+    let mut entries = std::mem::take(batch);
+    let count = entries.len();
+    tag_ingest_source(&mut entries, ConsoleSource::Import);
+    cap_fields_strictly(state, "import_console_logs", &mut entries)?;
+    active_capture_profile(state).redact_logs(&mut entries);
+    state
+        .append_logs_use_case
+        .execute(entries)
+        .map_err(|e| e.to_string())?;
+    Ok(count)
+}
+
+fn emit_import_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    result: &LogImportResult,
+    bytes_read: u64,
+    total_bytes: u64,
+) {
+    let progress = LogImportProgress {
+        path: result.path.clone(),
+        bytes_read,
+        total_bytes,
+        imported: result.imported,
+        skipped: result.skipped,
+    };
+    if let Err(e) = app.emit(LOG_IMPORT_PROGRESS_EVENT, &progress) {
+        tracing::warn!(error = %e, "Failed to emit log import progress");
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, webview))]
+pub async fn record_visibility_change<R: Runtime>(
+    app: AppHandle<R>,
+    webview: tauri::Webview<R>,
+    state: String,
+) -> Result<LifecycleEvent, String> {
+    let kind = match state.as_str() {
+        "visible" => LifecycleEventKind::Visible,
+        "hidden" => LifecycleEventKind::Hidden,
+        other => return Err(format!("Unknown visibility state: {}", other)),
+    };
+
+    let debug_state: State<'_, DebugToolsState> = app.state();
+
+    Ok(debug_state.record_lifecycle(kind, Some(webview.label().to_string()), None))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_error_rate<R: Runtime>(
+    app: AppHandle<R>,
+    windows: Option<usize>,
+) -> Result<ErrorRateReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let windows = windows.unwrap_or(state.config.error_rate_window_count);
+    Ok(state.error_rate.report(state.clock.now_secs(), windows))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_body_capture<R: Runtime>(
+    app: AppHandle<R>,
+    rule: Option<BodyCaptureRule>,
+) -> Result<Option<BodyCaptureRule>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if let Some(rule) = &rule {
+        if rule.url_pattern.trim().is_empty() {
+            return Err("url_pattern must not be empty".into());
+        }
+        if rule.max_body_bytes == 0 || rule.max_body_bytes > MAX_BODY_BYTES_LIMIT {
+            return Err(format!(
+                "max_body_bytes must be between 1 and {}",
+                MAX_BODY_BYTES_LIMIT
+            ));
+        }
+    }
+
+    let mut current = state
+        .body_capture
+        .write()
+        .map_err(|_| "Body capture lock poisoned".to_string())?;
+    *current = rule.clone();
+
+    tracing::info!(
+        url_pattern = rule.as_ref().map(|r| r.url_pattern.as_str()),
+        "Body capture rule updated"
+    );
+
+    Ok(rule)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_body_capture<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Option<BodyCaptureRule>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    state
+        .body_capture
+        .read()
+        .map(|rule| rule.clone())
+        .map_err(|_| "Body capture lock poisoned".to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, entries))]
+pub async fn append_network_logs<R: Runtime>(
+    app: AppHandle<R>,
+    mut entries: Vec<NetworkLogEntry>,
+) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let profile = active_capture_profile(&state);
+
+    // The frontend already caps bodies; enforce the limit here as well since
+    // the rule may have changed while a request was in flight.
+    let max_body_bytes = state
+        .body_capture
+        .read()
+        .ok()
+        .and_then(|rule| rule.as_ref().map(|rule| rule.max_body_bytes))
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES);
+
+    let privacy = state.privacy.settings();
+    for entry in &mut entries {
+        if privacy.strip_url_queries {
+            strip_url_query(&mut entry.url);
+        }
+        if !privacy.capture_bodies {
+            entry.request_body = None;
+            entry.response_body = None;
+        }
+        for body in [&mut entry.request_body, &mut entry.response_body]
+            .into_iter()
+            .flatten()
+        {
+            body.truncate_to(max_body_bytes);
+            profile.redact_text(&mut body.body);
+        }
+    }
+
+    let app_name = app.package_info().name.clone();
+    append_network_logs_file(
+        &state.config,
+        &app_name,
+        state.ids.process_id(),
+        &entries,
+        &state.activity,
+        &state.owned_files,
+    )
+    .map(|path| path.to_string_lossy().into_owned())
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_log_directory<R: Runtime>(app: AppHandle<R>) -> Result<LogDirectoryInfo, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let app_name = app.package_info().name.clone();
+    let pid = state.ids.process_id();
+    let paths = state.config.artifact_paths();
+    let display = |path: &std::path::Path| path.to_string_lossy().into_owned();
+
+    Ok(LogDirectoryInfo {
+        base_dir: display(&paths.log_dir),
+        frontend_log: display(&paths.console_log_file(&app_name, pid)),
+        backend_log: display(&paths.backend_log_file()),
+        screenshot_dir: display(&paths.screenshots),
+        dom_snapshot_dir: display(&paths.dom_snapshots),
+        plugin_log: state.config.plugin_log_path().as_deref().map(display),
+        console_log_dir: display(&paths.console_logs),
+        snapshot_dir: display(&paths.snapshots),
+        network_log_dir: display(&paths.network_logs),
+        config_history_dir: display(&paths.config_history),
+        recording_dir: display(&paths.recordings),
+        export_dir: display(&paths.exports),
+        annotation_dir: display(&paths.annotations),
+        legal_holds: display(&paths.legal_holds_file()),
+        overridden_roots: paths.overridden,
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_quiet_mode<R: Runtime>(
+    app: AppHandle<R>,
+    level: String,
+    duration_secs: u64,
+) -> Result<QuietModeStatus, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let level = LogLevel::from_str_lenient(&level)
+        .ok_or_else(|| format!("Unknown log level: {}", level))?;
+
+    let status = state
+        .append_logs_use_case
+        .set_quiet_mode(level, duration_secs)
+        .map_err(|e| e.to_string())?;
+
+    state.record_effective_config("set_quiet_mode");
+
+    Ok(status)
+}
+
+pub const FLUSH_LOGS_EVENT: &str = "debug-tools://flush-logs";
+
+// Sends pending forwarded backend events and asks every webview's console
+// collector to flush its queue now, regardless of the adaptive interval.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn flush_debug_logs<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if let Some(forwarder) = &state.forwarder {
+        forwarder.flush();
+    }
+    app.emit(FLUSH_LOGS_EVENT, ()).map_err(|e| e.to_string())
+}
+
+// Copies the parseable entries of a torn console log into a `.repaired.jsonl`
+// next to it; queries read the copy from then on. The original is kept as is.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn repair_console_log<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+) -> Result<LogRepairReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let path = validate_artifact_path(&path, &state.config)?;
+    let is_repaired = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(is_repaired_copy);
+    if is_repaired {
+        return Err(format!("Already a repaired copy: {}", path.display()));
+    }
+    if !path.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+
+    let report = repair_log_file(
+        &state.config,
+        &path,
+        &state.owned_files,
+        state.clock.now_millis(),
+    )
+    .map_err(|e| e.to_string())?;
+    tracing::info!(
+        path = %path.display(),
+        kept = report.kept_entries,
+        discarded_lines = report.discarded_lines,
+        "Console log checked"
+    );
+
+    Ok(report)
+}
+
+// Notes one entry of this session's console log by its `log_seq`. The log is
+// never rewritten; annotating a seq again replaces its annotation.
+#[tauri::command]
+#[tracing::instrument(skip(app, note))]
+pub async fn annotate_log_entry<R: Runtime>(
+    app: AppHandle<R>,
+    seq: u64,
+    note: String,
+    tag: Option<String>,
+) -> Result<LogAnnotation, AnnotationError> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let tag = tag
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty());
+    validate_annotation(&note, tag.as_deref())?;
+
+    let query = LogQuery {
+        field_equals: [(LOG_SEQ_FIELD.to_string(), seq.into())]
+            .into_iter()
+            .collect(),
+        limit: Some(1),
+        ..LogQuery::default()
+    };
+    let found = state
+        .log_query
+        .query_console_logs(&query)
+        .map_err(AnnotationError::storage)?;
+    if found.is_empty() {
+        return Err(AnnotationError::UnknownSeq { seq });
+    }
+
+    let log_file = state
+        .config
+        .frontend_log_path(&app.package_info().name, state.ids.process_id())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let annotation = state.annotations.annotate(
+        LogAnnotation {
+            session_id: state.session_id.clone(),
+            seq,
+            log_file,
+            note,
+            tag,
+            annotated_at: state.clock.now_millis(),
+        },
+        &state.owned_files,
+    )?;
+    tracing::info!(seq, tag = ?annotation.tag, "Console entry annotated");
+
+    Ok(annotation)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_log_annotations<R: Runtime>(
+    app: AppHandle<R>,
+    since: Option<i64>,
+) -> Result<Vec<LogAnnotation>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    Ok(state.annotations.list(since))
+}
+
+const DEFAULT_PLUGIN_LOG_TAIL_LINES: usize = 200;
+const MAX_PLUGIN_LOG_TAIL_LINES: usize = 10_000;
+
+// The newest lines of tauri-plugin-log's file, oldest first. A file that
+// does not exist yet reads as empty.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn tail_plugin_log<R: Runtime>(
+    app: AppHandle<R>,
+    lines: Option<usize>,
+) -> Result<Vec<PluginLogLine>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let path = state
+        .config
+        .plugin_log_path()
+        .ok_or("plugin_log_file_name is not configured")?;
+    let lines = lines
+        .unwrap_or(DEFAULT_PLUGIN_LOG_TAIL_LINES)
+        .min(MAX_PLUGIN_LOG_TAIL_LINES);
+    match tail_plugin_log_file(&path, lines) {
+        Ok(tail) => Ok(tail),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+// Stamps one marker into the console log (exempt from sampling, rate limits
+// and dedup like every marker), the backend log under its own target, and
+// the next full snapshot's `debug_moments` extension.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn mark_debug_moment<R: Runtime>(
+    app: AppHandle<R>,
+    label: String,
+) -> Result<DebugMoment, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("label must not be empty".into());
+    }
+    let moment = DebugMoment {
+        id: format!("moment-{}-{}", state.session_id, state.ids.next_sequence()),
+        label: label.chars().take(MAX_MOMENT_LABEL_CHARS).collect(),
+        timestamp: state.clock.now_millis(),
+    };
+
+    state
+        .append_logs_use_case
+        .append_marker(moment.to_log_entry())
+        .map_err(|e| e.to_string())?;
+    state.moments.record(moment.clone());
+    tracing::info!(
+        target: MOMENT_TRACING_TARGET,
+        moment_id = %moment.id,
+        label = %moment.label,
+        "Debug moment"
+    );
+
+    Ok(moment)
+}
+
+// Narrows `since`/`until` to the query's moments, found in memory or, for
+// earlier sessions, by their marker entry.
+fn resolve_moment_range(state: &DebugToolsState, query: &mut LogQuery) -> Result<(), String> {
+    let find = |id: &str| -> Result<DebugMoment, String> {
+        if let Some(moment) = state.moments.find(id) {
+            return Ok(moment);
+        }
+        let mut field_equals = serde_json::Map::new();
+        field_equals.insert(DEBUG_MOMENT_FIELD.to_string(), id.into());
+        let marker = state
+            .log_query
+            .query_console_logs(&LogQuery {
+                field_equals,
+                limit: Some(1),
+                ..LogQuery::default()
+            })
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Unknown debug moment: {}", id))?;
+        let label = marker
+            .fields
+            .as_ref()
+            .and_then(|fields| fields.get("label"))
+            .and_then(|label| label.as_str())
+            .unwrap_or_default()
+            .to_string();
+        Ok(DebugMoment {
+            id: id.to_string(),
+            label,
+            timestamp: marker.timestamp,
+        })
+    };
+
+    if let Some(id) = query.from_moment.take() {
+        let from = find(&id)?.timestamp;
+        query.since = Some(query.since.map_or(from, |since| since.max(from)));
+    }
+    if let Some(id) = query.to_moment.take() {
+        // `until` is exclusive; the closing marker is kept.
+        let to = find(&id)?.timestamp + 1;
+        query.until = Some(query.until.map_or(to, |until| until.min(to)));
+    }
+    Ok(())
+}
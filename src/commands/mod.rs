@@ -0,0 +1,130 @@
+mod bundles;
+mod config;
+mod debug_commands;
+mod diagnostics;
+mod error_alarm;
+mod final_beacon;
+mod health;
+mod logs;
+mod page_context;
+mod retention;
+mod screenshots;
+mod snapshots;
+
+pub use bundles::*;
+pub use config::*;
+pub use debug_commands::*;
+pub use diagnostics::*;
+pub use error_alarm::*;
+pub use final_beacon::*;
+pub use health::*;
+pub use logs::*;
+pub use page_context::*;
+pub use retention::*;
+pub use screenshots::*;
+pub use snapshots::*;
+
+use crate::config::{CaptureProfile, DebugToolsConfig};
+use crate::domain::privacy::strip_url_query;
+use crate::DebugToolsState;
+
+// The active profile narrowed by the privacy settings.
+pub(super) fn active_capture_profile(state: &DebugToolsState) -> CaptureProfile {
+    let mut profile = state
+        .capture_profile
+        .read()
+        .map(|active| active.profile.clone())
+        .unwrap_or_default();
+    let privacy = state.privacy.settings();
+    profile.enable_dom_capture &= privacy.capture_dom;
+    profile.enable_screenshots &= privacy.capture_screenshots;
+    profile.redact_sensitive_values |= privacy.redact_sensitive_values;
+    profile
+}
+
+pub(super) fn privacy_url(state: &DebugToolsState, mut url: String) -> String {
+    if state.privacy.settings().strip_url_queries {
+        strip_url_query(&mut url);
+    }
+    url
+}
+
+// Warns once per session per surface; every use is counted for
+// `get_deprecation_report`.
+pub(super) fn note_legacy_use(state: &DebugToolsState, surface: &str, replacement: &str) {
+    if state
+        .deprecations
+        .record(surface, replacement, state.clock.now_millis())
+    {
+        tracing::warn!(
+            surface,
+            replacement,
+            "Deprecated debug-tools command used; this warning is shown once per session"
+        );
+    }
+}
+
+// Accepts a bare file name made of ASCII letters, digits, `-`, `_` and `.`,
+// adding `extension` when missing.
+pub(super) fn sanitize_output_name(name: &str, extension: &str) -> Result<String, String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name.len() <= 128
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    if !valid {
+        tracing::warn!(name = %name, "Rejected output file name");
+        return Err("Invalid output name: use letters, digits, '-', '_' and '.' only".into());
+    }
+
+    let suffix = format!(".{}", extension);
+    Ok(if name.ends_with(&suffix) {
+        name.to_string()
+    } else {
+        format!("{}{}", name, suffix)
+    })
+}
+
+pub(super) fn validate_path_in_directory(
+    path_str: &str,
+    allowed_dir: &std::path::Path,
+) -> Result<std::path::PathBuf, String> {
+    let path = std::path::PathBuf::from(path_str);
+
+    if path
+        .components()
+        .any(|c| c == std::path::Component::ParentDir)
+    {
+        tracing::warn!(path = %path_str, "Path traversal attempt detected");
+        return Err("Invalid path: directory traversal not allowed".into());
+    }
+
+    if !path.starts_with(allowed_dir) {
+        tracing::warn!(
+            path = %path_str,
+            allowed_dir = %allowed_dir.display(),
+            "Path outside allowed directory"
+        );
+        return Err("Invalid path: must be within log directory".into());
+    }
+
+    Ok(path)
+}
+
+// `validate_path_in_directory` against whichever of `log_dir` and the
+// artifact root overrides the path is under.
+pub(super) fn validate_artifact_path(
+    path_str: &str,
+    config: &DebugToolsConfig,
+) -> Result<std::path::PathBuf, String> {
+    let paths = config.artifact_paths();
+    let root = paths
+        .override_roots()
+        .into_iter()
+        .find(|root| std::path::Path::new(path_str).starts_with(root))
+        .unwrap_or(&paths.log_dir);
+    validate_path_in_directory(path_str, root)
+}
@@ -0,0 +1,211 @@
+use super::active_capture_profile;
+use crate::domain::{
+    DebugSnapshot, FormState, FrontendRendererInfo, ResourceTiming, SecurityContext, StorageQuota,
+    UserAgents,
+};
+use crate::DebugToolsState;
+use tauri::{AppHandle, Manager, Runtime, State, Webview};
+
+#[tauri::command]
+#[tracing::instrument(skip(app, context))]
+pub async fn capture_security_context<R: Runtime>(
+    app: AppHandle<R>,
+    context: SecurityContext,
+) -> Result<SecurityContext, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    tracing::info!(
+        policies = context.content_security_policy.len(),
+        violations = context.violations.len(),
+        "Security context captured"
+    );
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .security_context = Some(context.clone());
+
+    Ok(context)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, route_state))]
+pub async fn capture_route_state<R: Runtime>(
+    app: AppHandle<R>,
+    route_state: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !route_state.is_object() {
+        return Err("route_state must be an object".to_string());
+    }
+    let route = DebugSnapshot::route_key(&route_state)
+        .ok_or("route_state must have a string `route` or `path`")?;
+
+    tracing::debug!(route = %route, "Route state captured");
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .route_state = Some(route_state.clone());
+
+    Ok(route_state)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, form_state))]
+pub async fn capture_form_state<R: Runtime>(
+    app: AppHandle<R>,
+    mut form_state: FormState,
+) -> Result<FormState, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !state.privacy.settings().capture_form_state {
+        return Err(
+            "Form capture is disabled; set enable_form_capture, or privacy_overrides.capture_form_state with a privacy preset"
+                .to_string(),
+        );
+    }
+
+    // The frontend masks passwords too; this covers callers that do not.
+    let profile = active_capture_profile(&state);
+    for field in &mut form_state.fields {
+        if field.masked || field.field_type.eq_ignore_ascii_case("password") {
+            field.mask();
+        } else {
+            profile.redact_text(&mut field.value);
+        }
+    }
+
+    tracing::debug!(fields = form_state.fields.len(), "Form state captured");
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .form_state = Some(form_state.clone());
+
+    Ok(form_state)
+}
+
+// Above this share of the quota a capture is logged as a warning.
+const STORAGE_QUOTA_WARN_RATIO: f64 = 0.9;
+
+#[tauri::command]
+#[tracing::instrument(skip(app, quota))]
+pub async fn capture_storage_quota<R: Runtime>(
+    app: AppHandle<R>,
+    quota: StorageQuota,
+) -> Result<StorageQuota, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    match quota.usage_ratio() {
+        Some(ratio) if ratio >= STORAGE_QUOTA_WARN_RATIO => tracing::warn!(
+            usage_bytes = quota.usage_bytes,
+            quota_bytes = quota.quota_bytes,
+            "WebView storage is close to its quota"
+        ),
+        Some(_) => tracing::debug!(
+            usage_bytes = quota.usage_bytes,
+            quota_bytes = quota.quota_bytes,
+            "Storage quota captured"
+        ),
+        None => tracing::debug!(
+            supported = quota.supported,
+            "Storage quota captured without an estimate"
+        ),
+    }
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .storage_quota = Some(quota.clone());
+
+    Ok(quota)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, resources))]
+pub async fn capture_resources<R: Runtime>(
+    app: AppHandle<R>,
+    mut resources: Vec<ResourceTiming>,
+) -> Result<Vec<ResourceTiming>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !active_capture_profile(&state).enable_dom_capture {
+        return Err(
+            "Resource capture is disabled by the active capture profile or privacy settings".into(),
+        );
+    }
+
+    // Keep the most recently started resources.
+    let max = state.config.max_resource_entries;
+    if resources.len() > max {
+        resources.sort_by(|a, b| a.start_time_ms.total_cmp(&b.start_time_ms));
+        resources.drain(..resources.len() - max);
+    }
+
+    tracing::info!(
+        count = resources.len(),
+        failed = resources
+            .iter()
+            .filter(|r| r.response_status.is_some_and(|status| status >= 400))
+            .count(),
+        "Resource timings captured"
+    );
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .resources = Some(resources.clone());
+
+    Ok(resources)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, info))]
+pub async fn report_renderer_info<R: Runtime>(
+    app: AppHandle<R>,
+    info: FrontendRendererInfo,
+) -> Result<FrontendRendererInfo, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    tracing::info!(
+        renderer = info
+            .unmasked_renderer
+            .as_deref()
+            .or(info.renderer.as_deref()),
+        "Renderer info reported"
+    );
+
+    state
+        .frontend_reports
+        .lock()
+        .map_err(|e| e.to_string())?
+        .renderer = Some(info.clone());
+
+    Ok(info)
+}
+
+// Sent by the init script on every page load; the latest report per
+// webview wins.
+#[tauri::command]
+#[tracing::instrument(skip(app, webview))]
+pub async fn report_user_agent<R: Runtime>(
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    user_agent: String,
+) -> Result<(), String> {
+    let user_agent = user_agent.trim();
+    if user_agent.is_empty() {
+        return Err("user_agent must not be empty".into());
+    }
+    if let Some(agents) = app.try_state::<UserAgents>() {
+        agents.record(webview.label(), user_agent);
+    }
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use super::validate_artifact_path;
+use crate::adapters::disk_usage::measure_disk_usage;
+use crate::adapters::filesystem::{
+    clear_debug_log_files, clear_debug_log_files_with_secure_delete, reset_console_logs,
+};
+use crate::adapters::ownership::plugin_named_files;
+use crate::domain::{DeletedFile, LegalHolds};
+use crate::DebugToolsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClearDebugLogsResult {
+    pub deleted_paths: Vec<String>,
+    pub truncated_paths: Vec<String>,
+    pub failed_paths: Vec<String>,
+    // Left in place because they are under legal hold.
+    #[serde(default)]
+    pub held_paths: Vec<String>,
+    // Named like plugin files but not created by it; left in place.
+    #[serde(default)]
+    pub skipped_foreign: Vec<String>,
+    // Each deleted or truncated path, telling overwritten-then-deleted
+    // apart from deleted only.
+    #[serde(default)]
+    pub deletions: Vec<DeletedFile>,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn reset_debug_logs<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let app_name = app.package_info().name.clone();
+    let pid = state.ids.process_id();
+
+    let path = reset_console_logs(&state.config, &app_name, pid, &state.owned_files)
+        .map_err(|e| e.to_string())?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn clear_debug_log_files_command<R: Runtime>(
+    app: AppHandle<R>,
+    secure_delete: Option<bool>,
+) -> Result<ClearDebugLogsResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let app_name = app.package_info().name.clone();
+
+    let holds = state.legal_holds.current();
+    let report = match secure_delete {
+        Some(secure_delete) => clear_debug_log_files_with_secure_delete(
+            &state.config,
+            &app_name,
+            &holds,
+            &state.owned_files,
+            secure_delete,
+        ),
+        None => clear_debug_log_files(&state.config, &app_name, &holds, &state.owned_files),
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(ClearDebugLogsResult {
+        deleted_paths: report
+            .deleted_paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        truncated_paths: report
+            .truncated_paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        failed_paths: report
+            .failed_paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        held_paths: report
+            .held_paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        skipped_foreign: report
+            .skipped_foreign
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        deletions: report.deletions,
+    })
+}
+
+pub const DISK_USAGE_WARNING_EVENT: &str = "debug-tools://disk-usage-warning";
+
+// Run by the housekeeping task on every tick.
+pub fn check_disk_usage<R: Runtime>(app: &AppHandle<R>, state: &DebugToolsState) {
+    let usage = match measure_disk_usage(&state.config, state.clock.now_millis()) {
+        Ok(usage) => usage,
+        Err(e) => {
+            tracing::debug!(error = %e, "Failed to measure debug directory usage");
+            return;
+        }
+    };
+    let Some(warning) = state.disk_usage.observe(usage) else {
+        return;
+    };
+
+    tracing::warn!(
+        threshold = warning.threshold,
+        total_bytes = warning.usage.total_bytes,
+        limit_bytes = warning.usage.limit_bytes,
+        "Debug directory is filling up"
+    );
+
+    if let Err(e) = state
+        .append_logs_use_case
+        .append_marker(warning.to_log_entry())
+    {
+        tracing::warn!(error = %e, "Failed to persist disk usage marker");
+    }
+
+    if let Err(e) = app.emit(DISK_USAGE_WARNING_EVENT, &warning) {
+        tracing::warn!(error = %e, "Failed to emit disk usage warning");
+    }
+}
+
+// Holds by path cover files under the log directory; a session hold covers
+// that session's snapshots and what they reference.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_legal_hold<R: Runtime>(
+    app: AppHandle<R>,
+    paths: Option<Vec<String>>,
+    session_id: Option<String>,
+    held: bool,
+) -> Result<LegalHolds, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let paths = paths
+        .unwrap_or_default()
+        .iter()
+        .map(|path| validate_artifact_path(path, &state.config))
+        .collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() && session_id.is_none() {
+        return Err("Provide paths or a session_id".to_string());
+    }
+
+    let count = paths.len();
+    let holds = state
+        .legal_holds
+        .set(&state.config, paths, session_id.clone(), held)
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!(held, paths = count, session_id = ?session_id, "Legal hold updated");
+
+    Ok(holds)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClaimFilesResult {
+    pub claimed_paths: Vec<String>,
+    pub already_owned: usize,
+    // Outside the log directory, missing, or not regular files.
+    pub rejected_paths: Vec<String>,
+}
+
+// Marks existing files as the plugin's, so clear and retention manage them.
+// Without `paths`, claims every file in the managed directories named like
+// the plugin's artifacts, such as those written before files were tracked.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn claim_existing_files<R: Runtime>(
+    app: AppHandle<R>,
+    paths: Option<Vec<String>>,
+) -> Result<ClaimFilesResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut rejected_paths = Vec::new();
+    let candidates = match paths {
+        Some(paths) => paths
+            .into_iter()
+            .filter_map(|path| {
+                let validated =
+                    validate_artifact_path(&path, &state.config)
+                        .ok()
+                        .filter(|validated| {
+                            std::fs::symlink_metadata(validated)
+                                .is_ok_and(|metadata| metadata.is_file())
+                        });
+                if validated.is_none() {
+                    rejected_paths.push(path);
+                }
+                validated
+            })
+            .collect(),
+        None => plugin_named_files(&state.config).map_err(|e| e.to_string())?,
+    };
+
+    let candidate_count = candidates.len();
+    let claimed = state
+        .owned_files
+        .claim(candidates)
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        claimed = claimed.len(),
+        rejected = rejected_paths.len(),
+        "Existing files claimed"
+    );
+
+    Ok(ClaimFilesResult {
+        already_owned: candidate_count - claimed.len(),
+        claimed_paths: claimed
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        rejected_paths,
+    })
+}
@@ -0,0 +1,614 @@
+use super::{
+    active_capture_profile, request_frontend_reply, validate_artifact_path,
+    validate_path_in_directory,
+};
+use crate::adapters::filesystem::{
+    held_snapshots_referencing, load_snapshot, snapshots_referencing,
+};
+use crate::adapters::recording::{assemble_apng, frame_path, list_frames};
+use crate::adapters::screenshot_diff::diff_screenshots;
+use crate::adapters::screenshots::{
+    capture_window_screenshot, crop_screenshot, delete_screenshot, list_screenshots,
+    newest_screenshot_within, read_image_dimensions,
+};
+use crate::adapters::storage::WritePolicy;
+use crate::application::recording::ActiveRecording;
+use crate::application::{CaptureWebViewStateUseCase, DEFAULT_WINDOW_LABEL};
+use crate::config::sanitize_filename;
+use crate::domain::screenshot_region::{crop_rect, ELEMENT_RECT_COMMAND};
+use crate::domain::{
+    DebugCommandOutcome, DeletedFile, RecordingResult, RecordingStatus, RegionScreenshot,
+    ScreenshotDeleteError, ScreenshotDiff, ScreenshotInfo, ScreenshotPolicy, ScreenshotRect,
+    ScreenshotRegion, ScreenshotRegionError, StrictViolationKind,
+};
+use crate::DebugToolsState;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Runtime, State, WebviewWindow};
+
+pub(super) struct LinkedScreenshot {
+    pub(super) path: PathBuf,
+    // Set when an earlier capture was linked instead of a new one.
+    pub(super) reused_age_secs: Option<u64>,
+}
+
+impl LinkedScreenshot {
+    pub(super) fn fresh(path: PathBuf) -> Self {
+        Self {
+            path,
+            reused_age_secs: None,
+        }
+    }
+}
+
+// The screenshot `policy` gives a full capture that was passed none. A
+// failed capture only costs the snapshot its screenshot.
+pub(super) async fn policy_screenshot<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &DebugToolsState,
+    policy: ScreenshotPolicy,
+) -> Result<Option<LinkedScreenshot>, String> {
+    if let ScreenshotPolicy::ReuseWithin { secs } = policy {
+        let now_secs = state.clock.now_secs();
+        match newest_screenshot_within(&state.config, &state.screenshot_index, now_secs, secs) {
+            Ok(Some(screenshot)) => {
+                return Ok(Some(LinkedScreenshot {
+                    path: screenshot.path,
+                    reused_age_secs: Some(
+                        now_secs.saturating_sub(screenshot.timestamp).max(0) as u64
+                    ),
+                }))
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to look up a recent screenshot"),
+        }
+    }
+    if policy == ScreenshotPolicy::Never {
+        return Ok(None);
+    }
+
+    let saved = match app.get_webview_window(DEFAULT_WINDOW_LABEL) {
+        Some(window) => save_window_screenshot(app, state, &window).await,
+        None => Err(format!("Window not found: {}", DEFAULT_WINDOW_LABEL)),
+    };
+    match saved {
+        Ok(path) => Ok(Some(LinkedScreenshot::fresh(path))),
+        Err(e) => {
+            state.strict_mode.check(
+                StrictViolationKind::ScreenshotsUnavailable,
+                "capture_full_debug_state",
+                format!("snapshot screenshot not captured: {}", e),
+                "save the snapshot without a screenshot",
+                state.clock.now_millis(),
+            )?;
+            tracing::warn!(error = %e, "Snapshot screenshot capture failed");
+            Ok(None)
+        }
+    }
+}
+
+// How long a capture requested right after startup waits for the
+// screenshots plugin's background registration before giving up.
+const SCREENSHOTS_PLUGIN_WAIT: Duration = Duration::from_secs(2);
+
+pub(super) async fn wait_for_screenshots_plugin(state: &DebugToolsState) -> Result<(), String> {
+    let started = Instant::now();
+    loop {
+        match state.screenshots_plugin.get() {
+            Some(Ok(())) => return Ok(()),
+            Some(Err(reason)) => return Err(format!("Screenshots are unavailable: {}", reason)),
+            None if started.elapsed() >= SCREENSHOTS_PLUGIN_WAIT => {
+                return Err(
+                    "The screenshots plugin is still being registered; retry shortly".to_string(),
+                )
+            }
+            None => tokio::time::sleep(Duration::from_millis(20)).await,
+        }
+    }
+}
+
+// Captures `window` through the screenshot queue and copies the PNG into
+// the screenshot directory as `<secs>_<label>_<millis>.png`, where listing,
+// reuse and retention find it.
+async fn save_window_screenshot<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &DebugToolsState,
+    window: &WebviewWindow<R>,
+) -> Result<PathBuf, String> {
+    wait_for_screenshots_plugin(state).await?;
+
+    let title = window.title().map_err(|e| e.to_string())?;
+    let source = state
+        .screenshot_queue
+        .capture(&title, || capture_window_screenshot(app, &title))
+        .await?;
+
+    let screenshot_dir = state.config.screenshot_dir();
+    std::fs::create_dir_all(&screenshot_dir).map_err(|e| e.to_string())?;
+    let destination = screenshot_dir.join(format!(
+        "{}_{}_{}.png",
+        state.clock.now_secs(),
+        sanitize_filename(window.label()),
+        state.clock.now_millis()
+    ));
+    std::fs::copy(&source, &destination).map_err(|e| e.to_string())?;
+    state.owned_files.register(&destination);
+
+    tracing::info!(path = %destination.display(), "Screenshot captured");
+    Ok(destination)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyScreenshotResult {
+    pub source_path: String,
+    pub destination_path: String,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn copy_screenshot_to_debug_dir<R: Runtime>(
+    app: AppHandle<R>,
+    source_path: String,
+) -> Result<CopyScreenshotResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let source = std::path::PathBuf::from(&source_path);
+    if !source.exists() {
+        return Err(format!("Source file does not exist: {}", source_path));
+    }
+
+    let screenshot_dir = state.config.screenshot_dir();
+    std::fs::create_dir_all(&screenshot_dir).map_err(|e| e.to_string())?;
+
+    let filename = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid source filename")?;
+
+    let timestamp = state.clock.now_secs();
+
+    let dest_filename = format!("{}_{}", timestamp, filename);
+    let destination = screenshot_dir.join(&dest_filename);
+
+    std::fs::copy(&source, &destination).map_err(|e| e.to_string())?;
+    state.owned_files.register(&destination);
+
+    tracing::info!(
+        source = %source_path,
+        destination = %destination.display(),
+        "Screenshot copied to debug directory"
+    );
+
+    Ok(CopyScreenshotResult {
+        source_path,
+        destination_path: destination.to_string_lossy().into_owned(),
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_debug_screenshots<R: Runtime>(
+    app: AppHandle<R>,
+    limit: Option<usize>,
+    before_ts: Option<i64>,
+) -> Result<Vec<ScreenshotInfo>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    list_screenshots(
+        &state.config,
+        &state.screenshot_index,
+        limit.unwrap_or(50),
+        before_ts,
+    )
+    .map_err(|e| e.to_string())
+}
+
+// Compares the screenshots recorded on two saved snapshots.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn diff_snapshot_screenshots<R: Runtime>(
+    app: AppHandle<R>,
+    path_a: String,
+    path_b: String,
+) -> Result<ScreenshotDiff, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut screenshots = Vec::with_capacity(2);
+    let mut stems = Vec::with_capacity(2);
+    for path in [&path_a, &path_b] {
+        let path = validate_artifact_path(path, &state.config)?;
+        let snapshot = load_snapshot(&path).map_err(|e| e.to_string())?;
+        let screenshot = snapshot
+            .screenshot_path
+            .ok_or_else(|| format!("Snapshot has no screenshot: {}", path.display()))?;
+        screenshots.push(screenshot);
+        stems.push(
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        );
+    }
+
+    let out = state
+        .config
+        .screenshot_diff_dir()
+        .join(format!("diff_{}_{}.png", stems[0], stems[1]));
+    let diff =
+        diff_screenshots(&screenshots[0], &screenshots[1], &out).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        path = %diff.diff_path.display(),
+        changed_percent = diff.changed_percent,
+        "Snapshot screenshots diffed"
+    );
+
+    Ok(diff)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn delete_debug_screenshot<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+) -> Result<DeletedFile, ScreenshotDeleteError> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let validated = validate_path_in_directory(&path, &state.config.screenshot_dir())
+        .map_err(|message| ScreenshotDeleteError::InvalidPath { message })?;
+    let holds = state.legal_holds.current();
+    let mut pinned_by = held_snapshots_referencing(&state.config, &validated, &holds)
+        .map_err(ScreenshotDeleteError::delete)?;
+    if holds.holds_path(&validated) {
+        pinned_by.insert(0, validated.clone());
+    }
+    if !pinned_by.is_empty() {
+        return Err(ScreenshotDeleteError::PinnedArtifact {
+            path: validated,
+            pinned_by,
+        });
+    }
+    // Retention keeps a shared screenshot while any snapshot references it;
+    // explicit deletes follow the same rule.
+    let snapshots =
+        snapshots_referencing(&state.config, &validated).map_err(ScreenshotDeleteError::delete)?;
+    if !snapshots.is_empty() {
+        return Err(ScreenshotDeleteError::StillReferenced {
+            path: validated,
+            snapshots,
+        });
+    }
+
+    let deletion = delete_screenshot(&state.config, &state.screenshot_index, &validated)
+        .map_err(ScreenshotDeleteError::delete)?;
+    state.owned_files.forget([&validated]);
+
+    Ok(deletion)
+}
+
+const DEFAULT_RECORDING_FPS: u32 = 5;
+const DEFAULT_RECORDING_FRAMES: usize = 100;
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn start_window_recording<R: Runtime>(
+    app: AppHandle<R>,
+    fps: Option<u32>,
+    max_frames: Option<usize>,
+) -> Result<RecordingStatus, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !active_capture_profile(&state).enable_screenshots {
+        return Err(
+            "Screenshots are disabled by the active capture profile or privacy settings"
+                .to_string(),
+        );
+    }
+
+    let fps = fps
+        .unwrap_or(DEFAULT_RECORDING_FPS)
+        .clamp(1, state.config.max_recording_fps.max(1));
+    let max_frames = max_frames
+        .unwrap_or(DEFAULT_RECORDING_FRAMES)
+        .clamp(1, state.config.max_recording_frames.max(1));
+    let started_at = state.clock.now_millis();
+    let dir = state.config.recording_dir().join(started_at.to_string());
+    let title = CaptureWebViewStateUseCase::execute(&app, DEFAULT_WINDOW_LABEL)
+        .map_err(|e| e.to_string())?
+        .title;
+
+    let status = RecordingStatus {
+        dir: dir.clone(),
+        fps,
+        max_frames,
+        started_at,
+    };
+    let started = state.window_recorder.start(|| {
+        let stop = Arc::new(AtomicBool::new(false));
+        let task = tauri::async_runtime::spawn(record_frames(
+            app.clone(),
+            title,
+            status.clone(),
+            stop.clone(),
+        ));
+        ActiveRecording {
+            status: status.clone(),
+            stop,
+            task,
+        }
+    });
+
+    let status = started.ok_or("A window recording is already running")?;
+    tracing::info!(dir = %status.dir.display(), fps, max_frames, "Window recording started");
+    Ok(status)
+}
+
+// Captures one frame per tick until the frame limit or a stop. Captures go
+// through the screenshot queue under per-frame keys, so frames are never
+// coalesced with each other.
+async fn record_frames<R: Runtime>(
+    app: AppHandle<R>,
+    title: String,
+    status: RecordingStatus,
+    stop: Arc<AtomicBool>,
+) -> (usize, usize) {
+    let state: State<'_, DebugToolsState> = app.state();
+    let policy = WritePolicy::for_config(&state.config);
+    if let Err(e) = std::fs::create_dir_all(&status.dir) {
+        tracing::error!(dir = %status.dir.display(), error = %e, "Failed to create recording directory");
+        return (0, 0);
+    }
+    if let Err(e) = wait_for_screenshots_plugin(&state).await {
+        tracing::error!(error = %e, "Window recording stopped before its first frame");
+        return (0, 0);
+    }
+
+    let mut interval =
+        tokio::time::interval(Duration::from_millis((1000 / status.fps as u64).max(1)));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let (mut frames, mut dropped) = (0, 0);
+
+    for tick in 0..status.max_frames {
+        interval.tick().await;
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let key = format!("recording:{}:{}", status.started_at, tick);
+        let captured = state
+            .screenshot_queue
+            .capture(&key, || capture_window_screenshot(&app, &title))
+            .await
+            .and_then(|path| {
+                policy
+                    .move_into(&path, &frame_path(&status.dir, frames))
+                    .map_err(|e| e.to_string())
+            });
+        match captured {
+            Ok(()) => frames += 1,
+            Err(e) => {
+                tracing::debug!(tick, error = %e, "Recording frame dropped");
+                dropped += 1;
+            }
+        }
+    }
+
+    (frames, dropped)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn stop_window_recording<R: Runtime>(
+    app: AppHandle<R>,
+    assemble: Option<bool>,
+) -> Result<RecordingResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let recording = state
+        .window_recorder
+        .take()
+        .ok_or("No window recording is running")?;
+    let (frames, dropped_frames) = recording.task.await.map_err(|e| e.to_string())?;
+    let status = recording.status;
+
+    let animation_path = if assemble.unwrap_or(false) && frames > 0 {
+        let frame_paths = list_frames(&status.dir).map_err(|e| e.to_string())?;
+        let out = status.dir.join("recording.png");
+        let assembled = assemble_apng(&frame_paths, status.fps, &out).map_err(|e| e.to_string())?;
+        tracing::info!(path = %out.display(), frames = assembled, "Recording assembled");
+        Some(out)
+    } else {
+        None
+    };
+
+    let result = RecordingResult {
+        dir: status.dir,
+        frames,
+        dropped_frames,
+        duration_ms: (state.clock.now_millis() - status.started_at).max(0) as u64,
+        animation_path,
+    };
+
+    tracing::info!(
+        dir = %result.dir.display(),
+        frames = result.frames,
+        dropped = result.dropped_frames,
+        "Window recording stopped"
+    );
+
+    Ok(result)
+}
+
+// `logical` (the default) takes `rect` in CSS pixels of the window's content
+// area. Parts of `rect` outside the window are cut off.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn capture_debug_screenshot_region<R: Runtime>(
+    app: AppHandle<R>,
+    window_label: String,
+    rect: ScreenshotRect,
+    logical: Option<bool>,
+) -> Result<RegionScreenshot, ScreenshotRegionError> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let window = screenshot_window(&app, &state, &window_label)?;
+    capture_region(&app, &state, &window, rect, logical.unwrap_or(true), None).await
+}
+
+// Asks the window's frontend for the bounding rect of `selector` through
+// the reply channel, then crops a capture to it. The frontend answers once
+// `registerElementRectHandler` (or `captureElementScreenshot`) has run.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn capture_element_screenshot<R: Runtime>(
+    app: AppHandle<R>,
+    window_label: String,
+    selector: String,
+    timeout_ms: Option<u64>,
+) -> Result<RegionScreenshot, ScreenshotRegionError> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let window = screenshot_window(&app, &state, &window_label)?;
+
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(5_000));
+    let outcome = request_frontend_reply(
+        &state,
+        &window,
+        ELEMENT_RECT_COMMAND.to_string(),
+        serde_json::json!({ "selector": selector }),
+        timeout,
+        None,
+    )
+    .await
+    .map_err(|message| ScreenshotRegionError::ElementRectUnavailable { message })?;
+
+    let unavailable = |message: String| ScreenshotRegionError::ElementRectUnavailable { message };
+    let rect = match outcome {
+        DebugCommandOutcome::Ok { value: None } => None,
+        DebugCommandOutcome::Ok { value: Some(value) } => {
+            serde_json::from_value::<Option<ScreenshotRect>>(value)
+                .map_err(|e| unavailable(format!("unexpected reply: {}", e)))?
+        }
+        DebugCommandOutcome::HandlerError { error } => return Err(unavailable(error.message)),
+        DebugCommandOutcome::MissingListener => {
+            return Err(unavailable(format!(
+                "no {} handler registered in {}",
+                ELEMENT_RECT_COMMAND, window_label
+            )))
+        }
+        DebugCommandOutcome::Timeout { timeout_ms } => {
+            return Err(unavailable(format!("no reply within {}ms", timeout_ms)))
+        }
+        DebugCommandOutcome::Sent => return Err(unavailable("no reply".to_string())),
+    };
+    let rect = rect.ok_or_else(|| ScreenshotRegionError::ElementNotFound {
+        selector: selector.clone(),
+    })?;
+
+    capture_region(&app, &state, &window, rect, true, Some(selector)).await
+}
+
+fn screenshot_window<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &DebugToolsState,
+    window_label: &str,
+) -> Result<WebviewWindow<R>, ScreenshotRegionError> {
+    if !active_capture_profile(state).enable_screenshots {
+        return Err(ScreenshotRegionError::Disabled);
+    }
+    app.get_webview_window(window_label)
+        .ok_or_else(|| ScreenshotRegionError::WindowNotFound {
+            window_label: window_label.to_string(),
+        })
+}
+
+// Crops a full capture of `window`, taken through the screenshot queue like
+// every other capture, into the screenshot directory.
+async fn capture_region<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &DebugToolsState,
+    window: &WebviewWindow<R>,
+    rect: ScreenshotRect,
+    logical: bool,
+    selector: Option<String>,
+) -> Result<RegionScreenshot, ScreenshotRegionError> {
+    let title = window.title().map_err(ScreenshotRegionError::capture)?;
+    let scale_factor = window
+        .scale_factor()
+        .map_err(ScreenshotRegionError::capture)?;
+    // Captures include the window frame; rects are relative to the content.
+    let offset = match (window.inner_position(), window.outer_position()) {
+        (Ok(inner), Ok(outer)) => (
+            (inner.x - outer.x).max(0) as u32,
+            (inner.y - outer.y).max(0) as u32,
+        ),
+        _ => (0, 0),
+    };
+
+    wait_for_screenshots_plugin(state)
+        .await
+        .map_err(ScreenshotRegionError::capture)?;
+    let full = state
+        .screenshot_queue
+        .capture(&title, || capture_window_screenshot(app, &title))
+        .await
+        .map_err(ScreenshotRegionError::capture)?;
+    let image = read_image_dimensions(&full)
+        .ok_or_else(|| ScreenshotRegionError::capture("unreadable capture"))?;
+
+    let scale = if logical { scale_factor } else { 1.0 };
+    let (crop, clamped) = crop_rect(rect, scale, offset, image)?;
+    let region = ScreenshotRegion {
+        window_label: window.label().to_string(),
+        requested: rect,
+        logical,
+        scale_factor,
+        crop,
+        clamped,
+        selector,
+    };
+
+    let screenshot_dir = state.config.screenshot_dir();
+    std::fs::create_dir_all(&screenshot_dir).map_err(ScreenshotRegionError::capture)?;
+    let path = screenshot_dir.join(format!(
+        "{}_{}_region_{}.png",
+        state.clock.now_secs(),
+        sanitize_filename(&region.window_label),
+        state.clock.now_millis()
+    ));
+    crop_screenshot(&full, &region, &path).map_err(ScreenshotRegionError::capture)?;
+    state.owned_files.register(&path);
+
+    tracing::info!(
+        path = %path.display(),
+        width = crop.width,
+        height = crop.height,
+        clamped,
+        "Screenshot region captured"
+    );
+
+    Ok(RegionScreenshot { path, region })
+}
+
+// Captures `label` (default: `main`) into the screenshot directory. The
+// returned path can be passed as `screenshot_path` to
+// `capture_full_debug_state`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn capture_screenshot<R: Runtime>(
+    app: AppHandle<R>,
+    label: Option<String>,
+) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !active_capture_profile(&state).enable_screenshots {
+        return Err(ScreenshotRegionError::Disabled.to_string());
+    }
+    let label = label.unwrap_or_else(|| DEFAULT_WINDOW_LABEL.to_string());
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Window not found: {}", label))?;
+
+    let path = save_window_screenshot(&app, &state, &window).await?;
+    Ok(path.to_string_lossy().into_owned())
+}
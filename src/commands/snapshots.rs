@@ -0,0 +1,855 @@
+use super::{
+    active_capture_profile, cap_fields_strictly, note_legacy_use, policy_screenshot, privacy_url,
+    validate_artifact_path, ConsoleLogEntryPayload, LinkedScreenshot,
+};
+use crate::adapters::dom_search::{
+    build_matcher as build_dom_matcher, search_dom_snapshots as search_dom_snapshot_files,
+};
+use crate::adapters::dom_upload::{ChunkEncoding, DomChunkAck};
+use crate::adapters::filesystem::{
+    annotate_snapshot as annotate_snapshot_file, load_snapshot, read_config_history,
+    read_dom_snapshot_html, save_dom_delta,
+};
+use crate::adapters::snapshot_index::scan_snapshots;
+use crate::analysis::session_compare::{
+    compare_sessions, read_effective_config, render_markdown, summarize_sessions,
+    SessionComparison, SessionData, SessionSummary,
+};
+use crate::application::{
+    build_info, focused_window_label, CaptureWebViewStateUseCase, DEFAULT_WINDOW_LABEL,
+    NAVIGATION_SNAPSHOT_EVENT,
+};
+use crate::config::sanitize_filename;
+use crate::domain::snapshot_read::snapshot_file_name;
+use crate::domain::{
+    apply_delta, diff_dom, render_text_summary, CaptureSizeReport, ConsoleLogEntry, DebugSnapshot,
+    DomDelta, DomDeltaOp, DomDeltaResult, DomSearchQuery, DomSearchReport, DomSnapshotResult,
+    NavigationSnapshotEvent, PartialDebugSnapshot, ReconstructedDom, RepositoryError,
+    ScreenshotPolicy, SnapshotListEntry, SnapshotPage, SnapshotReadError, SnapshotSort,
+    SnapshotSummary, TextSummaryInput, WebViewState,
+};
+use crate::DebugToolsState;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DomSnapshotPayload {
+    pub html: String,
+    pub url: String,
+    pub title: String,
+    pub viewport_width: u32,
+    pub viewport_height: u32,
+    // Set when the frontend serialized open shadow roots into `html`.
+    #[serde(default)]
+    pub shadow_dom_included: bool,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn capture_webview_state<R: Runtime>(
+    app: AppHandle<R>,
+    label: Option<String>,
+    // Deprecated name for `label`, which wins when both are given.
+    window_label: Option<String>,
+) -> Result<WebViewState, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if window_label.is_some() {
+        note_legacy_use(
+            &state,
+            "capture_webview_state (window_label)",
+            "capture_webview_state (label)",
+        );
+    }
+    let label = label
+        .or(window_label)
+        .unwrap_or_else(|| focused_window_label(&app));
+    let webview_state =
+        CaptureWebViewStateUseCase::execute(&app, &label).map_err(|e| e.to_string())?;
+
+    state.cache_webview_state(&webview_state);
+
+    Ok(webview_state)
+}
+
+const MAX_SUMMARY_ERRORS: usize = 50;
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn capture_text_summary<R: Runtime>(
+    app: AppHandle<R>,
+    error_count: Option<usize>,
+) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    // A summary is still useful when the window cannot be queried.
+    let webview = match CaptureWebViewStateUseCase::execute(&app, DEFAULT_WINDOW_LABEL) {
+        Ok(webview) => {
+            state.cache_webview_state(&webview);
+            webview
+        }
+        Err(e) => state
+            .cached_webview_state()
+            .ok_or_else(|| format!("Failed to capture WebView state: {}", e))?,
+    };
+
+    let errors = state
+        .log_query
+        .query_errors(error_count.unwrap_or(5).min(MAX_SUMMARY_ERRORS))
+        .map_err(|e| e.to_string())?;
+
+    Ok(render_text_summary(&TextSummaryInput {
+        captured_at_ms: state.clock.now_millis(),
+        webview: &webview,
+        build_info: &build_info(&app),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        session_id: &state.session_id,
+        errors: &errors,
+    }))
+}
+
+// The temp-dir path a legacy snapshot was written to before snapshots moved
+// under `log_dir`. Points there via a symlink; without symlink support a
+// `.moved` file next to the old path holds the new one.
+fn link_legacy_snapshot_path(target: &std::path::Path) {
+    let Some(name) = target.file_name() else {
+        return;
+    };
+    let legacy = std::env::temp_dir().join(name);
+    if legacy == target {
+        return;
+    }
+    let _ = std::fs::remove_file(&legacy);
+
+    #[cfg(unix)]
+    let linked = std::os::unix::fs::symlink(target, &legacy);
+    #[cfg(windows)]
+    let linked = std::os::windows::fs::symlink_file(target, &legacy);
+    #[cfg(not(any(unix, windows)))]
+    let linked: std::io::Result<()> = Err(std::io::ErrorKind::Unsupported.into());
+
+    if let Err(e) = linked {
+        let mut pointer = legacy.into_os_string();
+        pointer.push(".moved");
+        if let Err(pointer_err) = std::fs::write(&pointer, target.to_string_lossy().as_bytes()) {
+            tracing::debug!(
+                error = %e,
+                pointer_error = %pointer_err,
+                "Could not link legacy snapshot path"
+            );
+        }
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn search_dom_snapshots<R: Runtime>(
+    app: AppHandle<R>,
+    query: DomSearchQuery,
+) -> Result<DomSearchReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let matcher = build_dom_matcher(&query)?;
+
+    search_dom_snapshot_files(&state.config, &query, &matcher).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, payload))]
+pub async fn write_debug_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    payload: serde_json::Value,
+) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !state.config.enable_legacy_snapshot_writes {
+        return Err(
+            "write_debug_snapshot is disabled; use write_typed_debug_snapshot instead".into(),
+        );
+    }
+
+    let ts = state.clock.now_secs();
+
+    let path = state.config.legacy_snapshot_path(ts);
+    let json = serde_json::to_string_pretty(&payload)
+        .map_err(|e| format!("Failed to serialize payload: {}", e))?;
+
+    std::fs::write(&path, &json).map_err(|e| format!("Failed to write file: {}", e))?;
+    state.owned_files.register(&path);
+    state.snapshot_index.record(SnapshotListEntry {
+        path: path.clone(),
+        timestamp: ts,
+        size_bytes: json.len() as u64,
+        route: None,
+        size_report: None,
+        legacy: true,
+        annotations: serde_json::Map::new(),
+    });
+
+    tracing::info!(path = %path.display(), "Legacy debug snapshot saved");
+    note_legacy_use(&state, "write_debug_snapshot", "write_typed_debug_snapshot");
+    if state.config.compat_mode {
+        link_legacy_snapshot_path(&path);
+    }
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, payload))]
+pub async fn write_typed_debug_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    payload: serde_json::Value,
+) -> Result<DebugSnapshot, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    // Deserialized here rather than by the command macro so that shape errors
+    // name the offending field path.
+    let mut partial: PartialDebugSnapshot = serde_path_to_error::deserialize(payload)
+        .map_err(|e| format!("Invalid snapshot payload: {}: {}", e.path(), e.inner()))?;
+
+    let profile = active_capture_profile(&state);
+    profile.cap_logs(&mut partial.console_logs);
+    cap_fields_strictly(
+        &state,
+        "write_typed_debug_snapshot",
+        &mut partial.console_logs,
+    )?;
+    profile.redact_logs(&mut partial.console_logs);
+
+    state
+        .capture_snapshot_use_case
+        .execute_partial(
+            &app,
+            partial,
+            state.cached_webview_state(),
+            state.capture_context(),
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, payload))]
+pub async fn capture_dom_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    payload: DomSnapshotPayload,
+) -> Result<DomSnapshotResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    save_dom_payload(&state, payload)
+}
+
+fn save_dom_payload(
+    state: &DebugToolsState,
+    payload: DomSnapshotPayload,
+) -> Result<DomSnapshotResult, String> {
+    if !active_capture_profile(state).enable_dom_capture {
+        return Err(
+            "DOM capture is disabled by the active capture profile or privacy settings".into(),
+        );
+    }
+
+    state
+        .save_dom_use_case
+        .execute(
+            payload.html,
+            privacy_url(state, payload.url),
+            payload.title,
+            payload.viewport_width,
+            payload.viewport_height,
+            payload.shadow_dom_included,
+        )
+        .map_err(|e| e.to_string())
+}
+
+// Stores only the difference from the session's first DOM capture. Without a
+// baseline yet, the capture is saved in full and becomes it.
+#[tauri::command]
+#[tracing::instrument(skip(app, payload))]
+pub async fn capture_dom_delta<R: Runtime>(
+    app: AppHandle<R>,
+    payload: DomSnapshotPayload,
+) -> Result<DomDeltaResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !active_capture_profile(&state).enable_dom_capture {
+        return Err(
+            "DOM capture is disabled by the active capture profile or privacy settings".into(),
+        );
+    }
+
+    let Some(baseline) = state.save_dom_use_case.baseline() else {
+        let saved = state
+            .save_dom_use_case
+            .execute(
+                payload.html,
+                privacy_url(&state, payload.url),
+                payload.title,
+                payload.viewport_width,
+                payload.viewport_height,
+                payload.shadow_dom_included,
+            )
+            .map_err(|e| e.to_string())?;
+
+        return Ok(DomDeltaResult {
+            path: saved.path.clone(),
+            baseline_path: saved.path,
+            baseline_created: true,
+            inserted_bytes: 0,
+            deleted_bytes: 0,
+        });
+    };
+
+    let ops = diff_dom(&baseline.html, &payload.html);
+    let (inserted_bytes, deleted_bytes) =
+        ops.iter().fold((0, 0), |(inserted, deleted), op| match op {
+            DomDeltaOp::Insert { text } => (inserted + text.len(), deleted),
+            DomDeltaOp::Delete { bytes } => (inserted, deleted + bytes),
+            DomDeltaOp::Keep { .. } => (inserted, deleted),
+        });
+
+    let delta = DomDelta {
+        timestamp: state.clock.now_secs(),
+        url: privacy_url(&state, payload.url),
+        title: payload.title,
+        baseline_path: baseline.path.clone(),
+        baseline_timestamp: baseline.timestamp,
+        ops,
+    };
+    let path =
+        save_dom_delta(&state.config, &delta, &state.owned_files).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        path = %path.display(),
+        inserted_bytes,
+        deleted_bytes,
+        "DOM delta saved"
+    );
+
+    Ok(DomDeltaResult {
+        path,
+        baseline_path: baseline.path,
+        baseline_created: false,
+        inserted_bytes,
+        deleted_bytes,
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn reconstruct_from_baseline<R: Runtime>(
+    app: AppHandle<R>,
+    delta_path: String,
+) -> Result<ReconstructedDom, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let delta_path = validate_artifact_path(&delta_path, &state.config)?;
+    let delta: DomDelta = serde_json::from_slice(
+        &std::fs::read(&delta_path).map_err(|e| format!("Failed to read delta: {}", e))?,
+    )
+    .map_err(|e| format!("Invalid delta file: {}", e))?;
+
+    let baseline_path =
+        validate_artifact_path(&delta.baseline_path.to_string_lossy(), &state.config)?;
+    let baseline = read_dom_snapshot_html(&baseline_path)
+        .map_err(|e| format!("Failed to read baseline: {}", e))?;
+
+    let html =
+        apply_delta(&baseline, &delta.ops).ok_or("Delta does not match its baseline snapshot")?;
+
+    Ok(ReconstructedDom {
+        html,
+        url: delta.url,
+        title: delta.title,
+        timestamp: delta.timestamp,
+        baseline_path,
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, console_logs))]
+pub async fn capture_full_debug_state<R: Runtime>(
+    app: AppHandle<R>,
+    console_logs: Vec<ConsoleLogEntryPayload>,
+    screenshot_path: Option<String>,
+    dom_snapshot_path: Option<String>,
+    screenshot_policy: Option<ScreenshotPolicy>,
+) -> Result<DebugSnapshot, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    let profile = active_capture_profile(&state);
+
+    let validated_screenshot = screenshot_path
+        .filter(|_| profile.enable_screenshots)
+        .map(|p| validate_artifact_path(&p, &state.config))
+        .transpose()?;
+
+    let validated_dom = dom_snapshot_path
+        .filter(|_| profile.enable_dom_capture)
+        .map(|p| validate_artifact_path(&p, &state.config))
+        .transpose()?;
+
+    let mut entries: Vec<ConsoleLogEntry> = console_logs
+        .into_iter()
+        .map(|p| ConsoleLogEntry {
+            timestamp: p.timestamp,
+            level: p.level,
+            message: p.message,
+            args: p.args,
+            stack_trace: p.stack_trace,
+            fields: p.fields,
+        })
+        .collect();
+
+    profile.cap_logs(&mut entries);
+    cap_fields_strictly(&state, "capture_full_debug_state", &mut entries)?;
+    profile.redact_logs(&mut entries);
+
+    let policy = screenshot_policy.unwrap_or(state.config.screenshot_policy);
+    let linked = match validated_screenshot {
+        Some(path) => Some(LinkedScreenshot::fresh(path)),
+        None if profile.enable_screenshots => policy_screenshot(&app, &state, policy).await?,
+        None => None,
+    };
+    let (screenshot_path, reused_age_secs) = match linked {
+        Some(linked) => (Some(linked.path), linked.reused_age_secs),
+        None => (None, None),
+    };
+
+    let webview_state = CaptureWebViewStateUseCase::execute(&app, DEFAULT_WINDOW_LABEL)
+        .map_err(|e| e.to_string())?;
+    let mut snapshot = state.capture_snapshot_use_case.assemble(
+        &app,
+        webview_state,
+        entries,
+        screenshot_path,
+        validated_dom,
+        state.capture_context(),
+    );
+    snapshot.screenshot_reused = reused_age_secs.is_some();
+    snapshot.screenshot_age_secs = reused_age_secs;
+    let snapshot = state
+        .capture_snapshot_use_case
+        .persist(snapshot)
+        .map_err(|e| e.to_string())?;
+
+    state.cache_webview_state(&snapshot.webview_state);
+
+    Ok(snapshot)
+}
+
+// Lightweight capture for `auto_snapshot_on_navigation`: webview state only,
+// tagged "navigation". The webview is then asked to add a DOM snapshot
+// through `NAVIGATION_SNAPSHOT_EVENT` if its frontend opted in.
+pub(crate) async fn capture_navigation_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    webview_label: String,
+    url: String,
+    generation: u64,
+) {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let debounce = Duration::from_millis(state.config.navigation_debounce_ms);
+    tokio::time::sleep(debounce).await;
+    if !state.navigation.is_latest(&webview_label, generation) {
+        return;
+    }
+    if !state
+        .quiet_mode
+        .allows_automatic_snapshot(state.clock.now_secs())
+    {
+        tracing::debug!("Navigation snapshot paused by quiet mode");
+        return;
+    }
+
+    let mut webview_state = match CaptureWebViewStateUseCase::execute(&app, &webview_label) {
+        Ok(webview_state) => webview_state,
+        Err(e) => match state.cached_webview_state() {
+            Some(cached) => cached,
+            None => {
+                tracing::warn!(error = %e, "Skipping navigation snapshot");
+                return;
+            }
+        },
+    };
+    webview_state.url = url.clone();
+
+    let use_case = &state.capture_snapshot_use_case;
+    let mut snapshot = use_case.assemble(
+        &app,
+        webview_state,
+        Vec::new(),
+        None,
+        None,
+        state.capture_context(),
+    );
+    snapshot.label = Some("navigation".to_string());
+    snapshot.note = Some(url.clone());
+    snapshot.extensions.insert(
+        "context".to_string(),
+        serde_json::Value::String("navigation".to_string()),
+    );
+    snapshot.extensions.insert(
+        "webview_label".to_string(),
+        serde_json::Value::String(webview_label.clone()),
+    );
+
+    let snapshot = match use_case.persist(snapshot) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            tracing::warn!(error = %e, url = %url, "Failed to save navigation snapshot");
+            return;
+        }
+    };
+
+    tracing::info!(url = %url, webview = %webview_label, "Navigation snapshot saved");
+
+    let event = NavigationSnapshotEvent {
+        webview_label: webview_label.clone(),
+        url,
+        snapshot_timestamp: snapshot.timestamp,
+    };
+    if let Err(e) = app.emit_to(webview_label.as_str(), NAVIGATION_SNAPSHOT_EVENT, event) {
+        tracing::debug!(error = %e, "Failed to notify webview of navigation snapshot");
+    }
+}
+
+const MAX_SNAPSHOT_PAGE: usize = 500;
+
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_snapshots_paged<R: Runtime>(
+    app: AppHandle<R>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort: Option<SnapshotSort>,
+    route: Option<String>,
+) -> Result<SnapshotPage, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    state
+        .snapshot_index
+        .page(
+            &state.config,
+            offset.unwrap_or(0),
+            limit.unwrap_or(50).min(MAX_SNAPSHOT_PAGE),
+            sort.unwrap_or_default(),
+            route.as_deref(),
+        )
+        .map_err(|e| e.to_string())
+}
+
+// `capture_id` is the snapshot file stem, e.g. `snapshot_1700000000`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_capture_size_report<R: Runtime>(
+    app: AppHandle<R>,
+    capture_id: String,
+) -> Result<CaptureSizeReport, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let entry = state
+        .snapshot_index
+        .find(&state.config, &capture_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Snapshot not found: {}", capture_id))?;
+
+    entry
+        .size_report
+        .ok_or_else(|| format!("Snapshot {} has no size report", capture_id))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, value))]
+pub async fn annotate_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+    key: String,
+    value: serde_json::Value,
+) -> Result<SnapshotListEntry, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if key.trim().is_empty() {
+        return Err("key must not be empty".to_string());
+    }
+    let path = validate_artifact_path(&path, &state.config)?;
+
+    let entry =
+        annotate_snapshot_file(&state.config, &path, &key, value).map_err(|e| e.to_string())?;
+    state.snapshot_index.record(entry.clone());
+
+    tracing::info!(path = %path.display(), key = %key, "Snapshot annotated");
+
+    Ok(entry)
+}
+
+// Legacy payloads come back wrapped, with `schema_version: 0`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn load_debug_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    capture_id: String,
+) -> Result<DebugSnapshot, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let entry = state
+        .snapshot_index
+        .find(&state.config, &capture_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Snapshot not found: {}", capture_id))?;
+
+    load_snapshot(&entry.path).map_err(|e| e.to_string())
+}
+
+// Built from the snapshots each session left in the log directory. Sections
+// whose data one side lacks come back `unavailable` instead of failing.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn compare_debug_sessions<R: Runtime>(
+    app: AppHandle<R>,
+    session_a: String,
+    session_b: String,
+    write_markdown: Option<bool>,
+) -> Result<SessionComparison, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let mut a = SessionData {
+        session_id: session_a,
+        snapshots: Vec::new(),
+    };
+    let mut b = SessionData {
+        session_id: session_b,
+        snapshots: Vec::new(),
+    };
+    for entry in scan_snapshots(&state.config.snapshot_dir()).map_err(|e| e.to_string())? {
+        let Ok(snapshot) = load_snapshot(&entry.path) else {
+            continue;
+        };
+        let owner = snapshot.session_id.as_deref();
+        if owner == Some(b.session_id.as_str()) {
+            b.snapshots.push(snapshot.clone());
+        }
+        if owner == Some(a.session_id.as_str()) {
+            a.snapshots.push(snapshot);
+        }
+    }
+
+    let mut report = compare_sessions(&a, &b, &read_effective_config);
+
+    if write_markdown.unwrap_or(false) {
+        let name = format!(
+            "sessions_{}_vs_{}.md",
+            sanitize_filename(&a.session_id),
+            sanitize_filename(&b.session_id)
+        );
+        let path = state.config.log_export_dir().join(name);
+        std::fs::create_dir_all(state.config.log_export_dir()).map_err(|e| e.to_string())?;
+        std::fs::write(&path, render_markdown(&report)).map_err(|e| e.to_string())?;
+        report.markdown_path = Some(path);
+    }
+
+    tracing::info!(
+        session_a = %a.session_id,
+        session_b = %b.session_id,
+        snapshots_a = a.snapshots.len(),
+        snapshots_b = b.snapshots.len(),
+        "Debug sessions compared"
+    );
+
+    Ok(report)
+}
+
+// Sessions that left snapshots or config history in the log directory, newest
+// first, each with the config that was in force when it started.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_debug_sessions<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<SessionSummary>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let snapshots: Vec<DebugSnapshot> = scan_snapshots(&state.config.snapshot_dir())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|entry| load_snapshot(&entry.path).ok())
+        .collect();
+    let history = read_config_history(&state.config).map_err(|e| e.to_string())?;
+
+    Ok(summarize_sessions(
+        &snapshots,
+        &history,
+        &read_effective_config,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DomUploadMetadata {
+    pub url: String,
+    pub title: String,
+    pub viewport_width: u32,
+    pub viewport_height: u32,
+    #[serde(default)]
+    pub shadow_dom_included: bool,
+    // Number of chunks sent; without it, every chunk up to the highest
+    // received `seq` must be present.
+    #[serde(default)]
+    pub total_chunks: Option<u32>,
+}
+
+// Chunked alternative to `capture_dom_snapshot` for documents too large for
+// one IPC message: begin, append chunks in any order, then finish.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn begin_dom_capture<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !active_capture_profile(&state).enable_dom_capture {
+        return Err(
+            "DOM capture is disabled by the active capture profile or privacy settings".into(),
+        );
+    }
+
+    let upload_id = format!("{}-{}", state.clock.now_millis(), state.ids.next_sequence());
+    state
+        .dom_uploads
+        .begin(upload_id.clone())
+        .map_err(|e| e.to_string())?;
+    Ok(upload_id)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, bytes))]
+pub async fn append_dom_chunk<R: Runtime>(
+    app: AppHandle<R>,
+    upload_id: String,
+    seq: u32,
+    bytes: String,
+    encoding: Option<ChunkEncoding>,
+) -> Result<DomChunkAck, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let bytes = encoding
+        .unwrap_or_default()
+        .decode(bytes)
+        .map_err(|e| e.to_string())?;
+    state
+        .dom_uploads
+        .append(&upload_id, seq, &bytes)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app, metadata))]
+pub async fn finish_dom_capture<R: Runtime>(
+    app: AppHandle<R>,
+    upload_id: String,
+    metadata: DomUploadMetadata,
+) -> Result<DomSnapshotResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    if !active_capture_profile(&state).enable_dom_capture {
+        return Err(
+            "DOM capture is disabled by the active capture profile or privacy settings".into(),
+        );
+    }
+
+    let bytes = state
+        .dom_uploads
+        .finish(&upload_id, metadata.total_chunks)
+        .map_err(|e| e.to_string())?;
+    // Decoded only once joined, since a chunk boundary may split a character.
+    // Invalid sequences become U+FFFD and are counted like the single-shot
+    // path's lone surrogates.
+    let html = String::from_utf8(bytes)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+
+    state
+        .save_dom_use_case
+        .execute(
+            html,
+            privacy_url(&state, metadata.url),
+            metadata.title,
+            metadata.viewport_width,
+            metadata.viewport_height,
+            metadata.shadow_dom_included,
+        )
+        .map_err(|e| e.to_string())
+}
+
+// `capture_dom_snapshot` with the fields as flat arguments, for callers that
+// do not build a payload object. The HTML is taken as given, without shadow
+// roots.
+#[tauri::command]
+#[tracing::instrument(skip(app, html))]
+pub async fn save_dom_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    html: String,
+    url: String,
+    title: String,
+    viewport_width: u32,
+    viewport_height: u32,
+) -> Result<DomSnapshotResult, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+    save_dom_payload(
+        &state,
+        DomSnapshotPayload {
+            html,
+            url,
+            title,
+            viewport_width,
+            viewport_height,
+            shadow_dom_included: false,
+        },
+    )
+}
+
+const DEFAULT_SNAPSHOT_SUMMARIES: usize = 50;
+
+// Lightweight summaries for a snapshot panel, newest first. Pass the last
+// summary's `timestamp` as `before_timestamp` to load the next page.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_debug_snapshots<R: Runtime>(
+    app: AppHandle<R>,
+    limit: Option<usize>,
+    before_timestamp: Option<i64>,
+) -> Result<Vec<SnapshotSummary>, String> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    state
+        .snapshot_index
+        .summaries(
+            &state.config,
+            limit
+                .unwrap_or(DEFAULT_SNAPSHOT_SUMMARIES)
+                .min(MAX_SNAPSHOT_PAGE),
+            before_timestamp,
+        )
+        .map_err(|e| e.to_string())
+}
+
+// By timestamp rather than capture id, for a webview that kept the one
+// `capture_full_debug_state` returned. Legacy payloads come back wrapped,
+// as with `load_debug_snapshot`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn read_debug_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    timestamp: i64,
+) -> Result<DebugSnapshot, SnapshotReadError> {
+    let state: State<'_, DebugToolsState> = app.state();
+
+    let path = state
+        .config
+        .snapshot_dir()
+        .join(snapshot_file_name(timestamp)?);
+    load_snapshot(&path).map_err(|e| match e {
+        RepositoryError::Io(io) if io.kind() == std::io::ErrorKind::NotFound => {
+            SnapshotReadError::NotFound { timestamp }
+        }
+        e => SnapshotReadError::Unreadable {
+            timestamp,
+            message: e.to_string(),
+        },
+    })
+}
@@ -1,6 +1,16 @@
-use std::path::PathBuf;
+use crate::domain::privacy::{is_sensitive_key, redact_sensitive_text};
+use crate::domain::{
+    ArtifactKind, BodyCaptureRule, CaptureSizeThresholds, ConsoleLogEntry, ConsoleSource,
+    Durability, ErrorAlarmConfig, HealthHttpConfig, LogLevel, PrivacyOverrides, PrivacyPreset,
+    PrivacySettings, PrivacyStatus, QuietModeStatus, RetentionPolicy, ScreenshotPolicy,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 use tauri::{AppHandle, Manager, Runtime};
 use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -8,31 +18,644 @@ pub enum ConfigError {
     LogDirectory(String),
     #[error("Failed to create directory: {0}")]
     CreateDirectory(#[from] std::io::Error),
+    #[error("Unsafe log root: {0}")]
+    UnsafeLogRoot(String),
+    #[error("Invalid artifact root: {0}")]
+    ArtifactRoot(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     Json,
     Text,
 }
 
+// `Auto` switches to conservative writes when `log_dir` is on a network
+// filesystem.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageMode {
+    #[default]
+    Auto,
+    Standard,
+    Conservative,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CaptureProfile {
+    pub enable_dom_capture: bool,
+    pub enable_screenshots: bool,
+    pub max_console_logs: Option<usize>,
+    pub redact_patterns: Vec<String>,
+    // Also redact secrets, tokens and e-mail addresses found without a
+    // pattern; set by the privacy settings.
+    #[serde(default)]
+    pub redact_sensitive_values: bool,
+}
+
+impl Default for CaptureProfile {
+    fn default() -> Self {
+        Self {
+            enable_dom_capture: true,
+            enable_screenshots: true,
+            max_console_logs: None,
+            redact_patterns: Vec::new(),
+            redact_sensitive_values: false,
+        }
+    }
+}
+
+impl CaptureProfile {
+    pub fn redact_logs(&self, logs: &mut [ConsoleLogEntry]) {
+        if self.redact_patterns.is_empty() && !self.redact_sensitive_values {
+            return;
+        }
+
+        for entry in logs.iter_mut() {
+            for pattern in self.redact_patterns.iter().filter(|p| !p.is_empty()) {
+                if entry.message.contains(pattern.as_str()) {
+                    entry.message = entry.message.replace(pattern.as_str(), "[REDACTED]");
+                    entry.args = serde_json::Value::Null;
+                }
+            }
+            if self.redact_sensitive_values && redact_sensitive_text(&mut entry.message) {
+                entry.args = serde_json::Value::Null;
+            }
+            if let Some(fields) = entry.fields.as_mut() {
+                for (key, value) in fields.iter_mut() {
+                    if self.redact_sensitive_values && is_sensitive_key(key) {
+                        *value = serde_json::Value::String("[REDACTED]".to_string());
+                    } else {
+                        self.redact_value(value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn redact_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(text) => self.redact_text(text),
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| self.redact_value(item))
+            }
+            serde_json::Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    if self.redact_sensitive_values && is_sensitive_key(key) {
+                        *item = serde_json::Value::String("[REDACTED]".to_string());
+                    } else {
+                        self.redact_value(item);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn redact_text(&self, text: &mut String) {
+        for pattern in self.redact_patterns.iter().filter(|p| !p.is_empty()) {
+            if text.contains(pattern.as_str()) {
+                *text = text.replace(pattern.as_str(), "[REDACTED]");
+            }
+        }
+        if self.redact_sensitive_values {
+            redact_sensitive_text(text);
+        }
+    }
+
+    pub fn cap_logs(&self, logs: &mut Vec<ConsoleLogEntry>) {
+        if let Some(max) = self.max_console_logs {
+            if logs.len() > max {
+                logs.drain(..logs.len() - max);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActiveCaptureProfile {
+    pub name: String,
+    pub profile: CaptureProfile,
+}
+
+pub const DEFAULT_CAPTURE_PROFILE: &str = "default";
+
+fn default_durability() -> HashMap<ArtifactKind, Durability> {
+    ArtifactKind::ALL
+        .into_iter()
+        .map(|kind| (kind, kind.default_durability()))
+        .collect()
+}
+
+fn default_capture_profiles(enable_dom_capture: bool) -> HashMap<String, CaptureProfile> {
+    HashMap::from([
+        (
+            DEFAULT_CAPTURE_PROFILE.to_string(),
+            CaptureProfile {
+                enable_dom_capture,
+                ..CaptureProfile::default()
+            },
+        ),
+        (
+            "perf".to_string(),
+            CaptureProfile {
+                enable_dom_capture: false,
+                enable_screenshots: false,
+                max_console_logs: Some(200),
+                ..CaptureProfile::default()
+            },
+        ),
+        ("crash".to_string(), CaptureProfile::default()),
+        (
+            "visual".to_string(),
+            CaptureProfile {
+                max_console_logs: Some(50),
+                ..CaptureProfile::default()
+            },
+        ),
+    ])
+}
+
+// Snapshot of the settings in force at a point in time, written to
+// `config_history/` whenever any of them changes at runtime.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EffectiveConfig {
+    pub written_at: i64,
+    pub reason: String,
+    pub log_dir: PathBuf,
+    pub max_log_size_bytes: u64,
+    pub log_format: LogFormat,
+    pub enable_dom_capture: bool,
+    pub enable_rust_logging: bool,
+    pub active_profile: ActiveCaptureProfile,
+    pub quiet_mode: Option<QuietModeStatus>,
+    // Missing from files written before privacy presets.
+    #[serde(default)]
+    pub privacy: Option<PrivacyStatus>,
+    // Missing from files written before `list_debug_sessions`.
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct DebugToolsConfig {
     pub log_dir: PathBuf,
+    // The console log and `rust_debug.log` are moved aside to a numbered
+    // copy before a write that would take them past this; 0 never rotates.
     pub max_log_size_bytes: u64,
+    // Line format of `rust_debug.log`. Console logs are always JSONL.
     pub log_format: LogFormat,
     pub enable_dom_capture: bool,
+    // Off skips `rust_debug.log`; stdout and log forwarding still run.
     pub enable_rust_logging: bool,
+    pub capture_profiles: HashMap<String, CaptureProfile>,
+    pub max_config_history_files: usize,
+    // Deprecated untyped `write_debug_snapshot`; superseded by `write_typed_debug_snapshot`.
+    pub enable_legacy_snapshot_writes: bool,
+    pub error_rate_window_secs: u64,
+    pub error_rate_window_count: usize,
+    pub forward_backend_logs: bool,
+    pub emit_interval_ms: u64,
+    pub emit_batch_max: usize,
+    pub capture_bodies_for: Option<BodyCaptureRule>,
+    pub max_log_fields: usize,
+    pub max_log_field_value_bytes: usize,
+    // Entry fields flattened into their own columns by the CSV and timeline exports.
+    pub export_field_columns: Vec<String>,
+    pub max_resource_entries: usize,
+    // Fail setup instead of ignoring a second registration of the plugin.
+    pub strict_singleton: bool,
+    pub auto_snapshot_on_navigation: bool,
+    pub navigation_debounce_ms: u64,
+    pub housekeeping_interval_secs: u64,
+    // Minimum level for the plugin's own tracing events; errors always pass.
+    pub self_log_level: LogLevel,
+    pub self_log_summary_secs: u64,
+    // When false, a `log_dir` that is itself a symlink is rejected. Allowed
+    // targets are still checked so cleanup never runs against a shared root.
+    pub allow_symlinked_log_dir: bool,
+    pub snapshot_hook_timeout_ms: u64,
+    pub error_alarm: Option<ErrorAlarmConfig>,
+    // Pruning runs at startup and then every `retention_interval_secs`, only
+    // when a policy is set.
+    pub retention: Option<RetentionPolicy>,
+    pub retention_interval_secs: u64,
+    pub capture_size_warnings: CaptureSizeThresholds,
+    // Collapse repeated entries within each `append_debug_logs` batch.
+    pub dedup_console_logs: bool,
+    // Appended entries further behind their predecessor than this are
+    // flagged with `clock_skew_ms`.
+    pub clock_skew_tolerance_ms: u64,
+    // Shift frontend log timestamps by the skew measured with
+    // `check_clock_skew`, keeping the original in `original_timestamp`.
+    pub adjust_frontend_timestamps: bool,
+    pub storage_mode: StorageMode,
+    // Off by default: form values are what the user typed. When on,
+    // `capture_form_state` masks password fields and applies the active
+    // profile's `redact_patterns`.
+    pub enable_form_capture: bool,
+    // Screenshot captures allowed at once; each is bounded by the timeout,
+    // and repeat requests for a window within the coalesce window share one.
+    pub screenshot_concurrency: usize,
+    pub screenshot_timeout_ms: u64,
+    pub screenshot_coalesce_ms: u64,
+    // Upper bounds for `start_window_recording`; requests above are clamped.
+    pub max_recording_fps: u32,
+    pub max_recording_frames: usize,
+    // Per-artifact write durability; kinds missing from the map use
+    // `ArtifactKind::default_durability`.
+    pub durability: HashMap<ArtifactKind, Durability>,
+    // Frontend console log batching: entries are sent after the adaptive
+    // flush interval, or as soon as `flush_batch_size` are queued, in
+    // batches of at most `flush_batch_size`. Past `max_queued_entries` the
+    // oldest queued entries are dropped and counted.
+    pub flush_interval_ms: u64,
+    // The interval starts at `flush_interval_ms`, stretches up to
+    // `max_flush_interval_ms` while under one entry per second arrives, and
+    // tightens down to `min_flush_interval_ms` above
+    // `flush_burst_entries_per_sec`. Warnings, errors and a queue of
+    // `flush_byte_threshold` bytes are sent immediately.
+    pub min_flush_interval_ms: u64,
+    pub max_flush_interval_ms: u64,
+    pub flush_byte_threshold: usize,
+    pub flush_burst_entries_per_sec: u32,
+    pub flush_batch_size: usize,
+    pub max_queued_entries: usize,
+    // Keeps legacy command responses (plain path strings) for frontends
+    // written against them, and links the old temp-dir snapshot paths to
+    // their new location where the platform allows symlinks.
+    pub compat_mode: bool,
+    // Export all console logs to `exports/` as part of the shutdown sequence
+    // on exit; `shutdown_debug_tools` can override it per call.
+    pub export_logs_on_shutdown: bool,
+    // The app's shipped `.map` files, used when an export asks for
+    // `resolve_stack_traces`. Frames are looked up by URL path, then file name.
+    pub sourcemap_dir: Option<PathBuf>,
+    // Serves `/healthz` and `/last-errors` on 127.0.0.1 when set.
+    pub health_http: Option<HealthHttpConfig>,
+    // Chunked DOM uploads (`begin_dom_capture`): total size cap, and how
+    // long an upload may go without a chunk before it is discarded.
+    pub max_dom_upload_bytes: u64,
+    pub dom_upload_timeout_secs: u64,
+    // Coarse privacy level, resolved into concrete capture flags before
+    // `privacy_overrides`. Without a preset, capture works as configured by
+    // the individual flags; with one, `enable_form_capture` is ignored in
+    // favour of `privacy_overrides.capture_form_state`.
+    pub privacy_preset: Option<PrivacyPreset>,
+    pub privacy_overrides: PrivacyOverrides,
+    // Size the debug directory is expected to stay under. Housekeeping warns
+    // at 70% and 90% of it, or of the free disk space when unset.
+    pub max_total_disk_bytes: Option<u64>,
+    // Console pathways whose entries are always kept. Entries from the
+    // others are dropped when another pathway already delivered the same
+    // call. An empty list treats every pathway as authoritative.
+    pub console_sources: Vec<ConsoleSource>,
+    // Overwrite plugin-owned files with zeros before `clear_debug_log_files`
+    // deletes them. Best-effort; skipped on copy-on-write filesystems.
+    pub secure_delete: bool,
+    // File name of tauri-plugin-log's `LogDir` target, which writes next to
+    // `log_dir` in the app log directory. `None` when the host does not use
+    // it; read tooling then ignores it.
+    pub plugin_log_file_name: Option<String>,
+    // Let clear and retention touch that file and its rotated copies. Off
+    // by default, since the host may consider them its own.
+    pub manage_plugin_log: bool,
+    // Mirror tracing events to stdout; off for headless runs.
+    pub log_to_stdout: bool,
+    // Capacity kept between captures for reuse by snapshot and DOM
+    // serialization (0 disables pooling), freed after the pool goes unused
+    // for `buffer_pool_idle_secs`.
+    pub buffer_pool_max_bytes: usize,
+    pub buffer_pool_idle_secs: u64,
+    // What `capture_full_debug_state` does for a screenshot when the caller
+    // passes no path; a per-call policy overrides it.
+    pub screenshot_policy: ScreenshotPolicy,
+    // For CI: queue overflow, storage fallbacks, missing screenshots,
+    // substituted log fields and clock skew fail the triggering command
+    // instead of degrading, and are listed in `get_debug_stats`.
+    pub strict_mode: bool,
+    // Absolute directories replacing an artifact kind's place under
+    // `log_dir`, e.g. to keep screenshots on an encrypted volume. They may
+    // not overlap `log_dir` or each other; see `ArtifactPaths`.
+    pub console_log_root: Option<PathBuf>,
+    pub snapshot_root: Option<PathBuf>,
+    pub screenshot_root: Option<PathBuf>,
+    pub dom_snapshot_root: Option<PathBuf>,
+    pub network_log_root: Option<PathBuf>,
+    pub backend_log_root: Option<PathBuf>,
+    pub config_history_root: Option<PathBuf>,
+    pub recording_root: Option<PathBuf>,
+    pub export_root: Option<PathBuf>,
+    pub annotation_root: Option<PathBuf>,
+    pub legal_hold_root: Option<PathBuf>,
+}
+
+// Where each artifact kind is written, read, cleaned up and measured. Kinds
+// without a root override keep their usual place: console, network,
+// backend and annotation logs, snapshots and legal holds directly in
+// `log_dir`, the rest in its `screenshots/`, `dom_snapshots/`,
+// `config_history/`, `recordings/` and `exports/`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArtifactPaths {
+    pub log_dir: PathBuf,
+    pub console_logs: PathBuf,
+    pub snapshots: PathBuf,
+    pub screenshots: PathBuf,
+    pub dom_snapshots: PathBuf,
+    pub network_logs: PathBuf,
+    pub backend_logs: PathBuf,
+    pub config_history: PathBuf,
+    pub recordings: PathBuf,
+    pub exports: PathBuf,
+    pub annotations: PathBuf,
+    pub legal_holds: PathBuf,
+    // Config field names of the roots in effect, e.g. `screenshot_root`.
+    pub overridden: Vec<String>,
+}
+
+impl ArtifactPaths {
+    pub fn resolve(config: &DebugToolsConfig) -> Self {
+        let log_dir = config.log_dir.clone();
+        let mut overridden = Vec::new();
+        let mut root = |name: &str, root: &Option<PathBuf>, default: PathBuf| match root {
+            Some(root) => {
+                overridden.push(name.to_string());
+                root.clone()
+            }
+            None => default,
+        };
+        Self {
+            console_logs: root(
+                "console_log_root",
+                &config.console_log_root,
+                log_dir.clone(),
+            ),
+            snapshots: root("snapshot_root", &config.snapshot_root, log_dir.clone()),
+            screenshots: root(
+                "screenshot_root",
+                &config.screenshot_root,
+                log_dir.join("screenshots"),
+            ),
+            dom_snapshots: root(
+                "dom_snapshot_root",
+                &config.dom_snapshot_root,
+                log_dir.join("dom_snapshots"),
+            ),
+            network_logs: root(
+                "network_log_root",
+                &config.network_log_root,
+                log_dir.clone(),
+            ),
+            backend_logs: root(
+                "backend_log_root",
+                &config.backend_log_root,
+                log_dir.clone(),
+            ),
+            config_history: root(
+                "config_history_root",
+                &config.config_history_root,
+                log_dir.join("config_history"),
+            ),
+            recordings: root(
+                "recording_root",
+                &config.recording_root,
+                log_dir.join("recordings"),
+            ),
+            exports: root("export_root", &config.export_root, log_dir.join("exports")),
+            annotations: root("annotation_root", &config.annotation_root, log_dir.clone()),
+            legal_holds: root("legal_hold_root", &config.legal_hold_root, log_dir.clone()),
+            overridden,
+            log_dir,
+        }
+    }
+
+    pub fn console_log_file(&self, app_name: &str, pid: u32) -> PathBuf {
+        self.console_logs.join(format!(
+            "frontend_console_{}_{}.jsonl",
+            sanitize_filename(app_name),
+            pid
+        ))
+    }
+
+    pub fn snapshot_file(&self, timestamp: i64) -> PathBuf {
+        self.snapshots.join(format!("snapshot_{}.json", timestamp))
+    }
+
+    pub fn legacy_snapshot_file(&self, timestamp: i64) -> PathBuf {
+        self.snapshots
+            .join(format!("tauri_debug_snapshot_{}.json", timestamp))
+    }
+
+    pub fn network_log_file(&self, app_name: &str, pid: u32) -> PathBuf {
+        self.network_logs.join(format!(
+            "network_{}_{}.jsonl",
+            sanitize_filename(app_name),
+            pid
+        ))
+    }
+
+    pub fn backend_log_file(&self) -> PathBuf {
+        self.backend_logs.join("rust_debug.log")
+    }
+
+    pub fn annotations_file(&self, session_id: &str) -> PathBuf {
+        self.annotations.join(format!(
+            "annotations_{}.jsonl",
+            sanitize_filename(session_id)
+        ))
+    }
+
+    pub fn legal_holds_file(&self) -> PathBuf {
+        self.legal_holds.join("holds.json")
+    }
+
+    // Each kind's override field name with the directory in use for it.
+    fn roots(&self) -> [(&'static str, &PathBuf); 11] {
+        [
+            ("console_log_root", &self.console_logs),
+            ("snapshot_root", &self.snapshots),
+            ("screenshot_root", &self.screenshots),
+            ("dom_snapshot_root", &self.dom_snapshots),
+            ("network_log_root", &self.network_logs),
+            ("backend_log_root", &self.backend_logs),
+            ("config_history_root", &self.config_history),
+            ("recording_root", &self.recordings),
+            ("export_root", &self.exports),
+            ("annotation_root", &self.annotations),
+            ("legal_hold_root", &self.legal_holds),
+        ]
+    }
+
+    fn is_overridden(&self, name: &str) -> bool {
+        self.overridden.iter().any(|overridden| overridden == name)
+    }
+
+    // The overridden roots, which live outside `log_dir`.
+    pub fn override_roots(&self) -> Vec<&Path> {
+        self.roots()
+            .into_iter()
+            .filter(|(name, _)| self.is_overridden(name))
+            .map(|(_, root)| root.as_path())
+            .collect()
+    }
+
+    // Whether `path` is under `log_dir` or one of the overridden roots.
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.log_dir)
+            || self
+                .override_roots()
+                .iter()
+                .any(|root| path.starts_with(root))
+    }
+
+    // The root a directory's files are owned under: its override when it
+    // is one, otherwise `log_dir`.
+    pub fn root_of(&self, directory: &Path) -> &Path {
+        self.override_roots()
+            .into_iter()
+            .find(|root| directory.starts_with(root))
+            .unwrap_or(&self.log_dir)
+    }
+
+    // Ownership and cleanup tell kinds apart by directory, so an override
+    // must be absolute and may not contain or sit inside `log_dir` or
+    // another override. Compared lexically; the roots need not exist yet.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let overrides: Vec<(&str, PathBuf)> = self
+            .roots()
+            .into_iter()
+            .filter(|(name, _)| self.is_overridden(name))
+            .map(|(name, root)| (name, normalize(root)))
+            .collect();
+        let log_dir = normalize(&self.log_dir);
+
+        for (position, (name, root)) in overrides.iter().enumerate() {
+            if !root.is_absolute() {
+                return Err(ConfigError::ArtifactRoot(format!(
+                    "{} must be absolute: {}",
+                    name,
+                    root.display()
+                )));
+            }
+            let others = std::iter::once(("log_dir", &log_dir)).chain(
+                overrides[..position]
+                    .iter()
+                    .map(|(name, root)| (*name, root)),
+            );
+            for (other_name, other) in others {
+                if root.starts_with(other) || other.starts_with(root) {
+                    return Err(ConfigError::ArtifactRoot(format!(
+                        "{} ({}) overlaps {} ({})",
+                        name,
+                        root.display(),
+                        other_name,
+                        other.display()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// `.` and `..` removed without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
 }
 
 impl Default for DebugToolsConfig {
     fn default() -> Self {
         Self {
-            log_dir: PathBuf::from("/tmp/tauri-debug-tools"),
+            // The plugin itself always resolves the app log directory; this
+            // is for configs built by hand.
+            log_dir: std::env::temp_dir().join("tauri-debug-tools"),
             max_log_size_bytes: 50_000,
             log_format: LogFormat::Json,
             enable_dom_capture: true,
             enable_rust_logging: true,
+            capture_profiles: default_capture_profiles(true),
+            max_config_history_files: 20,
+            enable_legacy_snapshot_writes: true,
+            error_rate_window_secs: 60,
+            error_rate_window_count: 15,
+            forward_backend_logs: false,
+            emit_interval_ms: 250,
+            emit_batch_max: 200,
+            capture_bodies_for: None,
+            max_log_fields: 32,
+            max_log_field_value_bytes: 1024,
+            export_field_columns: Vec::new(),
+            max_resource_entries: 500,
+            strict_singleton: false,
+            auto_snapshot_on_navigation: false,
+            navigation_debounce_ms: 500,
+            housekeeping_interval_secs: 30,
+            self_log_level: LogLevel::Info,
+            self_log_summary_secs: 60,
+            allow_symlinked_log_dir: true,
+            snapshot_hook_timeout_ms: 5_000,
+            error_alarm: None,
+            retention: None,
+            retention_interval_secs: 3600,
+            capture_size_warnings: CaptureSizeThresholds::default(),
+            dedup_console_logs: false,
+            clock_skew_tolerance_ms: 1_000,
+            adjust_frontend_timestamps: false,
+            storage_mode: StorageMode::Auto,
+            enable_form_capture: false,
+            screenshot_concurrency: 1,
+            screenshot_timeout_ms: 10_000,
+            screenshot_coalesce_ms: 500,
+            max_recording_fps: 10,
+            max_recording_frames: 300,
+            durability: default_durability(),
+            flush_interval_ms: 1_000,
+            min_flush_interval_ms: 100,
+            max_flush_interval_ms: 10_000,
+            flush_byte_threshold: 256 * 1024,
+            flush_burst_entries_per_sec: 50,
+            flush_batch_size: 200,
+            max_queued_entries: 1_000,
+            compat_mode: false,
+            export_logs_on_shutdown: false,
+            sourcemap_dir: None,
+            health_http: None,
+            max_dom_upload_bytes: 256 * 1024 * 1024,
+            dom_upload_timeout_secs: 120,
+            privacy_preset: None,
+            privacy_overrides: PrivacyOverrides::default(),
+            max_total_disk_bytes: None,
+            console_sources: ConsoleSource::LIVE.to_vec(),
+            secure_delete: false,
+            plugin_log_file_name: Some(DEFAULT_PLUGIN_LOG_FILE_NAME.to_string()),
+            manage_plugin_log: false,
+            log_to_stdout: true,
+            buffer_pool_max_bytes: 32 * 1024 * 1024,
+            buffer_pool_idle_secs: 60,
+            screenshot_policy: ScreenshotPolicy::default(),
+            strict_mode: false,
+            console_log_root: None,
+            snapshot_root: None,
+            screenshot_root: None,
+            dom_snapshot_root: None,
+            network_log_root: None,
+            backend_log_root: None,
+            config_history_root: None,
+            recording_root: None,
+            export_root: None,
+            annotation_root: None,
+            legal_hold_root: None,
         }
     }
 }
@@ -49,34 +672,322 @@ impl DebugToolsConfig {
 
         Ok(Self {
             log_dir,
-            max_log_size_bytes: 50_000,
-            log_format: LogFormat::Json,
-            enable_dom_capture: true,
-            enable_rust_logging: true,
+            ..Self::default()
         })
     }
 
+    pub fn initial_capture_profile(&self) -> ActiveCaptureProfile {
+        let profile = self
+            .capture_profiles
+            .get(DEFAULT_CAPTURE_PROFILE)
+            .cloned()
+            .unwrap_or_else(|| CaptureProfile {
+                enable_dom_capture: self.enable_dom_capture,
+                ..CaptureProfile::default()
+            });
+
+        ActiveCaptureProfile {
+            name: DEFAULT_CAPTURE_PROFILE.to_string(),
+            profile,
+        }
+    }
+
+    // What a `None` privacy preset resolves to.
+    pub fn privacy_baseline(&self) -> PrivacySettings {
+        PrivacySettings::unrestricted(self.enable_form_capture)
+    }
+
+    // Keeps the first `max_log_fields` keys and drops values whose JSON form
+    // exceeds `max_log_field_value_bytes`, leaving a marker in their place.
+    // Serialization stops at the budget, so for an oversized value
+    // `size_bytes` is where it stopped rather than the full size.
+    // Returns how many values were replaced.
+    pub fn cap_log_fields(&self, logs: &mut [ConsoleLogEntry]) -> usize {
+        let mut substituted = 0;
+        for fields in logs.iter_mut().filter_map(|entry| entry.fields.as_mut()) {
+            if fields.len() > self.max_log_fields {
+                *fields = std::mem::take(fields)
+                    .into_iter()
+                    .take(self.max_log_fields)
+                    .collect();
+            }
+            for value in fields.values_mut() {
+                if let Err(size) = json_size_within(value, self.max_log_field_value_bytes) {
+                    *value = serde_json::json!({ "truncated": true, "size_bytes": size });
+                    substituted += 1;
+                }
+            }
+        }
+        substituted
+    }
+
+    pub fn artifact_paths(&self) -> ArtifactPaths {
+        ArtifactPaths::resolve(self)
+    }
+
     pub fn frontend_log_path(&self, app_name: &str, pid: u32) -> PathBuf {
-        let sanitized_name = app_name.replace(' ', "_");
-        self.log_dir
-            .join(format!("frontend_console_{}_{}.jsonl", sanitized_name, pid))
+        self.artifact_paths().console_log_file(app_name, pid)
+    }
+
+    pub fn console_log_dir(&self) -> PathBuf {
+        self.artifact_paths().console_logs
+    }
+
+    pub fn snapshot_dir(&self) -> PathBuf {
+        self.artifact_paths().snapshots
+    }
+
+    pub fn snapshot_path(&self, timestamp: i64) -> PathBuf {
+        self.artifact_paths().snapshot_file(timestamp)
+    }
+
+    pub fn network_log_path(&self, app_name: &str, pid: u32) -> PathBuf {
+        self.artifact_paths().network_log_file(app_name, pid)
     }
 
     pub fn backend_log_path(&self) -> PathBuf {
-        self.log_dir.join("rust_debug.log")
+        self.artifact_paths().backend_log_file()
+    }
+
+    // tauri-plugin-log's file, when configured. Rotated copies sit next to
+    // it as `<stem>_<timestamp>.log`.
+    pub fn plugin_log_path(&self) -> Option<PathBuf> {
+        let name = self.plugin_log_file_name.as_deref()?;
+        Some(self.log_dir.parent()?.join(name))
+    }
+
+    pub fn flamegraph_folded_path(&self) -> PathBuf {
+        self.log_dir.join("tracing.folded")
+    }
+
+    pub fn config_history_dir(&self) -> PathBuf {
+        self.artifact_paths().config_history
+    }
+
+    pub fn benchmark_scratch_path(&self) -> PathBuf {
+        self.log_dir.join(".benchmark_scratch")
+    }
+
+    pub fn final_beacon_path(&self, received_at: i64) -> PathBuf {
+        self.log_dir
+            .join(format!("final_beacon_{}.json", received_at))
+    }
+
+    pub fn legacy_snapshot_path(&self, timestamp: i64) -> PathBuf {
+        self.artifact_paths().legacy_snapshot_file(timestamp)
+    }
+
+    pub fn log_export_dir(&self) -> PathBuf {
+        self.artifact_paths().exports
+    }
+
+    pub fn bundle_import_dir(&self) -> PathBuf {
+        self.log_dir.join("imports")
+    }
+
+    pub fn merged_bundle_dir(&self) -> PathBuf {
+        self.log_dir.join("merged")
     }
 
     pub fn screenshot_dir(&self) -> PathBuf {
-        self.log_dir.join("screenshots")
+        self.artifact_paths().screenshots
+    }
+
+    pub fn annotations_path(&self, session_id: &str) -> PathBuf {
+        self.artifact_paths().annotations_file(session_id)
+    }
+
+    pub fn legal_holds_path(&self) -> PathBuf {
+        self.artifact_paths().legal_holds_file()
+    }
+
+    pub fn owned_files_path(&self) -> PathBuf {
+        self.log_dir.join(".owned_files")
+    }
+
+    pub fn screenshot_diff_dir(&self) -> PathBuf {
+        self.screenshot_dir().join("diffs")
+    }
+
+    pub fn durability_for(&self, kind: ArtifactKind) -> Durability {
+        self.durability
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| kind.default_durability())
+    }
+
+    pub fn recording_dir(&self) -> PathBuf {
+        self.artifact_paths().recordings
     }
 
     pub fn dom_snapshot_dir(&self) -> PathBuf {
-        self.log_dir.join("dom_snapshots")
+        self.artifact_paths().dom_snapshots
+    }
+
+    pub fn dom_upload_dir(&self) -> PathBuf {
+        self.log_dir.join(".dom_uploads")
+    }
+
+    pub fn disable_screenshots(&mut self) {
+        for profile in self.capture_profiles.values_mut() {
+            profile.enable_screenshots = false;
+        }
+    }
+
+    // Canonical directory every save and cleanup path must stay inside.
+    pub fn resolve_log_root(&self) -> Result<PathBuf, ConfigError> {
+        self.resolve_root(&self.log_dir)
+    }
+
+    // The resolved root files in `directory` are owned under: its artifact
+    // root override when it has one, `log_dir` otherwise. Overrides get the
+    // same safety checks as `log_dir`.
+    pub fn resolve_artifact_root(&self, directory: &Path) -> Result<PathBuf, ConfigError> {
+        self.resolve_root(self.artifact_paths().root_of(directory))
+    }
+
+    fn resolve_root(&self, dir: &Path) -> Result<PathBuf, ConfigError> {
+        let is_symlink = std::fs::symlink_metadata(dir)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(false);
+        if is_symlink && !self.allow_symlinked_log_dir {
+            return Err(ConfigError::UnsafeLogRoot(format!(
+                "{} is a symlink and allow_symlinked_log_dir is false",
+                dir.display()
+            )));
+        }
+
+        let root = dir.canonicalize()?;
+        if !root.is_dir() {
+            return Err(ConfigError::UnsafeLogRoot(format!(
+                "{} is not a directory",
+                root.display()
+            )));
+        }
+        if root.parent().is_none() || home_dir().is_some_and(|home| home == root) {
+            return Err(ConfigError::UnsafeLogRoot(format!(
+                "{} resolves to a shared location ({})",
+                dir.display(),
+                root.display()
+            )));
+        }
+
+        Ok(root)
     }
 
     pub fn ensure_subdirectories(&self) -> Result<(), ConfigError> {
-        std::fs::create_dir_all(self.screenshot_dir())?;
-        std::fs::create_dir_all(self.dom_snapshot_dir())?;
+        let paths = self.artifact_paths();
+        paths.validate()?;
+        for dir in [
+            &paths.log_dir,
+            &paths.console_logs,
+            &paths.snapshots,
+            &paths.screenshots,
+            &paths.dom_snapshots,
+            &paths.config_history,
+        ] {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Roots are resolved before the first write into them, so they
+        // must exist even for kinds written later or not at all.
+        for root in paths.override_roots() {
+            std::fs::create_dir_all(root)?;
+        }
+        Ok(())
+    }
+}
+
+const MAX_FILENAME_COMPONENT_BYTES: usize = 96;
+// What the documented tauri-plugin-log setup passes as `file_name`.
+pub const DEFAULT_PLUGIN_LOG_FILE_NAME: &str = "debug.log";
+
+// Makes a name safe to embed in a file name. The name is normalized to NFC,
+// so the same app name gives the same bytes on every platform; spaces,
+// path separators, characters Windows rejects, control characters and
+// bidi overrides become `_`. Anything else, CJK, emoji and RTL scripts
+// included, is kept as written. Results over the byte cap are cut on a
+// character boundary and suffixed with a hash of the full name.
+pub fn sanitize_filename(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.nfc() {
+        match c {
+            ' ' | '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => out.push('_'),
+            c if c.is_control() || is_bidi_control(c) => out.push('_'),
+            c => out.push(c),
+        }
+    }
+
+    if out.len() <= MAX_FILENAME_COMPONENT_BYTES {
+        return out;
+    }
+
+    // FNV-1a: stable across builds, unlike `DefaultHasher`.
+    let hash = out.bytes().fold(0x811c_9dc5u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    });
+    // Never split a character, or a base character from its combining marks.
+    let mut cut = MAX_FILENAME_COMPONENT_BYTES - 9;
+    while !out.is_char_boundary(cut)
+        || out[cut..]
+            .chars()
+            .next()
+            .is_some_and(unicode_normalization::char::is_combining_mark)
+    {
+        cut -= 1;
+    }
+    out.truncate(cut);
+    out.push_str(&format!("-{:08x}", hash));
+    out
+}
+
+// Embedding, override and isolate controls, which can make a name display
+// in a different order than it sorts and matches.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{200e}' | '\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+// NFC form of a file name read back from disk. macOS may hand back names
+// in NFD, so names are compared in this form.
+pub fn normalize_file_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+// Counts serialized bytes without keeping them, failing the write that
+// crosses `limit` so the serializer gives up there.
+struct BudgetedWriter {
+    written: usize,
+    limit: usize,
+}
+
+impl std::io::Write for BudgetedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written += buf.len();
+        if self.written > self.limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::WriteZero,
+                "size budget exceeded",
+            ));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
         Ok(())
     }
 }
+
+// The length of `value`'s JSON form, or `Err` with the bytes counted when
+// it passed `limit`.
+fn json_size_within(value: &serde_json::Value, limit: usize) -> Result<usize, usize> {
+    let mut writer = BudgetedWriter { written: 0, limit };
+    match serde_json::to_writer(&mut writer, value) {
+        Ok(()) => Ok(writer.written),
+        Err(_) => Err(writer.written),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .and_then(|home| PathBuf::from(home).canonicalize().ok())
+}
@@ -0,0 +1,190 @@
+// Injected into every webview by `Builder::inject_console_bridge(true)`.
+// Mirrors console output and uncaught errors into the debug-tools console log
+// for apps that do not bundle the guest-js package.
+(function () {
+  if (typeof window === "undefined" || window.__DEBUG_TOOLS_CONSOLE_BRIDGE__) {
+    return;
+  }
+  window.__DEBUG_TOOLS_CONSOLE_BRIDGE__ = true;
+
+  var LEVELS = ["log", "info", "warn", "error", "debug"];
+  var FLUSH_INTERVAL_MS = 1000;
+  var MAX_PENDING = 200;
+  // Browsers refuse beacons above 64 KiB; this leaves room for the envelope.
+  var MAX_BEACON_BYTES = 60000;
+  var SOURCE =
+    "bridge-" + Date.now().toString(36) + "-" + Math.random().toString(36).slice(2, 10);
+  var pending = [];
+  var inFlight = {};
+  var nextSeq = 0;
+  var nextBatchId = 0;
+  var dropped = 0;
+  var timer = null;
+
+  function toJson(arg) {
+    if (arg instanceof Error) {
+      return { name: arg.name, message: arg.message, stack: arg.stack || null };
+    }
+    try {
+      var encoded = JSON.stringify(arg);
+      return encoded === undefined ? String(arg) : JSON.parse(encoded);
+    } catch (_) {
+      return String(arg);
+    }
+  }
+
+  function format(args) {
+    return args
+      .map(function (arg) {
+        if (typeof arg === "string") return arg;
+        if (arg instanceof Error) return arg.name + ": " + arg.message;
+        try {
+          return JSON.stringify(arg);
+        } catch (_) {
+          return String(arg);
+        }
+      })
+      .join(" ");
+  }
+
+  function schedule() {
+    if (timer === null) {
+      timer = setTimeout(flush, FLUSH_INTERVAL_MS);
+    }
+  }
+
+  function flush() {
+    timer = null;
+    if (pending.length === 0) return;
+
+    var internals = window.__TAURI_INTERNALS__;
+    if (!internals || typeof internals.invoke !== "function") {
+      schedule();
+      return;
+    }
+
+    var batch = pending.splice(0, pending.length);
+    var batchId = nextBatchId++;
+    inFlight[batchId] = batch;
+    var settle = function () {
+      delete inFlight[batchId];
+    };
+    internals
+      .invoke("plugin:debug-tools|append_debug_logs", {
+        logs: batch,
+        dropped: dropped,
+        source: SOURCE,
+        ingestSource: "bridge",
+      })
+      .then(settle, settle);
+    // Failed batches are dropped rather than retried: logging the failure
+    // here would recurse.
+    dropped = 0;
+  }
+
+  // Sends undelivered entries, including those of unsettled flushes, in a
+  // request that outlives the page. Entries a flush also delivers are
+  // dropped by `seq` on the backend.
+  function sendFinalBeacon(reason) {
+    var internals = window.__TAURI_INTERNALS__;
+    if (!internals || typeof internals.convertFileSrc !== "function") return;
+
+    var unsettled = [];
+    Object.keys(inFlight).forEach(function (id) {
+      unsettled = unsettled.concat(inFlight[id]);
+    });
+    var entries = unsettled.concat(pending).sort(function (a, b) {
+      return a.seq - b.seq;
+    });
+    var inFlightCount = unsettled.length;
+    var truncated = 0;
+    var body;
+    for (;;) {
+      body = JSON.stringify({
+        source: SOURCE,
+        ingest_source: "bridge",
+        reason: reason,
+        entries: entries.slice(truncated),
+        state: {
+          url: location.href,
+          title: document.title,
+          visibility_state: document.visibilityState,
+          pending_entries: pending.length,
+          in_flight_entries: inFlightCount,
+          dropped_entries: dropped,
+          truncated_entries: truncated,
+        },
+      });
+      // Leaves out the oldest entries, about as many as the body is over by;
+      // UTF-16 length stands in for bytes.
+      if (body.length <= MAX_BEACON_BYTES || truncated === entries.length) break;
+      var excess = (body.length - MAX_BEACON_BYTES) / body.length;
+      truncated += Math.max(1, Math.ceil((entries.length - truncated) * excess));
+      truncated = Math.min(truncated, entries.length);
+    }
+
+    try {
+      var url = internals.convertFileSrc("final-beacon", "debug-tools");
+      if (navigator.sendBeacon && navigator.sendBeacon(url, body)) return;
+      fetch(url, { method: "POST", body: body, keepalive: true, mode: "no-cors" }).catch(
+        function () {},
+      );
+    } catch (_) {
+      // Nothing can be reported while the page unloads.
+    }
+  }
+
+  function record(level, args, stack) {
+    pending.push({
+      timestamp: Date.now(),
+      level: level,
+      message: format(args),
+      args: args.map(toJson),
+      stack_trace: stack || null,
+      seq: nextSeq++,
+    });
+    if (pending.length > MAX_PENDING) {
+      pending.shift();
+      dropped += 1;
+    }
+    schedule();
+  }
+
+  LEVELS.forEach(function (level) {
+    var original = console[level];
+    if (typeof original !== "function") return;
+    console[level] = function () {
+      var args = Array.prototype.slice.call(arguments);
+      try {
+        record(level, args);
+      } catch (_) {
+        // Never let the bridge break the caller's console.
+      }
+      return original.apply(console, arguments);
+    };
+  });
+
+  window.addEventListener("error", function (event) {
+    var error = event.error;
+    record(
+      "error",
+      [event.message || (error && error.message) || "Unhandled error"],
+      error && error.stack,
+    );
+  });
+
+  window.addEventListener("unhandledrejection", function (event) {
+    var reason = event.reason;
+    var message =
+      reason instanceof Error
+        ? reason.message
+        : typeof reason === "string"
+          ? reason
+          : "Unhandled promise rejection";
+    record("error", [message, reason], reason instanceof Error ? reason.stack : null);
+  });
+
+  window.addEventListener("pagehide", function () {
+    sendFinalBeacon("pagehide");
+  });
+})();
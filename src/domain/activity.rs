@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Counts the plugin's own routine writes so they can be reported as one
+// periodic summary instead of a log line per batch.
+#[derive(Debug, Default)]
+pub struct InternalActivity {
+    console_batches: AtomicU64,
+    console_entries: AtomicU64,
+    network_batches: AtomicU64,
+    network_entries: AtomicU64,
+    snapshots_saved: AtomicU64,
+    dom_snapshots_saved: AtomicU64,
+    // Session total, not part of the periodic summary.
+    dropped_console_entries: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActivitySummary {
+    pub console_batches: u64,
+    pub console_entries: u64,
+    pub network_batches: u64,
+    pub network_entries: u64,
+    pub snapshots_saved: u64,
+    pub dom_snapshots_saved: u64,
+}
+
+impl ActivitySummary {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl InternalActivity {
+    pub fn record_console_batch(&self, entries: usize) {
+        self.console_batches.fetch_add(1, Ordering::Relaxed);
+        self.console_entries
+            .fetch_add(entries as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_network_batch(&self, entries: usize) {
+        self.network_batches.fetch_add(1, Ordering::Relaxed);
+        self.network_entries
+            .fetch_add(entries as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_dropped_console_entries(&self, entries: u64) {
+        self.dropped_console_entries
+            .fetch_add(entries, Ordering::Relaxed);
+    }
+
+    pub fn dropped_console_entries(&self) -> u64 {
+        self.dropped_console_entries.load(Ordering::Relaxed)
+    }
+
+    pub fn record_snapshot(&self) {
+        self.snapshots_saved.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_dom_snapshot(&self) {
+        self.dom_snapshots_saved.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Returns the counts since the previous call and resets them.
+    pub fn take(&self) -> ActivitySummary {
+        ActivitySummary {
+            console_batches: self.console_batches.swap(0, Ordering::Relaxed),
+            console_entries: self.console_entries.swap(0, Ordering::Relaxed),
+            network_batches: self.network_batches.swap(0, Ordering::Relaxed),
+            network_entries: self.network_entries.swap(0, Ordering::Relaxed),
+            snapshots_saved: self.snapshots_saved.swap(0, Ordering::Relaxed),
+            dom_snapshots_saved: self.dom_snapshots_saved.swap(0, Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::domain::models::{ConsoleLogEntry, LogAnnotation};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+
+// Set on every persisted console entry; annotations refer to entries by it.
+pub const LOG_SEQ_FIELD: &str = "log_seq";
+// Where an entry's annotation appears in query results, exports and the
+// merged timeline.
+pub const ANNOTATION_FIELD: &str = "annotation";
+
+pub const MAX_ANNOTATION_NOTE_CHARS: usize = 2_000;
+pub const MAX_ANNOTATION_TAG_CHARS: usize = 64;
+pub const MAX_ANNOTATIONS_PER_SESSION: usize = 500;
+
+// Serialized with a `code` so the viewer can tell the failures apart.
+#[derive(Debug, Clone, Error, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum AnnotationError {
+    #[error("No console entry with seq {seq} in this session")]
+    UnknownSeq { seq: u64 },
+    #[error("Note is {chars} characters; at most {max} are allowed")]
+    NoteTooLong { chars: usize, max: usize },
+    #[error("Tag is {chars} characters; at most {max} are allowed")]
+    TagTooLong { chars: usize, max: usize },
+    #[error("The session already has {max} annotated entries")]
+    LimitReached { max: usize },
+    #[error("Failed to store the annotation: {message}")]
+    Storage { message: String },
+}
+
+impl AnnotationError {
+    pub fn storage(error: impl std::fmt::Display) -> Self {
+        Self::Storage {
+            message: error.to_string(),
+        }
+    }
+}
+
+// Numbers entries in the order they are written, from 1 for each process.
+#[derive(Debug, Default)]
+pub struct LogSequence {
+    last: AtomicU64,
+}
+
+impl LogSequence {
+    pub fn stamp(&self, entries: &mut [ConsoleLogEntry]) {
+        let first = self.last.fetch_add(entries.len() as u64, Ordering::Relaxed) + 1;
+        for (entry, seq) in entries.iter_mut().zip(first..) {
+            entry
+                .fields
+                .get_or_insert_with(Default::default)
+                .insert(LOG_SEQ_FIELD.to_string(), seq.into());
+        }
+    }
+}
+
+pub fn log_seq(entry: &ConsoleLogEntry) -> Option<u64> {
+    entry.fields.as_ref()?.get(LOG_SEQ_FIELD)?.as_u64()
+}
+
+// Checks the limits that do not depend on what is already stored.
+pub fn validate_annotation(note: &str, tag: Option<&str>) -> Result<(), AnnotationError> {
+    let chars = note.chars().count();
+    if chars > MAX_ANNOTATION_NOTE_CHARS {
+        return Err(AnnotationError::NoteTooLong {
+            chars,
+            max: MAX_ANNOTATION_NOTE_CHARS,
+        });
+    }
+    let chars = tag.map_or(0, |tag| tag.chars().count());
+    if chars > MAX_ANNOTATION_TAG_CHARS {
+        return Err(AnnotationError::TagTooLong {
+            chars,
+            max: MAX_ANNOTATION_TAG_CHARS,
+        });
+    }
+    Ok(())
+}
+
+pub fn attach_annotation(entry: &mut ConsoleLogEntry, annotation: &LogAnnotation) {
+    entry.fields.get_or_insert_with(Default::default).insert(
+        ANNOTATION_FIELD.to_string(),
+        serde_json::json!({
+            "note": annotation.note,
+            "tag": annotation.tag,
+            "annotated_at": annotation.annotated_at,
+        }),
+    );
+}
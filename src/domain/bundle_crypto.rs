@@ -0,0 +1,158 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::Serialize;
+use thiserror::Error;
+
+// `debug_bundle_<ts>.zip.enc` layout: the header below, then the zip sealed
+// with AES-256-GCM under a key derived from the passphrase with Argon2id.
+// The header is authenticated along with the zip, so edited KDF parameters
+// fail like a wrong passphrase.
+//
+//   magic (7) | version (1) | m_cost, t_cost, p_cost (u32 LE) | salt (16) | nonce (12)
+const MAGIC: &[u8; 7] = b"TDTBNDL";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 12 + SALT_LEN + NONCE_LEN;
+
+// Argon2id with its recommended defaults (19 MiB, 2 passes).
+const M_COST_KIB: u32 = 19 * 1024;
+const T_COST: u32 = 2;
+const P_COST: u32 = 1;
+// Imported files name their own parameters; anything above these is
+// refused instead of being allowed to exhaust memory or time. They leave
+// room over the export parameters, not for arbitrary tuning.
+const MAX_M_COST_KIB: u32 = 64 * 1024;
+const MAX_T_COST: u32 = 4;
+const MAX_P_COST: u32 = 16;
+
+// Serialized with a `code`, like the other command errors.
+#[derive(Debug, Clone, Error, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum EncryptedBundleError {
+    #[error("Passphrase must not be empty")]
+    EmptyPassphrase,
+    #[error("Not an encrypted debug bundle")]
+    NotEncrypted,
+    #[error("Unsupported encrypted bundle version {version}")]
+    UnsupportedVersion { version: u8 },
+    #[error("Encrypted bundle asks for unsupported key derivation parameters")]
+    UnsupportedParameters,
+    // AES-GCM cannot tell the two apart.
+    #[error("Wrong passphrase, or the bundle is corrupt")]
+    Decrypt,
+    #[error("Failed to derive the bundle key: {message}")]
+    Kdf { message: String },
+    #[error("{message}")]
+    Io { message: String },
+}
+
+impl EncryptedBundleError {
+    pub fn io(error: impl std::fmt::Display) -> Self {
+        Self::Io {
+            message: error.to_string(),
+        }
+    }
+}
+
+pub fn is_encrypted_bundle(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+pub fn encrypt_bundle(passphrase: &str, zip: &[u8]) -> Result<Vec<u8>, EncryptedBundleError> {
+    if passphrase.is_empty() {
+        return Err(EncryptedBundleError::EmptyPassphrase);
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let mut sealed = Vec::with_capacity(HEADER_LEN + zip.len() + 16);
+    sealed.extend_from_slice(MAGIC);
+    sealed.push(VERSION);
+    for cost in [M_COST_KIB, T_COST, P_COST] {
+        sealed.extend_from_slice(&cost.to_le_bytes());
+    }
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+
+    let cipher = cipher(passphrase, &salt, M_COST_KIB, T_COST, P_COST)?;
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: zip,
+                aad: &sealed,
+            },
+        )
+        .map_err(|_| EncryptedBundleError::io("Failed to encrypt bundle"))?;
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+pub fn decrypt_bundle(passphrase: &str, sealed: &[u8]) -> Result<Vec<u8>, EncryptedBundleError> {
+    if passphrase.is_empty() {
+        return Err(EncryptedBundleError::EmptyPassphrase);
+    }
+    if !is_encrypted_bundle(sealed) || sealed.len() < HEADER_LEN {
+        return Err(EncryptedBundleError::NotEncrypted);
+    }
+
+    let (header, ciphertext) = sealed.split_at(HEADER_LEN);
+    let version = header[MAGIC.len()];
+    if version != VERSION {
+        return Err(EncryptedBundleError::UnsupportedVersion { version });
+    }
+    let cost = |at: usize| {
+        let start = MAGIC.len() + 1 + at * 4;
+        u32::from_le_bytes([
+            header[start],
+            header[start + 1],
+            header[start + 2],
+            header[start + 3],
+        ])
+    };
+    let (m_cost, t_cost, p_cost) = (cost(0), cost(1), cost(2));
+    if m_cost > MAX_M_COST_KIB || t_cost > MAX_T_COST || p_cost > MAX_P_COST {
+        return Err(EncryptedBundleError::UnsupportedParameters);
+    }
+    let salt_at = MAGIC.len() + 1 + 12;
+    let salt = &header[salt_at..salt_at + SALT_LEN];
+    let nonce = &header[salt_at + SALT_LEN..];
+
+    cipher(passphrase, salt, m_cost, t_cost, p_cost)?
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| EncryptedBundleError::Decrypt)
+}
+
+fn cipher(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<Aes256Gcm, EncryptedBundleError> {
+    let kdf_error = |e: argon2::Error| EncryptedBundleError::Kdf {
+        message: e.to_string(),
+    };
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32)).map_err(kdf_error)?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(kdf_error)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| EncryptedBundleError::Kdf {
+        message: "derived key has the wrong length".to_string(),
+    });
+    key.fill(0);
+    cipher
+}
@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Every timestamp, filename suffix and id the plugin mints goes through these
+// two traits so hosts can substitute deterministic values in E2E tests.
+pub trait Clock: Send + Sync {
+    fn now_millis(&self) -> i64;
+
+    fn now_secs(&self) -> i64 {
+        self.now_millis().div_euclid(1000)
+    }
+}
+
+pub trait IdGenerator: Send + Sync {
+    // Used in per-process log filenames.
+    fn process_id(&self) -> u32;
+    fn session_id(&self) -> String;
+    // Monotonic per-process sequence for request and capture ids.
+    fn next_sequence(&self) -> u64;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug)]
+pub struct SystemIdGenerator {
+    session_id: String,
+    sequence: AtomicU64,
+}
+
+impl SystemIdGenerator {
+    pub fn new(clock: &dyn Clock) -> Self {
+        Self {
+            session_id: format!("{}-{}", std::process::id(), clock.now_millis()),
+            sequence: AtomicU64::new(0),
+        }
+    }
+}
+
+impl IdGenerator for SystemIdGenerator {
+    fn process_id(&self) -> u32 {
+        std::process::id()
+    }
+
+    fn session_id(&self) -> String {
+        self.session_id.clone()
+    }
+
+    fn next_sequence(&self) -> u64 {
+        self.sequence.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "test-utils")]
+pub use fixed::{FixedClock, SequentialIdGenerator};
+
+#[cfg(feature = "test-utils")]
+mod fixed {
+    use super::{Clock, IdGenerator};
+    use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+    // Returns `start_millis`, advancing by `step_millis` after every read.
+    #[derive(Debug)]
+    pub struct FixedClock {
+        now_millis: AtomicI64,
+        step_millis: i64,
+    }
+
+    impl FixedClock {
+        pub fn new(start_millis: i64) -> Self {
+            Self {
+                now_millis: AtomicI64::new(start_millis),
+                step_millis: 0,
+            }
+        }
+
+        pub fn with_step(mut self, step_millis: i64) -> Self {
+            self.step_millis = step_millis;
+            self
+        }
+
+        pub fn advance(&self, millis: i64) {
+            self.now_millis.fetch_add(millis, Ordering::SeqCst);
+        }
+
+        pub fn set(&self, millis: i64) {
+            self.now_millis.store(millis, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for FixedClock {
+        fn now_millis(&self) -> i64 {
+            self.now_millis
+                .fetch_add(self.step_millis, Ordering::SeqCst)
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct SequentialIdGenerator {
+        process_id: u32,
+        session_id: String,
+        sequence: AtomicU64,
+    }
+
+    impl SequentialIdGenerator {
+        pub fn new(session_id: impl Into<String>) -> Self {
+            Self {
+                process_id: 1,
+                session_id: session_id.into(),
+                sequence: AtomicU64::new(0),
+            }
+        }
+
+        pub fn with_process_id(mut self, process_id: u32) -> Self {
+            self.process_id = process_id;
+            self
+        }
+    }
+
+    impl Default for SequentialIdGenerator {
+        fn default() -> Self {
+            Self::new("test-session")
+        }
+    }
+
+    impl IdGenerator for SequentialIdGenerator {
+        fn process_id(&self) -> u32 {
+            self.process_id
+        }
+
+        fn session_id(&self) -> String {
+            self.session_id.clone()
+        }
+
+        fn next_sequence(&self) -> u64 {
+            self.sequence.fetch_add(1, Ordering::SeqCst)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::domain::models::ConsoleLogEntry;
+use std::sync::Mutex;
+
+// Set on entries whose timestamp is this many milliseconds behind the entry
+// persisted before them.
+pub const CLOCK_SKEW_FIELD: &str = "clock_skew_ms";
+
+// Watches the append path for device clocks jumping backwards. Only the
+// previous entry is compared, so a single jump is flagged once rather than
+// on every entry until the clock catches up.
+#[derive(Debug)]
+pub struct ClockSkewGuard {
+    tolerance_ms: i64,
+    last_timestamp: Mutex<Option<i64>>,
+}
+
+impl ClockSkewGuard {
+    pub fn new(tolerance_ms: u64) -> Self {
+        Self {
+            tolerance_ms: tolerance_ms as i64,
+            last_timestamp: Mutex::new(None),
+        }
+    }
+
+    // Returns how many entries were annotated.
+    pub fn annotate(&self, entries: &mut [ConsoleLogEntry]) -> usize {
+        let mut last = self
+            .last_timestamp
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut annotated = 0;
+
+        for entry in entries.iter_mut() {
+            if let Some(previous) = *last {
+                let behind = previous.saturating_sub(entry.timestamp);
+                if behind > self.tolerance_ms {
+                    entry
+                        .fields
+                        .get_or_insert_with(Default::default)
+                        .insert(CLOCK_SKEW_FIELD.to_string(), behind.into());
+                    annotated += 1;
+                }
+            }
+            *last = Some(entry.timestamp);
+        }
+
+        annotated
+    }
+}
+
+pub fn has_clock_skew(entry: &ConsoleLogEntry) -> bool {
+    entry
+        .fields
+        .as_ref()
+        .is_some_and(|fields| fields.contains_key(CLOCK_SKEW_FIELD))
+}
@@ -0,0 +1,130 @@
+use crate::domain::models::{ConsoleLogEntry, ConsoleSource, LogLevel, INGEST_SOURCE_FIELD};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+// Two pathways stamp the same console call within a few milliseconds of
+// each other; apps forwarding logs themselves may take longer.
+const CROSS_SOURCE_EPSILON_MS: i64 = 250;
+// Entries remembered for matching, oldest forgotten first.
+const MAX_REMEMBERED_ENTRIES: usize = 4096;
+
+#[derive(Debug, Default)]
+struct Remembered {
+    // Level and message hash -> (timestamp, source), oldest first.
+    by_key: HashMap<u64, VecDeque<(i64, ConsoleSource)>>,
+    order: VecDeque<u64>,
+    dropped: BTreeMap<ConsoleSource, u64>,
+}
+
+// Drops console entries from a non-authoritative pathway when another
+// pathway already delivered the same call. Entries from authoritative
+// pathways are always kept, so a copy that arrives before its
+// authoritative twin stays too.
+#[derive(Debug)]
+pub struct CrossSourceDedup {
+    authoritative: Vec<ConsoleSource>,
+    remembered: Mutex<Remembered>,
+}
+
+impl CrossSourceDedup {
+    // An empty list keeps every pathway authoritative.
+    pub fn new(authoritative: &[ConsoleSource]) -> Self {
+        let authoritative = if authoritative.is_empty() {
+            ConsoleSource::LIVE.to_vec()
+        } else {
+            authoritative.to_vec()
+        };
+        Self {
+            authoritative,
+            remembered: Mutex::new(Remembered::default()),
+        }
+    }
+
+    pub fn authoritative(&self) -> &[ConsoleSource] {
+        &self.authoritative
+    }
+
+    // Returns how many entries were dropped.
+    pub fn filter(&self, source: ConsoleSource, entries: &mut Vec<ConsoleLogEntry>) -> usize {
+        let all_authoritative = ConsoleSource::LIVE
+            .iter()
+            .all(|live| self.authoritative.contains(live));
+        if all_authoritative || !ConsoleSource::LIVE.contains(&source) {
+            return 0;
+        }
+
+        let keep_all = self.authoritative.contains(&source);
+        let mut remembered = self.remembered.lock().unwrap_or_else(|e| e.into_inner());
+        let before = entries.len();
+        entries.retain(|entry| {
+            let key = entry_key(entry);
+            let duplicate = remembered.by_key.get(&key).is_some_and(|seen| {
+                seen.iter().any(|(timestamp, seen_source)| {
+                    *seen_source != source
+                        && (timestamp - entry.timestamp).abs() <= CROSS_SOURCE_EPSILON_MS
+                })
+            });
+            if duplicate && !keep_all {
+                return false;
+            }
+            remembered.remember(key, entry.timestamp, source);
+            true
+        });
+
+        let dropped = before - entries.len();
+        if dropped > 0 {
+            *remembered.dropped.entry(source).or_default() += dropped as u64;
+        }
+        dropped
+    }
+
+    pub fn dropped(&self) -> BTreeMap<ConsoleSource, u64> {
+        self.remembered
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .dropped
+            .clone()
+    }
+}
+
+impl Remembered {
+    fn remember(&mut self, key: u64, timestamp: i64, source: ConsoleSource) {
+        if self.order.len() >= MAX_REMEMBERED_ENTRIES {
+            if let Some(oldest) = self.order.pop_front() {
+                if let Some(seen) = self.by_key.get_mut(&oldest) {
+                    seen.pop_front();
+                    if seen.is_empty() {
+                        self.by_key.remove(&oldest);
+                    }
+                }
+            }
+        }
+        self.by_key
+            .entry(key)
+            .or_default()
+            .push_back((timestamp, source));
+        self.order.push_back(key);
+    }
+}
+
+pub fn tag_ingest_source(entries: &mut [ConsoleLogEntry], source: ConsoleSource) {
+    for entry in entries {
+        entry
+            .fields
+            .get_or_insert_with(Default::default)
+            .insert(INGEST_SOURCE_FIELD.to_string(), source.as_str().into());
+    }
+}
+
+// Level aliases such as `warning` match their canonical name.
+fn entry_key(entry: &ConsoleLogEntry) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    match LogLevel::from_str_lenient(&entry.level) {
+        Some(level) => level.as_str().hash(&mut hasher),
+        None => entry.level.to_ascii_lowercase().hash(&mut hasher),
+    }
+    entry.message.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,61 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+// Collectors remembered at once; reloads start a new one, so the oldest are
+// forgotten first.
+const MAX_TRACKED_SOURCES: usize = 64;
+
+#[derive(Debug, Default)]
+struct Tracked {
+    // Inclusive, sorted and non-adjacent ranges of delivered sequence numbers.
+    sources: HashMap<String, Vec<(u64, u64)>>,
+    order: VecDeque<String>,
+}
+
+// Sequence numbers of frontend entries already written, per collector. A
+// collector that flushes in order costs a single range. Lets a final beacon
+// and a regular flush that completed after all carry the same entries
+// without writing them twice.
+#[derive(Debug, Default)]
+pub struct DeliveredSeqs {
+    tracked: Mutex<Tracked>,
+}
+
+impl DeliveredSeqs {
+    // Records every `seq` and returns whether each was new.
+    pub fn claim(&self, source: &str, seqs: &[u64]) -> Vec<bool> {
+        let mut tracked = self.tracked.lock().unwrap_or_else(|e| e.into_inner());
+        if !tracked.sources.contains_key(source) {
+            if tracked.order.len() >= MAX_TRACKED_SOURCES {
+                if let Some(oldest) = tracked.order.pop_front() {
+                    tracked.sources.remove(&oldest);
+                }
+            }
+            tracked.order.push_back(source.to_string());
+        }
+
+        let ranges = tracked.sources.entry(source.to_string()).or_default();
+        seqs.iter().map(|seq| insert(ranges, *seq)).collect()
+    }
+}
+
+fn insert(ranges: &mut Vec<(u64, u64)>, seq: u64) -> bool {
+    // First range starting after `seq`; the one before it may contain it.
+    let next = ranges.partition_point(|(start, _)| *start <= seq);
+    if next > 0 && ranges[next - 1].1 >= seq {
+        return false;
+    }
+
+    let joins_previous = next > 0 && ranges[next - 1].1 + 1 == seq;
+    let joins_next = next < ranges.len() && ranges[next].0 == seq + 1;
+    match (joins_previous, joins_next) {
+        (true, true) => {
+            ranges[next - 1].1 = ranges[next].1;
+            ranges.remove(next);
+        }
+        (true, false) => ranges[next - 1].1 = seq,
+        (false, true) => ranges[next].0 = seq,
+        (false, false) => ranges.insert(next, (seq, seq)),
+    }
+    true
+}
@@ -0,0 +1,34 @@
+use crate::domain::models::DeprecatedSurfaceUse;
+use std::sync::Mutex;
+
+// Legacy command surfaces the frontend has used this session, in order of
+// first use.
+#[derive(Debug, Default)]
+pub struct DeprecationTracker {
+    used: Mutex<Vec<DeprecatedSurfaceUse>>,
+}
+
+impl DeprecationTracker {
+    // True on the first use of `surface` this session.
+    pub fn record(&self, surface: &str, replacement: &str, now_ms: i64) -> bool {
+        let mut used = self.used.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = used.iter_mut().find(|entry| entry.surface == surface) {
+            existing.calls += 1;
+            existing.last_used_at = now_ms;
+            return false;
+        }
+
+        used.push(DeprecatedSurfaceUse {
+            surface: surface.to_string(),
+            replacement: replacement.to_string(),
+            first_used_at: now_ms,
+            last_used_at: now_ms,
+            calls: 1,
+        });
+        true
+    }
+
+    pub fn used(&self) -> Vec<DeprecatedSurfaceUse> {
+        self.used.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
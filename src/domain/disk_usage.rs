@@ -0,0 +1,100 @@
+use crate::domain::models::{
+    CleanupSuggestion, ConsoleLogEntry, DiskCategory, DiskUsage, DiskUsageWarning, LogLevel,
+};
+use std::sync::Mutex;
+
+// Shares of the limit that trigger a warning, ascending.
+pub const DISK_USAGE_THRESHOLDS: [f64; 2] = [0.7, 0.9];
+
+// What `clear_debug_log_files_command`, the only bulk cleanup command,
+// empties.
+const CLEARED_BY_CLEAR_COMMAND: &[DiskCategory] = &[
+    DiskCategory::ConsoleLogs,
+    DiskCategory::NetworkLogs,
+    DiskCategory::BackendLogs,
+    DiskCategory::Screenshots,
+    DiskCategory::DomSnapshots,
+];
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    latest: Option<DiskUsage>,
+    above: [bool; DISK_USAGE_THRESHOLDS.len()],
+}
+
+// Keeps the latest measurement and warns once per threshold crossing; a
+// threshold re-arms when usage drops back below it.
+#[derive(Debug, Default)]
+pub struct DiskUsageMonitor {
+    state: Mutex<MonitorState>,
+}
+
+impl DiskUsageMonitor {
+    // The warning names the highest threshold crossed since the last
+    // measurement.
+    pub fn observe(&self, usage: DiskUsage) -> Option<DiskUsageWarning> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut crossed = None;
+        for (above, threshold) in state.above.iter_mut().zip(DISK_USAGE_THRESHOLDS) {
+            let now_above = usage.usage_ratio.is_some_and(|ratio| ratio >= threshold);
+            if now_above && !*above {
+                crossed = Some(threshold);
+            }
+            *above = now_above;
+        }
+
+        let warning = crossed.map(|threshold| DiskUsageWarning {
+            threshold,
+            suggested_cleanup: suggest_cleanup(&usage),
+            usage: usage.clone(),
+        });
+        state.latest = Some(usage);
+        warning
+    }
+
+    pub fn latest(&self) -> Option<DiskUsage> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .latest
+            .clone()
+    }
+}
+
+pub fn suggest_cleanup(usage: &DiskUsage) -> Option<CleanupSuggestion> {
+    let frees_bytes: u64 = usage
+        .categories
+        .iter()
+        .filter(|category| CLEARED_BY_CLEAR_COMMAND.contains(&category.category))
+        .map(|category| category.bytes)
+        .sum();
+    (frees_bytes > 0).then(|| CleanupSuggestion {
+        command: "clear_debug_log_files_command".to_string(),
+        args: serde_json::json!({}),
+        frees_bytes,
+    })
+}
+
+impl DiskUsageWarning {
+    pub fn to_log_entry(&self) -> ConsoleLogEntry {
+        let mut fields = serde_json::Map::new();
+        fields.insert("disk_usage_warning".to_string(), true.into());
+        fields.insert("threshold".to_string(), self.threshold.into());
+        fields.insert("total_bytes".to_string(), self.usage.total_bytes.into());
+        fields.insert("limit_bytes".to_string(), self.usage.limit_bytes.into());
+
+        ConsoleLogEntry {
+            timestamp: self.usage.measured_at,
+            level: LogLevel::Warn.to_string(),
+            message: format!(
+                "[disk-usage] debug directory is at {:.0}% of its limit ({} of {} bytes)",
+                self.usage.usage_ratio.unwrap_or(self.threshold) * 100.0,
+                self.usage.total_bytes,
+                self.usage.limit_bytes.unwrap_or_default()
+            ),
+            args: serde_json::to_value(&self.usage.categories).unwrap_or_default(),
+            stack_trace: None,
+            fields: Some(fields),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::domain::models::DomDeltaOp;
+use similar::{capture_diff_slices_deadline, Algorithm, DiffOp};
+use std::time::{Duration, Instant};
+
+// Past this the changed middle is stored as one coarser replacement rather
+// than holding up the capture command.
+const DIFF_DEADLINE: Duration = Duration::from_millis(200);
+
+// Serialized HTML is often a single line, so it is compared tag by tag:
+// every token ends after a `>` (or at the end of the input).
+fn tokenize(html: &str) -> Vec<&str> {
+    html.split_inclusive('>').collect()
+}
+
+// Computes the ops that turn `baseline` into `current`. The ops always cover
+// the whole baseline, so `apply_delta` can verify it is given the right one.
+pub fn diff_dom(baseline: &str, current: &str) -> Vec<DomDeltaOp> {
+    let a = tokenize(baseline);
+    let b = tokenize(current);
+    let deadline = Instant::now().checked_add(DIFF_DEADLINE);
+
+    let mut ops = DeltaBuilder::default();
+    for op in capture_diff_slices_deadline(Algorithm::Myers, &a, &b, deadline) {
+        match op {
+            DiffOp::Equal { old_index, len, .. } => {
+                ops.keep(byte_len(&a[old_index..old_index + len]));
+            }
+            DiffOp::Delete {
+                old_index, old_len, ..
+            } => ops.delete(byte_len(&a[old_index..old_index + old_len])),
+            DiffOp::Insert {
+                new_index, new_len, ..
+            } => ops.insert(&b[new_index..new_index + new_len]),
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => {
+                ops.delete(byte_len(&a[old_index..old_index + old_len]));
+                ops.insert(&b[new_index..new_index + new_len]);
+            }
+        }
+    }
+    ops.finish()
+}
+
+// Returns `None` when the ops do not line up with `baseline`.
+pub fn apply_delta(baseline: &str, ops: &[DomDeltaOp]) -> Option<String> {
+    let mut out = String::with_capacity(baseline.len());
+    let mut pos = 0usize;
+
+    for op in ops {
+        match op {
+            DomDeltaOp::Keep { bytes } => {
+                let end = pos.checked_add(*bytes)?;
+                out.push_str(baseline.get(pos..end)?);
+                pos = end;
+            }
+            DomDeltaOp::Delete { bytes } => {
+                let end = pos.checked_add(*bytes)?;
+                baseline.get(pos..end)?;
+                pos = end;
+            }
+            DomDeltaOp::Insert { text } => out.push_str(text),
+        }
+    }
+
+    (pos == baseline.len()).then_some(out)
+}
+
+fn byte_len(tokens: &[&str]) -> usize {
+    tokens.iter().map(|token| token.len()).sum()
+}
+
+#[derive(Default)]
+struct DeltaBuilder {
+    ops: Vec<DomDeltaOp>,
+}
+
+impl DeltaBuilder {
+    fn keep(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        match self.ops.last_mut() {
+            Some(DomDeltaOp::Keep { bytes }) => *bytes += n,
+            _ => self.ops.push(DomDeltaOp::Keep { bytes: n }),
+        }
+    }
+
+    fn delete(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        match self.ops.last_mut() {
+            Some(DomDeltaOp::Delete { bytes }) => *bytes += n,
+            _ => self.ops.push(DomDeltaOp::Delete { bytes: n }),
+        }
+    }
+
+    fn insert(&mut self, tokens: &[&str]) {
+        if tokens.is_empty() {
+            return;
+        }
+        match self.ops.last_mut() {
+            Some(DomDeltaOp::Insert { text }) => text.extend(tokens.iter().copied()),
+            _ => self.ops.push(DomDeltaOp::Insert {
+                text: tokens.concat(),
+            }),
+        }
+    }
+
+    fn finish(self) -> Vec<DomDeltaOp> {
+        self.ops
+    }
+}
@@ -0,0 +1,199 @@
+use crate::domain::models::{
+    ConsoleLogEntry, ErrorAlarmEvent, ErrorAlarmStatus, ErrorFingerprintCount, LogLevel,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+const TOP_FINGERPRINTS: usize = 5;
+const MAX_FINGERPRINT_CHARS: usize = 120;
+
+pub type ErrorAlarmNotifier = Box<dyn Fn(&ErrorAlarmEvent) + Send + Sync>;
+
+#[derive(Debug, Default)]
+struct AlarmState {
+    // (timestamp ms, fingerprint), oldest first.
+    recent: VecDeque<(i64, String)>,
+    last_fired_at: Option<i64>,
+    firings: u64,
+}
+
+// Counts error-level entries from the frontend and the backend in a sliding
+// window and fires once the threshold is reached, then stays silent for the
+// cooldown. The notifier is installed once the app handle exists.
+pub struct ErrorAlarm {
+    threshold_count: usize,
+    window_ms: i64,
+    cooldown_ms: i64,
+    state: Mutex<AlarmState>,
+    notifier: OnceLock<ErrorAlarmNotifier>,
+}
+
+impl ErrorAlarm {
+    pub fn new(threshold_count: usize, window_secs: u64, cooldown_secs: u64) -> Self {
+        Self {
+            threshold_count: threshold_count.max(1),
+            window_ms: window_secs.max(1) as i64 * 1000,
+            cooldown_ms: cooldown_secs as i64 * 1000,
+            state: Mutex::new(AlarmState::default()),
+            notifier: OnceLock::new(),
+        }
+    }
+
+    pub fn set_notifier(&self, notifier: ErrorAlarmNotifier) {
+        let _ = self.notifier.set(notifier);
+    }
+
+    pub fn observe_entries(&self, logs: &[ConsoleLogEntry], now_ms: i64) {
+        let errors = logs
+            .iter()
+            .filter(|entry| LogLevel::from_str_lenient(&entry.level) == Some(LogLevel::Error))
+            .map(|entry| (entry.timestamp, fingerprint(&entry.message)));
+        self.observe(errors, now_ms);
+    }
+
+    pub fn observe_backend_error(&self, message: &str, now_ms: i64) {
+        self.observe(std::iter::once((now_ms, fingerprint(message))), now_ms);
+    }
+
+    pub fn status(&self, now_ms: i64) -> ErrorAlarmStatus {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let window_start = now_ms - self.window_ms;
+
+        ErrorAlarmStatus {
+            threshold_count: self.threshold_count,
+            window_secs: (self.window_ms / 1000) as u64,
+            cooldown_secs: (self.cooldown_ms / 1000) as u64,
+            errors_in_window: state
+                .recent
+                .iter()
+                .filter(|(ts, _)| *ts > window_start)
+                .count(),
+            firings: state.firings,
+            last_fired_at: state.last_fired_at,
+            cooling_down: state
+                .last_fired_at
+                .is_some_and(|fired| now_ms < fired + self.cooldown_ms),
+        }
+    }
+
+    fn observe(&self, errors: impl Iterator<Item = (i64, String)>, now_ms: i64) {
+        let event = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let window_start = now_ms - self.window_ms;
+
+            let mut added = false;
+            for (ts, fingerprint) in errors {
+                if ts > window_start {
+                    state.recent.push_back((ts, fingerprint));
+                    added = true;
+                }
+            }
+            while state
+                .recent
+                .front()
+                .is_some_and(|(ts, _)| *ts <= window_start)
+            {
+                state.recent.pop_front();
+            }
+
+            let cooling_down = state
+                .last_fired_at
+                .is_some_and(|fired| now_ms < fired + self.cooldown_ms);
+            if !added || cooling_down || state.recent.len() < self.threshold_count {
+                return;
+            }
+
+            state.last_fired_at = Some(now_ms);
+            state.firings += 1;
+            self.event(&state, now_ms)
+        };
+
+        // Called without the lock held: the notifier may log, which can feed
+        // backend errors straight back into `observe`.
+        if let Some(notifier) = self.notifier.get() {
+            notifier(&event);
+        }
+    }
+
+    fn event(&self, state: &AlarmState, now_ms: i64) -> ErrorAlarmEvent {
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for (_, fingerprint) in &state.recent {
+            *counts.entry(fingerprint.as_str()).or_default() += 1;
+        }
+
+        let mut top: Vec<ErrorFingerprintCount> = counts
+            .into_iter()
+            .map(|(fingerprint, count)| ErrorFingerprintCount {
+                fingerprint: fingerprint.to_string(),
+                count,
+            })
+            .collect();
+        top.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.fingerprint.cmp(&b.fingerprint))
+        });
+        top.truncate(TOP_FINGERPRINTS);
+
+        ErrorAlarmEvent {
+            fired_at: now_ms,
+            window_secs: (self.window_ms / 1000) as u64,
+            threshold_count: self.threshold_count,
+            errors_in_window: state.recent.len(),
+            top_fingerprints: top,
+        }
+    }
+}
+
+// Groups errors that differ only in ids, counts or positions: digit runs
+// collapse to `#` and the result is trimmed to a bounded length.
+pub fn fingerprint(message: &str) -> String {
+    let first_line = message.lines().next().unwrap_or_default().trim();
+    let mut out = String::new();
+    let mut chars = 0;
+    let mut in_digits = false;
+
+    for c in first_line.chars() {
+        if chars >= MAX_FINGERPRINT_CHARS {
+            break;
+        }
+        if c.is_ascii_digit() {
+            if in_digits {
+                continue;
+            }
+            out.push('#');
+            in_digits = true;
+        } else {
+            out.push(c);
+            in_digits = false;
+        }
+        chars += 1;
+    }
+
+    out
+}
+
+impl ErrorAlarmEvent {
+    // Warn rather than error, so the marker never counts towards the alarm.
+    pub fn to_log_entry(&self) -> ConsoleLogEntry {
+        let mut fields = serde_json::Map::new();
+        fields.insert("error_alarm".to_string(), true.into());
+        fields.insert(
+            "errors_in_window".to_string(),
+            (self.errors_in_window as u64).into(),
+        );
+        fields.insert("window_secs".to_string(), self.window_secs.into());
+
+        ConsoleLogEntry {
+            timestamp: self.fired_at,
+            level: LogLevel::Warn.to_string(),
+            message: format!(
+                "[error-alarm] {} errors in {}s (threshold {})",
+                self.errors_in_window, self.window_secs, self.threshold_count
+            ),
+            args: serde_json::to_value(&self.top_fingerprints).unwrap_or_default(),
+            stack_trace: None,
+            fields: Some(fields),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::domain::models::{ConsoleLogEntry, ErrorRateReport, ErrorRateWindow, LogLevel};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+// Sliding counter of error-level console entries, bucketed by entry time so
+// batched appends land in the window the errors actually happened in.
+#[derive(Debug)]
+pub struct ErrorRateTracker {
+    window_secs: i64,
+    window_count: usize,
+    buckets: Mutex<BTreeMap<i64, u64>>,
+}
+
+impl ErrorRateTracker {
+    pub fn new(window_secs: u64, window_count: usize) -> Self {
+        Self {
+            window_secs: window_secs.max(1) as i64,
+            window_count: window_count.max(1),
+            buckets: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn record(&self, logs: &[ConsoleLogEntry], now: i64) {
+        let oldest = self.oldest_window_start(now);
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        for entry in logs {
+            if LogLevel::from_str_lenient(&entry.level) != Some(LogLevel::Error) {
+                continue;
+            }
+            // Console timestamps are milliseconds.
+            let start = self.window_start(entry.timestamp / 1000);
+            if start >= oldest {
+                *buckets.entry(start).or_default() += 1;
+            }
+        }
+
+        buckets.retain(|start, _| *start >= oldest);
+    }
+
+    pub fn report(&self, now: i64, windows: usize) -> ErrorRateReport {
+        let windows = windows.clamp(1, self.window_count);
+        let current = self.window_start(now);
+        let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let per_minute = 60.0 / self.window_secs as f64;
+
+        let windows: Vec<ErrorRateWindow> = (0..windows as i64)
+            .map(|offset| {
+                let start = current - offset * self.window_secs;
+                let errors = buckets.get(&start).copied().unwrap_or_default();
+                ErrorRateWindow {
+                    start,
+                    errors,
+                    errors_per_minute: errors as f64 * per_minute,
+                }
+            })
+            .collect();
+
+        // The current window is still filling up, so rate it by elapsed time.
+        let elapsed = (now - current + 1).max(1) as f64;
+        let current_errors_per_minute = windows
+            .first()
+            .map(|window| window.errors as f64 * 60.0 / elapsed)
+            .unwrap_or_default();
+
+        ErrorRateReport {
+            window_secs: self.window_secs as u64,
+            current_errors_per_minute,
+            windows,
+        }
+    }
+
+    fn window_start(&self, secs: i64) -> i64 {
+        secs.div_euclid(self.window_secs) * self.window_secs
+    }
+
+    fn oldest_window_start(&self, now: i64) -> i64 {
+        self.window_start(now) - (self.window_count as i64 - 1) * self.window_secs
+    }
+}
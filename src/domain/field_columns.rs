@@ -0,0 +1,79 @@
+use crate::domain::models::ConsoleLogEntry;
+
+// Renderers for exports that have a fixed set of columns. Entry `fields`
+// named in `export_field_columns` become one column each, in config order;
+// strings are written as is, other values as JSON, and a missing field as an
+// empty cell.
+
+fn field_text(entry: &ConsoleLogEntry, name: &str) -> Option<String> {
+    let value = entry.fields.as_ref()?.get(name)?;
+    Some(match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    })
+}
+
+fn csv_cell(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn csv_header(field_columns: &[String]) -> String {
+    let mut header = String::from("timestamp,level,message,stack_trace");
+    for column in field_columns {
+        header.push(',');
+        header.push_str(&csv_cell(column));
+    }
+    header.push('\n');
+    header
+}
+
+pub fn render_csv_row(entry: &ConsoleLogEntry, field_columns: &[String]) -> String {
+    let mut row = format!(
+        "{},{},{},{}",
+        entry.timestamp,
+        csv_cell(&entry.level),
+        csv_cell(&entry.message),
+        csv_cell(entry.stack_trace.as_deref().unwrap_or_default()),
+    );
+    for column in field_columns {
+        row.push(',');
+        row.push_str(&csv_cell(&field_text(entry, column).unwrap_or_default()));
+    }
+    row.push('\n');
+    row
+}
+
+// Chrome trace event format, which Perfetto and `chrome://tracing` open:
+// each entry is a global instant event named after its message, categorised
+// by level, with the selected fields as its args.
+pub fn timeline_head() -> &'static str {
+    "{\"displayTimeUnit\":\"ms\",\"traceEvents\":[\n"
+}
+
+pub fn timeline_tail() -> &'static str {
+    "\n]}\n"
+}
+
+pub fn render_timeline_event(
+    entry: &ConsoleLogEntry,
+    field_columns: &[String],
+) -> Result<String, serde_json::Error> {
+    let args: serde_json::Map<String, serde_json::Value> = field_columns
+        .iter()
+        .filter_map(|column| Some((column.clone(), field_text(entry, column)?.into())))
+        .collect();
+    serde_json::to_string(&serde_json::json!({
+        "name": entry.message,
+        "cat": entry.level,
+        "ph": "i",
+        "s": "g",
+        "ts": entry.timestamp.saturating_mul(1000),
+        "pid": 1,
+        "tid": 1,
+        "args": args,
+    }))
+}
@@ -0,0 +1,121 @@
+use crate::domain::annotations::ANNOTATION_FIELD;
+use crate::domain::models::{ConsoleLogEntry, LogLevel};
+use crate::domain::text_summary::format_utc;
+
+// Self-contained page: styles and the level filter are inline so the file
+// can be attached to an issue and opened anywhere.
+const REPORT_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Console log export</title>
+<style>
+body { font: 13px/1.4 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; margin: 0; background: #fafafa; color: #222; }
+header { position: sticky; top: 0; padding: 8px 12px; background: #fff; border-bottom: 1px solid #ddd; }
+header label { margin-right: 12px; }
+table { border-collapse: collapse; width: 100%; }
+td { padding: 3px 8px; border-bottom: 1px solid #eee; vertical-align: top; white-space: pre-wrap; word-break: break-word; }
+td.time { color: #777; white-space: nowrap; }
+td.level { font-weight: bold; text-transform: uppercase; white-space: nowrap; }
+tr.level-error { background: #fdecea; } tr.level-error td.level { color: #c62828; }
+tr.level-warn { background: #fff8e1; } tr.level-warn td.level { color: #b26a00; }
+tr.level-info td.level { color: #1565c0; }
+tr.level-debug td.level { color: #6a1b9a; }
+tr.level-trace td.level, tr.level-unknown td.level { color: #777; }
+pre.stack { margin: 4px 0 0; color: #555; }
+tr.annotated td:first-child { box-shadow: inset 4px 0 #2e7d32; }
+div.annotation { margin: 4px 0 0; padding: 2px 6px; background: #e8f5e9; color: #1b5e20; }
+div.annotation b { margin-right: 6px; }
+</style>
+</head>
+<body>
+<header>
+<label><input type="checkbox" data-level="error" checked> error</label>
+<label><input type="checkbox" data-level="warn" checked> warn</label>
+<label><input type="checkbox" data-level="info" checked> info</label>
+<label><input type="checkbox" data-level="debug" checked> debug</label>
+<label><input type="checkbox" data-level="trace" checked> trace</label>
+<label><input type="checkbox" data-level="unknown" checked> other</label>
+</header>
+<table>
+<tbody>
+"#;
+
+const REPORT_TAIL: &str = r#"</tbody>
+</table>
+<script>
+document.querySelectorAll("header input[data-level]").forEach(function (box) {
+  box.addEventListener("change", function () {
+    var rows = document.querySelectorAll("tr.level-" + box.dataset.level);
+    for (var i = 0; i < rows.length; i++) rows[i].hidden = !box.checked;
+  });
+});
+</script>
+</body>
+</html>
+"#;
+
+pub fn html_report_head() -> &'static str {
+    REPORT_HEAD
+}
+
+pub fn html_report_tail() -> &'static str {
+    REPORT_TAIL
+}
+
+// One table row per entry, classed by severity so the filter and colours
+// apply. Unrecognised levels get `level-unknown`; annotated entries are
+// also classed `annotated` and show the note under the message.
+pub fn render_html_row(entry: &ConsoleLogEntry) -> String {
+    let class =
+        LogLevel::from_str_lenient(&entry.level).map_or("level-unknown", LogLevel::css_class);
+    let annotation = entry
+        .fields
+        .as_ref()
+        .and_then(|fields| fields.get(ANNOTATION_FIELD));
+    let mut row = format!(
+        "<tr class=\"{}{}\"><td class=\"time\">{}</td><td class=\"level\">{}</td><td>{}",
+        class,
+        if annotation.is_some() {
+            " annotated"
+        } else {
+            ""
+        },
+        format_utc(entry.timestamp),
+        escape_html(&entry.level),
+        escape_html(&entry.message),
+    );
+    if let Some(stack) = entry.stack_trace.as_deref().filter(|s| !s.is_empty()) {
+        row.push_str("<pre class=\"stack\">");
+        row.push_str(&escape_html(stack));
+        row.push_str("</pre>");
+    }
+    if let Some(annotation) = annotation {
+        let text = |key: &str| annotation.get(key).and_then(|value| value.as_str());
+        row.push_str("<div class=\"annotation\">");
+        if let Some(tag) = text("tag") {
+            row.push_str("<b>");
+            row.push_str(&escape_html(tag));
+            row.push_str("</b>");
+        }
+        row.push_str(&escape_html(text("note").unwrap_or_default()));
+        row.push_str("</div>");
+    }
+    row.push_str("</td></tr>\n");
+    row
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
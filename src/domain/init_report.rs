@@ -0,0 +1,150 @@
+use crate::domain::models::{InitPhase, PluginInitReport};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Instant;
+
+// Times the setup phases in order. Each runs inside a `plugin_init` span, so
+// a subscriber the app installed before the plugin sees them too.
+#[derive(Debug)]
+pub struct InitRecorder {
+    started: Instant,
+    phases: Vec<InitPhase>,
+}
+
+impl Default for InitRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InitRecorder {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    pub fn phase<T>(&mut self, name: &str, run: impl FnOnce() -> T) -> T {
+        let phase = self.start(name);
+        let value = run();
+        self.end(phase);
+        value
+    }
+
+    // For phases too long to wrap in a closure.
+    pub fn start(&self, name: &str) -> PhaseTimer {
+        PhaseTimer {
+            name: name.to_string(),
+            started: Instant::now(),
+            _span: tracing::debug_span!("plugin_init", phase = name).entered(),
+        }
+    }
+
+    pub fn end(&mut self, phase: PhaseTimer) {
+        self.phases.push(InitPhase {
+            name: phase.name,
+            duration_ms: elapsed_ms(phase.started),
+            thread: current_thread_name(),
+            fallback: None,
+            background: false,
+        });
+    }
+
+    // Marks the phase that just ran.
+    pub fn fallback(&mut self, reason: impl Into<String>) {
+        if let Some(phase) = self.phases.last_mut() {
+            phase.fallback = Some(reason.into());
+        }
+    }
+
+    pub fn finish(self, started_at: i64) -> PluginInitReport {
+        PluginInitReport {
+            started_at,
+            total_ms: elapsed_ms(self.started),
+            thread: current_thread_name(),
+            phases: self.phases,
+        }
+    }
+}
+
+#[must_use = "end the phase to record it"]
+pub struct PhaseTimer {
+    name: String,
+    started: Instant,
+    _span: tracing::span::EnteredSpan,
+}
+
+// The finished report, in managed state.
+#[derive(Debug)]
+pub struct InitReportCell {
+    report: RwLock<PluginInitReport>,
+    embedded: AtomicBool,
+}
+
+impl InitReportCell {
+    // Placeholder until setup finishes; background phases may land first.
+    pub fn pending(started_at: i64) -> Self {
+        Self {
+            report: RwLock::new(PluginInitReport {
+                started_at,
+                total_ms: 0.0,
+                thread: current_thread_name(),
+                phases: Vec::new(),
+            }),
+            embedded: AtomicBool::new(false),
+        }
+    }
+
+    // Keeps the background phases already recorded.
+    pub fn complete(&self, mut report: PluginInitReport) {
+        let mut current = self.report.write().unwrap_or_else(|e| e.into_inner());
+        report
+            .phases
+            .extend(current.phases.drain(..).filter(|phase| phase.background));
+        *current = report;
+    }
+
+    pub fn report(&self) -> PluginInitReport {
+        self.report
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn add_background_phase(&self, name: &str, started: Instant, fallback: Option<String>) {
+        let phase = InitPhase {
+            name: name.to_string(),
+            duration_ms: elapsed_ms(started),
+            thread: current_thread_name(),
+            fallback,
+            background: true,
+        };
+        tracing::info!(
+            phase = %phase.name,
+            duration_ms = phase.duration_ms,
+            "Plugin init phase finished in background"
+        );
+        self.report
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .phases
+            .push(phase);
+    }
+
+    // The report for the first snapshot assembled; `None` afterwards.
+    pub fn take_for_snapshot(&self) -> Option<PluginInitReport> {
+        (!self.embedded.swap(true, Ordering::Relaxed)).then(|| self.report())
+    }
+}
+
+pub fn current_thread_name() -> String {
+    let thread = std::thread::current();
+    thread
+        .name()
+        .map_or_else(|| format!("{:?}", thread.id()), str::to_string)
+}
+
+fn elapsed_ms(started: Instant) -> f64 {
+    started.elapsed().as_secs_f64() * 1000.0
+}
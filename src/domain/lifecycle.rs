@@ -0,0 +1,67 @@
+use crate::domain::models::{ConsoleLogEntry, LifecycleEvent, LifecycleEventKind, LogLevel};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+const RECENT_CAPACITY: usize = 50;
+
+// Most recent lifecycle markers, embedded into snapshots. The markers are
+// also persisted as console log entries so they show up in log queries.
+#[derive(Debug, Default)]
+pub struct LifecycleTracker {
+    recent: Mutex<VecDeque<LifecycleEvent>>,
+}
+
+impl LifecycleTracker {
+    pub fn push(&self, event: LifecycleEvent) {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() == RECENT_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(event);
+    }
+
+    pub fn recent(&self) -> Vec<LifecycleEvent> {
+        self.recent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+impl LifecycleEvent {
+    pub fn to_log_entry(&self) -> ConsoleLogEntry {
+        let mut fields = serde_json::Map::new();
+        fields.insert(
+            "lifecycle".to_string(),
+            serde_json::to_value(self.kind).unwrap_or_default(),
+        );
+        if let Some(label) = &self.window_label {
+            fields.insert("window_label".to_string(), label.clone().into());
+        }
+        if let Some(gap_ms) = self.gap_ms {
+            fields.insert("gap_ms".to_string(), gap_ms.into());
+        }
+
+        let kind = fields["lifecycle"].as_str().unwrap_or_default().to_string();
+        let message = match (&self.window_label, self.gap_ms) {
+            (_, Some(gap_ms)) => format!("[lifecycle] {} (gap {}ms)", kind, gap_ms),
+            (Some(label), None) => format!("[lifecycle] {} ({})", kind, label),
+            (None, None) => format!("[lifecycle] {}", kind),
+        };
+
+        ConsoleLogEntry {
+            timestamp: self.timestamp,
+            level: match self.kind {
+                LifecycleEventKind::SuspectedSuspend => LogLevel::Warn,
+                _ => LogLevel::Info,
+            }
+            .to_string(),
+            message,
+            args: serde_json::Value::Null,
+            stack_trace: None,
+            fields: Some(fields),
+        }
+    }
+}
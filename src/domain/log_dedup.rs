@@ -0,0 +1,46 @@
+use crate::domain::models::{ConsoleLogEntry, DeduplicatedLog};
+use std::collections::HashMap;
+
+// Collapses entries of a batch that repeat the same level, message and stack
+// trace into their first occurrence, which records `repeat_count` and
+// `last_timestamp` in its fields. Returns what collapsed, in batch order.
+pub fn dedup_entries(logs: &mut Vec<ConsoleLogEntry>) -> Vec<DeduplicatedLog> {
+    let mut first_seen: HashMap<(String, String, Option<String>), usize> = HashMap::new();
+    let mut kept: Vec<(ConsoleLogEntry, u64, i64)> = Vec::with_capacity(logs.len());
+
+    for entry in logs.drain(..) {
+        let key = (
+            entry.level.clone(),
+            entry.message.clone(),
+            entry.stack_trace.clone(),
+        );
+        match first_seen.get(&key) {
+            Some(&index) => {
+                let (_, count, last) = &mut kept[index];
+                *count += 1;
+                *last = (*last).max(entry.timestamp);
+            }
+            None => {
+                first_seen.insert(key, kept.len());
+                let timestamp = entry.timestamp;
+                kept.push((entry, 1, timestamp));
+            }
+        }
+    }
+
+    let mut deduplicated = Vec::new();
+    for (mut entry, count, last_timestamp) in kept {
+        if count > 1 {
+            let fields = entry.fields.get_or_insert_with(Default::default);
+            fields.insert("repeat_count".to_string(), count.into());
+            fields.insert("last_timestamp".to_string(), last_timestamp.into());
+            deduplicated.push(DeduplicatedLog {
+                message: entry.message.clone(),
+                count,
+            });
+        }
+        logs.push(entry);
+    }
+
+    deduplicated
+}
@@ -0,0 +1,81 @@
+use crate::domain::models::{ConsoleLogEntry, LogLevel};
+use serde_json::{Map, Value};
+
+// Maps level names and numeric levels used by common JSONL loggers (pino,
+// bunyan, syslog-style names) onto the plugin's levels.
+fn import_level(value: Option<&Value>) -> Result<LogLevel, String> {
+    let Some(value) = value else {
+        return Ok(LogLevel::Info);
+    };
+
+    if let Some(number) = value.as_i64() {
+        return Ok(match number {
+            i64::MIN..=10 => LogLevel::Trace,
+            11..=20 => LogLevel::Debug,
+            21..=30 => LogLevel::Info,
+            31..=40 => LogLevel::Warn,
+            _ => LogLevel::Error,
+        });
+    }
+
+    let Some(name) = value.as_str() else {
+        return Err("level is not a string or number".to_string());
+    };
+    if name.trim().is_empty() {
+        return Ok(LogLevel::Info);
+    }
+    LogLevel::from_str_lenient(name).ok_or_else(|| format!("unknown level `{}`", name.trim()))
+}
+
+// Validates one JSONL line against the `ConsoleLogEntry` shape and tags it
+// with where it came from. Only `timestamp` (milliseconds) is mandatory.
+pub fn parse_imported_entry(
+    line: &str,
+    source_label: &str,
+    session_id: &str,
+) -> Result<ConsoleLogEntry, String> {
+    let value: Value = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {}", e))?;
+    let Value::Object(mut object) = value else {
+        return Err("line is not a JSON object".to_string());
+    };
+
+    let timestamp = match object.get("timestamp") {
+        Some(Value::Number(number)) => number
+            .as_i64()
+            .or_else(|| number.as_f64().map(|ms| ms as i64))
+            .ok_or_else(|| "timestamp is out of range".to_string())?,
+        Some(_) => return Err("timestamp is not a number".to_string()),
+        None => return Err("missing timestamp".to_string()),
+    };
+
+    let level = import_level(object.get("level"))?;
+
+    let message = match object.remove("message").or_else(|| object.remove("msg")) {
+        Some(Value::String(message)) => message,
+        Some(other) => other.to_string(),
+        None => String::new(),
+    };
+
+    let stack_trace = match object.remove("stack_trace") {
+        Some(Value::String(stack)) => Some(stack),
+        Some(Value::Null) | None => None,
+        Some(_) => return Err("stack_trace is not a string".to_string()),
+    };
+
+    let mut fields = match object.remove("fields") {
+        Some(Value::Object(fields)) => fields,
+        Some(Value::Null) | None => Map::new(),
+        Some(_) => return Err("fields is not an object".to_string()),
+    };
+    fields.insert("source".to_string(), source_label.into());
+    fields.insert("session_id".to_string(), session_id.into());
+
+    Ok(ConsoleLogEntry {
+        timestamp,
+        level: level.as_str().to_string(),
+        message,
+        args: object.remove("args").unwrap_or(Value::Array(Vec::new())),
+        stack_trace,
+        fields: Some(fields),
+    })
+}
@@ -1,5 +1,66 @@
+pub mod activity;
+pub mod annotations;
+pub mod bundle_crypto;
+pub mod clock;
+pub mod clock_skew;
+pub mod console_sources;
+pub mod delivered_seqs;
+pub mod deprecation;
+pub mod disk_usage;
+pub mod dom_diff;
+pub mod error_alarm;
+pub mod error_rate;
+pub mod field_columns;
+pub mod html_report;
+pub mod init_report;
+pub mod lifecycle;
+pub mod log_dedup;
+pub mod log_import;
 pub mod models;
+pub mod moments;
+pub mod plugin_log;
 pub mod ports;
+pub mod privacy;
+pub mod quiet_mode;
+pub mod screenshot_delete;
+pub mod screenshot_region;
+pub mod snapshot_read;
+pub mod strict_mode;
+pub mod text_summary;
+pub mod trace_context;
+pub mod user_agents;
 
+pub use activity::{ActivitySummary, InternalActivity};
+pub use annotations::{AnnotationError, LogSequence};
+pub use bundle_crypto::{decrypt_bundle, encrypt_bundle, EncryptedBundleError};
+pub use clock::{Clock, IdGenerator, SystemClock, SystemIdGenerator};
+#[cfg(feature = "test-utils")]
+pub use clock::{FixedClock, SequentialIdGenerator};
+pub use clock_skew::ClockSkewGuard;
+pub use console_sources::CrossSourceDedup;
+pub use delivered_seqs::DeliveredSeqs;
+pub use deprecation::DeprecationTracker;
+pub use disk_usage::DiskUsageMonitor;
+pub use dom_diff::{apply_delta, diff_dom};
+pub use error_alarm::ErrorAlarm;
+pub use error_rate::ErrorRateTracker;
+pub use field_columns::{
+    csv_header, render_csv_row, render_timeline_event, timeline_head, timeline_tail,
+};
+pub use html_report::{html_report_head, html_report_tail, render_html_row};
+pub use init_report::{InitRecorder, InitReportCell};
+pub use lifecycle::LifecycleTracker;
+pub use log_dedup::dedup_entries;
+pub use log_import::parse_imported_entry;
 pub use models::*;
+pub use moments::DebugMoments;
 pub use ports::*;
+pub use privacy::PrivacyControl;
+pub use quiet_mode::QuietMode;
+pub use screenshot_delete::ScreenshotDeleteError;
+pub use screenshot_region::ScreenshotRegionError;
+pub use snapshot_read::SnapshotReadError;
+pub use strict_mode::StrictMode;
+pub use text_summary::{render_text_summary, TextSummaryInput};
+pub use trace_context::TraceContext;
+pub use user_agents::UserAgents;
@@ -1,7 +1,10 @@
+use crate::domain::trace_context::fields_match_trace;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WebViewState {
     pub url: String,
     pub title: String,
@@ -9,19 +12,22 @@ pub struct WebViewState {
     pub viewport: ViewportInfo,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ViewportInfo {
     pub width: u32,
     pub height: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConsoleLogEntry {
     pub timestamp: i64,
     pub level: String,
     pub message: String,
     pub args: serde_json::Value,
     pub stack_trace: Option<String>,
+    // App-defined structured metadata (e.g. `userId`, `requestId`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,15 +37,741 @@ pub struct DomState {
     pub title: String,
     pub viewport: ViewportInfo,
     pub captured_at: i64,
+    pub shadow_dom_included: bool,
+    pub replacement_chars: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DebugSnapshot {
     pub timestamp: i64,
     pub webview_state: WebViewState,
+    // Defaulted so snapshots from versions that omitted empty parts still
+    // load as typed rather than as legacy payloads.
+    #[serde(default)]
     pub console_logs: Vec<ConsoleLogEntry>,
+    #[serde(default)]
     pub screenshot_path: Option<PathBuf>,
+    #[serde(default)]
     pub dom_snapshot_path: Option<PathBuf>,
+    #[serde(default)]
+    pub system_info: Option<SystemInfo>,
+    #[serde(default)]
+    pub security_context: Option<SecurityContext>,
+    #[serde(default)]
+    pub effective_config_path: Option<PathBuf>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub build_info: Option<BuildInfo>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub resources: Vec<ResourceTiming>,
+    #[serde(default)]
+    pub recent_lifecycle_events: Vec<LifecycleEvent>,
+    // Logical router state reported by the frontend (route, params, stack).
+    #[serde(default)]
+    pub route_state: Option<serde_json::Value>,
+    #[serde(default)]
+    pub size_report: Option<CaptureSizeReport>,
+    #[serde(default)]
+    pub clock_skew: Option<ClockSkewMeasurement>,
+    // Typed snapshots written before the field existed count as current.
+    #[serde(default = "DebugSnapshot::current_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub form_state: Option<FormState>,
+    #[serde(default)]
+    pub storage_quota: Option<StorageQuota>,
+    // Added after capture by `annotate_snapshot` (assignee, severity, ...).
+    #[serde(default)]
+    pub annotations: serde_json::Map<String, serde_json::Value>,
+    // Set when `screenshot_path` links an earlier capture under
+    // `ScreenshotPolicy::ReuseWithin` instead of a new one; other snapshots
+    // may reference the same file.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub screenshot_reused: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screenshot_age_secs: Option<u64>,
+}
+
+// Where a full capture's screenshot comes from when the caller passes none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ScreenshotPolicy {
+    AlwaysCapture,
+    // Link the newest full screenshot at most `secs` old, capturing only
+    // when there is none.
+    ReuseWithin {
+        secs: u64,
+    },
+    // No screenshot, as before policies existed.
+    #[default]
+    Never,
+}
+
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+// Raw payloads from the untyped `write_debug_snapshot`, wrapped on load.
+pub const LEGACY_SNAPSHOT_SCHEMA_VERSION: u32 = 0;
+
+impl DebugSnapshot {
+    pub fn is_crash(&self) -> bool {
+        self.label.as_deref() == Some("crash")
+    }
+
+    fn current_schema_version() -> u32 {
+        SNAPSHOT_SCHEMA_VERSION
+    }
+
+    // Best-effort adapter for payloads that match no known schema: the raw
+    // value is kept under `extensions.legacy_payload` and only the url and
+    // title are lifted out when they happen to be strings.
+    pub fn from_legacy(payload: serde_json::Value, timestamp: i64) -> Self {
+        let text = |key: &str| {
+            payload
+                .get(key)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let webview_state = WebViewState {
+            url: text("url"),
+            title: text("title"),
+            user_agent: String::new(),
+            viewport: ViewportInfo {
+                width: 0,
+                height: 0,
+            },
+        };
+
+        let mut extensions = serde_json::Map::new();
+        extensions.insert("legacy_payload".to_string(), payload);
+
+        Self {
+            timestamp,
+            webview_state,
+            console_logs: Vec::new(),
+            screenshot_path: None,
+            dom_snapshot_path: None,
+            system_info: None,
+            security_context: None,
+            effective_config_path: None,
+            session_id: None,
+            build_info: None,
+            label: None,
+            note: None,
+            extensions,
+            resources: Vec::new(),
+            recent_lifecycle_events: Vec::new(),
+            route_state: None,
+            size_report: None,
+            clock_skew: None,
+            schema_version: LEGACY_SNAPSHOT_SCHEMA_VERSION,
+            form_state: None,
+            storage_quota: None,
+            annotations: serde_json::Map::new(),
+            screenshot_reused: false,
+            screenshot_age_secs: None,
+        }
+    }
+
+    // The route a snapshot is indexed under: `route`, falling back to `path`.
+    pub fn route_key(route_state: &serde_json::Value) -> Option<String> {
+        ["route", "path"]
+            .iter()
+            .find_map(|key| route_state.get(key)?.as_str())
+            .map(str::to_string)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotSort {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SnapshotListEntry {
+    pub path: PathBuf,
+    // Seconds, from the file name for typed and legacy snapshots alike.
+    pub timestamp: i64,
+    pub size_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_report: Option<CaptureSizeReport>,
+    // Written by the untyped `write_debug_snapshot`.
+    #[serde(default)]
+    pub legacy: bool,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub annotations: serde_json::Map<String, serde_json::Value>,
+}
+
+// What a snapshot panel shows per file, read from the file itself. A file
+// that does not parse is listed with `error` set and the other fields empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSummary {
+    pub path: PathBuf,
+    pub timestamp: i64,
+    pub size_bytes: u64,
+    pub legacy: bool,
+    pub url: Option<String>,
+    pub title: Option<String>,
+    pub label: Option<String>,
+    pub console_log_count: usize,
+    // Whether the referenced files are still on disk.
+    pub has_screenshot: bool,
+    pub has_dom_snapshot: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// Bytes taken by each part of a full capture. The snapshot JSON includes the
+// embedded console logs and extensions; screenshot and DOM are separate files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CaptureSizeReport {
+    pub snapshot_json_bytes: u64,
+    pub console_logs_bytes: u64,
+    pub extensions_bytes: u64,
+    pub screenshot_bytes: Option<u64>,
+    pub dom_bytes: Option<u64>,
+    pub total_bytes: u64,
+}
+
+// Per-component sizes above which a capture logs a warning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureSizeThresholds {
+    pub snapshot_json_bytes: u64,
+    pub console_logs_bytes: u64,
+    pub extensions_bytes: u64,
+    pub screenshot_bytes: u64,
+    pub dom_bytes: u64,
+}
+
+impl Default for CaptureSizeThresholds {
+    fn default() -> Self {
+        Self {
+            snapshot_json_bytes: 20 * 1024 * 1024,
+            console_logs_bytes: 10 * 1024 * 1024,
+            extensions_bytes: 2 * 1024 * 1024,
+            screenshot_bytes: 10 * 1024 * 1024,
+            dom_bytes: 20 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotPage {
+    pub entries: Vec<SnapshotListEntry>,
+    pub total: usize,
+}
+
+// The newest entries of the session's console log, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleLogTail {
+    pub entries: Vec<ConsoleLogEntry>,
+    // Well-formed entries in the file, before `limit` and `offset`.
+    pub total: usize,
+    // Lines that did not parse as an entry and were skipped.
+    pub malformed_lines: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotCapturedEvent {
+    pub path: PathBuf,
+    pub timestamp: i64,
+    pub label: Option<String>,
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleEventKind {
+    Focus,
+    Blur,
+    Visible,
+    Hidden,
+    SuspectedSuspend,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LifecycleEvent {
+    // Milliseconds, like console log entries.
+    pub timestamp: i64,
+    pub kind: LifecycleEventKind,
+    pub window_label: Option<String>,
+    // Wall-clock time unaccounted for by the housekeeping tick.
+    pub gap_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavigationSnapshotEvent {
+    pub webview_label: String,
+    pub url: String,
+    pub snapshot_timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BuildInfo {
+    pub app_name: String,
+    pub app_version: String,
+    pub plugin_version: String,
+    pub debug_build: bool,
+}
+
+// Frontend-supplied subset of `DebugSnapshot`; the backend fills in the rest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PartialDebugSnapshot {
+    #[serde(default)]
+    pub console_logs: Vec<ConsoleLogEntry>,
+    #[serde(default)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+    pub label: Option<String>,
+    pub note: Option<String>,
+    pub webview_state: Option<WebViewState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SnapshotFieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+const MAX_SNAPSHOT_LABEL_CHARS: usize = 200;
+
+impl PartialDebugSnapshot {
+    pub fn validate(&self) -> Vec<SnapshotFieldError> {
+        let mut errors = Vec::new();
+        let mut fail =
+            |field: String, message: String| errors.push(SnapshotFieldError { field, message });
+
+        if let Some(label) = &self.label {
+            if label.trim().is_empty() {
+                fail("label".into(), "must not be blank".into());
+            } else if label.chars().count() > MAX_SNAPSHOT_LABEL_CHARS {
+                fail(
+                    "label".into(),
+                    format!("must be at most {} characters", MAX_SNAPSHOT_LABEL_CHARS),
+                );
+            }
+        }
+
+        for (index, entry) in self.console_logs.iter().enumerate() {
+            if LogLevel::from_str_lenient(&entry.level).is_none() {
+                fail(
+                    format!("console_logs[{}].level", index),
+                    format!("unknown log level {:?}", entry.level),
+                );
+            }
+            if entry.timestamp < 0 {
+                fail(
+                    format!("console_logs[{}].timestamp", index),
+                    "must not be negative".into(),
+                );
+            }
+        }
+
+        if self.extensions.keys().any(|key| key.trim().is_empty()) {
+            fail("extensions".into(), "keys must not be blank".into());
+        }
+
+        errors
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CspViolation {
+    pub timestamp: i64,
+    pub blocked_uri: String,
+    pub violated_directive: String,
+    pub effective_directive: String,
+    pub original_policy: String,
+    pub disposition: String,
+    pub source_file: Option<String>,
+    pub line_number: Option<u32>,
+    pub column_number: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SecurityContext {
+    pub captured_at: i64,
+    pub url: String,
+    pub content_security_policy: Vec<String>,
+    pub referrer_policy: Option<String>,
+    pub is_secure_context: bool,
+    pub violations: Vec<CspViolation>,
+}
+
+// One `PerformanceResourceTiming` entry. Sizes are 0 for cross-origin
+// resources without `Timing-Allow-Origin`; `response_status` is only
+// reported by some engines.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResourceTiming {
+    pub url: String,
+    pub initiator_type: String,
+    pub start_time_ms: f64,
+    pub duration_ms: f64,
+    #[serde(default)]
+    pub transfer_size: u64,
+    #[serde(default)]
+    pub encoded_body_size: u64,
+    #[serde(default)]
+    pub response_status: Option<u16>,
+}
+
+// Latest state reported by the frontend through the `capture_*` round-trip
+// commands; embedded into the next full debug snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct FrontendReports {
+    pub security_context: Option<SecurityContext>,
+    pub renderer: Option<FrontendRendererInfo>,
+    pub resources: Option<Vec<ResourceTiming>>,
+    pub route_state: Option<serde_json::Value>,
+    pub clock_skew: Option<ClockSkewMeasurement>,
+    pub form_state: Option<FormState>,
+    pub storage_quota: Option<StorageQuota>,
+    // Adaptive flush interval sent with the latest console log batch.
+    pub console_flush_interval_ms: Option<u64>,
+}
+
+// `navigator.storage.estimate()` as reported by `capture_storage_quota`.
+// WebViews without the API report `supported: false` and no figures.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StorageQuota {
+    pub captured_at: i64,
+    pub supported: bool,
+    #[serde(default)]
+    pub usage_bytes: Option<u64>,
+    #[serde(default)]
+    pub quota_bytes: Option<u64>,
+    // Per-store breakdown (`indexedDB`, `caches`, ...) where the engine has one.
+    #[serde(default)]
+    pub usage_details: Option<serde_json::Map<String, serde_json::Value>>,
+    // `navigator.storage.persisted()`, when available.
+    #[serde(default)]
+    pub persisted: Option<bool>,
+}
+
+impl StorageQuota {
+    pub fn usage_ratio(&self) -> Option<f64> {
+        match (self.usage_bytes, self.quota_bytes) {
+            (Some(usage), Some(quota)) if quota > 0 => Some(usage as f64 / quota as f64),
+            _ => None,
+        }
+    }
+}
+
+// Form field values as entered, reported by `capture_form_state`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FormState {
+    pub captured_at: i64,
+    pub url: String,
+    pub fields: Vec<FormFieldValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FormFieldValue {
+    // `id` or `name` of the enclosing form, if any.
+    #[serde(default)]
+    pub form: Option<String>,
+    pub name: String,
+    // Input `type`, or the tag name for `select` and `textarea`.
+    pub field_type: String,
+    pub value: String,
+    #[serde(default)]
+    pub masked: bool,
+}
+
+impl FormFieldValue {
+    pub const MASK: &'static str = "********";
+
+    pub fn mask(&mut self) {
+        self.value = Self::MASK.to_string();
+        self.masked = true;
+    }
+}
+
+// Backend minus frontend clock, as last measured by `check_clock_skew`.
+// Positive means the frontend clock is behind.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ClockSkewMeasurement {
+    pub frontend_timestamp: i64,
+    pub backend_timestamp: i64,
+    pub skew_ms: i64,
+    pub within_tolerance: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CaptureContext {
+    pub frontend_reports: FrontendReports,
+    pub effective_config_path: Option<PathBuf>,
+    pub session_id: Option<String>,
+    pub platform_gpu: Option<PlatformGpuInfo>,
+    pub lifecycle_events: Vec<LifecycleEvent>,
+    pub strip_url_queries: bool,
+    pub plugin_init: Option<PluginInitReport>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SystemInfo {
+    pub repository_metrics: Option<RepositoryMetricsReport>,
+    #[serde(default)]
+    pub renderer: Option<RendererInfo>,
+    // Only in the session's first snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_init: Option<PluginInitReport>,
+}
+
+// Where the plugin's setup spent its time. Phases after setup returned,
+// such as the screenshots plugin registration, are appended as they finish.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PluginInitReport {
+    pub started_at: i64,
+    // The setup closure only; background phases are not included.
+    pub total_ms: f64,
+    pub thread: String,
+    pub phases: Vec<InitPhase>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InitPhase {
+    pub name: String,
+    pub duration_ms: f64,
+    pub thread: String,
+    // What the phase did instead of its usual path, if anything.
+    #[serde(default)]
+    pub fallback: Option<String>,
+    #[serde(default)]
+    pub background: bool,
+}
+
+// WebGL strings as seen by the WebView; unmasked values require the
+// `WEBGL_debug_renderer_info` extension and are often withheld.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FrontendRendererInfo {
+    pub reported_at: i64,
+    pub webgl_version: Option<String>,
+    pub vendor: Option<String>,
+    pub renderer: Option<String>,
+    pub unmasked_vendor: Option<String>,
+    pub unmasked_renderer: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GpuAdapterInfo {
+    pub name: Option<String>,
+    pub vendor_id: Option<String>,
+    pub device_id: Option<String>,
+    pub driver: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PlatformGpuInfo {
+    pub os: String,
+    pub arch: String,
+    pub adapters: Vec<GpuAdapterInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RendererInfo {
+    pub frontend: Option<FrontendRendererInfo>,
+    pub platform: Option<PlatformGpuInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugEnvironment {
+    pub session_id: String,
+    pub build_info: BuildInfo,
+    pub renderer: RendererInfo,
+    pub storage: StorageStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilesystemKind {
+    Local,
+    Network,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilesystemInfo {
+    pub kind: FilesystemKind,
+    pub fs_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageStatus {
+    pub log_dir: PathBuf,
+    pub filesystem: FilesystemInfo,
+    // Copy+delete instead of rename, and longer retries on transient errors.
+    pub conservative_writes: bool,
+    pub warning: Option<String>,
+}
+
+// What the files under `log_dir` are, for disk usage accounting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskCategory {
+    ConsoleLogs,
+    NetworkLogs,
+    BackendLogs,
+    Snapshots,
+    Screenshots,
+    DomSnapshots,
+    Recordings,
+    Exports,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryUsage {
+    pub category: DiskCategory,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskLimitSource {
+    // `max_total_disk_bytes`.
+    Quota,
+    // The directory's usage plus the space still free on its filesystem.
+    FreeDisk,
+}
+
+// Measured by the housekeeping task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsage {
+    pub measured_at: i64,
+    pub total_bytes: u64,
+    // `None` when there is no quota and free space could not be read.
+    pub limit_bytes: Option<u64>,
+    pub limit_source: Option<DiskLimitSource>,
+    pub usage_ratio: Option<f64>,
+    // Largest first.
+    pub categories: Vec<CategoryUsage>,
+}
+
+// A command call that should free `frees_bytes`, give or take held and
+// foreign files it leaves alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupSuggestion {
+    pub command: String,
+    pub args: serde_json::Value,
+    pub frees_bytes: u64,
+}
+
+// Payload of `debug-tools://disk-usage-warning`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsageWarning {
+    pub threshold: f64,
+    pub usage: DiskUsage,
+    pub suggested_cleanup: Option<CleanupSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LatencyBucket {
+    // Upper bound in milliseconds; `None` is the overflow bucket.
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OperationMetrics {
+    pub calls: u64,
+    pub errors: u64,
+    pub bytes_written: u64,
+    pub avg_latency_us: u64,
+    pub max_latency_us: u64,
+    pub latency_histogram: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RepositoryMetricsReport {
+    pub save_snapshot: OperationMetrics,
+    pub save_dom: OperationMetrics,
+    pub save_console_logs: OperationMetrics,
+    // Time spent making writes durable, per level. Empty for backends that
+    // do not report it.
+    #[serde(default)]
+    pub durability: Vec<DurabilityLatency>,
+    // Reuse of the file repository's snapshot and DOM serialization
+    // buffers. Missing from reports of older versions.
+    #[serde(default)]
+    pub buffer_pool: Option<BufferPoolStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BufferPoolStats {
+    // Captures that reused a pooled buffer, and those that allocated anew.
+    pub hits: u64,
+    pub misses: u64,
+    pub pooled_buffers: usize,
+    pub pooled_bytes: u64,
+    pub max_pooled_bytes: u64,
+}
+
+// What `FileSystemRepository` does after writing an artifact: `None` leaves
+// the data to the OS, `Flush` flushes the file handle, `Fsync` syncs the file
+// and, after a rename, its directory, so the write survives a power loss.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Durability {
+    #[default]
+    None,
+    Flush,
+    Fsync,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Snapshot,
+    // A snapshot labelled `crash` (see `DebugSnapshot::is_crash`).
+    CrashSnapshot,
+    DomSnapshot,
+    ConsoleLog,
+    NetworkLog,
+}
+
+impl ArtifactKind {
+    pub const ALL: [ArtifactKind; 5] = [
+        ArtifactKind::Snapshot,
+        ArtifactKind::CrashSnapshot,
+        ArtifactKind::DomSnapshot,
+        ArtifactKind::ConsoleLog,
+        ArtifactKind::NetworkLog,
+    ];
+
+    pub fn default_durability(self) -> Durability {
+        match self {
+            ArtifactKind::CrashSnapshot => Durability::Fsync,
+            ArtifactKind::Snapshot | ArtifactKind::DomSnapshot => Durability::Flush,
+            ArtifactKind::ConsoleLog | ArtifactKind::NetworkLog => Durability::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DurabilityLatency {
+    pub durability: Durability,
+    pub writes: u64,
+    pub avg_sync_us: u64,
+    pub max_sync_us: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,10 +780,1166 @@ pub struct DomSnapshotResult {
     pub metadata: DomSnapshotMetadata,
 }
 
+// Byte counts refer to the baseline HTML without its metadata comment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum DomDeltaOp {
+    Keep { bytes: usize },
+    Delete { bytes: usize },
+    Insert { text: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DomDelta {
+    pub timestamp: i64,
+    pub url: String,
+    pub title: String,
+    pub baseline_path: PathBuf,
+    pub baseline_timestamp: i64,
+    pub ops: Vec<DomDeltaOp>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomDeltaResult {
+    pub path: PathBuf,
+    pub baseline_path: PathBuf,
+    // Set when no baseline existed yet and this capture was saved in full as one.
+    pub baseline_created: bool,
+    pub inserted_bytes: usize,
+    pub deleted_bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconstructedDom {
+    pub html: String,
+    pub url: String,
+    pub title: String,
+    pub timestamp: i64,
+    pub baseline_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomSearchQuery {
+    pub query: String,
+    // Literal queries match case-insensitively; regexes are used as given.
+    #[serde(default)]
+    pub regex: bool,
+    // Snapshot timestamps in seconds; `until` is exclusive.
+    #[serde(default)]
+    pub since: Option<i64>,
+    #[serde(default)]
+    pub until: Option<i64>,
+    // Maximum number of files with matches, newest first.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomSearchMatch {
+    // Byte offset into the html, after the metadata comment.
+    pub offset: u64,
+    pub context: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomSearchFileResult {
+    pub path: PathBuf,
+    pub timestamp: i64,
+    pub url: Option<String>,
+    pub match_count: u64,
+    // First few matches only; `match_count` covers the whole file.
+    pub matches: Vec<DomSearchMatch>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomSearchSkippedFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomSearchReport {
+    pub results: Vec<DomSearchFileResult>,
+    pub files_scanned: usize,
+    pub skipped: Vec<DomSearchSkippedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DomSnapshotMetadata {
     pub url: String,
     pub title: String,
     pub timestamp: i64,
     pub viewport: ViewportInfo,
+    #[serde(default)]
+    pub shadow_dom_included: bool,
+    // U+FFFD characters in the html, i.e. content that was not valid UTF-16/UTF-8
+    // before it reached the backend.
+    #[serde(default)]
+    pub replacement_chars: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogExportResult {
+    pub path: PathBuf,
+    pub entries: usize,
+    // Entries whose stack trace was rewritten through a source map.
+    #[serde(default)]
+    pub resolved_stack_traces: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedBundleExport {
+    pub path: PathBuf,
+    // Artifacts in the bundle, not counting its manifest.
+    pub files: usize,
+    pub zip_bytes: u64,
+    pub encrypted_bytes: u64,
+}
+
+// A decrypted bundle, written as a `.zip` that `merge_debug_bundles` reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedBundle {
+    pub path: PathBuf,
+    // Archive entries, the manifest included.
+    pub entries: usize,
+    pub zip_bytes: u64,
+}
+
+// What `shutdown_debug_tools` (or the exit hook) flushed and wrote. Steps
+// that did not apply are `None`/`false`; failures do not stop later steps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownSummary {
+    // `command` or `exit`.
+    pub reason: String,
+    pub started_at: i64,
+    pub duration_ms: u64,
+    // Backend log events emitted to the frontend from the pending batch.
+    pub forwarded_events: usize,
+    // Files removed by the final retention pass; `None` without a policy.
+    pub retention_removed: Option<usize>,
+    pub export: Option<LogExportResult>,
+    // Chunked DOM uploads still open; their chunks are deleted.
+    pub abandoned_dom_uploads: usize,
+    pub tracing_flushed: bool,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeduplicatedLog {
+    pub message: String,
+    // Occurrences in the batch, including the one that was kept.
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppendLogsResult {
+    pub path: String,
+    #[serde(default)]
+    pub deduplicated: Vec<DeduplicatedLog>,
+    // Oldest entries of this batch dropped for exceeding `max_queued_entries`.
+    #[serde(default)]
+    pub dropped: usize,
+    // Entries already written by an earlier flush or a final beacon.
+    #[serde(default)]
+    pub duplicates: usize,
+}
+
+// What the page reported about itself while being torn down.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FinalBeaconState {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub visibility_state: Option<String>,
+    // Entries never sent, and sent without an answer yet.
+    #[serde(default)]
+    pub pending_entries: usize,
+    #[serde(default)]
+    pub in_flight_entries: usize,
+    // Lost to the queue limit, or left out to keep the beacon small.
+    #[serde(default)]
+    pub dropped_entries: u64,
+    #[serde(default)]
+    pub truncated_entries: usize,
+}
+
+// `final_beacon_<ms>.json`, written for each final beacon received. Its
+// entries go to the console log, marked with a `final_beacon` field.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FinalBeaconRecord {
+    pub final_beacon: bool,
+    pub source: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub received_at: i64,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    pub state: FinalBeaconState,
+    // Sequence numbers the beacon carried.
+    #[serde(default)]
+    pub first_seq: Option<u64>,
+    #[serde(default)]
+    pub last_seq: Option<u64>,
+    pub written_entries: usize,
+    pub duplicate_entries: usize,
+}
+
+// `append_debug_logs` answers with the bare log path, as it used to, when
+// `compat_mode` is on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppendLogsResponse {
+    Result(AppendLogsResult),
+    LegacyPath(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecatedSurfaceUse {
+    // Command name, with the legacy behaviour in parentheses where the
+    // command itself is current.
+    pub surface: String,
+    pub replacement: String,
+    pub first_used_at: i64,
+    pub last_used_at: i64,
+    pub calls: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationReport {
+    pub compat_mode: bool,
+    pub surfaces: Vec<DeprecatedSurfaceUse>,
+}
+
+// Console log pipeline settings the frontend collector applies, and what
+// it has lost so far this session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogPipelineStats {
+    pub flush_interval_ms: u64,
+    pub min_flush_interval_ms: u64,
+    pub max_flush_interval_ms: u64,
+    pub flush_byte_threshold: usize,
+    pub flush_burst_entries_per_sec: u32,
+    pub flush_batch_size: usize,
+    pub max_queued_entries: usize,
+    // Dropped from the frontend queue or from oversized batches.
+    pub dropped_entries: u64,
+    // Authoritative pathways, and the duplicates dropped from the others.
+    pub console_sources: Vec<ConsoleSource>,
+    pub cross_source_duplicates: BTreeMap<ConsoleSource, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSkippedLine {
+    // 1-based line number in the imported file.
+    pub line: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogImportResult {
+    pub path: PathBuf,
+    pub source_label: String,
+    pub imported: usize,
+    pub skipped: usize,
+    // The first skipped lines only; `skipped` has the full count.
+    pub skipped_lines: Vec<ImportSkippedLine>,
+}
+
+// A note on one console entry, kept in `annotations_<session>.jsonl` apart
+// from the log it refers to. The latest line per `seq` wins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LogAnnotation {
+    pub session_id: String,
+    // The entry's `log_seq` field.
+    pub seq: u64,
+    // File name of the console log holding the entry.
+    pub log_file: String,
+    pub note: String,
+    #[serde(default)]
+    pub tag: Option<String>,
+    pub annotated_at: i64,
+}
+
+// A named point in the session from `mark_debug_moment`, written as a
+// marker entry so logs, timelines and snapshots can be aligned on it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DebugMoment {
+    pub id: String,
+    pub label: String,
+    pub timestamp: i64,
+}
+
+// Bytes `start..end` of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+// Outcome of scanning a console log for lines that do not parse. Kept per
+// file in `repaired_logs.json`, which readers consult to use
+// `repaired_path` in place of `path`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LogRepairReport {
+    pub path: PathBuf,
+    // `None` when the file was intact and nothing was written.
+    pub repaired_path: Option<PathBuf>,
+    // Length of `path` when it was scanned. Readers take anything appended
+    // since from the original.
+    pub source_bytes: u64,
+    pub kept_entries: usize,
+    pub discarded_lines: usize,
+    pub discarded_bytes: u64,
+    // Adjacent discarded lines are merged; the first ranges only.
+    pub discarded_ranges: Vec<ByteRange>,
+    pub repaired_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogImportProgress {
+    pub path: PathBuf,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestCheck {
+    pub check: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+// `/healthz` body: the last self-test's checks and an overall verdict.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthSummary {
+    pub healthy: bool,
+    pub failed: usize,
+    pub session_id: String,
+    // When `run_self_test` produced `checks`; `None` until it has run.
+    pub checked_at: Option<i64>,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+// Phase durations of a single capture in milliseconds; skipped phases are `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureBenchmark {
+    pub state_ms: f64,
+    pub dom_ms: Option<f64>,
+    pub screenshot_ms: Option<f64>,
+    pub save_ms: f64,
+    pub total_ms: f64,
+    pub persisted: bool,
+    pub snapshot_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotInfo {
+    pub path: PathBuf,
+    pub capture_id: String,
+    pub timestamp: i64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub size_bytes: u64,
+    pub format: String,
+    pub has_thumbnail: bool,
+    // Set on crops made by `capture_debug_screenshot_region` and
+    // `capture_element_screenshot`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<ScreenshotRegion>,
+}
+
+// One file removed (or truncated) by a cleanup. `securely_deleted` means
+// its contents were overwritten first; `note` says why they were not when
+// secure deletion was asked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedFile {
+    pub path: PathBuf,
+    pub securely_deleted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+// A region of a window's content area. Logical coordinates are CSS pixels
+// and are scaled by the window's scale factor before cropping.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScreenshotRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// Physical pixels of the full capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PixelRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+// Stored in the cropped PNG itself, so listings can say what it shows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreenshotRegion {
+    pub window_label: String,
+    pub requested: ScreenshotRect,
+    pub logical: bool,
+    pub scale_factor: f64,
+    // What was kept, after clamping to the capture.
+    pub crop: PixelRect,
+    pub clamped: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionScreenshot {
+    pub path: PathBuf,
+    pub region: ScreenshotRegion,
+}
+
+// A line of tauri-plugin-log's file target. Lines in a format the parser
+// does not know keep only `message`, the line as written, with `raw` set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginLogLine {
+    pub timestamp: Option<i64>,
+    pub level: Option<LogLevel>,
+    pub target: Option<String>,
+    pub message: String,
+    pub raw: bool,
+}
+
+// Ordered by severity, so minimum-level filters compare with `>=`.
+// Serialized lowercase; deserialized with `from_str_lenient`, so frontend
+// spellings such as `WARN` or `Warning` are accepted too.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase", try_from = "String")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    // Case-insensitive, with the aliases seen in console, tracing, syslog and
+    // pino/winston-style logs. Console entries carry the frontend's method
+    // name, so `log` ranks with `info`.
+    pub fn from_str_lenient(level: &str) -> Option<Self> {
+        match level.trim().to_ascii_lowercase().as_str() {
+            "trace" | "verbose" | "fine" => Some(Self::Trace),
+            "debug" | "dbg" => Some(Self::Debug),
+            "info" | "log" | "information" | "notice" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" | "err" | "fatal" | "critical" | "crit" | "panic" | "alert" | "emergency"
+            | "emerg" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    // Row class in the HTML log export.
+    pub fn css_class(self) -> &'static str {
+        match self {
+            Self::Trace => "level-trace",
+            Self::Debug => "level-debug",
+            Self::Info => "level-info",
+            Self::Warn => "level-warn",
+            Self::Error => "level-error",
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for LogLevel {
+    type Error = String;
+
+    fn try_from(level: String) -> Result<Self, String> {
+        Self::from_str_lenient(&level).ok_or_else(|| format!("unknown log level `{}`", level))
+    }
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            _ => Self::Error,
+        }
+    }
+}
+
+impl From<LogLevel> for tracing::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => Self::TRACE,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Error => Self::ERROR,
+        }
+    }
+}
+
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Trace => Self::Trace,
+            log::Level::Debug => Self::Debug,
+            log::Level::Info => Self::Info,
+            log::Level::Warn => Self::Warn,
+            log::Level::Error => Self::Error,
+        }
+    }
+}
+
+impl From<LogLevel> for log::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => Self::Trace,
+            LogLevel::Debug => Self::Debug,
+            LogLevel::Info => Self::Info,
+            LogLevel::Warn => Self::Warn,
+            LogLevel::Error => Self::Error,
+        }
+    }
+}
+
+// tauri-plugin-log's `Builder::level` takes a filter. `LevelFilter::Off` has
+// no level; convert with `to_level().map(LogLevel::from)`.
+impl From<LogLevel> for log::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        log::Level::from(level).to_level_filter()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    #[default]
+    Jsonl,
+    // Self-contained report with per-level colours and a level filter.
+    Html,
+    // One row per entry plus a column per `export_field_columns` entry.
+    Csv,
+    // Chrome trace event JSON, e.g. for Perfetto.
+    Timeline,
+}
+
+impl LogExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Html => "html",
+            Self::Csv => "csv",
+            Self::Timeline => "json",
+        }
+    }
+}
+
+// Opt-in full body capture, scoped to URLs matching `url_pattern` (`*` wildcards).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyCaptureRule {
+    pub url_pattern: String,
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+// Limits applied by the retention task; a `None` limit is not enforced.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(default)]
+    pub max_snapshots: Option<usize>,
+    #[serde(default)]
+    pub max_snapshot_age_secs: Option<u64>,
+    // Per-kind age limits. Snapshot and DOM kinds fall back to
+    // `max_snapshot_age_secs`; console and network logs are only swept when
+    // listed here.
+    #[serde(default)]
+    pub ttl_secs: HashMap<ArtifactKind, u64>,
+}
+
+impl RetentionPolicy {
+    pub fn ttl_for(&self, kind: ArtifactKind) -> Option<u64> {
+        self.ttl_secs.get(&kind).copied().or(match kind {
+            ArtifactKind::Snapshot | ArtifactKind::CrashSnapshot | ArtifactKind::DomSnapshot => {
+                self.max_snapshot_age_secs
+            }
+            ArtifactKind::ConsoleLog | ArtifactKind::NetworkLog => None,
+        })
+    }
+}
+
+// Artifacts exempt from every cleanup path, persisted as `holds.json`. A
+// held session keeps its snapshots and the artifacts they reference.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalHolds {
+    #[serde(default)]
+    pub paths: BTreeSet<PathBuf>,
+    #[serde(default)]
+    pub sessions: BTreeSet<String>,
+}
+
+impl LegalHolds {
+    pub fn holds_path(&self, path: &Path) -> bool {
+        if self.paths.is_empty() {
+            return false;
+        }
+        if self.paths.contains(path) {
+            return true;
+        }
+        // Cleanup may walk the resolved log root while holds were recorded
+        // through a symlinked `log_dir`.
+        let Ok(resolved) = path.canonicalize() else {
+            return false;
+        };
+        self.paths
+            .iter()
+            .any(|held| held.canonicalize().is_ok_and(|held| held == resolved))
+    }
+
+    pub fn holds_session(&self, session_id: Option<&str>) -> bool {
+        session_id.is_some_and(|session| self.sessions.contains(session))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorAlarmConfig {
+    pub threshold_count: usize,
+    pub window_secs: u64,
+    pub cooldown_secs: u64,
+    // Save a lightweight snapshot labelled `error-alarm` when it fires.
+    #[serde(default)]
+    pub auto_snapshot: bool,
+}
+
+// Opt-in localhost HTTP endpoints for monitoring that cannot use IPC. The
+// server does not start without a non-empty token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthHttpConfig {
+    pub port: u16,
+    // Expected as `Authorization: Bearer <token>`.
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorFingerprintCount {
+    pub fingerprint: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorAlarmEvent {
+    // Milliseconds, like console log entries.
+    pub fired_at: i64,
+    pub window_secs: u64,
+    pub threshold_count: usize,
+    pub errors_in_window: usize,
+    pub top_fingerprints: Vec<ErrorFingerprintCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorAlarmStatus {
+    pub threshold_count: usize,
+    pub window_secs: u64,
+    pub cooldown_secs: u64,
+    pub errors_in_window: usize,
+    pub firings: u64,
+    pub last_fired_at: Option<i64>,
+    pub cooling_down: bool,
+}
+
+// A condition `strict_mode` turned from a graceful degradation into an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrictViolationKind {
+    QueueOverflow,
+    StorageFallback,
+    ScreenshotsUnavailable,
+    SerializationSubstitution,
+    ClockSkew,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrictViolation {
+    pub kind: StrictViolationKind,
+    pub message: String,
+    // What the plugin would have done instead with `strict_mode` off.
+    pub non_strict_behavior: String,
+    // `None` when the condition arose outside a command, e.g. during setup.
+    pub command: Option<String>,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrictModeStatus {
+    pub enabled: bool,
+    // Sticky until `clear_strict_violations`.
+    pub strict_violation: bool,
+    pub total_violations: u64,
+    // The most recent ones, oldest first.
+    pub violations: Vec<StrictViolation>,
+}
+
+pub const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+pub const MAX_BODY_BYTES_LIMIT: usize = 1024 * 1024;
+
+fn default_max_body_bytes() -> usize {
+    DEFAULT_MAX_BODY_BYTES
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CapturedBody {
+    pub content_type: Option<String>,
+    // Size before truncation.
+    pub size_bytes: usize,
+    pub truncated: bool,
+    pub body: String,
+}
+
+impl CapturedBody {
+    pub fn truncate_to(&mut self, max_bytes: usize) {
+        if self.body.len() <= max_bytes {
+            return;
+        }
+        let mut end = max_bytes;
+        while !self.body.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.body.truncate(end);
+        self.truncated = true;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkLogEntry {
+    pub timestamp: i64,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub duration_ms: Option<f64>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub request_body: Option<CapturedBody>,
+    #[serde(default)]
+    pub response_body: Option<CapturedBody>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BackendLogEvent {
+    pub timestamp: i64,
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendLogBatch {
+    pub events: Vec<BackendLogEvent>,
+    pub dropped_in_window: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorRateWindow {
+    pub start: i64,
+    pub errors: u64,
+    pub errors_per_minute: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorRateReport {
+    pub window_secs: u64,
+    pub current_errors_per_minute: f64,
+    // Newest window first.
+    pub windows: Vec<ErrorRateWindow>,
+}
+
+pub const INGEST_SOURCE_FIELD: &str = "ingest_source";
+
+// Pathway a console entry was persisted through, recorded as
+// `fields.ingest_source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsoleSource {
+    // The script injected by `Builder::inject_console_bridge`.
+    Bridge,
+    // The guest package's console collector.
+    Collector,
+    // Any other `append_debug_logs` caller, such as an app that forwards
+    // console output itself.
+    Host,
+    // `import_console_logs`; never deduplicated.
+    Import,
+    // Entries the plugin writes itself; never deduplicated.
+    Marker,
+}
+
+impl ConsoleSource {
+    // The pathways that can deliver the same console call more than once.
+    pub const LIVE: [ConsoleSource; 3] = [
+        ConsoleSource::Bridge,
+        ConsoleSource::Collector,
+        ConsoleSource::Host,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bridge => "bridge",
+            Self::Collector => "collector",
+            Self::Host => "host",
+            Self::Import => "import",
+            Self::Marker => "marker",
+        }
+    }
+}
+
+// Console log filter; timestamps are milliseconds, `until` is exclusive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogQuery {
+    #[serde(default)]
+    pub min_level: Option<LogLevel>,
+    #[serde(default)]
+    pub since: Option<i64>,
+    #[serde(default)]
+    pub until: Option<i64>,
+    // Case-insensitive substring of the message.
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    // Every listed field must be present on the entry with an equal value.
+    #[serde(default)]
+    pub field_equals: serde_json::Map<String, serde_json::Value>,
+    // Entries written before sources were recorded match no source.
+    #[serde(default)]
+    pub ingest_source: Option<ConsoleSource>,
+    // Moment ids from `mark_debug_moment`; commands narrow `since` and
+    // `until` to the entries from the first moment up to and including the
+    // second.
+    #[serde(default)]
+    pub from_moment: Option<String>,
+    #[serde(default)]
+    pub to_moment: Option<String>,
+    // W3C trace id, matched against the `trace_id` field or the one inside
+    // a `traceparent` field.
+    #[serde(default)]
+    pub trace_id: Option<String>,
+}
+
+impl LogQuery {
+    pub fn matches(&self, entry: &ConsoleLogEntry) -> bool {
+        if self.since.is_some_and(|since| entry.timestamp < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| entry.timestamp >= until) {
+            return false;
+        }
+        if let Some(min_level) = self.min_level {
+            if LogLevel::from_str_lenient(&entry.level).map_or(true, |level| level < min_level) {
+                return false;
+            }
+        }
+        if let Some(text) = self.text.as_deref().filter(|text| !text.is_empty()) {
+            if !entry.message.to_lowercase().contains(&text.to_lowercase()) {
+                return false;
+            }
+        }
+        if let Some(source) = self.ingest_source {
+            let recorded = entry
+                .fields
+                .as_ref()
+                .and_then(|fields| fields.get(INGEST_SOURCE_FIELD))
+                .and_then(|value| value.as_str());
+            if recorded != Some(source.as_str()) {
+                return false;
+            }
+        }
+        if let Some(trace_id) = self.trace_id.as_deref().filter(|id| !id.is_empty()) {
+            let traced = entry
+                .fields
+                .as_ref()
+                .is_some_and(|fields| fields_match_trace(fields, trace_id));
+            if !traced {
+                return false;
+            }
+        }
+        self.field_equals.iter().all(|(key, expected)| {
+            entry
+                .fields
+                .as_ref()
+                .and_then(|fields| fields.get(key))
+                .is_some_and(|value| value == expected)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogFieldCount {
+    pub value: serde_json::Value,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogHistogramBucket {
+    pub start: i64,
+    pub total: u64,
+    pub trace: u64,
+    pub debug: u64,
+    pub info: u64,
+    pub warn: u64,
+    pub error: u64,
+    pub unknown: u64,
+    // Some entry had a negative timestamp (clamped to 0) or was flagged as
+    // going backwards when it was appended.
+    #[serde(default)]
+    pub clock_skew_detected: bool,
+}
+
+impl LogHistogramBucket {
+    pub fn empty(start: i64) -> Self {
+        Self {
+            start,
+            total: 0,
+            trace: 0,
+            debug: 0,
+            info: 0,
+            warn: 0,
+            error: 0,
+            unknown: 0,
+            clock_skew_detected: false,
+        }
+    }
+
+    pub fn record(&mut self, level: Option<LogLevel>) {
+        self.total += 1;
+        let counter = match level {
+            Some(LogLevel::Trace) => &mut self.trace,
+            Some(LogLevel::Debug) => &mut self.debug,
+            Some(LogLevel::Info) => &mut self.info,
+            Some(LogLevel::Warn) => &mut self.warn,
+            Some(LogLevel::Error) => &mut self.error,
+            None => &mut self.unknown,
+        };
+        *counter += 1;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct QuietModeStatus {
+    pub level: LogLevel,
+    pub started_at: i64,
+    pub until: i64,
+    pub suppressed_entries: u64,
+    // Automatic snapshots (navigation, error alarm) skipped while active.
+    #[serde(default)]
+    pub paused_snapshots: u64,
+}
+
+// Coarse privacy level; expands into `PrivacySettings` before
+// `privacy_overrides` apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivacyPreset {
+    // Everything captured, nothing redacted.
+    Open,
+    // Sensitive values redacted and URL queries stripped; DOM, screenshots
+    // and request bodies matching `capture_bodies_for` are still captured,
+    // form values are not.
+    Balanced,
+    // Balanced, plus no DOM, screenshots or bodies, and one in ten console
+    // entries below `warn` kept.
+    Strict,
+}
+
+// Privacy-relevant capture flags after the preset and overrides resolved.
+// Each is a ceiling: capture profiles and `capture_bodies_for` can narrow
+// what is captured further, never widen it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacySettings {
+    // Built-in detection of secrets, tokens and e-mail addresses, on top of
+    // the active profile's `redact_patterns`.
+    pub redact_sensitive_values: bool,
+    // Drops the query string and fragment of recorded URLs.
+    pub strip_url_queries: bool,
+    pub capture_form_state: bool,
+    pub capture_bodies: bool,
+    pub capture_screenshots: bool,
+    pub capture_dom: bool,
+    // Share of frontend console entries below `warn` that are written;
+    // warnings and errors are always kept.
+    pub log_sample_rate: f64,
+}
+
+// Explicit settings that win over the preset, kept across preset changes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyOverrides {
+    #[serde(default)]
+    pub redact_sensitive_values: Option<bool>,
+    #[serde(default)]
+    pub strip_url_queries: Option<bool>,
+    #[serde(default)]
+    pub capture_form_state: Option<bool>,
+    #[serde(default)]
+    pub capture_bodies: Option<bool>,
+    #[serde(default)]
+    pub capture_screenshots: Option<bool>,
+    #[serde(default)]
+    pub capture_dom: Option<bool>,
+    #[serde(default)]
+    pub log_sample_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyStatus {
+    // `None` resolves from the individual config flags, as before presets.
+    pub preset: Option<PrivacyPreset>,
+    pub overrides: PrivacyOverrides,
+    pub effective: PrivacySettings,
+    #[serde(default)]
+    pub sampled_out_entries: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugStats {
+    pub quiet_mode: Option<QuietModeStatus>,
+    // Unix seconds until which entries below the quiet-mode level are
+    // suppressed; `None` when quiet mode is off.
+    pub suppress_below_level_until: Option<i64>,
+    pub total_suppressed_entries: u64,
+    pub error_alarm: Option<ErrorAlarmStatus>,
+    pub clock_skew: Option<ClockSkewMeasurement>,
+    pub screenshot_queue: ScreenshotQueueStats,
+    // Log events dropped because tracing's own write path raised them.
+    pub reentrant_events_suppressed: u64,
+    // The frontend collector's current flush interval; `None` before its
+    // first batch.
+    pub console_flush_interval_ms: Option<u64>,
+    // Latest housekeeping measurement; `None` before the first one.
+    pub disk_usage: Option<DiskUsage>,
+    pub strict_mode: StrictModeStatus,
+    pub screenshots_plugin: ScreenshotsPluginStatus,
+}
+
+// tauri-plugin-screenshots registers on a background thread after setup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ScreenshotsPluginStatus {
+    Registering,
+    Ready,
+    Unavailable { reason: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotDiff {
+    pub diff_path: PathBuf,
+    // The diff covers both screenshots when their sizes differ.
+    pub width: u32,
+    pub height: u32,
+    pub changed_pixels: u64,
+    pub total_pixels: u64,
+    pub changed_percent: f64,
+    pub size_mismatch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingStatus {
+    pub dir: PathBuf,
+    pub fps: u32,
+    pub max_frames: usize,
+    pub started_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingResult {
+    pub dir: PathBuf,
+    pub frames: usize,
+    // Ticks whose capture failed or timed out.
+    pub dropped_frames: usize,
+    pub duration_ms: u64,
+    // Animated PNG of the frames, when assembly was requested.
+    pub animation_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotQueueStats {
+    // Requests waiting for a slot or running.
+    pub depth: usize,
+    pub in_flight: usize,
+    pub concurrency: usize,
+    // Requests served by another request's capture.
+    pub coalesced: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugCommandReplyError {
+    pub message: String,
+    pub stack: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugCommandReply {
+    pub ok: bool,
+    pub value: Option<serde_json::Value>,
+    pub error: Option<DebugCommandReplyError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DebugCommandOutcome {
+    Sent,
+    Ok { value: Option<serde_json::Value> },
+    HandlerError { error: DebugCommandReplyError },
+    MissingListener,
+    Timeout { timeout_ms: u64 },
+}
+
+impl DebugCommandOutcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::HandlerError { .. } | Self::MissingListener | Self::Timeout { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugCommandRecord {
+    pub request_id: String,
+    pub command: String,
+    pub window_label: String,
+    pub sent_at: i64,
+    pub duration_ms: u64,
+    pub outcome: DebugCommandOutcome,
+    // W3C trace context the command was sent under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugCommandHistory {
+    pub recent: Vec<DebugCommandRecord>,
+    pub recent_failures: Vec<DebugCommandRecord>,
 }
@@ -0,0 +1,73 @@
+use crate::domain::models::{ConsoleLogEntry, DebugMoment, LogLevel};
+use std::sync::Mutex;
+
+// Field holding the moment id on its marker entry.
+pub const DEBUG_MOMENT_FIELD: &str = "debug_moment";
+// Outside the plugin's own targets, so `self_log_level` never drops it.
+pub const MOMENT_TRACING_TARGET: &str = "debug_tools::moment";
+// Extension the next full snapshot carries the moments marked since the
+// previous one under.
+pub const MOMENT_SNAPSHOT_EXTENSION: &str = "debug_moments";
+
+const MAX_MOMENTS_PER_SESSION: usize = 1000;
+pub const MAX_MOMENT_LABEL_CHARS: usize = 200;
+
+impl DebugMoment {
+    pub fn to_log_entry(&self) -> ConsoleLogEntry {
+        let mut fields = serde_json::Map::new();
+        fields.insert(DEBUG_MOMENT_FIELD.to_string(), self.id.clone().into());
+        fields.insert("label".to_string(), self.label.clone().into());
+
+        ConsoleLogEntry {
+            timestamp: self.timestamp,
+            level: LogLevel::Info.to_string(),
+            message: format!("[debug-moment] {}", self.label),
+            args: serde_json::Value::Array(Vec::new()),
+            stack_trace: None,
+            fields: Some(fields),
+        }
+    }
+}
+
+// This session's moments, for resolving ids without a log scan, and the
+// ones no snapshot has carried yet.
+#[derive(Debug, Default)]
+pub struct DebugMoments {
+    inner: Mutex<MomentsInner>,
+}
+
+#[derive(Debug, Default)]
+struct MomentsInner {
+    recorded: Vec<DebugMoment>,
+    pending: Vec<DebugMoment>,
+}
+
+impl DebugMoments {
+    // The oldest moment is forgotten past the session limit; its marker
+    // entry still resolves it from the log.
+    pub fn record(&self, moment: DebugMoment) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.recorded.len() >= MAX_MOMENTS_PER_SESSION {
+            inner.recorded.remove(0);
+        }
+        if inner.pending.len() >= MAX_MOMENTS_PER_SESSION {
+            inner.pending.remove(0);
+        }
+        inner.recorded.push(moment.clone());
+        inner.pending.push(moment);
+    }
+
+    pub fn find(&self, id: &str) -> Option<DebugMoment> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .recorded
+            .iter()
+            .find(|moment| moment.id == id)
+            .cloned()
+    }
+
+    pub fn take_pending(&self) -> Vec<DebugMoment> {
+        std::mem::take(&mut self.inner.lock().unwrap_or_else(|e| e.into_inner()).pending)
+    }
+}
@@ -0,0 +1,137 @@
+use crate::domain::models::{ConsoleLogEntry, LogLevel, PluginLogLine};
+
+// Where plugin-log lines are marked when they join console entries in the
+// merged timeline.
+pub const PLUGIN_LOG_SOURCE: &str = "plugin_log";
+
+const MAX_LEADING_GROUPS: usize = 5;
+
+// tauri-plugin-log v2 writes `[2024-05-01][12:34:56][target][INFO] message`
+// by default, with the time in UTC. The leading `[...]` groups are read in
+// any order, so a formatter that moves, merges or drops some of them still
+// parses; without a level among them the line is kept raw.
+pub fn parse_plugin_log_line(line: &str) -> PluginLogLine {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let raw = || PluginLogLine {
+        timestamp: None,
+        level: None,
+        target: None,
+        message: line.to_string(),
+        raw: true,
+    };
+
+    let mut rest = line;
+    let mut groups = Vec::new();
+    while groups.len() < MAX_LEADING_GROUPS {
+        let Some(inner) = rest.strip_prefix('[') else {
+            break;
+        };
+        let Some(end) = inner.find(']') else {
+            break;
+        };
+        groups.push(&inner[..end]);
+        rest = &inner[end + 1..];
+    }
+
+    let (mut date, mut time, mut level, mut target) = (None, None, None, None);
+    for group in groups {
+        let group = group.trim();
+        if level.is_none() && canonical_level(group).is_some() {
+            level = canonical_level(group);
+        } else if date.is_none() && parse_date(group).is_some() {
+            date = parse_date(group);
+        } else if time.is_none() && parse_time(group).is_some() {
+            time = parse_time(group);
+        } else if let Some((day, millis)) = parse_date_time(group) {
+            date = Some(day);
+            time = Some(millis);
+        } else if target.is_none() {
+            target = Some(group.to_string());
+        }
+    }
+
+    let Some(level) = level else {
+        return raw();
+    };
+    PluginLogLine {
+        timestamp: date.map(|days| days * 86_400_000 + time.unwrap_or(0)),
+        level: Some(level),
+        target,
+        message: rest.trim_start().to_string(),
+        raw: false,
+    }
+}
+
+// For the merged timeline; raw lines take `fallback_timestamp`, the last
+// parsed one before them.
+pub fn to_console_entry(line: PluginLogLine, fallback_timestamp: i64) -> ConsoleLogEntry {
+    let mut fields = serde_json::Map::new();
+    fields.insert("source".to_string(), PLUGIN_LOG_SOURCE.into());
+    if let Some(target) = &line.target {
+        fields.insert("target".to_string(), target.clone().into());
+    }
+    if line.raw {
+        fields.insert("raw".to_string(), true.into());
+    }
+    ConsoleLogEntry {
+        timestamp: line.timestamp.unwrap_or(fallback_timestamp),
+        level: line.level.unwrap_or(LogLevel::Info).to_string(),
+        message: line.message,
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: Some(fields),
+    }
+}
+
+// Only a level's canonical name, in any case; aliases such as `log` make
+// likely targets.
+fn canonical_level(group: &str) -> Option<LogLevel> {
+    LogLevel::from_str_lenient(group).filter(|level| group.eq_ignore_ascii_case(level.as_str()))
+}
+
+// Days since the Unix epoch of `YYYY-MM-DD`.
+fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || text.len() != 10 {
+        return None;
+    }
+    // Howard Hinnant's days_from_civil.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+// Milliseconds into the day of `HH:MM:SS[.fff]`.
+fn parse_time(text: &str) -> Option<i64> {
+    let (clock, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let mut parts = clock.splitn(3, ':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let seconds: i64 = parts.next()?.parse().ok()?;
+    if hours > 23 || minutes > 59 || seconds > 60 || clock.len() != 8 {
+        return None;
+    }
+    let millis = match fraction {
+        "" => 0,
+        digits if digits.bytes().all(|byte| byte.is_ascii_digit()) => {
+            format!("{:0<3}", &digits[..digits.len().min(3)])
+                .parse()
+                .ok()?
+        }
+        _ => return None,
+    };
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis)
+}
+
+// `YYYY-MM-DD HH:MM:SS` or `YYYY-MM-DDTHH:MM:SS[Z]` in one group.
+fn parse_date_time(text: &str) -> Option<(i64, i64)> {
+    let (date, time) = text.split_once([' ', 'T'])?;
+    let time = time.trim_end_matches('Z');
+    Some((parse_date(date)?, parse_time(time)?))
+}
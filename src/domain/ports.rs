@@ -1,11 +1,19 @@
-use crate::domain::models::{DebugSnapshot, DomSnapshotResult, DomState};
+use crate::domain::clock_skew::has_clock_skew;
+use crate::domain::models::{
+    ConsoleLogEntry, DebugSnapshot, DomSnapshotResult, DomState, DurabilityLatency, LogFieldCount,
+    LogHistogramBucket, LogLevel, LogQuery, RepositoryMetricsReport,
+};
 use std::path::PathBuf;
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum LogError {
     #[error("Failed to initialize logger: {0}")]
     Initialization(String),
+    #[cfg(feature = "flamegraph")]
+    #[error("Failed to export flamegraph: {0}")]
+    Flamegraph(String),
 }
 
 #[derive(Debug, Error)]
@@ -18,6 +26,10 @@ pub enum RepositoryError {
     Io(#[from] std::io::Error),
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("Path resolves outside the log directory: {0}")]
+    OutsideLogRoot(PathBuf),
+    #[error("Artifact is under legal hold: {0}")]
+    LegalHold(PathBuf),
 }
 
 pub trait SnapshotRepository: Send + Sync {
@@ -31,4 +43,152 @@ pub trait SnapshotRepository: Send + Sync {
         &self,
         logs: &[crate::domain::models::ConsoleLogEntry],
     ) -> Result<PathBuf, RepositoryError>;
+    fn metrics(&self) -> Option<RepositoryMetricsReport> {
+        None
+    }
+    fn durability_latency(&self) -> Vec<DurabilityLatency> {
+        Vec::new()
+    }
+}
+
+// Lets a host-provided `Arc<dyn SnapshotRepository>` sit behind the same
+// generic use cases and metering decorator as the built-in backend.
+impl<T: SnapshotRepository + ?Sized> SnapshotRepository for Arc<T> {
+    fn save_snapshot(&self, snapshot: &DebugSnapshot) -> Result<PathBuf, RepositoryError> {
+        (**self).save_snapshot(snapshot)
+    }
+
+    fn save_dom(
+        &self,
+        dom: &DomState,
+        timestamp: i64,
+    ) -> Result<DomSnapshotResult, RepositoryError> {
+        (**self).save_dom(dom, timestamp)
+    }
+
+    fn save_console_logs(&self, logs: &[ConsoleLogEntry]) -> Result<PathBuf, RepositoryError> {
+        (**self).save_console_logs(logs)
+    }
+
+    fn metrics(&self) -> Option<RepositoryMetricsReport> {
+        (**self).metrics()
+    }
+
+    fn durability_latency(&self) -> Vec<DurabilityLatency> {
+        (**self).durability_latency()
+    }
+}
+
+// Read side of console log storage, kept apart from `SnapshotRepository` so
+// each backend can answer queries its own way. Only `scan` is required; the
+// rest are derived from it and may be overridden with native queries.
+pub trait LogQueryService: Send + Sync {
+    // Visits entries matching `query` in storage order, ignoring `query.limit`.
+    fn scan(
+        &self,
+        query: &LogQuery,
+        visit: &mut dyn FnMut(ConsoleLogEntry),
+    ) -> Result<(), RepositoryError>;
+
+    // Most recent `query.limit` matches, oldest first.
+    fn query_console_logs(
+        &self,
+        query: &LogQuery,
+    ) -> Result<Vec<ConsoleLogEntry>, RepositoryError> {
+        let limit = query.limit.unwrap_or(usize::MAX);
+        let mut entries = std::collections::VecDeque::new();
+        let mut seq = 0u64;
+        self.scan(query, &mut |entry| {
+            if entries.len() == limit {
+                entries.pop_front();
+            }
+            if limit > 0 {
+                entries.push_back((seq, entry));
+            }
+            seq += 1;
+        })?;
+
+        // Clocks can jump backwards mid-session; file order breaks ties so
+        // equal timestamps keep the order they were written in.
+        let mut entries: Vec<(u64, ConsoleLogEntry)> = entries.into();
+        entries.sort_by_key(|(seq, entry)| (entry.timestamp, *seq));
+        Ok(entries.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    fn query_errors(&self, limit: usize) -> Result<Vec<ConsoleLogEntry>, RepositoryError> {
+        self.query_console_logs(&LogQuery {
+            min_level: Some(LogLevel::Error),
+            limit: Some(limit),
+            ..LogQuery::default()
+        })
+    }
+
+    fn histogram(
+        &self,
+        bucket_ms: i64,
+        query: &LogQuery,
+    ) -> Result<Vec<LogHistogramBucket>, RepositoryError> {
+        let bucket_ms = bucket_ms.max(1);
+        let mut buckets = std::collections::BTreeMap::<i64, LogHistogramBucket>::new();
+        self.scan(query, &mut |entry| {
+            let start = entry.timestamp.max(0).div_euclid(bucket_ms) * bucket_ms;
+            let bucket = buckets
+                .entry(start)
+                .or_insert_with(|| LogHistogramBucket::empty(start));
+            bucket.record(LogLevel::from_str_lenient(&entry.level));
+            if entry.timestamp < 0 || has_clock_skew(&entry) {
+                bucket.clock_skew_detected = true;
+            }
+        })?;
+        Ok(buckets.into_values().collect())
+    }
+
+    // Matching entries grouped by the value of `field`, most frequent first.
+    // Entries without the field are not counted.
+    fn count_by_field(
+        &self,
+        field: &str,
+        query: &LogQuery,
+    ) -> Result<Vec<LogFieldCount>, RepositoryError> {
+        let mut counts = std::collections::HashMap::<String, LogFieldCount>::new();
+        self.scan(query, &mut |entry| {
+            let Some(value) = entry.fields.as_ref().and_then(|fields| fields.get(field)) else {
+                return;
+            };
+            counts
+                .entry(value.to_string())
+                .or_insert_with(|| LogFieldCount {
+                    value: value.clone(),
+                    count: 0,
+                })
+                .count += 1;
+        })?;
+        let mut counts: Vec<LogFieldCount> = counts.into_values().collect();
+        counts.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.value.to_string().cmp(&b.value.to_string()))
+        });
+        Ok(counts)
+    }
+
+    // Entries within `[since, until)` in milliseconds.
+    fn timeline(&self, since: i64, until: i64) -> Result<Vec<ConsoleLogEntry>, RepositoryError> {
+        let mut entries = Vec::new();
+        self.scan(
+            &LogQuery {
+                since: Some(since),
+                until: Some(until),
+                ..LogQuery::default()
+            },
+            &mut |entry| entries.push(entry),
+        )?;
+        Ok(entries)
+    }
+}
+
+// Queryable log backend (e.g. SQLite) that receives a copy of every persisted
+// console log batch and serves log queries in place of the JSONL scan.
+pub trait LogStore: LogQueryService {
+    fn append_console_logs(&self, logs: &[ConsoleLogEntry]) -> Result<(), RepositoryError>;
 }
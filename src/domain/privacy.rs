@@ -0,0 +1,224 @@
+use crate::domain::models::{
+    ConsoleLogEntry, LogLevel, PrivacyOverrides, PrivacyPreset, PrivacySettings, PrivacyStatus,
+};
+use regex::Regex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+const REDACTED: &str = "[REDACTED]";
+
+// Field names whose values are dropped whole, matched case-insensitively as
+// substrings.
+const SENSITIVE_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "authorization",
+    "cookie",
+    "session",
+    "credential",
+];
+
+impl PrivacySettings {
+    pub fn for_preset(preset: PrivacyPreset) -> Self {
+        match preset {
+            PrivacyPreset::Open => Self::unrestricted(true),
+            PrivacyPreset::Balanced => Self {
+                redact_sensitive_values: true,
+                strip_url_queries: true,
+                capture_form_state: false,
+                ..Self::unrestricted(true)
+            },
+            PrivacyPreset::Strict => Self {
+                redact_sensitive_values: true,
+                strip_url_queries: true,
+                capture_form_state: false,
+                capture_bodies: false,
+                capture_screenshots: false,
+                capture_dom: false,
+                log_sample_rate: 0.1,
+            },
+        }
+    }
+
+    // What the plugin captured before presets existed; form capture has
+    // always had its own flag.
+    pub fn unrestricted(capture_form_state: bool) -> Self {
+        Self {
+            redact_sensitive_values: false,
+            strip_url_queries: false,
+            capture_form_state,
+            capture_bodies: true,
+            capture_screenshots: true,
+            capture_dom: true,
+            log_sample_rate: 1.0,
+        }
+    }
+}
+
+impl PrivacyOverrides {
+    pub fn apply(&self, settings: PrivacySettings) -> PrivacySettings {
+        PrivacySettings {
+            redact_sensitive_values: self
+                .redact_sensitive_values
+                .unwrap_or(settings.redact_sensitive_values),
+            strip_url_queries: self.strip_url_queries.unwrap_or(settings.strip_url_queries),
+            capture_form_state: self
+                .capture_form_state
+                .unwrap_or(settings.capture_form_state),
+            capture_bodies: self.capture_bodies.unwrap_or(settings.capture_bodies),
+            capture_screenshots: self
+                .capture_screenshots
+                .unwrap_or(settings.capture_screenshots),
+            capture_dom: self.capture_dom.unwrap_or(settings.capture_dom),
+            log_sample_rate: self
+                .log_sample_rate
+                .unwrap_or(settings.log_sample_rate)
+                .clamp(0.0, 1.0),
+        }
+    }
+}
+
+// The preset in force, switchable at runtime. Overrides are fixed at setup
+// and re-applied on every switch.
+#[derive(Debug)]
+pub struct PrivacyControl {
+    status: RwLock<PrivacyStatus>,
+    // Resolves a `None` preset.
+    baseline: PrivacySettings,
+    seen_entries: AtomicU64,
+    sampled_out: AtomicU64,
+}
+
+impl PrivacyControl {
+    pub fn new(
+        preset: Option<PrivacyPreset>,
+        overrides: PrivacyOverrides,
+        baseline: PrivacySettings,
+    ) -> Self {
+        let effective = resolve(preset, &overrides, &baseline);
+        Self {
+            status: RwLock::new(PrivacyStatus {
+                preset,
+                overrides,
+                effective,
+                sampled_out_entries: 0,
+            }),
+            baseline,
+            seen_entries: AtomicU64::new(0),
+            sampled_out: AtomicU64::new(0),
+        }
+    }
+
+    pub fn settings(&self) -> PrivacySettings {
+        self.status
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .effective
+            .clone()
+    }
+
+    pub fn status(&self) -> PrivacyStatus {
+        let mut status = self
+            .status
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        status.sampled_out_entries = self.sampled_out.load(Ordering::Relaxed);
+        status
+    }
+
+    pub fn set_preset(&self, preset: Option<PrivacyPreset>) -> PrivacyStatus {
+        {
+            let mut status = self.status.write().unwrap_or_else(|e| e.into_inner());
+            status.preset = preset;
+            status.effective = resolve(preset, &status.overrides, &self.baseline);
+        }
+        self.status()
+    }
+
+    // Keeps `log_sample_rate` of the entries below `warn`, spread evenly over
+    // the session rather than at random so reruns write the same entries.
+    pub fn sample_logs(&self, logs: &mut Vec<ConsoleLogEntry>) {
+        let rate = self.settings().log_sample_rate;
+        if rate >= 1.0 {
+            return;
+        }
+
+        let before = logs.len();
+        logs.retain(|entry| {
+            if LogLevel::from_str_lenient(&entry.level).is_some_and(|level| level >= LogLevel::Warn)
+            {
+                return true;
+            }
+            let seen = self.seen_entries.fetch_add(1, Ordering::Relaxed) as f64;
+            ((seen + 1.0) * rate).floor() > (seen * rate).floor()
+        });
+        self.sampled_out
+            .fetch_add((before - logs.len()) as u64, Ordering::Relaxed);
+    }
+}
+
+fn resolve(
+    preset: Option<PrivacyPreset>,
+    overrides: &PrivacyOverrides,
+    baseline: &PrivacySettings,
+) -> PrivacySettings {
+    let settings = preset.map_or_else(|| baseline.clone(), PrivacySettings::for_preset);
+    overrides.apply(settings)
+}
+
+pub fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEYS
+        .iter()
+        .any(|sensitive| key.contains(sensitive))
+}
+
+// Replaces bearer tokens, JWTs, e-mail addresses and the values of
+// `key=value` / `key: value` pairs with a sensitive key. Returns whether
+// anything was replaced.
+pub fn redact_sensitive_text(text: &mut String) -> bool {
+    static PATTERNS: OnceLock<Vec<(Regex, String)>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            (r"(?i)\bbearer\s+[A-Za-z0-9._~+/=-]+", REDACTED.to_string()),
+            (
+                r"\beyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+",
+                REDACTED.to_string(),
+            ),
+            (
+                r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+                REDACTED.to_string(),
+            ),
+            (
+                r#"(?i)\b((?:password|passwd|pwd|secret|token|api[_-]?key|access[_-]?key|authorization|cookie|session[_-]?id)["']?\s*[=:]\s*)("[^"]*"|'[^']*'|[^\s&;,]+)"#,
+                format!("${{1}}{}", REDACTED),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(pattern, replacement)| {
+            Regex::new(pattern).ok().map(|regex| (regex, replacement))
+        })
+        .collect()
+    });
+
+    let mut changed = false;
+    for (regex, replacement) in patterns {
+        if regex.is_match(text) {
+            *text = regex.replace_all(text, replacement.as_str()).into_owned();
+            changed = true;
+        }
+    }
+    changed
+}
+
+// Cuts everything from the first `?` or `#`.
+pub fn strip_url_query(url: &mut String) {
+    if let Some(index) = url.find(['?', '#']) {
+        url.truncate(index);
+    }
+}
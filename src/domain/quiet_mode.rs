@@ -0,0 +1,97 @@
+use crate::domain::models::{ConsoleLogEntry, LogLevel, QuietModeStatus};
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+
+// Runtime-only window during which entries below `level` are counted but not
+// persisted, the stdout and forwarding tracing layers are muted up to the
+// same level, and automatic snapshots are paused.
+#[derive(Debug, Default)]
+pub struct QuietMode {
+    window: Mutex<Option<QuietModeStatus>>,
+    total_suppressed: AtomicU64,
+    // Mirrors of the active window so the tracing filter never takes the lock.
+    active_until: AtomicI64,
+    level_rank: AtomicU8,
+}
+
+impl QuietMode {
+    pub fn activate(&self, level: LogLevel, duration_secs: u64, now: i64) -> QuietModeStatus {
+        let until = now.saturating_add(duration_secs as i64);
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+
+        let status = match window.take() {
+            Some(previous) if previous.until > now => QuietModeStatus {
+                level,
+                until,
+                ..previous
+            },
+            _ => QuietModeStatus {
+                level,
+                started_at: now,
+                until,
+                suppressed_entries: 0,
+                paused_snapshots: 0,
+            },
+        };
+
+        self.level_rank.store(level as u8, Ordering::Relaxed);
+        self.active_until.store(until, Ordering::Relaxed);
+        *window = Some(status.clone());
+
+        status
+    }
+
+    pub fn status(&self, now: i64) -> Option<QuietModeStatus> {
+        let window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        window.as_ref().filter(|status| status.until > now).cloned()
+    }
+
+    pub fn total_suppressed(&self) -> u64 {
+        self.total_suppressed.load(Ordering::Relaxed)
+    }
+
+    pub fn retain_persistable(&self, logs: &mut Vec<ConsoleLogEntry>, now: i64) {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(status) = window.as_mut().filter(|status| status.until > now) else {
+            return;
+        };
+
+        let before = logs.len();
+        logs.retain(|entry| {
+            LogLevel::from_str_lenient(&entry.level).map_or(true, |level| level >= status.level)
+        });
+
+        let suppressed = (before - logs.len()) as u64;
+        status.suppressed_entries += suppressed;
+        self.total_suppressed
+            .fetch_add(suppressed, Ordering::Relaxed);
+    }
+
+    // Whether an automatic snapshot may be taken now; a skipped one is
+    // counted in the window.
+    pub fn allows_automatic_snapshot(&self, now: i64) -> bool {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        match window.as_mut().filter(|status| status.until > now) {
+            Some(status) => {
+                status.paused_snapshots += 1;
+                false
+            }
+            None => true,
+        }
+    }
+
+    // Returns the finished window once, so exactly one caller writes the summary marker.
+    pub fn take_expired(&self, now: i64) -> Option<QuietModeStatus> {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        if window.as_ref().is_some_and(|status| status.until <= now) {
+            self.active_until.store(0, Ordering::Relaxed);
+            return window.take();
+        }
+        None
+    }
+
+    pub fn suppresses(&self, level: LogLevel, now: i64) -> bool {
+        let until = self.active_until.load(Ordering::Relaxed);
+        until > now && (level as u8) < self.level_rank.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,33 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use thiserror::Error;
+
+// Serialized with a `code`, like annotation errors. A screenshot is pinned
+// when a legal hold covers it or a snapshot linking it; `pinned_by` lists
+// those held paths.
+#[derive(Debug, Clone, Error, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum ScreenshotDeleteError {
+    #[error("{message}")]
+    InvalidPath { message: String },
+    #[error("Screenshot is pinned by a legal hold: {}", .path.display())]
+    PinnedArtifact {
+        path: PathBuf,
+        pinned_by: Vec<PathBuf>,
+    },
+    #[error("Screenshot is still referenced by {} snapshot(s)", .snapshots.len())]
+    StillReferenced {
+        path: PathBuf,
+        snapshots: Vec<PathBuf>,
+    },
+    #[error("Failed to delete screenshot: {message}")]
+    Delete { message: String },
+}
+
+impl ScreenshotDeleteError {
+    pub fn delete(error: impl std::fmt::Display) -> Self {
+        Self::Delete {
+            message: error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::domain::models::{PixelRect, ScreenshotRect};
+use serde::Serialize;
+use thiserror::Error;
+
+// Debug command the frontend answers with an element's bounding rect in
+// CSS pixels, or `null` when nothing matches.
+pub const ELEMENT_RECT_COMMAND: &str = "debug-tools:element-rect";
+
+// Serialized with a `code`, like annotation errors.
+#[derive(Debug, Clone, Error, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum ScreenshotRegionError {
+    #[error("Screenshots are disabled by the active capture profile or privacy settings")]
+    Disabled,
+    #[error("Window not found: {window_label}")]
+    WindowNotFound { window_label: String },
+    #[error("The region has no pixels inside the {image_width}x{image_height} capture")]
+    EmptyRegion {
+        rect: ScreenshotRect,
+        image_width: u32,
+        image_height: u32,
+    },
+    #[error("No element matches {selector}")]
+    ElementNotFound { selector: String },
+    #[error("The frontend did not report the element's bounds: {message}")]
+    ElementRectUnavailable { message: String },
+    #[error("Screenshot capture failed: {message}")]
+    Capture { message: String },
+}
+
+impl ScreenshotRegionError {
+    pub fn capture(error: impl std::fmt::Display) -> Self {
+        Self::Capture {
+            message: error.to_string(),
+        }
+    }
+}
+
+// Maps `rect` onto a capture of `image` pixels whose content area starts at
+// `offset`, clamping whatever falls outside. Returns the crop and whether
+// it was clamped.
+pub fn crop_rect(
+    rect: ScreenshotRect,
+    scale: f64,
+    offset: (u32, u32),
+    image: (u32, u32),
+) -> Result<(PixelRect, bool), ScreenshotRegionError> {
+    let empty = || ScreenshotRegionError::EmptyRegion {
+        rect,
+        image_width: image.0,
+        image_height: image.1,
+    };
+    let values = [rect.x, rect.y, rect.width, rect.height, scale];
+    if values.iter().any(|value| !value.is_finite()) {
+        return Err(empty());
+    }
+
+    let left = rect.x * scale + offset.0 as f64;
+    let top = rect.y * scale + offset.1 as f64;
+    let right = left + rect.width * scale;
+    let bottom = top + rect.height * scale;
+
+    let clamp = |value: f64, max: u32| value.clamp(0.0, max as f64) as u32;
+    let (x0, x1) = (clamp(left.floor(), image.0), clamp(right.ceil(), image.0));
+    let (y0, y1) = (clamp(top.floor(), image.1), clamp(bottom.ceil(), image.1));
+    if x1 <= x0 || y1 <= y0 {
+        return Err(empty());
+    }
+
+    let clamped = left < 0.0 || top < 0.0 || right > image.0 as f64 || bottom > image.1 as f64;
+    Ok((
+        PixelRect {
+            x: x0,
+            y: y0,
+            width: x1 - x0,
+            height: y1 - y0,
+        },
+        clamped,
+    ))
+}
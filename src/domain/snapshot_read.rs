@@ -0,0 +1,24 @@
+use serde::Serialize;
+use thiserror::Error;
+
+// Serialized with a `code`, like annotation errors, so the webview can tell
+// a missing snapshot from a broken one.
+#[derive(Debug, Clone, Error, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum SnapshotReadError {
+    #[error("Invalid snapshot timestamp: {timestamp}")]
+    InvalidTimestamp { timestamp: i64 },
+    #[error("No snapshot with timestamp {timestamp}")]
+    NotFound { timestamp: i64 },
+    #[error("Failed to read snapshot {timestamp}: {message}")]
+    Unreadable { timestamp: i64, message: String },
+}
+
+// `snapshot_{timestamp}.json`. Timestamps are Unix milliseconds, so a
+// negative one cannot name a saved snapshot.
+pub fn snapshot_file_name(timestamp: i64) -> Result<String, SnapshotReadError> {
+    if timestamp < 0 {
+        return Err(SnapshotReadError::InvalidTimestamp { timestamp });
+    }
+    Ok(format!("snapshot_{}.json", timestamp))
+}
@@ -0,0 +1,128 @@
+use crate::domain::models::{StrictModeStatus, StrictViolation, StrictViolationKind};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+const MAX_RECORDED_VIOLATIONS: usize = 100;
+
+// Marks the JSON error a command returns for a violation, so callers can
+// tell it apart from ordinary failures.
+pub const STRICT_VIOLATION_CODE: &str = "strict_violation";
+
+#[derive(Debug, Default)]
+struct Violations {
+    recent: VecDeque<StrictViolation>,
+    total: u64,
+}
+
+// Collects the degradations `strict_mode` refuses. Nothing is recorded
+// with strict mode off, where the plugin degrades as it always has.
+#[derive(Debug, Default)]
+pub struct StrictMode {
+    enabled: bool,
+    violations: Mutex<Violations>,
+}
+
+impl StrictMode {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            violations: Mutex::default(),
+        }
+    }
+
+    // `Ok` when strict mode is off and the caller should degrade as usual.
+    // Otherwise the violation is recorded and returned as the command's
+    // error.
+    pub fn check(
+        &self,
+        kind: StrictViolationKind,
+        command: &str,
+        message: impl Into<String>,
+        non_strict_behavior: impl Into<String>,
+        now_ms: i64,
+    ) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let violation = StrictViolation {
+            kind,
+            message: message.into(),
+            non_strict_behavior: non_strict_behavior.into(),
+            command: Some(command.to_string()),
+            timestamp: now_ms,
+        };
+        let error = violation_error(&violation);
+        tracing::error!(
+            kind = ?violation.kind,
+            command,
+            non_strict_behavior = %violation.non_strict_behavior,
+            "Strict mode violation: {}",
+            violation.message
+        );
+        self.push(violation);
+        Err(error)
+    }
+
+    // For conditions with no command to fail, such as a storage fallback
+    // chosen during setup. A no-op with strict mode off.
+    pub fn record(
+        &self,
+        kind: StrictViolationKind,
+        message: impl Into<String>,
+        non_strict_behavior: impl Into<String>,
+        now_ms: i64,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let violation = StrictViolation {
+            kind,
+            message: message.into(),
+            non_strict_behavior: non_strict_behavior.into(),
+            command: None,
+            timestamp: now_ms,
+        };
+        tracing::error!(
+            kind = ?violation.kind,
+            non_strict_behavior = %violation.non_strict_behavior,
+            "Strict mode violation: {}",
+            violation.message
+        );
+        self.push(violation);
+    }
+
+    pub fn status(&self) -> StrictModeStatus {
+        let violations = self.violations.lock().unwrap_or_else(|e| e.into_inner());
+        StrictModeStatus {
+            enabled: self.enabled,
+            strict_violation: violations.total > 0,
+            total_violations: violations.total,
+            violations: violations.recent.iter().cloned().collect(),
+        }
+    }
+
+    // Returns how many violations were cleared.
+    pub fn clear(&self) -> u64 {
+        let mut violations = self.violations.lock().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *violations).total
+    }
+
+    fn push(&self, violation: StrictViolation) {
+        let mut violations = self.violations.lock().unwrap_or_else(|e| e.into_inner());
+        if violations.recent.len() == MAX_RECORDED_VIOLATIONS {
+            violations.recent.pop_front();
+        }
+        violations.recent.push_back(violation);
+        violations.total += 1;
+    }
+}
+
+// `{"code": "strict_violation", ...the violation}` as a string, since
+// commands report errors as text.
+fn violation_error(violation: &StrictViolation) -> String {
+    let mut value = serde_json::to_value(violation).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.insert("code".to_string(), STRICT_VIOLATION_CODE.into());
+    }
+    value.to_string()
+}
@@ -0,0 +1,90 @@
+use crate::domain::models::{BuildInfo, ConsoleLogEntry, WebViewState};
+use std::fmt::Write;
+
+// Longest error message line kept in the summary.
+const MAX_ERROR_CHARS: usize = 300;
+
+pub struct TextSummaryInput<'a> {
+    pub captured_at_ms: i64,
+    pub webview: &'a WebViewState,
+    pub build_info: &'a BuildInfo,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub session_id: &'a str,
+    pub errors: &'a [ConsoleLogEntry],
+}
+
+// Plain text meant to be pasted into an issue or chat as-is, so it avoids
+// markup beyond what renders acceptably unformatted.
+pub fn render_text_summary(input: &TextSummaryInput<'_>) -> String {
+    let mut out = String::new();
+    let webview = input.webview;
+
+    let _ = writeln!(out, "Debug summary ({})", format_utc(input.captured_at_ms));
+    let _ = writeln!(out, "URL: {}", webview.url);
+    let _ = writeln!(out, "Title: {}", webview.title);
+    let _ = writeln!(
+        out,
+        "Viewport: {}x{}",
+        webview.viewport.width, webview.viewport.height
+    );
+    let _ = writeln!(
+        out,
+        "App: {} {}{} (debug-tools {})",
+        input.build_info.app_name,
+        input.build_info.app_version,
+        if input.build_info.debug_build {
+            " debug"
+        } else {
+            ""
+        },
+        input.build_info.plugin_version
+    );
+    let _ = writeln!(out, "Platform: {} {}", input.os, input.arch);
+    let _ = writeln!(out, "User agent: {}", webview.user_agent);
+    let _ = writeln!(out, "Session: {}", input.session_id);
+
+    if input.errors.is_empty() {
+        let _ = writeln!(out, "Recent errors: none");
+    } else {
+        let _ = writeln!(out, "Recent errors ({}):", input.errors.len());
+        for entry in input.errors {
+            let message = entry.message.lines().next().unwrap_or_default();
+            let mut message: String = message.chars().take(MAX_ERROR_CHARS).collect();
+            if entry.message.chars().count() > message.chars().count() {
+                message.push('…');
+            }
+            let _ = writeln!(out, "- [{}] {}", format_utc(entry.timestamp), message);
+        }
+    }
+
+    out
+}
+
+// `YYYY-MM-DD HH:MM:SS UTC`, using the days-to-civil conversion from
+// Howard Hinnant's date algorithms.
+pub fn format_utc(timestamp_ms: i64) -> String {
+    let secs = timestamp_ms.div_euclid(1000);
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
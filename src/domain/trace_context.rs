@@ -0,0 +1,102 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+// Console entry fields a frontend sets to tie a log line to a trace; either
+// one satisfies `LogQuery::trace_id`.
+pub const TRACE_ID_FIELD: &str = "trace_id";
+pub const TRACEPARENT_FIELD: &str = "traceparent";
+
+// A W3C `traceparent` (`00-<trace-id>-<parent-id>-<flags>`), kept as the
+// lowercase hex the header carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: String,
+    pub parent_id: String,
+    pub flags: u8,
+}
+
+impl TraceContext {
+    // Accepts future versions' extra fields after the four version-00 ones,
+    // as the spec asks, but never version `ff` or all-zero ids.
+    pub fn parse(traceparent: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid traceparent: {}", traceparent);
+        let traceparent = traceparent.trim();
+        let mut parts = traceparent.split('-');
+        let (Some(version), Some(trace_id), Some(parent_id), Some(flags)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let rest_allowed = version != "00" || parts.next().is_none();
+        if !is_hex(version, 2) || version == "ff" || !rest_allowed {
+            return Err(invalid());
+        }
+        if !is_hex(trace_id, 32) || !is_hex(parent_id, 16) || !is_hex(flags, 2) {
+            return Err(invalid());
+        }
+        if is_zero(trace_id) || is_zero(parent_id) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            trace_id: trace_id.to_string(),
+            parent_id: parent_id.to_string(),
+            flags: u8::from_str_radix(flags, 16).map_err(|_| invalid())?,
+        })
+    }
+
+    // A new sampled trace. `seed` only has to differ per call; the hasher
+    // keys are random per process, so ids do not repeat across runs.
+    pub fn generate(seed: impl Hash) -> Self {
+        let keys = RandomState::new();
+        let word = |salt: u8| keys.hash_one((salt, &seed)).max(1);
+        Self {
+            trace_id: format!("{:016x}{:016x}", word(0), word(1)),
+            parent_id: format!("{:016x}", word(2)),
+            flags: 0x01,
+        }
+    }
+
+    pub fn traceparent(&self) -> String {
+        format!("00-{}-{}-{:02x}", self.trace_id, self.parent_id, self.flags)
+    }
+
+    // For work done on behalf of the traced action, so backend log lines
+    // carry the same trace id as the frontend's.
+    pub fn span(&self) -> tracing::Span {
+        tracing::info_span!(
+            "debug_trace",
+            trace_id = %self.trace_id,
+            parent_id = %self.parent_id
+        )
+    }
+}
+
+// Whether console entry fields name `trace_id`, directly or inside a
+// `traceparent`.
+pub fn fields_match_trace(
+    fields: &serde_json::Map<String, serde_json::Value>,
+    trace_id: &str,
+) -> bool {
+    let trace_id = trace_id.to_ascii_lowercase();
+    let direct = fields
+        .get(TRACE_ID_FIELD)
+        .and_then(|value| value.as_str())
+        .is_some_and(|value| value.eq_ignore_ascii_case(&trace_id));
+    direct
+        || fields
+            .get(TRACEPARENT_FIELD)
+            .and_then(|value| value.as_str())
+            .and_then(|value| TraceContext::parse(value).ok())
+            .is_some_and(|context| context.trace_id == trace_id)
+}
+
+fn is_hex(text: &str, len: usize) -> bool {
+    text.len() == len
+        && text
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+}
+
+fn is_zero(text: &str) -> bool {
+    text.bytes().all(|byte| byte == b'0')
+}
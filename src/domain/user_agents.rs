@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Reported when a webview has not sent its user agent yet.
+pub const PLACEHOLDER_USER_AGENT: &str = "TauriWebView/2.0";
+
+// `navigator.userAgent` per webview label, as each page reports it on
+// load. Managed on its own so window captures can read it without the
+// rest of the plugin state.
+#[derive(Debug, Default)]
+pub struct UserAgents {
+    by_label: Mutex<HashMap<String, String>>,
+}
+
+impl UserAgents {
+    pub fn record(&self, label: &str, user_agent: &str) {
+        self.by_label
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(label.to_string(), user_agent.to_string());
+    }
+
+    pub fn get(&self, label: &str) -> Option<String> {
+        self.by_label
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(label)
+            .cloned()
+    }
+}
@@ -1,90 +1,1464 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use tauri::{
-    plugin::{Builder, TauriPlugin},
-    Manager, Runtime,
+    plugin::{Builder as PluginBuilder, TauriPlugin},
+    AppHandle, Emitter, Manager, RunEvent, Runtime, WindowEvent,
 };
 
 mod adapters;
+pub mod analysis;
 mod application;
 mod commands;
 mod config;
 mod domain;
 
-pub use config::DebugToolsConfig;
-pub use domain::{ConsoleLogEntry, DebugSnapshot, DomSnapshotResult, WebViewState};
+#[cfg(feature = "test-utils")]
+pub use adapters::buffer_pool::BufferPool;
+#[cfg(feature = "test-utils")]
+pub use adapters::disk_usage::measure_disk_usage;
+#[cfg(feature = "test-utils")]
+pub use adapters::dom_upload::{ChunkEncoding, DomUploadError, DomUploadStore};
+#[cfg(feature = "test-utils")]
+pub use adapters::filesystem::{
+    apply_retention, clear_debug_log_files, clear_debug_log_files_with_secure_delete,
+    ClearLogFilesReport, FileSystemRepository, RetentionReport,
+};
+#[cfg(feature = "test-utils")]
+pub use adapters::log_forwarding::{BackendLogForwarder, BatchAggregator};
+#[cfg(feature = "test-utils")]
+pub use adapters::log_query::tail_console_log;
+#[cfg(feature = "test-utils")]
+pub use adapters::log_query::{FileSystemLogQueryService, InMemoryLogQueryService};
+#[cfg(feature = "test-utils")]
+pub use adapters::ownership::OwnedFiles;
+#[cfg(feature = "test-utils")]
+pub use adapters::plugin_log::plugin_log_files;
+#[cfg(feature = "test-utils")]
+pub use adapters::recording::assemble_apng;
+#[cfg(feature = "sqlite")]
+pub use adapters::sqlite::SqliteRepository;
+#[cfg(feature = "test-utils")]
+pub use adapters::storage::{FileOps, StdFileOps, WritePolicy};
+#[cfg(feature = "test-utils")]
+pub use adapters::SnapshotIndex;
+#[cfg(feature = "test-utils")]
+pub use adapters::{
+    filesystem::{acquire_instance_lock, release_instance_lock, snapshots_referencing},
+    screenshots::{crop_screenshot, newest_screenshot_within, read_image_dimensions},
+};
+#[cfg(feature = "test-utils")]
+pub use application::{CaptureWebViewStateUseCase, DEFAULT_WINDOW_LABEL};
+#[cfg(feature = "test-utils")]
+pub use commands::{
+    annotate_log_entry, append_debug_logs, append_dom_chunk, begin_dom_capture,
+    capture_debug_screenshot_region, capture_dom_delta, capture_element_screenshot,
+    capture_screenshot, capture_webview_state, check_clock_skew, check_disk_usage,
+    clear_strict_violations, delete_debug_screenshot, export_encrypted_bundle,
+    export_filtered_logs, final_beacon_protocol, finish_dom_capture, get_debug_config,
+    get_debug_stats, get_log_directory, get_log_stats, import_console_logs,
+    import_encrypted_bundle, list_debug_screenshots, list_debug_sessions, list_log_annotations,
+    mark_debug_moment, merge_debug_bundles, query_console_logs, read_debug_snapshot,
+    reconstruct_from_baseline, run_self_test, save_dom_snapshot, set_debug_config, set_legal_hold,
+    set_quiet_mode, submit_final_beacon, tail_plugin_log, write_typed_debug_snapshot,
+    ConsoleLogEntryPayload, DebugConfigUpdate, DomSnapshotPayload, DomUploadMetadata,
+    ExportFilteredLogsRequest, FinalBeacon, DISK_USAGE_WARNING_EVENT,
+};
+#[cfg(feature = "test-utils")]
+pub use config::sanitize_filename;
+pub use config::{
+    ActiveCaptureProfile, ArtifactPaths, CaptureProfile, DebugToolsConfig, EffectiveConfig,
+    LogFormat,
+};
+#[cfg(feature = "test-utils")]
+pub use domain::{moments::DEBUG_MOMENT_FIELD, DebugMoments};
+#[cfg(feature = "test-utils")]
+pub use domain::{
+    plugin_log::{parse_plugin_log_line, to_console_entry, PLUGIN_LOG_SOURCE},
+    PluginLogLine,
+};
+#[cfg(feature = "test-utils")]
+pub use domain::{
+    screenshot_region::crop_rect, PixelRect, ScreenshotRect, ScreenshotRegion,
+    ScreenshotRegionError,
+};
+#[cfg(feature = "test-utils")]
+pub use domain::{user_agents::PLACEHOLDER_USER_AGENT, UserAgents};
+pub use domain::{
+    AnnotationError, Clock, ConsoleLogEntry, ConsoleSource, DebugMoment, DebugSnapshot,
+    DomSnapshotResult, DomState, EncryptedBundleError, HealthHttpConfig, HealthSummary,
+    IdGenerator, LogHistogramBucket, LogLevel, LogQuery, LogQueryService, LogStore,
+    PartialDebugSnapshot, PrivacyOverrides, PrivacyPreset, PrivacySettings, PrivacyStatus,
+    RepositoryError, ScreenshotDeleteError, ScreenshotInfo, ScreenshotsPluginStatus,
+    SnapshotReadError, SnapshotRepository, SystemClock, SystemIdGenerator, TraceContext,
+    WebViewState,
+};
+#[cfg(feature = "test-utils")]
+pub use domain::{ArtifactKind, InternalActivity, LegalHolds, RetentionPolicy, ViewportInfo};
+#[cfg(feature = "test-utils")]
+pub use domain::{BackendLogBatch, BackendLogEvent, FixedClock, SequentialIdGenerator};
+#[cfg(feature = "test-utils")]
+pub use domain::{
+    CategoryUsage, CrossSourceDedup, DiskCategory, DiskLimitSource, DiskUsage, DiskUsageMonitor,
+    DiskUsageWarning,
+};
+#[cfg(feature = "test-utils")]
+pub use domain::{SnapshotListEntry, SnapshotSort};
 
-use adapters::{init_tracing, FileSystemRepository};
-use application::{AppendConsoleLogsUseCase, CaptureDebugSnapshotUseCase, SaveDomSnapshotUseCase};
-use config::ConfigError;
+use adapters::annotations::AnnotationStore;
+use adapters::filesystem::{export_console_logs, InstanceLock};
+use adapters::health_http::HealthHttpServer;
+use adapters::legal_hold::LegalHoldStore;
+use adapters::log_forwarding::BACKEND_LOG_BATCH_EVENT;
+use adapters::log_repair::check_previous_logs;
+use adapters::logging::in_layer_write_scope;
+use adapters::platform::{probe_filesystem, FilesystemProbe};
+use adapters::{init_tracing, MeteredRepository, ScreenshotIndex};
+use analysis::sourcemap::StackResolver;
+use application::{
+    AppendConsoleLogsUseCase, BeforeCaptureHook, CaptureDebugSnapshotUseCase, DebugCommandBroker,
+    NavigationDebouncer, PeriodicTask, SaveDomSnapshotUseCase, ScreenshotQueue,
+    SnapshotCapturedHook, SnapshotContributor, SnapshotHooks, SuspendDetector, WindowRecorder,
+    SNAPSHOT_CAPTURED_EVENT,
+};
+use config::{ConfigError, StorageMode};
+use domain::{
+    ActivitySummary, BodyCaptureRule, CaptureContext, DeliveredSeqs, DeprecationTracker,
+    ErrorAlarm, ErrorRateTracker, FilesystemInfo, FilesystemKind, FrontendReports, InitRecorder,
+    InitReportCell, LifecycleEvent, LifecycleEventKind, LifecycleTracker, LogExportFormat,
+    PlatformGpuInfo, PrivacyControl, QuietMode, ShutdownSummary, StorageStatus, StrictMode,
+    StrictViolationKind,
+};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+type Repository = MeteredRepository<Arc<dyn SnapshotRepository>>;
+
+const CONSOLE_BRIDGE_SCRIPT: &str = include_str!("console_bridge.js");
+const USER_AGENT_SCRIPT: &str = include_str!("user_agent.js");
 
 pub struct DebugToolsState {
     pub config: Arc<DebugToolsConfig>,
-    pub repository: Arc<FileSystemRepository>,
-    pub append_logs_use_case: Arc<AppendConsoleLogsUseCase<FileSystemRepository>>,
-    pub save_dom_use_case: Arc<SaveDomSnapshotUseCase<FileSystemRepository>>,
-    pub capture_snapshot_use_case: Arc<CaptureDebugSnapshotUseCase<FileSystemRepository>>,
-    #[allow(dead_code)]
-    tracing_guard: adapters::logging::TracingGuard,
+    pub repository: Arc<Repository>,
+    pub log_query: Arc<dyn LogQueryService>,
+    pub append_logs_use_case: Arc<AppendConsoleLogsUseCase<Repository>>,
+    pub save_dom_use_case: Arc<SaveDomSnapshotUseCase<Repository>>,
+    pub capture_snapshot_use_case: Arc<CaptureDebugSnapshotUseCase<Repository>>,
+    pub screenshot_index: Arc<ScreenshotIndex>,
+    pub screenshot_queue: Arc<ScreenshotQueue>,
+    pub legal_holds: Arc<LegalHoldStore>,
+    pub annotations: Arc<AnnotationStore>,
+    pub moments: Arc<domain::DebugMoments>,
+    pub buffer_pool: Arc<adapters::buffer_pool::BufferPool>,
+    pub owned_files: Arc<adapters::ownership::OwnedFiles>,
+    pub window_recorder: Arc<WindowRecorder>,
+    pub snapshot_index: Arc<adapters::SnapshotIndex>,
+    pub quiet_mode: Arc<QuietMode>,
+    pub capture_profile: Arc<RwLock<ActiveCaptureProfile>>,
+    pub privacy: Arc<PrivacyControl>,
+    pub debug_commands: Arc<DebugCommandBroker>,
+    pub frontend_reports: Arc<Mutex<FrontendReports>>,
+    pub effective_config_path: Arc<Mutex<Option<PathBuf>>>,
+    pub session_id: String,
+    pub webview_state_cache: Arc<Mutex<Option<WebViewState>>>,
+    pub error_rate: Arc<ErrorRateTracker>,
+    pub error_alarm: Option<Arc<ErrorAlarm>>,
+    pub body_capture: Arc<RwLock<Option<BodyCaptureRule>>>,
+    pub platform_gpu: Arc<OnceLock<PlatformGpuInfo>>,
+    pub clock: Arc<dyn Clock>,
+    pub ids: Arc<dyn IdGenerator>,
+    pub instance_lock: Arc<InstanceLock>,
+    pub navigation: Arc<NavigationDebouncer>,
+    pub lifecycle: Arc<LifecycleTracker>,
+    pub deprecations: Arc<DeprecationTracker>,
+    pub activity: Arc<domain::InternalActivity>,
+    pub retention_task: Arc<OnceLock<PeriodicTask>>,
+    pub housekeeping_task: Arc<OnceLock<PeriodicTask>>,
+    // Set once tauri-plugin-screenshots, registered on a background thread
+    // after setup, is usable (`Ok`) or never will be (`Err` with the reason).
+    // The thread is needed because `AppHandle::plugin` cannot run while
+    // plugins are still being set up; everything else is in place before
+    // setup returns.
+    pub screenshots_plugin: Arc<OnceLock<Result<(), String>>>,
+    pub strict_mode: Arc<StrictMode>,
+    pub health_server: Arc<OnceLock<HealthHttpServer>>,
+    // What `/healthz` serves: the result of the last `run_self_test`.
+    pub last_self_test: Arc<Mutex<Option<HealthSummary>>>,
+    // Settled during setup for `StorageMode::Auto`; otherwise filled in by
+    // a background probe shortly after.
+    pub storage_status: Arc<RwLock<StorageStatus>>,
+    pub init_report: Arc<InitReportCell>,
+    pub dom_uploads: Arc<adapters::dom_upload::DomUploadStore>,
+    pub delivered_seqs: Arc<DeliveredSeqs>,
+    pub console_dedup: Arc<domain::CrossSourceDedup>,
+    pub disk_usage: Arc<domain::DiskUsageMonitor>,
+    // Present when `sourcemap_dir` is set; maps load on the first export
+    // that resolves stack traces.
+    pub stack_resolver: Option<Arc<StackResolver>>,
+    pub forwarder: Option<Arc<adapters::log_forwarding::BackendLogForwarder>>,
+    // Taken by the shutdown sequence; dropping it flushes the log writer.
+    pub(crate) tracing_guard: Mutex<Option<adapters::logging::TracingGuard>>,
+    pub(crate) shutdown: OnceLock<ShutdownSummary>,
 }
 
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("debug-tools")
-        .setup(|app, _api| {
-            let config = Arc::new(
-                DebugToolsConfig::from_app_handle(app.app_handle())
-                    .map_err(|e: ConfigError| e.to_string())?,
-            );
+impl DebugToolsState {
+    pub fn screenshots_plugin_status(&self) -> ScreenshotsPluginStatus {
+        match self.screenshots_plugin.get() {
+            None => ScreenshotsPluginStatus::Registering,
+            Some(Ok(())) => ScreenshotsPluginStatus::Ready,
+            Some(Err(reason)) => ScreenshotsPluginStatus::Unavailable {
+                reason: reason.clone(),
+            },
+        }
+    }
+
+    // Runs once; later calls return the first summary. Tracing is flushed
+    // last so that the log lines of the earlier steps reach the file.
+    pub(crate) fn shutdown(&self, reason: &str, export_logs: bool) -> ShutdownSummary {
+        self.shutdown
+            .get_or_init(|| {
+                let started = Instant::now();
+                let started_at = self.clock.now_millis();
+                let mut failures = Vec::new();
+
+                let forwarded_events = self
+                    .forwarder
+                    .as_ref()
+                    .map_or(0, |forwarder| forwarder.flush());
+
+                let retention_removed = run_retention(self);
+
+                let export = export_logs
+                    .then(|| {
+                        let path = self
+                            .config
+                            .log_export_dir()
+                            .join(format!("shutdown_{}.jsonl", started_at));
+                        export_console_logs(
+                            self.log_query.as_ref(),
+                            &LogQuery::default(),
+                            &path,
+                            LogExportFormat::Jsonl,
+                            &[],
+                            None,
+                            &self.annotations,
+                        )
+                        .map_err(|e| failures.push(format!("export: {}", e)))
+                        .ok()
+                    })
+                    .flatten();
+
+                if let Some(task) = self.retention_task.get() {
+                    task.cancel();
+                }
+                if let Some(task) = self.housekeeping_task.get() {
+                    task.cancel();
+                }
+
+                let abandoned_dom_uploads = self.dom_uploads.clear();
+
+                tracing::info!(
+                    reason,
+                    forwarded_events,
+                    retention_removed,
+                    abandoned_dom_uploads,
+                    exported = export.as_ref().map(|export| export.entries),
+                    "Debug tools shutting down"
+                );
+
+                let guard = self
+                    .tracing_guard
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .take();
+                let tracing_flushed = guard.is_some();
+                #[cfg(feature = "flamegraph")]
+                if let Some(guard) = &guard {
+                    if let Err(e) = guard.flush_flamegraph() {
+                        failures.push(format!("flamegraph: {}", e));
+                    }
+                }
+                drop(guard);
+
+                ShutdownSummary {
+                    reason: reason.to_string(),
+                    started_at,
+                    duration_ms: started.elapsed().as_millis() as u64,
+                    forwarded_events,
+                    retention_removed,
+                    export,
+                    abandoned_dom_uploads,
+                    tracing_flushed,
+                    failures,
+                }
+            })
+            .clone()
+    }
+
+    pub fn storage_status(&self) -> StorageStatus {
+        self.storage_status
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn capture_context(&self) -> CaptureContext {
+        CaptureContext {
+            frontend_reports: self
+                .frontend_reports
+                .lock()
+                .map(|reports| reports.clone())
+                .unwrap_or_default(),
+            effective_config_path: self
+                .effective_config_path
+                .lock()
+                .ok()
+                .and_then(|path| path.clone()),
+            session_id: Some(self.session_id.clone()),
+            platform_gpu: Some(self.platform_gpu_info()),
+            lifecycle_events: self.lifecycle.recent(),
+            strip_url_queries: self.privacy.settings().strip_url_queries,
+            plugin_init: self.init_report.take_for_snapshot(),
+        }
+    }
+
+    pub(crate) fn record_lifecycle(
+        &self,
+        kind: LifecycleEventKind,
+        window_label: Option<String>,
+        gap_ms: Option<i64>,
+    ) -> LifecycleEvent {
+        let event = LifecycleEvent {
+            timestamp: self.clock.now_millis(),
+            kind,
+            window_label,
+            gap_ms,
+        };
+
+        self.lifecycle.push(event.clone());
+        if let Err(e) = self
+            .append_logs_use_case
+            .append_marker(event.to_log_entry())
+        {
+            tracing::warn!(error = %e, "Failed to persist lifecycle marker");
+        }
+
+        event
+    }
+
+    // Queried on first use and cached: the hardware does not change within a session.
+    pub(crate) fn platform_gpu_info(&self) -> PlatformGpuInfo {
+        self.platform_gpu
+            .get_or_init(adapters::platform::query_gpu_info)
+            .clone()
+    }
+
+    pub(crate) fn cached_webview_state(&self) -> Option<WebViewState> {
+        self.webview_state_cache
+            .lock()
+            .ok()
+            .and_then(|cached| cached.clone())
+    }
+
+    pub(crate) fn cache_webview_state(&self, webview_state: &WebViewState) {
+        if let Ok(mut cached) = self.webview_state_cache.lock() {
+            *cached = Some(webview_state.clone());
+        }
+    }
+
+    pub(crate) fn effective_config(&self, reason: &str) -> EffectiveConfig {
+        let now_millis = self.clock.now_millis();
+
+        let active_profile = self
+            .capture_profile
+            .read()
+            .map(|active| active.clone())
+            .unwrap_or_else(|_| self.config.initial_capture_profile());
+
+        EffectiveConfig {
+            written_at: now_millis,
+            reason: reason.to_string(),
+            log_dir: self.config.log_dir.clone(),
+            max_log_size_bytes: self.config.max_log_size_bytes,
+            log_format: self.config.log_format.clone(),
+            enable_dom_capture: self.config.enable_dom_capture,
+            enable_rust_logging: self.config.enable_rust_logging,
+            active_profile,
+            quiet_mode: self.quiet_mode.status(now_millis.div_euclid(1000)),
+            privacy: Some(self.privacy.status()),
+            session_id: Some(self.session_id.clone()),
+        }
+    }
 
-            config.ensure_subdirectories().map_err(|e| e.to_string())?;
+    pub(crate) fn record_effective_config(&self, reason: &str) -> EffectiveConfig {
+        let effective = self.effective_config(reason);
 
-            let tracing_guard = init_tracing(config.clone()).map_err(|e| e.to_string())?;
+        match adapters::filesystem::write_effective_config(
+            &self.config,
+            &effective,
+            &self.owned_files,
+        ) {
+            Ok(path) => {
+                if let Ok(mut latest) = self.effective_config_path.lock() {
+                    *latest = Some(path);
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to write effective config"),
+        }
+
+        effective
+    }
+
+    // Ends an expired quiet mode and records the config change. Called from
+    // the housekeeping tick and by readers of quiet-mode state.
+    pub(crate) fn finish_quiet_mode(&self) -> Result<(), String> {
+        let finished = self
+            .append_logs_use_case
+            .finish_quiet_mode(self.clock.now_secs())
+            .map_err(|e| e.to_string())?;
+        if finished.is_some() {
+            self.record_effective_config("quiet_mode_expired");
+        }
+        Ok(())
+    }
+}
+
+/// Configures and builds the debug-tools plugin. [`init`] is the same as
+/// `Builder::new().build()`.
+///
+/// Zero-config, with the WebView console mirrored without the guest-js package:
+///
+/// ```rust,no_run
+/// let plugin = tauri_plugin_debug_tools::Builder::new()
+///     .inject_console_bridge(true)
+///     .build::<tauri::Wry>();
+/// ```
+///
+/// Custom log directory with screenshots turned off:
+///
+/// ```rust,no_run
+/// use tauri_plugin_debug_tools::{Builder, DebugToolsConfig};
+///
+/// let config = DebugToolsConfig {
+///     log_dir: std::env::temp_dir().join("my-app-debug"),
+///     ..DebugToolsConfig::default()
+/// };
+///
+/// let plugin = Builder::new()
+///     .config(config)
+///     .disable_screenshots()
+///     .build::<tauri::Wry>();
+/// ```
+///
+/// Extra snapshot data and a notification once a snapshot is saved:
+///
+/// ```rust,no_run
+/// let plugin = tauri_plugin_debug_tools::Builder::new()
+///     .contributor("feature_flags", |_snapshot| {
+///         Some(serde_json::json!({ "new_checkout": true }))
+///     })
+///     .on_snapshot(|snapshot| println!("snapshot saved at {}", snapshot.timestamp))
+///     .build::<tauri::Wry>();
+/// ```
+#[derive(Default)]
+pub struct Builder {
+    config: Option<DebugToolsConfig>,
+    repository: Option<Arc<dyn SnapshotRepository>>,
+    before_capture: Option<BeforeCaptureHook>,
+    contributors: Vec<(String, SnapshotContributor)>,
+    snapshot_hooks: Vec<SnapshotCapturedHook>,
+    clock: Option<Arc<dyn Clock>>,
+    id_generator: Option<Arc<dyn IdGenerator>>,
+    log_store: Option<Arc<dyn LogStore>>,
+    log_query: Option<Arc<dyn LogQueryService>>,
+    disable_screenshots: bool,
+    inject_console_bridge: bool,
+    plugin_log_file_name: Option<Option<String>>,
+    log_to_stdout: Option<bool>,
+    max_log_size_bytes: Option<u64>,
+    forward_backend_logs: Option<bool>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the config derived from the app's log directory. `log_dir` is
+    /// created if missing.
+    pub fn config(mut self, config: DebugToolsConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Persists snapshots, DOM captures and console logs through `repository`
+    /// instead of the log directory. Reads and cleanup still use the log directory.
+    pub fn repository(mut self, repository: Arc<dyn SnapshotRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Adds `name` to every full snapshot's `extensions` with the value returned
+    /// by `contribute`; `None` leaves it out. A frontend-supplied extension of
+    /// the same name takes precedence.
+    pub fn contributor<F>(mut self, name: impl Into<String>, contribute: F) -> Self
+    where
+        F: Fn(&DebugSnapshot) -> Option<serde_json::Value> + Send + Sync + 'static,
+    {
+        self.contributors.push((name.into(), Box::new(contribute)));
+        self
+    }
+
+    /// Shorthand for [`Builder::on_snapshot_captured`] when the saved path is not needed.
+    pub fn on_snapshot<F>(self, callback: F) -> Self
+    where
+        F: Fn(&DebugSnapshot) + Send + Sync + 'static,
+    {
+        self.on_snapshot_captured(move |snapshot, _path| callback(snapshot))
+    }
+
+    /// Called with every full snapshot and its saved path once it is persisted.
+    /// Callbacks run in registration order on a background thread; each is
+    /// bounded by `snapshot_hook_timeout_ms`, and a panic is logged instead of
+    /// reaching the plugin.
+    pub fn on_snapshot_captured<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&DebugSnapshot, &Path) + Send + Sync + 'static,
+    {
+        self.snapshot_hooks.push(Arc::new(callback));
+        self
+    }
+
+    /// Skips registering the screenshots plugin and turns screenshots off in
+    /// every capture profile.
+    pub fn disable_screenshots(mut self) -> Self {
+        self.disable_screenshots = true;
+        self
+    }
+
+    /// Names tauri-plugin-log's file in the app log directory, where
+    /// `tail_plugin_log` and the clean-up settings look for it. Applied on top
+    /// of [`Builder::config`].
+    pub fn log_file_name(mut self, name: impl Into<String>) -> Self {
+        self.plugin_log_file_name = Some(Some(name.into()));
+        self
+    }
+
+    /// For hosts without tauri-plugin-log's file target; its file is then ignored.
+    pub fn without_plugin_log_file(mut self) -> Self {
+        self.plugin_log_file_name = Some(None);
+        self
+    }
+
+    /// Whether tracing events are also printed to stdout (default `true`).
+    /// Applied on top of [`Builder::config`].
+    pub fn with_stdout(mut self, enabled: bool) -> Self {
+        self.log_to_stdout = Some(enabled);
+        self
+    }
+
+    /// Size in bytes the console log and `rust_debug.log` may reach before
+    /// they are rotated, recorded as `max_log_size_bytes` (default 50 KB; 0
+    /// turns rotation off). Takes a `u128` like tauri-plugin-log's
+    /// `max_file_size`; larger values saturate. Applied on top of
+    /// [`Builder::config`].
+    pub fn max_file_size(mut self, bytes: u128) -> Self {
+        self.max_log_size_bytes = Some(u64::try_from(bytes).unwrap_or(u64::MAX));
+        self
+    }
+
+    /// Whether tracing events are forwarded to the webview as
+    /// `debug-tools://backend-log-batch` (default `false`), like
+    /// tauri-plugin-log's webview target. Sets `forward_backend_logs`; applied
+    /// on top of [`Builder::config`].
+    pub fn with_webview_target(mut self, enabled: bool) -> Self {
+        self.forward_backend_logs = Some(enabled);
+        self
+    }
+
+    /// Injects a script into every webview that mirrors `console.*` calls and
+    /// uncaught errors into the console log, for apps not using the guest-js package.
+    pub fn inject_console_bridge(mut self, inject: bool) -> Self {
+        self.inject_console_bridge = inject;
+        self
+    }
+
+    /// Runs before every full snapshot is persisted; return `false` to abort the save.
+    pub fn before_capture<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut DebugSnapshot) -> bool + Send + Sync + 'static,
+    {
+        self.before_capture = Some(Box::new(hook));
+        self
+    }
+
+    /// Source of every timestamp the plugin records or puts in a filename.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Source of the process id used in log filenames, the session id and request ids.
+    pub fn id_generator(mut self, id_generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = Some(id_generator);
+        self
+    }
+
+    /// Mirrors persisted console logs into `store` and answers log queries from it
+    /// instead of scanning the JSONL files.
+    pub fn log_store<S: LogStore + 'static>(mut self, store: S) -> Self {
+        let store = Arc::new(store);
+        self.log_store = Some(store.clone());
+        self.log_query = Some(store);
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        let config = self.config;
+        let repository = self.repository;
+        let before_capture = self.before_capture;
+        let contributors = self.contributors;
+        let snapshot_hooks = self.snapshot_hooks;
+        let clock = self.clock;
+        let id_generator = self.id_generator;
+        let log_store = self.log_store;
+        let log_query = self.log_query;
+        let disable_screenshots = self.disable_screenshots;
+        let plugin_log_file_name = self.plugin_log_file_name;
+        let log_to_stdout = self.log_to_stdout;
+        let max_log_size_bytes = self.max_log_size_bytes;
+        let forward_backend_logs = self.forward_backend_logs;
+
+        // One init script per plugin, so the bridge rides along with the
+        // user agent report.
+        let init_script = if self.inject_console_bridge {
+            format!("{}\n{}", USER_AGENT_SCRIPT, CONSOLE_BRIDGE_SCRIPT)
+        } else {
+            USER_AGENT_SCRIPT.to_string()
+        };
+        let plugin = PluginBuilder::new("debug-tools").js_init_script(init_script);
+
+        plugin
+            .setup(move |app, _api| {
+                let mut init = InitRecorder::new();
+                let clock: Arc<dyn Clock> = clock.unwrap_or_else(|| Arc::new(SystemClock));
+                let ids: Arc<dyn IdGenerator> = id_generator
+                    .unwrap_or_else(|| Arc::new(SystemIdGenerator::new(clock.as_ref())));
+                let started_at = clock.now_millis();
+
+                let mut config = init.phase("config", || match config {
+                    Some(config) => {
+                        std::fs::create_dir_all(&config.log_dir).map_err(|e| e.to_string())?;
+                        Ok::<_, String>(config)
+                    }
+                    None => DebugToolsConfig::from_app_handle(app.app_handle())
+                        .map_err(|e: ConfigError| e.to_string()),
+                })?;
+                if disable_screenshots {
+                    config.disable_screenshots();
+                }
+                if let Some(name) = plugin_log_file_name {
+                    config.plugin_log_file_name = name;
+                }
+                if let Some(enabled) = log_to_stdout {
+                    config.log_to_stdout = enabled;
+                }
+                if let Some(bytes) = max_log_size_bytes {
+                    config.max_log_size_bytes = bytes;
+                }
+                if let Some(enabled) = forward_backend_logs {
+                    config.forward_backend_logs = enabled;
+                }
+
+                // A second registration would install another subscriber and
+                // race the first over the same files.
+                if app.try_state::<DebugToolsState>().is_some() {
+                    if config.strict_singleton {
+                        return Err("debug-tools plugin is already registered".into());
+                    }
+                    tracing::warn!(
+                        "debug-tools plugin registered more than once; ignoring the duplicate"
+                    );
+                    return Ok(());
+                }
+
+                // `Auto` needs the filesystem before anything is written, so
+                // its probe overlaps the instance lock; explicit modes only
+                // report it and are probed after setup.
+                let probe = (config.storage_mode == StorageMode::Auto)
+                    .then(|| FilesystemProbe::spawn(config.log_dir.clone()));
+
+                let instance_lock = init
+                    .phase("instance_lock", || {
+                        adapters::filesystem::acquire_instance_lock(
+                            &mut config,
+                            ids.process_id(),
+                            &ids.session_id(),
+                            clock.now_millis(),
+                        )
+                    })
+                    .map_err(|e| e.to_string())?;
+                if let Some(other_pid) = instance_lock.shared_with {
+                    init.fallback(format!("log directory shared with pid {}", other_pid));
+                } else if let Some(stale_pid) = instance_lock.reclaimed_from {
+                    init.fallback(format!("reclaimed stale lock of pid {}", stale_pid));
+                }
 
+                let storage_status = init.phase("storage_mode", || {
+                    probe.map(|probe| {
+                        let (filesystem, inline) = probe.wait();
+                        (resolve_storage_mode(&mut config, filesystem), inline)
+                    })
+                });
+                let storage_probed = storage_status.is_some();
+                let storage_status = match storage_status {
+                    Some((status, inline)) => {
+                        if inline {
+                            init.fallback("filesystem probed on the setup thread");
+                        }
+                        status
+                    }
+                    None => storage_status_for(
+                        &config,
+                        FilesystemInfo {
+                            kind: FilesystemKind::Unknown,
+                            fs_type: None,
+                        },
+                    ),
+                };
+
+                let config = Arc::new(config);
+                init.phase("directories", || {
+                    config.ensure_subdirectories()?;
+                    config.resolve_log_root()
+                })
+                .map_err(|e| e.to_string())?;
+
+                let quiet_mode = Arc::new(QuietMode::default());
+                let forwarder = config
+                    .forward_backend_logs
+                    .then(|| adapters::log_forwarding::BackendLogForwarder::new(&config, clock.clone()));
+                let error_alarm = config.error_alarm.as_ref().map(|alarm| {
+                    Arc::new(ErrorAlarm::new(
+                        alarm.threshold_count,
+                        alarm.window_secs,
+                        alarm.cooldown_secs,
+                    ))
+                });
+                let tracing_guard = init
+                    .phase("tracing", || {
+                        init_tracing(
+                            config.clone(),
+                            quiet_mode.clone(),
+                            clock.clone(),
+                            forwarder.clone(),
+                            error_alarm.clone(),
+                        )
+                    })
+                    .map_err(|e| e.to_string())?;
+
+                if let Some(forwarder) = &forwarder {
+                    let handle = app.app_handle().clone();
+                    forwarder.set_emitter(Arc::new(move |batch| {
+                        if let Err(e) = handle.emit(BACKEND_LOG_BATCH_EVENT, batch) {
+                            tracing::warn!(error = %e, "Failed to forward backend logs");
+                        }
+                    }));
+                }
+
+                tracing::info!(
+                    log_dir = %config.log_dir.display(),
+                    "Debug tools plugin initialized"
+                );
+
+                if let Some(other_pid) = instance_lock.shared_with {
+                    tracing::warn!(
+                        other_pid,
+                        log_dir = %config.log_dir.display(),
+                        "Log directory is in use by another process; writing to a per-instance subdirectory"
+                    );
+                }
+                if let Some(stale_pid) = instance_lock.reclaimed_from {
+                    tracing::info!(stale_pid, "Reclaimed stale instance lock");
+                }
+
+                let init_report = Arc::new(InitReportCell::pending(started_at));
+                let state_phase = init.start("state");
+                let app_name = app.package_info().name.clone();
+                let activity = Arc::new(domain::InternalActivity::default());
+                let snapshot_index = Arc::new(adapters::SnapshotIndex::default());
+                let owned_files = Arc::new(adapters::ownership::OwnedFiles::load(&config));
+                let buffer_pool = Arc::new(adapters::buffer_pool::BufferPool::new(
+                    config.buffer_pool_max_bytes,
+                    Duration::from_secs(config.buffer_pool_idle_secs),
+                ));
+                let backend: Arc<dyn SnapshotRepository> = repository.unwrap_or_else(|| {
+                    Arc::new(
+                        adapters::FileSystemRepository::new(
+                            config.clone(),
+                            app_name.clone(),
+                            ids.process_id(),
+                            activity.clone(),
+                        )
+                        .with_snapshot_index(snapshot_index.clone())
+                        .with_owned_files(owned_files.clone())
+                        .with_buffer_pool(buffer_pool.clone()),
+                    )
+                });
+                let repository = Arc::new(MeteredRepository::new(backend));
+                let log_query: Arc<dyn LogQueryService> = log_query.unwrap_or_else(|| {
+                    Arc::new(adapters::FileSystemLogQueryService::new(
+                        config.clone(),
+                        app_name,
+                        ids.process_id(),
+                    ))
+                });
+
+                let append_logs_use_case = Arc::new(
+                    AppendConsoleLogsUseCase::new(
+                        repository.clone(),
+                        quiet_mode.clone(),
+                        clock.clone(),
+                    )
+                    .with_log_store(log_store)
+                    .with_clock_skew_tolerance(config.clock_skew_tolerance_ms),
+                );
+                let save_dom_use_case = Arc::new(SaveDomSnapshotUseCase::new(
+                    repository.clone(),
+                    clock.clone(),
+                ));
+                let snapshot_hooks = {
+                    let handle = app.app_handle().clone();
+                    Arc::new(
+                        SnapshotHooks::new(
+                            snapshot_hooks,
+                            Duration::from_millis(config.snapshot_hook_timeout_ms),
+                        )
+                        .with_emitter(Box::new(move |event| {
+                            if let Err(e) = handle.emit(SNAPSHOT_CAPTURED_EVENT, event) {
+                                tracing::warn!(error = %e, "Failed to emit snapshot event");
+                            }
+                        })),
+                    )
+                };
+                let moments = Arc::new(domain::DebugMoments::default());
+                let capture_snapshot_use_case = Arc::new(
+                    CaptureDebugSnapshotUseCase::new(repository.clone(), clock.clone())
+                        .with_before_capture(before_capture)
+                        .with_contributors(contributors)
+                        .with_snapshot_hooks(snapshot_hooks)
+                        .with_size_thresholds(config.capture_size_warnings.clone())
+                        .with_moments(moments.clone()),
+                );
+
+                let legal_holds = Arc::new(LegalHoldStore::load(&config));
+                let annotations = Arc::new(AnnotationStore::load(&config, &ids.session_id()));
+
+                let screenshot_queue = Arc::new(ScreenshotQueue::new(
+                    config.screenshot_concurrency,
+                    Duration::from_millis(config.screenshot_timeout_ms),
+                    Duration::from_millis(config.screenshot_coalesce_ms),
+                ));
+
+                let capture_profile = Arc::new(RwLock::new(config.initial_capture_profile()));
+                let privacy = Arc::new(PrivacyControl::new(
+                    config.privacy_preset,
+                    config.privacy_overrides.clone(),
+                    config.privacy_baseline(),
+                ));
+
+                let error_rate = Arc::new(ErrorRateTracker::new(
+                    config.error_rate_window_secs,
+                    config.error_rate_window_count,
+                ));
+
+                let console_dedup = Arc::new(domain::CrossSourceDedup::new(&config.console_sources));
+                let body_capture = Arc::new(RwLock::new(config.capture_bodies_for.clone()));
+                let dom_uploads = Arc::new(adapters::dom_upload::DomUploadStore::new(
+                    &config,
+                    clock.clone(),
+                ));
+                let stack_resolver = config
+                    .sourcemap_dir
+                    .clone()
+                    .map(|dir| Arc::new(StackResolver::new(dir)));
+
+                let strict_mode = Arc::new(StrictMode::new(config.strict_mode));
+                record_storage_fallback(&strict_mode, &storage_status, clock.now_millis());
+
+                let state = DebugToolsState {
+                    config,
+                    repository,
+                    log_query,
+                    append_logs_use_case,
+                    save_dom_use_case,
+                    capture_snapshot_use_case,
+                    screenshot_index: Arc::new(ScreenshotIndex::default()),
+                    screenshot_queue,
+                    legal_holds,
+                    annotations,
+                    moments,
+                    buffer_pool,
+                    owned_files,
+                    window_recorder: Arc::new(WindowRecorder::default()),
+                    snapshot_index,
+                    quiet_mode,
+                    capture_profile,
+                    privacy,
+                    debug_commands: Arc::new(DebugCommandBroker::new(clock.clone(), ids.clone())),
+                    frontend_reports: Arc::new(Mutex::new(FrontendReports::default())),
+                    effective_config_path: Arc::new(Mutex::new(None)),
+                    session_id: ids.session_id(),
+                    webview_state_cache: Arc::new(Mutex::new(None)),
+                    error_rate,
+                    error_alarm: error_alarm.clone(),
+                    body_capture,
+                    platform_gpu: Arc::new(OnceLock::new()),
+                    clock,
+                    ids,
+                    instance_lock: Arc::new(instance_lock),
+                    navigation: Arc::new(NavigationDebouncer::default()),
+                    lifecycle: Arc::new(LifecycleTracker::default()),
+                    deprecations: Arc::new(DeprecationTracker::default()),
+                    activity,
+                    retention_task: Arc::new(OnceLock::new()),
+                    housekeeping_task: Arc::new(OnceLock::new()),
+                    screenshots_plugin: Arc::new(OnceLock::new()),
+                    strict_mode,
+                    health_server: Arc::new(OnceLock::new()),
+                    last_self_test: Arc::new(Mutex::new(None)),
+                    storage_status: Arc::new(RwLock::new(storage_status)),
+                    init_report: init_report.clone(),
+                    dom_uploads,
+                    delivered_seqs: Arc::new(DeliveredSeqs::default()),
+                    console_dedup,
+                    disk_usage: Arc::new(domain::DiskUsageMonitor::default()),
+                    stack_resolver,
+                    forwarder,
+                    tracing_guard: Mutex::new(Some(tracing_guard)),
+                    shutdown: OnceLock::new(),
+                };
+                init.end(state_phase);
+
+                init.phase("effective_config", || state.record_effective_config("setup"));
+
+                let housekeeping_interval =
+                    Duration::from_secs(state.config.housekeeping_interval_secs.max(1));
+                let summary_interval = Duration::from_secs(state.config.self_log_summary_secs);
+                let retention_interval = state
+                    .config
+                    .retention
+                    .is_some()
+                    .then(|| Duration::from_secs(state.config.retention_interval_secs.max(1)));
+
+                app.manage(state);
+                app.manage(domain::UserAgents::default());
+
+                let background_phase = init.start("background_tasks");
+                if let Some(alarm) = &error_alarm {
+                    let handle = app.app_handle().clone();
+                    alarm.set_notifier(Box::new(move |event| {
+                        tauri::async_runtime::spawn(in_layer_write_scope(
+                            commands::handle_error_alarm(handle.clone(), event.clone()),
+                        ));
+                    }));
+                }
+
+                spawn_housekeeping(
+                    app.app_handle().clone(),
+                    housekeeping_interval,
+                    summary_interval,
+                );
+
+                if let Some(interval) = retention_interval {
+                    spawn_retention(app.app_handle().clone(), interval);
+                }
+
+                spawn_health_http(app.app_handle().clone());
+                spawn_log_repair_check(app.app_handle().clone());
+                if !storage_probed {
+                    spawn_storage_probe(app.app_handle().clone());
+                }
+
+                let screenshots_status = app.state::<DebugToolsState>().screenshots_plugin.clone();
+                if disable_screenshots {
+                    let _ = screenshots_status
+                        .set(Err("screenshots are disabled by Builder::disable_screenshots".into()));
+                } else {
+                    let screenshots_plugin = tauri_plugin_screenshots::init();
+                    let handle = app.app_handle().clone();
+                    let init_report = init_report.clone();
+                    let started = Instant::now();
+                    std::thread::spawn(move || {
+                        let fallback = handle.plugin(screenshots_plugin).err().map(|e| {
+                            tracing::error!(error = %e, "Failed to initialize screenshots plugin");
+                            format!("screenshots unavailable: {}", e)
+                        });
+                        if let (Some(reason), Some(state)) =
+                            (&fallback, handle.try_state::<DebugToolsState>())
+                        {
+                            state.strict_mode.record(
+                                StrictViolationKind::ScreenshotsUnavailable,
+                                reason.clone(),
+                                "continue without screenshots; commands that need one fail or skip it",
+                                state.clock.now_millis(),
+                            );
+                        }
+                        let _ = screenshots_status.set(fallback.clone().map_or(Ok(()), Err));
+                        init_report.add_background_phase("screenshots_plugin", started, fallback);
+                    });
+                }
+                init.end(background_phase);
+
+                init_report.complete(init.finish(started_at));
+                let report = init_report.report();
+                tracing::info!(
+                    total_ms = report.total_ms,
+                    thread = %report.thread,
+                    phases = %serde_json::to_string(&report.phases).unwrap_or_default(),
+                    "Plugin init report"
+                );
+
+                Ok(())
+            })
+            .on_navigation(|webview, url| {
+                let app = webview.app_handle();
+                if let Some(state) = app.try_state::<DebugToolsState>() {
+                    if state.config.auto_snapshot_on_navigation {
+                        let label = webview.label().to_string();
+                        let generation = state.navigation.bump(&label);
+                        tauri::async_runtime::spawn(commands::capture_navigation_snapshot(
+                            app.clone(),
+                            label,
+                            url.to_string(),
+                            generation,
+                        ));
+                    }
+                }
+                true
+            })
+            .on_event(|app, event| match event {
+                RunEvent::WindowEvent {
+                    label,
+                    event: WindowEvent::Focused(focused),
+                    ..
+                } => {
+                    if let Some(state) = app.try_state::<DebugToolsState>() {
+                        let kind = if *focused {
+                            LifecycleEventKind::Focus
+                        } else {
+                            LifecycleEventKind::Blur
+                        };
+                        state.record_lifecycle(kind, Some(label.clone()), None);
+                    }
+                }
+                // `ExitRequested` can still be cancelled by the app; `Exit`
+                // is final.
+                RunEvent::Exit => {
+                    if let Some(state) = app.try_state::<DebugToolsState>() {
+                        state.shutdown("exit", state.config.export_logs_on_shutdown);
+                    }
+                }
+                _ => {}
+            })
+            // `sendBeacon` cannot reach the IPC endpoint, which needs custom
+            // headers; the final beacon is posted here instead.
+            .register_uri_scheme_protocol(commands::FINAL_BEACON_SCHEME, |ctx, request| {
+                commands::final_beacon_protocol(ctx.app_handle(), request)
+            })
+            .on_drop(|app| {
+                if let Some(state) = app.try_state::<DebugToolsState>() {
+                    if let Some(task) = state.retention_task.get() {
+                        task.cancel();
+                    }
+                    if let Some(task) = state.housekeeping_task.get() {
+                        task.cancel();
+                    }
+                    if let Some(server) = state.health_server.get() {
+                        server.stop();
+                    }
+                    state.dom_uploads.clear();
+                    adapters::filesystem::release_instance_lock(&state.instance_lock, state.ids.process_id());
+                }
+            })
+            .invoke_handler(tauri::generate_handler![
+                commands::capture_webview_state,
+                commands::get_console_logs,
+                commands::send_debug_command,
+                commands::append_debug_logs,
+                commands::reset_debug_logs,
+                commands::clear_debug_log_files_command,
+                commands::copy_screenshot_to_debug_dir,
+                commands::write_debug_snapshot,
+                commands::capture_dom_snapshot,
+                commands::capture_full_debug_state,
+                commands::get_log_directory,
+                commands::list_debug_screenshots,
+                commands::delete_debug_screenshot,
+                commands::set_quiet_mode,
+                commands::get_debug_stats,
+                commands::list_profiles,
+                commands::set_active_profile,
+                commands::get_repository_metrics,
+                commands::export_flamegraph,
+                commands::send_debug_command_with_reply,
+                commands::submit_debug_command_reply,
+                commands::register_debug_command_handlers,
+                commands::get_debug_command_history,
+                commands::capture_security_context,
+                commands::write_typed_debug_snapshot,
+                commands::get_error_rate,
+                commands::set_body_capture,
+                commands::get_body_capture,
+                commands::append_network_logs,
+                commands::report_renderer_info,
+                commands::get_debug_environment,
+                commands::benchmark_capture,
+                commands::query_console_logs,
+                commands::get_log_histogram,
+                commands::get_log_field_counts,
+                commands::capture_resources,
+                commands::search_dom_snapshots,
+                commands::export_filtered_logs,
+                commands::record_visibility_change,
+                commands::run_self_test,
+                commands::capture_dom_delta,
+                commands::reconstruct_from_baseline,
+                commands::list_snapshots_paged,
+                commands::import_console_logs,
+                commands::capture_route_state,
+                commands::get_capture_size_report,
+                commands::check_clock_skew,
+                commands::get_storage_status,
+                commands::capture_text_summary,
+                commands::merge_debug_bundles,
+                commands::load_debug_snapshot,
+                commands::capture_form_state,
+                commands::annotate_snapshot,
+                commands::start_window_recording,
+                commands::stop_window_recording,
+                commands::capture_storage_quota,
+                commands::get_log_stats,
+                commands::get_deprecation_report,
+                commands::diff_snapshot_screenshots,
+                commands::set_legal_hold,
+                commands::shutdown_debug_tools,
+                commands::compare_debug_sessions,
+                commands::begin_dom_capture,
+                commands::append_dom_chunk,
+                commands::finish_dom_capture,
+                commands::get_artifact_schema_manifest,
+                commands::flush_debug_logs,
+                commands::claim_existing_files,
+                commands::submit_final_beacon,
+                commands::get_debug_config,
+                commands::set_privacy_preset,
+                commands::repair_console_log,
+                commands::annotate_log_entry,
+                commands::list_log_annotations,
+                commands::get_plugin_init_report,
+                commands::capture_debug_screenshot_region,
+                commands::capture_element_screenshot,
+                commands::tail_plugin_log,
+                commands::mark_debug_moment,
+                commands::report_user_agent,
+                commands::save_dom_snapshot,
+                commands::list_debug_snapshots,
+                commands::capture_screenshot,
+                commands::clear_strict_violations,
+                commands::read_debug_snapshot,
+                commands::set_debug_config,
+                commands::list_debug_sessions,
+                commands::export_encrypted_bundle,
+                commands::import_encrypted_bundle,
+            ])
+            .build()
+    }
+}
+
+// Periodic background work: suspend detection, expiry of abandoned DOM
+// uploads and the summary of the plugin's own routine writes.
+fn spawn_housekeeping<R: Runtime>(
+    app: AppHandle<R>,
+    interval: Duration,
+    summary_interval: Duration,
+) {
+    let handle = app.clone();
+    let mut suspend = SuspendDetector::new(interval.as_millis() as i64);
+    let mut last_summary = Instant::now();
+    let spawned = PeriodicTask::spawn("debug-tools-housekeeping", interval, move || {
+        let Some(state) = handle.try_state::<DebugToolsState>() else {
+            return;
+        };
+
+        if let Some(gap_ms) = suspend.tick(state.clock.now_millis()) {
+            tracing::info!(gap_ms, "Wall-clock gap detected; suspected suspend");
+            state.record_lifecycle(LifecycleEventKind::SuspectedSuspend, None, Some(gap_ms));
+        }
+
+        if let Err(e) = state.finish_quiet_mode() {
+            tracing::error!(error = %e, "Failed to finish quiet mode");
+        }
+        state.dom_uploads.expire();
+        state.buffer_pool.release_if_idle();
+        commands::check_disk_usage(&handle, &state);
+
+        if last_summary.elapsed() >= summary_interval {
+            let window_secs = last_summary.elapsed().as_secs();
+            last_summary = Instant::now();
+            log_activity_summary(state.activity.take(), window_secs);
+        }
+    });
+
+    match spawned {
+        Ok(task) => {
+            if let Some(state) = app.try_state::<DebugToolsState>() {
+                let _ = state.housekeeping_task.set(task);
+            }
+        }
+        Err(e) => tracing::error!(error = %e, "Failed to start housekeeping thread"),
+    }
+}
+
+// Settles `StorageMode::Auto` from the filesystem `log_dir` lives on.
+fn resolve_storage_mode(
+    config: &mut DebugToolsConfig,
+    filesystem: FilesystemInfo,
+) -> StorageStatus {
+    if config.storage_mode == StorageMode::Auto {
+        config.storage_mode = if filesystem.kind == FilesystemKind::Network {
+            StorageMode::Conservative
+        } else {
+            StorageMode::Standard
+        };
+    }
+    storage_status_for(config, filesystem)
+}
+
+fn storage_status_for(config: &DebugToolsConfig, filesystem: FilesystemInfo) -> StorageStatus {
+    let network = filesystem.kind == FilesystemKind::Network;
+    let conservative_writes = config.storage_mode == StorageMode::Conservative;
+
+    let warning = network.then(|| {
+        format!(
+            "{} is on a network filesystem ({}); file locks are advisory and renames may fail. Set `log_dir` to a local directory for reliable capture.",
+            config.log_dir.display(),
+            filesystem.fs_type.as_deref().unwrap_or("unknown")
+        )
+    });
+    if let Some(warning) = &warning {
+        tracing::warn!(conservative_writes, "{}", warning);
+    }
+
+    StorageStatus {
+        log_dir: config.log_dir.clone(),
+        filesystem,
+        conservative_writes,
+        warning,
+    }
+}
+
+// Settled before any command runs, so there is no command to fail; the
+// violation only shows in the strict mode status.
+fn record_storage_fallback(strict_mode: &StrictMode, status: &StorageStatus, now_ms: i64) {
+    if let Some(warning) = &status.warning {
+        let behavior = if status.conservative_writes {
+            "keep writing with conservative copy+delete writes and longer retries"
+        } else {
+            "keep writing to the network filesystem with a warning"
+        };
+        strict_mode.record(
+            StrictViolationKind::StorageFallback,
+            warning.clone(),
+            behavior,
+            now_ms,
+        );
+    }
+}
+
+// Fills in the filesystem of an explicitly configured storage mode, which
+// setup does not wait for.
+fn spawn_storage_probe<R: Runtime>(app: AppHandle<R>) {
+    let Some(state) = app.try_state::<DebugToolsState>() else {
+        return;
+    };
+    let log_dir = state.config.log_dir.clone();
+    let started = Instant::now();
+    let handle = app.clone();
+    let spawned = std::thread::Builder::new()
+        .name("debug-tools-fs-probe".to_string())
+        .spawn(move || {
+            let filesystem = probe_filesystem(&log_dir);
+            if let Some(state) = handle.try_state::<DebugToolsState>() {
+                let status = storage_status_for(&state.config, filesystem);
+                record_storage_fallback(&state.strict_mode, &status, state.clock.now_millis());
+                *state
+                    .storage_status
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner()) = status;
+                state
+                    .init_report
+                    .add_background_phase("storage_probe", started, None);
+            }
+        });
+
+    if let Err(e) = spawned {
+        tracing::error!(error = %e, "Failed to start storage probe thread");
+    }
+}
+
+fn spawn_retention<R: Runtime>(app: AppHandle<R>, interval: Duration) {
+    let handle = app.clone();
+    let spawned = PeriodicTask::spawn("debug-tools-retention", interval, move || {
+        if let Some(state) = handle.try_state::<DebugToolsState>() {
+            run_retention(&state);
+        }
+    });
+
+    match spawned {
+        Ok(task) => {
+            if let Some(state) = app.try_state::<DebugToolsState>() {
+                let _ = state.retention_task.set(task);
+            }
+        }
+        Err(e) => tracing::error!(error = %e, "Failed to start retention thread"),
+    }
+}
+
+fn spawn_health_http<R: Runtime>(app: AppHandle<R>) {
+    let Some(state) = app.try_state::<DebugToolsState>() else {
+        return;
+    };
+    let Some(config) = state.config.health_http.clone() else {
+        return;
+    };
+    if config.token.trim().is_empty() {
+        tracing::warn!("health_http is configured without a token; not starting it");
+        return;
+    }
+
+    let handle = app.clone();
+    let handler = Box::new(move |request: &adapters::health_http::HttpRequest| {
+        commands::serve_health_request(&handle, request)
+    });
+    match HealthHttpServer::start(config.port, config.token, handler) {
+        Ok(server) => {
+            tracing::info!(addr = %server.addr(), "Health HTTP endpoint listening");
+            let _ = state.health_server.set(server);
+        }
+        Err(e) => {
+            tracing::error!(error = %e, port = config.port, "Failed to start health HTTP endpoint")
+        }
+    }
+}
+
+// Salvages console logs an earlier session left torn, off the setup path
+// since old logs can be large.
+fn spawn_log_repair_check<R: Runtime>(app: AppHandle<R>) {
+    let spawned = std::thread::Builder::new()
+        .name("debug-tools-log-repair".into())
+        .spawn(move || {
+            let Some(state) = app.try_state::<DebugToolsState>() else {
+                return;
+            };
+            let current = state
+                .config
+                .frontend_log_path(&app.package_info().name, state.ids.process_id());
+            match check_previous_logs(
+                &state.config,
+                &current,
+                &state.owned_files,
+                state.clock.now_millis(),
+            ) {
+                Ok(repaired) if !repaired.is_empty() => {
+                    tracing::info!(files = repaired.len(), "Repaired earlier console logs")
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "Failed to check earlier console logs"),
+            }
+        });
+
+    if let Err(e) = spawned {
+        tracing::error!(error = %e, "Failed to start log repair thread");
+    }
+}
+
+// Returns how many files were removed, or `None` without a policy.
+fn run_retention(state: &DebugToolsState) -> Option<usize> {
+    let policy = state.config.retention.as_ref()?;
+
+    // The session's DOM baseline backs every delta captured against it.
+    let keep: HashSet<PathBuf> = state
+        .save_dom_use_case
+        .baseline()
+        .map(|baseline| baseline.path)
+        .into_iter()
+        .collect();
+
+    let holds = state.legal_holds.current();
+    match adapters::filesystem::apply_retention(
+        &state.config,
+        policy,
+        &holds,
+        &state.owned_files,
+        state.clock.now_secs(),
+        &keep,
+    ) {
+        Ok(report) => {
+            for path in &report.pruned_snapshots {
+                state.snapshot_index.forget(path);
+            }
             tracing::info!(
-                log_dir = %config.log_dir.display(),
-                "Debug tools plugin initialized"
+                pruned_snapshots = report.pruned_snapshots.len(),
+                orphaned_artifacts = report.orphaned_artifacts.len(),
+                expired_logs = report.expired_logs.len(),
+                failed = report.failed_paths.len(),
+                held = report.held_paths.len(),
+                skipped_foreign = report.skipped_foreign.len(),
+                retained_snapshots = report.retained_snapshots,
+                securely_deleted = report
+                    .deletions
+                    .iter()
+                    .filter(|deletion| deletion.securely_deleted)
+                    .count(),
+                "Retention run finished"
             );
+            Some(
+                report.pruned_snapshots.len()
+                    + report.orphaned_artifacts.len()
+                    + report.expired_logs.len(),
+            )
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Retention run failed");
+            Some(0)
+        }
+    }
+}
 
-            let app_name = app.package_info().name.clone();
-            let repository = Arc::new(FileSystemRepository::new(config.clone(), app_name));
-
-            let append_logs_use_case = Arc::new(AppendConsoleLogsUseCase::new(repository.clone()));
-            let save_dom_use_case = Arc::new(SaveDomSnapshotUseCase::new(repository.clone()));
-            let capture_snapshot_use_case =
-                Arc::new(CaptureDebugSnapshotUseCase::new(repository.clone()));
-
-            let state = DebugToolsState {
-                config,
-                repository,
-                append_logs_use_case,
-                save_dom_use_case,
-                capture_snapshot_use_case,
-                tracing_guard,
-            };
+fn log_activity_summary(summary: ActivitySummary, window_secs: u64) {
+    if summary.is_empty() {
+        return;
+    }
+
+    tracing::info!(
+        console_batches = summary.console_batches,
+        console_entries = summary.console_entries,
+        network_batches = summary.network_batches,
+        network_entries = summary.network_entries,
+        snapshots = summary.snapshots_saved,
+        dom_snapshots = summary.dom_snapshots_saved,
+        "persisted {} batches / {} entries in last {}s",
+        summary.console_batches + summary.network_batches,
+        summary.console_entries + summary.network_entries,
+        window_secs
+    );
+}
+
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::new().build()
+}
 
-            app.manage(state);
-
-            let screenshots_plugin = tauri_plugin_screenshots::init();
-            let handle = app.app_handle().clone();
-            std::thread::spawn(move || {
-                if let Err(e) = handle.plugin(screenshots_plugin) {
-                    tracing::error!(error = %e, "Failed to initialize screenshots plugin");
-                }
-            });
-
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
-            commands::capture_webview_state,
-            commands::get_console_logs,
-            commands::send_debug_command,
-            commands::append_debug_logs,
-            commands::reset_debug_logs,
-            commands::clear_debug_log_files_command,
-            commands::copy_screenshot_to_debug_dir,
-            commands::write_debug_snapshot,
-            commands::capture_dom_snapshot,
-            commands::capture_full_debug_state,
-            commands::get_log_directory,
-        ])
-        .build()
+/// The same as `Builder::new().config(config).build()`.
+pub fn init_with_config<R: Runtime>(config: DebugToolsConfig) -> TauriPlugin<R> {
+    Builder::new().config(config).build()
 }
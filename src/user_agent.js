@@ -0,0 +1,12 @@
+// Injected into every webview. Reports `navigator.userAgent` once per page
+// load so `capture_webview_state` can return the engine's real value.
+(function () {
+  var internals = typeof window !== "undefined" && window.__TAURI_INTERNALS__;
+  if (!internals || typeof internals.invoke !== "function") return;
+  if (typeof navigator === "undefined" || !navigator.userAgent) return;
+  internals
+    .invoke("plugin:debug-tools|report_user_agent", {
+      userAgent: navigator.userAgent,
+    })
+    .catch(function () {});
+})();
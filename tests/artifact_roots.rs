@@ -0,0 +1,225 @@
+mod common;
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri_plugin_debug_tools::{
+    apply_retention, clear_debug_log_files, measure_disk_usage, DebugToolsConfig, DiskCategory,
+    LegalHolds, OwnedFiles, RetentionPolicy,
+};
+
+const APP: &str = "Roots Test";
+const PID: u32 = 4343;
+
+struct Fixture {
+    base: PathBuf,
+    config: DebugToolsConfig,
+}
+
+impl Fixture {
+    // A log directory with every artifact kind moved to a sibling root.
+    fn new(name: &str) -> Self {
+        let base = common::temp_dir("roots", name);
+        let config = DebugToolsConfig {
+            log_dir: base.join("logs"),
+            console_log_root: Some(base.join("console")),
+            snapshot_root: Some(base.join("snapshots")),
+            screenshot_root: Some(base.join("screenshots")),
+            dom_snapshot_root: Some(base.join("dom")),
+            network_log_root: Some(base.join("network")),
+            backend_log_root: Some(base.join("backend")),
+            config_history_root: Some(base.join("history")),
+            recording_root: Some(base.join("recordings")),
+            export_root: Some(base.join("exports")),
+            annotation_root: Some(base.join("annotations")),
+            legal_hold_root: Some(base.join("holds")),
+            ..DebugToolsConfig::default()
+        };
+        config
+            .ensure_subdirectories()
+            .expect("create artifact roots");
+        Self { base, config }
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.base);
+    }
+}
+
+fn write(path: &Path, contents: &str) -> PathBuf {
+    fs::write(path, contents).expect("write fixture");
+    path.to_path_buf()
+}
+
+#[test]
+fn paths_resolve_to_the_overridden_roots() {
+    let fixture = Fixture::new("resolve");
+    let config = &fixture.config;
+    let paths = config.artifact_paths();
+
+    assert!(config
+        .frontend_log_path(APP, PID)
+        .starts_with(fixture.base.join("console")));
+    assert!(config
+        .snapshot_path(1_700_000_000_000)
+        .starts_with(fixture.base.join("snapshots")));
+    assert_eq!(config.screenshot_dir(), fixture.base.join("screenshots"));
+    assert_eq!(config.dom_snapshot_dir(), fixture.base.join("dom"));
+    assert!(config
+        .network_log_path(APP, PID)
+        .starts_with(fixture.base.join("network")));
+    assert_eq!(
+        config.backend_log_path(),
+        fixture.base.join("backend").join("rust_debug.log")
+    );
+    assert_eq!(config.config_history_dir(), fixture.base.join("history"));
+    assert_eq!(config.recording_dir(), fixture.base.join("recordings"));
+    assert_eq!(config.log_export_dir(), fixture.base.join("exports"));
+    assert!(config
+        .annotations_path("session")
+        .starts_with(fixture.base.join("annotations")));
+    assert_eq!(
+        config.legal_holds_path(),
+        fixture.base.join("holds").join("holds.json")
+    );
+    assert_eq!(paths.overridden.len(), 11);
+    // Setup creates every root, including those of kinds written later.
+    for root in paths.override_roots() {
+        assert!(root.is_dir(), "{} was not created", root.display());
+    }
+}
+
+#[test]
+fn cleanup_and_retention_reach_the_overridden_roots() {
+    let fixture = Fixture::new("cleanup");
+    let config = &fixture.config;
+    let owned = OwnedFiles::load(config);
+
+    let console = write(&config.frontend_log_path(APP, PID), "{}\n");
+    let screenshot = write(
+        &config.screenshot_dir().join("1700000000_window.png"),
+        "png",
+    );
+    let dom = write(
+        &config.dom_snapshot_dir().join("dom_1700000000.html"),
+        "<p>",
+    );
+    let snapshot = write(&config.snapshot_path(1_700_000_000_000), "{}");
+    let network = write(&config.network_log_path(APP, PID), "{}\n");
+    for path in [&console, &screenshot, &dom, &snapshot, &network] {
+        owned.register(path);
+    }
+    // Recognized by its first event rather than the owned list.
+    let backend = write(
+        &config.backend_log_path(),
+        "{\"timestamp\":\"2023-11-14T22:13:20Z\",\"level\":\"INFO\",\"target\":\"app\"}\n",
+    );
+    let foreign = write(&config.screenshot_dir().join("holiday.png"), "png");
+    // Ownership of files under an override survives a reload.
+    assert!(OwnedFiles::load(config).is_owned(&screenshot));
+
+    let report = clear_debug_log_files(config, APP, &LegalHolds::default(), &owned).expect("clear");
+    for path in [&console, &screenshot, &dom, &network, &backend] {
+        assert!(!path.exists(), "{} was kept", path.display());
+    }
+    assert!(foreign.exists());
+    assert!(report.skipped_foreign.contains(&foreign));
+
+    let policy = RetentionPolicy {
+        max_snapshots: Some(0),
+        ..RetentionPolicy::default()
+    };
+    let report = apply_retention(
+        config,
+        &policy,
+        &LegalHolds::default(),
+        &owned,
+        i64::MAX / 1000,
+        &HashSet::new(),
+    )
+    .expect("retention");
+    assert_eq!(report.pruned_snapshots, vec![snapshot.clone()]);
+    assert!(!snapshot.exists());
+}
+
+#[test]
+fn disk_usage_counts_every_root_under_its_category() {
+    let fixture = Fixture::new("disk");
+    let config = &fixture.config;
+    let recording = config.recording_dir().join("1700000000000");
+    fs::create_dir_all(&recording).expect("create recording");
+    write(&recording.join("frames.jsonl"), &"x".repeat(400));
+    write(
+        &config.log_export_dir().join("triage.jsonl"),
+        &"x".repeat(300),
+    );
+    write(&config.network_log_path(APP, PID), &"x".repeat(200));
+    write(&config.backend_log_path(), &"x".repeat(100));
+
+    let usage = measure_disk_usage(config, 0).expect("measure");
+    let by_category: Vec<(DiskCategory, u64)> = usage
+        .categories
+        .iter()
+        .map(|category| (category.category, category.bytes))
+        .collect();
+
+    assert_eq!(
+        by_category,
+        [
+            (DiskCategory::Recordings, 400),
+            (DiskCategory::Exports, 300),
+            (DiskCategory::NetworkLogs, 200),
+            (DiskCategory::BackendLogs, 100),
+        ]
+    );
+}
+
+#[test]
+fn overlapping_roots_are_rejected() {
+    let base = common::temp_dir("roots", "overlap");
+    let log_dir = base.join("logs");
+    let cases = [
+        DebugToolsConfig {
+            screenshot_root: Some(log_dir.join("elsewhere")),
+            ..DebugToolsConfig::default()
+        },
+        DebugToolsConfig {
+            snapshot_root: Some(base.clone()),
+            ..DebugToolsConfig::default()
+        },
+        DebugToolsConfig {
+            screenshot_root: Some(base.join("shared")),
+            dom_snapshot_root: Some(base.join("shared").join("dom")),
+            ..DebugToolsConfig::default()
+        },
+        DebugToolsConfig {
+            console_log_root: Some(base.join("logs").join("..").join("logs")),
+            ..DebugToolsConfig::default()
+        },
+        DebugToolsConfig {
+            console_log_root: Some(PathBuf::from("relative/console")),
+            ..DebugToolsConfig::default()
+        },
+        DebugToolsConfig {
+            export_root: Some(base.join("shared")),
+            recording_root: Some(base.join("shared")),
+            ..DebugToolsConfig::default()
+        },
+    ];
+
+    for config in cases {
+        let config = DebugToolsConfig {
+            log_dir: log_dir.clone(),
+            ..config
+        };
+        let error = config
+            .artifact_paths()
+            .validate()
+            .expect_err("overlapping roots must be rejected");
+        assert!(error.to_string().starts_with("Invalid artifact root"));
+        assert!(config.ensure_subdirectories().is_err());
+    }
+    let _ = fs::remove_dir_all(&base);
+}
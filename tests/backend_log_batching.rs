@@ -0,0 +1,156 @@
+use std::sync::{Arc, Mutex};
+use tauri_plugin_debug_tools::{
+    BackendLogBatch, BackendLogEvent, BackendLogForwarder, BatchAggregator, DebugToolsConfig,
+    FixedClock, LogLevel,
+};
+use tracing_subscriber::layer::SubscriberExt;
+
+const START_MS: i64 = 1_700_000_000_000;
+
+fn event(level: LogLevel, message: &str) -> BackendLogEvent {
+    BackendLogEvent {
+        timestamp: 0,
+        level,
+        target: "test".to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn messages(batch: &BackendLogBatch) -> Vec<&str> {
+    batch
+        .events
+        .iter()
+        .map(|event| event.message.as_str())
+        .collect()
+}
+
+#[test]
+fn debug_events_wait_for_the_interval() {
+    let mut aggregator = BatchAggregator::new(250, 10, 0);
+
+    assert!(aggregator.push(event(LogLevel::Debug, "a"), 0).is_none());
+    assert!(aggregator.push(event(LogLevel::Debug, "b"), 100).is_none());
+    assert!(aggregator.push(event(LogLevel::Info, "c"), 200).is_none());
+    assert!(aggregator.poll(249).is_none());
+
+    let batch = aggregator.poll(250).expect("interval elapsed");
+    assert_eq!(messages(&batch), ["a", "b", "c"]);
+    assert_eq!(batch.dropped_in_window, 0);
+
+    // The next window starts at the flush, not at the first buffered event.
+    assert!(aggregator.push(event(LogLevel::Debug, "d"), 300).is_none());
+    assert!(aggregator.poll(499).is_none());
+    let batch = aggregator
+        .push(event(LogLevel::Debug, "e"), 500)
+        .expect("push past the interval flushes");
+    assert_eq!(messages(&batch), ["d", "e"]);
+}
+
+#[test]
+fn empty_windows_emit_nothing() {
+    let mut aggregator = BatchAggregator::new(250, 10, 0);
+
+    assert!(aggregator.poll(250).is_none());
+    assert!(aggregator.poll(10_000).is_none());
+}
+
+#[test]
+fn warn_and_error_flush_immediately_with_the_buffer() {
+    let mut aggregator = BatchAggregator::new(250, 10, 0);
+
+    assert!(aggregator.push(event(LogLevel::Debug, "a"), 10).is_none());
+    let batch = aggregator
+        .push(event(LogLevel::Warn, "warned"), 20)
+        .expect("warn flushes");
+    assert_eq!(messages(&batch), ["a", "warned"]);
+
+    let batch = aggregator
+        .push(event(LogLevel::Error, "failed"), 21)
+        .expect("error flushes");
+    assert_eq!(messages(&batch), ["failed"]);
+
+    // The immediate flush restarts the interval.
+    assert!(aggregator.push(event(LogLevel::Debug, "b"), 260).is_none());
+    assert!(aggregator.poll(270).is_none());
+    assert!(aggregator.poll(271).is_some());
+}
+
+#[test]
+fn a_full_buffer_flushes_once_per_window_then_drops() {
+    let mut aggregator = BatchAggregator::new(250, 3, 0);
+
+    assert!(aggregator.push(event(LogLevel::Debug, "1"), 1).is_none());
+    assert!(aggregator.push(event(LogLevel::Debug, "2"), 2).is_none());
+    let batch = aggregator
+        .push(event(LogLevel::Debug, "3"), 3)
+        .expect("size flush");
+    assert_eq!(messages(&batch), ["1", "2", "3"]);
+    assert_eq!(batch.dropped_in_window, 0);
+
+    // Refills within the same window are held, and overflow is counted.
+    for (i, at) in (4..=8).enumerate() {
+        let message = (i + 4).to_string();
+        assert!(aggregator
+            .push(event(LogLevel::Debug, &message), at)
+            .is_none());
+    }
+
+    let batch = aggregator.poll(250).expect("interval flush");
+    assert_eq!(messages(&batch), ["4", "5", "6"]);
+    assert_eq!(batch.dropped_in_window, 2);
+
+    let batch = aggregator
+        .push(event(LogLevel::Warn, "next"), 251)
+        .expect("warn flushes");
+    assert_eq!(batch.dropped_in_window, 0, "counter resets per window");
+}
+
+#[test]
+fn forwarder_batches_tracing_events_on_the_mocked_clock() {
+    // Long enough that the background poller never wakes during the test.
+    let config = DebugToolsConfig {
+        emit_interval_ms: 60_000,
+        emit_batch_max: 100,
+        ..DebugToolsConfig::default()
+    };
+    let clock = Arc::new(FixedClock::new(START_MS));
+    let forwarder = BackendLogForwarder::new(&config, clock.clone());
+    let subscriber = tracing_subscriber::registry().with(forwarder.layer());
+
+    let emitted: Arc<Mutex<Vec<BackendLogBatch>>> = Arc::default();
+    let sink = emitted.clone();
+    let taken = || std::mem::take(&mut *emitted.lock().unwrap());
+
+    tracing::subscriber::with_default(subscriber, || {
+        // Buffered until the emitter is set, then sent with the first batch.
+        tracing::debug!("before setup");
+        forwarder.set_emitter(Arc::new(move |batch: &BackendLogBatch| {
+            sink.lock().unwrap().push(batch.clone())
+        }));
+        tracing::debug!(attempt = 1, "chatty");
+        assert!(taken().is_empty());
+
+        clock.advance(60_000);
+        tracing::info!("tick");
+        let batches = taken();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            messages(&batches[0]),
+            ["before setup", "chatty attempt=1", "tick"]
+        );
+        let stamps: Vec<i64> = batches[0].events.iter().map(|e| e.timestamp).collect();
+        assert_eq!(stamps, [START_MS, START_MS, START_MS + 60_000]);
+
+        clock.advance(10);
+        tracing::debug!("buffered");
+        tracing::warn!("warned");
+        let batches = taken();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(messages(&batches[0]), ["buffered", "warned"]);
+
+        tracing::debug!("pending");
+        assert_eq!(forwarder.flush(), 1);
+        assert_eq!(messages(&taken()[0]), ["pending"]);
+        assert_eq!(forwarder.flush(), 0);
+    });
+}
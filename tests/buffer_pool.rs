@@ -0,0 +1,78 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri_plugin_debug_tools::{
+    BufferPool, DomState, FileSystemRepository, InternalActivity, SnapshotRepository, ViewportInfo,
+};
+
+fn repository(name: &str, pool: Option<Arc<BufferPool>>) -> (FileSystemRepository, PathBuf) {
+    let config = Arc::new(common::config("buffer-pool", name));
+    let log_dir = config.log_dir.clone();
+    let repository = FileSystemRepository::new(
+        config,
+        "Pool Test".into(),
+        7,
+        Arc::new(InternalActivity::default()),
+    );
+    let repository = match pool {
+        Some(pool) => repository.with_buffer_pool(pool),
+        None => repository,
+    };
+    (repository, log_dir)
+}
+
+fn dom(html: String) -> DomState {
+    DomState {
+        html,
+        url: "tauri://localhost/--><script>".into(),
+        title: "Pool".into(),
+        viewport: ViewportInfo {
+            width: 800,
+            height: 600,
+        },
+        captured_at: 1_700_000_000_000,
+        shadow_dom_included: false,
+        replacement_chars: 0,
+    }
+}
+
+#[test]
+fn pooled_dom_writes_match_unpooled_ones() {
+    let pool = Arc::new(BufferPool::new(64 * 1024 * 1024, Duration::from_secs(60)));
+    let (pooled, pooled_dir) = repository("pooled", Some(pool.clone()));
+    let (plain, plain_dir) = repository("plain", None);
+
+    // A large capture followed by a smaller one, which reuses its buffer.
+    for (timestamp, html) in [
+        (1, format!("<html>{}</html>", "x".repeat(1 << 20))),
+        (2, "<html>small</html>".to_string()),
+    ] {
+        let a = pooled
+            .save_dom(&dom(html.clone()), timestamp)
+            .expect("pooled save");
+        let b = plain.save_dom(&dom(html), timestamp).expect("plain save");
+        assert_eq!(fs::read(&a.path).unwrap(), fs::read(&b.path).unwrap());
+    }
+
+    let stats = pool.stats();
+    let _ = fs::remove_dir_all(&pooled_dir);
+    let _ = fs::remove_dir_all(&plain_dir);
+    assert_eq!((stats.misses, stats.hits), (1, 1));
+    assert_eq!(stats.pooled_buffers, 1);
+}
+
+#[test]
+fn pool_respects_its_limit_and_empties_when_idle() {
+    let pool = BufferPool::new(1024, Duration::ZERO);
+
+    pool.give(Vec::with_capacity(4096));
+    assert_eq!(pool.stats().pooled_buffers, 0);
+
+    pool.give(Vec::with_capacity(512));
+    assert_eq!(pool.stats().pooled_buffers, 1);
+    pool.release_if_idle();
+    assert_eq!(pool.stats().pooled_bytes, 0);
+}
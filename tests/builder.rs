@@ -0,0 +1,71 @@
+mod common;
+
+use std::fs;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{Builder, DebugToolsConfig, DebugToolsState};
+
+#[test]
+fn builder_settings_override_the_config() {
+    let log_dir = common::temp_dir("builder", "settings");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .log_file_name("my_app.log")
+                .with_stdout(false)
+                .max_file_size(2_000_000)
+                .with_webview_target(true)
+                .disable_screenshots()
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+
+    let state = app.state::<DebugToolsState>();
+    let plugin_log = state.config.plugin_log_path();
+    let log_to_stdout = state.config.log_to_stdout;
+    let max_log_size_bytes = state.config.max_log_size_bytes;
+    let forward_backend_logs = state.config.forward_backend_logs;
+    let forwarding = state.forwarder.is_some();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(
+        plugin_log.as_deref().and_then(|path| path.file_name()),
+        Some("my_app.log".as_ref())
+    );
+    assert!(!log_to_stdout);
+    assert_eq!(max_log_size_bytes, 2_000_000);
+    assert!(forward_backend_logs);
+    assert!(forwarding);
+}
+
+#[test]
+fn command_paths_stay_under_log_dir() {
+    let log_dir = common::temp_dir("builder", "paths");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    for path in [
+        config.frontend_log_path("My App", 42),
+        config.backend_log_path(),
+        config.legacy_snapshot_path(1_700_000_000),
+        config.screenshot_dir(),
+        config.dom_snapshot_dir(),
+    ] {
+        assert!(
+            path.starts_with(&log_dir),
+            "{:?} is outside the log dir",
+            path
+        );
+    }
+    assert!(DebugToolsConfig::default()
+        .log_dir
+        .starts_with(std::env::temp_dir()));
+}
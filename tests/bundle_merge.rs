@@ -0,0 +1,213 @@
+mod common;
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri_plugin_debug_tools::analysis::merge::merge_bundles;
+use zip::write::SimpleFileOptions;
+
+struct Fixture {
+    base: PathBuf,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Self {
+        let base = common::temp_dir("merge", name);
+        fs::create_dir_all(&base).expect("create fixture");
+        Self { base }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.base.join(name)
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.base);
+    }
+}
+
+fn console_log(entries: &[(i64, &str, &str)]) -> String {
+    entries
+        .iter()
+        .map(|(timestamp, level, message)| {
+            serde_json::json!({
+                "timestamp": timestamp,
+                "level": level,
+                "message": message,
+                "args": [],
+            })
+            .to_string()
+                + "\n"
+        })
+        .collect()
+}
+
+fn write_zip(path: &Path, files: &[(&str, &str)]) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).expect("create archive"));
+    for (name, contents) in files {
+        zip.start_file(*name, SimpleFileOptions::default())
+            .expect("start entry");
+        zip.write_all(contents.as_bytes()).expect("write entry");
+    }
+    zip.finish().expect("finish archive");
+}
+
+fn timeline(path: &Path) -> Vec<serde_json::Value> {
+    fs::read_to_string(path)
+        .expect("read timeline")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("timeline line"))
+        .collect()
+}
+
+#[test]
+fn zip_bundles_merge_alongside_directories() {
+    let fixture = Fixture::new("zip");
+
+    let directory = fixture.path("laptop");
+    fs::create_dir_all(directory.join("screenshots")).expect("create bundle");
+    fs::write(
+        directory.join("frontend_console_app_1.jsonl"),
+        console_log(&[(2_000, "error", "Checkout failed: 500")]),
+    )
+    .expect("write log");
+    fs::write(directory.join("screenshots").join("shot.png"), "png").expect("write screenshot");
+
+    // Zipped with its folder, and a manifest naming the device.
+    let wrapped = fixture.path("bundle-a.zip");
+    write_zip(
+        &wrapped,
+        &[
+            (
+                "bundle-a/manifest.json",
+                r#"{"device_id": "tablet", "session_id": "s-1"}"#,
+            ),
+            (
+                "bundle-a/frontend_console_app_2.jsonl",
+                &console_log(&[
+                    (1_000, "info", "boot"),
+                    (3_000, "error", "Checkout failed: 502"),
+                ]),
+            ),
+            ("bundle-a/dom_snapshots/dom_1.html", "<p></p>"),
+        ],
+    );
+    // Zipped flat, without a manifest: the file stem names the device.
+    let flat = fixture.path("phone.zip");
+    write_zip(
+        &flat,
+        &[(
+            "frontend_console_app_3.jsonl",
+            &console_log(&[(1_500, "warn", "slow network")]),
+        )],
+    );
+
+    let out_dir = fixture.path("merged");
+    let report = merge_bundles(&[directory, wrapped.clone(), flat.clone()], &out_dir)
+        .expect("merge bundles");
+
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    let devices: Vec<(&str, &str)> = report
+        .bundles
+        .iter()
+        .map(|bundle| (bundle.device_id.as_str(), bundle.namespace.as_str()))
+        .collect();
+    assert_eq!(
+        devices,
+        [
+            ("laptop", "laptop__session"),
+            ("tablet", "tablet__s-1"),
+            ("phone", "phone__session"),
+        ]
+    );
+    assert_eq!(report.bundles[1].source, wrapped);
+    assert_eq!(report.bundles[1].files, 3);
+    assert!(out_dir
+        .join("bundles/tablet__s-1/dom_snapshots/dom_1.html")
+        .is_file());
+    assert!(out_dir
+        .join("bundles/laptop__session/screenshots/shot.png")
+        .is_file());
+    assert!(
+        !out_dir.join(".unpacked").exists(),
+        "staging directory removed"
+    );
+
+    let timeline = timeline(&report.timeline_path);
+    let order: Vec<(&str, &str)> = timeline
+        .iter()
+        .map(|entry| {
+            (
+                entry["fields"]["device_id"].as_str().unwrap(),
+                entry["message"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        order,
+        [
+            ("tablet", "boot"),
+            ("phone", "slow network"),
+            ("laptop", "Checkout failed: 500"),
+            ("tablet", "Checkout failed: 502"),
+        ]
+    );
+    assert_eq!(report.fingerprints, 1);
+    let fingerprints: serde_json::Value =
+        serde_json::from_slice(&fs::read(&report.fingerprints_path).expect("read fingerprints"))
+            .expect("fingerprints json");
+    assert_eq!(fingerprints[0]["count"], 2);
+    assert_eq!(
+        fingerprints[0]["devices"],
+        serde_json::json!(["laptop", "tablet"])
+    );
+}
+
+#[test]
+fn unreadable_archives_are_skipped_with_a_reason() {
+    let fixture = Fixture::new("corrupt");
+
+    let garbage = fixture.path("truncated.zip");
+    fs::write(&garbage, b"PK\x03\x04 not really a zip").expect("write garbage");
+    let not_a_bundle = fixture.path("notes.txt");
+    fs::write(&not_a_bundle, "hello").expect("write text");
+    // A crafted entry name must not escape the staging directory.
+    let escaping = fixture.path("escaping.zip");
+    write_zip(
+        &escaping,
+        &[
+            ("../../escaped.txt", "gotcha"),
+            (
+                "frontend_console_app_1.jsonl",
+                &console_log(&[(1_000, "info", "kept")]),
+            ),
+        ],
+    );
+
+    let out_dir = fixture.path("merged");
+    let report = merge_bundles(&[garbage.clone(), not_a_bundle.clone(), escaping], &out_dir)
+        .expect("merge bundles");
+
+    let skipped: Vec<(&Path, &str)> = report
+        .skipped
+        .iter()
+        .map(|skipped| (skipped.source.as_path(), skipped.reason.as_str()))
+        .collect();
+    assert_eq!(skipped.len(), 2);
+    assert_eq!(skipped[0].0, garbage);
+    assert!(
+        skipped[0].1.starts_with("invalid zip archive"),
+        "{}",
+        skipped[0].1
+    );
+    assert_eq!(skipped[1].0, not_a_bundle);
+
+    assert_eq!(report.bundles.len(), 1);
+    assert_eq!(report.bundles[0].device_id, "escaping");
+    assert_eq!(report.bundles[0].files, 1);
+    assert_eq!(report.timeline_entries, 1);
+    assert!(!fixture.base.join("escaped.txt").exists());
+    assert!(!std::env::temp_dir().join("escaped.txt").exists());
+}
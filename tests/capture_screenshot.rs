@@ -0,0 +1,58 @@
+mod common;
+
+use std::fs;
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_debug_tools::{capture_screenshot, Builder, DebugToolsConfig, DebugToolsState};
+
+// The mock runtime cannot take real screenshots, so only the refusals are
+// covered here.
+#[test]
+fn reports_why_screenshots_are_unavailable() {
+    let log_dir = common::temp_dir("capture-screenshot", "unavailable");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .with_stdout(false)
+                .disable_screenshots()
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+        .build()
+        .expect("create window");
+    let capture = |label: Option<&str>| {
+        tauri::async_runtime::block_on(capture_screenshot(
+            app.handle().clone(),
+            label.map(str::to_string),
+        ))
+    };
+
+    let disabled = capture(None).expect_err("disabled by the profile");
+
+    // A profile switch turns screenshots back on, but the plugin was never
+    // registered.
+    app.state::<DebugToolsState>()
+        .capture_profile
+        .write()
+        .expect("profile lock")
+        .profile
+        .enable_screenshots = true;
+    let unregistered = capture(None).expect_err("plugin not registered");
+    let missing = capture(Some("settings")).expect_err("no such window");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(disabled.contains("disabled"), "{}", disabled);
+    assert!(
+        unregistered.contains("Builder::disable_screenshots"),
+        "{}",
+        unregistered
+    );
+    assert!(missing.contains("settings"), "{}", missing);
+}
@@ -0,0 +1,76 @@
+// Fixtures shared by the integration tests. Each test binary compiles its own
+// copy and uses only some of it.
+//
+// A test that builds an app gets a test binary of its own: the plugin
+// installs a global tracing subscriber, so only one app per process can
+// finish setup.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+#[cfg(feature = "test-utils")]
+use std::sync::Arc;
+use tauri_plugin_debug_tools::DebugToolsConfig;
+#[cfg(feature = "test-utils")]
+use tauri_plugin_debug_tools::{
+    DebugSnapshot, FileSystemRepository, InternalActivity, OwnedFiles, SnapshotRepository,
+};
+
+// `debug-tools-<prefix>-<name>-<pid>` under the temp directory, emptied of
+// anything a previous run left behind but not created.
+pub fn temp_dir(prefix: &str, name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "debug-tools-{}-{}-{}",
+        prefix,
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+// A default config over a fresh `temp_dir`, with every artifact directory
+// created.
+pub fn config(prefix: &str, name: &str) -> DebugToolsConfig {
+    let config = DebugToolsConfig {
+        log_dir: temp_dir(prefix, name),
+        ..DebugToolsConfig::default()
+    };
+    config.ensure_subdirectories().expect("create directories");
+    config
+}
+
+// Saves a snapshot the way captures do, through
+// `FileSystemRepository::save_snapshot`, so its file name and timestamp unit
+// are the production ones. `fields` (timestamp, label, artifact paths, ...)
+// go over a minimal webview state.
+#[cfg(feature = "test-utils")]
+pub fn save_snapshot(
+    config: &DebugToolsConfig,
+    owned: &Arc<OwnedFiles>,
+    fields: serde_json::Value,
+) -> PathBuf {
+    let mut snapshot = serde_json::json!({
+        "webview_state": {
+            "url": "http://localhost/",
+            "title": "Test",
+            "user_agent": "test",
+            "viewport": { "width": 800, "height": 600 },
+        },
+    });
+    if let (Some(snapshot), serde_json::Value::Object(fields)) = (snapshot.as_object_mut(), fields)
+    {
+        snapshot.extend(fields);
+    }
+    let snapshot: DebugSnapshot = serde_json::from_value(snapshot).expect("snapshot fields");
+
+    FileSystemRepository::new(
+        Arc::new(config.clone()),
+        "Test".into(),
+        1,
+        Arc::new(InternalActivity::default()),
+    )
+    .with_owned_files(owned.clone())
+    .save_snapshot(&snapshot)
+    .expect("save snapshot")
+}
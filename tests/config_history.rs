@@ -0,0 +1,140 @@
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    list_debug_sessions, set_debug_config, set_quiet_mode, Builder, DebugConfigUpdate,
+    DebugToolsConfig, DebugToolsState, FixedClock, PrivacyPreset,
+};
+
+const START_MS: i64 = 1_700_000_000_000;
+
+#[test]
+fn set_debug_config_records_history_and_sessions_report_their_start_config() {
+    let log_dir = common::temp_dir("config-history", "changes");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    // Left behind by an earlier run of the app.
+    let history_dir = config.config_history_dir();
+    fs::create_dir_all(&history_dir).expect("history dir");
+    let mut previous = serde_json::to_value(config.initial_capture_profile()).unwrap();
+    previous["name"] = "visual".into();
+    fs::write(
+        history_dir.join(format!("effective_config_{}.json", START_MS - 60_000)),
+        serde_json::json!({
+            "written_at": START_MS - 60_000,
+            "reason": "setup",
+            "log_dir": log_dir,
+            "max_log_size_bytes": 1024,
+            "log_format": "json",
+            "enable_dom_capture": true,
+            "enable_rust_logging": true,
+            "active_profile": previous,
+            "quiet_mode": null,
+            "session_id": "previous-session",
+        })
+        .to_string(),
+    )
+    .expect("write previous config");
+
+    let clock = Arc::new(FixedClock::new(START_MS));
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(clock.clone())
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+    let state = app.state::<DebugToolsState>();
+
+    clock.advance(1_000);
+    tauri::async_runtime::block_on(set_quiet_mode(handle(), "warn".into(), 60))
+        .expect("enable quiet mode");
+
+    clock.advance(1_000);
+    let rejected = tauri::async_runtime::block_on(set_debug_config(
+        handle(),
+        DebugConfigUpdate {
+            active_profile: Some("missing".into()),
+            privacy_preset: Some(Some(PrivacyPreset::Strict)),
+        },
+    ));
+    assert!(rejected.is_err());
+    assert_eq!(state.privacy.status().preset, None, "nothing applied");
+
+    clock.advance(1_000);
+    let update = serde_json::from_value::<DebugConfigUpdate>(serde_json::json!({
+        "active_profile": "visual",
+        "privacy_preset": "strict",
+    }))
+    .unwrap();
+    let effective =
+        tauri::async_runtime::block_on(set_debug_config(handle(), update)).expect("update");
+    assert_eq!(effective.reason, "set_debug_config");
+    assert_eq!(effective.active_profile.name, "visual");
+    assert_eq!(
+        effective
+            .privacy
+            .as_ref()
+            .and_then(|privacy| privacy.preset),
+        Some(PrivacyPreset::Strict)
+    );
+    assert!(effective.quiet_mode.is_some(), "quiet mode survives");
+    assert_eq!(
+        effective.session_id.as_deref(),
+        Some(state.session_id.as_str())
+    );
+
+    let written = history_dir.join(format!("effective_config_{}.json", START_MS + 3_000));
+    let on_disk: serde_json::Value =
+        serde_json::from_slice(&fs::read(&written).expect("history entry")).unwrap();
+    assert_eq!(on_disk["reason"], "set_debug_config");
+
+    // `null` clears the preset; the profile is left alone.
+    clock.advance(1_000);
+    let cleared = serde_json::from_value::<DebugConfigUpdate>(serde_json::json!({
+        "privacy_preset": null,
+    }))
+    .unwrap();
+    let effective =
+        tauri::async_runtime::block_on(set_debug_config(handle(), cleared)).expect("clear");
+    assert_eq!(effective.active_profile.name, "visual");
+    assert_eq!(
+        effective
+            .privacy
+            .as_ref()
+            .and_then(|privacy| privacy.preset),
+        None
+    );
+
+    let sessions =
+        tauri::async_runtime::block_on(list_debug_sessions(handle())).expect("list sessions");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+    assert_eq!(ids, [state.session_id.as_str(), "previous-session"]);
+
+    let current = sessions[0].start_config.as_ref().expect("start config");
+    assert_eq!(current.reason, "setup");
+    assert_eq!(current.written_at, START_MS);
+    assert_eq!(current.active_profile.name, "default");
+
+    let previous = sessions[1].start_config.as_ref().expect("previous config");
+    assert_eq!(previous.active_profile.name, "visual");
+    assert_eq!(
+        sessions[1].start_config_path.as_deref(),
+        Some(
+            history_dir
+                .join(format!("effective_config_{}.json", START_MS - 60_000))
+                .as_path()
+        )
+    );
+
+    let _ = fs::remove_dir_all(&log_dir);
+}
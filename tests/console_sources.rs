@@ -0,0 +1,192 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::fs;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, get_log_stats, query_console_logs, Builder, ConsoleLogEntry,
+    ConsoleLogEntryPayload, ConsoleSource, CrossSourceDedup, DebugToolsConfig, LogQuery,
+};
+
+const NOW_MS: i64 = 1_700_000_000_000;
+
+fn entry(offset_ms: i64, level: &str, message: &str) -> ConsoleLogEntry {
+    ConsoleLogEntry {
+        timestamp: NOW_MS + offset_ms,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+    }
+}
+
+fn messages(entries: &[ConsoleLogEntry]) -> Vec<&str> {
+    entries.iter().map(|entry| entry.message.as_str()).collect()
+}
+
+#[test]
+fn copies_from_other_pathways_within_the_window_are_dropped() {
+    let dedup = CrossSourceDedup::new(&[ConsoleSource::Bridge]);
+    let mut bridge = vec![
+        entry(0, "info", "loaded"),
+        entry(0, "warn", "slow"),
+        entry(0, "error", "boom"),
+    ];
+    let mut collector = vec![
+        entry(200, "info", "loaded"),
+        // Aliases match their canonical level.
+        entry(-50, "warning", "slow"),
+        // Too far apart to be the same call.
+        entry(300, "error", "boom"),
+        // Same message, different level.
+        entry(0, "debug", "loaded"),
+        entry(0, "info", "only here"),
+    ];
+
+    let from_bridge = dedup.filter(ConsoleSource::Bridge, &mut bridge);
+    let from_collector = dedup.filter(ConsoleSource::Collector, &mut collector);
+
+    assert_eq!(from_bridge, 0);
+    assert_eq!(from_collector, 2);
+    assert_eq!(messages(&collector), ["boom", "loaded", "only here"]);
+    assert_eq!(
+        dedup.dropped(),
+        BTreeMap::from([(ConsoleSource::Collector, 2)])
+    );
+}
+
+#[test]
+fn authoritative_pathways_keep_everything_they_deliver() {
+    let dedup = CrossSourceDedup::new(&[ConsoleSource::Bridge]);
+    // The copy arrives first, so it is kept, and so is its authoritative twin.
+    let mut host = vec![entry(0, "info", "early copy")];
+    let mut bridge = vec![entry(10, "info", "early copy")];
+    // Repeats from one pathway are separate calls, not copies.
+    let mut repeated = vec![entry(0, "info", "tick"), entry(1, "info", "tick")];
+
+    let dropped = [
+        dedup.filter(ConsoleSource::Host, &mut host),
+        dedup.filter(ConsoleSource::Bridge, &mut bridge),
+        dedup.filter(ConsoleSource::Collector, &mut repeated),
+    ];
+
+    assert_eq!(dropped, [0, 0, 0]);
+    assert_eq!((host.len(), bridge.len(), repeated.len()), (1, 1, 2));
+    assert!(dedup.dropped().is_empty());
+}
+
+#[test]
+fn nothing_is_dropped_when_every_pathway_is_authoritative() {
+    for sources in [&ConsoleSource::LIVE[..], &[]] {
+        let dedup = CrossSourceDedup::new(sources);
+        let mut bridge = vec![entry(0, "info", "twice")];
+        let mut collector = vec![entry(0, "info", "twice")];
+        dedup.filter(ConsoleSource::Bridge, &mut bridge);
+        dedup.filter(ConsoleSource::Collector, &mut collector);
+
+        assert_eq!(dedup.authoritative(), ConsoleSource::LIVE);
+        assert_eq!(collector.len(), 1);
+    }
+
+    // Imports and markers are never matched against live entries.
+    let dedup = CrossSourceDedup::new(&[ConsoleSource::Bridge]);
+    let mut bridge = vec![entry(0, "info", "replayed")];
+    dedup.filter(ConsoleSource::Bridge, &mut bridge);
+    for source in [ConsoleSource::Import, ConsoleSource::Marker] {
+        let mut other = vec![entry(0, "info", "replayed")];
+        assert_eq!(dedup.filter(source, &mut other), 0);
+        assert_eq!(other.len(), 1);
+    }
+}
+
+fn payload(offset_ms: i64, message: &str) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp: NOW_MS + offset_ms,
+        level: "info".to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    }
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn persisted_entries_carry_their_source_and_duplicates_are_counted() {
+    let log_dir = common::temp_dir("console-sources", "persisted");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        console_sources: vec![ConsoleSource::Bridge],
+        ..DebugToolsConfig::default()
+    };
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let append = |logs: Vec<ConsoleLogEntryPayload>, source: Option<ConsoleSource>| {
+        tauri::async_runtime::block_on(append_debug_logs(
+            app.handle().clone(),
+            logs,
+            None,
+            None,
+            None,
+            source,
+        ))
+        .expect("append")
+    };
+    let query = |ingest_source: Option<ConsoleSource>| {
+        tauri::async_runtime::block_on(query_console_logs(
+            app.handle().clone(),
+            Some(LogQuery {
+                ingest_source,
+                ..LogQuery::default()
+            }),
+        ))
+        .expect("query")
+    };
+
+    append(
+        vec![payload(0, "clicked"), payload(5, "rendered")],
+        Some(ConsoleSource::Bridge),
+    );
+    append(
+        vec![payload(20, "clicked"), payload(25, "collector only")],
+        Some(ConsoleSource::Collector),
+    );
+    // Callers that do not name a pathway are the host app.
+    append(vec![payload(500, "forwarded")], None);
+
+    let all = query(None);
+    let from_collector = query(Some(ConsoleSource::Collector));
+    let from_host = query(Some(ConsoleSource::Host));
+    let stats = tauri::async_runtime::block_on(get_log_stats(app.handle().clone())).expect("stats");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    let mut persisted: Vec<(&str, &str)> = all
+        .iter()
+        .map(|entry| {
+            let source = entry.fields.as_ref().expect("fields")["ingest_source"]
+                .as_str()
+                .expect("ingest_source");
+            (entry.message.as_str(), source)
+        })
+        .collect();
+    persisted.sort();
+    assert_eq!(
+        persisted,
+        [
+            ("clicked", "bridge"),
+            ("collector only", "collector"),
+            ("forwarded", "host"),
+            ("rendered", "bridge"),
+        ]
+    );
+    assert_eq!(messages(&from_collector), ["collector only"]);
+    assert_eq!(messages(&from_host), ["forwarded"]);
+    assert_eq!(stats.console_sources, [ConsoleSource::Bridge]);
+    assert_eq!(
+        stats.cross_source_duplicates,
+        BTreeMap::from([(ConsoleSource::Collector, 1)])
+    );
+}
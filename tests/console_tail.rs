@@ -0,0 +1,61 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use tauri_plugin_debug_tools::tail_console_log;
+
+fn log_file(name: &str, lines: &[&str]) -> PathBuf {
+    let dir = common::temp_dir("console-tail", name);
+    fs::create_dir_all(&dir).expect("create log directory");
+    let path = dir.join("frontend_console.jsonl");
+    fs::write(&path, lines.join("\n")).expect("write log");
+    path
+}
+
+fn entry(timestamp: i64) -> String {
+    format!(
+        r#"{{"timestamp":{},"level":"info","message":"entry {}","args":[],"stack_trace":null}}"#,
+        timestamp, timestamp
+    )
+}
+
+#[test]
+fn pages_back_from_the_newest_entry() {
+    let lines: Vec<String> = (1..=5).map(entry).collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let path = log_file("pages", &lines);
+
+    let newest = tail_console_log(&path, 2, 0).expect("read tail");
+    let timestamps: Vec<i64> = newest.entries.iter().map(|e| e.timestamp).collect();
+    assert_eq!(timestamps, [4, 5]);
+    assert_eq!(newest.total, 5);
+
+    let older = tail_console_log(&path, 2, 2).expect("read page");
+    let timestamps: Vec<i64> = older.entries.iter().map(|e| e.timestamp).collect();
+    assert_eq!(timestamps, [2, 3]);
+
+    let past_the_start = tail_console_log(&path, 2, 10).expect("read past start");
+    assert!(past_the_start.entries.is_empty());
+}
+
+#[test]
+fn counts_malformed_lines_instead_of_failing() {
+    let (first, last) = (entry(1), entry(2));
+    let path = log_file(
+        "malformed",
+        &[&first, "not json", "", &last, r#"{"timestamp":3,"lev"#],
+    );
+
+    let tail = tail_console_log(&path, 10, 0).expect("read tail");
+    assert_eq!(tail.entries.len(), 2);
+    assert_eq!(tail.total, 2);
+    assert_eq!(tail.malformed_lines, 2);
+}
+
+#[test]
+fn a_missing_file_reads_as_empty() {
+    let path = common::temp_dir("console-tail", "missing").join("frontend_console.jsonl");
+    let tail = tail_console_log(&path, 10, 0).expect("read missing");
+    assert!(tail.entries.is_empty());
+    assert_eq!(tail.malformed_lines, 0);
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use tauri::{WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_debug_tools::{
+    append_debug_logs, export_filtered_logs, mark_debug_moment, query_console_logs,
+    write_typed_debug_snapshot, Builder, ConsoleLogEntryPayload, DebugMoment, DebugMoments,
+    DebugToolsConfig, ExportFilteredLogsRequest, FixedClock, LogQuery, DEBUG_MOMENT_FIELD,
+};
+
+const NOW_MS: i64 = 1_700_000_000_000;
+
+fn moment(id: &str, timestamp: i64) -> DebugMoment {
+    DebugMoment {
+        id: id.to_string(),
+        label: format!("label of {}", id),
+        timestamp,
+    }
+}
+
+fn payload(offset_ms: i64, message: &str) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp: NOW_MS + offset_ms,
+        level: "info".to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    }
+}
+
+#[test]
+fn moments_are_found_by_id_and_handed_to_one_snapshot() {
+    let moments = DebugMoments::default();
+    moments.record(moment("moment-a", NOW_MS));
+    moments.record(moment("moment-b", NOW_MS + 10));
+
+    let found = moments.find("moment-b");
+    let pending = moments.take_pending();
+
+    assert_eq!(found, Some(moment("moment-b", NOW_MS + 10)));
+    assert_eq!(moments.find("moment-c"), None);
+    assert_eq!(
+        pending,
+        [moment("moment-a", NOW_MS), moment("moment-b", NOW_MS + 10)]
+    );
+    // Taken ones stay resolvable but no later snapshot carries them again.
+    assert!(moments.take_pending().is_empty());
+    assert!(moments.find("moment-a").is_some());
+}
+
+#[test]
+fn markers_carry_the_moment_id_and_label() {
+    let marker = moment("moment-a", NOW_MS).to_log_entry();
+    let fields = marker.fields.as_ref().expect("fields");
+
+    assert_eq!(marker.timestamp, NOW_MS);
+    assert_eq!(marker.level, "info");
+    assert_eq!(marker.message, "[debug-moment] label of moment-a");
+    assert_eq!(fields[DEBUG_MOMENT_FIELD], "moment-a");
+    assert_eq!(fields["label"], "label of moment-a");
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn logs_exports_and_snapshots_line_up_on_marked_moments() {
+    let log_dir = common::temp_dir("debug-moments", "marks");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+    let clock = Arc::new(FixedClock::new(NOW_MS));
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(clock.clone())
+                .with_stdout(false)
+                .disable_screenshots()
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+        .build()
+        .expect("create window");
+    let append = |logs: Vec<ConsoleLogEntryPayload>| {
+        tauri::async_runtime::block_on(append_debug_logs(
+            app.handle().clone(),
+            logs,
+            None,
+            None,
+            None,
+            None,
+        ))
+        .expect("append")
+    };
+    let mark = |label: &str| {
+        tauri::async_runtime::block_on(mark_debug_moment(app.handle().clone(), label.to_string()))
+    };
+    let between = |from_moment: Option<&str>, to_moment: Option<&str>| {
+        tauri::async_runtime::block_on(query_console_logs(
+            app.handle().clone(),
+            Some(LogQuery {
+                from_moment: from_moment.map(str::to_string),
+                to_moment: to_moment.map(str::to_string),
+                ..LogQuery::default()
+            }),
+        ))
+        .map(|entries| {
+            entries
+                .into_iter()
+                .map(|entry| entry.message)
+                .collect::<Vec<_>>()
+        })
+    };
+    let snapshot = || {
+        tauri::async_runtime::block_on(write_typed_debug_snapshot(
+            app.handle().clone(),
+            serde_json::json!({ "label": "repro" }),
+        ))
+        .expect("snapshot")
+    };
+
+    append(vec![payload(-10, "before")]);
+    let start = mark("  REPRO START  ").expect("mark start");
+    append(vec![payload(5, "during")]);
+    clock.set(NOW_MS + 10);
+    let end = mark("REPRO END").expect("mark end");
+    append(vec![payload(20, "after")]);
+    let blank = mark("   ");
+
+    // A marker entry from an earlier session resolves through the log.
+    let mut earlier = payload(-20, "[debug-moment] earlier");
+    earlier.fields = Some(serde_json::Map::from_iter([(
+        DEBUG_MOMENT_FIELD.to_string(),
+        "moment-earlier-1".into(),
+    )]));
+    append(vec![earlier]);
+
+    let sliced = between(Some(&start.id), Some(&end.id));
+    let from_start = between(Some(&start.id), None);
+    let from_earlier = between(Some("moment-earlier-1"), Some(&start.id));
+    let unknown = between(None, Some("moment-missing"));
+    let request: ExportFilteredLogsRequest = serde_json::from_value(serde_json::json!({
+        "output_name": "repro",
+        "from_moment": start.id,
+        "to_moment": end.id,
+    }))
+    .expect("request");
+    let export =
+        tauri::async_runtime::block_on(export_filtered_logs(app.handle().clone(), request))
+            .expect("export");
+    let exported: Vec<String> = fs::read_to_string(&export.path)
+        .expect("read export")
+        .lines()
+        .map(|line| {
+            let entry: serde_json::Value = serde_json::from_str(line).expect("export line");
+            entry["message"].as_str().expect("message").to_string()
+        })
+        .collect();
+    let first = snapshot();
+    let second = snapshot();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(start.label, "REPRO START");
+    assert_eq!(start.timestamp, NOW_MS);
+    assert_eq!(end.timestamp, NOW_MS + 10);
+    assert_ne!(start.id, end.id);
+    assert!(blank.is_err());
+
+    let repro = [
+        "[debug-moment] REPRO START",
+        "during",
+        "[debug-moment] REPRO END",
+    ];
+    assert_eq!(sliced.expect("slice"), repro);
+    assert_eq!(exported, repro);
+    let from_start = from_start.expect("open-ended slice");
+    assert_eq!(from_start.first().map(String::as_str), Some(repro[0]));
+    assert!(from_start.iter().any(|message| message == "after"));
+    assert_eq!(
+        from_earlier.expect("earlier slice"),
+        [
+            "[debug-moment] earlier",
+            "before",
+            "[debug-moment] REPRO START"
+        ]
+    );
+    assert!(
+        unknown
+            .as_ref()
+            .is_err_and(|e| e.contains("Unknown debug moment: moment-missing")),
+        "{:?}",
+        unknown
+    );
+
+    let carried: Vec<DebugMoment> =
+        serde_json::from_value(first.extensions["debug_moments"].clone()).expect("moments");
+    assert_eq!(carried, [start, end]);
+    assert!(!second.extensions.contains_key("debug_moments"));
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tauri::{Listener, Manager};
+use tauri_plugin_debug_tools::{
+    check_disk_usage, get_debug_stats, measure_disk_usage, Builder, CategoryUsage,
+    DebugToolsConfig, DebugToolsState, DiskCategory, DiskLimitSource, DiskUsage, DiskUsageMonitor,
+    DiskUsageWarning, DISK_USAGE_WARNING_EVENT,
+};
+
+const NOW_MS: i64 = 1_700_000_000_000;
+
+fn config(name: &str, quota: Option<u64>) -> DebugToolsConfig {
+    DebugToolsConfig {
+        max_total_disk_bytes: quota,
+        ..common::config("disk-usage", name)
+    }
+}
+
+fn write(path: &Path, bytes: usize) {
+    fs::write(path, vec![b'x'; bytes]).expect("write artifact");
+}
+
+// The measurement only matters through its ratio here.
+fn usage_at(ratio: f64, categories: Vec<CategoryUsage>) -> DiskUsage {
+    DiskUsage {
+        measured_at: NOW_MS,
+        total_bytes: (ratio * 1_000.0) as u64,
+        limit_bytes: Some(1_000),
+        limit_source: Some(DiskLimitSource::Quota),
+        usage_ratio: Some(ratio),
+        categories,
+    }
+}
+
+fn category(category: DiskCategory, bytes: u64) -> CategoryUsage {
+    CategoryUsage {
+        category,
+        bytes,
+        files: 1,
+    }
+}
+
+#[test]
+fn usage_is_measured_per_category_against_the_quota() {
+    let config = config("measure", Some(10_000));
+    write(&config.frontend_log_path("Disk Test", 1), 3_000);
+    write(&config.log_dir.join("network_Disk_Test_1.jsonl"), 500);
+    write(&config.log_dir.join("rust_debug.log"), 200);
+    write(&config.screenshot_dir().join("1_main.png"), 4_000);
+    let nested = config.log_dir.join("recordings").join("r1");
+    fs::create_dir_all(&nested).expect("create recording directory");
+    write(&nested.join("frames.jsonl"), 1_000);
+    write(&config.log_dir.join("notes.txt"), 100);
+
+    let usage = measure_disk_usage(&config, NOW_MS).expect("measure");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let by_category: Vec<(DiskCategory, u64)> = usage
+        .categories
+        .iter()
+        .map(|category| (category.category, category.bytes))
+        .collect();
+    assert_eq!(
+        by_category,
+        [
+            (DiskCategory::Screenshots, 4_000),
+            (DiskCategory::ConsoleLogs, 3_000),
+            (DiskCategory::Recordings, 1_000),
+            (DiskCategory::NetworkLogs, 500),
+            (DiskCategory::BackendLogs, 200),
+            (DiskCategory::Other, 100),
+        ]
+    );
+    assert_eq!(usage.total_bytes, 8_800);
+    assert_eq!(usage.limit_bytes, Some(10_000));
+    assert_eq!(usage.limit_source, Some(DiskLimitSource::Quota));
+    assert_eq!(usage.usage_ratio, Some(0.88));
+    assert_eq!(usage.measured_at, NOW_MS);
+}
+
+#[cfg(unix)]
+#[test]
+fn without_a_quota_free_disk_space_is_the_limit() {
+    let config = config("free", None);
+    write(&config.frontend_log_path("Disk Test", 1), 1_000);
+
+    let usage = measure_disk_usage(&config, NOW_MS).expect("measure");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(usage.limit_source, Some(DiskLimitSource::FreeDisk));
+    assert!(usage.limit_bytes.unwrap() > usage.total_bytes);
+    assert!(usage.usage_ratio.is_some_and(|ratio| ratio < 1.0));
+}
+
+#[test]
+fn each_threshold_warns_once_until_it_rearms() {
+    let monitor = DiskUsageMonitor::default();
+    let threshold = |warning: Option<DiskUsageWarning>| warning.map(|warning| warning.threshold);
+
+    let crossings: Vec<Option<f64>> = [0.5, 0.75, 0.8, 0.95, 0.99, 0.85, 0.95, 0.2, 0.96]
+        .into_iter()
+        .map(|ratio| threshold(monitor.observe(usage_at(ratio, Vec::new()))))
+        .collect();
+
+    assert_eq!(
+        crossings,
+        [
+            None,
+            Some(0.7),
+            None,
+            Some(0.9),
+            None,
+            // Dropping below 90% re-arms it; 70% stays crossed.
+            None,
+            Some(0.9),
+            None,
+            // Both re-armed; the highest one crossed is named.
+            Some(0.9),
+        ]
+    );
+    assert_eq!(
+        monitor.latest().and_then(|usage| usage.usage_ratio),
+        Some(0.96)
+    );
+}
+
+#[test]
+fn warnings_suggest_the_clear_command_and_become_marker_entries() {
+    let monitor = DiskUsageMonitor::default();
+    let warning = monitor
+        .observe(usage_at(
+            0.92,
+            vec![
+                category(DiskCategory::Recordings, 500),
+                category(DiskCategory::Screenshots, 300),
+                category(DiskCategory::ConsoleLogs, 100),
+                category(DiskCategory::Exports, 20),
+            ],
+        ))
+        .expect("warning");
+    let only_kept = DiskUsageMonitor::default()
+        .observe(usage_at(
+            0.95,
+            vec![category(DiskCategory::Recordings, 950)],
+        ))
+        .expect("warning");
+
+    // Recordings and exports are not removed by the clear command.
+    let cleanup = warning.suggested_cleanup.as_ref().expect("suggestion");
+    assert_eq!(cleanup.command, "clear_debug_log_files_command");
+    assert_eq!(cleanup.frees_bytes, 400);
+    assert!(only_kept.suggested_cleanup.is_none());
+
+    let marker = warning.to_log_entry();
+    let fields = marker.fields.as_ref().expect("fields");
+    assert_eq!(marker.level, "warn");
+    assert_eq!(marker.timestamp, NOW_MS);
+    assert!(marker.message.contains("92%"), "{}", marker.message);
+    assert_eq!(fields["disk_usage_warning"], true);
+    assert_eq!(fields["threshold"], 0.9);
+    assert_eq!(fields["total_bytes"], 920);
+    assert_eq!(fields["limit_bytes"], 1_000);
+    assert_eq!(marker.args[0]["category"], "recordings");
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn crossing_the_quota_emits_an_event_writes_a_marker_and_shows_in_stats() {
+    let config = config("app", Some(1_000_000));
+    let log_dir = config.log_dir.clone();
+    let console_dir = config.console_log_dir();
+    let screenshot = config.screenshot_dir().join("1_main.png");
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let received = events.clone();
+    app.listen_any(DISK_USAGE_WARNING_EVENT, move |event| {
+        let warning: DiskUsageWarning =
+            serde_json::from_str(event.payload()).expect("warning payload");
+        received.lock().unwrap().push(warning.threshold);
+    });
+    let check = || check_disk_usage(app.handle(), &app.state::<DebugToolsState>());
+
+    check();
+    write(&screenshot, 950_000);
+    check();
+    check();
+    fs::remove_file(&screenshot).expect("remove screenshot");
+    check();
+    write(&screenshot, 950_000);
+    check();
+
+    let stats =
+        tauri::async_runtime::block_on(get_debug_stats(app.handle().clone())).expect("stats");
+    let markers: Vec<serde_json::Value> = fs::read_dir(&console_dir)
+        .expect("read console log directory")
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with("frontend_console_"))
+        })
+        .flat_map(|entry| {
+            fs::read_to_string(entry.path())
+                .expect("read console log")
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("log line"))
+                .collect::<Vec<_>>()
+        })
+        .filter(|line| line["fields"]["disk_usage_warning"] == true)
+        .collect();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(*events.lock().unwrap(), [0.9, 0.9]);
+    assert_eq!(markers.len(), 2, "{:?}", markers);
+    assert!(markers.iter().all(|marker| marker["level"] == "warn"));
+
+    let usage = stats.disk_usage.expect("disk usage in stats");
+    assert_eq!(usage.limit_bytes, Some(1_000_000));
+    assert_eq!(usage.categories[0].category, DiskCategory::Screenshots);
+    assert!(usage.usage_ratio.is_some_and(|ratio| ratio >= 0.95));
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use tauri_plugin_debug_tools::{
+    capture_dom_delta, reconstruct_from_baseline, Builder, DebugToolsConfig, DomSnapshotPayload,
+    FixedClock,
+};
+
+fn payload(html: &str) -> DomSnapshotPayload {
+    DomSnapshotPayload {
+        html: html.to_string(),
+        url: "http://localhost/app".into(),
+        title: "App".into(),
+        viewport_width: 1280,
+        viewport_height: 720,
+        shadow_dom_included: false,
+    }
+}
+
+fn rendered_list(items: usize, marker: &str) -> String {
+    let mut html = String::from("<html><body><ul id=\"app\">");
+    for i in 0..items {
+        html.push_str(&format!("<li data-i=\"{}\">{} {}</li>", i, marker, i));
+    }
+    html.push_str("</ul></body></html>");
+    html
+}
+
+#[test]
+fn deltas_against_the_first_capture_reconstruct_the_dom() {
+    let log_dir = common::temp_dir("dom-delta", "deltas");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+    let clock = Arc::new(FixedClock::new(1_700_000_000_000).with_step(1_000));
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(clock)
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+
+    // An SPA shell: almost nothing is in the initial HTML.
+    let shell = "<html><body><div id=\"app\"></div></body></html>";
+    let first =
+        tauri::async_runtime::block_on(capture_dom_delta(handle(), payload(shell))).expect("first");
+    assert!(first.baseline_created);
+    assert_eq!(first.path, first.baseline_path);
+
+    let edited = rendered_list(200, "item").replace("item 120<", "item 120 (edited)<");
+    let cases = [
+        ("identical", shell.to_string()),
+        ("rendered", rendered_list(200, "item")),
+        ("edited", edited),
+        ("multibyte", "<p>héllo wörld ✓</p>".repeat(50)),
+        ("emptied", String::new()),
+    ];
+
+    let mut results = Vec::new();
+    for (name, html) in &cases {
+        let delta =
+            tauri::async_runtime::block_on(capture_dom_delta(handle(), payload(html))).expect(name);
+        assert!(!delta.baseline_created, "{}", name);
+        assert_eq!(delta.baseline_path, first.baseline_path, "{}", name);
+        let rebuilt = tauri::async_runtime::block_on(reconstruct_from_baseline(
+            handle(),
+            delta.path.to_string_lossy().into_owned(),
+        ))
+        .expect(name);
+        results.push((*name, delta, rebuilt));
+    }
+
+    // A delta whose baseline no longer matches is rejected, not misapplied.
+    let (_, stale, _) = &results[1];
+    let mut baseline = fs::read_to_string(&first.baseline_path).expect("read baseline");
+    baseline.push_str("<!-- edited afterwards -->");
+    fs::write(&first.baseline_path, baseline).expect("rewrite baseline");
+    let mismatch = tauri::async_runtime::block_on(reconstruct_from_baseline(
+        handle(),
+        stale.path.to_string_lossy().into_owned(),
+    ));
+    let _ = fs::remove_dir_all(&log_dir);
+
+    for ((name, html), (_, delta, rebuilt)) in cases.iter().zip(&results) {
+        assert_eq!(&rebuilt.html, html, "{}", name);
+        assert_eq!(rebuilt.title, "App", "{}", name);
+        match *name {
+            "identical" => assert_eq!((delta.inserted_bytes, delta.deleted_bytes), (0, 0)),
+            "emptied" => assert_eq!(
+                (delta.inserted_bytes, delta.deleted_bytes),
+                (0, shell.len())
+            ),
+            _ => assert!(delta.inserted_bytes > 0, "{}", name),
+        }
+    }
+    // Against the same baseline, one edited item costs only its own bytes.
+    let rendered = results[1].1.inserted_bytes;
+    let edited = results[2].1.inserted_bytes;
+    assert_eq!(edited - rendered, " (edited)".len());
+    assert!(mismatch.is_err());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{save_dom_snapshot, Builder, DebugToolsConfig};
+
+// The command is called directly because the mock runtime has no ACL for
+// plugin commands.
+#[test]
+fn save_dom_snapshot_writes_the_html_under_the_dom_directory() {
+    let log_dir = common::temp_dir("dom", "save");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config.clone())
+                .with_stdout(false)
+                .disable_screenshots()
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let result = tauri::async_runtime::block_on(save_dom_snapshot(
+        app.handle().clone(),
+        "<html><body><h1>Orders</h1></body></html>".into(),
+        "http://localhost:5173/orders".into(),
+        "Orders".into(),
+        1200,
+        800,
+    ))
+    .expect("command succeeds");
+
+    let html = fs::read_to_string(&result.path).ok();
+
+    assert!(result.path.starts_with(config.dom_snapshot_dir()));
+    assert_eq!(result.metadata.title, "Orders");
+    assert!(html.is_some_and(|html| html.contains("<h1>Orders</h1>")));
+
+    // `-->` in the URL or title must not end the metadata comment early, and
+    // the page's own comments are kept byte for byte.
+    let page = "<html><!-- a --><body>x --> y</body></html>";
+    let result = tauri::async_runtime::block_on(save_dom_snapshot(
+        app.handle().clone(),
+        page.into(),
+        "http://localhost:5173/#-->".into(),
+        "Orders --> Details".into(),
+        1200,
+        800,
+    ))
+    .expect("command succeeds");
+    let content = fs::read_to_string(&result.path).expect("read snapshot");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    let rest = content
+        .strip_prefix("<!--\nDOM Snapshot Metadata:\n")
+        .expect("metadata comment");
+    let (metadata, html) = rest.split_once("\n-->\n").expect("comment end");
+    assert!(!metadata.contains("-->"));
+    let metadata: serde_json::Value = serde_json::from_str(metadata).expect("metadata parses");
+    assert_eq!(metadata["url"], "http://localhost:5173/#-->");
+    assert_eq!(metadata["title"], "Orders --> Details");
+    assert_eq!(html, page);
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri_plugin_debug_tools::{
+    append_dom_chunk, begin_dom_capture, finish_dom_capture, save_dom_snapshot, Builder,
+    ChunkEncoding, DebugToolsConfig, DomUploadError, DomUploadMetadata, DomUploadStore, FixedClock,
+};
+
+const START_MS: i64 = 1_700_000_000_000;
+
+fn config(name: &str) -> DebugToolsConfig {
+    DebugToolsConfig {
+        log_dir: common::temp_dir("dom-upload", name),
+        max_dom_upload_bytes: 64,
+        dom_upload_timeout_secs: 60,
+        ..DebugToolsConfig::default()
+    }
+}
+
+fn store(config: &DebugToolsConfig) -> (DomUploadStore, Arc<FixedClock>) {
+    let clock = Arc::new(FixedClock::new(START_MS));
+    (DomUploadStore::new(config, clock.clone()), clock)
+}
+
+fn upload_dirs(config: &DebugToolsConfig) -> Vec<PathBuf> {
+    fs::read_dir(config.dom_upload_dir())
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn chunks_arriving_out_of_order_are_joined_by_sequence() {
+    let config = config("order");
+    let (uploads, _) = store(&config);
+    uploads.begin("a".into()).expect("begin");
+
+    for (seq, chunk) in [(2, "</p>"), (0, "<p>"), (1, "hello")] {
+        uploads.append("a", seq, chunk.as_bytes()).expect("append");
+    }
+    let html = uploads.finish("a", Some(3)).expect("finish");
+    let leftovers = upload_dirs(&config);
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(html, b"<p>hello</p>");
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+}
+
+#[test]
+fn duplicate_chunks_are_acknowledged_once_and_conflicts_rejected() {
+    let config = config("duplicate");
+    let (uploads, _) = store(&config);
+    uploads.begin("a".into()).expect("begin");
+
+    let first = uploads.append("a", 0, b"<p>").expect("append");
+    let resent = uploads.append("a", 0, b"<p>").expect("resend");
+    let conflict = uploads.append("a", 0, b"<div>");
+    uploads.append("a", 1, b"</p>").expect("append");
+    let html = uploads.finish("a", None).expect("finish");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert!(!first.duplicate);
+    assert!(resent.duplicate);
+    assert_eq!((resent.received_chunks, resent.received_bytes), (1, 3));
+    assert!(matches!(conflict, Err(DomUploadError::ConflictingChunk(0))));
+    assert_eq!(html, b"<p></p>");
+}
+
+#[test]
+fn a_gap_keeps_the_upload_open_until_the_chunk_is_resent() {
+    let config = config("gap");
+    let (uploads, _) = store(&config);
+    uploads.begin("a".into()).expect("begin");
+    uploads.append("a", 0, b"a").expect("append");
+    uploads.append("a", 2, b"c").expect("append");
+
+    let missing = uploads.finish("a", Some(4));
+    uploads.append("a", 1, b"b").expect("resend gap");
+    uploads.append("a", 3, b"d").expect("append last");
+    let html = uploads.finish("a", Some(4)).expect("finish");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert!(matches!(missing, Err(DomUploadError::MissingChunks(ref seqs)) if seqs == &[1, 3]));
+    assert_eq!(html, b"abcd");
+}
+
+#[test]
+fn going_over_the_size_cap_aborts_the_upload() {
+    let config = config("cap");
+    let (uploads, _) = store(&config);
+    uploads.begin("a".into()).expect("begin");
+    uploads.append("a", 0, &[b'x'; 40]).expect("append");
+
+    let over = uploads.append("a", 1, &[b'x'; 40]);
+    let after = uploads.append("a", 2, b"x");
+    let leftovers = upload_dirs(&config);
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert!(matches!(over, Err(DomUploadError::TooLarge(64))));
+    assert!(matches!(after, Err(DomUploadError::UnknownUpload(_))));
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+}
+
+#[test]
+fn abandoned_uploads_expire_after_the_timeout() {
+    let config = config("expire");
+    let (uploads, clock) = store(&config);
+    uploads.begin("stale".into()).expect("begin");
+    uploads.append("stale", 0, b"<p>").expect("append");
+    clock.advance(30_000);
+    uploads.begin("active".into()).expect("begin");
+
+    clock.advance(30_001);
+    let expired = uploads.expire();
+    let stale = uploads.append("stale", 1, b"</p>");
+    uploads
+        .append("active", 0, b"ok")
+        .expect("active upload survives");
+    let remaining = upload_dirs(&config);
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(expired, 1);
+    assert!(matches!(stale, Err(DomUploadError::UnknownUpload(_))));
+    assert_eq!(remaining, [config.dom_upload_dir().join("active")]);
+}
+
+#[test]
+fn uploads_left_by_an_exited_run_are_removed_on_start() {
+    let config = config("restart");
+    let (uploads, _) = store(&config);
+    uploads.begin("interrupted".into()).expect("begin");
+    uploads.append("interrupted", 0, b"half").expect("append");
+    // Not a chunk directory, so not the plugin's to remove.
+    let foreign = config.dom_upload_dir().join("notes");
+    fs::create_dir_all(&foreign).expect("create foreign directory");
+    fs::write(foreign.join("todo.txt"), "keep").expect("write foreign file");
+    // The process exits without finishing or clearing.
+    drop(uploads);
+
+    let (restarted, _) = store(&config);
+    let after_restart = upload_dirs(&config);
+    let unknown = restarted.finish("interrupted", None);
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(after_restart, [foreign]);
+    assert!(matches!(unknown, Err(DomUploadError::UnknownUpload(_))));
+}
+
+#[test]
+fn base64_chunks_decode_and_invalid_ones_are_rejected() {
+    assert_eq!(
+        ChunkEncoding::Base64
+            .decode("PHA+".to_string())
+            .expect("decode"),
+        b"<p>"
+    );
+    assert_eq!(
+        ChunkEncoding::Utf8
+            .decode("<p>".to_string())
+            .expect("decode"),
+        b"<p>"
+    );
+    assert!(matches!(
+        ChunkEncoding::Base64.decode("not base64!".to_string()),
+        Err(DomUploadError::InvalidBase64(_))
+    ));
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn a_chunked_capture_is_saved_like_a_single_shot_one() {
+    let config = DebugToolsConfig {
+        max_dom_upload_bytes: 1024 * 1024,
+        ..config("app")
+    };
+    let log_dir = config.log_dir.clone();
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+
+    // "é" is split across the first two chunks.
+    let html = "<p>caf\u{e9}</p>".as_bytes();
+    let split = html.iter().position(|byte| *byte == 0xc3).unwrap() + 1;
+    let upload_id = tauri::async_runtime::block_on(begin_dom_capture(handle())).expect("begin");
+    let chunks = [
+        (1, base64_of(&html[split..]), ChunkEncoding::Base64),
+        (0, base64_of(&html[..split]), ChunkEncoding::Base64),
+    ];
+    for (seq, bytes, encoding) in chunks {
+        tauri::async_runtime::block_on(append_dom_chunk(
+            handle(),
+            upload_id.clone(),
+            seq,
+            bytes,
+            Some(encoding),
+        ))
+        .expect("append chunk");
+    }
+    let metadata: DomUploadMetadata = serde_json::from_value(serde_json::json!({
+        "url": "http://localhost/",
+        "title": "Chunked",
+        "viewport_width": 800,
+        "viewport_height": 600,
+        "total_chunks": 2,
+    }))
+    .expect("metadata");
+    let chunked =
+        tauri::async_runtime::block_on(finish_dom_capture(handle(), upload_id.clone(), metadata))
+            .expect("finish");
+    // Read now: a save within the same second reuses the file name.
+    let chunked_html = fs::read_to_string(&chunked.path).expect("read chunked DOM");
+    let reused = tauri::async_runtime::block_on(append_dom_chunk(
+        handle(),
+        upload_id,
+        2,
+        "<p>".into(),
+        None,
+    ));
+
+    let single = tauri::async_runtime::block_on(save_dom_snapshot(
+        handle(),
+        "<p>small</p>".into(),
+        "http://localhost/".into(),
+        "Single".into(),
+        800,
+        600,
+    ))
+    .expect("single-shot save");
+    let single_html = fs::read_to_string(&single.path).expect("read single DOM");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(
+        chunked_html.contains("<p>caf\u{e9}</p>"),
+        "{}",
+        chunked_html
+    );
+    assert_eq!(chunked.metadata.replacement_chars, 0);
+    assert!(reused.is_err(), "finished uploads are closed");
+    assert!(single_html.contains("<p>small</p>"));
+}
+
+fn base64_of(bytes: &[u8]) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
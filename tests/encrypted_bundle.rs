@@ -0,0 +1,212 @@
+mod common;
+
+use std::fs;
+use std::io::Read;
+use tauri_plugin_debug_tools::analysis::merge::merge_bundles;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, export_encrypted_bundle, import_encrypted_bundle, merge_debug_bundles,
+    save_dom_snapshot, Builder, ConsoleLogEntryPayload, DebugToolsConfig, EncryptedBundleError,
+};
+
+const SECRET: &str = "card ending 4242 declined";
+const PASSPHRASE: &str = "correct horse battery staple";
+
+fn entry(timestamp: i64, level: &str, message: &str) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    }
+}
+
+#[test]
+fn bundles_round_trip_through_encryption_and_merge() {
+    let base = common::temp_dir("encrypted", "round-trip");
+    let log_dir = base.join("logs");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+
+    tauri::async_runtime::block_on(append_debug_logs(
+        handle(),
+        vec![
+            entry(1_700_000_000_000, "info", "checkout opened"),
+            entry(1_700_000_000_500, "error", SECRET),
+        ],
+        None,
+        None,
+        None,
+        None,
+    ))
+    .expect("append");
+    tauri::async_runtime::block_on(save_dom_snapshot(
+        handle(),
+        "<p>cart</p>".into(),
+        "http://localhost/cart".into(),
+        "Cart".into(),
+        800,
+        600,
+    ))
+    .expect("save dom");
+    // Not written by the plugin, so never bundled.
+    fs::write(log_dir.join("frontend_console_other_1.jsonl"), "{}\n").expect("write foreign");
+
+    assert!(matches!(
+        tauri::async_runtime::block_on(export_encrypted_bundle(handle(), String::new())),
+        Err(EncryptedBundleError::EmptyPassphrase)
+    ));
+    let export =
+        tauri::async_runtime::block_on(export_encrypted_bundle(handle(), PASSPHRASE.into()))
+            .expect("export bundle");
+    let name = export
+        .path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+    assert!(
+        name.starts_with("debug_bundle_") && name.ends_with(".zip.enc"),
+        "{}",
+        name
+    );
+    assert!(export.path.starts_with(log_dir.join("exports")));
+    let sealed = fs::read(&export.path).expect("read sealed bundle");
+    assert_eq!(sealed.len() as u64, export.encrypted_bytes);
+    assert!(
+        !sealed
+            .windows(SECRET.len())
+            .any(|window| window == SECRET.as_bytes()),
+        "plaintext leaked into the encrypted bundle"
+    );
+    assert!(!sealed.starts_with(b"PK"), "not a plain zip");
+
+    // Received by someone else: copied out of the log directory first.
+    let received = base.join("inbox").join(&name);
+    fs::create_dir_all(received.parent().unwrap()).expect("create inbox");
+    fs::copy(&export.path, &received).expect("copy bundle");
+    let import_into = |passphrase: &str, out_dir: &std::path::Path| {
+        tauri::async_runtime::block_on(import_encrypted_bundle(
+            handle(),
+            received.to_string_lossy().into_owned(),
+            passphrase.into(),
+            Some(out_dir.to_string_lossy().into_owned()),
+        ))
+    };
+    let imports = log_dir.join("imports").join("inbox");
+    let import = |passphrase: &str| import_into(passphrase, &imports);
+
+    // Imports land under the log directory's `imports/` only.
+    let elsewhere = base.join("elsewhere");
+    for out_dir in [elsewhere.clone(), log_dir.join("imports/../exports")] {
+        assert!(matches!(
+            import_into(PASSPHRASE, &out_dir),
+            Err(EncryptedBundleError::Io { .. })
+        ));
+    }
+    assert!(!elsewhere.exists());
+
+    assert!(matches!(
+        import("wrong passphrase"),
+        Err(EncryptedBundleError::Decrypt)
+    ));
+    assert!(!imports.exists(), "nothing written on a wrong passphrase");
+
+    let mut tampered = sealed.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    fs::write(&received, &tampered).expect("write tampered");
+    assert!(matches!(
+        import(PASSPHRASE),
+        Err(EncryptedBundleError::Decrypt)
+    ));
+    // Raising the KDF cost in the header is refused before any work, from
+    // just past the caps (64 MiB, 4 passes) up.
+    for (at, cost) in [(8, u32::MAX), (8, 64 * 1024 + 1), (12, 5)] {
+        let mut costly = sealed.clone();
+        costly[at..at + 4].copy_from_slice(&cost.to_le_bytes());
+        fs::write(&received, &costly).expect("write costly");
+        assert!(matches!(
+            import(PASSPHRASE),
+            Err(EncryptedBundleError::UnsupportedParameters)
+        ));
+    }
+    fs::write(&received, b"PK\x03\x04plain zip").expect("write plain");
+    assert!(matches!(
+        import(PASSPHRASE),
+        Err(EncryptedBundleError::NotEncrypted)
+    ));
+
+    fs::write(&received, &sealed).expect("restore bundle");
+    let imported = import(PASSPHRASE).expect("import bundle");
+    assert_eq!(
+        imported.path,
+        imports.join(name.replace(".zip.enc", ".zip"))
+    );
+    assert_eq!(imported.entries, export.files + 1);
+    assert_eq!(imported.zip_bytes, export.zip_bytes);
+
+    let mut archive =
+        zip::ZipArchive::new(fs::File::open(&imported.path).expect("open zip")).expect("zip");
+    let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+    names.sort();
+    let mut manifest = String::new();
+    archive
+        .by_name("manifest.json")
+        .expect("manifest entry")
+        .read_to_string(&mut manifest)
+        .expect("read manifest");
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).expect("manifest json");
+
+    let report =
+        merge_bundles(std::slice::from_ref(&imported.path), &base.join("merged")).expect("merge");
+    let merge = |out_dir: Option<&std::path::Path>| {
+        tauri::async_runtime::block_on(merge_debug_bundles(
+            handle(),
+            vec![imported.path.to_string_lossy().into_owned()],
+            out_dir.map(|dir| dir.to_string_lossy().into_owned()),
+            None,
+        ))
+    };
+    // Merged output stays under the log directory's `merged/`.
+    let refused = merge(Some(&base.join("elsewhere")));
+    let merged = merge(None).expect("merge command");
+    let merged_dir = fs::read_dir(log_dir.join("merged"))
+        .expect("merged directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    let _ = fs::remove_dir_all(&base);
+
+    assert!(names.contains(&"manifest.json".to_string()));
+    assert!(names
+        .iter()
+        .any(|name| name.starts_with("dom_snapshots/dom_")));
+    assert!(names
+        .iter()
+        .any(|name| name.starts_with("frontend_console_") && !name.contains("other")));
+    assert!(!names.contains(&"frontend_console_other_1.jsonl".to_string()));
+    assert_eq!(manifest["app_name"], app.package_info().name);
+    assert!(manifest["session_id"].is_string());
+
+    assert!(refused.is_err());
+    assert!(!base.join("elsewhere").exists());
+    assert_eq!(merged.timeline_entries, report.timeline_entries);
+    assert_eq!(merged_dir.len(), 1);
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    assert_eq!(
+        report.bundles[0].session_id.as_deref(),
+        manifest["session_id"].as_str()
+    );
+    assert_eq!(report.fingerprints, 1);
+    assert!(report.timeline_entries >= 2);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri_plugin_debug_tools::{
+    clear_debug_log_files, sanitize_filename, DebugToolsConfig, LegalHolds, OwnedFiles,
+};
+
+const PID: u32 = 777;
+// "Café" with the accent precomposed and as a combining mark.
+const NFC: &str = "Caf\u{e9} App";
+const NFD: &str = "Cafe\u{301} App";
+
+fn log_dir(name: &str) -> PathBuf {
+    let dir = common::temp_dir("filename", name);
+    fs::create_dir_all(&dir).expect("create log directory");
+    dir
+}
+
+fn config(log_dir: &Path) -> DebugToolsConfig {
+    DebugToolsConfig {
+        log_dir: log_dir.to_path_buf(),
+        ..DebugToolsConfig::default()
+    }
+}
+
+#[test]
+fn nfc_and_nfd_spellings_give_the_same_name() {
+    assert_eq!(sanitize_filename(NFC), "Caf\u{e9}_App");
+    assert_eq!(sanitize_filename(NFD), sanitize_filename(NFC));
+    // Already sanitized names come back unchanged.
+    let once = sanitize_filename(NFD);
+    assert_eq!(sanitize_filename(&once), once);
+}
+
+#[test]
+fn emoji_and_rtl_scripts_stay_readable() {
+    assert_eq!(sanitize_filename("Rocket 🚀"), "Rocket_🚀");
+    assert_eq!(sanitize_filename("שלום"), "שלום");
+    assert_eq!(sanitize_filename("مرحبا بك"), "مرحبا_بك");
+    assert_eq!(sanitize_filename("日本語アプリ"), "日本語アプリ");
+}
+
+#[test]
+fn unsafe_control_and_bidi_override_characters_are_replaced() {
+    assert_eq!(
+        sanitize_filename("a/b\\c:d*e?f\"g<h>i|j"),
+        "a_b_c_d_e_f_g_h_i_j"
+    );
+    assert_eq!(sanitize_filename("tab\there\nnull\0"), "tab_here_null_");
+    // U+202E would display "gpj.exe" as "exe.jpg".
+    assert_eq!(
+        sanitize_filename("invoice\u{202e}gpj.exe"),
+        "invoice_gpj.exe"
+    );
+    assert_eq!(sanitize_filename("\u{2067}x\u{2069}"), "_x_");
+}
+
+#[test]
+fn long_names_are_capped_with_a_hash_suffix() {
+    let short = "é".repeat(48);
+    assert_eq!(short.len(), 96);
+    assert_eq!(sanitize_filename(&short), short);
+
+    let long = "é".repeat(60);
+    let capped = sanitize_filename(&long);
+    assert!(capped.len() <= 96, "{} bytes", capped.len());
+    let (kept, hash) = capped.rsplit_once('-').expect("hash suffix");
+    assert!(long.starts_with(kept));
+    assert_eq!(hash.len(), 8);
+    assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+    // Names that share the kept prefix still differ.
+    assert_ne!(sanitize_filename(&format!("{}x", long)), capped);
+    // The NFD spelling caps to the same name.
+    let decomposed = "e\u{301}".repeat(60);
+    assert_eq!(sanitize_filename(&decomposed), capped);
+
+    // A combining mark that does not compose is never split from its base.
+    let marks = "q\u{301}\u{323}".repeat(30);
+    let capped = sanitize_filename(&marks);
+    let kept = capped.rsplit_once('-').expect("hash suffix").0;
+    // NFC orders the marks; each cluster is "q" plus two 2-byte marks.
+    assert_eq!(kept, "q\u{323}\u{301}".repeat(kept.len() / 5), "{:?}", kept);
+    assert!(capped.len() <= 96);
+}
+
+#[test]
+fn clear_matches_names_written_in_either_form() {
+    for (name, app, on_disk) in [("nfd-on-disk", NFC, NFD), ("nfc-on-disk", NFD, NFC)] {
+        let dir = log_dir(name);
+        let config = config(&dir);
+        let owned = OwnedFiles::load(&config);
+        let path = dir.join(format!(
+            "frontend_console_{}_{}.jsonl",
+            on_disk.replace(' ', "_"),
+            PID
+        ));
+        fs::write(&path, "{}\n").expect("write log");
+        owned.register(&path);
+
+        let report =
+            clear_debug_log_files(&config, app, &LegalHolds::default(), &owned).expect("clear");
+        let gone = !path.exists();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(gone, "{}: {:?}", name, report);
+        assert!(report.skipped_foreign.is_empty(), "{}: {:?}", name, report);
+    }
+}
+
+#[test]
+fn the_written_log_name_round_trips_through_the_owned_list() {
+    let dir = log_dir("round-trip");
+    let config = config(&dir);
+    let path = config.frontend_log_path(NFD, PID);
+    assert_eq!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some(format!("frontend_console_Caf\u{e9}_App_{}.jsonl", PID).as_str())
+    );
+
+    fs::create_dir_all(path.parent().unwrap()).expect("create console dir");
+    fs::write(&path, "{}\n").expect("write log");
+    OwnedFiles::load(&config).register(&path);
+    // Reloaded from disk, and looked up through the decomposed spelling.
+    let reloaded = OwnedFiles::load(&config);
+    let decomposed = path.with_file_name(format!("frontend_console_Cafe\u{301}_App_{}.jsonl", PID));
+    let owned = reloaded.is_owned(&path) && reloaded.is_owned(&decomposed);
+    let _ = fs::remove_dir_all(&dir);
+    assert!(owned);
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, final_beacon_protocol, submit_final_beacon, Builder, ConsoleLogEntryPayload,
+    DebugToolsConfig, FinalBeacon, FixedClock,
+};
+
+const NOW_MS: i64 = 1_700_000_000_000;
+
+fn entry(seq: u64, message: &str) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp: NOW_MS - 10_000 + seq as i64,
+        level: "info".to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: Some(seq),
+    }
+}
+
+fn beacon(entries: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "source": "main",
+        "reason": "pagehide",
+        "entries": entries,
+        "state": {
+            "url": "http://localhost/orders",
+            "visibility_state": "hidden",
+            "pending_entries": 2,
+        },
+    })
+}
+
+fn entry_json(seq: u64, message: &str) -> serde_json::Value {
+    serde_json::json!({
+        "timestamp": NOW_MS - 10_000 + seq as i64,
+        "level": "warn",
+        "message": message,
+        "args": [],
+        "seq": seq,
+    })
+}
+
+// Every console log line written, in file order.
+fn console_lines(dir: &Path) -> Vec<serde_json::Value> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .expect("read console log directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("frontend_console_"))
+        })
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .flat_map(|path| {
+            fs::read_to_string(path)
+                .expect("read console log")
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("log line"))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn post(path: &str, body: Vec<u8>) -> tauri::http::Request<Vec<u8>> {
+    tauri::http::Request::builder()
+        .method("POST")
+        .uri(format!("debug-tools://localhost{}", path))
+        .body(body)
+        .expect("request")
+}
+
+#[test]
+fn beacons_are_persisted_marked_and_deduplicated_by_seq() {
+    let log_dir = common::temp_dir("final-beacon", "app");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+    let console_dir = config.console_log_dir();
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(Arc::new(FixedClock::new(NOW_MS).with_step(1)))
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let flush = |entries: Vec<ConsoleLogEntryPayload>| {
+        tauri::async_runtime::block_on(append_debug_logs(
+            app.handle().clone(),
+            entries,
+            None,
+            None,
+            Some("main".to_string()),
+            None,
+        ))
+        .expect("flush")
+    };
+
+    flush(vec![entry(1, "one"), entry(2, "two")]);
+    // The beacon repeats seq 2, which a flush already delivered, and lists
+    // its entries out of order.
+    let submitted: FinalBeacon = serde_json::from_value(beacon(serde_json::json!([
+        entry_json(4, "four"),
+        entry_json(2, "two"),
+        entry_json(3, "three"),
+    ])))
+    .expect("beacon");
+    let record =
+        tauri::async_runtime::block_on(submit_final_beacon(app.handle().clone(), submitted))
+            .expect("submit beacon");
+    // A flush that was in flight at unload lands after the beacon.
+    flush(vec![entry(3, "three"), entry(4, "four"), entry(5, "five")]);
+
+    // The protocol handler is what `sendBeacon` reaches.
+    let handle = app.handle();
+    let posted = final_beacon_protocol(
+        handle,
+        post(
+            "/final-beacon",
+            beacon(serde_json::json!([entry_json(6, "six")]))
+                .to_string()
+                .into_bytes(),
+        ),
+    );
+    let preflight = final_beacon_protocol(
+        handle,
+        tauri::http::Request::builder()
+            .method("OPTIONS")
+            .uri("debug-tools://localhost/final-beacon")
+            .body(Vec::new())
+            .unwrap(),
+    );
+    let malformed = final_beacon_protocol(handle, post("/final-beacon", b"{".to_vec()));
+    let elsewhere = final_beacon_protocol(handle, post("/other", b"{}".to_vec()));
+    let oversized = final_beacon_protocol(
+        handle,
+        post("/final-beacon", vec![b' '; 8 * 1024 * 1024 + 1]),
+    );
+
+    let lines = console_lines(&console_dir);
+    let beacon_files: Vec<serde_json::Value> = fs::read_dir(&log_dir)
+        .expect("read log directory")
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with("final_beacon_"))
+        })
+        .map(|entry| {
+            serde_json::from_slice(&fs::read(entry.path()).expect("read beacon")).expect("json")
+        })
+        .collect();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(
+        (record.written_entries, record.duplicate_entries),
+        (2, 1),
+        "{:?}",
+        record
+    );
+    assert_eq!((record.first_seq, record.last_seq), (Some(2), Some(4)));
+    assert!(record.final_beacon);
+
+    let messages: Vec<&str> = lines
+        .iter()
+        .map(|line| line["message"].as_str().unwrap())
+        .collect();
+    assert_eq!(messages, ["one", "two", "three", "four", "five", "six"]);
+    for line in &lines {
+        let from_beacon = ["three", "four", "six"].contains(&line["message"].as_str().unwrap());
+        assert_eq!(
+            line["fields"]["final_beacon"].as_bool(),
+            from_beacon.then_some(true),
+            "{}",
+            line
+        );
+    }
+    // Beacon entries keep the time they were logged, not when they arrived.
+    assert_eq!(lines[2]["timestamp"], NOW_MS - 10_000 + 3);
+
+    assert_eq!(beacon_files.len(), 2);
+    assert!(beacon_files
+        .iter()
+        .all(|file| file["final_beacon"] == true && file["source"] == "main"));
+    assert!(beacon_files
+        .iter()
+        .any(|file| file["state"]["pending_entries"] == 2 && file["reason"] == "pagehide"));
+
+    assert_eq!(posted.status(), 204);
+    assert_eq!(preflight.status(), 204);
+    assert_eq!(malformed.status(), 400);
+    assert_eq!(elsewhere.status(), 404);
+    assert_eq!(oversized.status(), 413);
+}
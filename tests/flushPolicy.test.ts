@@ -0,0 +1,126 @@
+import { describe, expect, it } from "vitest";
+
+import type { ConsoleLogEntry } from "../guest-js/consoleLogger";
+import {
+  AdaptiveFlushPolicy,
+  DEFAULT_FLUSH_POLICY,
+} from "../guest-js/flushPolicy";
+
+interface ScriptedEntry {
+  at: number;
+  level: ConsoleLogEntry["level"];
+  bytes: number;
+}
+
+interface Delivery {
+  entry: ScriptedEntry;
+  latencyMs: number;
+}
+
+// The collector's default `flush_batch_size`.
+const BATCH_SIZE = 200;
+
+// Replays `script` against the policy the way the collector schedules
+// flushes: one pending timer, kept for later entries unless one asks for an
+// immediate flush or a full batch is queued.
+function simulate(
+  policy: AdaptiveFlushPolicy,
+  script: ScriptedEntry[],
+): Delivery[] {
+  const delivered: Delivery[] = [];
+  let queue: ScriptedEntry[] = [];
+  let pendingBytes = 0;
+  let timerAt: number | null = null;
+
+  const flush = (now: number) => {
+    for (const entry of queue) {
+      delivered.push({ entry, latencyMs: now - entry.at });
+    }
+    policy.recordFlush(queue.length, now);
+    queue = [];
+    pendingBytes = 0;
+    timerAt = null;
+  };
+
+  for (const entry of script) {
+    if (timerAt !== null && timerAt <= entry.at) flush(timerAt);
+
+    queue.push(entry);
+    pendingBytes += entry.bytes;
+    const delay =
+      queue.length >= BATCH_SIZE
+        ? 0
+        : policy.delayFor(entry.level, pendingBytes);
+    if (delay === 0) flush(entry.at);
+    else if (timerAt === null) timerAt = entry.at + delay;
+  }
+  if (timerAt !== null) flush(timerAt);
+
+  return delivered;
+}
+
+function every(
+  stepMs: number,
+  from: number,
+  to: number,
+  level: ConsoleLogEntry["level"] = "info",
+  bytes = 200,
+): ScriptedEntry[] {
+  const entries: ScriptedEntry[] = [];
+  for (let at = from; at < to; at += stepMs) {
+    entries.push({ at, level, bytes });
+  }
+  return entries;
+}
+
+const byTime = (a: ScriptedEntry, b: ScriptedEntry) => a.at - b.at;
+
+describe("AdaptiveFlushPolicy", () => {
+  // A quiet minute, a five-second burst, then quiet again, with errors
+  // sprinkled through all three phases.
+  const urgentEntries: ScriptedEntry[] = [
+    { at: 30_500, level: "error", bytes: 300 },
+    { at: 62_001, level: "error", bytes: 300 },
+    { at: 90_250, level: "warn", bytes: 300 },
+  ];
+  const script = [
+    ...every(3_000, 0, 60_000),
+    ...every(5, 60_000, 65_000),
+    ...every(4_000, 65_000, 120_000),
+    ...urgentEntries,
+  ].sort(byTime);
+
+  it("delivers warnings and errors without waiting", () => {
+    const delivered = simulate(new AdaptiveFlushPolicy(), script);
+    const urgent = delivered.filter(
+      (d) => d.entry.level === "error" || d.entry.level === "warn",
+    );
+    expect(urgent).toHaveLength(3);
+    for (const delivery of urgent) expect(delivery.latencyMs).toBe(0);
+  });
+
+  it("bounds the latency of every entry by the max interval", () => {
+    const delivered = simulate(new AdaptiveFlushPolicy(), script);
+    expect(delivered).toHaveLength(script.length);
+    const worst = Math.max(...delivered.map((d) => d.latencyMs));
+    expect(worst).toBeLessThanOrEqual(DEFAULT_FLUSH_POLICY.maxIntervalMs);
+  });
+
+  it("stretches while idle and tightens during bursts", () => {
+    const policy = new AdaptiveFlushPolicy();
+    simulate(policy, every(3_000, 0, 60_000));
+    expect(policy.currentIntervalMs).toBe(DEFAULT_FLUSH_POLICY.maxIntervalMs);
+
+    simulate(policy, every(5, 60_000, 65_000));
+    expect(policy.currentIntervalMs).toBe(DEFAULT_FLUSH_POLICY.minIntervalMs);
+  });
+
+  it("flushes as soon as the byte threshold is reached", () => {
+    const policy = new AdaptiveFlushPolicy({
+      ...DEFAULT_FLUSH_POLICY,
+      byteThreshold: 1_000,
+    });
+    const delivered = simulate(policy, every(10, 0, 50, "info", 400));
+    expect(delivered[2]?.latencyMs).toBe(0);
+  });
+});
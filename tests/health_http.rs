@@ -0,0 +1,111 @@
+mod common;
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    run_self_test, Builder, DebugToolsConfig, DebugToolsState, HealthHttpConfig,
+};
+
+const TOKEN: &str = "health-test-token";
+
+fn get(addr: SocketAddr, path: &str) -> (u16, serde_json::Value) {
+    let mut stream = TcpStream::connect(addr).expect("connect");
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\n\r\n",
+        path, TOKEN
+    )
+    .expect("send request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("read response");
+    let (head, body) = response.split_once("\r\n\r\n").expect("response head");
+    let status = head
+        .split(' ')
+        .nth(1)
+        .expect("status")
+        .parse()
+        .expect("status code");
+    (status, serde_json::from_str(body).expect("json body"))
+}
+
+// Every file under `dir` with its size, to show polling wrote nothing. The
+// backend log is left out: setup's background threads are still logging.
+fn listing(dir: &Path) -> Vec<(String, u64)> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if !entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("rust_debug.log")
+            {
+                let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                files.push((path.display().to_string(), size));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[test]
+fn healthz_serves_the_last_self_test_without_writing() {
+    let config = DebugToolsConfig {
+        health_http: Some(HealthHttpConfig {
+            port: 0,
+            token: TOKEN.into(),
+        }),
+        ..common::config("health-http", "healthz")
+    };
+    let log_dir = config.log_dir.clone();
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let state = app.state::<DebugToolsState>();
+    let addr = state.health_server.get().expect("health server").addr();
+
+    let before = listing(&log_dir);
+    let (first_status, first) = get(addr, "/healthz");
+    let (_, second) = get(addr, "/healthz");
+    let after = listing(&log_dir);
+
+    // A client that connects and never sends its request must not hold up
+    // the next probe until its read times out.
+    let stalled = TcpStream::connect(addr).expect("connect stalled client");
+    let started = Instant::now();
+    let (status_beside_stalled, _) = get(addr, "/healthz");
+    let waited = started.elapsed();
+    drop(stalled);
+
+    let checks = tauri::async_runtime::block_on(run_self_test(app.handle().clone(), None))
+        .expect("self-test");
+    let (after_self_test_status, after_self_test) = get(addr, "/healthz");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(first_status, 200);
+    assert_eq!(first["checked_at"], serde_json::Value::Null);
+    assert_eq!(first["checks"], serde_json::json!([]));
+    assert_eq!(first, second);
+    assert_eq!(before, after, "polling /healthz changed the log directory");
+
+    assert_eq!(status_beside_stalled, 200);
+    assert!(waited < Duration::from_secs(1), "waited {:?}", waited);
+
+    let healthy = checks.iter().all(|check| check.passed);
+    assert_eq!(after_self_test_status, if healthy { 200 } else { 503 });
+    assert_eq!(after_self_test["healthy"], healthy);
+    assert!(after_self_test["checked_at"].is_i64());
+    assert_eq!(
+        after_self_test["checks"].as_array().map(Vec::len),
+        Some(checks.len())
+    );
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use tauri_plugin_debug_tools::read_image_dimensions;
+
+fn fixture(name: &str, bytes: &[u8]) -> PathBuf {
+    let dir = common::temp_dir("dimensions", "fixtures");
+    fs::create_dir_all(&dir).expect("create fixture directory");
+    let path = dir.join(name);
+    fs::write(&path, bytes).expect("write fixture");
+    path
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+    bytes
+}
+
+// An APP0 segment before the frame, as encoders write them.
+fn jpeg(width: u16, height: u16) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+    bytes.extend_from_slice(b"JFIF\0\x01\x01\x00\x00\x01\x00\x01\x00\x00");
+    bytes.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08]);
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&[0x03; 10]);
+    bytes
+}
+
+fn webp(chunk: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut bytes = b"RIFF\x00\x00\x00\x00WEBP".to_vec();
+    bytes.extend_from_slice(chunk);
+    bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+#[test]
+fn reads_dimensions_from_each_format_header() {
+    let vp8 = {
+        let mut payload = vec![0x50, 0x01, 0x00, 0x9D, 0x01, 0x2A];
+        payload.extend_from_slice(&640u16.to_le_bytes());
+        payload.extend_from_slice(&480u16.to_le_bytes());
+        webp(b"VP8 ", &payload)
+    };
+    let vp8l = {
+        let bits: u32 = (320 - 1) | ((200 - 1) << 14);
+        let mut payload = vec![0x2F];
+        payload.extend_from_slice(&bits.to_le_bytes());
+        payload.resize(10, 0);
+        webp(b"VP8L", &payload)
+    };
+    let vp8x = {
+        let mut payload = vec![0x10, 0, 0, 0];
+        payload.extend_from_slice(&(4000u32 - 1).to_le_bytes()[..3]);
+        payload.extend_from_slice(&(3000u32 - 1).to_le_bytes()[..3]);
+        webp(b"VP8X", &payload)
+    };
+
+    let cases = [
+        ("shot.png", png(1280, 720), (1280, 720)),
+        ("shot.jpg", jpeg(1024, 768), (1024, 768)),
+        ("lossy.webp", vp8, (640, 480)),
+        ("lossless.webp", vp8l, (320, 200)),
+        ("extended.webp", vp8x, (4000, 3000)),
+    ];
+    for (name, bytes, expected) in cases {
+        assert_eq!(
+            read_image_dimensions(&fixture(name, &bytes)),
+            Some(expected),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn truncated_or_foreign_headers_have_no_dimensions() {
+    let png = png(1280, 720);
+    let jpeg = jpeg(1024, 768);
+    let cases = [
+        ("truncated.png", png[..20].to_vec()),
+        ("signature-only.png", png[..8].to_vec()),
+        // Cut inside the start-of-frame segment.
+        ("truncated.jpg", jpeg[..jpeg.len() - 14].to_vec()),
+        ("no-frame.jpg", jpeg[..22].to_vec()),
+        ("truncated.webp", webp(b"VP8X", &[0x10, 0, 0, 0, 1])),
+        ("unknown-chunk.webp", webp(b"ALPH", &[0; 16])),
+        ("empty.png", Vec::new()),
+        ("text.png", b"not an image at all, just some text".to_vec()),
+    ];
+    for (name, bytes) in cases {
+        assert_eq!(
+            read_image_dimensions(&fixture(name, &bytes)),
+            None,
+            "{}",
+            name
+        );
+    }
+    assert_eq!(
+        read_image_dimensions(&common::temp_dir("dimensions", "missing").join("missing.png")),
+        None
+    );
+}
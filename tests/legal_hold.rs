@@ -0,0 +1,262 @@
+mod common;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tauri_plugin_debug_tools::{
+    apply_retention, clear_debug_log_files, set_legal_hold, ArtifactKind, Builder,
+    DebugToolsConfig, LegalHolds, OwnedFiles, RetentionPolicy,
+};
+
+const APP: &str = "Hold Test";
+const NOW_SECS: i64 = 1_700_000_000;
+const DAY: u64 = 24 * 3600;
+
+fn write(path: &Path, contents: &str, owned: &OwnedFiles) -> PathBuf {
+    fs::write(path, contents).expect("write artifact");
+    owned.register(path);
+    path.to_path_buf()
+}
+
+fn age_file(path: &Path, age: Duration) {
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .expect("open artifact")
+        .set_modified(SystemTime::now() - age)
+        .expect("set mtime");
+}
+
+// A snapshot `age_days` before `NOW_SECS`, with the screenshot it references.
+fn snapshot(
+    config: &DebugToolsConfig,
+    owned: &Arc<OwnedFiles>,
+    age_days: u64,
+    label: &str,
+    session_id: &str,
+) -> (PathBuf, PathBuf) {
+    let timestamp = NOW_SECS - (age_days * DAY) as i64;
+    let screenshot = write(
+        &config
+            .screenshot_dir()
+            .join(format!("{}_main.png", timestamp)),
+        "png",
+        owned,
+    );
+    let path = common::save_snapshot(
+        config,
+        owned,
+        serde_json::json!({
+            "timestamp": timestamp,
+            "screenshot_path": screenshot,
+            "label": label,
+            "session_id": session_id,
+        }),
+    );
+    (path, screenshot)
+}
+
+fn compliance_policy() -> RetentionPolicy {
+    RetentionPolicy {
+        ttl_secs: HashMap::from([
+            (ArtifactKind::ConsoleLog, 7 * DAY),
+            (ArtifactKind::Snapshot, 7 * DAY),
+            (ArtifactKind::CrashSnapshot, 90 * DAY),
+        ]),
+        ..RetentionPolicy::default()
+    }
+}
+
+#[test]
+fn each_kind_expires_after_its_own_ttl() {
+    let config = common::config("legal-hold", "ttl");
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let old_manual = snapshot(&config, &owned, 8, "manual", "s1");
+    let old_crash = snapshot(&config, &owned, 30, "crash", "s1");
+    let ancient_crash = snapshot(&config, &owned, 91, "crash", "s1");
+    let old_console = write(&config.frontend_log_path(APP, 1), "{}\n", &owned);
+    age_file(&old_console, Duration::from_secs(8 * DAY));
+    let new_console = write(&config.frontend_log_path(APP, 2), "{}\n", &owned);
+    age_file(&new_console, Duration::from_secs(6 * DAY));
+    // No network TTL is configured, so network logs are never swept.
+    let network = write(
+        &config.log_dir.join("network_Hold_Test_1.jsonl"),
+        "{}\n",
+        &owned,
+    );
+    age_file(&network, Duration::from_secs(365 * DAY));
+
+    let report = apply_retention(
+        &config,
+        &compliance_policy(),
+        &LegalHolds::default(),
+        &owned,
+        NOW_SECS,
+        &HashSet::new(),
+    )
+    .expect("apply retention");
+    let exists = |path: &PathBuf| path.exists();
+    let remaining = (
+        [&old_manual.0, &ancient_crash.0, &old_console].map(exists),
+        [&old_crash.0, &old_crash.1, &new_console, &network].map(exists),
+    );
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let mut pruned = report.pruned_snapshots.clone();
+    pruned.sort();
+    let mut expected = vec![old_manual.0, ancient_crash.0];
+    expected.sort();
+    assert_eq!(pruned, expected);
+    assert_eq!(report.expired_logs, [old_console]);
+    assert!(report.held_paths.is_empty());
+    assert_eq!(remaining, ([false; 3], [true; 4]));
+}
+
+// A crash snapshot saved a moment ago, named as captures name it, sits far
+// inside a 90-day TTL; the age sweep must not mistake its seconds for an
+// ancient millisecond timestamp.
+#[test]
+fn a_fresh_crash_snapshot_survives_a_long_ttl() {
+    let config = common::config("legal-hold", "fresh-crash");
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let crash = snapshot(&config, &owned, 0, "crash", "s1");
+    let manual = snapshot(&config, &owned, 1, "manual", "s1");
+
+    let report = apply_retention(
+        &config,
+        &compliance_policy(),
+        &LegalHolds::default(),
+        &owned,
+        NOW_SECS,
+        &HashSet::new(),
+    )
+    .expect("apply retention");
+    let remaining = [&crash.0, &crash.1, &manual.0].map(|path| path.exists());
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert!(report.pruned_snapshots.is_empty());
+    assert_eq!(report.retained_snapshots, 2);
+    assert_eq!(remaining, [true; 3]);
+}
+
+#[test]
+fn held_paths_and_sessions_survive_the_age_sweep() {
+    let config = common::config("legal-hold", "sweep");
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let by_path = snapshot(&config, &owned, 10, "manual", "s1");
+    let by_session = snapshot(&config, &owned, 11, "manual", "investigation");
+    let unheld = snapshot(&config, &owned, 12, "manual", "s1");
+    let console = write(&config.frontend_log_path(APP, 1), "{}\n", &owned);
+    age_file(&console, Duration::from_secs(8 * DAY));
+    let holds = LegalHolds {
+        paths: BTreeSet::from([by_path.0.clone(), console.clone()]),
+        sessions: BTreeSet::from(["investigation".to_string()]),
+    };
+
+    let report = apply_retention(
+        &config,
+        &compliance_policy(),
+        &holds,
+        &owned,
+        NOW_SECS,
+        &HashSet::new(),
+    )
+    .expect("apply retention");
+    let exists = |path: &PathBuf| path.exists();
+    let remaining = (
+        [
+            &by_path.0,
+            &by_path.1,
+            &by_session.0,
+            &by_session.1,
+            &console,
+        ]
+        .map(exists),
+        [&unheld.0, &unheld.1].map(exists),
+    );
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let mut held = report.held_paths.clone();
+    held.sort();
+    let mut expected = vec![by_path.0, by_session.0, console];
+    expected.sort();
+    assert_eq!(held, expected);
+    assert_eq!(report.pruned_snapshots, [unheld.0]);
+    assert_eq!(report.retained_snapshots, 2);
+    assert_eq!(remaining, ([true; 5], [false; 2]));
+}
+
+#[test]
+fn clearing_logs_leaves_held_files_and_reports_them() {
+    let config = common::config("legal-hold", "clear");
+    let owned = OwnedFiles::load(&config);
+    let held = write(&config.frontend_log_path(APP, 1), "{}\n", &owned);
+    let cleared = write(&config.frontend_log_path(APP, 2), "{}\n", &owned);
+    let screenshot = write(&config.screenshot_dir().join("1_main.png"), "png", &owned);
+    let holds = LegalHolds {
+        paths: BTreeSet::from([held.clone(), screenshot.clone()]),
+        ..LegalHolds::default()
+    };
+
+    let report = clear_debug_log_files(&config, APP, &holds, &owned).expect("clear");
+    let remaining = [&held, &cleared, &screenshot].map(|path| path.exists());
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let mut reported = report.held_paths.clone();
+    reported.sort();
+    let mut expected = vec![held, screenshot];
+    expected.sort();
+    assert_eq!(reported, expected);
+    assert!(report.deleted_paths.contains(&cleared));
+    assert_eq!(remaining, [true, false, true]);
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn set_legal_hold_records_holds_in_holds_json() {
+    let config = common::config("legal-hold", "command");
+    let log_dir = config.log_dir.clone();
+    let holds_file = config.legal_holds_path();
+    let snapshot_path = config.snapshot_path(1_000);
+    fs::write(&snapshot_path, "{}").expect("write snapshot");
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let set = |paths: Option<Vec<String>>, session_id: Option<&str>, held: bool| {
+        tauri::async_runtime::block_on(set_legal_hold(
+            app.handle().clone(),
+            paths,
+            session_id.map(str::to_string),
+            held,
+        ))
+    };
+    let on_disk = || -> LegalHolds {
+        serde_json::from_slice(&fs::read(&holds_file).expect("read holds.json"))
+            .expect("holds.json")
+    };
+
+    let missing = set(None, None, true);
+    let outside = set(Some(vec!["/etc/passwd".into()]), None, true);
+    let held = set(
+        Some(vec![snapshot_path.to_string_lossy().into_owned()]),
+        Some("investigation"),
+        true,
+    )
+    .expect("hold");
+    let after_hold = on_disk();
+    let released = set(None, Some("investigation"), false).expect("release session");
+    let after_release = on_disk();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(missing.is_err());
+    assert!(outside.is_err());
+    assert_eq!(held.sessions, BTreeSet::from(["investigation".to_string()]));
+    assert_eq!(held.paths.len(), 1);
+    assert_eq!(after_hold, held);
+    assert!(released.sessions.is_empty());
+    assert_eq!(released.paths, held.paths);
+    assert_eq!(after_release, released);
+}
@@ -0,0 +1,232 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri_plugin_debug_tools::analysis::merge::merge_bundles;
+use tauri_plugin_debug_tools::{
+    annotate_log_entry, append_debug_logs, export_encrypted_bundle, export_filtered_logs,
+    import_encrypted_bundle, list_log_annotations, query_console_logs, AnnotationError, Builder,
+    ConsoleLogEntryPayload, DebugToolsConfig, ExportFilteredLogsRequest, FixedClock,
+};
+
+const NOW_MS: i64 = 1_700_000_000_000;
+const PASSPHRASE: &str = "annotations travel";
+
+fn entry(offset_ms: i64, level: &str, message: &str) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp: NOW_MS + offset_ms,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    }
+}
+
+fn console_logs(dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .expect("read console log directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("frontend_console_"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn annotations_are_stored_aside_and_follow_entries_everywhere() {
+    let base = common::temp_dir("annotations", "app");
+    let log_dir = base.join("logs");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        // The 500 entries below pass the default size cap; annotations name
+        // seqs in the live file, so it must not rotate.
+        max_log_size_bytes: 0,
+        ..DebugToolsConfig::default()
+    };
+    let console_dir = config.console_log_dir();
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(Arc::new(FixedClock::new(NOW_MS).with_step(1)))
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+    let annotate = |seq: u64, note: &str, tag: Option<&str>| {
+        tauri::async_runtime::block_on(annotate_log_entry(
+            handle(),
+            seq,
+            note.to_string(),
+            tag.map(str::to_string),
+        ))
+    };
+
+    tauri::async_runtime::block_on(append_debug_logs(
+        handle(),
+        vec![
+            entry(0, "info", "cart opened"),
+            entry(100, "error", "payment failed"),
+            entry(200, "info", "retry scheduled"),
+        ],
+        None,
+        None,
+        None,
+        None,
+    ))
+    .expect("append");
+    let logs = console_logs(&console_dir);
+    let original = fs::read(&logs[0]).expect("read console log");
+
+    let first = annotate(2, "first look", None).expect("annotate");
+    let replaced =
+        annotate(2, "caused by the expired card", Some("  relevant ")).expect("re-annotate");
+    let unknown = annotate(99, "nothing here", None);
+    let too_long = annotate(1, &"x".repeat(2_001), None);
+    let long_tag = annotate(1, "ok", Some(&"t".repeat(65)));
+
+    let all = tauri::async_runtime::block_on(list_log_annotations(handle(), None)).expect("list");
+    let since = tauri::async_runtime::block_on(list_log_annotations(
+        handle(),
+        Some(replaced.annotated_at + 1),
+    ))
+    .expect("list since");
+
+    let found = tauri::async_runtime::block_on(query_console_logs(handle(), None)).expect("query");
+    let report: ExportFilteredLogsRequest = serde_json::from_value(serde_json::json!({
+        "output_name": "triage",
+        "format": "html",
+    }))
+    .expect("request");
+    let report = tauri::async_runtime::block_on(export_filtered_logs(handle(), report))
+        .expect("html export");
+    let html = fs::read_to_string(&report.path).expect("read report");
+
+    let export =
+        tauri::async_runtime::block_on(export_encrypted_bundle(handle(), PASSPHRASE.into()))
+            .expect("export bundle");
+    let imported = tauri::async_runtime::block_on(import_encrypted_bundle(
+        handle(),
+        export.path.to_string_lossy().into_owned(),
+        PASSPHRASE.into(),
+        None,
+    ))
+    .expect("import bundle");
+    let merged =
+        merge_bundles(std::slice::from_ref(&imported.path), &base.join("merged")).expect("merge");
+    let timeline: Vec<serde_json::Value> = fs::read_to_string(&merged.timeline_path)
+        .expect("read timeline")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("timeline line"))
+        .collect();
+    // Annotating never rewrote the log.
+    let unchanged = fs::read(&logs[0]).expect("reread console log") == original;
+
+    // Seqs 1 to 3 are taken; 2 is annotated, so 499 more reach the cap.
+    for batch in 0..5 {
+        tauri::async_runtime::block_on(append_debug_logs(
+            handle(),
+            (0..100)
+                .map(|i| entry(300 + batch * 100 + i, "info", "tick"))
+                .collect(),
+            None,
+            None,
+            None,
+            None,
+        ))
+        .expect("append more");
+    }
+    for seq in 4..503 {
+        annotate(seq, "seen", None).expect("annotate up to the cap");
+    }
+    let over_cap = annotate(503, "one too many", None);
+    let replaced_at_cap = annotate(2, "still replaceable", None);
+
+    let annotation_files: Vec<PathBuf> = fs::read_dir(&log_dir)
+        .expect("read log directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("annotations_"))
+        })
+        .collect();
+    let _ = fs::remove_dir_all(&base);
+
+    assert!(unchanged);
+    assert_eq!(annotation_files.len(), 1, "{:?}", annotation_files);
+    assert_eq!(first.seq, 2);
+    assert_eq!(replaced.tag.as_deref(), Some("relevant"));
+    assert_eq!(
+        replaced.log_file,
+        logs[0].file_name().unwrap().to_str().unwrap()
+    );
+
+    assert!(matches!(
+        unknown,
+        Err(AnnotationError::UnknownSeq { seq: 99 })
+    ));
+    assert_eq!(
+        serde_json::to_value(unknown.unwrap_err()).unwrap()["code"],
+        "unknown_seq"
+    );
+    assert!(matches!(
+        too_long,
+        Err(AnnotationError::NoteTooLong {
+            chars: 2_001,
+            max: 2_000
+        })
+    ));
+    assert!(matches!(long_tag, Err(AnnotationError::TagTooLong { .. })));
+
+    // One annotation per seq: the latest.
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].note, "caused by the expired card");
+    assert!(since.is_empty());
+
+    let annotated: Vec<&str> = found
+        .iter()
+        .filter(|entry| {
+            entry
+                .fields
+                .as_ref()
+                .is_some_and(|fields| fields.contains_key("annotation"))
+        })
+        .map(|entry| entry.message.as_str())
+        .collect();
+    assert_eq!(annotated, ["payment failed"]);
+
+    assert!(html.contains("level-error annotated"), "{}", html);
+    assert!(
+        html.contains("<div class=\"annotation\"><b>relevant</b>caused by the expired card</div>")
+    );
+    assert_eq!(html.matches(" annotated\"").count(), 1);
+
+    // The bundle carries the annotation file, and merging reattaches it.
+    assert_eq!(merged.bundles[0].annotated_entries, 1, "{:?}", merged);
+    let notes: Vec<&serde_json::Value> = timeline
+        .iter()
+        .filter_map(|line| line.pointer("/fields/annotation/note"))
+        .collect();
+    assert_eq!(notes, [&serde_json::json!("caused by the expired card")]);
+
+    assert!(matches!(
+        over_cap,
+        Err(AnnotationError::LimitReached { max: 500 })
+    ));
+    assert_eq!(
+        replaced_at_cap.expect("replace at cap").note,
+        "still replaceable"
+    );
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, export_filtered_logs, Builder, ConsoleLogEntryPayload, DebugToolsConfig,
+    ExportFilteredLogsRequest,
+};
+
+fn entry(
+    timestamp: i64,
+    level: &str,
+    message: &str,
+    fields: serde_json::Value,
+) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: fields.as_object().cloned(),
+        seq: None,
+    }
+}
+
+fn request(output_name: &str, format: &str) -> ExportFilteredLogsRequest {
+    serde_json::from_value(serde_json::json!({
+        "output_name": output_name,
+        "format": format,
+        "pattern": "order",
+    }))
+    .expect("request")
+}
+
+#[test]
+fn csv_and_timeline_exports_flatten_the_configured_fields() {
+    let log_dir = common::temp_dir("export-columns", "app");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        export_field_columns: vec!["userId".into(), "attempt".into()],
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+
+    tauri::async_runtime::block_on(append_debug_logs(
+        handle(),
+        vec![
+            entry(
+                1_700_000_000_000,
+                "info",
+                "Loaded orders",
+                serde_json::json!({"userId": "u-1", "route": "/orders"}),
+            ),
+            entry(
+                1_700_000_000_250,
+                "error",
+                "Order \"42\" failed, retrying",
+                serde_json::json!({"userId": "u-2", "attempt": 2}),
+            ),
+            entry(
+                1_700_000_000_500,
+                "info",
+                "unrelated",
+                serde_json::json!({}),
+            ),
+        ],
+        None,
+        None,
+        None,
+        None,
+    ))
+    .expect("append");
+
+    let csv =
+        tauri::async_runtime::block_on(export_filtered_logs(handle(), request("orders", "csv")))
+            .expect("csv export");
+    assert_eq!(csv.entries, 2);
+    assert!(csv.path.ends_with("orders.csv"));
+    let csv = fs::read_to_string(&csv.path).expect("read csv");
+    assert_eq!(
+        csv.lines().collect::<Vec<_>>(),
+        [
+            "timestamp,level,message,stack_trace,userId,attempt",
+            "1700000000000,info,Loaded orders,,u-1,",
+            "1700000000250,error,\"Order \"\"42\"\" failed, retrying\",,u-2,2",
+        ]
+    );
+
+    let timeline = tauri::async_runtime::block_on(export_filtered_logs(
+        handle(),
+        request("orders", "timeline"),
+    ))
+    .expect("timeline export");
+    assert!(timeline.path.ends_with("orders.json"));
+    let trace: serde_json::Value =
+        serde_json::from_slice(&fs::read(&timeline.path).expect("read timeline"))
+            .expect("timeline is valid JSON");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    let events = trace["traceEvents"].as_array().expect("trace events");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["name"], "Loaded orders");
+    assert_eq!(events[0]["ts"], 1_700_000_000_000_000i64);
+    assert_eq!(events[0]["args"], serde_json::json!({"userId": "u-1"}));
+    assert_eq!(events[1]["cat"], "error");
+    assert_eq!(
+        events[1]["args"],
+        serde_json::json!({"userId": "u-2", "attempt": "2"})
+    );
+}
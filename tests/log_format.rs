@@ -0,0 +1,41 @@
+mod common;
+
+use std::fs;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_debug_tools::{Builder, DebugToolsConfig, DebugToolsState, LogFormat};
+
+#[test]
+fn text_log_format_writes_plain_backend_lines() {
+    let config = DebugToolsConfig {
+        log_format: LogFormat::Text,
+        ..common::config("log-format", "text")
+    };
+    let log_dir = config.log_dir.clone();
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let state = app.state::<DebugToolsState>();
+    let backend_log = state.config.backend_log_path();
+
+    tracing::info!("plain text event");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut text = String::new();
+    while !text.contains("plain text event") && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+        text = fs::read_to_string(&backend_log).unwrap_or_default();
+    }
+    let owned = state.owned_files.is_owned(&backend_log);
+    let _ = fs::remove_dir_all(&log_dir);
+
+    let line = text
+        .lines()
+        .find(|line| line.contains("plain text event"))
+        .expect("event in rust_debug.log");
+    assert!(serde_json::from_str::<serde_json::Value>(line).is_err());
+    assert!(line.contains(" INFO "), "{}", line);
+    assert!(!line.contains('\u{1b}'), "ANSI escapes in {}", line);
+    // Cleanup still recognizes the file as the plugin's.
+    assert!(owned);
+}
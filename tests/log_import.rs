@@ -0,0 +1,46 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{import_console_logs, Builder};
+
+const ENTRY: &str = r#"{"timestamp":1700000000000,"level":"info","message":"imported","args":[]}"#;
+
+#[test]
+fn import_reads_only_files_inside_the_log_directory() {
+    let config = common::config("log-import", "paths");
+    let log_dir = config.log_dir.clone();
+    let inside = config.bundle_import_dir().join("cli.jsonl");
+    fs::create_dir_all(inside.parent().expect("imports dir")).expect("create imports dir");
+    fs::write(&inside, format!("{}\n", ENTRY)).expect("write import");
+    let outside = common::temp_dir("log-import", "outside").with_extension("jsonl");
+    fs::write(&outside, format!("{}\n", ENTRY)).expect("write outside file");
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let import = |path: &std::path::Path| {
+        tauri::async_runtime::block_on(import_console_logs(
+            app.handle().clone(),
+            path.display().to_string(),
+            "cli".into(),
+        ))
+    };
+
+    let imported = import(&inside);
+    let rejected = import(&outside);
+    let escaped = import(
+        &log_dir
+            .join("imports/../../")
+            .join(outside.file_name().unwrap()),
+    );
+    let _ = fs::remove_dir_all(&log_dir);
+    let _ = fs::remove_file(&outside);
+
+    assert_eq!(imported.expect("import inside log dir").imported, 1);
+    assert!(
+        rejected.is_err(),
+        "imported a file outside the log directory"
+    );
+    assert!(escaped.is_err(), "followed `..` out of the log directory");
+}
@@ -0,0 +1,131 @@
+use tauri_plugin_debug_tools::{ConsoleLogEntry, LogLevel, LogQuery};
+
+// Spellings found in console bridges, tracing output, syslog and
+// pino/winston-style JSONL, in the case variants they appear in.
+const ALIASES: &[(&str, LogLevel)] = &[
+    ("trace", LogLevel::Trace),
+    ("TRACE", LogLevel::Trace),
+    ("verbose", LogLevel::Trace),
+    ("Verbose", LogLevel::Trace),
+    ("fine", LogLevel::Trace),
+    ("debug", LogLevel::Debug),
+    ("DEBUG", LogLevel::Debug),
+    ("Debug", LogLevel::Debug),
+    ("dbg", LogLevel::Debug),
+    ("info", LogLevel::Info),
+    ("INFO", LogLevel::Info),
+    ("Info", LogLevel::Info),
+    ("log", LogLevel::Info),
+    ("information", LogLevel::Info),
+    ("Information", LogLevel::Info),
+    ("notice", LogLevel::Info),
+    ("warn", LogLevel::Warn),
+    ("WARN", LogLevel::Warn),
+    ("Warn", LogLevel::Warn),
+    ("warning", LogLevel::Warn),
+    ("Warning", LogLevel::Warn),
+    ("WARNING", LogLevel::Warn),
+    ("error", LogLevel::Error),
+    ("ERROR", LogLevel::Error),
+    ("Error", LogLevel::Error),
+    ("err", LogLevel::Error),
+    ("fatal", LogLevel::Error),
+    ("FATAL", LogLevel::Error),
+    ("critical", LogLevel::Error),
+    ("CRITICAL", LogLevel::Error),
+    ("crit", LogLevel::Error),
+    ("panic", LogLevel::Error),
+    ("alert", LogLevel::Error),
+    ("emergency", LogLevel::Error),
+    ("emerg", LogLevel::Error),
+    (" warn ", LogLevel::Warn),
+];
+
+const LEVELS: [LogLevel; 5] = [
+    LogLevel::Trace,
+    LogLevel::Debug,
+    LogLevel::Info,
+    LogLevel::Warn,
+    LogLevel::Error,
+];
+
+fn entry(level: &str) -> ConsoleLogEntry {
+    ConsoleLogEntry {
+        timestamp: 0,
+        level: level.to_string(),
+        message: String::new(),
+        args: serde_json::Value::Null,
+        stack_trace: None,
+        fields: None,
+    }
+}
+
+#[test]
+fn every_alias_parses_and_deserializes() {
+    for (spelling, level) in ALIASES {
+        assert_eq!(
+            LogLevel::from_str_lenient(spelling),
+            Some(*level),
+            "{:?}",
+            spelling
+        );
+        let parsed: LogLevel = serde_json::from_value(serde_json::json!(spelling)).expect(spelling);
+        assert_eq!(parsed, *level, "{:?}", spelling);
+    }
+    for unknown in ["", "loud", "warnings", "5"] {
+        assert_eq!(LogLevel::from_str_lenient(unknown), None, "{:?}", unknown);
+        assert!(serde_json::from_value::<LogLevel>(serde_json::json!(unknown)).is_err());
+    }
+}
+
+#[test]
+fn canonical_spelling_round_trips() {
+    for level in LEVELS {
+        let serialized = serde_json::to_value(level).expect("serialize");
+        assert_eq!(serialized, serde_json::json!(level.as_str()));
+        assert_eq!(level.to_string(), level.as_str());
+        assert_eq!(LogLevel::from_str_lenient(level.as_str()), Some(level));
+        assert_eq!(LogLevel::from(tracing::Level::from(level)), level);
+        assert_eq!(LogLevel::from(log::Level::from(level)), level);
+        let filter = log::LevelFilter::from(level);
+        assert_eq!(filter.as_str().to_ascii_lowercase(), level.as_str());
+        assert_eq!(filter.to_level().map(LogLevel::from), Some(level));
+    }
+    assert!(LEVELS.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(log::LevelFilter::Off.to_level().map(LogLevel::from), None);
+}
+
+// Both crates order by severity, though in opposite directions: `log` puts
+// `Error` lowest. A `>=` filter on `LogLevel` is a `<=` filter in `log`.
+#[test]
+fn log_filters_keep_the_same_levels() {
+    for min in LEVELS {
+        let filter = log::LevelFilter::from(min);
+        for level in LEVELS {
+            assert_eq!(
+                log::Level::from(level) <= filter,
+                level >= min,
+                "{:?} with minimum {:?}",
+                level,
+                min
+            );
+        }
+    }
+}
+
+#[test]
+fn min_level_filter_accepts_aliases_at_or_above() {
+    let query = LogQuery {
+        min_level: Some(LogLevel::Warn),
+        ..LogQuery::default()
+    };
+    for (spelling, level) in ALIASES {
+        assert_eq!(
+            query.matches(&entry(spelling)),
+            *level >= LogLevel::Warn,
+            "{:?}",
+            spelling
+        );
+    }
+    assert!(!query.matches(&entry("loud")));
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri_plugin_debug_tools::{
+    tail_console_log, ConsoleLogEntry, DebugToolsConfig, FileSystemLogQueryService,
+    InMemoryLogQueryService, LogLevel, LogQuery, LogQueryService,
+};
+
+const APP: &str = "equivalence";
+const PID: u32 = 4242;
+
+fn line(timestamp: i64, level: &str, message: &str, fields: Option<serde_json::Value>) -> String {
+    let mut entry = serde_json::json!({
+        "timestamp": timestamp,
+        "level": level,
+        "message": message,
+        "args": [],
+        "stack_trace": null,
+    });
+    if let Some(fields) = fields {
+        entry["fields"] = fields;
+    }
+    entry.to_string()
+}
+
+// A session log as the plugin writes it, with a blank line, a malformed line
+// and a torn final write mixed in.
+fn write_fixture(name: &str) -> (DebugToolsConfig, PathBuf) {
+    let config = DebugToolsConfig {
+        log_dir: common::temp_dir("log-query", name),
+        ..DebugToolsConfig::default()
+    };
+    let path = config.frontend_log_path(APP, PID);
+    fs::create_dir_all(path.parent().unwrap()).expect("create log directory");
+
+    let lines = [
+        line(1_000, "debug", "boot", None),
+        line(
+            1_100,
+            "info",
+            "Loaded orders",
+            Some(serde_json::json!({"route": "/orders"})),
+        ),
+        String::new(),
+        line(
+            1_200,
+            "warn",
+            "Slow response",
+            Some(serde_json::json!({"route": "/orders"})),
+        ),
+        "not json".to_string(),
+        line(
+            1_200,
+            "error",
+            "Order fetch failed",
+            Some(serde_json::json!({"route": "/orders"})),
+        ),
+        line(
+            2_500,
+            "log",
+            "ORDERS refreshed",
+            Some(serde_json::json!({"route": "/cart"})),
+        ),
+        line(3_000, "error", "Cart failed", None),
+        r#"{"timestamp":3100,"lev"#.to_string(),
+    ];
+    fs::write(&path, lines.join("\n")).expect("write log");
+    (config, path)
+}
+
+fn json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("serialize")
+}
+
+fn all_entries(path: &std::path::Path) -> Vec<ConsoleLogEntry> {
+    tail_console_log(path, usize::MAX, 0)
+        .expect("read tail")
+        .entries
+}
+
+#[test]
+fn filesystem_queries_match_the_console_tail_reader() {
+    let (config, path) = write_fixture("tail");
+    let service = FileSystemLogQueryService::new(Arc::new(config.clone()), APP.into(), PID);
+
+    let tail = tail_console_log(&path, usize::MAX, 0).expect("read tail");
+    assert_eq!(tail.total, 6);
+    assert_eq!(tail.malformed_lines, 2);
+
+    for limit in [0, 1, 3, 6, 10] {
+        let queried = service
+            .query_console_logs(&LogQuery {
+                limit: Some(limit),
+                ..LogQuery::default()
+            })
+            .expect("query");
+        let tailed = tail_console_log(&path, limit, 0).expect("tail").entries;
+        assert_eq!(json(&queried), json(&tailed), "limit {}", limit);
+    }
+
+    let _ = fs::remove_dir_all(&config.log_dir);
+}
+
+#[test]
+fn filesystem_queries_match_the_in_memory_service() {
+    let (config, path) = write_fixture("memory");
+    let filesystem = FileSystemLogQueryService::new(Arc::new(config.clone()), APP.into(), PID);
+    let memory = InMemoryLogQueryService::new(all_entries(&path));
+
+    let mut route = serde_json::Map::new();
+    route.insert("route".into(), "/orders".into());
+    let queries = [
+        LogQuery::default(),
+        LogQuery {
+            min_level: Some(LogLevel::Warn),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            text: Some("orders".into()),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            since: Some(1_100),
+            until: Some(2_500),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            field_equals: route,
+            limit: Some(2),
+            ..LogQuery::default()
+        },
+    ];
+
+    for query in &queries {
+        assert_eq!(
+            json(&filesystem.query_console_logs(query).unwrap()),
+            json(&memory.query_console_logs(query).unwrap()),
+            "{:?}",
+            query
+        );
+        assert_eq!(
+            json(&filesystem.histogram(500, query).unwrap()),
+            json(&memory.histogram(500, query).unwrap()),
+            "{:?}",
+            query
+        );
+        assert_eq!(
+            json(&filesystem.count_by_field("route", query).unwrap()),
+            json(&memory.count_by_field("route", query).unwrap()),
+            "{:?}",
+            query
+        );
+    }
+
+    assert_eq!(
+        json(&filesystem.query_errors(10).unwrap()),
+        json(&memory.query_errors(10).unwrap())
+    );
+    assert_eq!(
+        json(&filesystem.timeline(1_200, 3_000).unwrap()),
+        json(&memory.timeline(1_200, 3_000).unwrap())
+    );
+
+    let _ = fs::remove_dir_all(&config.log_dir);
+}
+
+#[test]
+fn a_missing_log_reads_as_empty_in_every_reader() {
+    let config = DebugToolsConfig {
+        log_dir: common::temp_dir("log-query", "missing"),
+        ..DebugToolsConfig::default()
+    };
+    let path = config.frontend_log_path(APP, PID);
+    let service = FileSystemLogQueryService::new(Arc::new(config), APP.into(), PID);
+
+    assert!(service
+        .query_console_logs(&LogQuery::default())
+        .unwrap()
+        .is_empty());
+    assert!(service
+        .histogram(500, &LogQuery::default())
+        .unwrap()
+        .is_empty());
+    assert!(tail_console_log(&path, 10, 0).unwrap().entries.is_empty());
+}
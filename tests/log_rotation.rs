@@ -0,0 +1,166 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, Builder, ConsoleLogEntryPayload, DebugToolsState,
+};
+
+const MAX_BYTES: u64 = 4_000;
+const ENTRIES: usize = 60;
+const BATCH: usize = 3;
+
+fn entry(i: usize) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp: 1_700_000_000_000 + i as i64,
+        level: "info".to_string(),
+        message: format!("entry-{}", i),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    }
+}
+
+// Files in `dir` starting with `prefix`, rotated copies by number and then
+// the live file, oldest first.
+fn oldest_first(dir: &Path, prefix: &str, live: &Path) -> Vec<PathBuf> {
+    let mut rotated: Vec<(u64, PathBuf)> = fs::read_dir(dir)
+        .expect("read directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path != live)
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let n = name
+                .strip_prefix(prefix)?
+                .trim_end_matches(".jsonl")
+                .parse()
+                .ok()?;
+            Some((n, path))
+        })
+        .collect();
+    rotated.sort();
+    let mut files: Vec<PathBuf> = rotated.into_iter().map(|(_, path)| path).collect();
+    files.push(live.to_path_buf());
+    files
+}
+
+fn lines(path: &Path) -> Vec<serde_json::Value> {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("read {}: {}", path.display(), e))
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("JSON line"))
+        .collect()
+}
+
+#[test]
+fn console_and_backend_logs_rotate_at_max_file_size() {
+    let config = common::config("log-rotation", "cap");
+    let log_dir = config.log_dir.clone();
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .with_stdout(false)
+                .max_file_size(MAX_BYTES.into())
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let state = app.state::<DebugToolsState>();
+
+    for start in (0..ENTRIES).step_by(BATCH) {
+        tauri::async_runtime::block_on(append_debug_logs(
+            app.handle().clone(),
+            (start..start + BATCH).map(entry).collect(),
+            None,
+            None,
+            None,
+            None,
+        ))
+        .expect("append");
+    }
+    let live_console = state
+        .config
+        .frontend_log_path(&app.package_info().name, std::process::id());
+    let console_prefix = format!(
+        "{}.",
+        live_console
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+    );
+    let console_files = oldest_first(
+        &state.config.console_log_dir(),
+        &console_prefix,
+        &live_console,
+    );
+
+    // The plugin logs at debug level; events from here make sure there is
+    // enough to rotate even if it is quiet.
+    let live_backend = state.config.backend_log_path();
+    let backend_prefix = format!(
+        "{}.",
+        live_backend
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+    );
+    let first_rotated_backend = live_backend.with_file_name(format!("{}1", backend_prefix));
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !first_rotated_backend.exists() && Instant::now() < deadline {
+        tracing::info!(padding = %"x".repeat(200), "filling the backend log");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let backend_files = oldest_first(
+        live_backend.parent().expect("backend log directory"),
+        &backend_prefix,
+        &live_backend,
+    );
+
+    let console_sizes: Vec<u64> = console_files
+        .iter()
+        .map(|path| fs::metadata(path).expect("console log").len())
+        .collect();
+    let messages: Vec<String> = console_files
+        .iter()
+        .flat_map(|path| lines(path))
+        .map(|line| line["message"].as_str().unwrap_or_default().to_string())
+        .collect();
+    let console_owned = console_files
+        .iter()
+        .all(|path| state.owned_files.is_owned(path));
+    let backend_sizes: Vec<u64> = backend_files
+        .iter()
+        .map(|path| fs::metadata(path).map(|meta| meta.len()).unwrap_or(0))
+        .collect();
+    let backend_first_lines_parse = backend_files[..backend_files.len() - 1].iter().all(|path| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| text.lines().next().map(str::to_string))
+            .is_some_and(|line| serde_json::from_str::<serde_json::Value>(&line).is_ok())
+    });
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(console_files.len() > 2, "{:?}", console_files);
+    assert!(
+        console_sizes.iter().all(|size| *size <= MAX_BYTES),
+        "{:?}",
+        console_sizes
+    );
+    // Nothing is lost or reordered across the rotated copies.
+    let expected: Vec<String> = (0..ENTRIES).map(|i| format!("entry-{}", i)).collect();
+    assert_eq!(messages, expected);
+    assert!(console_owned, "rotated console logs are not owned");
+
+    assert!(backend_files.len() > 1, "{:?}", backend_files);
+    assert!(
+        backend_sizes.iter().all(|size| *size <= MAX_BYTES),
+        "{:?}",
+        backend_sizes
+    );
+    assert!(backend_first_lines_parse);
+}
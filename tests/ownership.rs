@@ -0,0 +1,208 @@
+mod common;
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri_plugin_debug_tools::{
+    apply_retention, clear_debug_log_files, clear_debug_log_files_with_secure_delete, ArtifactKind,
+    DebugToolsConfig, LegalHolds, OwnedFiles, RetentionPolicy,
+};
+
+const APP: &str = "Ownership Test";
+const PID: u32 = 4242;
+
+struct Fixture {
+    config: DebugToolsConfig,
+    owned: OwnedFiles,
+    plugin_files: Vec<PathBuf>,
+    foreign_files: Vec<PathBuf>,
+}
+
+impl Fixture {
+    // A log directory holding, in each directory cleanup manages, one file
+    // the plugin wrote and one another tool wrote under a plugin-like name.
+    fn new(name: &str) -> Self {
+        let config = common::config("ownership", name);
+        let owned = OwnedFiles::load(&config);
+
+        let screenshot = config.screenshot_dir().join("1700000000_window.png");
+        let dom = config.dom_snapshot_dir().join("dom_1700000000.html");
+        let snapshot = config.log_dir.join("snapshot_1700000000000.json");
+        let snapshot_json = serde_json::json!({
+            "screenshot_path": screenshot,
+            "dom_snapshot_path": dom,
+        })
+        .to_string();
+        let plugin_files = vec![
+            write(&config.frontend_log_path(APP, PID), "{}\n"),
+            write(&config.network_log_path(APP, PID), "{}\n"),
+            write(
+                &config.log_dir.join("rust_debug.log.2026-01-01"),
+                "{\"timestamp\":\"2026-01-01T00:00:00Z\",\"level\":\"INFO\",\"fields\":{},\"target\":\"app\"}\n",
+            ),
+            write(&snapshot, &snapshot_json),
+            write(&dom, "<html></html>"),
+            write(&screenshot, "png"),
+        ];
+        for path in &plugin_files {
+            owned.register(path);
+        }
+
+        let foreign_files = vec![
+            write(
+                &config
+                    .log_dir
+                    .join("frontend_console_Ownership_Test_other.jsonl"),
+                "{}\n",
+            ),
+            write(
+                &config.log_dir.join("network_Ownership_Test_tool.jsonl"),
+                "{}\n",
+            ),
+            write(
+                &config.log_dir.join("rust_debug.log.notes"),
+                "written by hand\n",
+            ),
+            write(&config.log_dir.join("snapshot_1600000000000.json"), "{}"),
+            write(&config.dom_snapshot_dir().join("dom_export.html"), "<p>"),
+            write(&config.screenshot_dir().join("holiday.png"), "png"),
+        ];
+
+        Self {
+            config,
+            owned,
+            plugin_files,
+            foreign_files,
+        }
+    }
+
+    // Every foreign file is still there and was reported, apart from the
+    // `unreported` ones the operation never looks at.
+    fn assert_foreign_survived(&self, skipped_foreign: &[PathBuf], unreported: &[&str]) {
+        for path in &self.foreign_files {
+            assert!(path.exists(), "{} was deleted", path.display());
+            let name = path.file_name();
+            assert!(
+                skipped_foreign
+                    .iter()
+                    .any(|skipped| skipped.file_name() == name)
+                    || unreported.iter().any(|skip| name == Some(skip.as_ref())),
+                "{} was not reported as foreign",
+                path.display()
+            );
+        }
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.config.log_dir);
+    }
+}
+
+fn write(path: &Path, contents: &str) -> PathBuf {
+    fs::write(path, contents).expect("write fixture");
+    path.to_path_buf()
+}
+
+#[test]
+fn clear_keeps_foreign_files_in_every_managed_directory() {
+    let fixture = Fixture::new("clear");
+
+    let report =
+        clear_debug_log_files(&fixture.config, APP, &LegalHolds::default(), &fixture.owned)
+            .expect("clear");
+
+    // Clear leaves snapshots to retention.
+    fixture.assert_foreign_survived(&report.skipped_foreign, &["snapshot_1600000000000.json"]);
+    for path in fixture
+        .plugin_files
+        .iter()
+        .filter(|path| !path.ends_with("snapshot_1700000000000.json"))
+    {
+        assert!(!path.exists(), "{} was kept", path.display());
+        assert!(!fixture.owned.is_owned(path));
+    }
+}
+
+#[test]
+fn retention_keeps_foreign_files_in_every_managed_directory() {
+    let fixture = Fixture::new("retention");
+    // Age limits of zero expire every file written before the run.
+    std::thread::sleep(Duration::from_millis(20));
+    let policy = RetentionPolicy {
+        max_snapshots: Some(0),
+        max_snapshot_age_secs: Some(0),
+        ttl_secs: [(ArtifactKind::ConsoleLog, 0), (ArtifactKind::NetworkLog, 0)]
+            .into_iter()
+            .collect(),
+    };
+
+    let report = apply_retention(
+        &fixture.config,
+        &policy,
+        &LegalHolds::default(),
+        &fixture.owned,
+        i64::MAX / 1000,
+        &HashSet::new(),
+    )
+    .expect("retention");
+
+    // Retention never looks at backend logs.
+    fixture.assert_foreign_survived(&report.skipped_foreign, &["rust_debug.log.notes"]);
+    assert_eq!(report.pruned_snapshots.len(), 1);
+    assert_eq!(report.orphaned_artifacts.len(), 2);
+    assert_eq!(report.expired_logs.len(), 2);
+}
+
+#[test]
+fn claimed_files_are_cleared() {
+    let fixture = Fixture::new("claim");
+    let foreign_log = fixture
+        .config
+        .log_dir
+        .join("network_Ownership_Test_tool.jsonl");
+
+    let claimed = fixture.owned.claim([foreign_log.clone()]).expect("claim");
+    assert_eq!(claimed, vec![foreign_log.clone()]);
+    assert!(OwnedFiles::load(&fixture.config).is_owned(&foreign_log));
+
+    clear_debug_log_files(&fixture.config, APP, &LegalHolds::default(), &fixture.owned)
+        .expect("clear");
+    assert!(!foreign_log.exists());
+}
+
+#[test]
+fn secure_clear_reports_each_deletion() {
+    let fixture = Fixture::new("secure");
+
+    let report = clear_debug_log_files_with_secure_delete(
+        &fixture.config,
+        APP,
+        &LegalHolds::default(),
+        &fixture.owned,
+        true,
+    )
+    .expect("clear");
+
+    fixture.assert_foreign_survived(&report.skipped_foreign, &["snapshot_1600000000000.json"]);
+    assert_eq!(report.deletions.len(), report.deleted_paths.len());
+    for deletion in &report.deletions {
+        assert!(
+            !deletion.path.exists(),
+            "{} was kept",
+            deletion.path.display()
+        );
+        // Copy-on-write filesystems are deleted only, with a note saying why.
+        assert_ne!(deletion.securely_deleted, deletion.note.is_some());
+    }
+
+    let plain = Fixture::new("plain");
+    let report = clear_debug_log_files(&plain.config, APP, &LegalHolds::default(), &plain.owned)
+        .expect("clear");
+    assert!(report
+        .deletions
+        .iter()
+        .all(|deletion| !deletion.securely_deleted && deletion.note.is_none()));
+}
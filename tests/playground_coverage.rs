@@ -0,0 +1,55 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const BUILD_SCRIPT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/build.rs");
+const PLAYGROUND: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/debug-playground/src");
+
+fn read(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e))
+}
+
+// The literal command names in `text` that follow `prefix` and run up to
+// `end`; interpolated ones are skipped.
+fn names_after(text: &str, prefix: &str, end: char) -> BTreeSet<String> {
+    text.split(prefix)
+        .skip(1)
+        .filter_map(|rest| rest.split(end).next())
+        .filter(|name| name.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn registered_commands() -> BTreeSet<String> {
+    let script = read(Path::new(BUILD_SCRIPT));
+    let start = script.find("const COMMANDS").expect("COMMANDS in build.rs");
+    let list = &script[start..];
+    let list = &list[..list.find("];").expect("end of COMMANDS")];
+    list.lines()
+        .filter_map(|line| line.trim().strip_prefix('"'))
+        .filter_map(|line| line.split('"').next())
+        .map(str::to_string)
+        .collect()
+}
+
+// Every command the plugin registers has a step in the playground, which
+// `--exercise-all` runs. main.js invokes `submit_debug_command_reply` from
+// its debug command handler rather than from a step.
+#[test]
+fn playground_exercises_every_registered_command() {
+    let playground = Path::new(PLAYGROUND);
+    let steps = read(&playground.join("steps.js"));
+    let main = read(&playground.join("main.js"));
+
+    let mut exercised = names_after(&steps, "command: \"", '"');
+    exercised.extend(names_after(&main, "plugin:debug-tools|", '"'));
+    let registered = registered_commands();
+
+    let missing: Vec<_> = registered.difference(&exercised).collect();
+    assert!(missing.is_empty(), "no playground step for {:?}", missing);
+    let unknown: Vec<_> = exercised
+        .difference(&registered)
+        // Debug command names passed as arguments, not plugin commands.
+        .filter(|name| !matches!(name.as_str(), "echo" | "ping"))
+        .collect();
+    assert!(unknown.is_empty(), "steps for unregistered {:?}", unknown);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::fs;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_debug_tools::{Builder, DebugToolsConfig, DebugToolsState};
+
+// Generous for a debug build on a loaded CI machine; setup that blocks on
+// the filesystem or a helper thread takes far longer.
+const SETUP_BUDGET: Duration = Duration::from_secs(2);
+
+#[test]
+fn setup_completes_within_budget() {
+    let log_dir = common::temp_dir("plugin-init", "setup");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let started = Instant::now();
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).disable_screenshots().build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let elapsed = started.elapsed();
+
+    let report = app.state::<DebugToolsState>().init_report.report();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(elapsed < SETUP_BUDGET, "setup took {:?}", elapsed);
+    let phases: Vec<&str> = report
+        .phases
+        .iter()
+        .filter(|phase| !phase.background)
+        .map(|phase| phase.name.as_str())
+        .collect();
+    for expected in [
+        "config",
+        "instance_lock",
+        "storage_mode",
+        "tracing",
+        "state",
+    ] {
+        assert!(phases.contains(&expected), "missing phase {}", expected);
+    }
+    assert!(report.total_ms < SETUP_BUDGET.as_secs_f64() * 1000.0);
+}
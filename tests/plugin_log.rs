@@ -0,0 +1,354 @@
+mod common;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri_plugin_debug_tools::analysis::merge::merge_bundles;
+use tauri_plugin_debug_tools::{
+    apply_retention, clear_debug_log_files, export_encrypted_bundle, get_log_directory,
+    import_encrypted_bundle, parse_plugin_log_line, plugin_log_files, tail_plugin_log,
+    to_console_entry, ArtifactKind, Builder, DebugToolsConfig, LegalHolds, LogLevel, OwnedFiles,
+    PluginLogLine, RetentionPolicy, PLUGIN_LOG_SOURCE,
+};
+
+const APP: &str = "Plugin Log Test";
+// 2024-05-01T12:34:56Z.
+const LOGGED_AT_MS: i64 = 1_714_566_896_000;
+const DAY: u64 = 24 * 3600;
+
+// The debug directory sits inside the app log directory, where
+// tauri-plugin-log writes `debug.log`.
+fn config(name: &str, manage_plugin_log: bool) -> DebugToolsConfig {
+    let config = DebugToolsConfig {
+        log_dir: common::temp_dir("plugin-log", name).join("debug-tools"),
+        manage_plugin_log,
+        ..DebugToolsConfig::default()
+    };
+    config.ensure_subdirectories().expect("create directories");
+    config
+}
+
+fn app_log_dir(config: &DebugToolsConfig) -> PathBuf {
+    config.log_dir.parent().unwrap().to_path_buf()
+}
+
+fn age_file(path: &Path, age: Duration) {
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .expect("open log")
+        .set_modified(SystemTime::now() - age)
+        .expect("set mtime");
+}
+
+fn parsed(
+    timestamp: Option<i64>,
+    level: LogLevel,
+    target: Option<&str>,
+    message: &str,
+) -> (Option<i64>, Option<LogLevel>, Option<String>, String, bool) {
+    (
+        timestamp,
+        Some(level),
+        target.map(str::to_string),
+        message.to_string(),
+        false,
+    )
+}
+
+fn fields(line: PluginLogLine) -> (Option<i64>, Option<LogLevel>, Option<String>, String, bool) {
+    (
+        line.timestamp,
+        line.level,
+        line.target,
+        line.message,
+        line.raw,
+    )
+}
+
+#[test]
+fn the_default_layout_parses_into_its_parts() {
+    assert_eq!(
+        fields(parse_plugin_log_line(
+            "[2024-05-01][12:34:56][app::orders][INFO] order [42] saved\r\n"
+        )),
+        parsed(
+            Some(LOGGED_AT_MS),
+            LogLevel::Info,
+            Some("app::orders"),
+            "order [42] saved"
+        )
+    );
+}
+
+#[test]
+fn reordered_merged_or_missing_groups_still_parse() {
+    assert_eq!(
+        fields(parse_plugin_log_line(
+            "[WARN][2024-05-01 12:34:56.5][webview] slow render"
+        )),
+        parsed(
+            Some(LOGGED_AT_MS + 500),
+            LogLevel::Warn,
+            Some("webview"),
+            "slow render"
+        )
+    );
+    assert_eq!(
+        fields(parse_plugin_log_line("[2024-05-01T12:34:56Z][error] boom")),
+        parsed(Some(LOGGED_AT_MS), LogLevel::Error, None, "boom")
+    );
+    // No date, so no timestamp; `log` is a target, not a level.
+    assert_eq!(
+        fields(parse_plugin_log_line("[log][DEBUG] cache warmed")),
+        parsed(None, LogLevel::Debug, Some("log"), "cache warmed")
+    );
+}
+
+#[test]
+fn lines_without_a_level_are_kept_raw() {
+    for line in [
+        "2024-05-01 12:34:56 INFO a formatter without brackets",
+        "[2024-05-01][12:34:56][app] no level here",
+        "[unterminated INFO",
+        "",
+    ] {
+        let parsed = parse_plugin_log_line(line);
+        assert!(parsed.raw, "{:?}", line);
+        assert_eq!(parsed.message, line);
+        assert_eq!((parsed.timestamp, parsed.level), (None, None));
+    }
+}
+
+#[test]
+fn timeline_entries_are_marked_and_raw_lines_take_the_previous_time() {
+    let entry = to_console_entry(
+        parse_plugin_log_line("[2024-05-01][12:34:56][app][WARN] low disk"),
+        0,
+    );
+    let raw = to_console_entry(parse_plugin_log_line("   at frame 3"), LOGGED_AT_MS);
+
+    let fields = entry.fields.expect("fields");
+    assert_eq!(
+        (entry.timestamp, entry.level.as_str()),
+        (LOGGED_AT_MS, "warn")
+    );
+    assert_eq!(fields["source"], PLUGIN_LOG_SOURCE);
+    assert_eq!(fields["target"], "app");
+    assert!(!fields.contains_key("raw"));
+
+    let raw_fields = raw.fields.expect("fields");
+    assert_eq!((raw.timestamp, raw.level.as_str()), (LOGGED_AT_MS, "info"));
+    assert_eq!(raw_fields["raw"], true);
+    assert_eq!(raw.message, "   at frame 3");
+}
+
+#[test]
+fn the_live_file_is_listed_before_its_rotated_copies() {
+    let config = config("files", false);
+    let dir = app_log_dir(&config);
+    for name in [
+        "debug_2024-05-02.log",
+        "debug_2024-05-01.log",
+        "debug.txt",
+        "other_2024-05-01.log",
+    ] {
+        fs::write(dir.join(name), "x").expect("write log");
+    }
+    let unnamed = DebugToolsConfig {
+        plugin_log_file_name: None,
+        ..config.clone()
+    };
+
+    let files = plugin_log_files(&config);
+    let none = plugin_log_files(&unnamed);
+    let _ = fs::remove_dir_all(&dir);
+
+    // The live file is listed whether or not it exists yet.
+    assert_eq!(
+        files,
+        [
+            dir.join("debug.log"),
+            dir.join("debug_2024-05-01.log"),
+            dir.join("debug_2024-05-02.log"),
+        ]
+    );
+    assert!(none.is_empty());
+}
+
+#[test]
+fn cleanup_leaves_the_files_alone_unless_managed() {
+    let unmanaged = config("unmanaged", false);
+    let managed = config("managed", true);
+    let prepare = |config: &DebugToolsConfig| {
+        let dir = app_log_dir(config);
+        let live = dir.join("debug.log");
+        let rotated = dir.join("debug_2024-05-01.log");
+        let held = dir.join("debug_2024-04-30.log");
+        for path in [&live, &rotated, &held] {
+            fs::write(path, "[INFO] line\n").expect("write log");
+        }
+        (live, rotated, held)
+    };
+    let (unmanaged_live, unmanaged_rotated, _) = prepare(&unmanaged);
+    let (live, rotated, held) = prepare(&managed);
+    let holds = LegalHolds {
+        paths: BTreeSet::from([held.clone()]),
+        ..LegalHolds::default()
+    };
+
+    let untouched = clear_debug_log_files(&unmanaged, APP, &holds, &OwnedFiles::load(&unmanaged))
+        .expect("clear unmanaged");
+    let cleared =
+        clear_debug_log_files(&managed, APP, &holds, &OwnedFiles::load(&managed)).expect("clear");
+    let unmanaged_sizes =
+        [&unmanaged_live, &unmanaged_rotated].map(|path| fs::metadata(path).map(|m| m.len()).ok());
+    let managed_state = (
+        fs::metadata(&live).map(|m| m.len()).ok(),
+        rotated.exists(),
+        held.exists(),
+    );
+    let _ = fs::remove_dir_all(app_log_dir(&unmanaged));
+    let _ = fs::remove_dir_all(app_log_dir(&managed));
+
+    assert_eq!(unmanaged_sizes, [Some(12), Some(12)]);
+    let unmanaged_dir = app_log_dir(&unmanaged);
+    assert!(!untouched
+        .deletions
+        .iter()
+        .any(|deleted| deleted.path.parent() == Some(unmanaged_dir.as_path())));
+
+    // The log plugin keeps the live file open, so it is emptied instead.
+    assert_eq!(managed_state, (Some(0), false, true));
+    assert!(cleared.truncated_paths.contains(&live));
+    assert!(cleared.deleted_paths.contains(&rotated));
+    assert_eq!(cleared.held_paths, [held]);
+}
+
+#[test]
+fn retention_expires_old_rotated_copies_only() {
+    let config = config("retention", true);
+    let dir = app_log_dir(&config);
+    let live = dir.join("debug.log");
+    let old = dir.join("debug_2024-04-01.log");
+    let recent = dir.join("debug_2024-05-01.log");
+    for (path, age_days) in [(&live, 30), (&old, 30), (&recent, 1)] {
+        fs::write(path, "[INFO] line\n").expect("write log");
+        age_file(path, Duration::from_secs(age_days * DAY));
+    }
+    let policy = RetentionPolicy {
+        ttl_secs: HashMap::from([(ArtifactKind::ConsoleLog, 7 * DAY)]),
+        ..RetentionPolicy::default()
+    };
+    let unmanaged = DebugToolsConfig {
+        manage_plugin_log: false,
+        ..config.clone()
+    };
+    let owned = OwnedFiles::load(&config);
+    let run = |config: &DebugToolsConfig| {
+        apply_retention(
+            config,
+            &policy,
+            &LegalHolds::default(),
+            &owned,
+            1_700_000_000,
+            &HashSet::new(),
+        )
+        .expect("apply retention")
+    };
+
+    let skipped = run(&unmanaged);
+    let after_skipped = old.exists();
+    let report = run(&config);
+    let remaining = [&live, &old, &recent].map(|path| path.exists());
+    let _ = fs::remove_dir_all(&dir);
+
+    assert!(skipped.expired_logs.is_empty());
+    assert!(after_skipped);
+    assert_eq!(report.expired_logs, [old]);
+    assert_eq!(remaining, [true, false, true]);
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn the_file_is_tailed_reported_and_carried_into_merged_timelines() {
+    let config = config("app", false);
+    let dir = app_log_dir(&config);
+    let lines: Vec<String> = (0..5)
+        .map(|i| format!("[2024-05-01][12:34:5{}][app][INFO] step {}", i, i))
+        .chain(["plain text from a custom formatter".to_string()])
+        .collect();
+    let mut contents = lines.join("\n\n");
+    // Invalid UTF-8 is replaced rather than dropping the line.
+    contents.push('\n');
+    let mut contents = contents.into_bytes();
+    contents.extend_from_slice(b"[2024-05-01][12:35:00][app][ERROR] caf\xe9\n");
+    fs::write(dir.join("debug.log"), &contents).expect("write debug.log");
+    fs::write(
+        dir.join("debug_2024-04-30.log"),
+        "[2024-04-30][23:59:59][app][INFO] yesterday\n",
+    )
+    .expect("write rotated copy");
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+
+    let tail = tauri::async_runtime::block_on(tail_plugin_log(handle(), Some(3))).expect("tail");
+    let directory =
+        tauri::async_runtime::block_on(get_log_directory(handle())).expect("log directory");
+    let export = tauri::async_runtime::block_on(export_encrypted_bundle(handle(), "pw".into()))
+        .expect("export bundle");
+    let imported = tauri::async_runtime::block_on(import_encrypted_bundle(
+        handle(),
+        export.path.to_string_lossy().into_owned(),
+        "pw".into(),
+        None,
+    ))
+    .expect("import bundle");
+    let merged =
+        merge_bundles(std::slice::from_ref(&imported.path), &dir.join("merged")).expect("merge");
+    let timeline: Vec<serde_json::Value> = fs::read_to_string(&merged.timeline_path)
+        .expect("read timeline")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("timeline line"))
+        .collect();
+    fs::remove_file(dir.join("debug.log")).expect("remove debug.log");
+    let missing = tauri::async_runtime::block_on(tail_plugin_log(handle(), None)).expect("tail");
+    let _ = fs::remove_dir_all(&dir);
+
+    let tail: Vec<(String, bool)> = tail
+        .into_iter()
+        .map(|line| (line.message, line.raw))
+        .collect();
+    assert_eq!(
+        tail,
+        [
+            ("step 4".to_string(), false),
+            ("plain text from a custom formatter".to_string(), true),
+            ("caf\u{fffd}".to_string(), false),
+        ]
+    );
+    assert_eq!(
+        directory.plugin_log.map(PathBuf::from),
+        Some(dir.join("debug.log"))
+    );
+    assert!(missing.is_empty());
+
+    // Both files, alongside the console entries.
+    assert_eq!(merged.bundles[0].plugin_log_entries, 8, "{:?}", merged);
+    let from_plugin_log: Vec<&str> = timeline
+        .iter()
+        .filter(|line| line["fields"]["source"] == PLUGIN_LOG_SOURCE)
+        .map(|line| line["message"].as_str().unwrap())
+        .collect();
+    assert!(
+        from_plugin_log.contains(&"yesterday"),
+        "{:?}",
+        from_plugin_log
+    );
+    assert!(from_plugin_log.contains(&"step 0"), "{:?}", from_plugin_log);
+}
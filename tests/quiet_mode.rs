@@ -0,0 +1,96 @@
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, get_debug_config, get_debug_stats, set_quiet_mode, Builder,
+    ConsoleLogEntryPayload, DebugToolsConfig, DebugToolsState, FixedClock,
+};
+
+const START_MS: i64 = 1_700_000_000_000;
+
+fn entry(level: &str, message: &str) -> ConsoleLogEntryPayload {
+    ConsoleLogEntryPayload {
+        timestamp: START_MS,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    }
+}
+
+#[test]
+fn quiet_mode_suppresses_pauses_and_summarizes_on_expiry() {
+    let log_dir = common::temp_dir("quiet-mode", "app");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+    let clock = Arc::new(FixedClock::new(START_MS));
+
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(clock.clone())
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+    let state = app.state::<DebugToolsState>();
+    let log_path = state
+        .config
+        .frontend_log_path(&app.package_info().name, state.ids.process_id());
+    let append = |logs| {
+        tauri::async_runtime::block_on(append_debug_logs(handle(), logs, None, None, None, None))
+            .expect("append");
+    };
+
+    let status = tauri::async_runtime::block_on(set_quiet_mode(handle(), "warn".into(), 60))
+        .expect("enable quiet mode");
+    append(vec![entry("info", "chatty"), entry("error", "kept")]);
+    let paused = !state
+        .quiet_mode
+        .allows_automatic_snapshot(state.clock.now_secs());
+    let stats = tauri::async_runtime::block_on(get_debug_stats(handle())).expect("stats");
+    let config = tauri::async_runtime::block_on(get_debug_config(handle())).expect("config");
+
+    clock.advance(61_000);
+    let after = tauri::async_runtime::block_on(get_debug_stats(handle())).expect("stats");
+    let snapshots_resume = state
+        .quiet_mode
+        .allows_automatic_snapshot(state.clock.now_secs());
+    let persisted = fs::read_to_string(&log_path).unwrap_or_default();
+    // Expiry follows the injected clock and is recorded in the config history.
+    let mut history: Vec<_> = fs::read_dir(state.config.config_history_dir())
+        .expect("config history")
+        .map(|entry| entry.expect("history entry").path())
+        .collect();
+    history.sort();
+    let latest: serde_json::Value =
+        serde_json::from_slice(&fs::read(history.last().expect("history")).expect("read"))
+            .expect("parse");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(paused);
+    assert_eq!(stats.suppress_below_level_until, Some(status.until));
+    let window = stats.quiet_mode.expect("quiet mode active");
+    assert_eq!((window.suppressed_entries, window.paused_snapshots), (1, 1));
+    assert_eq!(
+        config.quiet_mode.map(|active| active.until),
+        Some(status.until)
+    );
+
+    assert!(after.quiet_mode.is_none() && after.suppress_below_level_until.is_none());
+    assert_eq!(after.total_suppressed_entries, 1);
+    assert!(snapshots_resume);
+    assert_eq!(latest["reason"], "quiet_mode_expired");
+    assert!(!persisted.contains("chatty"));
+    assert!(persisted.contains("kept"));
+    assert!(persisted.contains("1 entries below Warn suppressed, 1 automatic snapshots paused"));
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{read_debug_snapshot, Builder, DebugToolsConfig, SnapshotReadError};
+
+#[test]
+fn reads_by_timestamp_with_typed_errors() {
+    let log_dir = common::temp_dir("read-snapshot", "legacy");
+    fs::create_dir_all(&log_dir).expect("create log directory");
+    // As an older version wrote it: no console logs, paths or later fields.
+    let snapshot = serde_json::json!({
+        "timestamp": 1700000000000_i64,
+        "webview_state": {
+            "url": "http://localhost:5173/",
+            "title": "Home",
+            "user_agent": "Mozilla/5.0",
+            "viewport": { "width": 800, "height": 600 },
+        },
+    });
+    fs::write(
+        log_dir.join("snapshot_1700000000000.json"),
+        snapshot.to_string(),
+    )
+    .expect("write snapshot");
+    fs::write(
+        log_dir.join("snapshot_1700000000001.json"),
+        "{\"timestamp\"",
+    )
+    .expect("write torn");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let read = |timestamp: i64| {
+        tauri::async_runtime::block_on(read_debug_snapshot(app.handle().clone(), timestamp))
+    };
+
+    let loaded = read(1700000000000).expect("read older snapshot");
+    let missing = read(1700000000002).expect_err("no such snapshot");
+    let torn = read(1700000000001).expect_err("torn snapshot");
+    let negative = read(-1).expect_err("negative timestamp");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(loaded.webview_state.title, "Home");
+    assert!(loaded.console_logs.is_empty());
+    assert!(loaded.screenshot_path.is_none());
+    assert!(matches!(
+        missing,
+        SnapshotReadError::NotFound {
+            timestamp: 1700000000002
+        }
+    ));
+    assert_eq!(serde_json::to_value(&missing).unwrap()["code"], "not_found");
+    assert!(matches!(torn, SnapshotReadError::Unreadable { .. }));
+    assert!(matches!(
+        negative,
+        SnapshotReadError::InvalidTimestamp { .. }
+    ));
+}
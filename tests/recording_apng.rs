@@ -0,0 +1,69 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri_plugin_debug_tools::assemble_apng;
+
+fn write_png(path: &Path, width: u32, height: u32, rgba: [u8; 4]) -> PathBuf {
+    let mut encoder =
+        png::Encoder::new(fs::File::create(path).expect("create frame"), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let pixels: Vec<u8> = rgba.repeat((width * height) as usize);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .expect("encode frame");
+    path.to_path_buf()
+}
+
+// A PNG signature and an IHDR chunk two bytes long, as a torn capture
+// could leave behind.
+fn short_header(path: &Path) -> PathBuf {
+    let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    bytes.extend_from_slice(&2u32.to_be_bytes());
+    bytes.extend_from_slice(b"IHDR\x00\x04");
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+    fs::write(path, bytes).expect("write short header");
+    path.to_path_buf()
+}
+
+#[test]
+fn frames_that_do_not_fit_are_left_out_of_the_animation() {
+    let dir = common::temp_dir("recording", "apng");
+    fs::create_dir_all(&dir).expect("create recording directory");
+    let colours = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]];
+    let frames = vec![
+        short_header(&dir.join("frame_00000.png")),
+        write_png(&dir.join("frame_00001.png"), 4, 3, colours[0]),
+        write_png(&dir.join("frame_00002.png"), 4, 3, colours[1]),
+        write_png(&dir.join("frame_00003.png"), 2, 2, [9, 9, 9, 255]),
+        write_png(&dir.join("frame_00004.png"), 4, 3, colours[2]),
+    ];
+    let out = dir.join("recording.png");
+
+    let assembled = assemble_apng(&frames, 5, &out).expect("assemble");
+    let empty = assemble_apng(&frames[..1], 5, &dir.join("empty.png"));
+
+    let decoder = png::Decoder::new(fs::File::open(&out).expect("open animation"));
+    let mut reader = decoder.read_info().expect("read animation");
+    let animation = reader.info().animation_control.expect("acTL chunk");
+    let mut first_pixels = Vec::new();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    for _ in 0..animation.num_frames {
+        reader.next_frame(&mut buf).expect("decode frame");
+        first_pixels.push([buf[0], buf[1], buf[2], buf[3]]);
+    }
+    let delay = reader.info().frame_control.map(|control| control.delay_den);
+    let _ = fs::remove_dir_all(&dir);
+
+    assert_eq!(assembled, 3);
+    assert_eq!(animation.num_frames, 3);
+    assert_eq!(first_pixels, colours);
+    assert_eq!(delay, Some(5));
+    assert!(
+        empty.is_err(),
+        "assembled an animation with no usable frames"
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use std::fs;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{get_debug_stats, Builder, DebugToolsConfig, DebugToolsState};
+
+const EVENTS: usize = 500;
+
+// A disk that refuses every write.
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Err(io::Error::other("disk full"))
+    }
+}
+
+#[test]
+fn a_failing_write_that_logs_does_not_feed_back_into_the_layers() {
+    let log_dir = common::temp_dir("reentrant", "app");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        forward_backend_logs: true,
+        ..DebugToolsConfig::default()
+    };
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let forwarder = app
+        .state::<DebugToolsState>()
+        .forwarder
+        .clone()
+        .expect("forwarding enabled");
+    forwarder.flush();
+
+    // Every forwarded batch fails to write and reports it at error level,
+    // which would flush straight back into the forwarder without the guard.
+    let writes = Arc::new(AtomicUsize::new(0));
+    let counted = writes.clone();
+    forwarder.set_emitter(Arc::new(move |batch| {
+        counted.fetch_add(1, Ordering::SeqCst);
+        if let Err(e) = FailingWriter.write_all(&serde_json::to_vec(batch).unwrap_or_default()) {
+            tracing::error!(error = %e, "Failed to write forwarded logs");
+        }
+    }));
+
+    let before = tauri::async_runtime::block_on(get_debug_stats(app.handle().clone()))
+        .expect("stats")
+        .reentrant_events_suppressed;
+    let (done, finished) = mpsc::channel();
+    std::thread::spawn(move || {
+        for i in 0..EVENTS {
+            tracing::warn!(attempt = i, "Write failed upstream");
+        }
+        let _ = done.send(());
+    });
+    let completed = finished.recv_timeout(Duration::from_secs(30)).is_ok();
+
+    let stats = tauri::async_runtime::block_on(get_debug_stats(app.handle().clone()));
+    let leftover = forwarder.flush();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(completed, "logging did not finish: livelock");
+    // Warnings flush one batch each; no write was triggered by another.
+    let writes = writes.load(Ordering::SeqCst);
+    assert_eq!(writes, EVENTS);
+    let suppressed = stats.expect("stats").reentrant_events_suppressed - before;
+    assert_eq!(suppressed as usize, writes);
+    // Nothing the failing writer logged was buffered for another round.
+    assert_eq!(leftover, 0);
+}
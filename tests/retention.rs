@@ -0,0 +1,236 @@
+mod common;
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tauri_plugin_debug_tools::{
+    apply_retention, ArtifactKind, Builder, DebugToolsConfig, FixedClock, LegalHolds, OwnedFiles,
+    RetentionPolicy,
+};
+
+const NOW_SECS: i64 = 1_700_000_000;
+const HOUR: u64 = 3600;
+
+fn write(path: &Path, contents: &str, owned: &OwnedFiles) -> PathBuf {
+    fs::write(path, contents).expect("write artifact");
+    owned.register(path);
+    path.to_path_buf()
+}
+
+// A snapshot `age_secs` before `NOW_SECS`, with its screenshot and DOM files.
+fn snapshot(
+    config: &DebugToolsConfig,
+    owned: &Arc<OwnedFiles>,
+    age_secs: i64,
+    label: &str,
+) -> (PathBuf, PathBuf, PathBuf) {
+    let timestamp = NOW_SECS - age_secs;
+    let screenshot = write(
+        &config
+            .screenshot_dir()
+            .join(format!("{}_main.png", timestamp)),
+        "png",
+        owned,
+    );
+    let dom = write(
+        &config
+            .dom_snapshot_dir()
+            .join(format!("dom_{}.html", timestamp)),
+        "<p></p>",
+        owned,
+    );
+    let path = common::save_snapshot(
+        config,
+        owned,
+        serde_json::json!({
+            "timestamp": timestamp,
+            "screenshot_path": screenshot,
+            "dom_snapshot_path": dom,
+            "label": label,
+        }),
+    );
+    (path, screenshot, dom)
+}
+
+fn age_file(path: &Path, age: Duration) {
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .expect("open artifact")
+        .set_modified(SystemTime::now() - age)
+        .expect("set mtime");
+}
+
+#[test]
+fn aged_snapshots_are_pruned_with_the_artifacts_only_they_reference() {
+    let config = common::config("retention", "aged");
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let hour = HOUR as i64;
+
+    // One second either side of the cutoff.
+    let expired = snapshot(&config, &owned, hour + 1, "manual");
+    let boundary = snapshot(&config, &owned, hour, "manual");
+    let fresh = snapshot(&config, &owned, 1, "manual");
+    // Crash snapshots have a longer TTL of their own.
+    let crash = snapshot(&config, &owned, 2 * hour, "crash");
+    // Old enough, but not written by the plugin.
+    let foreign = config.snapshot_path(NOW_SECS - 5 * hour);
+    fs::write(&foreign, "{}").expect("write foreign snapshot");
+    // An unreferenced screenshot whose file is older than the limit.
+    let stray = write(&config.screenshot_dir().join("stray.png"), "png", &owned);
+    age_file(&stray, Duration::from_secs(2 * HOUR));
+
+    let policy = RetentionPolicy {
+        max_snapshot_age_secs: Some(HOUR),
+        ttl_secs: HashMap::from([(ArtifactKind::CrashSnapshot, 24 * HOUR)]),
+        ..RetentionPolicy::default()
+    };
+    let report = apply_retention(
+        &config,
+        &policy,
+        &LegalHolds::default(),
+        &owned,
+        NOW_SECS,
+        &HashSet::new(),
+    )
+    .expect("apply retention");
+    let exists = |path: &PathBuf| path.exists();
+    let remaining = (
+        [&expired.0, &expired.1, &expired.2, &stray].map(exists),
+        [
+            &boundary.0,
+            &fresh.0,
+            &fresh.1,
+            &fresh.2,
+            &crash.0,
+            &crash.1,
+        ]
+        .map(exists),
+        foreign.exists(),
+    );
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(report.pruned_snapshots, std::slice::from_ref(&expired.0));
+    let mut orphaned = report.orphaned_artifacts.clone();
+    orphaned.sort();
+    let mut expected = vec![expired.1, expired.2, stray];
+    expected.sort();
+    assert_eq!(orphaned, expected);
+    assert_eq!(report.skipped_foreign, [foreign]);
+    assert_eq!(report.retained_snapshots, 3);
+    assert_eq!(remaining, ([false; 4], [true; 6], true));
+}
+
+#[test]
+fn max_snapshots_prunes_the_oldest_beyond_the_count() {
+    let config = common::config("retention", "count");
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let snapshots: Vec<_> = (1..=4)
+        .map(|minutes| snapshot(&config, &owned, minutes * 60, "manual"))
+        .collect();
+
+    let policy = RetentionPolicy {
+        max_snapshots: Some(2),
+        ..RetentionPolicy::default()
+    };
+    let report = apply_retention(
+        &config,
+        &policy,
+        &LegalHolds::default(),
+        &owned,
+        NOW_SECS,
+        &HashSet::new(),
+    )
+    .expect("apply retention");
+    let remaining: Vec<bool> = snapshots.iter().map(|(path, ..)| path.exists()).collect();
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let mut pruned = report.pruned_snapshots.clone();
+    pruned.sort();
+    assert_eq!(pruned, [snapshots[3].0.clone(), snapshots[2].0.clone()]);
+    assert_eq!(remaining, [true, true, false, false]);
+}
+
+#[test]
+fn secure_delete_overwrites_what_retention_removes() {
+    let config = DebugToolsConfig {
+        secure_delete: true,
+        ..common::config("retention", "secure")
+    };
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let (expired, screenshot, dom) = snapshot(&config, &owned, 2 * HOUR as i64, "manual");
+
+    let policy = RetentionPolicy {
+        max_snapshot_age_secs: Some(HOUR),
+        ..RetentionPolicy::default()
+    };
+    let report = apply_retention(
+        &config,
+        &policy,
+        &LegalHolds::default(),
+        &owned,
+        NOW_SECS,
+        &HashSet::new(),
+    )
+    .expect("apply retention");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let mut deleted: Vec<_> = report
+        .deletions
+        .iter()
+        .map(|deletion| deletion.path.clone())
+        .collect();
+    deleted.sort();
+    let mut expected = vec![expired, screenshot, dom];
+    expected.sort();
+    assert_eq!(deleted, expected);
+    for deletion in &report.deletions {
+        // Copy-on-write filesystems are deleted only, with a note saying why.
+        assert_ne!(deletion.securely_deleted, deletion.note.is_some());
+    }
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn setup_runs_retention_once_at_startup() {
+    let config = DebugToolsConfig {
+        retention: Some(RetentionPolicy {
+            max_snapshot_age_secs: Some(HOUR),
+            ..RetentionPolicy::default()
+        }),
+        retention_interval_secs: 24 * HOUR,
+        ..common::config("retention", "startup")
+    };
+    let owned = Arc::new(OwnedFiles::load(&config));
+    let (expired, screenshot, dom) = snapshot(&config, &owned, 2 * HOUR as i64, "manual");
+    let (fresh, ..) = snapshot(&config, &owned, 1, "manual");
+    drop(owned);
+
+    let log_dir = config.log_dir.clone();
+    let _app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .clock(Arc::new(FixedClock::new(NOW_SECS * 1000)))
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+
+    // The first run happens on the retention thread right after setup.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while [&expired, &screenshot, &dom]
+        .iter()
+        .any(|path| path.exists())
+        && Instant::now() < deadline
+    {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let remaining = [&expired, &screenshot, &dom, &fresh].map(|path| path.exists());
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(remaining, [false, false, false, true]);
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+use tauri_plugin_debug_tools::analysis::schema_manifest::{
+    artifact_schema_manifest, write_schema_manifest, ARTIFACT_MANIFEST_VERSION,
+};
+
+const SNAPSHOT: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/schemas/artifact-manifest.json"
+);
+const REGENERATE: &str = "UPDATE_SCHEMA_MANIFEST=1 cargo test --test schema_manifest";
+
+// Any change to the generated manifest must come with a new
+// `ARTIFACT_MANIFEST_VERSION` and a regenerated snapshot.
+#[test]
+fn manifest_matches_snapshot() {
+    if std::env::var_os("UPDATE_SCHEMA_MANIFEST").is_some() {
+        write_schema_manifest(Path::new(SNAPSHOT)).expect("write snapshot");
+        return;
+    }
+
+    let generated = serde_json::to_value(artifact_schema_manifest()).expect("serialize manifest");
+    let snapshot: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(SNAPSHOT)
+            .unwrap_or_else(|e| panic!("read {}: {} ({})", SNAPSHOT, e, REGENERATE)),
+    )
+    .expect("parse snapshot");
+    if generated == snapshot {
+        return;
+    }
+
+    assert_ne!(
+        snapshot["manifest_version"].as_u64(),
+        Some(u64::from(ARTIFACT_MANIFEST_VERSION)),
+        "artifact schemas changed: bump ARTIFACT_MANIFEST_VERSION, then run {}",
+        REGENERATE
+    );
+    panic!(
+        "schemas/artifact-manifest.json is out of date for version {}: run {}",
+        ARTIFACT_MANIFEST_VERSION, REGENERATE
+    );
+}
+
+#[test]
+fn manifest_version_only_moves_forward() {
+    let snapshot: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(SNAPSHOT).expect("read snapshot"))
+            .expect("parse snapshot");
+    let recorded = snapshot["manifest_version"].as_u64().unwrap_or_default();
+    assert!(
+        u64::from(ARTIFACT_MANIFEST_VERSION) >= recorded,
+        "ARTIFACT_MANIFEST_VERSION {} is older than the snapshot's {}",
+        ARTIFACT_MANIFEST_VERSION,
+        recorded
+    );
+}
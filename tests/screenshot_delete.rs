@@ -0,0 +1,85 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{
+    delete_debug_screenshot, set_legal_hold, Builder, DebugToolsConfig, ScreenshotDeleteError,
+};
+
+#[test]
+fn pinned_and_referenced_screenshots_are_refused() {
+    let log_dir = common::temp_dir("screenshot-delete", "refused");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        secure_delete: true,
+        ..DebugToolsConfig::default()
+    };
+    let screenshots = config.screenshot_dir();
+    fs::create_dir_all(&screenshots).expect("create screenshot directory");
+    let write = |name: &str| {
+        let path = screenshots.join(name);
+        fs::write(&path, b"png").expect("write screenshot");
+        path
+    };
+    let linked = write("1000_main.png");
+    let held = write("1001_main.png");
+    let loose = write("1002_main.png");
+    let snapshot = log_dir.join("snapshot_1000000.json");
+    fs::write(
+        &snapshot,
+        serde_json::json!({ "screenshot_path": linked }).to_string(),
+    )
+    .expect("write snapshot");
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+    let delete = |path: &std::path::Path| {
+        tauri::async_runtime::block_on(delete_debug_screenshot(
+            handle(),
+            path.to_string_lossy().into_owned(),
+        ))
+    };
+    let hold = |path: &std::path::Path, held: bool| {
+        tauri::async_runtime::block_on(set_legal_hold(
+            handle(),
+            Some(vec![path.to_string_lossy().into_owned()]),
+            None,
+            held,
+        ))
+        .expect("set hold");
+    };
+
+    let referenced = delete(&linked).expect_err("linked screenshot");
+    hold(&snapshot, true);
+    let pinned_by_snapshot = delete(&linked).expect_err("held snapshot");
+    hold(&held, true);
+    let pinned = delete(&held).expect_err("held screenshot");
+    let outside = delete(&log_dir.join("snapshot_1000000.json")).expect_err("not a screenshot");
+    let deletion = delete(&loose).expect("delete unpinned screenshot");
+    let still_there = (linked.exists(), held.exists(), loose.exists());
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(matches!(
+        referenced,
+        ScreenshotDeleteError::StillReferenced { ref snapshots, .. } if snapshots == std::slice::from_ref(&snapshot)
+    ));
+    assert!(matches!(
+        pinned_by_snapshot,
+        ScreenshotDeleteError::PinnedArtifact { ref pinned_by, .. } if pinned_by == std::slice::from_ref(&snapshot)
+    ));
+    assert_eq!(
+        serde_json::to_value(&pinned).unwrap()["code"],
+        "pinned_artifact"
+    );
+    assert!(matches!(
+        pinned,
+        ScreenshotDeleteError::PinnedArtifact { ref pinned_by, .. } if pinned_by == std::slice::from_ref(&held)
+    ));
+    assert!(matches!(outside, ScreenshotDeleteError::InvalidPath { .. }));
+    assert_eq!(still_there, (true, true, false));
+    // `secure_delete` covers explicit deletes too.
+    assert_eq!(deletion.path, loose);
+    assert_ne!(deletion.securely_deleted, deletion.note.is_some());
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_debug_tools::{
+    capture_debug_screenshot_region, capture_element_screenshot, crop_rect, crop_screenshot,
+    list_debug_screenshots, newest_screenshot_within, Builder, DebugToolsState, PixelRect,
+    ScreenshotRect, ScreenshotRegion, ScreenshotRegionError,
+};
+
+const WIDTH: u32 = 8;
+const HEIGHT: u32 = 6;
+
+fn rect(x: f64, y: f64, width: f64, height: f64) -> ScreenshotRect {
+    ScreenshotRect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+fn pixel_rect(x: u32, y: u32, width: u32, height: u32) -> PixelRect {
+    PixelRect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+// Every pixel's red and green channels are its column and row.
+fn write_capture(path: &Path) {
+    let mut pixels = Vec::new();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            pixels.extend_from_slice(&[x as u8, y as u8, 0, 255]);
+        }
+    }
+    let mut encoder = png::Encoder::new(
+        fs::File::create(path).expect("create capture"),
+        WIDTH,
+        HEIGHT,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .expect("encode capture");
+}
+
+fn read_pixels(path: &Path) -> (u32, u32, Vec<u8>) {
+    let mut reader = png::Decoder::new(fs::File::open(path).expect("open crop"))
+        .read_info()
+        .expect("decode crop");
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).expect("read crop");
+    pixels.truncate(info.buffer_size());
+    (info.width, info.height, pixels)
+}
+
+fn region(crop: PixelRect, selector: Option<&str>) -> ScreenshotRegion {
+    ScreenshotRegion {
+        window_label: "main".to_string(),
+        requested: rect(1.0, 1.0, 1.5, 1.0),
+        logical: true,
+        scale_factor: 2.0,
+        crop,
+        clamped: false,
+        selector: selector.map(str::to_string),
+    }
+}
+
+#[test]
+fn logical_rects_are_scaled_and_offset_into_the_capture() {
+    // A 2x display with the content area 10 physical pixels below the frame.
+    let (crop, clamped) =
+        crop_rect(rect(10.0, 20.0, 30.5, 40.0), 2.0, (0, 10), (800, 600)).expect("crop");
+    // Physical coordinates are used as given.
+    let (physical, _) =
+        crop_rect(rect(10.0, 20.0, 30.0, 40.0), 1.0, (0, 0), (800, 600)).expect("crop");
+
+    assert_eq!(crop, pixel_rect(20, 50, 61, 80));
+    assert!(!clamped);
+    assert_eq!(physical, pixel_rect(10, 20, 30, 40));
+}
+
+#[test]
+fn rects_reaching_past_the_capture_are_clamped() {
+    let (crop, clamped) =
+        crop_rect(rect(-10.0, 590.0, 100.0, 50.0), 1.0, (0, 0), (800, 600)).expect("crop");
+
+    assert_eq!(crop, pixel_rect(0, 590, 90, 10));
+    assert!(clamped);
+}
+
+#[test]
+fn regions_without_pixels_are_structured_errors() {
+    let outside = crop_rect(rect(900.0, 0.0, 10.0, 10.0), 1.0, (0, 0), (800, 600));
+    let flat = crop_rect(rect(10.0, 10.0, 0.0, 10.0), 1.0, (0, 0), (800, 600));
+    let not_a_number = crop_rect(rect(f64::NAN, 0.0, 10.0, 10.0), 1.0, (0, 0), (800, 600));
+
+    for result in [&outside, &flat, &not_a_number] {
+        assert!(
+            matches!(
+                result,
+                Err(ScreenshotRegionError::EmptyRegion {
+                    image_width: 800,
+                    image_height: 600,
+                    ..
+                })
+            ),
+            "{:?}",
+            result
+        );
+    }
+    let serialized = serde_json::to_value(outside.unwrap_err()).expect("serialize");
+    assert_eq!(serialized["code"], "empty_region");
+    assert_eq!(serialized["rect"]["x"], 900.0);
+}
+
+#[test]
+fn crops_keep_the_selected_pixels_and_are_not_reused_as_full_captures() {
+    let config = common::config("screenshot-region", "crop");
+    let full = config.screenshot_dir().join("1000_main.png");
+    let cropped = config.screenshot_dir().join("1001_main_region_1001000.png");
+    write_capture(&full);
+
+    crop_screenshot(&full, &region(pixel_rect(2, 1, 3, 2), None), &cropped).expect("crop");
+    let too_wide = crop_screenshot(
+        &full,
+        &region(pixel_rect(6, 0, 3, 1), None),
+        &config.screenshot_dir().join("1002_main_region.png"),
+    );
+    let (width, height, pixels) = read_pixels(&cropped);
+    let reused = newest_screenshot_within(&config, &Default::default(), 1001, 5).expect("look up");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!((width, height), (3, 2));
+    let positions: Vec<(u8, u8)> = pixels.chunks(4).map(|rgba| (rgba[0], rgba[1])).collect();
+    assert_eq!(positions, [(2, 1), (3, 1), (4, 1), (2, 2), (3, 2), (4, 2)]);
+    assert!(too_wide.is_err());
+    assert_eq!(reused.map(|screenshot| screenshot.path), Some(full));
+}
+
+// The only test here that builds an app (see tests/common/mod.rs). The mock
+// runtime cannot take real screenshots, so the commands are covered up to
+// the capture, and listings are checked against a crop written directly.
+#[test]
+fn region_commands_refuse_early_and_listings_show_the_region() {
+    let config = common::config("screenshot-region", "app");
+    let log_dir = config.log_dir.clone();
+    let full = config.screenshot_dir().join("1000_main.png");
+    let cropped: PathBuf = config.screenshot_dir().join("1001_main_region_1001000.png");
+    write_capture(&full);
+    crop_screenshot(
+        &full,
+        &region(pixel_rect(0, 0, 4, 4), Some("#cart")),
+        &cropped,
+    )
+    .expect("crop");
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .with_stdout(false)
+                .disable_screenshots()
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+        .build()
+        .expect("create window");
+    let region_of = |label: &str| {
+        tauri::async_runtime::block_on(capture_debug_screenshot_region(
+            app.handle().clone(),
+            label.to_string(),
+            rect(0.0, 0.0, 10.0, 10.0),
+            None,
+        ))
+    };
+    let element_of = |label: &str| {
+        tauri::async_runtime::block_on(capture_element_screenshot(
+            app.handle().clone(),
+            label.to_string(),
+            "#cart".to_string(),
+            Some(200),
+        ))
+    };
+
+    let disabled = (region_of("main"), element_of("main"));
+    app.state::<DebugToolsState>()
+        .capture_profile
+        .write()
+        .expect("profile lock")
+        .profile
+        .enable_screenshots = true;
+    let missing = (region_of("settings"), element_of("settings"));
+    // No frontend is listening, so the element's bounds never arrive.
+    let unanswered = element_of("main");
+    let listed =
+        tauri::async_runtime::block_on(list_debug_screenshots(app.handle().clone(), None, None))
+            .expect("list screenshots");
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert!(matches!(disabled.0, Err(ScreenshotRegionError::Disabled)));
+    assert!(matches!(disabled.1, Err(ScreenshotRegionError::Disabled)));
+    for result in [missing.0, missing.1] {
+        assert!(
+            matches!(&result, Err(ScreenshotRegionError::WindowNotFound { window_label }) if window_label == "settings"),
+            "{:?}",
+            result
+        );
+    }
+    assert!(
+        matches!(
+            unanswered,
+            Err(ScreenshotRegionError::ElementRectUnavailable { .. })
+        ),
+        "{:?}",
+        unanswered
+    );
+
+    let regions: Vec<(PathBuf, Option<ScreenshotRegion>)> = listed
+        .into_iter()
+        .map(|screenshot| (screenshot.path, screenshot.region))
+        .collect();
+    assert_eq!(
+        regions,
+        [
+            (cropped, Some(region(pixel_rect(0, 0, 4, 4), Some("#cart")))),
+            (full, None),
+        ]
+    );
+}
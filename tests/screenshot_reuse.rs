@@ -0,0 +1,41 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{newest_screenshot_within, snapshots_referencing};
+
+#[test]
+fn reuses_only_screenshots_inside_the_window() {
+    let config = common::config("screenshot-reuse", "window");
+    for name in ["1000_main.png", "1008_main.png"] {
+        fs::write(config.screenshot_dir().join(name), b"png").expect("write screenshot");
+    }
+
+    let index = Default::default();
+    let recent = newest_screenshot_within(&config, &index, 1010, 5).expect("look up");
+    let stale = newest_screenshot_within(&config, &index, 1020, 5).expect("look up");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(recent.map(|screenshot| screenshot.timestamp), Some(1008));
+    assert!(stale.is_none());
+}
+
+#[test]
+fn finds_every_snapshot_sharing_a_screenshot() {
+    let config = common::config("screenshot-reuse", "shared");
+    let shared = config.screenshot_dir().join("1000_main.png");
+    fs::write(&shared, b"png").expect("write screenshot");
+    for timestamp in [1_000_000, 1_002_000] {
+        let snapshot = serde_json::json!({ "screenshot_path": shared, "screenshot_reused": true });
+        fs::write(
+            config.log_dir.join(format!("snapshot_{}.json", timestamp)),
+            snapshot.to_string(),
+        )
+        .expect("write snapshot");
+    }
+    fs::write(config.log_dir.join("snapshot_1003000.json"), "{}").expect("write snapshot");
+
+    let referencing = snapshots_referencing(&config, &shared).expect("scan snapshots");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(referencing.len(), 2);
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use std::fs;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    acquire_instance_lock, release_instance_lock, Builder, DebugToolsConfig, DebugToolsState,
+};
+
+const LOCK_FILE: &str = ".debug-tools.lock";
+
+fn config(log_dir: &std::path::Path) -> DebugToolsConfig {
+    DebugToolsConfig {
+        log_dir: log_dir.to_path_buf(),
+        ..DebugToolsConfig::default()
+    }
+}
+
+fn write_lock(dir: &std::path::Path, pid: u32) {
+    fs::create_dir_all(dir).expect("create log directory");
+    fs::write(
+        dir.join(LOCK_FILE),
+        serde_json::json!({"pid": pid, "session_id": "other", "acquired_at": 0}).to_string(),
+    )
+    .expect("write lock");
+}
+
+fn lock_holder(dir: &std::path::Path) -> serde_json::Value {
+    serde_json::from_slice(&fs::read(dir.join(LOCK_FILE)).expect("read lock")).expect("lock json")
+}
+
+#[test]
+fn a_free_directory_is_claimed_and_released() {
+    let dir = common::temp_dir("singleton", "free");
+    let mut config = config(&dir);
+
+    let lock = acquire_instance_lock(&mut config, 1234, "session-a", 10).expect("acquire");
+    assert_eq!(config.log_dir, dir);
+    assert_eq!(lock.shared_with, None);
+    assert_eq!(lock.reclaimed_from, None);
+    assert_eq!(lock_holder(&dir)["session_id"], "session-a");
+
+    release_instance_lock(&lock, 1234);
+    assert!(!dir.join(LOCK_FILE).exists());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn a_live_holder_moves_the_second_process_to_a_suffixed_directory() {
+    let dir = common::temp_dir("singleton", "live");
+    // The test runner's parent process is alive for the whole test.
+    let live_pid = std::os::unix::process::parent_id();
+    write_lock(&dir, live_pid);
+    let mut config = config(&dir);
+
+    let lock = acquire_instance_lock(&mut config, 1234, "session-b", 10).expect("acquire");
+    assert_eq!(lock.shared_with, Some(live_pid));
+    assert_eq!(config.log_dir, dir.join("instance_1234"));
+    assert_eq!(lock.path, dir.join("instance_1234").join(LOCK_FILE));
+    assert_eq!(
+        lock_holder(&dir)["pid"],
+        live_pid,
+        "holder's lock untouched"
+    );
+
+    // Releasing only removes a lock this process holds.
+    release_instance_lock(&lock, 1234);
+    assert!(dir.join(LOCK_FILE).exists());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn stale_and_corrupt_locks_are_reclaimed() {
+    let dir = common::temp_dir("singleton", "stale");
+    // Above the kernel's pid limit, so no such process can exist.
+    let dead_pid = 4_194_304 + 1;
+    write_lock(&dir, dead_pid);
+    let mut config = config(&dir);
+
+    let lock = acquire_instance_lock(&mut config, 1234, "session-c", 10).expect("acquire");
+    assert_eq!(lock.reclaimed_from, Some(dead_pid));
+    assert_eq!(lock.shared_with, None);
+    assert_eq!(config.log_dir, dir);
+    assert_eq!(lock_holder(&dir)["pid"], 1234);
+    release_instance_lock(&lock, 1234);
+
+    fs::write(dir.join(LOCK_FILE), "{\"pid\":").expect("write torn lock");
+    let mut config = self::config(&dir);
+    let lock = acquire_instance_lock(&mut config, 1234, "session-d", 20).expect("acquire");
+    assert_eq!(lock.reclaimed_from, None);
+    assert_eq!(lock_holder(&dir)["session_id"], "session-d");
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn a_second_registration_is_ignored_or_rejected_when_strict() {
+    let dir = common::temp_dir("singleton", "app");
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config(&dir))
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let session_id = app.state::<DebugToolsState>().session_id.clone();
+
+    let other_dir = common::temp_dir("singleton", "app-duplicate");
+    app.handle()
+        .plugin(
+            Builder::new()
+                .config(config(&other_dir))
+                .with_stdout(false)
+                .build(),
+        )
+        .expect("lenient duplicate is ignored");
+    assert_eq!(app.state::<DebugToolsState>().session_id, session_id);
+    assert_eq!(app.state::<DebugToolsState>().config.log_dir, dir);
+    assert!(is_empty(&other_dir), "duplicate wrote nothing");
+
+    let strict = DebugToolsConfig {
+        strict_singleton: true,
+        ..config(&other_dir)
+    };
+    let rejected = app
+        .handle()
+        .plugin(Builder::new().config(strict).with_stdout(false).build());
+    let nothing_written = is_empty(&other_dir);
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_dir_all(&other_dir);
+
+    assert!(rejected.is_err());
+    assert!(nothing_written);
+}
+
+// The directory itself is created while the config is resolved, before the
+// duplicate is detected.
+fn is_empty(dir: &std::path::Path) -> bool {
+    fs::read_dir(dir).map_or(true, |mut entries| entries.next().is_none())
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use tauri_plugin_debug_tools::{DebugToolsConfig, SnapshotIndex, SnapshotListEntry, SnapshotSort};
+
+fn config_with_snapshots(name: &str, count: i64) -> DebugToolsConfig {
+    let log_dir = common::temp_dir("snapshot-paging", name);
+    fs::create_dir_all(&log_dir).expect("create log directory");
+
+    // Written out of order, so any ordering comes from the index.
+    for i in (0..count).rev() {
+        let route = if i % 3 == 0 { "/orders" } else { "/cart" };
+        let snapshot = serde_json::json!({
+            "timestamp": i * 1000,
+            "route_state": { "route": route },
+        });
+        fs::write(
+            log_dir.join(format!("snapshot_{}.json", i * 1000)),
+            snapshot.to_string(),
+        )
+        .expect("write snapshot");
+    }
+    fs::write(log_dir.join("notes.json"), "{}").expect("write unrelated file");
+
+    DebugToolsConfig {
+        log_dir,
+        ..DebugToolsConfig::default()
+    }
+}
+
+fn timestamps(entries: &[SnapshotListEntry]) -> Vec<i64> {
+    entries.iter().map(|entry| entry.timestamp).collect()
+}
+
+#[test]
+fn pages_through_snapshots_in_either_order() {
+    let config = config_with_snapshots("order", 7);
+    let index = SnapshotIndex::default();
+    let page = |offset, limit, sort| {
+        index
+            .page(&config, offset, limit, sort, None)
+            .expect("page")
+    };
+
+    let first = page(0, 3, SnapshotSort::Desc);
+    let second = page(3, 3, SnapshotSort::Desc);
+    let last = page(6, 3, SnapshotSort::Desc);
+    let past_the_end = page(10, 3, SnapshotSort::Desc);
+    let ascending = page(2, 2, SnapshotSort::Asc);
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(timestamps(&first.entries), [6000, 5000, 4000]);
+    assert_eq!(timestamps(&second.entries), [3000, 2000, 1000]);
+    assert_eq!(timestamps(&last.entries), [0]);
+    assert!(past_the_end.entries.is_empty());
+    assert_eq!(timestamps(&ascending.entries), [2000, 3000]);
+    for listed in [&first, &second, &last, &past_the_end, &ascending] {
+        assert_eq!(listed.total, 7);
+    }
+}
+
+#[test]
+fn route_filter_applies_before_paging() {
+    let config = config_with_snapshots("route", 10);
+    let page = SnapshotIndex::default()
+        .page(&config, 1, 2, SnapshotSort::Asc, Some("/orders"))
+        .expect("page");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(page.total, 4);
+    assert_eq!(timestamps(&page.entries), [3000, 6000]);
+    assert!(page
+        .entries
+        .iter()
+        .all(|entry| entry.route.as_deref() == Some("/orders")));
+}
+
+#[test]
+fn later_pages_come_from_the_index_not_the_directory() {
+    let config = config_with_snapshots("index", 4);
+    let index = SnapshotIndex::default();
+    assert_eq!(
+        index
+            .page(&config, 0, 10, SnapshotSort::Asc, None)
+            .expect("page")
+            .total,
+        4
+    );
+
+    // Files that appear or vanish behind the index's back are not rescanned;
+    // saves reach it through `record` and deletions through `forget`.
+    fs::write(config.log_dir.join("snapshot_99000.json"), "{}").expect("write");
+    fs::remove_file(config.log_dir.join("snapshot_0.json")).expect("remove");
+    let unchanged = index
+        .page(&config, 0, 10, SnapshotSort::Asc, None)
+        .expect("page");
+
+    index.record(entry(&config, 1500));
+    index.forget(&config.log_dir.join("snapshot_0.json"));
+    let updated = index
+        .page(&config, 0, 10, SnapshotSort::Asc, None)
+        .expect("page");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(timestamps(&unchanged.entries), [0, 1000, 2000, 3000]);
+    assert_eq!(timestamps(&updated.entries), [1000, 1500, 2000, 3000]);
+    assert_eq!(updated.total, 4);
+}
+
+#[test]
+fn legacy_snapshots_sort_among_typed_ones_by_seconds() {
+    let config = config_with_snapshots("legacy", 3);
+    fs::write(config.legacy_snapshot_path(1500), "{}").expect("write legacy snapshot");
+    let page = SnapshotIndex::default()
+        .page(&config, 0, 10, SnapshotSort::Asc, None)
+        .expect("page");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(timestamps(&page.entries), [0, 1000, 1500, 2000]);
+    let legacy: Vec<bool> = page.entries.iter().map(|entry| entry.legacy).collect();
+    assert_eq!(legacy, [false, false, true, false]);
+}
+
+fn entry(config: &DebugToolsConfig, timestamp: i64) -> SnapshotListEntry {
+    SnapshotListEntry {
+        path: config
+            .log_dir
+            .join(PathBuf::from(format!("snapshot_{}.json", timestamp))),
+        timestamp,
+        size_bytes: 2,
+        route: None,
+        size_report: None,
+        legacy: false,
+        annotations: serde_json::Map::new(),
+    }
+}
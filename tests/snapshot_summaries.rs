@@ -0,0 +1,65 @@
+mod common;
+
+use std::fs;
+use tauri_plugin_debug_tools::{DebugToolsConfig, SnapshotIndex};
+
+fn config_with_snapshots(name: &str) -> DebugToolsConfig {
+    let log_dir = common::temp_dir("snapshot-summaries", name);
+    fs::create_dir_all(&log_dir).expect("create log directory");
+
+    let screenshot = log_dir.join("shot.png");
+    fs::write(&screenshot, b"png").expect("write screenshot");
+    let snapshot = serde_json::json!({
+        "timestamp": 3000,
+        "webview_state": { "url": "http://localhost:5173/orders", "title": "Orders" },
+        "console_logs": [{ "message": "a" }, { "message": "b" }],
+        "screenshot_path": screenshot,
+        "dom_snapshot_path": log_dir.join("missing.html"),
+        "label": "checkout",
+    });
+    fs::write(log_dir.join("snapshot_3000.json"), snapshot.to_string()).expect("write snapshot");
+    fs::write(log_dir.join("snapshot_2000.json"), "{\"timestamp\": 20").expect("write torn");
+    fs::write(log_dir.join("snapshot_1000.json"), "{}").expect("write empty");
+
+    DebugToolsConfig {
+        log_dir,
+        ..DebugToolsConfig::default()
+    }
+}
+
+#[test]
+fn summarizes_newest_first_and_keeps_unreadable_files() {
+    let config = config_with_snapshots("newest");
+    let summaries = SnapshotIndex::default()
+        .summaries(&config, 2, None)
+        .expect("list summaries");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    let timestamps: Vec<i64> = summaries.iter().map(|s| s.timestamp).collect();
+    assert_eq!(timestamps, [3000, 2000]);
+
+    let newest = &summaries[0];
+    assert_eq!(newest.url.as_deref(), Some("http://localhost:5173/orders"));
+    assert_eq!(newest.title.as_deref(), Some("Orders"));
+    assert_eq!(newest.label.as_deref(), Some("checkout"));
+    assert_eq!(newest.console_log_count, 2);
+    assert!(newest.has_screenshot);
+    assert!(!newest.has_dom_snapshot);
+    assert!(newest.error.is_none());
+
+    assert!(summaries[1].error.is_some());
+}
+
+#[test]
+fn pages_back_with_before_timestamp() {
+    let config = config_with_snapshots("before");
+    let summaries = SnapshotIndex::default()
+        .summaries(&config, 10, Some(2000))
+        .expect("list summaries");
+    let _ = fs::remove_dir_all(&config.log_dir);
+
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].timestamp, 1000);
+    assert_eq!(summaries[0].console_log_count, 0);
+    assert!(summaries[0].error.is_none());
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use tauri_plugin_debug_tools::analysis::sourcemap::{OriginalPosition, SourceMap, StackResolver};
+
+// Generated line 1: column 1 maps to app.ts 1:1, column 15 to app.ts 3:5 as
+// `handleClick`. Line 2 has no mappings. Line 3: column 1 maps to
+// util.ts 10:1, and column 21 starts a segment that maps to nothing.
+const MAPPINGS: &str = "AAAA,cAEIA;;ACOJ,oB";
+
+fn map_json(source_root: Option<&str>) -> serde_json::Value {
+    let mut map = serde_json::json!({
+        "version": 3,
+        "file": "app-3f2d.js",
+        "sources": ["src/app.ts", "src/util.ts"],
+        "names": ["handleClick"],
+        "mappings": MAPPINGS,
+    });
+    if let Some(root) = source_root {
+        map["sourceRoot"] = root.into();
+    }
+    map
+}
+
+fn position(source: &str, line: u32, column: u32, name: Option<&str>) -> OriginalPosition {
+    OriginalPosition {
+        source: source.to_string(),
+        line,
+        column,
+        name: name.map(str::to_string),
+    }
+}
+
+fn parse(json: &serde_json::Value) -> SourceMap {
+    SourceMap::parse(json.to_string().as_bytes()).expect("parse map")
+}
+
+struct MapDir {
+    dir: PathBuf,
+}
+
+impl MapDir {
+    fn new(name: &str) -> Self {
+        let dir = common::temp_dir("sourcemap", name);
+        fs::create_dir_all(dir.join("assets")).expect("create map directory");
+        Self { dir }
+    }
+
+    fn write(&self, relative: &str, contents: &str) {
+        fs::write(self.dir.join(relative), contents).expect("write map");
+    }
+}
+
+impl Drop for MapDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn mappings_decode_to_original_positions() {
+    let map = parse(&map_json(None));
+
+    assert_eq!(map.lookup(1, 1), Some(position("src/app.ts", 1, 1, None)));
+    assert_eq!(
+        map.lookup(1, 15),
+        Some(position("src/app.ts", 3, 5, Some("handleClick")))
+    );
+    // Between segments, the one before the column applies.
+    assert_eq!(
+        map.lookup(1, 900),
+        Some(position("src/app.ts", 3, 5, Some("handleClick")))
+    );
+    assert_eq!(map.lookup(3, 5), Some(position("src/util.ts", 10, 1, None)));
+}
+
+#[test]
+fn unmapped_positions_resolve_to_nothing() {
+    let map = parse(&map_json(None));
+
+    // Line 2 has no segments; line 1's must not leak onto it.
+    assert_eq!(map.lookup(2, 1), None);
+    // A segment without a source.
+    assert_eq!(map.lookup(3, 25), None);
+    assert_eq!(map.lookup(0, 1), None);
+    assert_eq!(map.lookup(99, 1), None);
+}
+
+#[test]
+fn source_root_prefixes_the_sources() {
+    let map = parse(&map_json(Some("webpack:///")));
+
+    assert_eq!(
+        map.lookup(1, 1).map(|original| original.source),
+        Some("webpack:///src/app.ts".to_string())
+    );
+}
+
+#[test]
+fn index_maps_offset_each_section() {
+    let index = serde_json::json!({
+        "version": 3,
+        "sections": [
+            { "offset": { "line": 0, "column": 0 }, "map": map_json(None) },
+            { "offset": { "line": 10, "column": 0 }, "map": map_json(None) },
+        ],
+    });
+    let map = parse(&index);
+
+    assert_eq!(
+        map.lookup(11, 15),
+        Some(position("src/app.ts", 3, 5, Some("handleClick")))
+    );
+    assert_eq!(
+        map.lookup(13, 1),
+        Some(position("src/util.ts", 10, 1, None))
+    );
+}
+
+#[test]
+fn malformed_maps_are_rejected() {
+    assert!(SourceMap::parse(b"not json").is_err());
+    let mut bad = map_json(None);
+    bad["mappings"] = "AA!A".into();
+    assert!(SourceMap::parse(bad.to_string().as_bytes()).is_err());
+}
+
+#[test]
+fn frames_are_rewritten_through_the_map_for_their_url() {
+    let maps = MapDir::new("frames");
+    maps.write("assets/app-3f2d.js.map", &map_json(None).to_string());
+    let resolver = StackResolver::new(maps.dir.clone());
+    let stack = [
+        "Error: boom",
+        "    at e (http://localhost:1420/assets/app-3f2d.js:1:15)",
+        "e@http://localhost:1420/assets/app-3f2d.js?v=2:3:5",
+        "    at http://localhost:1420/assets/vendor.js:1:1",
+        "    at t (http://localhost:1420/assets/app-3f2d.js:2:1)",
+    ]
+    .join("\n");
+
+    let (resolved, used) = resolver.resolve_stack(&stack).expect("resolved");
+
+    assert_eq!(
+        resolved.lines().collect::<Vec<_>>(),
+        [
+            "Error: boom",
+            "    at handleClick (src/app.ts:3:5)",
+            "e@src/util.ts:10:1",
+            // No map for the file, and no mapping for the line.
+            "    at http://localhost:1420/assets/vendor.js:1:1",
+            "    at t (http://localhost:1420/assets/app-3f2d.js:2:1)",
+        ]
+    );
+    assert_eq!(used, ["assets/app-3f2d.js.map"]);
+}
+
+#[test]
+fn missing_or_corrupt_maps_leave_the_stack_alone() {
+    let maps = MapDir::new("corrupt");
+    maps.write("assets/app-3f2d.js.map", "{\"version\":3,\"mappings\":");
+    let resolver = StackResolver::new(maps.dir.clone());
+
+    assert_eq!(
+        resolver.resolve_stack("    at e (http://localhost:1420/assets/app-3f2d.js:1:15)"),
+        None
+    );
+    assert_eq!(
+        resolver.resolve_stack("    at e (http://localhost:1420/assets/other.js:1:15)"),
+        None
+    );
+    // Frames cannot reach outside the map directory.
+    assert_eq!(
+        resolver.resolve_stack("    at e (http://localhost:1420/../../etc/app-3f2d.js:1:15)"),
+        None
+    );
+}
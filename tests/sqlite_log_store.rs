@@ -0,0 +1,225 @@
+mod common;
+
+use serde::Serialize;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, query_console_logs, Builder, ConsoleLogEntry, ConsoleLogEntryPayload,
+    DebugToolsConfig, DebugToolsState, InMemoryLogQueryService, LogLevel, LogQuery,
+    LogQueryService, LogStore, SqliteRepository,
+};
+
+fn entry(timestamp: i64, level: &str, message: &str) -> ConsoleLogEntry {
+    ConsoleLogEntry {
+        timestamp,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+    }
+}
+
+fn with_route(mut entry: ConsoleLogEntry, route: &str) -> ConsoleLogEntry {
+    let mut fields = serde_json::Map::new();
+    fields.insert("route".into(), route.into());
+    entry.fields = Some(fields);
+    entry
+}
+
+fn fixture() -> Vec<ConsoleLogEntry> {
+    vec![
+        entry(1_000, "debug", "boot"),
+        with_route(entry(1_100, "log", "Loaded ÖRDERS"), "/orders"),
+        with_route(entry(1_200, "warn", "100% of quota_used"), "/orders"),
+        entry(1_200, "error", "Order fetch failed"),
+        // The clock went backwards; queries still return timestamp order.
+        with_route(entry(900, "info", "late flush of örders"), "/cart"),
+        entry(3_000, "fatal", "Cart failed"),
+        entry(3_100, "verbose?", "unknown level"),
+    ]
+}
+
+fn json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("serialize")
+}
+
+#[test]
+fn sql_filtered_queries_match_the_in_memory_service() {
+    let store = SqliteRepository::open_in_memory().expect("open");
+    let entries = fixture();
+    let (first, rest) = entries.split_at(3);
+    store.append_console_logs(first).expect("append");
+    store.append_console_logs(rest).expect("append");
+    let memory = InMemoryLogQueryService::new(fixture());
+
+    let mut route = serde_json::Map::new();
+    route.insert("route".into(), "/orders".into());
+    let queries = [
+        LogQuery::default(),
+        LogQuery {
+            limit: Some(3),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            limit: Some(0),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            min_level: Some(LogLevel::Warn),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            since: Some(1_100),
+            until: Some(3_000),
+            ..LogQuery::default()
+        },
+        // Unicode case folding and LIKE wildcards in the needle.
+        LogQuery {
+            text: Some("örders".into()),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            text: Some("100%".into()),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            text: Some("a_u".into()),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            field_equals: route,
+            limit: Some(1),
+            ..LogQuery::default()
+        },
+        LogQuery {
+            min_level: Some(LogLevel::Info),
+            text: Some("order".into()),
+            limit: Some(2),
+            ..LogQuery::default()
+        },
+    ];
+
+    for query in &queries {
+        assert_eq!(
+            json(&store.query_console_logs(query).unwrap()),
+            json(&memory.query_console_logs(query).unwrap()),
+            "{:?}",
+            query
+        );
+        assert_eq!(
+            json(&store.histogram(1_000, query).unwrap()),
+            json(&memory.histogram(1_000, query).unwrap()),
+            "{:?}",
+            query
+        );
+    }
+    assert_eq!(
+        json(&store.query_errors(10).unwrap()),
+        json(&memory.query_errors(10).unwrap())
+    );
+    assert_eq!(
+        json(&store.timeline(1_000, 1_200).unwrap()),
+        json(&memory.timeline(1_000, 1_200).unwrap())
+    );
+}
+
+#[test]
+fn max_rows_drops_the_oldest_rows() {
+    let store = SqliteRepository::open_in_memory()
+        .expect("open")
+        .with_max_rows(3);
+    for batch in fixture().chunks(2) {
+        store.append_console_logs(batch).expect("append");
+    }
+
+    assert_eq!(store.row_count().unwrap(), 3);
+    let messages: Vec<String> = store
+        .query_console_logs(&LogQuery::default())
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.message)
+        .collect();
+    assert_eq!(
+        messages,
+        ["late flush of örders", "Cart failed", "unknown level"]
+    );
+}
+
+#[test]
+fn rows_survive_reopening_the_database() {
+    let path = common::temp_dir("sqlite", "reopen").with_extension("db");
+    let _ = std::fs::remove_file(&path);
+
+    SqliteRepository::open(&path)
+        .expect("open")
+        .append_console_logs(&fixture())
+        .expect("append");
+    let reopened = SqliteRepository::open(&path).expect("reopen");
+    let errors = reopened.query_errors(10).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let messages: Vec<&str> = errors.iter().map(|entry| entry.message.as_str()).collect();
+    assert_eq!(messages, ["Order fetch failed", "Cart failed"]);
+}
+
+// The only test here that builds an app (see tests/common/mod.rs).
+#[test]
+fn builder_log_store_answers_query_commands_from_sqlite() {
+    let log_dir = common::temp_dir("sqlite", "app");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(
+            Builder::new()
+                .config(config)
+                .log_store(SqliteRepository::open_in_memory().expect("open"))
+                .with_stdout(false)
+                .build(),
+        )
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let handle = || app.handle().clone();
+    let payload = |level: &str, message: &str| ConsoleLogEntryPayload {
+        timestamp: 1_700_000_000_000,
+        level: level.to_string(),
+        message: message.to_string(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: None,
+        seq: None,
+    };
+    tauri::async_runtime::block_on(append_debug_logs(
+        handle(),
+        vec![
+            payload("info", "Loaded orders"),
+            payload("error", "Order fetch failed"),
+        ],
+        None,
+        None,
+        None,
+        None,
+    ))
+    .expect("append");
+
+    // Queries no longer read the JSONL copy.
+    let state = app.state::<DebugToolsState>();
+    let jsonl = state
+        .config
+        .frontend_log_path(&app.package_info().name, state.ids.process_id());
+    std::fs::remove_file(&jsonl).expect("remove jsonl copy");
+
+    let query = LogQuery {
+        min_level: Some(LogLevel::Error),
+        text: Some("ORDER".into()),
+        ..LogQuery::default()
+    };
+    let entries =
+        tauri::async_runtime::block_on(query_console_logs(handle(), Some(query))).expect("query");
+    let _ = std::fs::remove_dir_all(&log_dir);
+
+    let messages: Vec<&str> = entries.iter().map(|entry| entry.message.as_str()).collect();
+    assert_eq!(messages, ["Order fetch failed"]);
+}
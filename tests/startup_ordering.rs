@@ -0,0 +1,66 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    append_debug_logs, tail_console_log, Builder, ConsoleLogEntryPayload, DebugToolsConfig,
+    DebugToolsState, ScreenshotsPluginStatus,
+};
+
+// Screenshots stay enabled so their background registration overlaps the
+// first command.
+#[test]
+fn logs_written_right_after_setup_are_kept() {
+    let log_dir = common::temp_dir("startup-ordering", "setup");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let logs = (0..3)
+        .map(|i| ConsoleLogEntryPayload {
+            timestamp: 1_700_000_000_000 + i,
+            level: "info".to_string(),
+            message: format!("startup {}", i),
+            args: serde_json::Value::Array(Vec::new()),
+            stack_trace: None,
+            fields: None,
+            seq: None,
+        })
+        .collect();
+    let response = tauri::async_runtime::block_on(append_debug_logs(
+        app.handle().clone(),
+        logs,
+        None,
+        None,
+        None,
+        None,
+    ))
+    .expect("append right after setup");
+    let path = serde_json::to_value(&response).expect("serialize response")["path"]
+        .as_str()
+        .map(PathBuf::from)
+        .expect("log path");
+    let tail = tail_console_log(&path, 10, 0).expect("read log back");
+
+    let state = app.state::<DebugToolsState>();
+    let started = Instant::now();
+    while state.screenshots_plugin_status() == ScreenshotsPluginStatus::Registering
+        && started.elapsed() < Duration::from_secs(10)
+    {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let screenshots = state.screenshots_plugin_status();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    let messages: Vec<&str> = tail.entries.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, ["startup 0", "startup 1", "startup 2"]);
+    assert_eq!(tail.total, 3);
+    assert_ne!(screenshots, ScreenshotsPluginStatus::Registering);
+}
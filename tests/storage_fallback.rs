@@ -0,0 +1,143 @@
+mod common;
+
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use tauri_plugin_debug_tools::{FileOps, StdFileOps, WritePolicy};
+
+// The real filesystem, except that renames always fail and copies fail
+// with the queued errors first.
+#[derive(Default)]
+struct FailingRename {
+    copy_errors: RefCell<Vec<ErrorKind>>,
+    calls: RefCell<Vec<&'static str>>,
+}
+
+impl FailingRename {
+    fn failing_copies(errors: &[ErrorKind]) -> Self {
+        Self {
+            copy_errors: RefCell::new(errors.iter().rev().copied().collect()),
+            ..Self::default()
+        }
+    }
+
+    fn calls(&self) -> Vec<&'static str> {
+        self.calls.borrow().clone()
+    }
+}
+
+impl FileOps for FailingRename {
+    fn rename(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+        self.calls.borrow_mut().push("rename");
+        Err(io::Error::other("cross-device rename"))
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        self.calls.borrow_mut().push("copy");
+        match self.copy_errors.borrow_mut().pop() {
+            Some(kind) => Err(kind.into()),
+            None => StdFileOps.copy(from, to),
+        }
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.calls.borrow_mut().push("remove");
+        StdFileOps.remove_file(path)
+    }
+}
+
+fn dir(name: &str) -> PathBuf {
+    let dir = common::temp_dir("storage", name);
+    fs::create_dir_all(&dir).expect("create directory");
+    dir
+}
+
+fn file_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .expect("read directory")
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn a_failed_rename_falls_back_to_copy_and_delete() {
+    let dir = dir("fallback");
+    let path = dir.join("holds.json");
+    fs::write(&path, "old").expect("write original");
+    let ops = FailingRename::default();
+
+    let result = WritePolicy::default().replace_file_with(&ops, &path, b"new");
+    let contents = fs::read_to_string(&path);
+    let names = file_names(&dir);
+    let _ = fs::remove_dir_all(&dir);
+
+    result.expect("replace");
+    assert_eq!(contents.unwrap(), "new");
+    assert_eq!(names, ["holds.json"]);
+    assert_eq!(ops.calls(), ["rename", "copy", "remove"]);
+}
+
+#[test]
+fn conservative_mode_never_attempts_a_rename() {
+    let dir = dir("conservative");
+    let path = dir.join("holds.json");
+    let ops = FailingRename::default();
+    let policy = WritePolicy {
+        conservative: true,
+        ..WritePolicy::default()
+    };
+
+    let result = policy.replace_file_with(&ops, &path, b"new");
+    let contents = fs::read_to_string(&path);
+    let _ = fs::remove_dir_all(&dir);
+
+    result.expect("replace");
+    assert_eq!(contents.unwrap(), "new");
+    assert_eq!(ops.calls(), ["copy", "remove"]);
+}
+
+#[test]
+fn a_failed_move_keeps_the_original_and_removes_the_staging_file() {
+    let dir = dir("failed");
+    let path = dir.join("holds.json");
+    fs::write(&path, "old").expect("write original");
+    let ops = FailingRename::failing_copies(&[ErrorKind::PermissionDenied]);
+
+    let result = WritePolicy::default().replace_file_with(&ops, &path, b"new");
+    let contents = fs::read_to_string(&path);
+    let names = file_names(&dir);
+    let _ = fs::remove_dir_all(&dir);
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::PermissionDenied);
+    assert_eq!(contents.unwrap(), "old");
+    assert_eq!(names, ["holds.json"], "staging file left behind");
+    assert_eq!(ops.calls(), ["rename", "copy", "remove"]);
+}
+
+#[test]
+fn transient_copy_errors_are_retried_without_leaking_staging_files() {
+    let dir = dir("transient");
+    let path = dir.join("holds.json");
+    fs::write(&path, "old").expect("write original");
+    let ops = FailingRename::failing_copies(&[ErrorKind::TimedOut, ErrorKind::Interrupted]);
+    let policy = WritePolicy {
+        conservative: true,
+        ..WritePolicy::default()
+    };
+
+    let result = policy.retry(|| policy.replace_file_with(&ops, &path, b"new"));
+    let contents = fs::read_to_string(&path);
+    let names = file_names(&dir);
+    let _ = fs::remove_dir_all(&dir);
+
+    result.expect("replace after retries");
+    assert_eq!(contents.unwrap(), "new");
+    assert_eq!(names, ["holds.json"]);
+    assert_eq!(
+        ops.calls(),
+        ["copy", "remove", "copy", "remove", "copy", "remove"]
+    );
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+use tauri::Manager;
+use tauri_plugin_debug_tools::{
+    check_clock_skew, clear_strict_violations, Builder, DebugToolsConfig, DebugToolsState,
+};
+
+#[test]
+fn clock_skew_fails_the_command_and_stays_flagged_until_cleared() {
+    let log_dir = common::temp_dir("strict-mode", "app");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        strict_mode: true,
+        clock_skew_tolerance_ms: 1_000,
+        ..DebugToolsConfig::default()
+    };
+
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    let state = app.state::<DebugToolsState>();
+    let now_ms = state.clock.now_millis();
+    let skew = |frontend_timestamp: i64| {
+        tauri::async_runtime::block_on(check_clock_skew(app.handle().clone(), frontend_timestamp))
+    };
+
+    skew(now_ms).expect("clocks agree");
+    assert!(!state.strict_mode.status().strict_violation);
+
+    let error = skew(now_ms - 60_000).expect_err("skew beyond tolerance");
+    let violation: serde_json::Value = serde_json::from_str(&error).expect("structured error");
+    let status = state.strict_mode.status();
+
+    // Still flagged after a command that succeeds.
+    skew(now_ms).expect("clocks agree again");
+    let still_flagged = state.strict_mode.status().strict_violation;
+
+    let cleared =
+        tauri::async_runtime::block_on(clear_strict_violations(app.handle().clone())).unwrap();
+    let after_clear = state.strict_mode.status();
+    let _ = fs::remove_dir_all(&log_dir);
+
+    assert_eq!(violation["code"], "strict_violation");
+    assert_eq!(violation["kind"], "clock_skew");
+    assert_eq!(violation["command"], "check_clock_skew");
+    assert!(violation["non_strict_behavior"]
+        .as_str()
+        .is_some_and(|behavior| behavior.contains("frontend timestamps")));
+    assert!(status.enabled && status.strict_violation);
+    assert_eq!(status.violations.len(), 1);
+    assert!(still_flagged);
+    assert_eq!(cleared, 1);
+    assert!(!after_clear.strict_violation && after_clear.violations.is_empty());
+}
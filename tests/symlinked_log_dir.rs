@@ -0,0 +1,165 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri_plugin_debug_tools::{
+    clear_debug_log_files, DebugToolsConfig, DomState, FileSystemRepository, InternalActivity,
+    LegalHolds, OwnedFiles, RepositoryError, SnapshotRepository, ViewportInfo,
+};
+
+const APP: &str = "Symlink Test";
+const PID: u32 = 4545;
+
+struct Fixture {
+    base: PathBuf,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Self {
+        let base = common::temp_dir("symlink", name);
+        fs::create_dir_all(&base).expect("create fixture");
+        Self { base }
+    }
+
+    fn dir(&self, name: &str) -> PathBuf {
+        let dir = self.base.join(name);
+        fs::create_dir_all(&dir).expect("create directory");
+        dir
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.base);
+    }
+}
+
+fn config(log_dir: &Path, allow_symlinked_log_dir: bool) -> DebugToolsConfig {
+    DebugToolsConfig {
+        log_dir: log_dir.to_path_buf(),
+        allow_symlinked_log_dir,
+        ..DebugToolsConfig::default()
+    }
+}
+
+fn repository(config: &DebugToolsConfig) -> FileSystemRepository {
+    FileSystemRepository::new(
+        Arc::new(config.clone()),
+        APP.into(),
+        PID,
+        Arc::new(InternalActivity::default()),
+    )
+}
+
+fn dom() -> DomState {
+    DomState {
+        html: "<p>hello</p>".into(),
+        url: "http://localhost/".into(),
+        title: "Test".into(),
+        viewport: ViewportInfo {
+            width: 800,
+            height: 600,
+        },
+        captured_at: 1_700_000_000_000,
+        shadow_dom_included: false,
+        replacement_chars: 0,
+    }
+}
+
+fn owned_file(path: &Path, owned: &OwnedFiles) -> PathBuf {
+    fs::create_dir_all(path.parent().unwrap()).expect("create parent");
+    fs::write(path, "{}\n").expect("write file");
+    owned.register(path);
+    path.to_path_buf()
+}
+
+#[test]
+fn a_symlinked_log_dir_is_followed_when_allowed() {
+    let fixture = Fixture::new("allowed");
+    let target = fixture.dir("relocated");
+    let link = fixture.base.join("logs");
+    symlink(&target, &link).expect("symlink log_dir");
+    let config = config(&link, true);
+
+    assert_eq!(
+        config.resolve_log_root().expect("resolve root"),
+        target.canonicalize().unwrap()
+    );
+
+    let saved = repository(&config).save_dom(&dom(), 1).expect("save");
+    assert!(saved
+        .path
+        .canonicalize()
+        .unwrap()
+        .starts_with(target.canonicalize().unwrap()));
+
+    let owned = OwnedFiles::load(&config);
+    let console = owned_file(&config.frontend_log_path(APP, PID), &owned);
+    let relative = console.strip_prefix(&link).unwrap().to_path_buf();
+    let report =
+        clear_debug_log_files(&config, APP, &LegalHolds::default(), &owned).expect("clear");
+    assert!(!console.exists());
+    // Reported under the resolved root, not the link.
+    let deleted: Vec<PathBuf> = report
+        .deleted_paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(target.canonicalize().unwrap()).ok())
+        .map(Path::to_path_buf)
+        .collect();
+    assert!(deleted.contains(&relative), "{:?}", report.deleted_paths);
+}
+
+#[test]
+fn a_symlinked_log_dir_is_refused_when_not_allowed() {
+    let fixture = Fixture::new("refused");
+    let target = fixture.dir("shared");
+    let keep = target.join(format!("frontend_console_Symlink_Test_{}.jsonl", PID));
+    fs::write(&keep, "{}\n").expect("write file");
+    let link = fixture.base.join("logs");
+    symlink(&target, &link).expect("symlink log_dir");
+    let config = config(&link, false);
+
+    assert!(config.resolve_log_root().is_err());
+    assert!(matches!(
+        repository(&config).save_dom(&dom(), 1),
+        Err(RepositoryError::OutsideLogRoot(_))
+    ));
+    let cleared = clear_debug_log_files(
+        &config,
+        APP,
+        &LegalHolds::default(),
+        &OwnedFiles::load(&config),
+    );
+    assert!(matches!(cleared, Err(RepositoryError::OutsideLogRoot(_))));
+    assert!(keep.exists());
+}
+
+#[test]
+fn a_subdirectory_linked_outside_the_root_is_never_cleared_or_written() {
+    let fixture = Fixture::new("subdir");
+    let log_dir = fixture.dir("logs");
+    let outside = fixture.dir("outside");
+    let config = config(&log_dir, true);
+    symlink(&outside, config.screenshot_dir()).expect("symlink screenshots");
+    symlink(&outside, config.dom_snapshot_dir()).expect("symlink dom snapshots");
+
+    // Even a file the plugin believes it owns stays put when reached
+    // through a link out of the root.
+    let owned = OwnedFiles::load(&config);
+    let screenshot = owned_file(&config.screenshot_dir().join("1700000000_main.png"), &owned);
+    let report =
+        clear_debug_log_files(&config, APP, &LegalHolds::default(), &owned).expect("clear");
+    assert!(screenshot.exists());
+    assert!(outside.join("1700000000_main.png").exists());
+    assert!(report.failed_paths.contains(&config.screenshot_dir()));
+
+    assert!(matches!(
+        repository(&config).save_dom(&dom(), 1),
+        Err(RepositoryError::OutsideLogRoot(_))
+    ));
+    assert!(!outside.join("dom_1.html").exists());
+}
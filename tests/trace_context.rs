@@ -0,0 +1,61 @@
+use tauri_plugin_debug_tools::{ConsoleLogEntry, LogQuery, TraceContext};
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+fn entry_with(fields: serde_json::Value) -> ConsoleLogEntry {
+    ConsoleLogEntry {
+        timestamp: 1,
+        level: "info".into(),
+        message: "fetch /api/orders".into(),
+        args: serde_json::Value::Array(Vec::new()),
+        stack_trace: None,
+        fields: fields.as_object().cloned(),
+    }
+}
+
+#[test]
+fn parses_and_formats_a_traceparent() {
+    let context = TraceContext::parse(TRACEPARENT).expect("valid traceparent");
+    assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(context.parent_id, "00f067aa0ba902b7");
+    assert_eq!(context.traceparent(), TRACEPARENT);
+
+    for invalid in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+    ] {
+        assert!(TraceContext::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn generated_contexts_are_valid_and_distinct() {
+    let first = TraceContext::generate("request-1");
+    let second = TraceContext::generate("request-2");
+
+    assert_eq!(TraceContext::parse(&first.traceparent()), Ok(first.clone()));
+    assert_ne!(first.trace_id, second.trace_id);
+}
+
+#[test]
+fn log_query_matches_either_trace_field() {
+    let query = LogQuery {
+        trace_id: Some("4bf92f3577b34da6a3ce929d0e0e4736".into()),
+        ..LogQuery::default()
+    };
+
+    assert!(query.matches(&entry_with(serde_json::json!({
+        "trace_id": "4bf92f3577b34da6a3ce929d0e0e4736"
+    }))));
+    assert!(query.matches(&entry_with(serde_json::json!({
+        "traceparent": TRACEPARENT
+    }))));
+    assert!(!query.matches(&entry_with(serde_json::json!({
+        "trace_id": "0af7651916cd43dd8448eb211c80319c"
+    }))));
+    assert!(!query.matches(&entry_with(serde_json::Value::Null)));
+}
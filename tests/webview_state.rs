@@ -0,0 +1,109 @@
+mod common;
+
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_debug_tools::{
+    capture_webview_state, Builder, CaptureWebViewStateUseCase, DebugToolsConfig, DebugToolsState,
+    UserAgents, DEFAULT_WINDOW_LABEL, PLACEHOLDER_USER_AGENT,
+};
+
+// The mock runtime reports the same URL and title for every window, so the
+// windows are told apart by which labels resolve at all.
+fn app_with_windows(labels: &[&str]) -> tauri::App<tauri::test::MockRuntime> {
+    let app = tauri::test::mock_builder()
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    for label in labels {
+        WebviewWindowBuilder::new(&app, *label, WebviewUrl::default())
+            .build()
+            .expect("create window");
+    }
+    app
+}
+
+#[test]
+fn captures_the_default_window() {
+    let app = app_with_windows(&[DEFAULT_WINDOW_LABEL]);
+
+    assert!(CaptureWebViewStateUseCase::execute(app.handle(), DEFAULT_WINDOW_LABEL).is_ok());
+}
+
+#[test]
+fn captures_a_named_window_without_main() {
+    let app = app_with_windows(&["settings", "tray"]);
+
+    assert!(CaptureWebViewStateUseCase::execute(app.handle(), "settings").is_ok());
+    let error = CaptureWebViewStateUseCase::execute(app.handle(), DEFAULT_WINDOW_LABEL)
+        .expect_err("no main window")
+        .to_string();
+    assert!(error.contains("main"), "{}", error);
+    assert!(error.contains("settings, tray"), "{}", error);
+}
+
+#[test]
+fn reports_the_user_agent_each_window_sent() {
+    let app = app_with_windows(&[DEFAULT_WINDOW_LABEL, "settings"]);
+    let agents = UserAgents::default();
+    agents.record(
+        DEFAULT_WINDOW_LABEL,
+        "Mozilla/5.0 (X11; Linux x86_64) WebKitGTK",
+    );
+    app.manage(agents);
+
+    let main = CaptureWebViewStateUseCase::execute(app.handle(), DEFAULT_WINDOW_LABEL)
+        .expect("capture main");
+    assert_eq!(main.user_agent, "Mozilla/5.0 (X11; Linux x86_64) WebKitGTK");
+    let settings =
+        CaptureWebViewStateUseCase::execute(app.handle(), "settings").expect("capture settings");
+    assert_eq!(settings.user_agent, PLACEHOLDER_USER_AGENT);
+}
+
+// The only test here that registers the plugin (see tests/common/mod.rs).
+#[test]
+fn the_command_takes_a_label_and_still_accepts_window_label() {
+    let log_dir = common::temp_dir("webview-state", "capture");
+    let config = DebugToolsConfig {
+        log_dir: log_dir.clone(),
+        ..DebugToolsConfig::default()
+    };
+    let app = tauri::test::mock_builder()
+        .plugin(Builder::new().config(config).with_stdout(false).build())
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("build app");
+    for label in [DEFAULT_WINDOW_LABEL, "settings"] {
+        WebviewWindowBuilder::new(&app, label, WebviewUrl::default())
+            .build()
+            .expect("create window");
+    }
+    let capture = |label: Option<&str>, window_label: Option<&str>| {
+        tauri::async_runtime::block_on(capture_webview_state(
+            app.handle().clone(),
+            label.map(str::to_string),
+            window_label.map(str::to_string),
+        ))
+    };
+
+    let default = capture(None, None);
+    let labelled = capture(Some("settings"), None);
+    let deprecations_before = app.state::<DebugToolsState>().deprecations.used();
+    let legacy = capture(None, Some("settings"));
+    // `label` wins over the deprecated name.
+    let both = capture(Some("settings"), Some("missing"));
+    let missing = capture(Some("missing"), None);
+    let deprecations = app.state::<DebugToolsState>().deprecations.used();
+    let _ = std::fs::remove_dir_all(&log_dir);
+
+    assert!(default.is_ok(), "{:?}", default);
+    assert!(labelled.is_ok(), "{:?}", labelled);
+    assert!(legacy.is_ok(), "{:?}", legacy);
+    assert!(both.is_ok(), "{:?}", both);
+    let error = missing.expect_err("unknown window");
+    assert!(error.contains("missing"), "{}", error);
+    assert!(error.contains("main, settings"), "{}", error);
+
+    assert!(deprecations_before.is_empty());
+    let surfaces: Vec<(&str, u64)> = deprecations
+        .iter()
+        .map(|used| (used.surface.as_str(), used.calls))
+        .collect();
+    assert_eq!(surfaces, [("capture_webview_state (window_label)", 2)]);
+}